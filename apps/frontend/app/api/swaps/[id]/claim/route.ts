@@ -1,5 +1,5 @@
 import { NextRequest, NextResponse } from 'next/server';
-import { FusionDatabase, getDatabaseConfig, FusionDAO } from '../../../../../../../packages/shared/src/database';
+import { FusionDatabase, getDatabaseConfig, FusionDAO, openSecret, PREIMAGE_CONTEXT } from '../../../../../../../packages/shared/src/database';
 import dotenv from 'dotenv';
 import path from 'path';
 
@@ -62,7 +62,7 @@ export async function GET(
         id: swap.id,
         status: swap.status,
         poolHtlcContract: swap.poolHtlcContract,
-        preimage: swap.preimageHash, // Frontend expects 'preimage'
+        preimage: openSecret(swap.preimageHash, PREIMAGE_CONTEXT), // Frontend expects 'preimage'
         userAddress: swap.userAddress,
         targetToken: swap.targetToken,
         expectedAmount: swap.expectedAmount
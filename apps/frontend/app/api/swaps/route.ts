@@ -107,6 +107,7 @@ export async function POST(request: NextRequest) {
         createdAt: createdAt,
         updatedAt: createdAt
       };
+      preimage.fill(0); // Only the hex copy survives; the DAO seals it before storage
 
       try {
         const dbConfig = getDatabaseConfig();
@@ -0,0 +1,82 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { ethers } from 'ethers';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError, isJsonOutput } from '../../utils/output';
+import { parseDuration } from '../../utils/duration';
+import { configuredChains, requireChain } from '../../services/omni-chains';
+import { accountBalances, loadAccounts } from '../../services/omni-accounts';
+import type { Account, AccountBalance } from '../../services/omni-accounts';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+function formatBalance(row: AccountBalance): string {
+  if (row.error !== undefined) return chalk.red('unreachable');
+  const amount = requireChain(row.chain).type === 'evm' ? ethers.formatEther(row.balance!) : row.balance!.toString();
+  return row.balance! > 0n ? `${amount} ${row.denom}` : chalk.red(`0 ${row.denom}`);
+}
+
+function render(balances: AccountBalance[]): void {
+  logger.table(balances.map(row => ({
+    Key: row.account,
+    Chain: row.chain,
+    Address: row.address,
+    Balance: formatBalance(row)
+  })));
+
+  const empty = balances.filter(row => row.balance === 0n);
+  const unreachable = [...new Set(balances.filter(row => row.error !== undefined).map(row => row.chain))];
+  if (empty.length) logger.warn(`No gas on ${empty.map(row => `${row.chain} (${row.account})`).join(', ')}`);
+  if (unreachable.length) logger.warn(`Could not query ${unreachable.join(', ')}`);
+  if (!empty.length && !unreachable.length) logger.success('Gas everywhere');
+}
+
+export const accountsCommand = new Command('accounts')
+  .description('Show every key\'s address and gas balance on each configured chain')
+  .option('-c, --chain <chain>', 'only this chain (repeatable); default the profile\'s chains, else all', collect)
+  .option('-k, --key <name>', 'only this keyring key (repeatable)', collect)
+  .option('-w, --watch', 'refresh until interrupted')
+  .option('-i, --interval <duration>', 'refresh interval with --watch', '30s')
+  .action(async (options) => {
+    logger.heading('👛 Accounts');
+
+    let chains: string[];
+    let accounts: Account[];
+    try {
+      chains = configuredChains(options.chain);
+      accounts = await loadAccounts(chains, options.key);
+    } catch (error) {
+      exitWithError(error);
+    }
+    if (accounts.length === 0) {
+      exitWithError(new Error('No keys; add one with omni keys add <name> --type evm|cosmos, or set PRIVATE_KEY / COSMOS_MNEMONIC'));
+    }
+
+    const refresh = async () => {
+      spinner.start('accounts', `Querying ${chains.length} chain(s)...`);
+      const balances = await accountBalances(accounts, chains);
+      spinner.stop('accounts');
+      return balances;
+    };
+
+    if (!options.watch) {
+      emit(await refresh(), render);
+      return;
+    }
+
+    const intervalMs = parseDuration(options.interval) * 1000;
+    // Nothing to clean up, so Ctrl-C ends it
+    for (;;) {
+      const balances = await refresh();
+      if (!isJsonOutput()) {
+        console.clear();
+        logger.heading(`👛 Accounts — ${new Date().toLocaleTimeString()}, every ${options.interval}`);
+      }
+      // One JSON line per refresh
+      emit({ at: Math.floor(Date.now() / 1000), balances }, result => render(result.balances));
+      await new Promise(resolve => setTimeout(resolve, intervalMs));
+    }
+  });
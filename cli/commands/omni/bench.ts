@@ -0,0 +1,75 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { DEFAULT_HTLC_ARTIFACT } from '../../services/omni-deploy';
+import { benchChain, compareGas, readSnapshot, toSnapshot, writeSnapshot } from '../../services/omni-bench';
+import type { GasDiff, GasMeasurement } from '../../services/omni-bench';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+function formatChange(diff: GasDiff): string {
+  if (diff.status === 'new') return chalk.cyan('new');
+  if (diff.status === 'removed') return chalk.gray('removed');
+  const text = `${diff.change! >= 0 ? '+' : ''}${diff.change!.toFixed(2)}%`;
+  return diff.status === 'regressed' ? chalk.red(text) : diff.status === 'improved' ? chalk.green(text) : text;
+}
+
+export const benchCommand = new Command('bench')
+  .description('Measure gas per HTLC entry point on a localnet and compare it with a stored snapshot')
+  .requiredOption('-c, --chain <chain>', 'localnet to bench on (repeatable)', collect)
+  .option('--artifact <file>', 'SimpleHTLC Hardhat artifact to deploy (EVM)', DEFAULT_HTLC_ARTIFACT)
+  .option('--wasm <file>', 'HTLC wasm to upload and bench instead of the chain\'s code_id (Cosmos)')
+  .option('-k, --key <name>', 'keyring key that pays on Cosmos localnets')
+  .option('--snapshot <file>', 'baseline to compare with', 'gas-snapshot.json')
+  .option('--tolerance <percent>', 'change either way that still counts as the same', '1')
+  .option('--update', 'write the measurements to the snapshot instead of failing on regressions')
+  .action(async (options) => {
+    logger.heading('⛽ Gas Bench');
+
+    const tolerance = Number(options.tolerance);
+    if (!Number.isFinite(tolerance) || tolerance < 0) exitWithError(new Error(`Invalid --tolerance "${options.tolerance}"`));
+
+    const measurements: GasMeasurement[] = [];
+    for (const chain of options.chain as string[]) {
+      spinner.start('bench', `Deploying and running scenarios on ${chain}...`);
+      try {
+        measurements.push(...await benchChain(chain, { artifact: options.artifact, wasm: options.wasm, key: options.key }));
+        spinner.succeed('bench', `Benched ${chain}`);
+      } catch (error) {
+        spinner.fail('bench', `Bench failed on ${chain}`);
+        exitWithError(error);
+      }
+    }
+
+    const current = toSnapshot(measurements);
+    let diffs: GasDiff[];
+    try {
+      const baseline = await readSnapshot(options.snapshot);
+      diffs = compareGas(current, baseline, tolerance);
+      if (options.update) await writeSnapshot(options.snapshot, baseline, current);
+    } catch (error) {
+      exitWithError(error);
+    }
+
+    const regressions = diffs.filter(diff => diff.status === 'regressed');
+    emit({ snapshot: options.snapshot, updated: !!options.update, measurements, diffs }, result => {
+      logger.table(result.diffs.map(diff => {
+        const [chain, scenario, entryPoint] = diff.key.split('/');
+        return {
+          Chain: chain,
+          Scenario: scenario,
+          'Entry point': entryPoint,
+          Gas: diff.current ?? '-',
+          Baseline: diff.baseline ?? '-',
+          Change: formatChange(diff)
+        };
+      }));
+      if (result.updated) logger.success(`Snapshot written to ${result.snapshot}`);
+      else if (regressions.length) logger.error(`${regressions.length} entry point(s) use more than ${tolerance}% more gas than ${result.snapshot}`);
+    });
+    if (regressions.length && !options.update) process.exit(1);
+  });
@@ -0,0 +1,57 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore, openVault } from '../../services/omni-store';
+import { configuredChains } from '../../services/omni-chains';
+import { fundedLegs } from '../../services/omni-cancel';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+const store = new SwapStore();
+
+export const cancelCommand = new Command('cancel')
+  .description('Call off a swap that is not funded yet, after checking no leg was locked under its hashlock')
+  .argument('<swap-id>', 'swap ID from swap create or quote --create')
+  .option('-c, --chain <chain>', 'chain a counterparty may have locked on (repeatable); default the profile\'s chains, else all', collect)
+  .option('--blocks <n>', 'blocks to scan back; default back to when the swap was created')
+  .option('--keep-secret', 'keep the preimage in the vault instead of deleting it')
+  .action(async (swapId: string, options) => {
+    logger.heading('🛑 Cancel Swap');
+
+    try {
+      const record = await store.find(swapId);
+      if (!record) throw new Error(`Unknown swap ${swapId}; only swaps created on this machine can be cancelled`);
+      if (record.cancelledAt) throw new Error(`Swap ${record.swapId} is already cancelled`);
+      if (record.contractId) {
+        throw new Error(`Swap ${record.swapId} is funded as ${record.contractId}; refund it after its timelock with omni swap refund`);
+      }
+
+      spinner.start('cancel', 'Checking that neither leg is funded...');
+      const legs = await fundedLegs(record, configuredChains(options.chain), options.blocks ? Number(options.blocks) : undefined);
+      if (legs.length) {
+        spinner.fail('cancel', 'A leg is already funded');
+        const found = legs.map(leg => `${leg.chain}:${leg.contractId}${leg.txHash ? ` (tx ${leg.txHash})` : ''}`).join(', ');
+        throw new Error(`Not cancelled: ${found} is locked under this swap's hashlock. Settle it, or refund after the timelock`);
+      }
+
+      await store.put({ ...record, cancelledAt: Math.floor(Date.now() / 1000) });
+      let secretDeleted = false;
+      if (!options.keepSecret) {
+        const vault = await openVault();
+        secretDeleted = await vault.delete(record.swapId);
+        vault.close();
+      }
+      spinner.succeed('cancel', 'Swap cancelled');
+
+      emit({ swapId: record.swapId, chain: record.chain, cancelled: true, secretDeleted }, result => {
+        logger.info(`Swap ${result.swapId} will not be funded; omni swap fund refuses it from now on`);
+        if (result.secretDeleted) logger.info('Its preimage was deleted, so a counterparty locking late can only refund');
+      });
+    } catch (error) {
+      spinner.stop('cancel');
+      exitWithError(error);
+    }
+  });
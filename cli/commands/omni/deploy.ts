@@ -0,0 +1,63 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { configPath, recordChainSettings } from '../../services/omni-config';
+import { DEFAULT_HTLC_ARTIFACT, deployEvmHtlc, storeWasm } from '../../services/omni-deploy';
+
+export const deployCommand = new Command('deploy')
+  .description('Deploy the HTLC contracts and record their code IDs and addresses in the profile');
+
+deployCommand
+  .command('wasm')
+  .description('Store the CosmWasm HTLC code on a Cosmos chain and record its code ID')
+  .argument('<file>', 'optimized htlc.wasm')
+  .requiredOption('-c, --chain <chain>', 'Cosmos chain to store the code on')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .option('--no-record', 'do not write the code ID to config.toml')
+  .action(async (file: string, options) => {
+    logger.heading('🚀 Store HTLC Code');
+
+    try {
+      spinner.start('deploy', `Uploading ${file} to ${options.chain}...`);
+      const stored = await storeWasm(options.chain, file, options.key);
+      const profile = options.record ? await recordChainSettings(options.chain, { codeId: String(stored.codeId), codeChecksum: stored.checksum }) : null;
+      spinner.succeed('deploy', `Stored code ${stored.codeId}`);
+
+      emit({ ...stored, profile }, result => {
+        logger.info(`Checksum:    ${result.checksum}`);
+        logger.info(`Transaction: ${result.txHash} (height ${result.height})`);
+        if (result.profile) logger.info(`Recorded code_id = "${result.codeId}" and its checksum for ${result.chain} in profile ${result.profile} (${configPath()})`);
+        logger.info('New swaps on this chain instantiate the HTLC from this code ID');
+      });
+    } catch (error) {
+      spinner.fail('deploy', 'Store failed');
+      exitWithError(error);
+    }
+  });
+
+deployCommand
+  .command('evm')
+  .description('Deploy SimpleHTLC on an EVM chain and record its address')
+  .requiredOption('-c, --chain <chain>', 'EVM chain to deploy on')
+  .option('--artifact <file>', 'Hardhat artifact to deploy', DEFAULT_HTLC_ARTIFACT)
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .option('--no-record', 'do not write the address to config.toml')
+  .action(async (options) => {
+    logger.heading('🚀 Deploy HTLC');
+
+    try {
+      spinner.start('deploy', `Deploying to ${options.chain}...`);
+      const deployed = await deployEvmHtlc(options.chain, options.artifact, options.key);
+      const profile = options.record ? await recordChainSettings(options.chain, { htlcAddress: deployed.address, htlcCodeHash: deployed.codeHash }) : null;
+      spinner.succeed('deploy', `Deployed at ${deployed.address}`);
+
+      emit({ ...deployed, profile }, result => {
+        logger.info(`Transaction: ${result.txHash} (block ${result.blockNumber})`);
+        if (result.profile) logger.info(`Recorded htlc_address and htlc_code_hash for ${result.chain} in profile ${result.profile} (${configPath()})`);
+      });
+    } catch (error) {
+      spinner.fail('deploy', 'Deployment failed');
+      exitWithError(error);
+    }
+  });
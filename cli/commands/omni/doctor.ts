@@ -0,0 +1,60 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { configuredChains } from '../../services/omni-chains';
+import { diagnoseChain } from '../../services/omni-doctor';
+import type { CheckStatus, DoctorCheck } from '../../services/omni-doctor';
+import { parseDuration } from '../../utils/duration';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+const ICONS: Record<CheckStatus, string> = {
+  ok: chalk.green('✔'),
+  fail: chalk.red('✘'),
+  warn: chalk.yellow('!')
+};
+
+export const doctorCommand = new Command('doctor')
+  .description('Check RPC reachability, deployed contract versions, keys and clock skew on each configured chain')
+  .option('-c, --chain <chain>', 'only check this chain (repeatable); default the profile\'s chains, else all', collect)
+  .option('-k, --key <name>', 'keyring key to check balances for')
+  .option('--max-skew <duration>', 'clock skew to tolerate against chain time', '2m')
+  .action(async (options) => {
+    logger.heading('🩺 Omni Doctor');
+
+    let chains: string[];
+    try {
+      chains = configuredChains(options.chain);
+    } catch (error) {
+      exitWithError(error);
+    }
+
+    const checks: DoctorCheck[] = [];
+    for (const chain of chains) {
+      spinner.start('doctor', `Checking ${chain}...`);
+      try {
+        checks.push(...await diagnoseChain(chain, { key: options.key, maxSkewSeconds: parseDuration(options.maxSkew) }));
+      } catch (error) {
+        checks.push({ chain, check: 'rpc', status: 'fail', detail: (error as Error).message });
+      }
+      spinner.stop('doctor');
+    }
+
+    const failed = checks.filter(check => check.status === 'fail').length;
+    const warned = checks.filter(check => check.status === 'warn').length;
+    emit({ healthy: failed === 0, checks }, result => {
+      logger.table(result.checks.map(check => ({
+        Chain: check.chain,
+        Check: check.check,
+        Status: ICONS[check.status],
+        Detail: check.detail
+      })));
+      if (failed) logger.error(`${failed} check(s) failed, ${warned} warning(s)`);
+      else logger.success(`All chains healthy${warned ? `, ${warned} warning(s)` : ''}`);
+    });
+    if (failed) process.exit(1);
+  });
@@ -0,0 +1,63 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { cosmosSigningClient, evmSigner, requireChain } from '../../services/omni-chains';
+import { requestFaucet } from '../../services/omni-faucet';
+import type { FaucetGrant } from '../../services/omni-faucet';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+/** The address the active key signs with on a chain, without needing an HTLC deployed there */
+async function keyAddress(chain: string, key?: string): Promise<string> {
+  if (requireChain(chain).type === 'evm') return (await evmSigner(chain, key)).address;
+  const { client, address } = await cosmosSigningClient(chain, key);
+  client.getClient().disconnect();
+  return address;
+}
+
+export const faucetCommand = new Command('faucet')
+  .description('Get gas and test tokens on testnets and localnets');
+
+faucetCommand
+  .command('request')
+  .description('Fund the active key (or an address) from the configured faucet, or directly on a localnet')
+  .requiredOption('-c, --chain <chain>', 'chain to fund on (repeatable)', collect)
+  .option('-a, --address <address>', 'fund this address instead of the active key')
+  .option('--asset <asset>', 'denom or token address to ask for, "native" for gas (repeatable); default from the profile, else native', collect)
+  .option('-k, --key <name>', 'keyring key whose address to fund')
+  .action(async (options) => {
+    logger.heading('🚰 Faucet');
+
+    const grants: FaucetGrant[] = [];
+    const failures: { chain: string; error: string }[] = [];
+    for (const chain of options.chain as string[]) {
+      try {
+        const address = options.address ?? await keyAddress(chain, options.key);
+        spinner.start('faucet', `Requesting funds for ${address} on ${chain}...`);
+        grants.push(...await requestFaucet(chain, address, options.asset));
+        spinner.succeed('faucet', `Funded on ${chain}`);
+      } catch (error) {
+        spinner.fail('faucet', `No funds on ${chain}`);
+        failures.push({ chain, error: (error as Error).message });
+      }
+    }
+
+    if (grants.length === 0 && failures.length === 1) exitWithError(new Error(failures[0].error));
+    emit({ grants, failures }, result => {
+      if (result.grants.length) {
+        logger.table(result.grants.map(grant => ({
+          Chain: grant.chain,
+          Address: grant.address,
+          Asset: grant.asset,
+          Amount: grant.amount ?? '-',
+          Via: grant.via,
+          Tx: grant.txHash ?? '-'
+        })));
+      }
+      for (const { chain, error } of result.failures) logger.warn(`${chain}: ${error}`);
+    });
+    if (failures.length) process.exit(1);
+  });
@@ -0,0 +1,194 @@
+import { Command } from 'commander';
+import { promises as fs } from 'fs';
+import inquirer from 'inquirer';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError, isJsonOutput } from '../../utils/output';
+import { SwapStore } from '../../services/omni-store';
+import { credentialsFor, requireChain, withChain } from '../../services/omni-chains';
+import {
+  HANDOFF_VERSION,
+  checkTimelocks,
+  decodeHandoff,
+  deriveSwapId,
+  encodeHandoffUri,
+  recommendTimelocks,
+  signHandoff,
+  verifyHandoff
+} from '../../../packages/shared/src/sdk';
+import type { HandoffAminoSigner, HandoffSigner, SwapHandoff } from '../../../packages/shared/src/sdk';
+import { SwapState } from '../../../packages/shared/src/utils/htlc';
+
+const store = new SwapStore();
+
+export const exportCommand = new Command('export')
+  .description("Write a signed handoff of a swap's public parameters for the counterparty to import")
+  .argument('<swap-id>', 'swap created with "swap create"')
+  .requiredOption('--ask-chain <chain>', 'chain the counterparty should lock on')
+  .requiredOption('--ask-amount <amount>', 'amount the counterparty should lock, in base units')
+  .option('--ask-token <address>', 'token the counterparty should lock; omit for the native asset')
+  .option('-r, --recipient <address>', 'your address on the ask chain; defaults to the address of your key there')
+  .option('--recipient-key <name>', 'keyring key whose address on the ask chain receives the funds')
+  .option('-k, --key <name>', "keyring key to sign the handoff with; must be the swap's originator")
+  .option('--out <file>', 'also write the handoff as JSON to this file')
+  .action(async (swapId: string, options) => {
+    logger.heading('📤 Export Swap');
+
+    try {
+      const record = await store.find(swapId);
+      if (!record) throw new Error(`Unknown swap ${swapId}`);
+      const makerChain = requireChain(record.chain);
+      const askChain = requireChain(options.askChain);
+
+      const recipient: string = options.recipient
+        ?? await withChain(options.askChain, { sign: true, key: options.recipientKey }, chain => chain.address!);
+      // The latest ask expiry checkTimelocks accepts against our lock
+      const { marginSeconds } = recommendTimelocks(record.chain, options.askChain);
+
+      const handoff: SwapHandoff = {
+        version: HANDOFF_VERSION,
+        maker: {
+          chain: record.chain,
+          chainId: makerChain.chainId,
+          swapId: record.swapId,
+          contractId: record.contractId,
+          originator: record.originator,
+          beneficiary: record.beneficiary,
+          hashLock: record.hashLock,
+          timelock: record.timelock,
+          amount: record.amount,
+          token: record.token
+        },
+        ask: {
+          chain: options.askChain,
+          chainId: askChain.chainId,
+          beneficiary: recipient,
+          amount: options.askAmount,
+          token: options.askToken,
+          timelock: record.timelock - marginSeconds - 1
+        },
+        createdAt: Math.floor(Date.now() / 1000)
+      };
+
+      const credentials = await credentialsFor(record.chain, options.key);
+      const signer: HandoffSigner | null = credentials.evmSigner ? { type: 'evm', signer: credentials.evmSigner }
+        : credentials.privateKey ? { type: 'evm', privateKey: credentials.privateKey }
+        : credentials.cosmosSigner ? { type: 'cosmos', signer: credentials.cosmosSigner as unknown as HandoffAminoSigner }
+        : credentials.mnemonic ? { type: 'cosmos', mnemonic: credentials.mnemonic } : null;
+      if (!signer) throw new Error(`No key configured for ${record.chain}; pass --key <name>`);
+      const signed = await signHandoff(handoff, signer);
+
+      if (options.out) await fs.writeFile(options.out, JSON.stringify(signed, null, 2) + '\n');
+      const uri = encodeHandoffUri(signed);
+
+      emit({ ...signed, uri, file: options.out ?? null }, result => {
+        if (!record.contractId) logger.warn('This swap is not funded yet; the counterparty should wait for your lock before funding theirs');
+        if (result.file) logger.success(`Wrote ${result.file}`);
+        logger.info('Send this to the counterparty; they run omni import <uri-or-file>:');
+        console.log(`\n  ${result.uri}\n`);
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+export const importCommand = new Command('import')
+  .description('Verify a swap handoff and create (and fund) the matching escrow')
+  .argument('<handoff>', 'omni-swap: URI or handoff JSON file')
+  .option('-k, --key <name>', 'keyring key to sign with on the ask chain')
+  .option('--no-fund', 'only record the swap; fund it later with omni swap fund')
+  .option('-y, --yes', 'skip the confirmation prompt')
+  .action(async (input: string, options) => {
+    logger.heading('📥 Import Swap');
+
+    try {
+      const text = input.startsWith('omni-swap:') ? input : await fs.readFile(input, 'utf8');
+      const handoff = verifyHandoff(decodeHandoff(text));
+      const { maker, ask } = handoff;
+
+      for (const leg of [maker, ask]) {
+        const config = requireChain(leg.chain);
+        if (String(config.chainId) !== String(leg.chainId)) {
+          throw new Error(`Handoff expects ${leg.chain} to be chain ${leg.chainId}, but it is ${config.chainId} here`);
+        }
+      }
+
+      const now = Math.floor(Date.now() / 1000);
+      const timelockErrors = checkTimelocks(maker.chain, ask.chain, { sourceExpiry: maker.timelock, destExpiry: ask.timelock }, now);
+      if (timelockErrors.length) {
+        throw new Error(`Unsafe timelocks: ${timelockErrors.map(error => error.message).join('; ')}`);
+      }
+
+      // A funded maker lock must match what was signed; otherwise there is nothing to check yet
+      if (maker.contractId) {
+        spinner.start('import', `Checking ${maker.contractId} on ${maker.chain}...`);
+        const htlc = await withChain(maker.chain, {}, chain => chain.getHtlc(maker.contractId!));
+        spinner.stop('import');
+        const matches = htlc
+          && htlc.state === SwapState.OPEN
+          && htlc.hashLock.toLowerCase() === maker.hashLock.toLowerCase()
+          && BigInt(htlc.value) === BigInt(maker.amount)
+          && htlc.timelock === maker.timelock
+          && htlc.beneficiary.toLowerCase() === maker.beneficiary.toLowerCase();
+        if (!matches) throw new Error(`The maker's HTLC ${maker.contractId} on ${maker.chain} is missing, settled or differs from the handoff`);
+      }
+
+      if (!options.yes) {
+        if (isJsonOutput()) throw new Error('Pass --yes to import with --output json');
+        logger.info(`They lock ${maker.amount} ${maker.token || 'native'} on ${maker.chain} for ${maker.beneficiary}${maker.contractId ? '' : ' (not funded yet)'}`);
+        logger.info(`You lock ${ask.amount} ${ask.token || 'native'} on ${ask.chain} for ${ask.beneficiary}, expiring ${new Date(ask.timelock * 1000).toISOString()}`);
+        const { confirmed } = await inquirer.prompt([{ type: 'confirm', name: 'confirmed', default: false, message: 'Create this escrow?' }]);
+        if (!confirmed) return;
+      }
+
+      spinner.start('import', `Preparing swap on ${ask.chain}...`);
+      const result = await withChain(ask.chain, { sign: true, key: options.key }, async chain => {
+        const originator = chain.address!;
+        const swapId = deriveSwapId({
+          chainId: ask.chainId,
+          hashLock: maker.hashLock,
+          originator,
+          beneficiary: ask.beneficiary,
+          token: ask.token,
+          amount: ask.amount,
+          timelock: ask.timelock
+        });
+        const record = {
+          swapId,
+          chain: ask.chain,
+          originator,
+          beneficiary: ask.beneficiary,
+          hashLock: maker.hashLock,
+          timelock: ask.timelock,
+          amount: ask.amount,
+          token: ask.token,
+          createdAt: Math.floor(Date.now() / 1000)
+        };
+        await store.put(record);
+        if (!options.fund) return { swapId, contractId: null, txHash: null };
+
+        spinner.update('import', `Locking ${ask.amount} on ${ask.chain}...`);
+        const funded = await chain.lock({
+          contractId: swapId,
+          beneficiary: ask.beneficiary,
+          hashLock: maker.hashLock,
+          timelock: ask.timelock,
+          amount: ask.amount,
+          token: ask.token
+        });
+        await store.put({ ...record, contractId: funded.contractId, fundTx: funded.txHash });
+        return { swapId, contractId: funded.contractId, txHash: funded.txHash };
+      });
+      spinner.succeed('import', result.contractId ? 'Escrow funded' : 'Swap recorded');
+
+      emit({ ...result, chain: ask.chain, counterparty: maker }, imported => {
+        logger.info(`Swap ID: ${imported.swapId}`);
+        if (imported.contractId) logger.info(`HTLC: ${imported.contractId} (tx ${imported.txHash})`);
+        else logger.info(`Fund it with: omni swap fund ${imported.swapId}`);
+        logger.info(`Once they claim it, claim theirs with the revealed preimage: omni swap claim ${maker.contractId ?? maker.swapId} --chain ${maker.chain} --preimage 0x...`);
+      });
+    } catch (error) {
+      spinner.fail('import', 'Import failed');
+      exitWithError(error);
+    }
+  });
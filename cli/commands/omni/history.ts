@@ -0,0 +1,108 @@
+import { Command } from 'commander';
+import { promises as fs } from 'fs';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { parseWhen } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { requireChain } from '../../services/omni-chains';
+import {
+  chainsForAddress,
+  historyCsv,
+  historyState,
+  indexerUrl,
+  queryIndexer,
+  scanChains
+} from '../../services/omni-history';
+import type { HistoryEntry, HistoryQuery, HistoryState } from '../../services/omni-history';
+
+const STATES: HistoryState[] = ['open', 'expired', 'claimed', 'refunded'];
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const historyCommand = new Command('history')
+  .description('List the swaps an address sent or received, from the indexer or by scanning the chains')
+  .argument('<address>', 'EVM or bech32 address')
+  .option('-c, --chain <chain>', 'chain to search (repeatable); defaults to every chain the address can be on', collect)
+  .option('-s, --state <state>', `only these states: ${STATES.join(', ')} (repeatable)`, collect)
+  .option('--since <when>', 'funded at or after: ISO date, or a duration ago such as 30d')
+  .option('--until <when>', 'funded at or before: ISO date, or a duration ago')
+  .option('--csv [file]', 'write CSV to the file, or to stdout when no file is given')
+  .option('--no-indexer', 'scan the chains even when an indexer is configured')
+  .option('--from-height <height>', 'first block to scan', Number)
+  .option('--blocks <count>', 'blocks to scan back from the head when --from-height is not given', Number, 10000)
+  .action(async (address: string, options) => {
+    try {
+      const states: HistoryState[] | undefined = options.state;
+      const unknown = states?.find(state => !STATES.includes(state));
+      if (unknown) throw new Error(`Unknown state "${unknown}"; expected one of ${STATES.join(', ')}`);
+
+      const chains: string[] = options.chain ?? chainsForAddress(address);
+      chains.forEach(chain => requireChain(chain));
+      if (chains.length === 0) throw new Error(`No configured chain uses addresses like ${address}; pass --chain`);
+
+      const query: HistoryQuery = {
+        address,
+        chains,
+        states,
+        since: options.since ? parseWhen(options.since) : undefined,
+        until: options.until ? parseWhen(options.until) : undefined
+      };
+
+      // A CSV on stdout must not be mixed with progress output
+      const csvToStdout = options.csv === true;
+      if (!csvToStdout) logger.heading('🧾 Swap History');
+
+      const url = options.indexer ? indexerUrl() : undefined;
+      let entries: HistoryEntry[] | undefined;
+      let source = 'indexer';
+      if (url) {
+        if (!csvToStdout) spinner.start('history', `Querying ${url}...`);
+        try {
+          entries = await queryIndexer(url, query);
+          spinner.stop('history');
+        } catch (error) {
+          spinner.warn('history', `Indexer unavailable (${(error as Error).message}); scanning the chains instead`);
+        }
+      }
+      if (!entries) {
+        source = 'chain scan';
+        if (!csvToStdout) spinner.start('history', 'Scanning chains...');
+        entries = await scanChains(query, { fromHeight: options.fromHeight, blocks: options.blocks }, (chain, from, to) =>
+          spinner.update('history', `Scanning ${chain} blocks ${from}..${to}...`)
+        );
+        spinner.stop('history');
+      }
+
+      const now = Math.floor(Date.now() / 1000);
+      if (options.csv) {
+        const csv = historyCsv(entries, now);
+        if (csvToStdout) {
+          process.stdout.write(csv);
+          return;
+        }
+        await fs.writeFile(options.csv, csv);
+      }
+
+      emit({ address, source, htlcs: entries.map(entry => ({ ...entry, state: historyState(entry, now) })) }, result => {
+        if (options.csv) logger.success(`Wrote ${result.htlcs.length} rows to ${options.csv}`);
+        if (result.htlcs.length === 0) {
+          logger.info(`No swaps found for ${address} (${result.source})`);
+          return;
+        }
+        logger.table(result.htlcs.map(entry => ({
+          Funded: new Date(entry.fundedTime * 1000).toISOString(),
+          Chain: entry.chain,
+          HTLC: entry.contractId,
+          Role: entry.role,
+          State: entry.state,
+          Amount: entry.value
+        })));
+        logger.info(`${result.htlcs.length} swaps, from the ${result.source}`);
+      });
+    } catch (error) {
+      spinner.fail('history', 'History lookup failed');
+      exitWithError(error);
+    }
+  });
@@ -0,0 +1,117 @@
+import { Command } from 'commander';
+import inquirer from 'inquirer';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError, isJsonOutput } from '../../utils/output';
+import { configPath, saveProfile } from '../../services/omni-config';
+import type { Profile } from '../../services/omni-config';
+import { KeyStore } from '../../services/omni-keys';
+import type { KeyType } from '../../services/omni-keys';
+import { COSMOS_REGISTRY_URL, EVM_CHAIN_LIST_URL, scaffoldCosmosChain, scaffoldEvmChain } from '../../services/omni-init';
+import type { ScaffoldedChain } from '../../services/omni-init';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+/** `chain=value` pairs, keyed by chain */
+function pairs(values: string[] = [], flag: string): Record<string, string> {
+  return Object.fromEntries(values.map(value => {
+    const at = value.indexOf('=');
+    if (at <= 0) throw new Error(`${flag} takes chain=value, got "${value}"`);
+    return [value.slice(0, at), value.slice(at + 1)];
+  }));
+}
+
+async function pickKey(type: KeyType, keyEnv: string): Promise<string | undefined> {
+  const names = (await KeyStore.open()).list().filter(key => key.type === type).map(key => key.name);
+  if (names.length === 0) return undefined;
+  const { key } = await inquirer.prompt([{
+    type: 'list',
+    name: 'key',
+    message: `${type === 'evm' ? 'EVM' : 'Cosmos'} signing key:`,
+    choices: [...names, { name: `none, read $${keyEnv}`, value: '' }]
+  }]);
+  return key || undefined;
+}
+
+export const initConfigCommand = new Command('init-config')
+  .description('Create a profile from the Cosmos chain registry and the EVM chain list, asking only for contract addresses and keys')
+  .argument('<profile>', 'name of the profile to create')
+  .option('--cosmos <chain>', 'chain-registry name such as osmosistestnet (repeatable)', collect)
+  .option('--evm <chain>', 'EVM chain ID or name from chainid.network, such as 421614 (repeatable)', collect)
+  .option('--htlc <chain=address>', 'HTLC contract for a chain, instead of being asked (repeatable)', collect)
+  .option('--evm-key <name>', 'keyring key for EVM chains, instead of being asked')
+  .option('--cosmos-key <name>', 'keyring key for Cosmos chains, instead of being asked')
+  .option('--registry-url <url>', 'Cosmos chain registry base URL', COSMOS_REGISTRY_URL)
+  .option('--chain-list-url <url>', 'EVM chain list (chains.json)', EVM_CHAIN_LIST_URL)
+  .option('--default', 'make it the default profile')
+  .option('--force', 'replace an existing profile of the same name')
+  .option('-y, --yes', 'do not prompt; contracts not given with --htlc are left for omni deploy to fill in')
+  .action(async (name: string, options) => {
+    logger.heading('🧩 Init Config');
+
+    try {
+      const cosmos: string[] = options.cosmos ?? [];
+      const evm: string[] = options.evm ?? [];
+      if (cosmos.length + evm.length === 0) throw new Error('Pass at least one --cosmos or --evm chain');
+      const htlcs = pairs(options.htlc, '--htlc');
+      const interactive = !options.yes && !isJsonOutput() && process.stdin.isTTY;
+
+      const chains: ScaffoldedChain[] = [];
+      for (const chain of cosmos) {
+        spinner.start('init', `Reading ${chain} from the chain registry...`);
+        chains.push(await scaffoldCosmosChain(chain, options.registryUrl));
+      }
+      for (const chain of evm) {
+        spinner.start('init', `Looking up ${chain} in the EVM chain list...`);
+        chains.push(await scaffoldEvmChain(chain, options.chainListUrl));
+      }
+      spinner.stop('init');
+
+      for (const chain of chains) {
+        if (!chain.rpcVerified) logger.warn(`No RPC for ${chain.key} answered; using ${chain.config.rpcUrl} unchecked`);
+        const given = htlcs[chain.key] ?? htlcs[String(chain.config.chainId)];
+        if (given || !interactive) {
+          if (given) chain.config.htlcAddress = given;
+          continue;
+        }
+        const answers = await inquirer.prompt([
+          { type: 'input', name: 'htlcAddress', message: `HTLC contract on ${chain.config.name} (blank to deploy later):` },
+          {
+            type: 'input',
+            name: 'codeId',
+            message: `Code ID of the HTLC on ${chain.config.name} (blank if unknown):`,
+            when: () => chain.config.type === 'cosmos'
+          }
+        ]);
+        if (answers.htlcAddress) chain.config.htlcAddress = answers.htlcAddress.trim();
+        if (answers.codeId) (chain.config as { codeId?: string }).codeId = answers.codeId.trim();
+      }
+
+      const profile: Profile = {
+        name,
+        chains: Object.fromEntries(chains.map(chain => [chain.key, chain.config])),
+        evmKey: options.evmKey,
+        cosmosKey: options.cosmosKey
+      };
+      if (interactive && evm.length && !profile.evmKey) profile.evmKey = await pickKey('evm', 'PRIVATE_KEY');
+      if (interactive && cosmos.length && !profile.cosmosKey) profile.cosmosKey = await pickKey('cosmos', 'COSMOS_MNEMONIC');
+
+      await saveProfile(profile, { makeDefault: options.default, overwrite: options.force });
+
+      emit({ profile: name, file: configPath(), chains }, result => {
+        logger.success(`Wrote profile ${result.profile} to ${result.file}`);
+        logger.table(result.chains.map(chain => ({
+          Chain: chain.key,
+          'Chain ID': chain.config.chainId,
+          RPC: chain.config.rpcUrl,
+          HTLC: chain.config.htlcAddress || '(omni deploy)'
+        })));
+        logger.info(`Check it with: omni --profile ${result.profile} doctor`);
+      });
+    } catch (error) {
+      spinner.fail('init', 'Profile not created');
+      exitWithError(error);
+    }
+  });
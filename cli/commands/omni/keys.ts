@@ -0,0 +1,149 @@
+import { Command } from 'commander';
+import inquirer from 'inquirer';
+import { ethers } from 'ethers';
+import { logger } from '../../utils/logger';
+import { emit, exitWithError, isJsonOutput } from '../../utils/output';
+import { KeyStore } from '../../services/omni-keys';
+import type { KeyBackend, KeyType } from '../../services/omni-keys';
+import { ledgerEnabled, showLedgerAddress, useLedger } from '../../services/omni-ledger';
+import { requireChain } from '../../services/omni-chains';
+
+export const keysCommand = new Command('keys')
+  .description('Manage named EVM and Cosmos signing keys');
+
+function parseType(value: string): KeyType {
+  if (value !== 'evm' && value !== 'cosmos') throw new Error(`Unknown key type "${value}"; expected evm or cosmos`);
+  return value;
+}
+
+function parseBackend(value?: string): KeyBackend | undefined {
+  if (value === undefined) return undefined;
+  if (value !== 'file' && value !== 'os') throw new Error(`Unknown keyring backend "${value}"; expected file or os`);
+  return value;
+}
+
+async function readStdin(): Promise<string> {
+  const chunks: Buffer[] = [];
+  for await (const chunk of process.stdin) chunks.push(chunk as Buffer);
+  return Buffer.concat(chunks).toString('utf8');
+}
+
+keysCommand
+  .command('add')
+  .description('Generate a new key')
+  .argument('<name>', 'key name')
+  .requiredOption('-t, --type <type>', 'evm or cosmos')
+  .option('--backend <backend>', 'file or os (default OMNI_KEYRING_BACKEND, else file)')
+  .action(async (name: string, options) => {
+    try {
+      const type = parseType(options.type);
+      const wallet = ethers.Wallet.createRandom();
+      const secret = type === 'evm' ? wallet.privateKey : wallet.mnemonic!.phrase;
+      const info = await (await KeyStore.open()).add(name, type, secret, parseBackend(options.backend));
+
+      emit({ ...info, ...(type === 'cosmos' ? { mnemonic: secret } : {}) }, key => {
+        logger.success(`Added ${key.type} key ${key.name} (${key.backend})`);
+        if (key.address) logger.info(`Address: ${key.address}`);
+        if (key.type === 'cosmos') {
+          logger.warn('Write down this mnemonic; it is the only way to recover the key:');
+          console.log(`\n  ${secret}\n`);
+        }
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+keysCommand
+  .command('import')
+  .description('Import a private key (EVM) or mnemonic (Cosmos), read from a prompt or stdin')
+  .argument('<name>', 'key name')
+  .requiredOption('-t, --type <type>', 'evm or cosmos')
+  .option('--backend <backend>', 'file or os (default OMNI_KEYRING_BACKEND, else file)')
+  .option('--stdin', 'read the key from stdin instead of prompting')
+  .action(async (name: string, options) => {
+    try {
+      const type = parseType(options.type);
+      const secret: string = options.stdin
+        ? await readStdin()
+        : (await inquirer.prompt([{
+            type: 'password',
+            name: 'secret',
+            mask: '*',
+            message: type === 'evm' ? 'Private key:' : 'Mnemonic:'
+          }])).secret;
+      const info = await (await KeyStore.open()).add(name, type, secret, parseBackend(options.backend));
+
+      emit(info, key => {
+        logger.success(`Imported ${key.type} key ${key.name} (${key.backend})`);
+        if (key.address) logger.info(`Address: ${key.address}`);
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+keysCommand
+  .command('list')
+  .description('List stored keys')
+  .action(async () => {
+    try {
+      const keys = (await KeyStore.open()).list();
+      emit(keys, list => {
+        if (list.length === 0) {
+          logger.info('No keys; add one with omni keys add <name> --type evm|cosmos');
+          return;
+        }
+        logger.table(list.map(key => ({
+          Name: key.name,
+          Type: key.type,
+          Backend: key.backend,
+          Address: key.address ?? '-',
+          Added: new Date(key.createdAt * 1000).toISOString()
+        })));
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+keysCommand
+  .command('export')
+  .description('Print a stored private key or mnemonic')
+  .argument('<name>', 'key name')
+  .option('-y, --yes', 'skip the confirmation prompt')
+  .action(async (name: string, options) => {
+    try {
+      if (!options.yes) {
+        if (isJsonOutput()) throw new Error('Pass --yes to export a key with --output json');
+        const { confirmed } = await inquirer.prompt([{
+          type: 'confirm',
+          name: 'confirmed',
+          default: false,
+          message: `Print the secret for ${name}? Anyone who sees it controls the key.`
+        }]);
+        if (!confirmed) return;
+      }
+      const key = await (await KeyStore.open()).secret(name);
+      emit({ name, ...key }, exported => console.log(exported.secret));
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+keysCommand
+  .command('ledger')
+  .description('Show the Ledger account a chain would sign with, and confirm it on the device')
+  .requiredOption('-c, --chain <chain>', 'chain whose address format to use')
+  .option('--account <index>', 'Ledger account index', Number, 0)
+  .action(async (options) => {
+    try {
+      if (!ledgerEnabled()) useLedger(options.account);
+      const config = requireChain(options.chain);
+      logger.info(`Check that the address on the Ledger matches, then confirm it there (${config.type === 'evm' ? 'Ethereum' : 'Cosmos'} app)`);
+      const address = await showLedgerAddress(config);
+      emit({ chain: options.chain, address }, shown => logger.success(`${shown.chain}: ${shown.address}`));
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
@@ -0,0 +1,65 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { parseDuration } from '../../utils/duration';
+import { exitWithError } from '../../utils/output';
+import { requireChain } from '../../services/omni-chains';
+import { listen, postWebhook } from '../../services/omni-listen';
+import type { HtlcEvent, HtlcEventType } from '../../services/omni-listen';
+
+const TYPES: HtlcEventType[] = ['created', 'claimed', 'refunded'];
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const listenCommand = new Command('listen')
+  .description('Stream HTLC events as NDJSON on stdout, or forward them to a webhook')
+  .requiredOption('-c, --chain <chain>', 'chain to listen on (repeatable)', collect)
+  .option('-e, --event <type>', `only these events: ${TYPES.join(', ')} (repeatable)`, collect)
+  .option('--address <address>', 'only HTLCs this address funded or is paid by (repeatable)', collect)
+  .option('--hashlock <hex>', 'only HTLCs under this hashlock (repeatable)', collect)
+  .option('--contract <id>', 'only this HTLC ID / contract address (repeatable)', collect)
+  .option('--webhook <url>', 'POST each event as JSON to this URL instead of printing it')
+  .option('--webhook-secret <secret>', 'sign webhook bodies with HMAC-SHA256 (X-Omni-Signature); default $OMNI_WEBHOOK_SECRET')
+  .option('--from-height <height>', 'first block to read on every chain without a cursor; default the next block', Number)
+  .option('--confirmations <blocks>', 'blocks to stay behind the head', Number, 0)
+  .option('--cursor <file>', 'save progress here and resume from it on restart')
+  .option('-i, --interval <duration>', 'poll interval', '15s')
+  .action(async (options) => {
+    let stopping = false;
+    process.once('SIGINT', () => { stopping = true; });
+    process.once('SIGTERM', () => { stopping = true; });
+
+    try {
+      const chains: string[] = options.chain;
+      chains.forEach(chain => requireChain(chain));
+      const types: HtlcEventType[] | undefined = options.event;
+      const unknown = types?.find(type => !TYPES.includes(type));
+      if (unknown) throw new Error(`Unknown event "${unknown}"; expected one of ${TYPES.join(', ')}`);
+
+      const secret: string | undefined = options.webhookSecret ?? process.env.OMNI_WEBHOOK_SECRET;
+      // stdout carries only events, so progress goes to stderr
+      if (options.webhook) logger.heading(`📡 Forwarding ${chains.join(', ')} events to ${options.webhook}`);
+      else console.error(`Listening on ${chains.join(', ')}; events follow as NDJSON`);
+
+      const deliver = options.webhook
+        ? async (event: HtlcEvent) => {
+          await postWebhook(options.webhook, event, secret);
+          logger.info(`${event.chain} ${event.type} ${event.contractId} (block ${event.height})`);
+        }
+        : async (event: HtlcEvent) => {
+          process.stdout.write(JSON.stringify(event) + '\n');
+        };
+
+      await listen(chains, {
+        fromHeight: options.fromHeight,
+        confirmations: options.confirmations,
+        intervalMs: parseDuration(options.interval) * 1000,
+        cursorFile: options.cursor,
+        filter: { types, addresses: options.address, hashLocks: options.hashlock, contractIds: options.contract },
+        stopped: () => stopping
+      }, deliver);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
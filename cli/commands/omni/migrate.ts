@@ -0,0 +1,110 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { cosmosSigningClient, requireChain } from '../../services/omni-chains';
+import { recordChainSettings } from '../../services/omni-config';
+import { storeWasm } from '../../services/omni-deploy';
+import { checkMigration } from '../../services/omni-migrate';
+import type { MigrationCheck } from '../../services/omni-migrate';
+import type { CosmosChainConfig } from '../../../packages/shared/src/chains';
+import { HTLC_CONTRACT_VERSION } from '../../../packages/shared/src/sdk';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const migrateCommand = new Command('migrate')
+  .description('Store new HTLC code and migrate contract instances to it, after a cw2 version check')
+  .requiredOption('-c, --chain <chain>', 'Cosmos chain the instances are on')
+  .option('--wasm <file>', 'new code to store first')
+  .option('--code-id <id>', 'already stored code to migrate to', Number)
+  .option('--contract <address>', 'instance to migrate (repeatable)', collect)
+  .option('--all', "every instance of the profile's current code ID that you administer")
+  .option('--to-version <version>', 'cw2 version the new code reports', HTLC_CONTRACT_VERSION)
+  .option('--contract-name <name>', 'cw2 contract name the instances must report', 'crates.io:htlc')
+  .option('--msg <json>', 'MigrateMsg', '{}')
+  .option('--allow-breaking', 'allow a major (or 0.x minor) version jump')
+  .option('--dry-run', 'run the pre-flight only')
+  .option('-k, --key <name>', 'keyring key of the instances\' admin')
+  .action(async (options) => {
+    logger.heading('🔁 Migrate HTLC');
+
+    try {
+      const config = requireChain(options.chain);
+      if (config.type !== 'cosmos') throw new Error(`${options.chain} is not a Cosmos chain; MsgMigrateContract is CosmWasm only`);
+      if (!options.wasm === (options.codeId === undefined)) throw new Error('Pass exactly one of --wasm and --code-id');
+      if (!options.contract && !options.all) throw new Error('Pass --contract <address> or --all');
+      const msg = JSON.parse(options.msg);
+
+      const { client, address } = await cosmosSigningClient(options.chain, options.key);
+      const signing = client.getSigningClient()!;
+
+      let checks: MigrationCheck[];
+      const migrated: { contract: string; txHash?: string; error?: string }[] = [];
+      let codeId: number | undefined = options.codeId;
+      try {
+        spinner.start('migrate', 'Checking instances...');
+        const contracts: string[] = [...(options.contract ?? [])];
+        if (options.all) {
+          const current = (config as CosmosChainConfig).codeId;
+          if (!current) throw new Error(`No code_id configured for ${options.chain} to find instances by`);
+          contracts.push(...await signing.getContracts(Number(current)));
+        }
+        checks = await Promise.all([...new Set(contracts)].map(contract => checkMigration(signing, contract, address, {
+          contract: options.contractName,
+          version: options.toVersion,
+          allowBreaking: options.allowBreaking
+        })));
+        // --all sweeps up every swap instance; only the administered ones are meant
+        if (options.all && !options.contract) checks = checks.filter(check => check.admin === address);
+        spinner.stop('migrate');
+
+        const ready = checks.filter(check => !check.refusal);
+        if (!options.dryRun && ready.length > 0) {
+          if (options.wasm) {
+            spinner.start('migrate', `Storing ${options.wasm}...`);
+            codeId = (await storeWasm(options.chain, options.wasm, options.key)).codeId;
+            await recordChainSettings(options.chain, { codeId: String(codeId) });
+          }
+          for (const check of ready) {
+            spinner.start('migrate', `Migrating ${check.contract} to code ${codeId}...`);
+            try {
+              const result = await signing.migrate(address, check.contract, codeId!, msg, 'auto');
+              migrated.push({ contract: check.contract, txHash: result.transactionHash });
+            } catch (error) {
+              migrated.push({ contract: check.contract, error: (error as Error).message });
+            }
+          }
+          spinner.stop('migrate');
+        }
+      } finally {
+        client.getClient().disconnect();
+      }
+
+      const failed = checks.some(check => check.refusal) || migrated.some(result => result.error);
+      emit({ chain: options.chain, codeId: codeId ?? null, toVersion: options.toVersion, dryRun: !!options.dryRun, checks, migrated }, report => {
+        if (report.checks.length === 0) {
+          logger.info('No instances to migrate');
+          return;
+        }
+        logger.table(report.checks.map(check => {
+          const result = report.migrated.find(entry => entry.contract === check.contract);
+          return {
+            Contract: check.contract,
+            Deployed: check.current ? `${check.current.contract}@${check.current.version}` : '-',
+            Target: report.toVersion,
+            Result: check.refusal
+              ? chalk.red(`refused: ${check.refusal}`)
+              : report.dryRun ? 'ok to migrate'
+              : result?.error ? chalk.red(`failed: ${result.error}`) : result?.txHash ?? '-'
+          };
+        }));
+      });
+      if (failed) process.exit(1);
+    } catch (error) {
+      spinner.fail('migrate', 'Migration failed');
+      exitWithError(error);
+    }
+  });
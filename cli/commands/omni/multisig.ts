@@ -0,0 +1,142 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore, openVault } from '../../services/omni-store';
+import { resolveHtlc } from '../../services/omni-chains';
+import {
+  approveProposal,
+  combineProposals,
+  createProposal,
+  executeProposal,
+  readProposal,
+  writeProposal
+} from '../../services/omni-multisig';
+import type { MultisigAction, MultisigProposal } from '../../services/omni-multisig';
+
+export const multisigCommand = new Command('multisig')
+  .description('Claim or refund HTLCs whose party is a Gnosis Safe or cw3 multisig');
+
+const store = new SwapStore();
+
+function fail(id: string, text: string, error: unknown): never {
+  spinner.fail(id, text);
+  exitWithError(error);
+}
+
+function approvals(proposal: MultisigProposal): number {
+  return proposal.kind === 'safe' ? proposal.signatures?.length ?? 0 : proposal.votes?.length ?? 0;
+}
+
+multisigCommand
+  .command('create')
+  .description('Write the unsigned claim or refund for the signers (on Cosmos, open the cw3 proposal)')
+  .argument('<action>', 'claim or refund')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .requiredOption('-m, --multisig <address>', 'Safe or cw3 contract that is the beneficiary (claim) or originator (refund)')
+  .requiredOption('--out <file>', 'proposal file to hand to the signers')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .option('-p, --preimage <hex>', 'preimage for a claim; defaults to the secret stored for this swap')
+  .option('-k, --key <name>', 'cw3 voter key that opens the proposal (Cosmos only)')
+  .action(async (action: string, id: string, options) => {
+    logger.heading('🖋  Multisig Proposal');
+
+    try {
+      if (action !== 'claim' && action !== 'refund') throw new Error(`Unknown action "${action}"; expected claim or refund`);
+      const { chain, contractId, record } = await resolveHtlc(store, id, options.chain);
+
+      let preimage: string | undefined = options.preimage;
+      if (action === 'claim' && !preimage && record) {
+        const vault = await openVault();
+        const secret = vault.get(record.swapId);
+        vault.close();
+        preimage = secret?.hex();
+        secret?.zeroize();
+      }
+      if (action === 'claim' && !preimage) throw new Error('No preimage given and none stored for this swap; pass --preimage');
+
+      spinner.start('multisig', `Preparing ${action} of ${contractId} on ${chain}...`);
+      const proposal = await createProposal(chain, options.multisig, action as MultisigAction, contractId, { preimage, key: options.key });
+      await writeProposal(options.out, proposal);
+      spinner.succeed('multisig', 'Proposal written');
+
+      emit({ file: options.out, ...proposal }, result => {
+        if (result.kind === 'safe') {
+          logger.info(`Safe tx hash: ${result.safeTxHash} (nonce ${result.safeTx!.nonce})`);
+          logger.info(`Needs ${result.threshold} owner signatures: omni multisig sign ${result.file} --key <owner>`);
+        } else {
+          logger.info(`cw3 proposal ${result.proposalId} opened, with your yes vote`);
+          logger.info(`Needs ${result.threshold} in total: omni multisig sign ${result.file} --key <voter>`);
+        }
+      });
+    } catch (error) {
+      fail('multisig', 'Could not create the proposal', error);
+    }
+  });
+
+multisigCommand
+  .command('sign')
+  .description('Add a signature (Safe) or yes vote (cw3) to a proposal file')
+  .argument('<file>', 'proposal file')
+  .option('--out <file>', 'write the signed copy here instead of updating the file')
+  .option('-k, --key <name>', 'owner / voter key')
+  .action(async (file: string, options) => {
+    logger.heading('✍️  Sign Proposal');
+
+    try {
+      const proposal = await readProposal(file);
+      spinner.start('sign', proposal.kind === 'safe' ? 'Signing...' : `Voting on proposal ${proposal.proposalId}...`);
+      const signed = await approveProposal(proposal, options.key);
+      await writeProposal(options.out ?? file, signed);
+      spinner.succeed('sign', proposal.kind === 'safe' ? 'Signed' : 'Voted yes');
+
+      emit({ file: options.out ?? file, approvals: approvals(signed), threshold: signed.threshold }, result => {
+        logger.info(`${result.approvals} of ${result.threshold} approvals in ${result.file}`);
+      });
+    } catch (error) {
+      fail('sign', 'Signing failed', error);
+    }
+  });
+
+multisigCommand
+  .command('combine')
+  .description('Merge copies of one proposal that were signed separately')
+  .argument('<files...>', 'signed proposal files')
+  .requiredOption('--out <file>', 'file to write the combined proposal to')
+  .action(async (files: string[], options) => {
+    try {
+      const combined = combineProposals(await Promise.all(files.map(readProposal)));
+      await writeProposal(options.out, combined);
+      emit({ file: options.out, approvals: approvals(combined), threshold: combined.threshold }, result => {
+        logger.success(`${result.approvals} of ${result.threshold} approvals combined into ${result.file}`);
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+multisigCommand
+  .command('broadcast')
+  .description('Combine the signatures and send the transaction (on Cosmos, execute the passed proposal)')
+  .argument('<files...>', 'signed proposal files')
+  .option('-k, --key <name>', 'key that pays the gas; need not be a signer')
+  .action(async (files: string[], options) => {
+    logger.heading('📤 Broadcast Proposal');
+
+    try {
+      const proposal = combineProposals(await Promise.all(files.map(readProposal)));
+      spinner.start('broadcast', `Sending ${proposal.action} of ${proposal.contractId} from ${proposal.multisig}...`);
+      const txHash = await executeProposal(proposal, options.key);
+      await writeProposal(files[0], { ...proposal, executedTx: txHash });
+      spinner.succeed('broadcast', `HTLC ${proposal.action === 'claim' ? 'claimed' : 'refunded'}`);
+
+      const record = await store.find(proposal.contractId);
+      if (record) await store.put({ ...record, [proposal.action === 'claim' ? 'claimTx' : 'refundTx']: txHash });
+
+      emit({ chain: proposal.chain, contractId: proposal.contractId, action: proposal.action, txHash }, result => {
+        logger.info(`Transaction: ${result.txHash}`);
+      });
+    } catch (error) {
+      fail('broadcast', 'Broadcast failed', error);
+    }
+  });
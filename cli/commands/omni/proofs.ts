@@ -0,0 +1,61 @@
+import { Command } from 'commander';
+import { promises as fs } from 'fs';
+import { ethers } from 'ethers';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore } from '../../services/omni-store';
+import { configuredChains } from '../../services/omni-chains';
+import { collectEvidence } from '../../services/omni-proofs';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+const store = new SwapStore();
+
+export const proofsCommand = new Command('proofs')
+  .description('Export the txs, logs, headers and Merkle proofs of every step of a swap on both chains as one archive')
+  .argument('<swap-id>', 'swap ID from swap create or quote --create')
+  .option('-c, --chain <chain>', 'chain the other leg is on (repeatable); default the profile\'s chains, else all', collect)
+  .option('--blocks <n>', 'blocks to scan back; default back to when the swap was created')
+  .option('--out <file>', 'archive to write (default proofs-<swap-id>.json)')
+  .action(async (swapId: string, options) => {
+    logger.heading('🔏 Swap Evidence');
+
+    try {
+      const record = await store.find(swapId);
+      if (!record) throw new Error(`Unknown swap ${swapId}; only swaps created on this machine can be exported`);
+
+      spinner.start('proofs', 'Finding HTLCs under the swap\'s hashlock...');
+      const archive = await collectEvidence(record, configuredChains(options.chain), options.blocks ? Number(options.blocks) : undefined,
+        step => spinner.update('proofs', `Collecting ${step}...`));
+      if (archive.steps.length === 0) throw new Error(`No transaction under swap ${record.swapId}'s hashlock was found; it may not be funded yet`);
+
+      const file: string = options.out ?? `proofs-${record.swapId.replace(/^0x/, '').slice(0, 16)}.json`;
+      const contents = JSON.stringify(archive, null, 2) + '\n';
+      await fs.writeFile(file, contents);
+      spinner.succeed('proofs', `Collected ${archive.steps.length} steps`);
+
+      const failed = [...archive.checks, ...archive.steps.flatMap(step => step.checks)].filter(check => !check.ok);
+      emit({
+        file,
+        sha256: ethers.sha256(Buffer.from(contents)),
+        steps: archive.steps.map(step => ({ chain: step.chain, step: step.step, txHash: step.txHash, height: step.height, missing: step.missing })),
+        failedChecks: failed.map(check => check.name)
+      }, result => {
+        logger.table(result.steps.map(step => ({
+          Chain: step.chain,
+          Step: step.step,
+          Height: step.height,
+          Tx: step.txHash,
+          Missing: step.missing.join(', ') || '-'
+        })));
+        result.failedChecks.forEach(name => logger.warn(`Check failed: ${name}`));
+        logger.success(`Wrote ${result.file} (sha256 ${result.sha256})`);
+      });
+    } catch (error) {
+      spinner.fail('proofs', 'Evidence export failed');
+      exitWithError(error);
+    }
+  });
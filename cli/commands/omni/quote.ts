@@ -0,0 +1,118 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { parseDuration } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { isNativeAsset, parseQuoteAsset, quoteBeneficiary, quoteSources, settlementSeconds } from '../../services/omni-quote';
+import { createSwap } from '../../services/omni-swap';
+import { Amount, QuoteAggregator, recommendTimelocks } from '../../../packages/shared/src/sdk';
+import type { Quote } from '../../../packages/shared/src/sdk';
+
+function collectResolver(value: string, previous: Record<string, string> = {}): Record<string, string> {
+  const separator = value.indexOf('=');
+  if (separator <= 0) throw new Error(`Invalid resolver "${value}"; expected name=url`);
+  return { ...previous, [value.slice(0, separator)]: value.slice(separator + 1) };
+}
+
+function formatSeconds(seconds: number): string {
+  return seconds < 120 ? `~${seconds}s` : `~${Math.round(seconds / 60)}m`;
+}
+
+/** A quote by its 1-based row in the table or by source name */
+function pickQuote(quotes: Quote[], choice: string): Quote {
+  const quote = /^\d+$/.test(choice) ? quotes[Number(choice) - 1] : quotes.find(candidate => candidate.source === choice);
+  if (!quote) throw new Error(`No quote "${choice}"; pick a row number or one of ${quotes.map(candidate => candidate.source).join(', ') || 'none'}`);
+  return quote;
+}
+
+export const quoteCommand = new Command('quote')
+  .description('Compare quotes from resolvers and public routes for a pair and size, and optionally create the swap')
+  .requiredOption('--from <chain:asset>', 'what you send, e.g. sepolia:native or junoTestnet:ujunox')
+  .requiredOption('--to <chain:asset>', 'what you want, e.g. osmosisTestnet:uosmo')
+  .requiredOption('-a, --amount <amount>', 'amount to send, in whole units (e.g. 1.5)')
+  .option('--from-decimals <n>', 'decimals of the input asset; defaults to the native asset\'s', Number)
+  .option('--to-decimals <n>', 'decimals of the output asset; defaults to the native asset\'s', Number)
+  .option('--resolver <name=url>', 'resolver RFQ endpoint to ask as well as the profile\'s (repeatable)', collectResolver)
+  .option('--no-skip', 'do not ask Skip Go for bridged routes')
+  .option('--taker <address>', 'your sending address, for sources that need it')
+  .option('--timeout <duration>', 'how long to wait for each source', '5s')
+  .option('--create <quote>', 'create the swap for this quote (row number or source name)')
+  .option('-b, --beneficiary <address>', 'resolver address to lock for, when its quote does not name one')
+  .option('-e, --expires-in <duration>', 'time until the lock can be refunded; defaults to the recommended timelock')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (options) => {
+    logger.heading('💱 Quotes');
+
+    try {
+      const from = parseQuoteAsset(options.from, options.fromDecimals);
+      const to = parseQuoteAsset(options.to, options.toDecimals);
+      const amountIn = Amount.parse(options.amount, from.decimals).base;
+      const sources = quoteSources({ resolvers: options.resolver, skip: options.skip });
+      if (sources.length === 0) throw new Error('No quote sources; add resolvers to the profile\'s [quotes] table, pass --resolver, or drop --no-skip');
+
+      spinner.start('quote', `Asking ${sources.map(source => source.name).join(', ')}...`);
+      const aggregator = new QuoteAggregator(sources, { timeoutMs: parseDuration(options.timeout) * 1000 });
+      const { quotes, errors } = await aggregator.quotes({ from, to, amountIn, taker: options.taker });
+      spinner.stop('quote');
+
+      const now = Math.floor(Date.now() / 1000);
+      const rows = quotes.map((quote, index) => ({
+        index: index + 1,
+        source: quote.source,
+        amountOut: Amount.fromBase(quote.amountOut, to.decimals).toDisplay(),
+        rate: quote.effectiveRate,
+        fees: quote.fees.map(fee => ({ label: fee.label, amount: fee.amount.toString(), asset: fee.asset, deducted: fee.deducted })),
+        settlementSeconds: settlementSeconds(quote),
+        expiresAt: quote.expiresAt,
+        quoteId: quote.quoteId
+      }));
+
+      let swap: { swapId: string; source: string; quoteId?: string; hashLock: string; timelock: number } | undefined;
+      if (options.create) {
+        const quote = pickQuote(quotes, options.create);
+        if (quote.source === 'skip') throw new Error('Skip routes are bridge transfers, not HTLC swaps; pick a resolver quote');
+        if (quote.expiresAt && quote.expiresAt <= now) throw new Error(`The ${quote.source} quote has expired; ask again`);
+        const beneficiary = options.beneficiary ?? quoteBeneficiary(quote);
+        if (!beneficiary) throw new Error(`The ${quote.source} quote names no address to lock for; pass --beneficiary`);
+
+        spinner.start('create', `Creating swap on ${from.chain} for ${quote.source}...`);
+        const created = await createSwap({
+          chain: from.chain,
+          beneficiary,
+          amount: amountIn.toString(),
+          token: isNativeAsset(from) ? undefined : from.asset,
+          durationSeconds: options.expiresIn
+            ? parseDuration(options.expiresIn)
+            : recommendTimelocks(from.chain, to.chain).sourceDurationSeconds,
+          key: options.key
+        });
+        spinner.succeed('create', 'Swap created');
+        swap = { swapId: created.swapId, source: quote.source, quoteId: quote.quoteId, hashLock: created.hashLock, timelock: created.timelock };
+      }
+
+      emit({ from, to, amountIn: amountIn.toString(), quotes: rows, errors, swap }, result => {
+        if (result.quotes.length === 0) logger.warn('No source quoted this pair');
+        else logger.table(result.quotes.map(row => ({
+          '#': row.index,
+          Source: row.source,
+          Receive: row.amountOut,
+          Rate: row.rate.toPrecision(6),
+          Fees: row.fees.map(fee => `${fee.label} ${fee.amount} ${fee.asset.asset}${fee.deducted ? '' : ' (extra)'}`).join(', ') || '-',
+          Settles: formatSeconds(row.settlementSeconds),
+          Expires: row.expiresAt ? (row.expiresAt > now ? `in ${formatSeconds(row.expiresAt - now)}` : 'expired') : '-'
+        })));
+        for (const { source, error } of result.errors) logger.warn(`${source}: ${error}`);
+
+        if (!result.swap) return;
+        logger.info(`Swap ID:   ${result.swap.swapId}`);
+        if (result.swap.quoteId) logger.info(`Quote ID:  ${result.swap.quoteId} (give it to ${result.swap.source} with the hash lock)`);
+        logger.info(`Hash lock: ${result.swap.hashLock}`);
+        logger.info(`Expires:   ${new Date(result.swap.timelock * 1000).toISOString()}`);
+        logger.info(`Fund it with: omni swap fund ${result.swap.swapId}`);
+      });
+    } catch (error) {
+      spinner.fail('quote', 'Quote failed');
+      spinner.fail('create', 'Failed to create swap');
+      exitWithError(error);
+    }
+  });
@@ -0,0 +1,79 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore } from '../../services/omni-store';
+import { requireChain } from '../../services/omni-chains';
+import { sweepRefunds } from '../../services/omni-refund';
+import type { RefundOutcome } from '../../services/omni-refund';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const refundAllCommand = new Command('refund-all')
+  .description('Refund every expired, still-open HTLC your key funded, batching the transactions')
+  .option('-c, --chain <chain>', 'chain to sweep (repeatable); defaults to the chains of unsettled local swaps', collect)
+  .option('--scan', 'also find HTLCs from chain history, not just swaps created here')
+  .option('--from-height <height>', 'first block to scan', Number)
+  .option('--blocks <count>', 'blocks to scan back from the head when --from-height is not given', Number, 10000)
+  .option('--any-sender', "also refund other senders' HTLCs, where the HTLC lets anyone refund (Cosmos only)")
+  .option('--dry-run', 'list what would be refunded without sending anything')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (options) => {
+    logger.heading(options.dryRun ? '🧹 Refund Sweep (dry run)' : '🧹 Refund Sweep');
+
+    try {
+      const store = new SwapStore();
+      const records = await store.list();
+      const unsettled = records.filter(record => record.contractId && !record.claimTx && !record.refundTx);
+      const chains: string[] = options.chain ?? [...new Set(unsettled.map(record => record.chain))];
+      chains.forEach(chain => requireChain(chain));
+      if (chains.length === 0) {
+        if (options.scan) throw new Error('No unsettled local swaps to pick chains from; pass --chain with --scan');
+        emit({ dryRun: !!options.dryRun, refunds: [] }, () => logger.info('No unsettled swaps to refund'));
+        return;
+      }
+
+      const refunds: RefundOutcome[] = [];
+      for (const chain of chains) {
+        spinner.start('refund-all', `${options.scan ? 'Scanning' : 'Checking'} ${chain}...`);
+        const outcomes = await sweepRefunds(chain, {
+          key: options.key,
+          records,
+          scan: options.scan ? { fromHeight: options.fromHeight, blocks: options.blocks } : undefined,
+          anySender: options.anySender,
+          dryRun: options.dryRun
+        });
+        spinner.stop('refund-all');
+        refunds.push(...outcomes);
+
+        for (const outcome of outcomes) {
+          const record = outcome.swapId && records.find(candidate => candidate.swapId === outcome.swapId);
+          if (record && outcome.txHash && !outcome.error) await store.put({ ...record, refundTx: outcome.txHash });
+        }
+      }
+
+      const failed = refunds.filter(refund => refund.error);
+      emit({ dryRun: !!options.dryRun, refunds }, result => {
+        if (result.refunds.length === 0) {
+          logger.info('Nothing to refund: no expired open HTLCs from your key');
+          return;
+        }
+        logger.table(result.refunds.map(refund => ({
+          Chain: refund.chain,
+          HTLC: refund.contractId,
+          Amount: refund.value,
+          Expired: new Date(refund.timelock * 1000).toISOString(),
+          Result: result.dryRun ? 'would refund' : refund.error ? `failed: ${refund.error}` : refund.txHash
+        })));
+        if (result.dryRun) logger.info(`${result.refunds.length} HTLCs would be refunded; run again without --dry-run to send`);
+        else if (failed.length) logger.warn(`${failed.length} of ${result.refunds.length} refunds failed`);
+        else logger.success(`Refunded ${result.refunds.length} HTLCs`);
+      });
+      if (failed.length) process.exit(1);
+    } catch (error) {
+      spinner.fail('refund-all', 'Refund sweep failed');
+      exitWithError(error);
+    }
+  });
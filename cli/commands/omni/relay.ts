@@ -0,0 +1,68 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { parseDuration } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { readToml } from '../../services/omni-config';
+import { SelfRelay } from '../../services/omni-relay';
+import type { RelayChainConfig, RelayConfig } from '../../services/omni-relay';
+
+export const relayCommand = new Command('relay')
+  .description('Relay your own swaps without the full resolver stack');
+
+/**
+ * relay.toml:
+ *
+ *   poll_interval = "10s"
+ *   claim = true
+ *   refund = true
+ *
+ *   [chains.sepolia]
+ *   key = "alice-eth"
+ *   from_height = 6500000
+ *
+ *   [chains.osmosisTestnet]
+ *   key = "alice-osmo"
+ */
+async function loadRelayConfig(file: string): Promise<RelayConfig> {
+  const raw = await readToml<{ pollInterval?: string | number; claim?: boolean; refund?: boolean; chains?: Record<string, RelayChainConfig> }>(file);
+  if (!raw.chains || Object.keys(raw.chains).length < 2) {
+    throw new Error(`${file} must list at least two [chains.<name>] tables`);
+  }
+  return {
+    chains: raw.chains,
+    pollIntervalSeconds: raw.pollInterval !== undefined ? parseDuration(String(raw.pollInterval)) : undefined,
+    claim: raw.claim,
+    refund: raw.refund
+  };
+}
+
+relayCommand
+  .command('run')
+  .description('Claim HTLCs paying you as their preimages are revealed, and refund your expired ones')
+  .requiredOption('-c, --config <file>', 'relay config (TOML)')
+  .action(async (options) => {
+    logger.heading('📡 Self-relay');
+
+    let relay: SelfRelay | undefined;
+    try {
+      const config = await loadRelayConfig(options.config);
+      relay = new SelfRelay(config);
+      relay.on('claimed', event => emit({ event: 'claimed', ...event }, e => logger.success(`Claimed ${e.contractId} on ${e.chain} (${e.txHash})`)));
+      relay.on('refunded', event => emit({ event: 'refunded', ...event }, e => logger.success(`Refunded ${e.contractId} on ${e.chain} (${e.txHash})`)));
+      relay.on('error', ({ chain, contractId, error }) => {
+        emit({ event: 'error', chain, contractId, message: (error as Error).message }, e => logger.warn(`${e.chain} ${e.contractId}: ${e.message}`));
+      });
+
+      await relay.start();
+      logger.info(`Relaying on ${Object.keys(config.chains).join(', ')}; Ctrl-C to stop`);
+    } catch (error) {
+      relay?.stop();
+      exitWithError(error);
+    }
+
+    process.once('SIGINT', () => {
+      logger.info(`Stopping; ${relay!.tracked().length} HTLC(s) still open`);
+      relay!.stop();
+      process.exit(0);
+    });
+  });
@@ -0,0 +1,108 @@
+import { Command } from 'commander';
+import { promises as fs } from 'fs';
+import { extname } from 'path';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { parseWhen } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { requireChain } from '../../services/omni-chains';
+import { chainsForAddress, indexerUrl, queryIndexer, scanChains } from '../../services/omni-history';
+import type { HistoryEntry, HistoryQuery } from '../../services/omni-history';
+import { reportCsv, reportRows, reportTotals, writeReportParquet } from '../../services/omni-report';
+import type { ReportRow } from '../../services/omni-report';
+
+const FORMATS = ['csv', 'parquet'];
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const reportCommand = new Command('report')
+  .description('Settlement report per address: amounts in and out per asset, fees paid, times and tx hashes, as CSV or Parquet')
+  .argument('<address...>', 'EVM or bech32 addresses')
+  .option('-c, --chain <chain>', 'chain to report on (repeatable); defaults to every chain each address can be on', collect)
+  .option('--since <when>', 'funded at or after: ISO date, or a duration ago such as 30d')
+  .option('--until <when>', 'funded at or before: ISO date, or a duration ago')
+  .option('-f, --format <format>', `${FORMATS.join(' or ')}; defaults to the --out extension, else csv`)
+  .option('--out <file>', 'file to write; CSV goes to stdout when omitted')
+  .option('--no-indexer', 'scan the chains even when an indexer is configured')
+  .option('--from-height <height>', 'first block to scan', Number)
+  .option('--blocks <count>', 'blocks to scan back from the head when --from-height is not given', Number, 10000)
+  .action(async (addresses: string[], options) => {
+    try {
+      const format: string = options.format ?? (options.out && extname(options.out) === '.parquet' ? 'parquet' : 'csv');
+      if (!FORMATS.includes(format)) throw new Error(`Unknown format "${format}"; expected ${FORMATS.join(' or ')}`);
+      if (format === 'parquet' && !options.out) throw new Error('Parquet is binary; pass --out <file>');
+
+      const queries: HistoryQuery[] = addresses.map(address => {
+        const chains: string[] = options.chain ?? chainsForAddress(address);
+        chains.forEach(chain => requireChain(chain));
+        if (chains.length === 0) throw new Error(`No configured chain uses addresses like ${address}; pass --chain`);
+        return {
+          address,
+          chains,
+          since: options.since ? parseWhen(options.since) : undefined,
+          until: options.until ? parseWhen(options.until) : undefined
+        };
+      });
+
+      // A CSV on stdout must not be mixed with progress output
+      const toStdout = !options.out;
+      if (!toStdout) logger.heading('📊 Settlement Report');
+
+      const url = options.indexer ? indexerUrl() : undefined;
+      const rows: ReportRow[] = [];
+      for (const query of queries) {
+        let entries: HistoryEntry[] | undefined;
+        if (url) {
+          if (!toStdout) spinner.start('report', `Querying ${url} for ${query.address}...`);
+          try {
+            entries = await queryIndexer(url, query);
+          } catch (error) {
+            spinner.warn('report', `Indexer unavailable (${(error as Error).message}); scanning the chains instead`);
+          }
+        }
+        if (!entries) {
+          if (!toStdout) spinner.start('report', 'Scanning chains...');
+          entries = await scanChains(query, { fromHeight: options.fromHeight, blocks: options.blocks }, (chain, from, to) =>
+            spinner.update('report', `Scanning ${chain} blocks ${from}..${to} for ${query.address}...`)
+          );
+        }
+        spinner.update('report', `Reading fees and block times for ${query.address}...`);
+        rows.push(...await reportRows(query.address, entries));
+        spinner.stop('report');
+      }
+
+      if (toStdout) {
+        process.stdout.write(reportCsv(rows));
+        return;
+      }
+      if (format === 'parquet') {
+        await writeReportParquet(options.out, rows);
+      } else {
+        await fs.writeFile(options.out, reportCsv(rows));
+      }
+
+      const totals = reportTotals(rows).map(total => ({
+        ...total,
+        amountIn: total.amountIn.toString(),
+        amountOut: total.amountOut.toString(),
+        fees: total.fees.toString()
+      }));
+      emit({ file: options.out, format, rows: rows.length, totals }, result => {
+        logger.success(`Wrote ${result.rows} rows to ${result.file}`);
+        if (result.totals.length === 0) return;
+        logger.table(result.totals.map(total => ({
+          Address: total.address,
+          Chain: total.chain,
+          Asset: total.asset,
+          In: total.amountIn,
+          Out: total.amountOut,
+          Fees: total.fees
+        })));
+      });
+    } catch (error) {
+      spinner.fail('report', 'Report failed');
+      exitWithError(error);
+    }
+  });
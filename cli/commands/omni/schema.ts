@@ -0,0 +1,39 @@
+import { Command } from 'commander';
+import fs from 'fs';
+import path from 'path';
+import { logger } from '../../utils/logger';
+import { exitWithError } from '../../utils/output';
+import { HTLC_CONTRACT_VERSION, htlcContractSchema, htlcSchemaFiles } from '../../../packages/shared/src/sdk';
+
+export const schemaCommand = new Command('schema')
+  .description(`Print or write the HTLC contract's JSON schemas (contract ${HTLC_CONTRACT_VERSION})`)
+  .option('-m, --message <name>', 'only one schema: instantiate, execute, query or response_to_<query>')
+  .option('--out <dir>', 'write <dir>/<version>/htlc.json and raw/*.json instead of printing')
+  .action(async (options) => {
+    try {
+      if (options.out) {
+        const out = path.join(options.out, HTLC_CONTRACT_VERSION);
+        fs.mkdirSync(path.join(out, 'raw'), { recursive: true });
+        fs.writeFileSync(path.join(out, 'htlc.json'), JSON.stringify(htlcContractSchema(), null, 2) + '\n');
+        for (const [name, schema] of Object.entries(htlcSchemaFiles())) {
+          fs.writeFileSync(path.join(out, 'raw', name), JSON.stringify(schema, null, 2) + '\n');
+        }
+        logger.success(`Wrote HTLC ${HTLC_CONTRACT_VERSION} schemas to ${out}`);
+        return;
+      }
+
+      // Schemas are JSON whatever the output mode
+      if (options.message) {
+        const files = htlcSchemaFiles();
+        const schema = files[`${options.message}.json`];
+        if (!schema) {
+          throw new Error(`Unknown schema ${options.message}; expected ${Object.keys(files).map(name => name.replace(/\.json$/, '')).join(', ')}`);
+        }
+        console.log(JSON.stringify(schema, null, 2));
+      } else {
+        console.log(JSON.stringify(htlcContractSchema(), null, 2));
+      }
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
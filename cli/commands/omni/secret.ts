@@ -0,0 +1,115 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { emit, exitWithError } from '../../utils/output';
+import { openVault } from '../../services/omni-store';
+import { HASH_ALGORITHMS, Secret, hashPreimage } from '../../../packages/shared/src/sdk';
+import type { HashAlgorithm } from '../../../packages/shared/src/sdk';
+
+type Format = 'hex' | 'base64' | 'binary';
+const FORMATS: Format[] = ['hex', 'base64', 'binary'];
+
+export const secretCommand = new Command('secret')
+  .description('Generate swap secrets and check preimages against hashlocks');
+
+/**
+ * Vault entry for a secret generated outside a swap, keyed by its hashlock
+ */
+export function secretVaultId(algorithm: HashAlgorithm, hashLock: string): string {
+  return `secret:${algorithm}:${hashLock.toLowerCase()}`;
+}
+
+function encode(bytes: Uint8Array, format: Format): string {
+  const buffer = Buffer.from(bytes);
+  switch (format) {
+    case 'hex': return `0x${buffer.toString('hex')}`;
+    case 'base64': return buffer.toString('base64');
+    case 'binary': return Array.from(buffer, byte => byte.toString(2).padStart(8, '0')).join('');
+  }
+}
+
+function decode(value: string, format: Format): Uint8Array {
+  switch (format) {
+    case 'hex': return Buffer.from(value.replace(/^0x/, ''), 'hex');
+    case 'base64': return Buffer.from(value, 'base64');
+    case 'binary': {
+      if (!/^[01]*$/.test(value) || value.length % 8 !== 0) throw new Error('Binary input must be a whole number of 8-bit groups');
+      return Uint8Array.from(value.match(/.{8}/g) ?? [], bits => parseInt(bits, 2));
+    }
+  }
+}
+
+function parseOption<T extends string>(value: string, allowed: readonly T[], name: string): T {
+  if (!allowed.includes(value as T)) {
+    throw new Error(`Unknown ${name} "${value}"; expected ${allowed.join(', ')}`);
+  }
+  return value as T;
+}
+
+secretCommand
+  .command('gen')
+  .description('Generate preimages and their hashlocks, storing them in the vault')
+  .option('-a, --algo <algorithm>', `hashlock algorithm (${HASH_ALGORITHMS.join(', ')})`, 'sha256')
+  .option('-n, --count <count>', 'number of secrets', '1')
+  .option('-f, --format <format>', `output encoding (${FORMATS.join(', ')})`, 'hex')
+  .option('--no-store', 'print only; do not store the secrets in the vault')
+  .action(async (options) => {
+    try {
+      const algorithm = parseOption(options.algo, HASH_ALGORITHMS, 'algorithm');
+      const format = parseOption(options.format, FORMATS, 'format');
+      const count = Number(options.count);
+      if (!Number.isInteger(count) || count < 1) throw new Error('--count must be a positive integer');
+
+      const vault = options.store ? await openVault() : null;
+      const rows: { preimage: string; hashLock: string }[] = [];
+      try {
+        for (let i = 0; i < count; i++) {
+          const secret = Secret.generate();
+          const bytes = secret.toBytes();
+          const hashLock = hashPreimage(bytes, algorithm);
+          if (vault) await vault.put(secretVaultId(algorithm, encode(hashLock, 'hex')), secret);
+          rows.push({ preimage: encode(bytes, format), hashLock: encode(hashLock, format) });
+          bytes.fill(0);
+          secret.zeroize();
+        }
+      } finally {
+        vault?.close();
+      }
+
+      emit({ algorithm, format, stored: !!vault, secrets: rows }, result => {
+        logger.heading(`🔑 ${count} ${algorithm} secret${count === 1 ? '' : 's'}`);
+        result.secrets.forEach(row => {
+          logger.info(`preimage: ${row.preimage}`);
+          logger.info(`hashlock: ${row.hashLock}`);
+        });
+        if (result.stored) logger.success('Stored in the vault');
+      });
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+secretCommand
+  .command('verify')
+  .description('Check that a preimage opens a hashlock')
+  .argument('<preimage>', 'preimage')
+  .argument('<hashlock>', 'hashlock')
+  .option('-a, --algo <algorithm>', 'hashlock algorithm; every known one is tried when omitted')
+  .option('-f, --format <format>', `encoding of both values (${FORMATS.join(', ')})`, 'hex')
+  .action(async (preimageArg: string, hashLockArg: string, options) => {
+    try {
+      const format = parseOption(options.format, FORMATS, 'format');
+      const algorithms = options.algo ? [parseOption(options.algo, HASH_ALGORITHMS, 'algorithm')] : HASH_ALGORITHMS;
+      const preimage = decode(preimageArg, format);
+      const expected = Buffer.from(decode(hashLockArg, format));
+
+      const match = algorithms.find(algorithm => expected.equals(Buffer.from(hashPreimage(preimage, algorithm))));
+      preimage.fill(0);
+      emit({ valid: !!match, algorithm: match ?? null }, result => {
+        if (result.valid) logger.success(`Preimage matches the hashlock (${result.algorithm})`);
+        else logger.error(`Preimage does not match the hashlock under ${algorithms.join(', ')}`);
+      });
+      if (!match) process.exit(1);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
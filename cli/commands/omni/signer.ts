@@ -0,0 +1,72 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { emit, exitWithError } from '../../utils/output';
+import { remoteSignerCredentials, startRemoteSignerServer } from '../../../packages/shared/src/clients/remote-signer';
+import type { RemoteSignerKey } from '../../../packages/shared/src/clients/remote-signer';
+import { KeyStore } from '../../services/omni-keys';
+import { requireChain } from '../../services/omni-chains';
+import { remoteSigner, remoteSignerSettings } from '../../services/omni-signer';
+
+function collect(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(',')];
+}
+
+export const signerCommand = new Command('signer')
+  .description('Keep keys on a separate host and sign over an authenticated gRPC connection');
+
+signerCommand
+  .command('serve')
+  .description('Serve keyring keys to omni and the resolver over mutual TLS; the keys never leave this host')
+  .requiredOption('-k, --key <name>', 'keyring key to serve (repeatable); clients ask for it by this name', collect)
+  .option('--listen <address>', 'host:port to listen on', '0.0.0.0:26659')
+  .option('--chain-id <id>', 'only sign for this chain ID (repeatable); default any', collect)
+  .option('--tls-ca <file>', 'CA that client certificates must be issued by')
+  .option('--tls-cert <file>', 'server certificate')
+  .option('--tls-key <file>', 'server private key')
+  .option('--token-env <name>', 'environment variable with a bearer token clients must also send', 'OMNI_SIGNER_TOKEN')
+  .option('--insecure', 'no TLS; only allowed when listening on a loopback address')
+  .action(async (options) => {
+    try {
+      const keyring = await KeyStore.open();
+      const keys: Record<string, RemoteSignerKey> = {};
+      for (const name of options.key as string[]) keys[name] = await keyring.secret(name);
+
+      await startRemoteSignerServer({
+        listen: options.listen,
+        keys,
+        chainIds: options.chainId,
+        caFile: options.tlsCa,
+        certFile: options.tlsCert,
+        keyFile: options.tlsKey,
+        token: process.env[options.tokenEnv] || undefined,
+        insecure: options.insecure,
+        onRequest: entry => emit({ at: new Date().toISOString(), ...entry }, request => {
+          const line = `${request.method} ${request.keyId} on ${request.chainId}`;
+          if (request.error) logger.warn(`${line}: refused, ${request.error}`);
+          else logger.info(line);
+        })
+      });
+      logger.success(`Remote signer listening on ${options.listen} with ${Object.keys(keys).join(', ')}`);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+signerCommand
+  .command('address')
+  .description('Show the address a key on the profile\'s remote signer has on a chain')
+  .argument('<key>', 'key name on the remote signer')
+  .requiredOption('-c, --chain <chain>', 'chain whose address format to use')
+  .action(async (key: string, options) => {
+    try {
+      const settings = remoteSignerSettings();
+      if (!settings) throw new Error('The active profile has no remote_signer');
+      const credentials = await remoteSignerCredentials(requireChain(options.chain), await remoteSigner(settings), key);
+      const address = credentials.evmSigner
+        ? credentials.evmSigner.address
+        : (await credentials.cosmosSigner!.getAccounts())[0].address;
+      emit({ chain: options.chain, key, address }, shown => logger.success(`${shown.key} on ${shown.chain}: ${shown.address}`));
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
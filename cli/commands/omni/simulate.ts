@@ -0,0 +1,164 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { parseDuration } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore, openVault } from '../../services/omni-store';
+import { requireChain, resolveHtlc, withChain } from '../../services/omni-chains';
+import { simulate } from '../../services/omni-simulate';
+import type { Simulation } from '../../services/omni-simulate';
+import { Secret, deriveSwapId } from '../../../packages/shared/src/sdk';
+import type { FeeCall } from '../../../packages/shared/src/sdk';
+
+export const simulateCommand = new Command('simulate')
+  .description('Dry-run a swap action and show its gas, fee and events without broadcasting');
+
+const store = new SwapStore();
+
+async function run(chain: string, call: FeeCall, key?: string): Promise<void> {
+  spinner.start('simulate', `Simulating ${call.action} on ${chain}...`);
+  let result: Simulation;
+  try {
+    result = await simulate(chain, call, key);
+  } catch (error) {
+    spinner.fail('simulate', 'The call would fail');
+    exitWithError(error);
+  }
+  spinner.succeed('simulate', 'Simulation succeeded; nothing was broadcast');
+
+  const config = requireChain(chain);
+  emit(result, ({ fee, events, sender }) => {
+    logger.info(`Sender:    ${sender}`);
+    logger.info(`Gas used:  ${fee.gasUsed}`);
+    logger.info(`Gas limit: ${fee.gasLimit}`);
+    if (fee.type === 'cosmos') {
+      logger.info(`Fee:       ${fee.formatted} (${fee.amount} ${fee.denom})`);
+    } else {
+      logger.info(`Max fee:   ${fee.formatted} ${config.nativeDenom ?? 'in the native token'} (${fee.maxCost} wei)`);
+      if (fee.l1DataFee > 0n) logger.info(`L1 data:   ${fee.l1DataFee} wei`);
+    }
+    logger.table(events.map(event => ({
+      Event: event.name,
+      Attributes: Object.entries(event.attributes).map(([name, value]) => `${name}=${value}`).join(' ')
+    })));
+  });
+}
+
+simulateCommand
+  .command('create')
+  .description('Simulate creating and funding a new swap, as "swap create" then "swap fund" would')
+  .requiredOption('-c, --chain <chain>', 'chain to lock on (registry key, e.g. sepolia)')
+  .requiredOption('-b, --beneficiary <address>', 'address that can claim with the secret')
+  .requiredOption('-a, --amount <amount>', 'amount in base units')
+  .option('-t, --token <address>', 'ERC-20 / CW20 token; omit for the native asset')
+  .option('-e, --expires-in <duration>', 'time until the lock can be refunded', '1h')
+  .option('--hashlock <hex>', 'hashlock to lock under; defaults to a throwaway one')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (options) => {
+    logger.heading('🧪 Simulate Create');
+
+    try {
+      const config = requireChain(options.chain);
+      const { originator, now } = await withChain(options.chain, { sign: true, key: options.key }, async chain => ({
+        originator: chain.address!,
+        now: await chain.currentTime()
+      }));
+
+      // Nothing is stored, so the secret behind a generated hashlock is never needed
+      const secret = options.hashlock ? null : Secret.generate();
+      const hashLock: string = options.hashlock ?? secret!.hashLock();
+      secret?.zeroize();
+      const timelock = now + parseDuration(options.expiresIn);
+      const contractId = deriveSwapId({
+        chainId: config.chainId,
+        hashLock,
+        originator,
+        beneficiary: options.beneficiary,
+        token: options.token,
+        amount: options.amount,
+        timelock
+      });
+
+      await run(options.chain, {
+        action: 'fund',
+        params: { contractId, beneficiary: options.beneficiary, hashLock, timelock, value: options.amount, token: options.token }
+      }, options.key);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+simulateCommand
+  .command('fund')
+  .description('Simulate funding a swap created with "swap create"')
+  .argument('<swap-id>', 'swap ID')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (swapId: string, options) => {
+    logger.heading('🧪 Simulate Fund');
+
+    try {
+      const record = await store.find(swapId);
+      if (!record) throw new Error(`Unknown swap ${swapId}`);
+      if (record.contractId) throw new Error(`Swap already funded as ${record.contractId} (tx ${record.fundTx})`);
+
+      await run(record.chain, {
+        action: 'fund',
+        params: {
+          contractId: record.swapId,
+          beneficiary: record.beneficiary,
+          hashLock: record.hashLock,
+          timelock: record.timelock,
+          value: record.amount,
+          token: record.token
+        }
+      }, options.key);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+simulateCommand
+  .command('claim')
+  .description('Simulate claiming an HTLC with its preimage')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .option('-p, --preimage <hex>', 'preimage; defaults to the secret stored for this swap')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (id: string, options) => {
+    logger.heading('🧪 Simulate Claim');
+
+    try {
+      const { chain, contractId, record } = await resolveHtlc(store, id, options.chain);
+      let preimage: string | undefined = options.preimage;
+      if (!preimage && record) {
+        const vault = await openVault();
+        const secret = vault.get(record.swapId);
+        vault.close();
+        preimage = secret?.hex();
+        secret?.zeroize();
+      }
+      // The contracts reject a claim with the wrong preimage, so there is nothing to estimate without it
+      if (!preimage) throw new Error('No preimage given and none stored for this swap; pass --preimage');
+
+      await run(chain, { action: 'claim', contractId, preimage }, options.key);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
+
+simulateCommand
+  .command('refund')
+  .description('Simulate refunding an expired HTLC')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (id: string, options) => {
+    logger.heading('🧪 Simulate Refund');
+
+    try {
+      const { chain, contractId } = await resolveHtlc(store, id, options.chain);
+      await run(chain, { action: 'refund', contractId }, options.key);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
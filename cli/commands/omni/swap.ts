@@ -0,0 +1,194 @@
+import { Command } from 'commander';
+import { logger } from '../../utils/logger';
+import { spinner } from '../../utils/spinner';
+import { parseDuration } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore, openVault } from '../../services/omni-store';
+import { createSwap } from '../../services/omni-swap';
+import { htlcToOrder, resolveHtlc, withChain } from '../../services/omni-chains';
+import { describeSwap } from '../../../packages/shared/src/sdk';
+import { SwapState } from '../../../packages/shared/src/utils/htlc';
+
+export const swapCommand = new Command('swap')
+  .description('Create, fund, claim, refund and inspect HTLC swaps');
+
+const store = new SwapStore();
+
+function fail(id: string, text: string, error: unknown): never {
+  spinner.fail(id, text);
+  exitWithError(error);
+}
+
+swapCommand
+  .command('create')
+  .description('Generate a secret and record a new swap, ready to fund')
+  .requiredOption('-c, --chain <chain>', 'chain to lock on (registry key, e.g. sepolia)')
+  .requiredOption('-b, --beneficiary <address>', 'address that can claim with the secret')
+  .requiredOption('-a, --amount <amount>', 'amount in base units')
+  .option('-t, --token <address>', 'ERC-20 / CW20 token; omit for the native asset')
+  .option('-e, --expires-in <duration>', 'time until the lock can be refunded', '1h')
+  .option('--hashlock <hex>', "lock under the counterparty's hashlock instead of a new secret")
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (options) => {
+    logger.heading('🔐 Create Swap');
+
+    try {
+      spinner.start('create', `Preparing swap on ${options.chain}...`);
+      const { swapId, hashLock, timelock, secretStored } = await createSwap({
+        chain: options.chain,
+        beneficiary: options.beneficiary,
+        amount: options.amount,
+        token: options.token,
+        durationSeconds: parseDuration(options.expiresIn),
+        hashLock: options.hashlock,
+        key: options.key
+      }, store);
+      spinner.succeed('create', 'Swap created');
+
+      emit({ swapId, chain: options.chain, hashLock, timelock, secretStored }, result => {
+        logger.info(`Swap ID:   ${result.swapId}`);
+        logger.info(`Hash lock: ${result.hashLock}`);
+        logger.info(`Expires:   ${new Date(result.timelock * 1000).toISOString()}`);
+        logger.info(`Fund it with: omni swap fund ${result.swapId}`);
+      });
+    } catch (error) {
+      fail('create', 'Failed to create swap', error);
+    }
+  });
+
+swapCommand
+  .command('fund')
+  .description('Lock the funds for a swap created with "swap create"')
+  .argument('<swap-id>', 'swap ID')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (swapId: string, options) => {
+    logger.heading('💰 Fund Swap');
+
+    try {
+      const record = await store.find(swapId);
+      if (!record) throw new Error(`Unknown swap ${swapId}`);
+      if (record.contractId) throw new Error(`Swap already funded as ${record.contractId} (tx ${record.fundTx})`);
+      if (record.cancelledAt) throw new Error(`Swap ${record.swapId} was cancelled; create a new one`);
+
+      spinner.start('fund', `Locking ${record.amount} on ${record.chain}...`);
+      const result = await withChain(record.chain, { sign: true, key: options.key }, chain => chain.lock({
+        contractId: record.swapId,
+        beneficiary: record.beneficiary,
+        hashLock: record.hashLock,
+        timelock: record.timelock,
+        amount: record.amount,
+        token: record.token
+      }));
+      await store.put({ ...record, contractId: result.contractId, fundTx: result.txHash });
+      spinner.succeed('fund', 'Swap funded');
+
+      emit({ swapId: record.swapId, chain: record.chain, contractId: result.contractId, txHash: result.txHash }, funded => {
+        logger.success(`HTLC: ${funded.contractId}`);
+        logger.info(`Transaction: ${funded.txHash}`);
+      });
+    } catch (error) {
+      fail('fund', 'Funding failed', error);
+    }
+  });
+
+swapCommand
+  .command('claim')
+  .description('Claim an HTLC with its preimage')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .option('-p, --preimage <hex>', 'preimage; defaults to the secret stored for this swap')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (id: string, options) => {
+    logger.heading('🔓 Claim Swap');
+
+    try {
+      const { chain, contractId, record } = await resolveHtlc(store, id, options.chain);
+      let preimage: string | undefined = options.preimage;
+      if (!preimage && record) {
+        const vault = await openVault();
+        const secret = vault.get(record.swapId);
+        vault.close();
+        preimage = secret?.hex();
+        secret?.zeroize();
+      }
+      if (!preimage) throw new Error('No preimage given and none stored for this swap; pass --preimage');
+
+      spinner.start('claim', `Claiming ${contractId} on ${chain}...`);
+      const result = await withChain(chain, { sign: true, key: options.key }, swapChain => swapChain.claim(contractId, preimage!));
+      if (record) await store.put({ ...record, claimTx: result.txHash });
+      spinner.succeed('claim', 'Swap claimed');
+
+      emit({ chain, contractId, txHash: result.txHash }, claimed => logger.info(`Transaction: ${claimed.txHash}`));
+    } catch (error) {
+      fail('claim', 'Claim failed', error);
+    }
+  });
+
+swapCommand
+  .command('refund')
+  .description('Refund an expired HTLC to its originator')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .option('-k, --key <name>', 'keyring key to sign with')
+  .action(async (id: string, options) => {
+    logger.heading('↩️  Refund Swap');
+
+    try {
+      const { chain, contractId, record } = await resolveHtlc(store, id, options.chain);
+      spinner.start('refund', `Refunding ${contractId} on ${chain}...`);
+      const result = await withChain(chain, { sign: true, key: options.key }, swapChain => swapChain.refund(contractId));
+      if (record) await store.put({ ...record, refundTx: result.txHash });
+      spinner.succeed('refund', 'Swap refunded');
+
+      emit({ chain, contractId, txHash: result.txHash }, refunded => logger.info(`Transaction: ${refunded.txHash}`));
+    } catch (error) {
+      fail('refund', 'Refund failed', error);
+    }
+  });
+
+swapCommand
+  .command('status')
+  .description('Show the on-chain state of a swap')
+  .argument('<swap-id>', 'swap ID or HTLC ID')
+  .option('-c, --chain <chain>', 'chain the HTLC is on, for swaps not created here')
+  .action(async (id: string, options) => {
+    logger.heading('📊 Swap Status');
+
+    try {
+      const record = await store.find(id);
+      if (record && !record.contractId) {
+        const state = record.cancelledAt ? 'CANCELLED' : 'UNFUNDED';
+        emit({ swapId: record.swapId, chain: record.chain, state }, () => {
+          logger.info(record.cancelledAt ? `Swap ${record.swapId} was cancelled before funding` : `Swap ${record.swapId} is created but not funded`);
+        });
+        return;
+      }
+
+      const { chain, contractId } = await resolveHtlc(store, id, options.chain);
+      spinner.start('status', `Reading ${contractId} on ${chain}...`);
+      const { htlc, now } = await withChain(chain, {}, async swapChain => ({
+        htlc: await swapChain.getHtlc(contractId),
+        now: await swapChain.currentTime()
+      }));
+      spinner.stop('status');
+
+      if (!htlc || htlc.state === SwapState.INVALID) {
+        exitWithError(new Error(`No HTLC ${contractId} on ${chain}`));
+      }
+
+      const order = htlcToOrder(chain, htlc);
+      emit({ ...order, state: SwapState[htlc.state], expired: now > htlc.timelock, summary: describeSwap(order, { now }) }, status => {
+        logger.info(status.summary);
+        logger.table([{
+          Chain: status.chain,
+          HTLC: status.contractId,
+          State: status.state,
+          Amount: status.amount,
+          Expires: new Date(status.timelock * 1000).toISOString(),
+          Expired: status.expired ? 'yes' : 'no'
+        }]);
+      });
+    } catch (error) {
+      fail('status', 'Status lookup failed', error);
+    }
+  });
@@ -0,0 +1,162 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { emit, exitWithError, isJsonOutput } from '../../utils/output';
+import { parseDuration } from '../../utils/duration';
+import { SwapStore } from '../../services/omni-store';
+import { activeProfile } from '../../services/omni-config';
+import { indexerUrl } from '../../services/omni-history';
+import { dashboardAddresses, loadDashboard, relayerUrl } from '../../services/omni-dashboard';
+import type { Dashboard, DashboardState } from '../../services/omni-dashboard';
+
+const ANSI = /\x1b\[[0-9;]*m/g;
+
+function width(text: string): number {
+  return text.replace(ANSI, '').length;
+}
+
+/** Left-aligned columns, sized to their widest cell; cells may carry colors */
+function grid(headers: string[], rows: string[][]): string[] {
+  const widths = headers.map((header, i) => Math.max(header.length, ...rows.map(row => width(row[i]))));
+  const line = (cells: string[]) => cells.map((cell, i) => cell + ' '.repeat(widths[i] - width(cell))).join('  ');
+  return [chalk.bold(line(headers)), ...rows.map(line)];
+}
+
+function short(id: string): string {
+  return id.length > 16 ? `${id.slice(0, 10)}…${id.slice(-4)}` : id;
+}
+
+function remaining(seconds: number): string {
+  const abs = Math.abs(seconds);
+  const text = abs >= 3600 ? `${Math.floor(abs / 3600)}h ${String(Math.floor(abs % 3600 / 60)).padStart(2, '0')}m`
+    : abs >= 60 ? `${Math.floor(abs / 60)}m ${String(abs % 60).padStart(2, '0')}s`
+    : `${abs}s`;
+  if (seconds < 0) return chalk.red(`${text} ago`);
+  return seconds < 600 ? chalk.yellow(text) : text;
+}
+
+function colorState(state: DashboardState): string {
+  switch (state) {
+    case 'claimed': return chalk.green(state);
+    case 'refunded': return chalk.yellow(state);
+    case 'expired': return chalk.red(state);
+    case 'unfunded':
+    case 'unknown': return chalk.gray(state);
+    default: return chalk.cyan(state);
+  }
+}
+
+/** The whole screen for one snapshot, with countdowns as of `now` */
+function draw(dashboard: Dashboard | undefined, now: number, intervalLabel: string): string {
+  const profile = activeProfile()?.name;
+  const lines = [
+    `${chalk.bold.cyan('omni')}${profile ? ` · ${profile}` : ''} · ${new Date(now * 1000).toLocaleTimeString()}`
+    + chalk.gray(`   ${dashboard ? `refreshed ${now - dashboard.at}s ago, every ${intervalLabel}` : 'loading…'}   q to quit`),
+    ''
+  ];
+  if (!dashboard) return lines.join('\n');
+
+  const rowsLeft = (process.stdout.rows || 40) - 16 - (dashboard.relayer?.length ?? 1) - dashboard.settlements.length;
+  const shown = dashboard.swaps.slice(0, Math.max(3, rowsLeft));
+  lines.push(chalk.bold(`Open swaps (${dashboard.swaps.length})`));
+  lines.push(...(shown.length ? grid(
+    ['Swap', 'Chain', 'HTLC', 'Amount', 'State', 'Expires in', 'Counterpart'],
+    shown.map(swap => [
+      short(swap.swapId),
+      swap.chain,
+      swap.contractId ? short(swap.contractId) : '-',
+      swap.amount,
+      colorState(swap.state),
+      remaining(swap.timelock - now),
+      swap.counterpart ? `${swap.counterpart.chain} ${colorState(swap.counterpart.state)}` : chalk.gray('-')
+    ])
+  ) : [chalk.gray('none')]));
+  if (shown.length < dashboard.swaps.length) lines.push(chalk.gray(`… ${dashboard.swaps.length - shown.length} more`));
+
+  lines.push('', chalk.bold('Relayer'));
+  if (!dashboard.relayer) {
+    lines.push(chalk.gray('no relayer_url / OMNI_RELAYER_URL set'));
+  } else {
+    lines.push(...grid(['Chain', 'Queue', 'Loop'], dashboard.relayer.map(queue => [
+      queue.chain,
+      queue.queueSize > 0 ? chalk.yellow(String(queue.queueSize)) : '0',
+      queue.draining ? chalk.yellow('draining') : queue.processing ? chalk.cyan('processing') : 'idle'
+    ])));
+  }
+
+  lines.push('', chalk.bold('Recent settlements'));
+  lines.push(...(dashboard.settlements.length ? grid(
+    ['Chain', 'HTLC', 'Outcome', 'Amount', 'Tx'],
+    dashboard.settlements.map(settlement => [
+      settlement.chain,
+      short(settlement.contractId),
+      colorState(settlement.state),
+      settlement.value,
+      settlement.txHash ? short(settlement.txHash) : '-'
+    ])
+  ) : [chalk.gray('none')]));
+
+  if (dashboard.errors.length) lines.push('', ...dashboard.errors.map(error => chalk.red(`✘ ${error}`)));
+  return lines.join('\n');
+}
+
+export const tuiCommand = new Command('tui')
+  .description('Live dashboard of open swaps, timelock countdowns, counterpart legs, relayer queues and settlements')
+  .option('-i, --interval <duration>', 'how often to refresh from the chains and APIs', '10s')
+  .option('--indexer <url>', 'indexer for counterpart legs and settlements (default OMNI_INDEXER_URL or indexer_url)')
+  .option('--relayer <url>', 'resolver health server for queue depth (default OMNI_RELAYER_URL or relayer_url)')
+  .option('--lookback <duration>', 'how far back to ask the indexer for HTLCs', '1d')
+  .action(async (options) => {
+    const store = new SwapStore();
+    const sources = {
+      indexerUrl: options.indexer ?? indexerUrl(),
+      relayerUrl: options.relayer ?? relayerUrl(),
+      lookbackSeconds: parseDuration(options.lookback)
+    };
+    const intervalMs = parseDuration(options.interval) * 1000;
+
+    let addresses: string[] = [];
+    try {
+      if (!isJsonOutput() && !process.stdout.isTTY) throw new Error('omni tui needs a terminal; use --output json for one snapshot per line');
+      // Before the screen is taken over, since a sealed keyring prompts
+      if (sources.indexerUrl) addresses = await dashboardAddresses();
+    } catch (error) {
+      exitWithError(error);
+    }
+
+    if (isJsonOutput()) {
+      for (;;) {
+        emit(await loadDashboard(store, addresses, sources), () => undefined);
+        await new Promise(resolve => setTimeout(resolve, intervalMs));
+      }
+    }
+
+    let dashboard: Dashboard | undefined;
+    const render = () => {
+      process.stdout.write('\x1b[H\x1b[2J' + draw(dashboard, Math.floor(Date.now() / 1000), options.interval));
+    };
+    // Countdowns tick every second between refreshes
+    const ticker = setInterval(render, 1000);
+    const quit = () => {
+      clearInterval(ticker);
+      process.stdout.write('\x1b[?25h\x1b[?1049l');
+      process.exit(0);
+    };
+
+    // Alternate screen, cursor hidden; restored on exit
+    process.stdout.write('\x1b[?1049h\x1b[?25l');
+    process.stdin.setRawMode?.(true);
+    process.stdin.resume();
+    process.stdin.on('data', key => {
+      const pressed = key.toString();
+      if (pressed === 'q' || pressed === '\u0003') quit();
+    });
+    process.once('SIGTERM', quit);
+    process.stdout.on('resize', render);
+
+    render();
+    for (;;) {
+      dashboard = await loadDashboard(store, addresses, sources);
+      render();
+      await new Promise(resolve => setTimeout(resolve, intervalMs));
+    }
+  });
@@ -0,0 +1,91 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { logger } from '../../utils/logger';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore } from '../../services/omni-store';
+import { requireChain, withChain } from '../../services/omni-chains';
+import { verifyLegs } from '../../services/omni-verify';
+import type { Leg, LegCheck } from '../../services/omni-verify';
+import { SwapState } from '../../../packages/shared/src/utils/htlc';
+
+const ICONS: Record<LegCheck['status'], string> = {
+  pass: chalk.green('✔'),
+  fail: chalk.red('✘'),
+  warn: chalk.yellow('!')
+};
+
+/**
+ * `<chain>:<htlc-id>` on chain, or a local swap; a local swap not funded yet
+ * is checked as planned
+ */
+async function loadLeg(reference: string, store: SwapStore): Promise<Leg & { planned: boolean }> {
+  const record = await store.find(reference);
+  if (record && !record.contractId) {
+    return {
+      chain: record.chain,
+      planned: true,
+      htlc: {
+        contractId: record.swapId,
+        token: record.token ?? '',
+        beneficiary: record.beneficiary,
+        originator: record.originator,
+        hashLock: record.hashLock,
+        timelock: record.timelock,
+        value: record.amount,
+        state: SwapState.OPEN
+      }
+    };
+  }
+
+  const [chain, contractId] = record
+    ? [record.chain, record.contractId!]
+    : [reference.slice(0, reference.indexOf(':')), reference.slice(reference.indexOf(':') + 1)];
+  if (!record && !reference.includes(':')) {
+    throw new Error(`"${reference}" is not a local swap; use <chain>:<htlc-id>`);
+  }
+  requireChain(chain);
+  const htlc = await withChain(chain, {}, swapChain => swapChain.getHtlc(contractId));
+  return { chain, planned: false, htlc };
+}
+
+export const verifyCommand = new Command('verify')
+  .description('Check that both escrows of a swap agree and are safe before funding your side')
+  .argument('<source>', 'leg the counterparty claims: <chain>:<htlc-id> or a local swap ID')
+  .argument('<destination>', 'leg paying you: <chain>:<htlc-id> or a local swap ID')
+  .option('-r, --recipient <address>', 'your address, which the destination leg must pay')
+  .option('--source-amount <amount>', 'expected source amount (base units)')
+  .option('--dest-amount <amount>', 'expected destination amount (base units)')
+  .option('--min-rate <rate>', 'least destination base units per source base unit', Number)
+  .option('--dest-token <token>', 'expected destination token; "" for the native asset')
+  .action(async (sourceRef: string, destRef: string, options) => {
+    logger.heading('🔍 Verify Swap');
+
+    try {
+      const store = new SwapStore();
+      const [source, destination] = await Promise.all([loadLeg(sourceRef, store), loadLeg(destRef, store)]);
+      const now = Math.floor(Date.now() / 1000);
+      const checks = verifyLegs(source, destination, {
+        recipient: options.recipient,
+        sourceAmount: options.sourceAmount,
+        destAmount: options.destAmount,
+        minRate: options.minRate,
+        destToken: options.destToken
+      }, now);
+      const safe = checks.every(check => check.status !== 'fail');
+
+      emit({ safe, source, destination, checks }, report => {
+        for (const leg of [report.source, report.destination]) {
+          if (leg.planned) logger.info(`${leg.chain} leg is not funded yet; checking the planned parameters`);
+        }
+        for (const check of report.checks) {
+          console.log(`  ${ICONS[check.status]} ${check.check.padEnd(20)} ${chalk.gray(check.detail)}`);
+        }
+        console.log();
+        if (report.safe) logger.success('Both legs are consistent; safe to fund');
+        else logger.error('Do not fund: at least one check failed');
+      });
+      if (!safe) process.exit(1);
+    } catch (error) {
+      exitWithError(error);
+    }
+  });
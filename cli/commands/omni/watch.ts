@@ -0,0 +1,109 @@
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { logger } from '../../utils/logger';
+import { parseDuration } from '../../utils/duration';
+import { emit, exitWithError } from '../../utils/output';
+import { SwapStore } from '../../services/omni-store';
+import { openChain } from '../../services/omni-chains';
+import { SwapWatcher } from '../../../packages/shared/src/sdk';
+import type { WatchOutcome, WatchReport, WatchTarget } from '../../../packages/shared/src/sdk';
+
+// Exit codes under --until-final
+const EXIT_CODES: Record<WatchOutcome, number> = {
+  'completed': 0,
+  'refunded': 2,
+  'one-sided': 3,
+  'timed-out': 4
+};
+
+export const watchCommand = new Command('watch')
+  .description('Follow swaps across both chains and print state transitions as they happen')
+  .argument('<targets...>', 'swap IDs, HTLC IDs / contract addresses, or hashlock:0x…')
+  .requiredOption('-s, --source <chain>', 'chain the first leg is locked on')
+  .requiredOption('-d, --destination <chain>', 'chain the second leg is locked on')
+  .option('--source-from-height <height>', 'source block to scan for HTLC events from', Number)
+  .option('--dest-from-height <height>', 'destination block to scan for HTLC events from', Number)
+  .option('-i, --interval <duration>', 'poll interval', '15s')
+  .option('--timeout <duration>', 'give up on swaps still open after this long')
+  .option('--until-final', 'for scripts: exit 0 only if every swap completed (2 refunded, 3 one-sided, 4 timed out)')
+  .action(async (targets: string[], options) => {
+    logger.heading('👀 Watching Swaps');
+
+    const source = await openChain(options.source, { fromHeight: options.sourceFromHeight });
+    const destination = await openChain(options.destination, { fromHeight: options.destFromHeight });
+    const watchers: SwapWatcher[] = [];
+    const shutdown = () => {
+      watchers.forEach(watcher => watcher.stop());
+      source.close();
+      destination.close();
+    };
+    process.once('SIGINT', () => {
+      shutdown();
+      process.exit(130);
+    });
+
+    try {
+      const store = new SwapStore();
+      const resolved = await Promise.all(targets.map(target => resolveTarget(store, target)));
+      const reports = await Promise.all(resolved.map(({ label, target }) => {
+        const watcher = new SwapWatcher({ source, destination }, target, {
+          pollIntervalMs: parseDuration(options.interval) * 1000,
+          timeoutMs: options.timeout ? parseDuration(options.timeout) * 1000 : undefined
+        });
+        watchers.push(watcher);
+        // One JSON line per event in JSON mode
+        watcher.on('update', ({ leg, report }) => {
+          const event = { event: 'update', swap: label, leg, chain: report.chain, contractId: report.htlc?.contractId ?? null, status: report.status };
+          emit(event, e => logger.info(`${chalk.bold(e.swap)} ${e.leg} ${e.chain} ${e.contractId ?? ''} → ${colorStatus(e.status)}`));
+        });
+        watcher.on('done', (report: WatchReport) => {
+          emit({ event: 'done', swap: label, ...report }, e => logger.info(`${chalk.bold(e.swap)} settled: ${colorOutcome(e.outcome)}`));
+        });
+        return watcher.run();
+      }));
+
+      shutdown();
+      if (options.untilFinal) {
+        process.exit(Math.max(...reports.map(report => EXIT_CODES[report.outcome])));
+      }
+      logger.success('All watched swaps have settled');
+    } catch (error) {
+      shutdown();
+      exitWithError(error);
+    }
+  });
+
+/**
+ * A local swap (watched by its funded HTLC ID), a hashlock, or any other ID
+ */
+async function resolveTarget(store: SwapStore, value: string): Promise<{ label: string; target: WatchTarget }> {
+  const record = await store.find(value);
+  if (record?.contractId) {
+    return { label: short(record.swapId), target: { swapId: record.contractId } };
+  }
+  if (record) {
+    return { label: short(record.swapId), target: { hashLock: record.hashLock } };
+  }
+  // 32-byte hex is an EVM HTLC ID as often as a hashlock; the hashlock wins only when asked for
+  if (value.startsWith('hashlock:')) {
+    return { label: short(value.slice(9)), target: { hashLock: value.slice(9) } };
+  }
+  return { label: short(value), target: { swapId: value } };
+}
+
+function short(id: string): string {
+  return id.length > 16 ? `${id.slice(0, 10)}…${id.slice(-4)}` : id;
+}
+
+function colorStatus(status: string): string {
+  switch (status) {
+    case 'claimed': return chalk.green(status);
+    case 'refunded': return chalk.yellow(status);
+    case 'expired': return chalk.red(status);
+    default: return chalk.cyan(status);
+  }
+}
+
+function colorOutcome(outcome: WatchOutcome): string {
+  return outcome === 'completed' ? chalk.green(outcome) : chalk.yellow(outcome);
+}
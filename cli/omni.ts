@@ -0,0 +1,85 @@
+#!/usr/bin/env tsx
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import './config/config';
+import { exitWithError, setOutputFormat } from './utils/output';
+import { selectProfile } from './services/omni-config';
+import { useLedger } from './services/omni-ledger';
+import { swapCommand } from './commands/omni/swap';
+import { watchCommand } from './commands/omni/watch';
+import { secretCommand } from './commands/omni/secret';
+import { keysCommand } from './commands/omni/keys';
+import { relayCommand } from './commands/omni/relay';
+import { verifyCommand } from './commands/omni/verify';
+import { schemaCommand } from './commands/omni/schema';
+import { simulateCommand } from './commands/omni/simulate';
+import { exportCommand, importCommand } from './commands/omni/handoff';
+import { historyCommand } from './commands/omni/history';
+import { refundAllCommand } from './commands/omni/refund-all';
+import { deployCommand } from './commands/omni/deploy';
+import { migrateCommand } from './commands/omni/migrate';
+import { quoteCommand } from './commands/omni/quote';
+import { listenCommand } from './commands/omni/listen';
+import { multisigCommand } from './commands/omni/multisig';
+import { faucetCommand } from './commands/omni/faucet';
+import { doctorCommand } from './commands/omni/doctor';
+import { benchCommand } from './commands/omni/bench';
+import { accountsCommand } from './commands/omni/accounts';
+import { cancelCommand } from './commands/omni/cancel';
+import { tuiCommand } from './commands/omni/tui';
+import { reportCommand } from './commands/omni/report';
+import { initConfigCommand } from './commands/omni/init-config';
+import { proofsCommand } from './commands/omni/proofs';
+import { signerCommand } from './commands/omni/signer';
+
+const program = new Command();
+
+program
+  .name('omni')
+  .description('Create, fund and settle cross-chain HTLC swaps on Cosmos and EVM networks')
+  .version('1.0.0')
+  .option('-o, --output <format>', 'output format: text or json', 'text')
+  .option('--profile <name>', 'network profile from ~/.omni/config.toml')
+  .option('--ledger [account]', 'sign on a Ledger (account index, default 0) instead of with a stored key')
+  .hook('preAction', async (command) => {
+    setOutputFormat(command.opts().output);
+    await selectProfile(command.opts().profile);
+    const { ledger } = command.opts();
+    if (ledger !== undefined) useLedger(ledger === true ? 0 : Number(ledger));
+  });
+
+program.addCommand(swapCommand);
+program.addCommand(watchCommand);
+program.addCommand(secretCommand);
+program.addCommand(keysCommand);
+program.addCommand(relayCommand);
+program.addCommand(verifyCommand);
+program.addCommand(schemaCommand);
+program.addCommand(simulateCommand);
+program.addCommand(exportCommand);
+program.addCommand(importCommand);
+program.addCommand(historyCommand);
+program.addCommand(refundAllCommand);
+program.addCommand(deployCommand);
+program.addCommand(migrateCommand);
+program.addCommand(quoteCommand);
+program.addCommand(listenCommand);
+program.addCommand(multisigCommand);
+program.addCommand(faucetCommand);
+program.addCommand(doctorCommand);
+program.addCommand(benchCommand);
+program.addCommand(accountsCommand);
+program.addCommand(cancelCommand);
+program.addCommand(tuiCommand);
+program.addCommand(reportCommand);
+program.addCommand(initConfigCommand);
+program.addCommand(proofsCommand);
+program.addCommand(signerCommand);
+
+process.on('unhandledRejection', (reason) => {
+  console.error(chalk.red('Unhandled Rejection:'), reason);
+  process.exit(1);
+});
+
+program.parseAsync().catch((error) => exitWithError(error));
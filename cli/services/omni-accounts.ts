@@ -0,0 +1,112 @@
+import { ethers } from 'ethers';
+import type { CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { cosmosAddressFromPubkey } from '../../packages/shared/src/sdk';
+import { activeProfile } from './omni-config';
+import { KeyStore } from './omni-keys';
+import type { KeyType } from './omni-keys';
+import { requireChain } from './omni-chains';
+
+// The path CosmosHTLCClient.fromMnemonic signs with
+const COSMOS_HD_PATH = "m/44'/118'/0'/0/0";
+
+export interface Account {
+  /** Keyring name, or `$VAR` for a key read from the environment */
+  name: string;
+  type: KeyType;
+  /** Address on each chain of the key's type */
+  addresses: Record<string, string>;
+}
+
+export interface AccountBalance {
+  account: string;
+  chain: string;
+  address: string;
+  denom: string;
+  /** Base units; unset when the chain could not be queried */
+  balance?: bigint;
+  error?: string;
+}
+
+/**
+ * Every keyring key (or those in `names`), plus keys in the profile's key
+ * environment variables, with their address on each of `chains`. Cosmos
+ * addresses are derived from the mnemonic, so sealed keyrings prompt once for
+ * the passphrase.
+ */
+export async function loadAccounts(chains: string[], names?: string[]): Promise<Account[]> {
+  const byType = (type: KeyType) => chains.filter(chain => requireChain(chain).type === type);
+  const store = await KeyStore.open();
+  const keys = store.list().filter(key => !names?.length || names.includes(key.name));
+  const missing = names?.filter(name => !store.has(name)) ?? [];
+  if (missing.length) throw new Error(`No key named ${missing.join(', ')}; see omni keys list`);
+
+  const accounts: Account[] = [];
+  for (const key of keys) {
+    const evmAddress = key.type === 'evm' ? key.address : undefined;
+    const secret = key.type === 'cosmos' && byType('cosmos').length ? (await store.secret(key.name)).secret : undefined;
+    accounts.push(account(key.name, key.type, byType(key.type), evmAddress, secret));
+  }
+
+  if (!names?.length) {
+    const profile = activeProfile();
+    const evmEnv = profile?.evmKeyEnv ?? 'PRIVATE_KEY';
+    const cosmosEnv = profile?.cosmosKeyEnv ?? 'COSMOS_MNEMONIC';
+    if (process.env[evmEnv]) accounts.push(account(`$${evmEnv}`, 'evm', byType('evm'), new ethers.Wallet(process.env[evmEnv]!).address));
+    if (process.env[cosmosEnv]) accounts.push(account(`$${cosmosEnv}`, 'cosmos', byType('cosmos'), undefined, process.env[cosmosEnv]));
+  }
+  return accounts;
+}
+
+function account(name: string, type: KeyType, chains: string[], evmAddress?: string, mnemonic?: string): Account {
+  const pubkey = mnemonic ? ethers.HDNodeWallet.fromPhrase(mnemonic.trim(), undefined, COSMOS_HD_PATH).publicKey : undefined;
+  const addresses = Object.fromEntries(chains.map(chain => [
+    chain,
+    type === 'evm' ? evmAddress! : cosmosAddressFromPubkey(pubkey!, (requireChain(chain) as CosmosChainConfig).addressPrefix)
+  ]));
+  return { name, type, addresses };
+}
+
+/**
+ * Gas balances of every account on every chain, one connection per chain. A
+ * chain that cannot be reached gets rows with `error` rather than failing the
+ * rest.
+ */
+export async function accountBalances(accounts: Account[], chains: string[]): Promise<AccountBalance[]> {
+  const perChain = await Promise.all(chains.map(async chain => {
+    const config = requireChain(chain);
+    const holders = accounts.filter(account => account.addresses[chain]);
+    const denom = config.type === 'evm'
+      ? config.nativeDenom ?? 'ETH'
+      : (config as CosmosChainConfig).gasDenom ?? (config as CosmosChainConfig).nativeDenom;
+    const rows = holders.map(account => ({ account: account.name, chain, address: account.addresses[chain], denom }));
+
+    try {
+      const balances = config.type === 'evm'
+        ? await evmBalances(config as EvmChainConfig, rows.map(row => row.address))
+        : await cosmosBalances(config as CosmosChainConfig, rows.map(row => row.address), denom);
+      return rows.map((row, i): AccountBalance => ({ ...row, balance: balances[i] }));
+    } catch (error) {
+      return rows.map((row): AccountBalance => ({ ...row, error: (error as Error).message }));
+    }
+  }));
+  return perChain.flat();
+}
+
+async function evmBalances(config: EvmChainConfig, addresses: string[]): Promise<bigint[]> {
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, config.chainId, { staticNetwork: true });
+  try {
+    return await Promise.all(addresses.map(address => provider.getBalance(address)));
+  } finally {
+    provider.destroy();
+  }
+}
+
+async function cosmosBalances(config: CosmosChainConfig, addresses: string[], denom: string): Promise<bigint[]> {
+  const client = await CosmosHTLCClient.connect(config);
+  try {
+    return await Promise.all(addresses.map(async address => BigInt(await client.getBalance(address, denom))));
+  } finally {
+    client.getClient().disconnect();
+  }
+}
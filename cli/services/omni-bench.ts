@@ -0,0 +1,206 @@
+import { promises as fs } from 'fs';
+import path from 'path';
+import { ethers } from 'ethers';
+import type { CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import { cosmosSigningClient, requireChain } from './omni-chains';
+import { DEFAULT_HTLC_ARTIFACT, readArtifact, storeWasm } from './omni-deploy';
+import type { HardhatArtifact } from './omni-deploy';
+import { isLocalnet } from './omni-faucet';
+
+/** Built alongside SimpleHTLC; mints its supply to the deployer */
+const MOCK_TOKEN_ARTIFACT = path.join(__dirname, '../../contracts/evm/artifacts/contracts/MockUSDC.sol/MockUSDC.json');
+
+const NATIVE_AMOUNT = ethers.parseEther('0.001');
+const TOKEN_AMOUNT = 1_000_000n;
+const COSMOS_AMOUNT = '1000';
+// Long enough for the fund to land before a Cosmos refund scenario's timelock passes
+const COSMOS_REFUND_TIMELOCK_SECONDS = 15;
+
+export interface GasMeasurement {
+  chain: string;
+  /** e.g. native-claim: fund with the native asset, then claim */
+  scenario: string;
+  entryPoint: string;
+  gasUsed: number;
+}
+
+/** Gas used by `<chain>/<scenario>/<entry point>` */
+export type GasSnapshot = Record<string, number>;
+
+export interface GasDiff {
+  key: string;
+  baseline?: number;
+  current?: number;
+  /** Change against the baseline, in percent */
+  change?: number;
+  status: 'same' | 'regressed' | 'improved' | 'new' | 'removed';
+}
+
+export interface BenchOptions {
+  artifact?: string;
+  /** Upload this wasm and bench it instead of the chain's code_id */
+  wasm?: string;
+  key?: string;
+}
+
+/**
+ * Deploy fresh contracts to a localnet and measure every HTLC entry point
+ * through a claim and a refund, for each asset kind the chain supports
+ */
+export async function benchChain(chain: string, options: BenchOptions = {}): Promise<GasMeasurement[]> {
+  const config = requireChain(chain);
+  if (!isLocalnet(config)) {
+    throw new Error(`${chain} is not a localnet; omni bench deploys contracts and moves time forward, so it only runs against one`);
+  }
+  return config.type === 'evm'
+    ? benchEvm(chain, config as EvmChainConfig, options.artifact ?? DEFAULT_HTLC_ARTIFACT)
+    : benchCosmos(chain, config as CosmosChainConfig, options);
+}
+
+/**
+ * Fixed preimages and IDs, so calldata (and with it gas) is the same from run
+ * to run
+ */
+function fixture(scenario: string): { contractId: string; preimage: string; hashLock: string } {
+  const preimage = ethers.id(`omni-bench/${scenario}`);
+  return { contractId: ethers.id(`omni-bench/${scenario}/id`), preimage, hashLock: ethers.sha256(preimage) };
+}
+
+async function deploy(artifact: HardhatArtifact, signer: ethers.Signer): Promise<ethers.Contract> {
+  const contract = await new ethers.ContractFactory(artifact.abi, artifact.bytecode, signer).deploy();
+  await contract.waitForDeployment();
+  return contract as ethers.Contract;
+}
+
+/**
+ * Hardhat / Anvil, from the node's first unlocked account, which both funds
+ * and is the beneficiary. Refunds fast-forward the node's clock past the
+ * timelock.
+ */
+async function benchEvm(chain: string, config: EvmChainConfig, artifactFile: string): Promise<GasMeasurement[]> {
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, config.chainId);
+  try {
+    const signer = await provider.getSigner(0);
+    const self = await signer.getAddress();
+    const htlc = await deploy(await readArtifact(artifactFile), signer);
+    const token = await deploy(await readArtifact(MOCK_TOKEN_ARTIFACT), signer);
+    await (await token.approve(await htlc.getAddress(), ethers.MaxUint256)).wait();
+
+    const measurements: GasMeasurement[] = [];
+    const measure = async (scenario: string, entryPoint: string, send: () => Promise<ethers.ContractTransactionResponse>) => {
+      const receipt = await (await send()).wait();
+      measurements.push({ chain, scenario, entryPoint, gasUsed: Number(receipt!.gasUsed) });
+    };
+
+    for (const asset of ['native', 'erc20']) {
+      for (const settle of ['claim', 'refund']) {
+        const scenario = `${asset}-${settle}`;
+        const { contractId, preimage, hashLock } = fixture(scenario);
+        const timelock = (await provider.getBlock('latest'))!.timestamp + 3600;
+
+        if (asset === 'native') {
+          await measure(scenario, 'fundETH', () => htlc.fundETH(contractId, self, hashLock, timelock, { value: NATIVE_AMOUNT }));
+        } else {
+          await measure(scenario, 'fund', () => htlc.fund(contractId, token.target, self, hashLock, timelock, TOKEN_AMOUNT));
+        }
+
+        if (settle === 'claim') {
+          await measure(scenario, 'claim', () => htlc.claim(contractId, preimage));
+        } else {
+          await provider.send('evm_increaseTime', [3601]);
+          await provider.send('evm_mine', []);
+          await measure(scenario, 'refund', () => htlc.refund(contractId));
+        }
+      }
+    }
+    return measurements;
+  } finally {
+    provider.destroy();
+  }
+}
+
+/**
+ * A wasmd-style localnet, from the configured key. Each scenario instantiates
+ * its own HTLC; the refund one waits out a short timelock, since chain time
+ * cannot be moved.
+ */
+async function benchCosmos(chain: string, config: CosmosChainConfig, options: BenchOptions): Promise<GasMeasurement[]> {
+  const codeId = options.wasm ? (await storeWasm(chain, options.wasm, options.key)).codeId : Number(config.codeId);
+  if (!codeId) throw new Error(`No code_id for ${chain}; pass --wasm to upload one`);
+
+  const { client, address } = await cosmosSigningClient(chain, options.key);
+  try {
+    const measurements: GasMeasurement[] = [];
+    for (const settle of ['claim', 'refund']) {
+      const scenario = `native-${settle}`;
+      const { contractId, preimage, hashLock } = fixture(scenario);
+      const timelock = await client.getCurrentTime() + (settle === 'claim' ? 3600 : COSMOS_REFUND_TIMELOCK_SECONDS);
+
+      const { contractAddress, result } = await client.instantiateHTLC(
+        { contractId, beneficiary: address, hashLock, timelock, value: COSMOS_AMOUNT },
+        address,
+        codeId
+      );
+      measurements.push({ chain, scenario, entryPoint: 'instantiate', gasUsed: Number(result.gasUsed) });
+
+      if (settle === 'claim') {
+        const claimed = await client.claim(contractAddress, address, preimage.slice(2));
+        measurements.push({ chain, scenario, entryPoint: 'claim', gasUsed: Number(claimed.gasUsed) });
+      } else {
+        while (await client.getCurrentTime() <= timelock) {
+          await new Promise(resolve => setTimeout(resolve, (config.blockTimeSeconds ?? 2) * 1000));
+        }
+        const refunded = await client.refund(contractAddress, address);
+        measurements.push({ chain, scenario, entryPoint: 'refund', gasUsed: Number(refunded.gasUsed) });
+      }
+    }
+    return measurements;
+  } finally {
+    client.getClient().disconnect();
+  }
+}
+
+export function toSnapshot(measurements: GasMeasurement[]): GasSnapshot {
+  const entries = measurements.map(m => [`${m.chain}/${m.scenario}/${m.entryPoint}`, m.gasUsed] as const);
+  return Object.fromEntries(entries.sort(([a], [b]) => a.localeCompare(b)));
+}
+
+/** The stored baseline, or an empty one when there is none yet */
+export async function readSnapshot(file: string): Promise<GasSnapshot> {
+  try {
+    return JSON.parse(await fs.readFile(file, 'utf8'));
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return {};
+    throw new Error(`Cannot read gas snapshot ${file}: ${(error as Error).message}`);
+  }
+}
+
+/**
+ * Merge fresh measurements over the baseline, so chains not benched this run
+ * keep their entries
+ */
+export async function writeSnapshot(file: string, baseline: GasSnapshot, current: GasSnapshot): Promise<void> {
+  const chains = new Set(Object.keys(current).map(key => key.split('/')[0]));
+  const kept = Object.entries(baseline).filter(([key]) => !chains.has(key.split('/')[0]));
+  const merged = Object.fromEntries([...kept, ...Object.entries(current)].sort(([a], [b]) => a.localeCompare(b)));
+  await fs.writeFile(file, `${JSON.stringify(merged, null, 2)}\n`);
+}
+
+/**
+ * Compare against the baseline for the chains measured. A change within
+ * `tolerance` percent either way counts as the same.
+ */
+export function compareGas(current: GasSnapshot, baseline: GasSnapshot, tolerance: number): GasDiff[] {
+  const chains = new Set(Object.keys(current).map(key => key.split('/')[0]));
+  const keys = new Set([...Object.keys(current), ...Object.keys(baseline).filter(key => chains.has(key.split('/')[0]))]);
+
+  return [...keys].sort().map((key): GasDiff => {
+    const now = current[key];
+    const before = baseline[key];
+    if (before === undefined) return { key, current: now, status: 'new' };
+    if (now === undefined) return { key, baseline: before, status: 'removed' };
+    const change = ((now - before) / before) * 100;
+    const status = change > tolerance ? 'regressed' : change < -tolerance ? 'improved' : 'same';
+    return { key, baseline: before, current: now, change, status };
+  });
+}
@@ -0,0 +1,33 @@
+import { SwapState } from '../../packages/shared/src/utils/htlc';
+import { requireChain, withChain } from './omni-chains';
+import { scanHashLock } from './omni-history';
+import type { SwapRecord } from './omni-store';
+
+export interface FundedLeg {
+  chain: string;
+  contractId: string;
+  /** Empty when the HTLC was read directly rather than found in a scan */
+  txHash: string;
+}
+
+/**
+ * Every HTLC under the swap's hashlock on `chains`: the maker's own leg, funded
+ * by this or another machine, or a counterparty's leg locked against it. The
+ * scan starts where the chain was when the swap was created, estimated from
+ * its block time, unless `blocks` says how far back to look.
+ */
+export async function fundedLegs(record: SwapRecord, chains: string[], blocks?: number): Promise<FundedLeg[]> {
+  const legs: FundedLeg[] = [];
+
+  // On EVM the HTLC ID is the swap ID, so the maker's leg can be read directly
+  if (requireChain(record.chain).type === 'evm') {
+    const htlc = await withChain(record.chain, {}, chain => chain.getHtlc(record.swapId));
+    if (htlc && htlc.state !== SwapState.INVALID) legs.push({ chain: record.chain, contractId: record.swapId, txHash: '' });
+  }
+
+  for (const htlc of await scanHashLock(record.hashLock, [record.chain, ...chains], record.createdAt, blocks)) {
+    if (legs.some(leg => leg.chain === htlc.chain && leg.contractId.toLowerCase() === htlc.contractId.toLowerCase())) continue;
+    legs.push({ chain: htlc.chain, contractId: htlc.contractId, txHash: htlc.fundedAt.txHash });
+  }
+  return legs;
+}
@@ -0,0 +1,187 @@
+import { ethers } from 'ethers';
+import { allChains } from '../../packages/shared/src/chains';
+import type { CosmosChainConfig } from '../../packages/shared/src/chains';
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { remoteSignerCredentials } from '../../packages/shared/src/clients/remote-signer';
+import { createChainAdapter } from '../../packages/shared/src/resolver/adapters';
+import type { AdapterCredentials, ChainAdapter } from '../../packages/shared/src/resolver/adapters';
+import { fromChainAdapter } from '../../packages/shared/src/sdk';
+import type { SwapChain, SwapOrder } from '../../packages/shared/src/sdk';
+import type { HTLCDetails } from '../../packages/shared/src/utils/htlc';
+import { activeProfile } from './omni-config';
+import { KeyStore } from './omni-keys';
+import { ledgerCredentials, ledgerEnabled } from './omni-ledger';
+import { remoteSigner, remoteSignerSettings } from './omni-signer';
+import type { SwapRecord, SwapStore } from './omni-store';
+
+/**
+ * Environment variable holding the key for a chain: PRIVATE_KEY for EVM chains
+ * and COSMOS_MNEMONIC for Cosmos chains, unless the active profile names others
+ */
+export function keyEnvFor(chain: string): string {
+  const profile = activeProfile();
+  return requireChain(chain).type === 'evm'
+    ? profile?.evmKeyEnv ?? 'PRIVATE_KEY'
+    : profile?.cosmosKeyEnv ?? 'COSMOS_MNEMONIC';
+}
+
+/**
+ * Signing material for a chain. A named key (`--key`, or the profile's
+ * evm_key / cosmos_key) comes from the keyring; otherwise the key is read from
+ * the chain's environment variable, for CI. With `--ledger` the key stays on
+ * the device and only a signer for it is returned; with a profile
+ * remote_signer the name is a key on that signer.
+ */
+export async function credentialsFor(chain: string, keyName?: string): Promise<AdapterCredentials> {
+  const config = requireChain(chain);
+  const type = config.type;
+  if (ledgerEnabled()) {
+    if (keyName) throw new Error('Pass either --ledger or --key, not both');
+    return ledgerCredentials(config);
+  }
+
+  const profile = activeProfile();
+  const name = keyName ?? (type === 'evm' ? profile?.evmKey : profile?.cosmosKey);
+
+  const remote = remoteSignerSettings();
+  if (remote) {
+    if (!name) throw new Error(`Signing on the remote signer needs a key name; pass --key or set ${type}_key in the profile`);
+    return remoteSignerCredentials(config, await remoteSigner(remote), name);
+  }
+
+  if (name) {
+    const key = await (await KeyStore.open()).secret(name);
+    if (key.type !== type) {
+      throw new Error(`Key ${name} is a ${key.type} key but ${chain} is a ${type} chain`);
+    }
+    return type === 'evm' ? { privateKey: key.secret } : { mnemonic: key.secret };
+  }
+
+  const key = process.env[keyEnvFor(chain)];
+  if (!key) return {};
+  return type === 'evm' ? { privateKey: key } : { mnemonic: key };
+}
+
+export function requireChain(chain: string) {
+  const config = allChains[chain];
+  if (!config) {
+    throw new Error(`Unknown chain ${chain}; expected one of ${Object.keys(allChains).join(', ')}`);
+  }
+  return config;
+}
+
+/** `only` if given, else the active profile's chains, else every chain in the registry */
+export function configuredChains(only?: string[]): string[] {
+  if (only?.length) {
+    only.forEach(requireChain);
+    return only;
+  }
+  const profile = activeProfile();
+  return profile && Object.keys(profile.chains).length ? Object.keys(profile.chains) : Object.keys(allChains);
+}
+
+export interface ChainOptions {
+  sign?: boolean;
+  /** Keyring name to sign with */
+  key?: string;
+  fromHeight?: number;
+}
+
+/**
+ * A SwapChain for `chain`, signing when `sign` is set and watch-only otherwise.
+ * Close it when done so the event subscription stops.
+ */
+export async function openChain(chain: string, options: ChainOptions = {}): Promise<SwapChain & { close(): void; adapter: ChainAdapter }> {
+  requireChain(chain);
+  const credentials = options.sign ? await credentialsFor(chain, options.key) : {};
+  if (options.sign && !hasSigner(credentials)) {
+    throw new Error(`No key configured for ${chain}; pass --key <name> (see omni keys) or set ${keyEnvFor(chain)}`);
+  }
+
+  const adapter = await createChainAdapter(chain, credentials, { fees: activeProfile()?.fees });
+  const swapChain = await fromChainAdapter(adapter, { fromHeight: options.fromHeight });
+  return {
+    ...swapChain,
+    adapter,
+    close() {
+      swapChain.close();
+      void adapter.close();
+    }
+  };
+}
+
+export function hasSigner(credentials: AdapterCredentials): boolean {
+  return !!(credentials.privateKey || credentials.mnemonic || credentials.evmSigner || credentials.cosmosSigner);
+}
+
+/**
+ * A signing CosmWasm client for a Cosmos chain's key, for calls the adapters
+ * do not cover. Disconnect it when done.
+ */
+export async function cosmosSigningClient(chain: string, key?: string): Promise<{ client: CosmosHTLCClient; address: string }> {
+  const config = requireChain(chain);
+  if (config.type !== 'cosmos') throw new Error(`${chain} is not a Cosmos chain`);
+  const { mnemonic, cosmosSigner } = await credentialsFor(chain, key);
+  if (cosmosSigner) return CosmosHTLCClient.fromSigner(config as CosmosChainConfig, cosmosSigner);
+  if (!mnemonic) throw new Error(`No key configured for ${chain}; pass --key <name> (see omni keys) or set ${keyEnvFor(chain)}`);
+  return CosmosHTLCClient.fromMnemonic(config as CosmosChainConfig, mnemonic);
+}
+
+/** The signer for an EVM chain's key, connected to `provider` if given */
+export async function evmSigner(chain: string, key?: string, provider: ethers.Provider | null = null): Promise<ethers.Signer & { readonly address: string }> {
+  const config = requireChain(chain);
+  if (config.type !== 'evm') throw new Error(`${chain} is not an EVM chain`);
+  const { privateKey, evmSigner: signer } = await credentialsFor(chain, key);
+  if (signer) return signer.connect(provider) as ethers.Signer & { readonly address: string };
+  if (!privateKey) throw new Error(`No key configured for ${chain}; pass --key <name> (see omni keys) or set ${keyEnvFor(chain)}`);
+  return new ethers.Wallet(privateKey, provider);
+}
+
+/**
+ * Run `fn` against a chain and close it afterwards
+ */
+export async function withChain<T>(
+  chain: string,
+  options: ChainOptions,
+  fn: (chain: SwapChain & { adapter: ChainAdapter }) => Promise<T>
+): Promise<T> {
+  const opened = await openChain(chain, options);
+  try {
+    return await fn(opened);
+  } finally {
+    opened.close();
+  }
+}
+
+export function htlcToOrder(chain: string, htlc: HTLCDetails): SwapOrder {
+  return {
+    chain,
+    contractId: htlc.contractId,
+    originator: htlc.originator,
+    beneficiary: htlc.beneficiary,
+    hashLock: htlc.hashLock,
+    timelock: htlc.timelock,
+    amount: htlc.value,
+    token: htlc.token && !/^0x0{40}$/.test(htlc.token) ? htlc.token : undefined,
+    state: htlc.state
+  };
+}
+
+/**
+ * The chain and HTLC ID an argument refers to: a swap created here (by swap ID
+ * or HTLC ID), or a raw HTLC ID on `chain`
+ */
+export async function resolveHtlc(store: SwapStore, id: string, chain?: string): Promise<{ chain: string; contractId: string; record: SwapRecord | null }> {
+  const record = await store.find(id);
+  if (record) {
+    if (!record.contractId) {
+      throw new Error(`Swap ${record.swapId} has not been funded yet; run omni swap fund ${record.swapId}`);
+    }
+    return { chain: record.chain, contractId: record.contractId, record };
+  }
+  if (!chain) {
+    throw new Error(`Swap ${id} is not known locally; pass --chain to look it up on chain`);
+  }
+  requireChain(chain);
+  return { chain, contractId: id, record: null };
+}
@@ -0,0 +1,249 @@
+import { promises as fs } from 'fs';
+import path from 'path';
+import { parse, stringify } from 'smol-toml';
+import { allChains, cosmosChains, evmChains, htlcAddressBook } from '../../packages/shared/src/chains';
+import type { ChainConfig, CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import type { FeePolicy } from '../../packages/shared/src/clients/fees';
+import type { RemoteSignerSettings } from '../../packages/shared/src/clients/remote-signer';
+import { omniHome } from './omni-store';
+
+/**
+ * Per-chain settings in a profile. For a registry chain every field is an
+ * optional override; a chain the registry does not know (a localnet) needs
+ * `type`, `name`, `rpcUrl`, `chainId` and `htlcAddress`, plus `addressPrefix`
+ * and `nativeDenom` on Cosmos.
+ */
+export type ChainProfile = Partial<EvmChainConfig> | Partial<CosmosChainConfig>;
+
+export interface Profile {
+  name: string;
+  chains: Record<string, ChainProfile>;
+  /** Gas padding and fee budgets applied to every chain */
+  fees?: FeePolicy;
+  /** Keyring names signing commands use when no --key is given */
+  evmKey?: string;
+  cosmosKey?: string;
+  /** Environment variables holding the keys when no keyring name applies (default PRIVATE_KEY / COSMOS_MNEMONIC) */
+  evmKeyEnv?: string;
+  cosmosKeyEnv?: string;
+  /** Swap indexer `omni history` queries before falling back to scanning the chains */
+  indexerUrl?: string;
+  /** Resolver health server whose `/status` `omni tui` shows queue depth from */
+  relayerUrl?: string;
+  /** Where `omni quote` asks for quotes */
+  quotes?: QuoteSettings;
+  /** Machine-usable faucets for `omni faucet request`, by chain */
+  faucets?: Record<string, FaucetSettings>;
+  /** Sign on a remote signer; evm_key / cosmos_key then name keys held there */
+  remoteSigner?: RemoteSignerProfile;
+}
+
+export interface RemoteSignerProfile extends Omit<RemoteSignerSettings, 'token'> {
+  /** Environment variable holding the bearer token (default OMNI_SIGNER_TOKEN) */
+  tokenEnv?: string;
+}
+
+export interface FaucetSettings {
+  url?: string;
+  /** `cosmjs` for @cosmjs/faucet (POST /credit), `json` for a plain POST of the address (default) */
+  kind?: 'cosmjs' | 'json';
+  /** Denoms or token addresses to ask for; default the native asset */
+  assets?: string[];
+  /** Localnet only: environment variable with the mnemonic of a funded genesis account to send from */
+  mnemonicEnv?: string;
+}
+
+export interface QuoteSettings {
+  /** Resolver RFQ endpoints by name */
+  resolvers?: Record<string, string>;
+  /** Also ask Skip Go for bridged routes (default true) */
+  skip?: boolean;
+  skipApiUrl?: string;
+}
+
+export interface OmniConfig {
+  defaultProfile?: string;
+  profiles: Record<string, Profile>;
+}
+
+let active: Profile | undefined;
+
+export function configPath(): string {
+  return path.join(omniHome(), 'config.toml');
+}
+
+/**
+ * Read <omni home>/config.toml; missing file means no profiles. Keys are
+ * snake_case in the file and camelCase here.
+ *
+ *   default_profile = "testnet"
+ *
+ *   [profiles.testnet]
+ *   evm_key_env = "TESTNET_PRIVATE_KEY"
+ *
+ *   [profiles.testnet.fees]
+ *   gas_multiplier = 1.3
+ *
+ *   [profiles.testnet.chains.sepolia]
+ *   rpc_url = "https://sepolia.example.org"
+ *   htlc_address = "0x..."
+ */
+export async function loadConfig(file: string = configPath()): Promise<OmniConfig> {
+  let parsed: { defaultProfile?: string; profiles?: Record<string, Omit<Profile, 'name'>> };
+  try {
+    parsed = await readToml(file);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return { profiles: {} };
+    throw error;
+  }
+
+  const profiles: Record<string, Profile> = {};
+  for (const [name, profile] of Object.entries(parsed.profiles ?? {})) {
+    profiles[name] = { ...profile, name, chains: profile.chains ?? {} };
+  }
+  return { defaultProfile: parsed.defaultProfile, profiles };
+}
+
+/**
+ * Pick the profile named by `--profile`, OMNI_PROFILE or `default_profile`
+ * and apply it to the chain registry. No profile at all leaves the built-in
+ * registry as is.
+ */
+export async function selectProfile(name?: string): Promise<Profile | undefined> {
+  const config = await loadConfig();
+  const wanted = name || process.env.OMNI_PROFILE || config.defaultProfile;
+  if (!wanted) return undefined;
+
+  const profile = config.profiles[wanted];
+  if (!profile) {
+    const known = Object.keys(config.profiles);
+    throw new Error(`No profile "${wanted}" in ${configPath()}${known.length ? `; known: ${known.join(', ')}` : ''}`);
+  }
+  applyProfile(profile);
+  return profile;
+}
+
+/**
+ * Overlay a profile's chain settings onto the shared registry, which the
+ * adapters read their RPC endpoints and contract addresses from
+ */
+export function applyProfile(profile: Profile): void {
+  for (const [key, overrides] of Object.entries(profile.chains)) {
+    const existing = allChains[key];
+    if (!existing && (!overrides.type || !overrides.rpcUrl || overrides.chainId === undefined)) {
+      throw new Error(`Profile ${profile.name}: chain ${key} is not in the registry and needs type, rpc_url and chain_id`);
+    }
+
+    const config = Object.assign(existing ?? { name: key, htlcAddress: '' }, overrides) as ChainConfig;
+    allChains[key] = config;
+    if (config.type === 'evm') {
+      evmChains[key] = config as EvmChainConfig;
+      if (overrides.htlcAddress) htlcAddressBook[config.chainId as number] = overrides.htlcAddress;
+    } else {
+      cosmosChains[key] = config as CosmosChainConfig;
+    }
+  }
+  active = profile;
+}
+
+export function activeProfile(): Profile | undefined {
+  return active;
+}
+
+/**
+ * Save settings for one chain (code IDs and addresses after a deploy) into
+ * the active profile, or a new `default` profile when none is active, and
+ * apply them right away. Rewrites config.toml, so comments in it are lost.
+ * Returns the profile written to.
+ */
+export async function recordChainSettings(chain: string, settings: ChainProfile): Promise<string> {
+  const file = configPath();
+  const raw = await readRawConfig(file);
+
+  const name = active?.name ?? raw.default_profile ?? 'default';
+  raw.default_profile ??= name;
+  raw.profiles ??= {};
+  raw.profiles[name] ??= {};
+  raw.profiles[name].chains ??= {};
+  const entry = raw.profiles[name].chains[chain] ??= {};
+  for (const [key, value] of Object.entries(settings)) {
+    entry[snakeCase(key)] = value;
+  }
+  await writeRawConfig(file, raw);
+
+  const profile = active ?? { name, chains: {} };
+  profile.chains[chain] = { ...profile.chains[chain], ...settings } as ChainProfile;
+  applyProfile(profile);
+  return name;
+}
+
+/**
+ * Write a whole profile into config.toml, replacing one of the same name
+ * only when `overwrite` is set. Other profiles are kept, but comments are
+ * lost as with recordChainSettings.
+ */
+export async function saveProfile(profile: Profile, options: { makeDefault?: boolean; overwrite?: boolean } = {}): Promise<void> {
+  const file = configPath();
+  const raw = await readRawConfig(file);
+  raw.profiles ??= {};
+  if (raw.profiles[profile.name] && !options.overwrite) {
+    throw new Error(`Profile ${profile.name} already exists in ${file}; pass --force to replace it`);
+  }
+
+  const { name, ...settings } = profile;
+  raw.profiles[name] = snakeKeys(settings);
+  if (options.makeDefault || !raw.default_profile) raw.default_profile = name;
+  await writeRawConfig(file, raw);
+}
+
+async function readRawConfig(file: string): Promise<Record<string, any>> {
+  try {
+    return parse(await fs.readFile(file, 'utf8'));
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return {};
+    throw error;
+  }
+}
+
+async function writeRawConfig(file: string, raw: Record<string, any>): Promise<void> {
+  await fs.mkdir(path.dirname(file), { recursive: true, mode: 0o700 });
+  await fs.writeFile(file, stringify(raw) + '\n');
+}
+
+function snakeCase(key: string): string {
+  return key.replace(/[A-Z]/g, c => `_${c.toLowerCase()}`);
+}
+
+// The inverse of camelize, leaving out unset values, which TOML cannot hold
+function snakeKeys(value: unknown, keepKeys = false): unknown {
+  if (Array.isArray(value)) return value.map(inner => snakeKeys(inner));
+  if (value === null || typeof value !== 'object' || value instanceof Date) return value;
+  return Object.fromEntries(
+    Object.entries(value)
+      .filter(([, inner]) => inner !== undefined)
+      .map(([key, inner]) => [
+        keepKeys ? key : snakeCase(key),
+        snakeKeys(inner, !keepKeys && (key === 'chains' || key === 'resolvers' || key === 'faucets'))
+      ])
+  );
+}
+
+/**
+ * Parse a TOML file with its snake_case keys turned into camelCase. Tables
+ * named `profiles` or `chains` keep their keys as written.
+ */
+export async function readToml<T>(file: string): Promise<T> {
+  return camelize(parse(await fs.readFile(file, 'utf8'))) as T;
+}
+
+// Profile names, chain keys (also under faucets) and resolver names are names, not settings, and keep their spelling
+function camelize(value: unknown, keepKeys = false): unknown {
+  if (Array.isArray(value)) return value.map(inner => camelize(inner));
+  if (value === null || typeof value !== 'object' || value instanceof Date) return value;
+  return Object.fromEntries(
+    Object.entries(value).map(([key, inner]) => {
+      const name = keepKeys ? key : key.replace(/_([a-z0-9])/g, (_, c: string) => c.toUpperCase());
+      return [name, camelize(inner, !keepKeys && (name === 'profiles' || name === 'chains' || name === 'resolvers' || name === 'faucets'))];
+    })
+  );
+}
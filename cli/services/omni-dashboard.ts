@@ -0,0 +1,180 @@
+import axios from 'axios';
+import { SwapState } from '../../packages/shared/src/utils/htlc';
+import { activeProfile } from './omni-config';
+import { configuredChains, withChain } from './omni-chains';
+import { historyState, queryIndexer } from './omni-history';
+import type { HistoryEntry, HistoryState } from './omni-history';
+import { loadAccounts } from './omni-accounts';
+import type { SwapRecord, SwapStore } from './omni-store';
+
+export type DashboardState = 'unfunded' | HistoryState | 'unknown';
+
+export interface DashboardSwap {
+  swapId: string;
+  chain: string;
+  contractId?: string;
+  amount: string;
+  token?: string;
+  timelock: number;
+  state: DashboardState;
+  /** The other side's HTLC under the same hashlock, when the indexer knows it */
+  counterpart?: { chain: string; contractId: string; state: HistoryState };
+}
+
+export interface RelayerQueue {
+  chain: string;
+  queueSize: number;
+  processing: boolean;
+  draining: boolean;
+}
+
+export interface Settlement {
+  chain: string;
+  contractId: string;
+  state: 'claimed' | 'refunded';
+  value: string;
+  token?: string;
+  txHash?: string;
+  /** Funding time, Unix seconds; the indexer does not report when it settled */
+  fundedTime?: number;
+}
+
+export interface Dashboard {
+  at: number;
+  swaps: DashboardSwap[];
+  /** Unset without a relayer URL */
+  relayer?: RelayerQueue[];
+  settlements: Settlement[];
+  /** Sources that failed this refresh; the rest of the dashboard is still current */
+  errors: string[];
+}
+
+export interface DashboardSources {
+  indexerUrl?: string;
+  relayerUrl?: string;
+  /** How far back the indexer is asked for HTLCs, seconds */
+  lookbackSeconds: number;
+}
+
+/** $OMNI_RELAYER_URL, else the active profile's relayer_url */
+export function relayerUrl(): string | undefined {
+  return process.env.OMNI_RELAYER_URL || activeProfile()?.relayerUrl;
+}
+
+/**
+ * Addresses whose HTLCs the indexer is asked for: every key's address on
+ * every configured chain, so counterpart legs paying us are found too
+ */
+export async function dashboardAddresses(): Promise<string[]> {
+  const accounts = await loadAccounts(configuredChains());
+  return [...new Set(accounts.flatMap(account => Object.values(account.addresses)))];
+}
+
+/**
+ * One refresh: the local swaps still in play with their on-chain state, the
+ * relayer's queues, and recent settlements. Each source fails on its own.
+ */
+export async function loadDashboard(store: SwapStore, addresses: string[], sources: DashboardSources): Promise<Dashboard> {
+  const now = Math.floor(Date.now() / 1000);
+  const errors: string[] = [];
+  const records = (await store.list()).filter(record => !record.cancelledAt && !record.claimTx && !record.refundTx);
+
+  const [swaps, relayer, indexed] = await Promise.all([
+    swapStates(records, errors),
+    sources.relayerUrl ? relayerQueues(sources.relayerUrl).catch(error => {
+      errors.push(`relayer: ${(error as Error).message}`);
+      return undefined;
+    }) : Promise.resolve(undefined),
+    sources.indexerUrl ? indexedHtlcs(sources.indexerUrl, addresses, now - sources.lookbackSeconds).catch(error => {
+      errors.push(`indexer: ${(error as Error).message}`);
+      return undefined;
+    }) : Promise.resolve(undefined)
+  ]);
+
+  if (indexed) {
+    for (const swap of swaps) {
+      const record = records.find(candidate => candidate.swapId === swap.swapId)!;
+      const other = indexed.find(entry =>
+        entry.hashLock.toLowerCase() === record.hashLock.toLowerCase()
+        && entry.contractId.toLowerCase() !== (record.contractId ?? record.swapId).toLowerCase());
+      if (other) swap.counterpart = { chain: other.chain, contractId: other.contractId, state: historyState(other, now) };
+    }
+  }
+
+  return { at: now, swaps, relayer, settlements: indexed ? indexedSettlements(indexed, now) : await localSettlements(store), errors };
+}
+
+/** Chain state of every funded swap, reading each chain once */
+async function swapStates(records: SwapRecord[], errors: string[]): Promise<DashboardSwap[]> {
+  const swaps = records.map((record): DashboardSwap => ({
+    swapId: record.swapId,
+    chain: record.chain,
+    contractId: record.contractId,
+    amount: record.amount,
+    token: record.token,
+    timelock: record.timelock,
+    state: record.contractId ? 'unknown' : 'unfunded'
+  }));
+
+  const chains = [...new Set(swaps.filter(swap => swap.contractId).map(swap => swap.chain))];
+  await Promise.all(chains.map(chain => withChain(chain, {}, async swapChain => {
+    const chainNow = await swapChain.currentTime();
+    for (const swap of swaps.filter(candidate => candidate.chain === chain && candidate.contractId)) {
+      const htlc = await swapChain.getHtlc(swap.contractId!);
+      if (!htlc || htlc.state === SwapState.INVALID) continue;
+      swap.state = htlc.state === SwapState.CLAIMED ? 'claimed'
+        : htlc.state === SwapState.REFUNDED ? 'refunded'
+        : htlc.timelock < chainNow ? 'expired' : 'open';
+    }
+  }).catch(error => {
+    errors.push(`${chain}: ${(error as Error).message}`);
+  })));
+
+  return swaps.sort((a, b) => a.timelock - b.timelock);
+}
+
+async function relayerQueues(url: string): Promise<RelayerQueue[]> {
+  const response = await axios.get<{ chains: RelayerQueue[] }>(`${url.replace(/\/$/, '')}/status`, { timeout: 10000 });
+  return response.data.chains.map(({ chain, queueSize, processing, draining }) => ({ chain, queueSize, processing, draining }));
+}
+
+async function indexedHtlcs(url: string, addresses: string[], since: number): Promise<HistoryEntry[]> {
+  const chains = configuredChains();
+  const results = await Promise.all(addresses.map(address => queryIndexer(url, { address, chains, since })));
+  const unique = new Map(results.flat().map(entry => [`${entry.chain}/${entry.contractId.toLowerCase()}`, entry]));
+  return [...unique.values()];
+}
+
+const RECENT_SETTLEMENTS = 10;
+
+function indexedSettlements(entries: HistoryEntry[], now: number): Settlement[] {
+  return entries
+    .filter(entry => entry.state !== 'OPEN')
+    .sort((a, b) => b.fundedTime - a.fundedTime)
+    .slice(0, RECENT_SETTLEMENTS)
+    .map(entry => ({
+      chain: entry.chain,
+      contractId: entry.contractId,
+      state: historyState(entry, now) as Settlement['state'],
+      value: entry.value,
+      token: entry.token,
+      txHash: entry.settledTxHash,
+      fundedTime: entry.fundedTime
+    }));
+}
+
+/** Without an indexer, the swaps this machine settled */
+async function localSettlements(store: SwapStore): Promise<Settlement[]> {
+  return (await store.list())
+    .filter(record => record.contractId && (record.claimTx || record.refundTx))
+    .sort((a, b) => b.createdAt - a.createdAt)
+    .slice(0, RECENT_SETTLEMENTS)
+    .map(record => ({
+      chain: record.chain,
+      contractId: record.contractId!,
+      state: record.claimTx ? 'claimed' as const : 'refunded' as const,
+      value: record.amount,
+      token: record.token,
+      txHash: record.claimTx ?? record.refundTx
+    }));
+}
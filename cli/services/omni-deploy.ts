@@ -0,0 +1,104 @@
+import { promises as fs } from 'fs';
+import path from 'path';
+import { ethers } from 'ethers';
+import type { EvmChainConfig } from '../../packages/shared/src/chains';
+import { cosmosSigningClient, evmSigner, requireChain } from './omni-chains';
+
+/** Where `pnpm --filter @swap-sage/contracts-evm build` writes the SimpleHTLC artifact */
+export const DEFAULT_HTLC_ARTIFACT = path.join(__dirname, '../../contracts/evm/artifacts/contracts/SimpleHTLC.sol/SimpleHTLC.json');
+
+// Largest wasm most chains accept in a MsgStoreCode (wasmd's default MaxWasmSize)
+const MAX_WASM_BYTES = 819200;
+
+export interface StoredCode {
+  chain: string;
+  codeId: number;
+  checksum: string;
+  txHash: string;
+  height: number;
+}
+
+export interface DeployedContract {
+  chain: string;
+  address: string;
+  txHash: string;
+  blockNumber: number;
+  /** keccak256 of the deployed runtime bytecode */
+  codeHash: string;
+}
+
+/**
+ * Upload a CosmWasm HTLC build with MsgStoreCode and confirm the chain holds
+ * exactly these bytes. HTLC instances are then created per swap from the code
+ * ID, so nothing is instantiated here.
+ */
+export async function storeWasm(chain: string, file: string, key?: string): Promise<StoredCode> {
+  const config = requireChain(chain);
+  if (config.type !== 'cosmos') throw new Error(`${chain} is not a Cosmos chain; use omni deploy evm`);
+
+  const wasm = new Uint8Array(await fs.readFile(file));
+  if (wasm.length < 4 || Buffer.from(wasm.subarray(0, 4)).toString('hex') !== '0061736d') {
+    throw new Error(`${file} is not a wasm module`);
+  }
+  if (wasm.length > MAX_WASM_BYTES) {
+    throw new Error(`${file} is ${wasm.length} bytes, over the ${MAX_WASM_BYTES}-byte upload limit; build it with the cosmwasm optimizer`);
+  }
+  const checksum = ethers.sha256(wasm).slice(2);
+
+  const { client, address } = await cosmosSigningClient(chain, key);
+  try {
+    const signing = client.getSigningClient()!;
+    const result = await signing.upload(address, wasm, 'auto', `Store HTLC ${checksum.slice(0, 12)}`);
+    const stored = await signing.getCodeDetails(result.codeId);
+    if (stored.checksum.toLowerCase() !== checksum) {
+      throw new Error(`Code ${result.codeId} has checksum ${stored.checksum}, expected ${checksum}`);
+    }
+    return { chain, codeId: result.codeId, checksum, txHash: result.transactionHash, height: result.height };
+  } finally {
+    client.getClient().disconnect();
+  }
+}
+
+export interface HardhatArtifact {
+  contractName?: string;
+  abi: ethers.InterfaceAbi;
+  bytecode: string;
+}
+
+/** A Hardhat artifact with creation bytecode */
+export async function readArtifact(file: string): Promise<HardhatArtifact> {
+  let artifact: HardhatArtifact;
+  try {
+    artifact = JSON.parse(await fs.readFile(file, 'utf8'));
+  } catch (error) {
+    throw new Error(`Cannot read ${file} (${(error as Error).message}); build contracts/evm first or pass --artifact`);
+  }
+  if (!artifact.bytecode || artifact.bytecode === '0x') {
+    throw new Error(`${file} has no bytecode; it is an interface or abstract contract`);
+  }
+  return artifact;
+}
+
+/**
+ * Deploy SimpleHTLC from its Hardhat artifact and check the new contract
+ * answers before it is recorded anywhere
+ */
+export async function deployEvmHtlc(chain: string, artifactFile: string = DEFAULT_HTLC_ARTIFACT, key?: string): Promise<DeployedContract> {
+  const config = requireChain(chain);
+  if (config.type !== 'evm') throw new Error(`${chain} is not an EVM chain; use omni deploy wasm`);
+
+  const artifact = await readArtifact(artifactFile);
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, (config as EvmChainConfig).chainId);
+  try {
+    const wallet = await evmSigner(chain, key, provider);
+    const contract = await new ethers.ContractFactory(artifact.abi, artifact.bytecode, wallet).deploy();
+    const receipt = await contract.deploymentTransaction()!.wait();
+    const address = await contract.getAddress();
+    // SimpleHTLC's only view without arguments; a revert means the wrong artifact
+    await contract.getFunction('getCurrentTime')();
+    const codeHash = ethers.keccak256(await provider.getCode(address));
+    return { chain, address, txHash: receipt!.hash, blockNumber: receipt!.blockNumber, codeHash };
+  } finally {
+    provider.destroy();
+  }
+}
@@ -0,0 +1,173 @@
+import { promises as fs } from 'fs';
+import { ethers } from 'ethers';
+import type { CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { cosmosSigningClient, credentialsFor, evmSigner, hasSigner, keyEnvFor, requireChain } from './omni-chains';
+import { DEFAULT_HTLC_ARTIFACT } from './omni-deploy';
+
+export type CheckStatus = 'ok' | 'warn' | 'fail';
+
+export interface DoctorCheck {
+  chain: string;
+  check: 'rpc' | 'contract' | 'key' | 'clock';
+  status: CheckStatus;
+  detail: string;
+  /** RPC round trip, or clock skew, in milliseconds */
+  ms?: number;
+}
+
+export interface DoctorOptions {
+  key?: string;
+  /** Skew between this machine and the chain beyond which timelocks are unsafe (default 120s) */
+  maxSkewSeconds?: number;
+}
+
+/** The same margin `recommend` assumes when sizing timelocks */
+const DEFAULT_MAX_SKEW_SECONDS = 120;
+
+/**
+ * Run every check against one chain. A chain whose RPC cannot be reached
+ * gets only the RPC check, since the rest would fail the same way.
+ */
+export async function diagnoseChain(chain: string, options: DoctorOptions = {}): Promise<DoctorCheck[]> {
+  const config = requireChain(chain);
+  return config.type === 'evm'
+    ? diagnoseEvm(chain, config as EvmChainConfig, options)
+    : diagnoseCosmos(chain, config as CosmosChainConfig, options);
+}
+
+async function diagnoseEvm(chain: string, config: EvmChainConfig, options: DoctorOptions): Promise<DoctorCheck[]> {
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, undefined, { staticNetwork: true });
+  try {
+    const started = Date.now();
+    let block: ethers.Block | null;
+    let chainId: bigint;
+    try {
+      [block, { chainId }] = await Promise.all([provider.getBlock('latest'), provider.getNetwork()]);
+    } catch (error) {
+      return [unreachable(chain, config.rpcUrl, error)];
+    }
+    const checks = [rpcCheck(chain, Date.now() - started, String(chainId), String(config.chainId))];
+
+    checks.push(await evmContractCheck(chain, config, provider));
+    checks.push(await keyCheck(chain, options.key, async () => {
+      const signer = await evmSigner(chain, options.key, provider);
+      return { address: signer.address, balance: await provider.getBalance(signer.address), unit: 'wei' };
+    }));
+    if (block) checks.push(clockCheck(chain, block.timestamp, options));
+    return checks;
+  } finally {
+    provider.destroy();
+  }
+}
+
+/** The deployed runtime code against the recorded release hash, or the bundled artifact's */
+async function evmContractCheck(chain: string, config: EvmChainConfig, provider: ethers.Provider): Promise<DoctorCheck> {
+  if (!config.htlcAddress) return { chain, check: 'contract', status: 'warn', detail: 'No htlc_address configured; see omni deploy evm' };
+
+  const code = await provider.getCode(config.htlcAddress);
+  if (code === '0x') return { chain, check: 'contract', status: 'fail', detail: `No contract at ${config.htlcAddress}` };
+
+  const hash = ethers.keccak256(code);
+  const expected = config.htlcCodeHash ?? await artifactCodeHash();
+  if (!expected) return { chain, check: 'contract', status: 'warn', detail: `Code ${short(hash)} at ${config.htlcAddress}; no htlc_code_hash or built artifact to compare with` };
+  return hash.toLowerCase() === expected.toLowerCase()
+    ? { chain, check: 'contract', status: 'ok', detail: `${config.htlcAddress} runs code ${short(hash)}` }
+    : { chain, check: 'contract', status: 'fail', detail: `${config.htlcAddress} runs code ${short(hash)}, expected ${short(expected)}` };
+}
+
+async function artifactCodeHash(): Promise<string | undefined> {
+  try {
+    const { deployedBytecode } = JSON.parse(await fs.readFile(DEFAULT_HTLC_ARTIFACT, 'utf8')) as { deployedBytecode?: string };
+    return deployedBytecode && deployedBytecode !== '0x' ? ethers.keccak256(deployedBytecode) : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+async function diagnoseCosmos(chain: string, config: CosmosChainConfig, options: DoctorOptions): Promise<DoctorCheck[]> {
+  let client: CosmosHTLCClient;
+  let chainId: string;
+  let chainTime: number;
+  const started = Date.now();
+  try {
+    client = await CosmosHTLCClient.connect(config);
+    [chainId, chainTime] = await Promise.all([client.getClient().getChainId(), client.getCurrentTime()]);
+  } catch (error) {
+    return [unreachable(chain, config.rpcUrl, error)];
+  }
+
+  try {
+    const checks = [rpcCheck(chain, Date.now() - started, chainId, String(config.chainId))];
+    checks.push(await cosmosCodeCheck(chain, config, client));
+    checks.push(await keyCheck(chain, options.key, async () => {
+      const { client: signing, address } = await cosmosSigningClient(chain, options.key);
+      signing.getClient().disconnect();
+      return { address, balance: BigInt(await client.getBalance(address)), unit: config.nativeDenom };
+    }));
+    checks.push(clockCheck(chain, chainTime, options));
+    return checks;
+  } finally {
+    client.getClient().disconnect();
+  }
+}
+
+/** The stored code's checksum against the one recorded when it was uploaded */
+async function cosmosCodeCheck(chain: string, config: CosmosChainConfig, client: CosmosHTLCClient): Promise<DoctorCheck> {
+  if (!config.codeId) return { chain, check: 'contract', status: 'warn', detail: 'No code_id configured; see omni deploy wasm' };
+
+  let checksum: string;
+  try {
+    ({ checksum } = await client.getClient().getCodeDetails(Number(config.codeId)));
+  } catch (error) {
+    return { chain, check: 'contract', status: 'fail', detail: `Code ${config.codeId} not found (${(error as Error).message})` };
+  }
+  if (!config.codeChecksum) return { chain, check: 'contract', status: 'warn', detail: `Code ${config.codeId} is ${short(checksum)}; no code_checksum to compare with` };
+  return checksum.toLowerCase() === config.codeChecksum.replace(/^0x/, '').toLowerCase()
+    ? { chain, check: 'contract', status: 'ok', detail: `Code ${config.codeId} is ${short(checksum)}` }
+    : { chain, check: 'contract', status: 'fail', detail: `Code ${config.codeId} is ${short(checksum)}, expected ${short(config.codeChecksum)}` };
+}
+
+function rpcCheck(chain: string, ms: number, actual: string, expected: string): DoctorCheck {
+  return actual === expected
+    ? { chain, check: 'rpc', status: 'ok', detail: `Chain ID ${actual} in ${ms}ms`, ms }
+    : { chain, check: 'rpc', status: 'fail', detail: `RPC serves chain ID ${actual}, expected ${expected}`, ms };
+}
+
+function unreachable(chain: string, rpcUrl: string, error: unknown): DoctorCheck {
+  return { chain, check: 'rpc', status: 'fail', detail: `${rpcUrl} unreachable: ${(error as Error).message}` };
+}
+
+/** A key that cannot be found is only a warning: watching and verifying need none */
+async function keyCheck(
+  chain: string,
+  key: string | undefined,
+  probe: () => Promise<{ address: string; balance: bigint; unit: string }>
+): Promise<DoctorCheck> {
+  try {
+    if (!hasSigner(await credentialsFor(chain, key))) {
+      return { chain, check: 'key', status: 'warn', detail: `No key; pass --key <name> or set ${keyEnvFor(chain)}` };
+    }
+    const { address, balance, unit } = await probe();
+    return balance > 0n
+      ? { chain, check: 'key', status: 'ok', detail: `${address} holds ${balance} ${unit}` }
+      : { chain, check: 'key', status: 'warn', detail: `${address} has no gas; see omni faucet request` };
+  } catch (error) {
+    return { chain, check: 'key', status: 'fail', detail: (error as Error).message };
+  }
+}
+
+function clockCheck(chain: string, chainTime: number, options: DoctorOptions): DoctorCheck {
+  const skew = Date.now() / 1000 - chainTime;
+  const limit = options.maxSkewSeconds ?? DEFAULT_MAX_SKEW_SECONDS;
+  const ms = Math.round(skew * 1000);
+  // The latest block is up to a block time old, so a lagging chain is expected
+  const describe = `${skew >= 0 ? 'ahead of' : 'behind'} the latest block by ${Math.abs(skew).toFixed(1)}s`;
+  return Math.abs(skew) <= limit
+    ? { chain, check: 'clock', status: 'ok', detail: `Local clock ${describe}`, ms }
+    : { chain, check: 'clock', status: 'fail', detail: `Local clock ${describe}, over the ${limit}s timelocks allow for; check NTP or whether the chain has halted`, ms };
+}
+
+function short(hash: string): string {
+  return hash.length > 14 ? `${hash.slice(0, 10)}…` : hash;
+}
@@ -0,0 +1,131 @@
+import axios from 'axios';
+import { ethers } from 'ethers';
+import type { ChainConfig, CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { activeProfile } from './omni-config';
+import type { FaucetSettings } from './omni-config';
+import { requireChain } from './omni-chains';
+
+export interface FaucetGrant {
+  chain: string;
+  address: string;
+  /** Denom, token address, or "native" */
+  asset: string;
+  /** Base units, when the faucet says or omni sent it */
+  amount?: string;
+  txHash?: string;
+  via: 'faucet' | 'localnet';
+}
+
+// What the built-in localnet faucet hands out
+const LOCAL_EVM_BALANCE = ethers.parseEther('100');
+const LOCAL_TOKEN_AMOUNT = ethers.parseEther('1000');
+const LOCAL_COSMOS_AMOUNT = '100000000';
+
+/**
+ * A chain omni may fund itself: an RPC on this machine, or Hardhat / Anvil's
+ * default chain ID
+ */
+export function isLocalnet(config: ChainConfig): boolean {
+  const host = (() => {
+    try {
+      return new URL(config.rpcUrl).hostname;
+    } catch {
+      return '';
+    }
+  })();
+  return ['localhost', '127.0.0.1', '0.0.0.0', '::1'].includes(host) || config.chainId === 31337 || config.chainId === 1337;
+}
+
+/**
+ * Fund `address` with gas and test tokens: through the profile's faucet for
+ * the chain when there is one, else directly on a localnet. Public testnets
+ * without a faucet API get an error pointing at their web faucet.
+ */
+export async function requestFaucet(chain: string, address: string, assets?: string[]): Promise<FaucetGrant[]> {
+  const config = requireChain(chain);
+  const settings: FaucetSettings = activeProfile()?.faucets?.[chain] ?? {};
+  const wanted = assets?.length ? assets : settings.assets?.length ? settings.assets : ['native'];
+
+  if (settings.url) {
+    const grants: FaucetGrant[] = [];
+    for (const asset of wanted) grants.push(await askFaucet(chain, config, settings, address, asset));
+    return grants;
+  }
+  if (isLocalnet(config)) {
+    return config.type === 'evm'
+      ? fundLocalEvm(chain, config as EvmChainConfig, address, wanted)
+      : fundLocalCosmos(chain, config as CosmosChainConfig, address, wanted, settings);
+  }
+  throw new Error(`No faucet API configured for ${chain}${config.faucetUrl ? `; request funds by hand at ${config.faucetUrl}` : ''}, or add [profiles.<name>.faucets.${chain}] url = "..."`);
+}
+
+async function askFaucet(chain: string, config: ChainConfig, settings: FaucetSettings, address: string, asset: string): Promise<FaucetGrant> {
+  const url = settings.url!.replace(/\/+$/, '');
+  if (settings.kind === 'cosmjs') {
+    const denom = asset === 'native' ? config.nativeDenom : asset;
+    await axios.post(`${url}/credit`, { address, denom }, { timeout: 60000 });
+    return { chain, address, asset: denom ?? asset, via: 'faucet' };
+  }
+
+  const response = await axios.post(url, {
+    address,
+    chain,
+    chainId: config.chainId,
+    ...(asset === 'native' ? {} : { token: asset })
+  }, { timeout: 60000 });
+  const body = (response.data ?? {}) as { txHash?: unknown; amount?: unknown };
+  return {
+    chain,
+    address,
+    asset,
+    amount: body.amount !== undefined ? String(body.amount) : undefined,
+    txHash: typeof body.txHash === 'string' ? body.txHash : undefined,
+    via: 'faucet'
+  };
+}
+
+/**
+ * Hardhat / Anvil: set the native balance with the node's dev RPC, and mint
+ * tokens from the node's first unlocked account, which deployed them
+ */
+async function fundLocalEvm(chain: string, config: EvmChainConfig, address: string, assets: string[]): Promise<FaucetGrant[]> {
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, config.chainId);
+  try {
+    const grants: FaucetGrant[] = [];
+    for (const asset of assets) {
+      if (asset === 'native') {
+        const balance = ethers.toQuantity(LOCAL_EVM_BALANCE);
+        await provider.send('hardhat_setBalance', [address, balance])
+          .catch(() => provider.send('anvil_setBalance', [address, balance]))
+          .catch(() => { throw new Error(`${chain} supports neither hardhat_setBalance nor anvil_setBalance; configure a faucet for it`); });
+        grants.push({ chain, address, asset, amount: LOCAL_EVM_BALANCE.toString(), via: 'localnet' });
+        continue;
+      }
+
+      const token = new ethers.Contract(asset, ['function mint(address to, uint256 amount)'], await provider.getSigner(0));
+      const tx = await token.mint(address, LOCAL_TOKEN_AMOUNT);
+      await tx.wait();
+      grants.push({ chain, address, asset, amount: LOCAL_TOKEN_AMOUNT.toString(), txHash: tx.hash, via: 'localnet' });
+    }
+    return grants;
+  } finally {
+    provider.destroy();
+  }
+}
+
+/** A localnet's genesis account sends native denoms with a bank transfer */
+async function fundLocalCosmos(chain: string, config: CosmosChainConfig, address: string, assets: string[], settings: FaucetSettings): Promise<FaucetGrant[]> {
+  const env = settings.mnemonicEnv ?? 'OMNI_FAUCET_MNEMONIC';
+  const mnemonic = process.env[env];
+  if (!mnemonic) throw new Error(`Set ${env} to the mnemonic of a funded ${chain} genesis account to fund from`);
+
+  const { client, address: from } = await CosmosHTLCClient.fromMnemonic(config, mnemonic);
+  try {
+    const coins = assets.map(asset => ({ denom: asset === 'native' ? config.nativeDenom : asset, amount: LOCAL_COSMOS_AMOUNT }));
+    const result = await client.getSigningClient()!.sendTokens(from, address, coins, 'auto', 'omni faucet');
+    return coins.map(coin => ({ chain, address, asset: coin.denom, amount: coin.amount, txHash: result.transactionHash, via: 'localnet' as const }));
+  } finally {
+    client.getClient().disconnect();
+  }
+}
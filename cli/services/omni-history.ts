@@ -0,0 +1,167 @@
+import axios from 'axios';
+import { cosmosChains, evmChains } from '../../packages/shared/src/chains';
+import { CosmosBackfiller, EvmBackfiller } from '../../packages/shared/src/resolver/backfill';
+import type { BackfilledHTLC } from '../../packages/shared/src/resolver/backfill';
+import { addressKind } from '../../packages/shared/src/sdk';
+import { activeProfile } from './omni-config';
+import { requireChain } from './omni-chains';
+
+export type HistoryState = 'open' | 'expired' | 'claimed' | 'refunded';
+
+/** One HTLC the address funded or is paid by */
+export interface HistoryEntry extends BackfilledHTLC {
+  role: 'sender' | 'beneficiary';
+  /** Unix seconds of the funding block */
+  fundedTime: number;
+}
+
+export interface HistoryQuery {
+  address: string;
+  chains: string[];
+  states?: HistoryState[];
+  /** Unix seconds, inclusive */
+  since?: number;
+  until?: number;
+}
+
+export interface ScanRange {
+  /** First block to scan; defaults to `blocks` before the head */
+  fromHeight?: number;
+  blocks: number;
+  chunkSize?: number;
+}
+
+/** $OMNI_INDEXER_URL, else the active profile's indexer_url */
+export function indexerUrl(): string | undefined {
+  return process.env.OMNI_INDEXER_URL || activeProfile()?.indexerUrl;
+}
+
+/**
+ * Registry chains an address can hold HTLCs on: EVM chains for 0x addresses,
+ * Cosmos chains with a matching bech32 prefix otherwise
+ */
+export function chainsForAddress(address: string): string[] {
+  if (addressKind(address) === 'evm') return Object.keys(evmChains);
+  const prefix = address.slice(0, address.lastIndexOf('1'));
+  return Object.entries(cosmosChains).filter(([, chain]) => chain.addressPrefix === prefix).map(([name]) => name);
+}
+
+/**
+ * History from the indexer's `GET /htlcs`, which takes the query as
+ * parameters (address, chain, state, since, until) and answers
+ * `{ htlcs: HistoryEntry[] }`
+ */
+export async function queryIndexer(url: string, query: HistoryQuery): Promise<HistoryEntry[]> {
+  const response = await axios.get<{ htlcs: HistoryEntry[] }>(`${url.replace(/\/$/, '')}/htlcs`, {
+    params: {
+      address: query.address,
+      chain: query.chains.join(','),
+      state: query.states?.join(','),
+      since: query.since,
+      until: query.until
+    },
+    timeout: 30000
+  });
+  // Filter again, in case the indexer ignores a parameter
+  return filterHistory(response.data.htlcs, query, Math.floor(Date.now() / 1000));
+}
+
+/** A read-only backfiller for a registry chain, for scanning without the resolver database */
+export function backfillerFor(chain: string): EvmBackfiller | CosmosBackfiller {
+  return evmChains[chain]
+    ? new EvmBackfiller(chain, evmChains[chain], null)
+    : new CosmosBackfiller(chain, cosmosChains[chain], null);
+}
+
+/** Blocks scanned beyond an age, for clock drift and slow blocks */
+const SCAN_MARGIN_BLOCKS = 500;
+
+function normalizeHashLock(hashLock: string): string {
+  return hashLock.toLowerCase().replace(/^0x/, '');
+}
+
+/**
+ * Every HTLC under `hashLock` on `chains`, scanning back to `since` (Unix
+ * seconds) as estimated from each chain's block time, or `blocks` blocks
+ */
+export async function scanHashLock(hashLock: string, chains: string[], since: number, blocks?: number): Promise<BackfilledHTLC[]> {
+  const wanted = normalizeHashLock(hashLock);
+  const age = Math.max(0, Math.floor(Date.now() / 1000) - since);
+  const found: BackfilledHTLC[] = [];
+  for (const chain of new Set(chains)) {
+    const backfiller = backfillerFor(chain);
+    const toHeight = await backfiller.latestHeight();
+    const span = blocks ?? Math.ceil(age / (requireChain(chain).blockTimeSeconds ?? 2)) + SCAN_MARGIN_BLOCKS;
+    const htlcs = await backfiller.scan(Math.max(1, toHeight - span + 1), toHeight, 2000);
+    found.push(...htlcs.filter(htlc => normalizeHashLock(htlc.hashLock) === wanted));
+  }
+  return found;
+}
+
+/**
+ * History rebuilt from the chains' HTLC events in a block range, as the
+ * resolver's backfill does. Slow on long ranges, and blind to HTLCs funded
+ * before the range unless they settled inside it.
+ */
+export async function scanChains(query: HistoryQuery, range: ScanRange, onChain?: (chain: string, fromHeight: number, toHeight: number) => void): Promise<HistoryEntry[]> {
+  const entries: HistoryEntry[] = [];
+  for (const chain of query.chains) {
+    const backfiller = backfillerFor(chain);
+    const toHeight = await backfiller.latestHeight();
+    const fromHeight = range.fromHeight ?? Math.max(1, toHeight - range.blocks + 1);
+    onChain?.(chain, fromHeight, toHeight);
+
+    const htlcs = await backfiller.scan(fromHeight, toHeight, range.chunkSize ?? 2000);
+    const address = query.address.toLowerCase();
+    for (const htlc of htlcs) {
+      const role = htlc.originator.toLowerCase() === address ? 'sender'
+        : htlc.beneficiary.toLowerCase() === address ? 'beneficiary'
+        : null;
+      if (!role) continue;
+      entries.push({ ...htlc, role, fundedTime: await backfiller.blockTime(htlc.fundedAt.height) });
+    }
+  }
+  return filterHistory(entries, query, Math.floor(Date.now() / 1000));
+}
+
+export function historyState(entry: BackfilledHTLC, now: number): HistoryState {
+  if (entry.state === 'OPEN') return entry.timelock < now ? 'expired' : 'open';
+  return entry.state === 'CLAIMED' ? 'claimed' : 'refunded';
+}
+
+function filterHistory(entries: HistoryEntry[], query: HistoryQuery, now: number): HistoryEntry[] {
+  return entries
+    .filter(entry => !query.states?.length || query.states.includes(historyState(entry, now)))
+    .filter(entry => query.since === undefined || entry.fundedTime >= query.since)
+    .filter(entry => query.until === undefined || entry.fundedTime <= query.until)
+    .sort((a, b) => a.fundedTime - b.fundedTime);
+}
+
+const CSV_COLUMNS = [
+  'fundedTime', 'chain', 'contractId', 'role', 'state', 'originator', 'beneficiary',
+  'token', 'value', 'hashLock', 'timelock', 'fundTx', 'settleTx'
+] as const;
+
+/** One row per HTLC, with ISO timestamps, for spreadsheets and accounting tools */
+export function historyCsv(entries: HistoryEntry[], now: number): string {
+  const rows = entries.map(entry => [
+    new Date(entry.fundedTime * 1000).toISOString(),
+    entry.chain,
+    entry.contractId,
+    entry.role,
+    historyState(entry, now),
+    entry.originator,
+    entry.beneficiary,
+    entry.token,
+    entry.value,
+    entry.hashLock,
+    new Date(entry.timelock * 1000).toISOString(),
+    entry.fundedAt.txHash,
+    entry.settledTxHash ?? ''
+  ]);
+  return [CSV_COLUMNS, ...rows].map(row => row.map(csvField).join(',')).join('\n') + '\n';
+}
+
+export function csvField(value: string): string {
+  return /[",\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
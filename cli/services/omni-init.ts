@@ -0,0 +1,137 @@
+import axios from 'axios';
+import { cosmosChains, evmChains } from '../../packages/shared/src/chains';
+import type { CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+
+export const COSMOS_REGISTRY_URL = 'https://raw.githubusercontent.com/cosmos/chain-registry/master';
+export const EVM_CHAIN_LIST_URL = 'https://chainid.network/chains.json';
+
+const PROBE_TIMEOUT_MS = 5000;
+/** RPC endpoints tried per chain before settling on the first listed */
+const MAX_PROBES = 5;
+
+/** Chain settings read from a registry, ready to be written to a profile */
+export interface ScaffoldedChain {
+  /** Key in the profile's chains table */
+  key: string;
+  config: Partial<EvmChainConfig> | Partial<CosmosChainConfig>;
+  /** Whether the RPC answered with the expected chain ID */
+  rpcVerified: boolean;
+}
+
+// The parts of a chain-registry chain.json read here
+interface RegistryChain {
+  chain_name: string;
+  pretty_name?: string;
+  chain_id: string;
+  bech32_prefix: string;
+  fees?: { fee_tokens?: Array<{ denom: string; average_gas_price?: number; fixed_min_gas_price?: number }> };
+  staking?: { staking_tokens?: Array<{ denom: string }> };
+  apis?: { rpc?: Array<{ address: string }> };
+  explorers?: Array<{ url?: string }>;
+}
+
+// One entry of the ethereum-lists chains.json
+interface ListedEvmChain {
+  name: string;
+  shortName?: string;
+  chainId: number;
+  rpc: string[];
+  faucets?: string[];
+  explorers?: Array<{ url: string }>;
+}
+
+/**
+ * A Cosmos chain from the chain registry, by its directory name (`osmosis`,
+ * `osmosistestnet`); testnets live under `testnets/`
+ */
+export async function scaffoldCosmosChain(name: string, registryUrl = COSMOS_REGISTRY_URL): Promise<ScaffoldedChain> {
+  const base = registryUrl.replace(/\/$/, '');
+  let chain: RegistryChain | undefined;
+  for (const candidate of [`${base}/${name}/chain.json`, `${base}/testnets/${name}/chain.json`]) {
+    try {
+      chain = (await axios.get<RegistryChain>(candidate, { timeout: 15000 })).data;
+      break;
+    } catch (error) {
+      if (!axios.isAxiosError(error) || error.response?.status !== 404) throw error;
+    }
+  }
+  if (!chain) throw new Error(`${name} is not in the chain registry at ${registryUrl}`);
+
+  const feeToken = chain.fees?.fee_tokens?.[0];
+  if (!feeToken) throw new Error(`${name} lists no fee tokens in the chain registry`);
+  const nativeDenom = chain.staking?.staking_tokens?.[0]?.denom ?? feeToken.denom;
+  const gasPrice = feeToken.average_gas_price ?? feeToken.fixed_min_gas_price;
+
+  const rpcs = (chain.apis?.rpc ?? []).map(rpc => rpc.address.replace(/\/$/, ''));
+  const { rpcUrl, verified } = await pickRpc(rpcs, url => cosmosNetwork(url).then(network => network === chain!.chain_id));
+  const existing = Object.entries(cosmosChains).find(([, config]) => config.chainId === chain!.chain_id)?.[0];
+
+  return {
+    key: existing ?? chain.chain_name,
+    config: {
+      type: 'cosmos',
+      name: chain.pretty_name ?? chain.chain_name,
+      chainId: chain.chain_id,
+      rpcUrl,
+      addressPrefix: chain.bech32_prefix,
+      nativeDenom,
+      gasPrice: gasPrice === undefined ? undefined : String(gasPrice),
+      gasDenom: feeToken.denom === nativeDenom ? undefined : feeToken.denom,
+      blockExplorer: chain.explorers?.find(explorer => explorer.url)?.url
+    },
+    rpcVerified: verified
+  };
+}
+
+/** An EVM chain from the ethereum-lists chain list, by chain ID, name or short name */
+export async function scaffoldEvmChain(wanted: string, listUrl = EVM_CHAIN_LIST_URL): Promise<ScaffoldedChain> {
+  const list = (await axios.get<ListedEvmChain[]>(listUrl, { timeout: 30000 })).data;
+  const needle = wanted.toLowerCase();
+  const chain = /^\d+$/.test(wanted)
+    ? list.find(candidate => candidate.chainId === Number(wanted))
+    : list.find(candidate => candidate.name.toLowerCase() === needle || candidate.shortName?.toLowerCase() === needle);
+  if (!chain) throw new Error(`${wanted} is not in the EVM chain list at ${listUrl}`);
+
+  // Endpoints needing an API key are templated as ${INFURA_API_KEY}; websockets are no use to the adapters
+  const rpcs = chain.rpc.filter(url => /^https?:\/\//.test(url) && !url.includes('${'));
+  const { rpcUrl, verified } = await pickRpc(rpcs, url => evmChainId(url).then(id => id === chain.chainId));
+  const existing = Object.entries(evmChains).find(([, config]) => config.chainId === chain.chainId)?.[0];
+
+  return {
+    key: existing ?? camelKey(chain.name),
+    config: {
+      type: 'evm',
+      name: chain.name,
+      chainId: chain.chainId,
+      rpcUrl,
+      blockExplorer: chain.explorers?.[0]?.url,
+      faucetUrl: chain.faucets?.find(url => !url.includes('${'))
+    },
+    rpcVerified: verified
+  };
+}
+
+/** The first endpoint that answers for the right chain, else the first listed */
+async function pickRpc(urls: string[], probe: (url: string) => Promise<boolean>): Promise<{ rpcUrl: string; verified: boolean }> {
+  if (urls.length === 0) throw new Error('The registry lists no usable RPC endpoint');
+  for (const url of urls.slice(0, MAX_PROBES)) {
+    if (await probe(url).catch(() => false)) return { rpcUrl: url, verified: true };
+  }
+  return { rpcUrl: urls[0], verified: false };
+}
+
+async function cosmosNetwork(url: string): Promise<string> {
+  const response = await axios.get<{ result: { node_info: { network: string } } }>(`${url}/status`, { timeout: PROBE_TIMEOUT_MS });
+  return response.data.result.node_info.network;
+}
+
+async function evmChainId(url: string): Promise<number> {
+  const response = await axios.post<{ result: string }>(url, { jsonrpc: '2.0', id: 1, method: 'eth_chainId', params: [] }, { timeout: PROBE_TIMEOUT_MS });
+  return Number(response.data.result);
+}
+
+// 'Arbitrum Sepolia' -> 'arbitrumSepolia', like the built-in chain keys
+function camelKey(name: string): string {
+  const words = name.replace(/[^A-Za-z0-9]+/g, ' ').trim().split(' ');
+  return words.map((word, i) => i === 0 ? word.toLowerCase() : word[0].toUpperCase() + word.slice(1).toLowerCase()).join('');
+}
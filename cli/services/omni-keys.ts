@@ -0,0 +1,182 @@
+import { createCipheriv, createDecipheriv, randomBytes, scryptSync } from 'crypto';
+import { promises as fs } from 'fs';
+import path from 'path';
+import inquirer from 'inquirer';
+import { ethers } from 'ethers';
+import { omniHome } from './omni-store';
+
+export type KeyType = 'evm' | 'cosmos';
+export type KeyBackend = 'file' | 'os';
+
+export interface KeyInfo {
+  name: string;
+  type: KeyType;
+  backend: KeyBackend;
+  /** EVM address; Cosmos addresses depend on the chain prefix and are derived when used */
+  address?: string;
+  createdAt: number;
+}
+
+interface Sealed {
+  iv: string;
+  data: string;
+  tag: string;
+}
+
+interface KeyFile {
+  version: 1;
+  kdf: { name: 'scrypt'; salt: string; N: number; r: number; p: number };
+  keys: Record<string, KeyInfo & { sealed?: Sealed }>;
+}
+
+const KDF = { N: 2 ** 15, r: 8, p: 1 };
+const KEYRING_SERVICE = 'omni';
+
+// keytar is optional: it needs a native build and a running secret service
+function loadKeytar(): { getPassword(service: string, account: string): Promise<string | null>; setPassword(service: string, account: string, password: string): Promise<void>; deletePassword(service: string, account: string): Promise<boolean> } {
+  try {
+    // eslint-disable-next-line @typescript-eslint/no-var-requires
+    return require('keytar');
+  } catch {
+    throw new Error('The OS keyring backend needs the optional keytar package; install it or use OMNI_KEYRING_BACKEND=file');
+  }
+}
+
+/**
+ * Named signing keys: EVM private keys and Cosmos mnemonics. Secrets go to the
+ * OS keyring (OMNI_KEYRING_BACKEND=os) or are sealed with AES-256-GCM in
+ * <omni home>/keys.json under a passphrase from OMNI_KEYRING_PASSPHRASE or a
+ * prompt. Names, types and EVM addresses are stored in the clear either way.
+ */
+export class KeyStore {
+  private key?: Buffer;
+
+  private constructor(private file: string, private contents: KeyFile) {}
+
+  static async open(file: string = path.join(omniHome(), 'keys.json')): Promise<KeyStore> {
+    try {
+      return new KeyStore(file, JSON.parse(await fs.readFile(file, 'utf8')));
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== 'ENOENT') throw error;
+      return new KeyStore(file, {
+        version: 1,
+        kdf: { name: 'scrypt', salt: randomBytes(16).toString('hex'), ...KDF },
+        keys: {}
+      });
+    }
+  }
+
+  list(): KeyInfo[] {
+    return Object.values(this.contents.keys).map(({ sealed, ...info }) => info);
+  }
+
+  has(name: string): boolean {
+    return name in this.contents.keys;
+  }
+
+  async add(name: string, type: KeyType, secret: string, backend: KeyBackend = defaultBackend()): Promise<KeyInfo> {
+    if (this.has(name)) throw new Error(`Key ${name} already exists`);
+    const normalized = validateSecret(type, secret);
+    const info: KeyInfo = {
+      name,
+      type,
+      backend,
+      address: type === 'evm' ? new ethers.Wallet(normalized).address : undefined,
+      createdAt: Math.floor(Date.now() / 1000)
+    };
+
+    if (backend === 'os') {
+      await loadKeytar().setPassword(KEYRING_SERVICE, name, normalized);
+      this.contents.keys[name] = info;
+    } else {
+      this.contents.keys[name] = { ...info, sealed: seal(await this.unlock(), Buffer.from(normalized)) };
+    }
+    await this.save();
+    return info;
+  }
+
+  /**
+   * The private key or mnemonic stored under `name`
+   */
+  async secret(name: string): Promise<{ type: KeyType; secret: string }> {
+    const entry = this.contents.keys[name];
+    if (!entry) throw new Error(`No key named ${name}; see omni keys list`);
+
+    if (entry.backend === 'os') {
+      const secret = await loadKeytar().getPassword(KEYRING_SERVICE, name);
+      if (!secret) throw new Error(`Key ${name} is missing from the OS keyring`);
+      return { type: entry.type, secret };
+    }
+    return { type: entry.type, secret: unseal(await this.unlock(), entry.sealed!).toString() };
+  }
+
+  async remove(name: string): Promise<boolean> {
+    const entry = this.contents.keys[name];
+    if (!entry) return false;
+    if (entry.backend === 'os') await loadKeytar().deletePassword(KEYRING_SERVICE, name);
+    delete this.contents.keys[name];
+    await this.save();
+    return true;
+  }
+
+  private async unlock(): Promise<Buffer> {
+    if (this.key) return this.key;
+
+    let passphrase = process.env.OMNI_KEYRING_PASSPHRASE;
+    if (!passphrase) {
+      const answer = await inquirer.prompt([{ type: 'password', name: 'passphrase', message: 'Keyring passphrase:', mask: '*' }]);
+      passphrase = answer.passphrase as string;
+    }
+    const { salt, N, r, p } = this.contents.kdf;
+    const key = scryptSync(passphrase!, Buffer.from(salt, 'hex'), 32, { N, r, p, maxmem: 256 * N * r });
+
+    // Any file-backed entry doubles as the passphrase check
+    const sample = Object.values(this.contents.keys).find(entry => entry.sealed);
+    if (sample) {
+      try {
+        unseal(key, sample.sealed!);
+      } catch {
+        key.fill(0);
+        throw new Error('Wrong keyring passphrase');
+      }
+    }
+    this.key = key;
+    return key;
+  }
+
+  private async save(): Promise<void> {
+    await fs.mkdir(path.dirname(this.file), { recursive: true, mode: 0o700 });
+    const temp = `${this.file}.tmp`;
+    await fs.writeFile(temp, JSON.stringify(this.contents, null, 2), { mode: 0o600 });
+    await fs.rename(temp, this.file);
+  }
+}
+
+export function defaultBackend(): KeyBackend {
+  return process.env.OMNI_KEYRING_BACKEND === 'os' ? 'os' : 'file';
+}
+
+function validateSecret(type: KeyType, secret: string): string {
+  const trimmed = secret.trim();
+  if (type === 'evm') {
+    const hex = trimmed.startsWith('0x') ? trimmed : `0x${trimmed}`;
+    if (!ethers.isHexString(hex, 32)) throw new Error('An EVM key must be a 32-byte hex private key');
+    return hex;
+  }
+  const words = trimmed.split(/\s+/).join(' ');
+  if (!ethers.Mnemonic.isValidMnemonic(words)) throw new Error('A Cosmos key must be a valid BIP-39 mnemonic');
+  return words;
+}
+
+function seal(key: Buffer, plaintext: Buffer): Sealed {
+  const iv = randomBytes(12);
+  const cipher = createCipheriv('aes-256-gcm', key, iv);
+  const data = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+  return { iv: iv.toString('hex'), data: data.toString('hex'), tag: cipher.getAuthTag().toString('hex') };
+}
+
+function unseal(key: Buffer, sealed: Sealed): Buffer {
+  const decipher = createDecipheriv('aes-256-gcm', key, Buffer.from(sealed.iv, 'hex'));
+  decipher.setAuthTag(Buffer.from(sealed.tag, 'hex'));
+  return Buffer.concat([decipher.update(Buffer.from(sealed.data, 'hex')), decipher.final()]);
+}
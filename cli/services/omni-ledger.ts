@@ -0,0 +1,129 @@
+import { ethers } from 'ethers';
+import type { ChainConfig, CosmosChainConfig } from '../../packages/shared/src/chains';
+import type { AdapterCredentials } from '../../packages/shared/src/resolver/adapters';
+
+interface LedgerEthApp {
+  getAddress(path: string, display?: boolean): Promise<{ address: string }>;
+  signTransaction(path: string, rawTxHex: string, resolution?: null): Promise<{ v: string; r: string; s: string }>;
+  signPersonalMessage(path: string, messageHex: string): Promise<{ v: number; r: string; s: string }>;
+}
+
+interface LedgerCosmosSigner {
+  getAccounts(): Promise<readonly { address: string; algo: string; pubkey: Uint8Array }[]>;
+  signAmino(address: string, signDoc: unknown): Promise<any>;
+  showAddress(path?: unknown): Promise<{ address: string }>;
+}
+
+let account: number | undefined;
+let transport: Promise<unknown> | undefined;
+
+/** Sign with the Ledger account at this index (the `--ledger` flag) instead of a stored key */
+export function useLedger(index: number | undefined): void {
+  account = index;
+}
+
+export function ledgerEnabled(): boolean {
+  return account !== undefined;
+}
+
+// The Ledger packages are optional: node-hid needs a native build
+function load<T>(name: string): T {
+  try {
+    // eslint-disable-next-line @typescript-eslint/no-var-requires
+    return require(name);
+  } catch {
+    throw new Error(`--ledger needs the optional ${name} package; install the cli's optional dependencies`);
+  }
+}
+
+/** One USB connection per process; the device allows no more */
+function openTransport(): Promise<unknown> {
+  transport ??= load<{ default: { create(): Promise<unknown> } }>('@ledgerhq/hw-transport-node-hid').default.create()
+    .catch((error: Error) => {
+      transport = undefined;
+      throw new Error(`Cannot reach the Ledger (${error.message}); connect and unlock it, and open the Ethereum or Cosmos app`);
+    });
+  return transport;
+}
+
+function evmPath(): string {
+  return `44'/60'/${account ?? 0}'/0/0`;
+}
+
+async function ethApp(): Promise<LedgerEthApp> {
+  const Eth = load<{ default: new (transport: unknown) => LedgerEthApp }>('@ledgerhq/hw-app-eth').default;
+  return new Eth(await openTransport());
+}
+
+async function cosmosSigner(config: CosmosChainConfig): Promise<LedgerCosmosSigner> {
+  const { LedgerSigner } = load<{ LedgerSigner: new (transport: unknown, options: unknown) => LedgerCosmosSigner }>('@cosmjs/ledger-amino');
+  const { makeCosmoshubPath } = load<{ makeCosmoshubPath(index: number): unknown }>('@cosmjs/amino');
+  return new LedgerSigner(await openTransport(), { hdPaths: [makeCosmoshubPath(account ?? 0)], prefix: config.addressPrefix });
+}
+
+/**
+ * An ethers signer backed by the Ledger Ethereum app. Transactions to the
+ * HTLC carry contract data, so the app needs blind signing enabled.
+ */
+export class LedgerEvmSigner extends ethers.AbstractSigner {
+  constructor(private app: LedgerEthApp, private path: string, readonly address: string, provider: ethers.Provider | null = null) {
+    super(provider);
+  }
+
+  async getAddress(): Promise<string> {
+    return this.address;
+  }
+
+  connect(provider: ethers.Provider | null): LedgerEvmSigner {
+    return new LedgerEvmSigner(this.app, this.path, this.address, provider);
+  }
+
+  async signTransaction(request: ethers.TransactionRequest): Promise<string> {
+    const to = request.to ? await ethers.resolveAddress(request.to, this.provider) : request.to;
+    const from = request.from ? await ethers.resolveAddress(request.from, this.provider) : undefined;
+    if (from && from.toLowerCase() !== this.address.toLowerCase()) {
+      throw new Error(`Transaction is from ${from}, not the Ledger account ${this.address}`);
+    }
+
+    const fields = { ...request, to };
+    delete fields.from;
+    const tx = ethers.Transaction.from(fields as ethers.TransactionLike<string>);
+    const signature = await this.app.signTransaction(this.path, tx.unsignedSerialized.slice(2), null);
+    tx.signature = ethers.Signature.from({ r: `0x${signature.r}`, s: `0x${signature.s}`, v: Number(`0x${signature.v}`) });
+    return tx.serialized;
+  }
+
+  async signMessage(message: string | Uint8Array): Promise<string> {
+    const bytes = typeof message === 'string' ? ethers.toUtf8Bytes(message) : message;
+    const signature = await this.app.signPersonalMessage(this.path, ethers.hexlify(bytes).slice(2));
+    return ethers.Signature.from({ r: `0x${signature.r}`, s: `0x${signature.s}`, v: signature.v }).serialized;
+  }
+
+  async signTypedData(): Promise<string> {
+    throw new Error('omni does not sign typed data with a Ledger');
+  }
+}
+
+/** Adapter credentials that sign on the Ledger, for a registry chain config */
+export async function ledgerCredentials(config: ChainConfig): Promise<AdapterCredentials> {
+  if (config.type === 'evm') {
+    const app = await ethApp();
+    const { address } = await app.getAddress(evmPath(), false);
+    return { evmSigner: new LedgerEvmSigner(app, evmPath(), ethers.getAddress(address)) };
+  }
+  // Structurally an OfflineAminoSigner
+  return { cosmosSigner: await cosmosSigner(config as CosmosChainConfig) as unknown as AdapterCredentials['cosmosSigner'] };
+}
+
+/**
+ * Show the Ledger account's address on the device for the user to compare
+ * with the one printed, and return it
+ */
+export async function showLedgerAddress(config: ChainConfig): Promise<string> {
+  if (config.type === 'evm') {
+    const { address } = await (await ethApp()).getAddress(evmPath(), true);
+    return ethers.getAddress(address);
+  }
+  const { address } = await (await cosmosSigner(config as CosmosChainConfig)).showAddress();
+  return address;
+}
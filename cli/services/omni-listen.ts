@@ -0,0 +1,151 @@
+import { promises as fs } from 'fs';
+import { createHmac } from 'crypto';
+import axios from 'axios';
+import type { BackfilledHTLC } from '../../packages/shared/src/resolver/backfill';
+import { backfillerFor } from './omni-history';
+
+export type HtlcEventType = 'created' | 'claimed' | 'refunded';
+
+export interface HtlcEvent {
+  type: HtlcEventType;
+  chain: string;
+  contractId: string;
+  height: number;
+  txHash: string;
+  originator: string;
+  beneficiary: string;
+  token: string;
+  value: string;
+  hashLock: string;
+  timelock: number;
+  /** Revealed secret, on claimed events */
+  preimage?: string;
+}
+
+export interface EventFilter {
+  types?: HtlcEventType[];
+  /** Originator or beneficiary */
+  addresses?: string[];
+  hashLocks?: string[];
+  contractIds?: string[];
+}
+
+export interface ListenOptions {
+  /** First block per chain when the cursor has none; defaults to the next block */
+  fromHeight?: number;
+  /** Blocks to stay behind the head, so events are not sent for blocks that may reorg */
+  confirmations?: number;
+  intervalMs: number;
+  /** JSON file of the next height per chain, saved after each delivered batch */
+  cursorFile?: string;
+  filter?: EventFilter;
+  /** Returns true once listening should stop */
+  stopped: () => boolean;
+}
+
+/**
+ * The events in a block range from the HTLCs a backfill scan rebuilt from
+ * it, in block order. An HTLC funded before the range only yields its
+ * settlement.
+ */
+export function htlcEvents(htlcs: BackfilledHTLC[], fromHeight: number): HtlcEvent[] {
+  const events: HtlcEvent[] = [];
+  for (const htlc of htlcs) {
+    const fields = {
+      chain: htlc.chain,
+      contractId: htlc.contractId,
+      originator: htlc.originator,
+      beneficiary: htlc.beneficiary,
+      token: htlc.token,
+      value: htlc.value,
+      hashLock: htlc.hashLock,
+      timelock: htlc.timelock
+    };
+    if (htlc.fundedAt.txHash && htlc.fundedAt.height >= fromHeight) {
+      events.push({ type: 'created', height: htlc.fundedAt.height, txHash: htlc.fundedAt.txHash, ...fields });
+    }
+    if (htlc.state !== 'OPEN' && htlc.settledHeight !== undefined) {
+      events.push({
+        type: htlc.state === 'CLAIMED' ? 'claimed' : 'refunded',
+        height: htlc.settledHeight,
+        txHash: htlc.settledTxHash ?? '',
+        ...fields,
+        ...(htlc.preimage ? { preimage: htlc.preimage } : {})
+      });
+    }
+  }
+  // Stable, so an HTLC created and settled in one block keeps that order
+  return events.sort((a, b) => a.height - b.height);
+}
+
+export function matchesFilter(event: HtlcEvent, filter: EventFilter = {}): boolean {
+  const lower = (values?: string[]) => values?.map(value => value.toLowerCase());
+  const addresses = lower(filter.addresses);
+  const hashLocks = lower(filter.hashLocks);
+  const contractIds = lower(filter.contractIds);
+  return (!filter.types?.length || filter.types.includes(event.type))
+    && (!addresses?.length || addresses.includes(event.originator.toLowerCase()) || addresses.includes(event.beneficiary.toLowerCase()))
+    && (!hashLocks?.length || hashLocks.includes(event.hashLock.toLowerCase()))
+    && (!contractIds?.length || contractIds.includes(event.contractId.toLowerCase()));
+}
+
+/**
+ * Poll each chain for new HTLC events and hand them to `deliver` in block
+ * order until `stopped()`. The cursor only moves past a batch once every
+ * event in it was delivered, so a failed delivery is retried on restart
+ * (at-least-once).
+ */
+export async function listen(chains: string[], options: ListenOptions, deliver: (event: HtlcEvent) => Promise<void>): Promise<void> {
+  const cursor: Record<string, number> = options.cursorFile ? await readCursor(options.cursorFile) : {};
+  const backfillers = Object.fromEntries(chains.map(chain => [chain, backfillerFor(chain)]));
+
+  for (const chain of chains) {
+    cursor[chain] ??= options.fromHeight ?? (await backfillers[chain].latestHeight()) + 1;
+  }
+
+  while (!options.stopped()) {
+    for (const chain of chains) {
+      const head = (await backfillers[chain].latestHeight()) - (options.confirmations ?? 0);
+      const from = cursor[chain];
+      if (head < from) continue;
+
+      const htlcs = await backfillers[chain].scan(from, head, 2000);
+      for (const event of htlcEvents(htlcs, from)) {
+        if (matchesFilter(event, options.filter)) await deliver(event);
+      }
+      cursor[chain] = head + 1;
+      if (options.cursorFile) await fs.writeFile(options.cursorFile, JSON.stringify(cursor, null, 2) + '\n');
+      if (options.stopped()) return;
+    }
+    await new Promise(resolve => setTimeout(resolve, options.intervalMs));
+  }
+}
+
+async function readCursor(file: string): Promise<Record<string, number>> {
+  try {
+    return JSON.parse(await fs.readFile(file, 'utf8'));
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return {};
+    throw error;
+  }
+}
+
+/**
+ * POST one event to a webhook, retrying with backoff. With a secret the body
+ * is signed: `X-Omni-Signature: sha256=<hex HMAC of the body>`.
+ */
+export async function postWebhook(url: string, event: HtlcEvent, secret?: string, attempts = 4): Promise<void> {
+  const body = JSON.stringify(event);
+  const headers: Record<string, string> = { 'Content-Type': 'application/json' };
+  if (secret) headers['X-Omni-Signature'] = `sha256=${createHmac('sha256', secret).update(body).digest('hex')}`;
+
+  for (let attempt = 1; ; attempt++) {
+    try {
+      await axios.post(url, body, { headers, timeout: 10000 });
+      return;
+    } catch (error) {
+      if (attempt >= attempts) throw new Error(`Webhook ${url} failed for ${event.type} ${event.contractId}: ${(error as Error).message}`);
+      await new Promise(resolve => setTimeout(resolve, 1000 * 2 ** (attempt - 1)));
+    }
+  }
+}
@@ -0,0 +1,82 @@
+import type { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+
+type SigningClient = NonNullable<ReturnType<CosmosHTLCClient['getSigningClient']>>;
+
+/** The cw2 `contract_info` item every CosmWasm contract should store */
+export interface Cw2Version {
+  contract: string;
+  version: string;
+}
+
+export interface MigrationCheck {
+  contract: string;
+  admin: string;
+  codeId: number;
+  current: Cw2Version | null;
+  /** Why this instance must not be migrated; unset when it may */
+  refusal?: string;
+}
+
+/** Raw storage key of cw2's CONTRACT item */
+const CW2_KEY = new TextEncoder().encode('contract_info');
+
+export async function readCw2(client: SigningClient, contract: string): Promise<Cw2Version | null> {
+  const raw = await client.queryContractRaw(contract, CW2_KEY);
+  return raw && raw.length ? JSON.parse(new TextDecoder().decode(raw)) : null;
+}
+
+/**
+ * Whether `from` can move to `to` without a breaking change: no downgrade, and
+ * no change to the leading non-zero component (major, or minor for 0.x,
+ * patch for 0.0.x), as cargo treats semver compatibility
+ */
+export function compatibleUpgrade(from: string, to: string): { ok: boolean; breaking?: boolean; reason?: string } {
+  const parse = (version: string) => {
+    const match = /^v?(\d+)\.(\d+)\.(\d+)/.exec(version);
+    if (!match) throw new Error(`"${version}" is not a semver version`);
+    return match.slice(1, 4).map(Number);
+  };
+  const a = parse(from);
+  const b = parse(to);
+
+  const order = a[0] - b[0] || a[1] - b[1] || a[2] - b[2];
+  if (order > 0) return { ok: false, reason: `${to} is older than the deployed ${from}` };
+  if (order === 0) return { ok: false, reason: `already at ${from}` };
+
+  const leading = a.findIndex(part => part !== 0);
+  const index = leading === -1 ? 2 : leading;
+  if (a[index] !== b[index] || a.slice(0, index).some((part, i) => part !== b[i])) {
+    return { ok: false, breaking: true, reason: `${from} → ${to} is a breaking version change` };
+  }
+  return { ok: true };
+}
+
+/**
+ * Pre-flight for one instance: `sender` must be its admin, it must report a
+ * cw2 version for `target.contract`, and moving to `target.version` must be a
+ * compatible upgrade unless `target.allowBreaking`
+ */
+export async function checkMigration(
+  client: SigningClient,
+  contract: string,
+  sender: string,
+  target: { contract: string; version: string; allowBreaking?: boolean }
+): Promise<MigrationCheck> {
+  const info = await client.getContract(contract);
+  const current = await readCw2(client, contract).catch(() => null);
+  const check: MigrationCheck = { contract, admin: info.admin ?? '', codeId: info.codeId, current };
+
+  if (!info.admin) {
+    check.refusal = 'no admin; the instance is immutable';
+  } else if (info.admin !== sender) {
+    check.refusal = `admin is ${info.admin}, not ${sender}`;
+  } else if (!current) {
+    check.refusal = 'no cw2 contract_info; cannot tell which version is deployed';
+  } else if (current.contract !== target.contract) {
+    check.refusal = `deployed contract is ${current.contract}, not ${target.contract}`;
+  } else {
+    const { ok, breaking, reason } = compatibleUpgrade(current.version, target.version);
+    if (!ok && !(breaking && target.allowBreaking)) check.refusal = reason;
+  }
+  return check;
+}
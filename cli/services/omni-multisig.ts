@@ -0,0 +1,266 @@
+import { promises as fs } from 'fs';
+import { ethers } from 'ethers';
+import type { EvmChainConfig } from '../../packages/shared/src/chains';
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { cosmosSigningClient, evmSigner, requireChain } from './omni-chains';
+
+export const MULTISIG_PROPOSAL_VERSION = 1;
+
+export type MultisigAction = 'claim' | 'refund';
+
+/** A Safe transaction with every field execTransaction takes */
+export interface SafeTx {
+  to: string;
+  value: string;
+  data: string;
+  operation: number;
+  safeTxGas: string;
+  baseGas: string;
+  gasPrice: string;
+  gasToken: string;
+  refundReceiver: string;
+  nonce: string;
+}
+
+/**
+ * A claim or refund waiting on a multisig, as passed between signers. Gnosis
+ * Safe proposals collect detached signatures over `safeTxHash`; cw3
+ * proposals live on chain, so the file tracks the proposal ID and votes.
+ */
+export interface MultisigProposal {
+  version: number;
+  kind: 'safe' | 'cw3';
+  chain: string;
+  chainId: string;
+  action: MultisigAction;
+  /** HTLC ID on EVM, HTLC contract address on CosmWasm */
+  contractId: string;
+  /** Safe or cw3 contract address */
+  multisig: string;
+  threshold: number;
+  safeTx?: SafeTx;
+  safeTxHash?: string;
+  signatures?: { signer: string; signature: string }[];
+  proposalId?: number;
+  votes?: string[];
+  executedTx?: string;
+}
+
+const SAFE_ABI = [
+  'function nonce() view returns (uint256)',
+  'function getThreshold() view returns (uint256)',
+  'function isOwner(address owner) view returns (bool)',
+  'function getTransactionHash(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address refundReceiver, uint256 _nonce) view returns (bytes32)',
+  'function execTransaction(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address payable refundReceiver, bytes signatures) payable returns (bool)'
+];
+
+const HTLC_ABI = [
+  'function claim(bytes32 contractId, bytes32 preimage) external',
+  'function refund(bytes32 contractId) external'
+];
+
+export async function readProposal(file: string): Promise<MultisigProposal> {
+  const proposal = JSON.parse(await fs.readFile(file, 'utf8')) as MultisigProposal;
+  if (proposal.version !== MULTISIG_PROPOSAL_VERSION) {
+    throw new Error(`${file} is a version ${proposal.version} proposal; this omni reads version ${MULTISIG_PROPOSAL_VERSION}`);
+  }
+  return proposal;
+}
+
+export async function writeProposal(file: string, proposal: MultisigProposal): Promise<void> {
+  await fs.writeFile(file, JSON.stringify(proposal, null, 2) + '\n');
+}
+
+/**
+ * Start a claim or refund sent by a multisig. On EVM this only reads the
+ * Safe's nonce and threshold and returns the unsigned transaction; on Cosmos
+ * `key` (a cw3 voter) opens the proposal on chain, which counts as its yes.
+ */
+export async function createProposal(
+  chain: string,
+  multisig: string,
+  action: MultisigAction,
+  contractId: string,
+  options: { preimage?: string; key?: string; title?: string }
+): Promise<MultisigProposal> {
+  const config = requireChain(chain);
+  if (action === 'claim' && !options.preimage) throw new Error('A claim needs the preimage');
+  const base = { version: MULTISIG_PROPOSAL_VERSION, chain, chainId: String(config.chainId), action, contractId, multisig };
+
+  if (config.type === 'evm') {
+    const provider = new ethers.JsonRpcProvider(config.rpcUrl, (config as EvmChainConfig).chainId);
+    try {
+      const safe = new ethers.Contract(multisig, SAFE_ABI, provider);
+      const htlc = new ethers.Interface(HTLC_ABI);
+      const data = action === 'claim'
+        ? htlc.encodeFunctionData('claim', [contractId, options.preimage])
+        : htlc.encodeFunctionData('refund', [contractId]);
+      const safeTx: SafeTx = {
+        to: config.htlcAddress,
+        value: '0',
+        data,
+        operation: 0,
+        safeTxGas: '0',
+        baseGas: '0',
+        gasPrice: '0',
+        gasToken: ethers.ZeroAddress,
+        refundReceiver: ethers.ZeroAddress,
+        nonce: (await safe.nonce()).toString()
+      };
+      const safeTxHash: string = await safe.getTransactionHash(...safeTxArgs(safeTx));
+      return { ...base, kind: 'safe', threshold: Number(await safe.getThreshold()), safeTx, safeTxHash, signatures: [] };
+    } finally {
+      provider.destroy();
+    }
+  }
+
+  return withCw3Voter(chain, options.key, async (signing, voter) => {
+    const msg = action === 'claim' ? CosmosHTLCClient.claimMsg(options.preimage!) : CosmosHTLCClient.refundMsg();
+    const result = await signing.execute(voter, multisig, {
+      propose: {
+        title: options.title ?? `${action} HTLC ${contractId}`,
+        description: `omni multisig ${action} of ${contractId}`,
+        msgs: [{ wasm: { execute: { contract_addr: contractId, msg: Buffer.from(JSON.stringify(msg)).toString('base64'), funds: [] } } }]
+      }
+    }, 'auto');
+    const proposalId = Number(wasmAttribute(result.events, 'proposal_id'));
+    if (!proposalId) throw new Error(`${multisig} did not report a proposal ID; is it a cw3 contract?`);
+    const threshold = await cw3Threshold(signing, multisig);
+    return { ...base, kind: 'cw3', threshold, proposalId, votes: [voter] };
+  });
+}
+
+/**
+ * Add `key`'s approval: a detached signature over the Safe transaction hash,
+ * or a yes vote on the cw3 proposal. Signing twice with one key is refused.
+ */
+export async function approveProposal(proposal: MultisigProposal, key?: string): Promise<MultisigProposal> {
+  if (proposal.kind === 'safe') {
+    const signer = await evmSigner(proposal.chain, key);
+    if (proposal.signatures?.some(entry => entry.signer.toLowerCase() === signer.address.toLowerCase())) {
+      throw new Error(`${signer.address} has already signed this proposal`);
+    }
+    return { ...proposal, signatures: [...(proposal.signatures ?? []), { signer: signer.address, signature: await signSafeHash(signer, proposal.safeTxHash!) }] };
+  }
+
+  return withCw3Voter(proposal.chain, key, async (signing, voter) => {
+    if (proposal.votes?.includes(voter)) throw new Error(`${voter} has already voted on proposal ${proposal.proposalId}`);
+    await signing.execute(voter, proposal.multisig, { vote: { proposal_id: proposal.proposalId, vote: 'yes' } }, 'auto');
+    return { ...proposal, votes: [...(proposal.votes ?? []), voter] };
+  });
+}
+
+/**
+ * Merge the signatures of copies of one proposal signed separately; they must
+ * all be for the same transaction
+ */
+export function combineProposals(proposals: MultisigProposal[]): MultisigProposal {
+  const [first, ...rest] = proposals;
+  const same = (other: MultisigProposal) => other.kind === first.kind && other.chain === first.chain
+    && other.multisig.toLowerCase() === first.multisig.toLowerCase()
+    && other.safeTxHash === first.safeTxHash && other.proposalId === first.proposalId;
+  const mismatch = rest.find(other => !same(other));
+  if (mismatch) throw new Error('The proposals are for different transactions; combine copies of one proposal only');
+
+  const signatures = new Map<string, { signer: string; signature: string }>();
+  const votes = new Set<string>();
+  for (const proposal of proposals) {
+    proposal.signatures?.forEach(entry => signatures.set(entry.signer.toLowerCase(), entry));
+    proposal.votes?.forEach(voter => votes.add(voter));
+  }
+  return { ...first, signatures: [...signatures.values()], votes: [...votes] };
+}
+
+/**
+ * Send the approved transaction: Safe execTransaction with the signatures of
+ * current owners, sorted by owner as the Safe requires, or cw3 execute once
+ * the proposal has passed. `key` only pays the gas.
+ */
+export async function executeProposal(proposal: MultisigProposal, key?: string): Promise<string> {
+  const config = requireChain(proposal.chain);
+  if (String(config.chainId) !== proposal.chainId) {
+    throw new Error(`${proposal.chain} is chain ${config.chainId} here, but the proposal is for ${proposal.chainId}`);
+  }
+
+  if (proposal.kind === 'safe') {
+    const provider = new ethers.JsonRpcProvider(config.rpcUrl, (config as EvmChainConfig).chainId);
+    try {
+      const safe = new ethers.Contract(proposal.multisig, SAFE_ABI, await evmSigner(proposal.chain, key, provider));
+      const nonce = (await safe.nonce()).toString();
+      if (nonce !== proposal.safeTx!.nonce) {
+        throw new Error(`The Safe is at nonce ${nonce} but the proposal was made for ${proposal.safeTx!.nonce}; create it again`);
+      }
+
+      const valid: { signer: string; signature: string }[] = [];
+      for (const entry of proposal.signatures ?? []) {
+        const signer = recoverSafeSigner(proposal.safeTxHash!, entry.signature);
+        if (signer.toLowerCase() === entry.signer.toLowerCase() && await safe.isOwner(signer)) valid.push({ signer, signature: entry.signature });
+      }
+      const threshold = Number(await safe.getThreshold());
+      if (valid.length < threshold) {
+        throw new Error(`${valid.length} valid owner signature(s), the Safe needs ${threshold}`);
+      }
+
+      valid.sort((a, b) => (BigInt(a.signer) < BigInt(b.signer) ? -1 : 1));
+      const signatures = ethers.concat(valid.slice(0, threshold).map(entry => entry.signature));
+      const tx = await safe.execTransaction(...safeTxArgs(proposal.safeTx!).slice(0, 9), signatures);
+      const receipt = await tx.wait();
+      return receipt.hash;
+    } finally {
+      provider.destroy();
+    }
+  }
+
+  return withCw3Voter(proposal.chain, key, async (signing, sender) => {
+    const { status } = await signing.queryContractSmart(proposal.multisig, { proposal: { proposal_id: proposal.proposalId } });
+    if (status !== 'passed') throw new Error(`Proposal ${proposal.proposalId} is ${status}, not passed`);
+    const result = await signing.execute(sender, proposal.multisig, { execute: { proposal_id: proposal.proposalId } }, 'auto');
+    return result.transactionHash;
+  });
+}
+
+/**
+ * A software key signs the hash itself (v = 27/28). A hardware wallet can
+ * only sign it as an eth_sign message, which the Safe takes with v + 4.
+ */
+async function signSafeHash(signer: ethers.Signer, hash: string): Promise<string> {
+  if (signer instanceof ethers.Wallet) return signer.signingKey.sign(hash).serialized;
+  const signature = ethers.Signature.from(await signer.signMessage(ethers.getBytes(hash)));
+  return ethers.concat([signature.r, signature.s, ethers.toBeHex(signature.v + 4, 1)]);
+}
+
+function recoverSafeSigner(hash: string, signature: string): string {
+  const v = ethers.getBytes(signature)[64];
+  if (v <= 30) return ethers.recoverAddress(hash, signature);
+  const adjusted = ethers.concat([ethers.dataSlice(signature, 0, 64), ethers.toBeHex(v - 4, 1)]);
+  return ethers.recoverAddress(ethers.hashMessage(ethers.getBytes(hash)), adjusted);
+}
+
+function safeTxArgs(tx: SafeTx) {
+  return [tx.to, tx.value, tx.data, tx.operation, tx.safeTxGas, tx.baseGas, tx.gasPrice, tx.gasToken, tx.refundReceiver, tx.nonce] as const;
+}
+
+type SigningClient = NonNullable<ReturnType<CosmosHTLCClient['getSigningClient']>>;
+
+async function withCw3Voter<T>(chain: string, key: string | undefined, run: (signing: SigningClient, voter: string) => Promise<T>): Promise<T> {
+  const { client, address } = await cosmosSigningClient(chain, key);
+  try {
+    return await run(client.getSigningClient()!, address);
+  } finally {
+    client.getClient().disconnect();
+  }
+}
+
+/** Votes needed to pass, from cw3's `threshold` query (absolute count or weight) */
+async function cw3Threshold(signing: SigningClient, multisig: string): Promise<number> {
+  const response = await signing.queryContractSmart(multisig, { threshold: {} });
+  const threshold = response.absolute_count ?? response.absolute_percentage ?? response.threshold_quorum;
+  return Number(threshold?.weight ?? threshold?.percentage ?? threshold?.threshold ?? 0);
+}
+
+function wasmAttribute(events: readonly { type: string; attributes: readonly { key: string; value: string }[] }[], key: string): string | undefined {
+  return events
+    .filter(event => event.type === 'wasm')
+    .flatMap(event => event.attributes)
+    .find(attribute => attribute.key === key)?.value;
+}
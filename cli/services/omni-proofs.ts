@@ -0,0 +1,209 @@
+import axios from 'axios';
+import { ethers } from 'ethers';
+import type { BackfilledHTLC } from '../../packages/shared/src/resolver/backfill';
+import { verifyPreimage } from '../../packages/shared/src/utils/htlc';
+import { requireChain } from './omni-chains';
+import { scanHashLock } from './omni-history';
+import type { SwapRecord } from './omni-store';
+
+export const EVIDENCE_VERSION = 1;
+
+export type LifecycleStep = 'lock' | 'claim' | 'refund';
+
+/** A check anyone holding the archive can redo offline */
+export interface EvidenceCheck {
+  name: string;
+  ok: boolean;
+  detail?: string;
+}
+
+/**
+ * One transaction in a swap's life, with what it takes to show it happened:
+ * the signed bytes, the chain's record of executing it, the block that
+ * includes it and a proof tying the two together.
+ */
+export interface StepEvidence {
+  chain: string;
+  chainId: string | number;
+  contractId: string;
+  step: LifecycleStep;
+  txHash: string;
+  height: number;
+  /** Signed transaction bytes, hex on EVM and base64 on Cosmos */
+  rawTx?: string;
+  /** EVM: the receipt with its logs. Cosmos: the DeliverTx result with its events */
+  result?: unknown;
+  /** EVM: the block header. Cosmos: the signed header and its commit */
+  header?: unknown;
+  /**
+   * EVM: eth_getProof of the HTLC's storage slots after the block, against its
+   * state root. Cosmos: the Merkle proof of the tx against the header's data hash.
+   */
+  proof?: unknown;
+  /** Pieces the node would not return, such as state proofs from a pruned node */
+  missing: string[];
+  checks: EvidenceCheck[];
+}
+
+export interface EvidenceArchive {
+  version: number;
+  swapId: string;
+  hashLock: string;
+  /** Revealed by the claim, when there was one */
+  preimage?: string;
+  collectedAt: string;
+  htlcs: BackfilledHTLC[];
+  steps: StepEvidence[];
+  checks: EvidenceCheck[];
+}
+
+/** Storage slot of `contracts` in SimpleHTLC, and the slots its HTLC struct spans */
+const HTLC_MAPPING_SLOT = 0n;
+const HTLC_STRUCT_SLOTS = 7;
+
+/**
+ * Evidence for every lock, claim and refund under the swap's hashlock on the
+ * swap's chain and `chains`, found by scanning back to when the swap was
+ * created. A piece a node cannot supply is listed as missing rather than
+ * failing the whole archive.
+ */
+export async function collectEvidence(record: SwapRecord, chains: string[], blocks?: number, onStep?: (step: string) => void): Promise<EvidenceArchive> {
+  const htlcs = await scanHashLock(record.hashLock, [record.chain, ...chains], record.createdAt, blocks);
+  const steps: StepEvidence[] = [];
+
+  for (const htlc of htlcs) {
+    const transactions: Array<[LifecycleStep, string, number]> = [];
+    if (htlc.fundedAt.txHash) transactions.push(['lock', htlc.fundedAt.txHash, htlc.fundedAt.height]);
+    if (htlc.settledTxHash) transactions.push([htlc.state === 'REFUNDED' ? 'refund' : 'claim', htlc.settledTxHash, htlc.settledHeight!]);
+
+    for (const [step, txHash, height] of transactions) {
+      onStep?.(`${htlc.chain} ${step} ${txHash}`);
+      steps.push(requireChain(htlc.chain).type === 'evm'
+        ? await evmStep(htlc, step, txHash, height)
+        : await cosmosStep(htlc, step, txHash, height));
+    }
+  }
+
+  const preimage = htlcs.find(htlc => htlc.preimage)?.preimage;
+  const checks: EvidenceCheck[] = [];
+  if (preimage) {
+    const hex = (value: string) => value.startsWith('0x') ? value : `0x${value}`;
+    checks.push({ name: 'preimage hashes to the hashlock (sha256)', ok: verifyPreimage(hex(preimage), hex(record.hashLock)) });
+  }
+  const mismatched = htlcs.filter(htlc => htlc.preimage && htlc.preimage.replace(/^0x/, '') !== preimage!.replace(/^0x/, ''));
+  if (mismatched.length) checks.push({ name: 'every claim reveals the same preimage', ok: false, detail: mismatched.map(htlc => htlc.contractId).join(', ') });
+
+  return {
+    version: EVIDENCE_VERSION,
+    swapId: record.swapId,
+    hashLock: record.hashLock,
+    preimage,
+    collectedAt: new Date().toISOString(),
+    htlcs,
+    steps: steps.sort((a, b) => a.height - b.height),
+    checks
+  };
+}
+
+async function evmStep(htlc: BackfilledHTLC, step: LifecycleStep, txHash: string, height: number): Promise<StepEvidence> {
+  const config = requireChain(htlc.chain);
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl);
+  const evidence: StepEvidence = { chain: htlc.chain, chainId: config.chainId, contractId: htlc.contractId, step, txHash, height, missing: [], checks: [] };
+  const blockTag = ethers.toQuantity(height);
+
+  const [rawTx, receipt, header] = await Promise.all([
+    provider.send('eth_getRawTransactionByHash', [txHash]).catch(() => undefined) as Promise<string | undefined>,
+    provider.send('eth_getTransactionReceipt', [txHash]) as Promise<{ blockHash: string; status: string; logs: Array<{ address: string }> } | null>,
+    provider.send('eth_getBlockByNumber', [blockTag, false]) as Promise<{ hash: string; stateRoot: string; receiptsRoot: string; transactions: string[] } | null>
+  ]);
+
+  if (rawTx) {
+    evidence.rawTx = rawTx;
+    evidence.checks.push({ name: 'raw tx hashes to the tx hash', ok: ethers.keccak256(rawTx) === txHash.toLowerCase() });
+  } else {
+    evidence.missing.push('rawTx');
+  }
+  if (receipt) {
+    evidence.result = receipt;
+    evidence.checks.push({ name: 'tx succeeded', ok: receipt.status === '0x1' });
+    evidence.checks.push({
+      name: 'tx logs come from the HTLC contract',
+      ok: receipt.logs.some(log => log.address.toLowerCase() === config.htlcAddress.toLowerCase())
+    });
+  } else {
+    evidence.missing.push('result');
+  }
+  if (header) {
+    const { transactions, ...rest } = header;
+    evidence.header = rest;
+    evidence.checks.push({ name: 'block includes the tx', ok: transactions.some(hash => hash.toLowerCase() === txHash.toLowerCase()) });
+    if (receipt) evidence.checks.push({ name: 'receipt is from this block', ok: receipt.blockHash === header.hash });
+  } else {
+    evidence.missing.push('header');
+  }
+
+  // The HTLC struct as this block left it, provable against the header's state root
+  const base = BigInt(ethers.keccak256(ethers.AbiCoder.defaultAbiCoder().encode(['bytes32', 'uint256'], [htlc.contractId, HTLC_MAPPING_SLOT])));
+  const slots = Array.from({ length: HTLC_STRUCT_SLOTS }, (_, i) => ethers.toBeHex(base + BigInt(i), 32));
+  try {
+    evidence.proof = await provider.send('eth_getProof', [config.htlcAddress, slots, blockTag]);
+  } catch {
+    // Needs an archive node once the block is old
+    evidence.missing.push('proof');
+  }
+  return evidence;
+}
+
+// The CometBFT RPC responses read here
+interface CometTx {
+  hash: string;
+  height: string;
+  tx: string;
+  tx_result: { code: number; events: Array<{ type: string; attributes: Array<{ key: string; value: string }> }> };
+  proof?: { root_hash: string };
+}
+
+interface CometCommit {
+  signed_header: { header: { chain_id: string; height: string; data_hash: string } };
+}
+
+async function cosmosStep(htlc: BackfilledHTLC, step: LifecycleStep, txHash: string, height: number): Promise<StepEvidence> {
+  const config = requireChain(htlc.chain);
+  const rpc = config.rpcUrl.replace(/\/$/, '');
+  const evidence: StepEvidence = { chain: htlc.chain, chainId: config.chainId, contractId: htlc.contractId, step, txHash, height, missing: [], checks: [] };
+  const get = async <T>(path: string, params: Record<string, string>): Promise<T> =>
+    (await axios.get<{ result: T }>(`${rpc}/${path}`, { params, timeout: 15000 })).data.result;
+
+  const hash = txHash.replace(/^0x/, '').toUpperCase();
+  const [tx, commit] = await Promise.all([
+    get<CometTx>('tx', { hash: `0x${hash}`, prove: 'true' }).catch(() => undefined),
+    get<CometCommit>('commit', { height: String(height) }).catch(() => undefined)
+  ]);
+
+  if (tx) {
+    evidence.rawTx = tx.tx;
+    evidence.result = tx.tx_result;
+    evidence.proof = tx.proof;
+    const digest = ethers.sha256(Buffer.from(tx.tx, 'base64')).slice(2).toUpperCase();
+    evidence.checks.push({ name: 'raw tx hashes to the tx hash', ok: digest === hash });
+    evidence.checks.push({ name: 'tx succeeded', ok: tx.tx_result.code === 0 });
+    evidence.checks.push({
+      name: 'tx events name the HTLC contract',
+      ok: tx.tx_result.events.some(event => event.attributes.some(attribute => attribute.key === '_contract_address' && attribute.value === htlc.contractId))
+    });
+    if (!tx.proof) evidence.missing.push('proof');
+  } else {
+    evidence.missing.push('rawTx', 'result', 'proof');
+  }
+  if (commit) {
+    evidence.header = commit;
+    const header = commit.signed_header.header;
+    evidence.checks.push({ name: 'header is for this chain and height', ok: header.chain_id === config.chainId && Number(header.height) === height });
+    if (tx?.proof) {
+      evidence.checks.push({ name: 'tx proof root is the header data hash', ok: tx.proof.root_hash.toUpperCase() === header.data_hash.toUpperCase() });
+    }
+  } else {
+    evidence.missing.push('header');
+  }
+  return evidence;
+}
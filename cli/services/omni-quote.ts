@@ -0,0 +1,74 @@
+import { allChains } from '../../packages/shared/src/chains';
+import { chainTiming, rfqSource, skipSource } from '../../packages/shared/src/sdk';
+import type { Quote, QuoteAsset, QuoteSource } from '../../packages/shared/src/sdk';
+import { activeProfile } from './omni-config';
+
+export interface QuoteSourceOptions {
+  /** Resolver endpoints on top of the profile's, by name */
+  resolvers?: Record<string, string>;
+  skip?: boolean;
+}
+
+/**
+ * Parse `<chain>:<asset>`, where the asset is a denom, token address or
+ * "native". Decimals default to the native asset's (18 on EVM, 6 on Cosmos)
+ * and must be given for anything else.
+ */
+export function parseQuoteAsset(value: string, decimals?: number): QuoteAsset {
+  const separator = value.indexOf(':');
+  const chain = separator === -1 ? value : value.slice(0, separator);
+  const asset = separator === -1 ? 'native' : value.slice(separator + 1);
+  const config = allChains[chain];
+  if (!config) throw new Error(`Unknown chain ${chain}; known: ${Object.keys(allChains).join(', ')}`);
+
+  if (decimals === undefined) {
+    if (!isNativeAsset({ chain, asset })) throw new Error(`Decimals of ${asset} on ${chain} are not known; pass them with the decimals option`);
+    decimals = config.type === 'evm' ? 18 : 6;
+  }
+  return { chain, asset, decimals };
+}
+
+/** Whether the asset is the chain's native one, which HTLCs lock without a token */
+export function isNativeAsset(asset: { chain: string; asset: string }): boolean {
+  const config = allChains[asset.chain];
+  return asset.asset === 'native' || (config?.type === 'cosmos' && asset.asset === config.nativeDenom);
+}
+
+/** Resolver RFQ endpoints from the profile and `options`, plus Skip Go unless turned off */
+export function quoteSources(options: QuoteSourceOptions = {}): QuoteSource[] {
+  const settings = activeProfile()?.quotes ?? {};
+  const resolvers = { ...settings.resolvers, ...options.resolvers };
+  const sources = Object.entries(resolvers).map(([name, url]) => rfqSource(name, url));
+
+  if (options.skip ?? settings.skip ?? true) {
+    const chainIds = Object.fromEntries(Object.entries(allChains).map(([key, chain]) => [key, String(chain.chainId)]));
+    sources.push(skipSource(chainIds, { apiUrl: settings.skipApiUrl, apiKey: process.env.SKIP_API_KEY }));
+  }
+  return sources;
+}
+
+/**
+ * Rough time from funding to both sides holding their output. Skip reports its
+ * own route estimate; for an HTLC swap it is the source lock being included
+ * and trusted, the counterparty's lock likewise, then the claim on each side.
+ */
+export function settlementSeconds(quote: Quote): number {
+  const estimate = (quote.raw as { estimated_route_duration_seconds?: unknown } | null)?.estimated_route_duration_seconds;
+  if (typeof estimate === 'number') return estimate;
+
+  const source = chainTiming(quote.request.from.chain);
+  const dest = chainTiming(quote.request.to.chain);
+  return source.inclusionSeconds + source.finalitySeconds
+    + dest.inclusionSeconds + dest.finalitySeconds
+    + dest.inclusionSeconds
+    + source.inclusionSeconds;
+}
+
+/**
+ * The address a resolver quote says to lock funds for, when the resolver
+ * includes one (`beneficiary` in its response)
+ */
+export function quoteBeneficiary(quote: Quote): string | undefined {
+  const beneficiary = (quote.raw as { beneficiary?: unknown } | null)?.beneficiary;
+  return typeof beneficiary === 'string' ? beneficiary : undefined;
+}
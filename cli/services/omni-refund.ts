@@ -0,0 +1,124 @@
+import { CosmosHTLCClient } from '../../packages/shared/src/clients/cosmos';
+import { SwapState } from '../../packages/shared/src/utils/htlc';
+import { cosmosSigningClient, openChain, requireChain } from './omni-chains';
+import { backfillerFor } from './omni-history';
+import type { ScanRange } from './omni-history';
+import type { SwapRecord } from './omni-store';
+
+export interface RefundCandidate {
+  chain: string;
+  contractId: string;
+  originator: string;
+  value: string;
+  token: string;
+  timelock: number;
+  /** Local swap the HTLC was funded for, if any */
+  swapId?: string;
+}
+
+export interface RefundOutcome extends RefundCandidate {
+  txHash?: string;
+  error?: string;
+}
+
+export interface RefundSweepOptions {
+  key?: string;
+  /** Swaps created here; funded and unsettled ones on this chain are candidates */
+  records: SwapRecord[];
+  /** Also look for HTLCs in this block range of the chain's history */
+  scan?: ScanRange;
+  /**
+   * Include other senders' expired HTLCs, for HTLC deployments that let anyone
+   * refund (the funds still go to the sender). SimpleHTLC does not, so this is
+   * ignored on EVM chains.
+   */
+  anySender?: boolean;
+  dryRun?: boolean;
+}
+
+/**
+ * Find the expired, still-open HTLCs on one chain that our key may refund, and
+ * refund them unless `dryRun`. Cosmos refunds are packed into as few
+ * transactions as the gas limit allows; EVM refunds are sent back to back from
+ * one nonce sequence and awaited together. Every candidate is re-read from the
+ * chain first, so a stale record or scan never costs a failed transaction.
+ */
+export async function sweepRefunds(chain: string, options: RefundSweepOptions): Promise<RefundOutcome[]> {
+  const config = requireChain(chain);
+  const swapChain = await openChain(chain, { sign: true, key: options.key });
+  try {
+    const address = swapChain.address!.toLowerCase();
+    const anySender = options.anySender && config.type === 'cosmos';
+    const candidates = new Map<string, { contractId: string; swapId?: string }>();
+
+    for (const record of options.records) {
+      if (record.chain === chain && record.contractId && !record.claimTx && !record.refundTx) {
+        candidates.set(record.contractId.toLowerCase(), { contractId: record.contractId, swapId: record.swapId });
+      }
+    }
+    if (options.scan) {
+      const backfiller = backfillerFor(chain);
+      const toHeight = await backfiller.latestHeight();
+      const fromHeight = options.scan.fromHeight ?? Math.max(1, toHeight - options.scan.blocks + 1);
+      for (const htlc of await backfiller.scan(fromHeight, toHeight, options.scan.chunkSize ?? 2000)) {
+        if (htlc.state !== 'OPEN' || candidates.has(htlc.contractId.toLowerCase())) continue;
+        if (anySender || htlc.originator.toLowerCase() === address) {
+          candidates.set(htlc.contractId.toLowerCase(), { contractId: htlc.contractId });
+        }
+      }
+    }
+
+    const now = await swapChain.currentTime();
+    const refundable: RefundCandidate[] = [];
+    for (const { contractId, swapId } of candidates.values()) {
+      const htlc = await swapChain.getHtlc(contractId);
+      if (!htlc || htlc.state !== SwapState.OPEN || htlc.timelock >= now) continue;
+      if (!anySender && htlc.originator.toLowerCase() !== address) continue;
+      refundable.push({
+        chain,
+        contractId,
+        originator: htlc.originator,
+        value: htlc.value,
+        token: htlc.token,
+        timelock: htlc.timelock,
+        ...(swapId ? { swapId } : {})
+      });
+    }
+    if (options.dryRun || refundable.length === 0) return refundable;
+
+    if (config.type === 'cosmos') {
+      const { client, address: sender } = await cosmosSigningClient(chain, options.key);
+      try {
+        const outcomes = await client.executeBatch(sender, refundable.map(candidate => ({
+          contractAddress: candidate.contractId,
+          msg: CosmosHTLCClient.refundMsg()
+        })), { memo: `Refund of ${refundable.length} expired HTLCs` });
+        return refundable.map(candidate => {
+          const outcome = outcomes.find(result => result.contractAddress === candidate.contractId);
+          return { ...candidate, txHash: outcome?.transactionHash, error: outcome?.error };
+        });
+      } finally {
+        client.getClient().disconnect();
+      }
+    }
+
+    const submitted = await Promise.all(refundable.map(async candidate => {
+      try {
+        return { candidate, pending: await swapChain.adapter.submitRefund(candidate.contractId) };
+      } catch (error) {
+        return { candidate, error: (error as Error).message };
+      }
+    }));
+    return Promise.all(submitted.map(async ({ candidate, pending, error }) => {
+      if (!pending) return { ...candidate, error };
+      try {
+        await pending.wait();
+        return { ...candidate, txHash: pending.txHash };
+      } catch (failure) {
+        return { ...candidate, txHash: pending.txHash, error: (failure as Error).message };
+      }
+    }));
+  } finally {
+    swapChain.close();
+  }
+}
@@ -0,0 +1,72 @@
+import { EventEmitter } from 'events';
+import { openChain } from './omni-chains';
+import type { ChainAdapter, HTLCEvent } from '../../packages/shared/src/resolver/adapters';
+import { SwapRelay } from '../../packages/shared/src/sdk';
+import type { RelayEndpoint, SwapChain } from '../../packages/shared/src/sdk';
+
+export interface RelayChainConfig {
+  /** Keyring name to sign with on this chain */
+  key?: string;
+  /** First block to pick up HTLC events from; defaults to the head at startup */
+  fromHeight?: number;
+}
+
+export interface RelayConfig {
+  chains: Record<string, RelayChainConfig>;
+  /** Seconds between sweeps (claim retries and refunds) and event polls (default 10) */
+  pollIntervalSeconds?: number;
+  /** Claim HTLCs paying us once their preimage is revealed elsewhere (default true) */
+  claim?: boolean;
+  /** Refund our own HTLCs once they expire (default true) */
+  refund?: boolean;
+}
+
+type RelayChain = SwapChain & { adapter: ChainAdapter; close(): void };
+
+/**
+ * The SDK's SwapRelay on the configured chains, run in-process for a single
+ * counterparty: no database or auction feed; state is rebuilt from chain
+ * events from `fromHeight`, and expired locks are swept every poll interval.
+ *
+ * Emits 'claimed', 'refunded' ({ chain, contractId, txHash }) and 'error'
+ * ({ chain, contractId, error }).
+ */
+export class SelfRelay extends EventEmitter {
+  private chains: RelayChain[] = [];
+  private relay?: SwapRelay;
+  private timer?: NodeJS.Timeout;
+
+  constructor(private config: RelayConfig) {
+    super();
+  }
+
+  async start(): Promise<void> {
+    const pollIntervalMs = (this.config.pollIntervalSeconds ?? 10) * 1000;
+    const endpoints: RelayEndpoint[] = [];
+    for (const [chain, options] of Object.entries(this.config.chains)) {
+      const swapChain = await openChain(chain, { sign: true, key: options.key });
+      this.chains.push(swapChain);
+      endpoints.push({
+        swapChain,
+        subscribe: handler => swapChain.adapter.subscribeEvents(handler, { fromHeight: options.fromHeight, pollIntervalMs })
+      });
+    }
+
+    this.relay = new SwapRelay(endpoints, { claim: this.config.claim, refund: this.config.refund });
+    for (const event of ['claimed', 'refunded', 'error']) this.relay.on(event, payload => this.emit(event, payload));
+    await this.relay.start();
+    this.timer = setInterval(() => void this.relay!.sweep(), pollIntervalMs);
+  }
+
+  stop(): void {
+    if (this.timer) clearInterval(this.timer);
+    this.relay?.stop();
+    this.chains.forEach(chain => chain.close());
+    this.chains = [];
+  }
+
+  /** HTLCs seen so far that involve one of our addresses */
+  tracked(): Extract<HTLCEvent, { kind: 'funded' }>[] {
+    return this.relay?.tracked() ?? [];
+  }
+}
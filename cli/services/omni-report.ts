@@ -0,0 +1,181 @@
+import { ethers } from 'ethers';
+import type { TxFee } from '../../packages/shared/src/resolver/backfill';
+import { requireChain } from './omni-chains';
+import { backfillerFor, csvField } from './omni-history';
+import type { HistoryEntry } from './omni-history';
+
+/**
+ * One movement of funds for an address: `lock` moves its funds into an HTLC,
+ * `claim` pays it out of one, `refund` returns its own lock
+ */
+export type ReportEvent = 'lock' | 'claim' | 'refund';
+
+export interface ReportRow {
+  address: string;
+  chain: string;
+  contractId: string;
+  event: ReportEvent;
+  /** Unix seconds of the block; unset when the transaction could not be found */
+  time?: number;
+  /** Denom, token address, or the chain's native asset */
+  asset: string;
+  /** Base units */
+  amountIn: string;
+  amountOut: string;
+  /** Fee the address paid for this transaction; 0 when someone else sent it, unset when unknown */
+  fee?: string;
+  feeAsset?: string;
+  txHash: string;
+}
+
+export interface ReportTotal {
+  address: string;
+  chain: string;
+  asset: string;
+  amountIn: bigint;
+  amountOut: bigint;
+  /** Fees paid in this asset on this chain */
+  fees: bigint;
+}
+
+function nativeAsset(chain: string): string {
+  return requireChain(chain).nativeDenom ?? 'native';
+}
+
+function assetOf(entry: HistoryEntry): string {
+  return !entry.token || entry.token === ethers.ZeroAddress ? nativeAsset(entry.chain) : entry.token;
+}
+
+/**
+ * The movements in an address's HTLC history, with the fee and block time of
+ * each transaction read back from its chain. A claim paid to someone else, or
+ * a refund to someone else, moves nothing for this address and is left out.
+ */
+export async function reportRows(address: string, entries: HistoryEntry[]): Promise<ReportRow[]> {
+  const rows: ReportRow[] = [];
+  const lookups = new Map<string, ReturnType<typeof backfillerFor>>();
+  const backfiller = (chain: string) => {
+    if (!lookups.has(chain)) lookups.set(chain, backfillerFor(chain));
+    return lookups.get(chain)!;
+  };
+
+  const row = async (entry: HistoryEntry, event: ReportEvent, txHash: string | undefined, amountIn: string, amountOut: string, knownTime?: number) => {
+    let time = knownTime;
+    let fee: string | undefined;
+    let feeAsset: string | undefined;
+    if (txHash) {
+      const paid: TxFee | undefined = await backfiller(entry.chain).txFee(txHash).catch(() => undefined);
+      if (paid) {
+        const payer = paid.payer.toLowerCase() === address.toLowerCase();
+        fee = payer ? paid.amount : '0';
+        feeAsset = paid.denom ?? nativeAsset(entry.chain);
+        time ??= await backfiller(entry.chain).blockTime(paid.height);
+      }
+    }
+    rows.push({
+      address,
+      chain: entry.chain,
+      contractId: entry.contractId,
+      event,
+      time,
+      asset: assetOf(entry),
+      amountIn,
+      amountOut,
+      fee,
+      feeAsset,
+      txHash: txHash ?? ''
+    });
+  };
+
+  for (const entry of entries) {
+    if (entry.role === 'sender') {
+      await row(entry, 'lock', entry.fundedAt.txHash || undefined, '0', entry.value, entry.fundedTime);
+      if (entry.state === 'REFUNDED') await row(entry, 'refund', entry.settledTxHash, entry.value, '0');
+    } else if (entry.state === 'CLAIMED') {
+      await row(entry, 'claim', entry.settledTxHash, entry.value, '0');
+    }
+  }
+  return rows.sort((a, b) => (a.time ?? 0) - (b.time ?? 0));
+}
+
+/** Amounts in and out, and fees paid, per address, chain and asset */
+export function reportTotals(rows: ReportRow[]): ReportTotal[] {
+  const totals = new Map<string, ReportTotal>();
+  const total = (address: string, chain: string, asset: string) => {
+    const key = `${address}/${chain}/${asset}`;
+    if (!totals.has(key)) totals.set(key, { address, chain, asset, amountIn: 0n, amountOut: 0n, fees: 0n });
+    return totals.get(key)!;
+  };
+
+  for (const row of rows) {
+    const moved = total(row.address, row.chain, row.asset);
+    moved.amountIn += BigInt(row.amountIn);
+    moved.amountOut += BigInt(row.amountOut);
+    if (row.fee && row.feeAsset) total(row.address, row.chain, row.feeAsset).fees += BigInt(row.fee);
+  }
+  return [...totals.values()];
+}
+
+const COLUMNS = ['time', 'address', 'chain', 'contractId', 'event', 'asset', 'amountIn', 'amountOut', 'fee', 'feeAsset', 'txHash'] as const;
+
+function isoTime(time?: number): string {
+  return time === undefined ? '' : new Date(time * 1000).toISOString();
+}
+
+export function reportCsv(rows: ReportRow[]): string {
+  const lines = rows.map(row => COLUMNS.map(column => csvField(column === 'time' ? isoTime(row.time) : String(row[column] ?? ''))));
+  return [COLUMNS, ...lines].map(line => line.join(',')).join('\n') + '\n';
+}
+
+interface ParquetModule {
+  ParquetSchema: new (fields: Record<string, { type: string; optional?: boolean }>) => unknown;
+  ParquetWriter: {
+    openFile(schema: unknown, file: string): Promise<{ appendRow(row: Record<string, unknown>): Promise<void>; close(): Promise<void> }>;
+  };
+}
+
+// Parquet support is optional, like the keyring and Ledger backends
+function loadParquet(): ParquetModule {
+  try {
+    // eslint-disable-next-line @typescript-eslint/no-var-requires
+    return require('@dsnp/parquetjs');
+  } catch {
+    throw new Error('Parquet output needs the optional @dsnp/parquetjs package; install it or write CSV');
+  }
+}
+
+/**
+ * Write the rows as Parquet. Amounts stay decimal strings: base units
+ * overflow every Parquet integer type.
+ */
+export async function writeReportParquet(file: string, rows: ReportRow[]): Promise<void> {
+  const { ParquetSchema, ParquetWriter } = loadParquet();
+  const text = { type: 'UTF8' };
+  const optionalText = { type: 'UTF8', optional: true };
+  const schema = new ParquetSchema({
+    time: { type: 'TIMESTAMP_MILLIS', optional: true },
+    address: text,
+    chain: text,
+    contractId: text,
+    event: text,
+    asset: text,
+    amountIn: text,
+    amountOut: text,
+    fee: optionalText,
+    feeAsset: optionalText,
+    txHash: optionalText
+  });
+
+  const writer = await ParquetWriter.openFile(schema, file);
+  try {
+    for (const row of rows) {
+      await writer.appendRow({
+        ...row,
+        time: row.time === undefined ? undefined : new Date(row.time * 1000),
+        txHash: row.txHash || undefined
+      });
+    }
+  } finally {
+    await writer.close();
+  }
+}
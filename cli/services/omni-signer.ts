@@ -0,0 +1,22 @@
+import { RemoteSignerClient } from '../../packages/shared/src/clients/remote-signer';
+import type { RemoteSignerSettings } from '../../packages/shared/src/clients/remote-signer';
+import { activeProfile } from './omni-config';
+
+let client: Promise<RemoteSignerClient> | undefined;
+
+/** The profile's remote_signer, with its token read from the environment */
+export function remoteSignerSettings(): RemoteSignerSettings | undefined {
+  const settings = activeProfile()?.remoteSigner;
+  if (!settings) return undefined;
+  const { tokenEnv, ...rest } = settings;
+  return { ...rest, token: process.env[tokenEnv ?? 'OMNI_SIGNER_TOKEN'] || undefined };
+}
+
+/** One connection per process, opened on first use */
+export function remoteSigner(settings: RemoteSignerSettings): Promise<RemoteSignerClient> {
+  client ??= RemoteSignerClient.connect(settings).catch(error => {
+    client = undefined;
+    throw error;
+  });
+  return client;
+}
@@ -0,0 +1,95 @@
+import { ethers } from 'ethers';
+import type { CosmosChainConfig, EvmChainConfig } from '../../packages/shared/src/chains';
+import { estimateFees } from '../../packages/shared/src/sdk';
+import type { FeeCall, FeeSuggestion } from '../../packages/shared/src/sdk';
+import { activeProfile } from './omni-config';
+import { cosmosSigningClient, evmSigner, requireChain } from './omni-chains';
+
+export interface ExpectedEvent {
+  name: string;
+  attributes: Record<string, string | number>;
+}
+
+export interface Simulation {
+  chain: string;
+  action: FeeCall['action'];
+  sender: string;
+  fee: FeeSuggestion;
+  events: ExpectedEvent[];
+}
+
+/**
+ * Dry-run one HTLC call: Cosmos tx simulation or EVM estimateGas, both of
+ * which fail the same way the real call would. Nothing is broadcast. The
+ * events are the ones the contract emits for the call, filled in from its
+ * arguments.
+ */
+export async function simulate(chain: string, call: FeeCall, key?: string): Promise<Simulation> {
+  const config = requireChain(chain);
+  const policy = activeProfile()?.fees;
+
+  if (config.type === 'cosmos') {
+    // Simulation needs the account's public key, so a Cosmos dry-run needs the key itself
+    const { client, address } = await cosmosSigningClient(chain, key);
+    try {
+      const fee = await estimateFees(call, config, { client: client.getSigningClient()!, sender: address }, policy);
+      return { chain, action: call.action, sender: address, fee, events: cosmosEvents(call, address, config as CosmosChainConfig) };
+    } finally {
+      client.getClient().disconnect();
+    }
+  }
+
+  const from = (await evmSigner(chain, key)).address;
+  const provider = new ethers.JsonRpcProvider(config.rpcUrl, (config as EvmChainConfig).chainId);
+  try {
+    const fee = await estimateFees(call, config, { provider, from }, policy);
+    return { chain, action: call.action, sender: from, fee, events: evmEvents(call, from, config as EvmChainConfig) };
+  } finally {
+    provider.destroy();
+  }
+}
+
+function evmEvents(call: FeeCall, from: string, chain: EvmChainConfig): ExpectedEvent[] {
+  switch (call.action) {
+    case 'fund': {
+      const { contractId, token, beneficiary, hashLock, timelock, value } = call.params;
+      const native = !token || token === ethers.ZeroAddress;
+      return [
+        ...(native ? [] : [{ name: 'Transfer', attributes: { from, to: chain.htlcAddress, value } }]),
+        {
+          name: 'HTLCCreated',
+          attributes: { contractId, originator: from, beneficiary, token: token || ethers.ZeroAddress, value, hashLock, timelock }
+        }
+      ];
+    }
+    case 'claim':
+      return [{ name: 'HTLCClaimed', attributes: { contractId: call.contractId, claimer: from, preimage: call.preimage } }];
+    case 'refund':
+      return [{ name: 'HTLCRefunded', attributes: { contractId: call.contractId, refunder: from } }];
+  }
+}
+
+function cosmosEvents(call: FeeCall, sender: string, chain: CosmosChainConfig): ExpectedEvent[] {
+  switch (call.action) {
+    case 'fund': {
+      const { beneficiary, hashLock, timelock, value, token } = call.params;
+      return [
+        { name: 'instantiate', attributes: { code_id: chain.codeId || '1' } },
+        {
+          name: 'wasm',
+          attributes: { method: 'fund', sender, beneficiary, amount: value, token: token || chain.nativeDenom, hash_lock: hashLock, timelock }
+        }
+      ];
+    }
+    case 'claim':
+      return [
+        { name: 'wasm', attributes: { _contract_address: call.contractId, method: 'claim', preimage: call.preimage } },
+        { name: 'transfer', attributes: { sender: call.contractId } }
+      ];
+    case 'refund':
+      return [
+        { name: 'wasm', attributes: { _contract_address: call.contractId, method: 'refund' } },
+        { name: 'transfer', attributes: { sender: call.contractId } }
+      ];
+  }
+}
@@ -0,0 +1,102 @@
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import inquirer from 'inquirer';
+import { SecretVault } from '../../packages/shared/src/sdk';
+
+/**
+ * A swap created with `omni swap create`. The secret itself lives in the vault
+ * under the same swap ID; everything here is public.
+ */
+export interface SwapRecord {
+  swapId: string;
+  /** Chain registry key the HTLC is locked on */
+  chain: string;
+  originator: string;
+  beneficiary: string;
+  hashLock: string;
+  timelock: number;
+  amount: string;
+  token?: string;
+  /** HTLC ID once funded; the swap ID on EVM, the contract address on CosmWasm */
+  contractId?: string;
+  fundTx?: string;
+  claimTx?: string;
+  refundTx?: string;
+  createdAt: number;
+  /** Set by `omni cancel`; the swap must not be funded after it */
+  cancelledAt?: number;
+}
+
+/**
+ * Where omni keeps its state: $OMNI_HOME, or ~/.omni
+ */
+export function omniHome(): string {
+  return process.env.OMNI_HOME || path.join(os.homedir(), '.omni');
+}
+
+/**
+ * One JSON file per swap under <omni home>/swaps
+ */
+export class SwapStore {
+  constructor(private dir: string = path.join(omniHome(), 'swaps')) {}
+
+  async get(swapId: string): Promise<SwapRecord | null> {
+    try {
+      return JSON.parse(await fs.readFile(this.file(swapId), 'utf8'));
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === 'ENOENT') return null;
+      throw error;
+    }
+  }
+
+  /**
+   * Look a swap up by swap ID or by the HTLC ID it was funded under
+   */
+  async find(id: string): Promise<SwapRecord | null> {
+    const direct = await this.get(id);
+    if (direct) return direct;
+    const records = await this.list();
+    return records.find(record => record.contractId?.toLowerCase() === id.toLowerCase()) ?? null;
+  }
+
+  async put(record: SwapRecord): Promise<void> {
+    await fs.mkdir(this.dir, { recursive: true, mode: 0o700 });
+    const target = this.file(record.swapId);
+    const temp = `${target}.tmp`;
+    await fs.writeFile(temp, JSON.stringify(record, null, 2));
+    await fs.rename(temp, target);
+  }
+
+  async list(): Promise<SwapRecord[]> {
+    let names: string[];
+    try {
+      names = await fs.readdir(this.dir);
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === 'ENOENT') return [];
+      throw error;
+    }
+    const records = await Promise.all(
+      names.filter(name => name.endsWith('.json')).map(async name => JSON.parse(await fs.readFile(path.join(this.dir, name), 'utf8')) as SwapRecord)
+    );
+    return records.sort((a, b) => a.createdAt - b.createdAt);
+  }
+
+  private file(swapId: string): string {
+    return path.join(this.dir, `${swapId.toLowerCase()}.json`);
+  }
+}
+
+/**
+ * Open the secret vault at <omni home>/secrets.vault. The passphrase comes from
+ * OMNI_VAULT_PASSPHRASE or is prompted for.
+ */
+export async function openVault(): Promise<SecretVault> {
+  let passphrase = process.env.OMNI_VAULT_PASSPHRASE;
+  if (!passphrase) {
+    const answer = await inquirer.prompt([{ type: 'password', name: 'passphrase', message: 'Vault passphrase:', mask: '*' }]);
+    passphrase = answer.passphrase as string;
+  }
+  await fs.mkdir(omniHome(), { recursive: true, mode: 0o700 });
+  return SecretVault.open(path.join(omniHome(), 'secrets.vault'), passphrase);
+}
@@ -0,0 +1,66 @@
+import { Secret, deriveSwapId } from '../../packages/shared/src/sdk';
+import { requireChain, withChain } from './omni-chains';
+import { SwapStore, openVault } from './omni-store';
+import type { SwapRecord } from './omni-store';
+
+export interface CreateSwapParams {
+  chain: string;
+  beneficiary: string;
+  /** Base units */
+  amount: string;
+  token?: string;
+  /** Seconds from the chain's current time until the lock can be refunded */
+  durationSeconds: number;
+  /** Lock under the counterparty's hashlock instead of generating a secret */
+  hashLock?: string;
+  key?: string;
+}
+
+/**
+ * Record a new swap, ready to fund: generate its secret into the vault (unless
+ * a hashlock is given), derive the swap ID and store the record
+ */
+export async function createSwap(params: CreateSwapParams, store = new SwapStore()): Promise<SwapRecord & { secretStored: boolean }> {
+  const config = requireChain(params.chain);
+  const { originator, now } = await withChain(params.chain, { sign: true, key: params.key }, async chain => ({
+    originator: chain.address!,
+    now: await chain.currentTime()
+  }));
+
+  const secret = params.hashLock ? null : Secret.generate();
+  const hashLock = params.hashLock ?? secret!.hashLock();
+  const timelock = now + params.durationSeconds;
+  const swapId = deriveSwapId({
+    chainId: config.chainId,
+    hashLock,
+    originator,
+    beneficiary: params.beneficiary,
+    token: params.token,
+    amount: params.amount,
+    timelock
+  });
+
+  if (secret) {
+    const vault = await openVault();
+    try {
+      await vault.put(swapId, secret);
+    } finally {
+      vault.close();
+      secret.zeroize();
+    }
+  }
+
+  const record: SwapRecord = {
+    swapId,
+    chain: params.chain,
+    originator,
+    beneficiary: params.beneficiary,
+    hashLock,
+    timelock,
+    amount: params.amount,
+    token: params.token,
+    createdAt: Math.floor(Date.now() / 1000)
+  };
+  await store.put(record);
+  return { ...record, secretStored: !!secret };
+}
@@ -0,0 +1,104 @@
+import { allChains } from '../../packages/shared/src/chains';
+import { checkTimelocks } from '../../packages/shared/src/sdk';
+import { SwapState } from '../../packages/shared/src/utils/htlc';
+import type { HTLCDetails } from '../../packages/shared/src/utils/htlc';
+
+export type CheckStatus = 'pass' | 'fail' | 'warn';
+
+export interface LegCheck {
+  check: string;
+  status: CheckStatus;
+  detail: string;
+}
+
+export interface Leg {
+  chain: string;
+  htlc: HTLCDetails | null;
+}
+
+export interface VerifyExpectations {
+  /** Address that must receive the destination leg (you) */
+  recipient?: string;
+  /** Exact base-unit amounts each leg must lock */
+  sourceAmount?: string;
+  destAmount?: string;
+  /** Least destination base units per source base unit */
+  minRate?: number;
+  /** Token the destination leg must pay in; omitted means no check */
+  destToken?: string;
+}
+
+/**
+ * Whether the two escrows of a swap are safe to fund against: the same
+ * hashlock, the agreed amounts and recipient, and a source expiry far enough
+ * after the destination expiry for the source claimer to act on the reveal.
+ * `source` is the leg the counterparty claims with the revealed secret.
+ */
+export function verifyLegs(source: Leg, dest: Leg, expect: VerifyExpectations, now: number): LegCheck[] {
+  const checks: LegCheck[] = [];
+  const add = (check: string, status: CheckStatus, detail: string) => checks.push({ check, status, detail });
+
+  for (const [name, leg] of [['source', source], ['destination', dest]] as const) {
+    if (!leg.htlc || leg.htlc.state === SwapState.INVALID) {
+      add(`${name} exists`, 'fail', `no HTLC on ${leg.chain}`);
+    } else if (leg.htlc.state !== SwapState.OPEN) {
+      add(`${name} open`, 'fail', `already ${SwapState[leg.htlc.state].toLowerCase()}`);
+    } else {
+      add(`${name} open`, 'pass', `${leg.htlc.contractId} on ${leg.chain}`);
+    }
+  }
+  const s = source.htlc;
+  const d = dest.htlc;
+  if (!s || !d || s.state === SwapState.INVALID || d.state === SwapState.INVALID) return checks;
+
+  add(
+    'hashlock',
+    s.hashLock.toLowerCase() === d.hashLock.toLowerCase() ? 'pass' : 'fail',
+    s.hashLock.toLowerCase() === d.hashLock.toLowerCase() ? s.hashLock : `source ${s.hashLock} ≠ destination ${d.hashLock}`
+  );
+
+  if (expect.sourceAmount !== undefined) {
+    add('source amount', BigInt(s.value) === BigInt(expect.sourceAmount) ? 'pass' : 'fail', `${s.value} locked, ${expect.sourceAmount} expected`);
+  }
+  if (expect.destAmount !== undefined) {
+    add('destination amount', BigInt(d.value) === BigInt(expect.destAmount) ? 'pass' : 'fail', `${d.value} locked, ${expect.destAmount} expected`);
+  }
+  if (expect.minRate !== undefined) {
+    const rate = Number(BigInt(d.value)) / Number(BigInt(s.value));
+    add('rate', rate >= expect.minRate ? 'pass' : 'fail', `${rate} destination units per source unit, at least ${expect.minRate} expected`);
+  }
+  if (expect.destToken !== undefined) {
+    const same = normalizeToken(d.token) === normalizeToken(expect.destToken);
+    add('destination token', same ? 'pass' : 'fail', `${d.token || 'native'}${same ? '' : `, ${expect.destToken} expected`}`);
+  }
+
+  if (expect.recipient) {
+    const ok = d.beneficiary.toLowerCase() === expect.recipient.toLowerCase();
+    add('recipient', ok ? 'pass' : 'fail', ok ? d.beneficiary : `destination pays ${d.beneficiary}, not ${expect.recipient}`);
+  } else {
+    add('recipient', 'warn', `destination pays ${d.beneficiary}; pass --recipient to check it is you`);
+  }
+
+  // The counterparty claims the source and funds the destination; comparable only on one chain family
+  if (allChains[source.chain]?.type === allChains[dest.chain]?.type) {
+    const ok = s.beneficiary.toLowerCase() === d.originator.toLowerCase();
+    add('counterparty', ok ? 'pass' : 'warn', ok ? s.beneficiary : `source pays ${s.beneficiary} but ${d.originator} funded the destination`);
+  } else {
+    add('counterparty', 'warn', `source pays ${s.beneficiary}, destination funded by ${d.originator} (different chain families)`);
+  }
+
+  const timelockErrors = checkTimelocks(source.chain, dest.chain, { sourceExpiry: s.timelock, destExpiry: d.timelock }, now);
+  add(
+    'timelock ordering',
+    timelockErrors.length ? 'fail' : 'pass',
+    timelockErrors.length
+      ? timelockErrors.map(error => error.message).join('; ')
+      : `destination expires ${d.timelock - now}s from now, source ${s.timelock - d.timelock}s after it`
+  );
+
+  return checks;
+}
+
+function normalizeToken(token: string): string {
+  return !token || /^0x0{40}$/i.test(token) ? '' : token.toLowerCase();
+}
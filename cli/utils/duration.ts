@@ -0,0 +1,20 @@
+const UNITS: Record<string, number> = { s: 1, m: 60, h: 3600, d: 86400 };
+
+/**
+ * Parse "90", "30s", "15m", "2h" or "1d" into seconds
+ */
+export function parseDuration(value: string): number {
+  const match = /^(\d+)([smhd]?)$/.exec(value.trim());
+  if (!match) {
+    throw new Error(`Invalid duration "${value}"; use e.g. 30m, 2h or 1d`);
+  }
+  return Number(match[1]) * UNITS[match[2] || 's'];
+}
+
+/** An ISO date, or a duration back from now (`30d`), as Unix seconds */
+export function parseWhen(value: string): number {
+  if (/^\d+[smhd]$/.test(value)) return Math.floor(Date.now() / 1000) - parseDuration(value);
+  const time = Date.parse(value);
+  if (Number.isNaN(time)) throw new Error(`Invalid date "${value}"; use an ISO date or a duration like 30d`);
+  return Math.floor(time / 1000);
+}
@@ -4,7 +4,8 @@ export enum LogLevel {
   DEBUG = 0,
   INFO = 1,
   WARN = 2,
-  ERROR = 3
+  ERROR = 3,
+  SILENT = 4
 }
 
 export class Logger {
@@ -0,0 +1,55 @@
+import { Logger, LogLevel } from './logger';
+import { spinner } from './spinner';
+
+export type OutputFormat = 'text' | 'json';
+
+let format: OutputFormat = 'text';
+
+/**
+ * Switch between human-readable output and JSON. In JSON mode the logger and
+ * spinners are silenced so stdout carries nothing but JSON documents.
+ */
+export function setOutputFormat(value: string) {
+  if (value !== 'text' && value !== 'json') {
+    throw new Error(`Unknown output format "${value}"; expected text or json`);
+  }
+  format = value;
+  if (format === 'json') {
+    Logger.setLevel(LogLevel.SILENT);
+    spinner.setSilent(true);
+  }
+}
+
+export function isJsonOutput(): boolean {
+  return format === 'json';
+}
+
+/**
+ * Print a command's result: as one JSON document in JSON mode, otherwise with `render`
+ */
+export function emit<T>(result: T, render: (result: T) => void) {
+  if (format === 'json') {
+    console.log(JSON.stringify(result, jsonReplacer));
+  } else {
+    render(result);
+  }
+}
+
+/**
+ * Report a failure and exit; `{ "error": { "message": ... } }` in JSON mode
+ */
+export function exitWithError(error: unknown, exitCode = 1): never {
+  const message = error instanceof Error ? error.message : String(error);
+  if (format === 'json') {
+    const code = (error as { code?: unknown; kind?: unknown })?.kind ?? (error as { code?: unknown })?.code;
+    console.log(JSON.stringify({ error: { message, ...(code !== undefined ? { code } : {}) } }));
+  } else {
+    Logger.error(message);
+  }
+  process.exit(exitCode);
+}
+
+// bigints (balances, amounts) as decimal strings
+function jsonReplacer(_key: string, value: unknown) {
+  return typeof value === 'bigint' ? value.toString() : value;
+}
@@ -4,6 +4,7 @@ import chalk from 'chalk';
 export class SpinnerManager {
   private static instance: SpinnerManager;
   private spinners: Map<string, Ora> = new Map();
+  private silent = false;
 
   static getInstance(): SpinnerManager {
     if (!SpinnerManager.instance) {
@@ -16,13 +17,18 @@ export class SpinnerManager {
     const spinner = ora({
       text: chalk.blue(text),
       color: 'blue',
-      spinner: 'dots'
+      spinner: 'dots',
+      isSilent: this.silent
     }).start();
     
     this.spinners.set(id, spinner);
     return spinner;
   }
 
+  setSilent(silent: boolean) {
+    this.silent = silent;
+  }
+
   update(id: string, text: string) {
     const spinner = this.spinners.get(id);
     if (spinner) {
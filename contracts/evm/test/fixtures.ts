@@ -0,0 +1,99 @@
+import { ethers } from "hardhat";
+import { mine, time } from "@nomicfoundation/hardhat-network-helpers";
+import { HardhatEthersSigner } from "@nomicfoundation/hardhat-ethers/signers";
+import { MockUSDC, SimpleHTLC } from "../typechain-types";
+
+// Shared setup for the SimpleHTLC tests. Wrap `deployHTLC` in `loadFixture`
+// when a test wants a fresh snapshot rather than a fresh deployment.
+
+/** Balance every signer gets in the fixture, in USDC base units */
+export const USDC_BALANCE = 10n ** 12n;
+
+export interface HTLCFixture {
+  htlc: SimpleHTLC;
+  usdc: MockUSDC;
+  htlcAddress: string;
+  usdcAddress: string;
+  signers: HardhatEthersSigner[];
+}
+
+/** SimpleHTLC and MockUSDC, with every signer holding USDC and the HTLC approved to spend it */
+export async function deployHTLC(signerCount = 4): Promise<HTLCFixture> {
+  const signers = (await ethers.getSigners()).slice(0, signerCount);
+  const htlc = await (await ethers.getContractFactory("SimpleHTLC")).deploy();
+  const usdc = await (await ethers.getContractFactory("MockUSDC")).deploy();
+  const htlcAddress = await htlc.getAddress();
+  for (const signer of signers) {
+    await usdc.mint(signer.address, USDC_BALANCE);
+    await usdc.connect(signer).approve(htlcAddress, ethers.MaxUint256);
+  }
+  return { htlc, usdc, htlcAddress, usdcAddress: await usdc.getAddress(), signers };
+}
+
+export interface Secret {
+  preimage: string;
+  hashLock: string;
+}
+
+/** A random preimage and the sha256 hashlock SimpleHTLC checks it against */
+export function newSecret(): Secret {
+  const preimage = ethers.hexlify(ethers.randomBytes(32));
+  return { preimage, hashLock: ethers.sha256(preimage) };
+}
+
+export interface LockParams {
+  from: HardhatEthersSigner;
+  beneficiary: HardhatEthersSigner | string;
+  hashLock: string;
+  timelock: number;
+  value: bigint;
+  /** Defaults to a random ID */
+  id?: string;
+  /** ERC-20 to lock instead of ETH */
+  token?: string;
+}
+
+/**
+ * Builds a `fund`/`fundETH` call. The transaction is returned unawaited so a
+ * test can assert on it directly, e.g. `await expect(lock.send()).to.emit(...)`.
+ */
+export function lock(htlc: SimpleHTLC, params: LockParams) {
+  const id = params.id ?? ethers.hexlify(ethers.randomBytes(32));
+  const beneficiary = typeof params.beneficiary === "string" ? params.beneficiary : params.beneficiary.address;
+  const send = () => params.token
+    ? htlc.connect(params.from).fund(id, params.token, beneficiary, params.hashLock, params.timelock, params.value)
+    : htlc.connect(params.from).fundETH(id, beneficiary, params.hashLock, params.timelock, { value: params.value });
+  return { id, send };
+}
+
+/** Latest block timestamp, as a number for timelock arithmetic */
+export async function now(): Promise<number> {
+  return Number(await time.latest());
+}
+
+// Time travel. `advanceTo` and `advancePast` fix the block.timestamp the *next*
+// transaction executes with, so `advanceTo(timelock)` then `claim()` runs exactly
+// at the timelock rather than a second or more after it.
+
+/** The next transaction runs with block.timestamp == `timestamp` */
+export async function advanceTo(timestamp: number): Promise<void> {
+  await time.setNextBlockTimestamp(timestamp);
+}
+
+/** The next transaction runs one second after `timestamp`, the first moment it has passed */
+export async function advancePast(timestamp: number): Promise<void> {
+  await advanceTo(timestamp + 1);
+}
+
+/** Mines an empty block at `timestamp`, for when nothing else will be sent there */
+export async function mineAt(timestamp: number): Promise<void> {
+  await advanceTo(timestamp);
+  await mine();
+}
+
+/** Mines empty blocks until the chain is at `height` */
+export async function setHeight(height: number): Promise<void> {
+  const current = await ethers.provider.getBlockNumber();
+  if (height < current) throw new Error(`Chain is already at height ${current}`);
+  if (height > current) await mine(height - current);
+}
@@ -0,0 +1,65 @@
+import { expect } from "chai";
+import fs from "fs";
+import path from "path";
+import { ContractTransactionResponse } from "ethers";
+import { advancePast, deployHTLC, lock, newSecret, now } from "./fixtures";
+
+// Gas per SimpleHTLC entry point, checked against the committed baseline.
+// A run fails when any operation costs more than GAS_TOLERANCE_PCT (default 2)
+// percent over its baseline. After an intended change, refresh the file with
+//   pnpm gas:update
+const BASELINE_FILE = path.join(__dirname, "gas-baseline.json");
+const TOLERANCE_PCT = Number(process.env.GAS_TOLERANCE_PCT ?? 2);
+const UPDATE = process.env.UPDATE_GAS_BASELINE === "1";
+
+async function gasOf(tx: Promise<ContractTransactionResponse>): Promise<number> {
+  const receipt = await (await tx).wait();
+  return Number(receipt!.gasUsed);
+}
+
+describe("SimpleHTLC gas", function () {
+  it(`stays within ${TOLERANCE_PCT}% of the baseline`, async function () {
+    const { htlc, usdcAddress, signers: [alice, bob] } = await deployHTLC(2);
+    const measured: Record<string, number> = {};
+
+    const open = async (token?: string) => {
+      const secret = newSecret();
+      const timelock = (await now()) + 3600;
+      const { id, send } = lock(htlc, { from: alice, beneficiary: bob, ...secret, timelock, value: 1_000_000n, token });
+      return { id, timelock, secret, gas: await gasOf(send()) };
+    };
+
+    const eth = await open();
+    measured.fundETH = eth.gas;
+    measured.claimETH = await gasOf(htlc.connect(bob).claim(eth.id, eth.secret.preimage));
+
+    const erc20 = await open(usdcAddress);
+    measured.fundERC20 = erc20.gas;
+    measured.claimERC20 = await gasOf(htlc.connect(bob).claim(erc20.id, erc20.secret.preimage));
+
+    const expiring = await open();
+    const expiringToken = await open(usdcAddress);
+    await advancePast(expiringToken.timelock);
+    measured.refundETH = await gasOf(htlc.connect(alice).refund(expiring.id));
+    measured.refundERC20 = await gasOf(htlc.connect(alice).refund(expiringToken.id));
+
+    if (UPDATE || !fs.existsSync(BASELINE_FILE)) {
+      fs.writeFileSync(BASELINE_FILE, JSON.stringify(measured, null, 2) + "\n");
+      console.log(`    wrote ${path.relative(process.cwd(), BASELINE_FILE)}`);
+      return;
+    }
+
+    const baseline: Record<string, number> = JSON.parse(fs.readFileSync(BASELINE_FILE, "utf8"));
+    const rows = Object.entries(measured).map(([operation, gas]) => {
+      const before = baseline[operation];
+      const change = before ? ((gas - before) / before) * 100 : NaN;
+      return { operation, baseline: before ?? "-", gas, change: isNaN(change) ? "new" : `${change.toFixed(2)}%` };
+    });
+    console.table(rows);
+
+    const missing = Object.keys(measured).filter(operation => baseline[operation] === undefined);
+    expect(missing, "operations missing from the baseline; run pnpm gas:update").to.deep.equal([]);
+    const regressed = rows.filter(row => Number(row.gas) > Number(row.baseline) * (1 + TOLERANCE_PCT / 100));
+    expect(regressed.map(row => `${row.operation}: ${row.baseline} -> ${row.gas}`), "gas regressions").to.deep.equal([]);
+  });
+});
@@ -0,0 +1,146 @@
+import { expect } from "chai";
+import { ethers } from "hardhat";
+import { advanceTo, deployHTLC, lock, mineAt, now as latest } from "./fixtures";
+
+// Random sequences of fund / claim / refund / time jumps against SimpleHTLC,
+// checked step by step against a model. Rerun a failure with the seed it prints:
+//   HTLC_PROPERTY_SEED=1234 pnpm test --grep "state machine"
+const RUNS = Number(process.env.HTLC_PROPERTY_RUNS ?? 20);
+const STEPS = Number(process.env.HTLC_PROPERTY_STEPS ?? 30);
+const SEED = Number(process.env.HTLC_PROPERTY_SEED ?? Date.now() % 2 ** 31);
+
+enum State { INVALID, PENDING, CLAIMED, REFUNDED }
+
+interface ModelHTLC {
+  id: string;
+  erc20: boolean;
+  originator: number;
+  beneficiary: number;
+  preimage: string;
+  hashLock: string;
+  timelock: number;
+  value: bigint;
+  state: State;
+}
+
+// mulberry32: small, seedable, good enough to pick actions
+function random(seed: number) {
+  let state = seed >>> 0;
+  const next = () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 2 ** 32;
+  };
+  return {
+    int: (min: number, max: number) => min + Math.floor(next() * (max - min + 1)),
+    pick: <T>(items: T[]) => items[Math.floor(next() * items.length)],
+    chance: (p: number) => next() < p
+  };
+}
+
+describe("SimpleHTLC state machine", function () {
+  this.timeout(0);
+
+  it(`keeps escrow and state invariants over ${RUNS} random sequences (seed ${SEED})`, async function () {
+    for (let run = 0; run < RUNS; run++) {
+      const seed = SEED + run;
+      try {
+        await runSequence(seed);
+      } catch (error) {
+        throw new Error(`Sequence failed with HTLC_PROPERTY_SEED=${seed}: ${(error as Error).message}`);
+      }
+    }
+  });
+});
+
+async function runSequence(seed: number): Promise<void> {
+  const rng = random(seed);
+  const { htlc, usdc, htlcAddress, usdcAddress, signers: actors } = await deployHTLC();
+
+  const model: ModelHTLC[] = [];
+  let now = await latest();
+
+  // Every transaction lands exactly at `now + 1`, so the model knows block.timestamp
+  const at = async () => {
+    now += 1;
+    await advanceTo(now);
+  };
+
+  const checkInvariants = async () => {
+    const escrowed = (erc20: boolean) => model
+      .filter(entry => entry.erc20 === erc20 && entry.state === State.PENDING)
+      .reduce((sum, entry) => sum + entry.value, 0n);
+    expect(await ethers.provider.getBalance(htlcAddress), "ETH held equals open ETH HTLCs").to.equal(escrowed(false));
+    expect(await usdc.balanceOf(htlcAddress), "USDC held equals open USDC HTLCs").to.equal(escrowed(true));
+    for (const entry of model) {
+      const details = await htlc.getDetails(entry.id);
+      expect(Number(details.state), `state of ${entry.id}`).to.equal(entry.state);
+    }
+  };
+
+  for (let step = 0; step < STEPS; step++) {
+    const open = model.filter(entry => entry.state === State.PENDING);
+    const action = open.length === 0 ? "fund" : rng.pick(["fund", "claim", "claim", "refund", "refund", "wait"]);
+
+    if (action === "fund") {
+      const preimage = ethers.hexlify(ethers.randomBytes(32));
+      const entry: ModelHTLC = {
+        id: ethers.keccak256(ethers.toUtf8Bytes(`${seed}/${step}`)),
+        erc20: rng.chance(0.5),
+        originator: rng.int(0, actors.length - 1),
+        beneficiary: rng.int(0, actors.length - 1),
+        preimage,
+        hashLock: ethers.sha256(preimage),
+        timelock: now + rng.int(2, 600),
+        value: BigInt(rng.int(1, 1_000_000)),
+        state: State.PENDING
+      };
+      const from = actors[entry.originator];
+      const beneficiary = actors[entry.beneficiary];
+      await at();
+      await lock(htlc, { ...entry, from, beneficiary, token: entry.erc20 ? usdcAddress : undefined }).send();
+      model.push(entry);
+
+      // Reusing an ID is always refused, whatever state the first HTLC is in
+      const reused = rng.pick(model);
+      await at();
+      await expect(lock(htlc, { id: reused.id, from, beneficiary, hashLock: entry.hashLock, timelock: now + 100, value: 1n }).send())
+        .to.be.revertedWith("Contract already exists");
+    } else if (action === "claim") {
+      const entry = rng.pick(model);
+      const caller = rng.int(0, actors.length - 1);
+      const preimage = rng.chance(0.8) ? entry.preimage : ethers.hexlify(ethers.randomBytes(32));
+      await at();
+      const tx = htlc.connect(actors[caller]).claim(entry.id, preimage);
+
+      if (entry.state !== State.PENDING) await expect(tx).to.be.revertedWith("Contract is not pending");
+      else if (now > entry.timelock) await expect(tx).to.be.revertedWith("Contract has expired");
+      else if (preimage !== entry.preimage) await expect(tx).to.be.revertedWith("Invalid preimage");
+      else if (caller !== entry.beneficiary) await expect(tx).to.be.revertedWith("Only beneficiary can claim");
+      else {
+        await expect(tx).to.emit(htlc, "HTLCClaimed").withArgs(entry.id, actors[caller].address, preimage);
+        entry.state = State.CLAIMED;
+      }
+    } else if (action === "refund") {
+      const entry = rng.pick(model);
+      const caller = rng.int(0, actors.length - 1);
+      await at();
+      const tx = htlc.connect(actors[caller]).refund(entry.id);
+
+      if (entry.state !== State.PENDING) await expect(tx).to.be.revertedWith("Contract is not pending");
+      else if (now <= entry.timelock) await expect(tx).to.be.revertedWith("Contract has not expired");
+      else if (caller !== entry.originator) await expect(tx).to.be.revertedWith("Only originator can refund");
+      else {
+        await expect(tx).to.emit(htlc, "HTLCRefunded").withArgs(entry.id, actors[caller].address);
+        entry.state = State.REFUNDED;
+      }
+    } else {
+      now += rng.int(1, 400);
+      await mineAt(now);
+    }
+
+    await checkInvariants();
+  }
+}
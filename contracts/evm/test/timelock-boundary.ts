@@ -0,0 +1,85 @@
+import { expect } from "chai";
+import { takeSnapshot } from "@nomicfoundation/hardhat-network-helpers";
+import { advancePast, advanceTo, deployHTLC, lock, newSecret, now } from "./fixtures";
+
+// SimpleHTLC's claim window is `block.timestamp <= timelock` and its refund window
+// is `block.timestamp > timelock`. At every second around the boundary exactly one
+// of the two may succeed, so a swap can never pay out to both sides.
+describe("SimpleHTLC timelock boundary", function () {
+  async function openHTLC(token?: boolean) {
+    const fixture = await deployHTLC(2);
+    const { htlc, usdcAddress, signers: [alice, bob] } = fixture;
+    const secret = newSecret();
+    const timelock = (await now()) + 3600;
+    const { id, send } = lock(htlc, { from: alice, beneficiary: bob, ...secret, timelock, value: 1000n, token: token ? usdcAddress : undefined });
+    await send();
+    const claim = () => htlc.connect(bob).claim(id, secret.preimage);
+    const refund = () => htlc.connect(alice).refund(id);
+    return { ...fixture, id, timelock, claim, refund };
+  }
+
+  for (const token of [false, true]) {
+    describe(token ? "ERC-20" : "ETH", function () {
+      it("claims at exactly the timelock and refuses the refund there", async function () {
+        const { htlc, timelock, claim, refund } = await openHTLC(token);
+        const snapshot = await takeSnapshot();
+
+        await advanceTo(timelock);
+        await expect(refund()).to.be.revertedWith("Contract has not expired");
+        await snapshot.restore();
+
+        await advanceTo(timelock);
+        await expect(claim()).to.emit(htlc, "HTLCClaimed");
+      });
+
+      it("refunds one second after the timelock and refuses the claim there", async function () {
+        const { htlc, timelock, claim, refund } = await openHTLC(token);
+        const snapshot = await takeSnapshot();
+
+        await advancePast(timelock);
+        await expect(claim()).to.be.revertedWith("Contract has expired");
+        await snapshot.restore();
+
+        await advancePast(timelock);
+        await expect(refund()).to.emit(htlc, "HTLCRefunded");
+      });
+
+      it("lets exactly one of claim and refund through at each second around the timelock", async function () {
+        const { htlc, id, timelock, claim, refund } = await openHTLC(token);
+        const snapshot = await takeSnapshot();
+        const succeeds = async (send: () => Promise<unknown>, at: number) => {
+          await snapshot.restore();
+          await advanceTo(at);
+          try {
+            await send();
+            return true;
+          } catch {
+            return false;
+          }
+        };
+
+        for (const offset of [-1, 0, 1, 2]) {
+          const claimed = await succeeds(claim, timelock + offset);
+          const refunded = await succeeds(refund, timelock + offset);
+          expect({ offset, claimed, refunded }).to.deep.equal({ offset, claimed: offset <= 0, refunded: offset > 0 });
+        }
+
+        // And whichever lands first closes the other path for good
+        await snapshot.restore();
+        await advanceTo(timelock);
+        await claim();
+        await advancePast(timelock);
+        await expect(refund()).to.be.revertedWith("Contract is not pending");
+        expect(Number((await htlc.getDetails(id)).state)).to.equal(2);
+      });
+    });
+  }
+
+  it("refuses a timelock equal to the funding block's timestamp", async function () {
+    const { htlc, signers: [alice, bob] } = await deployHTLC(2);
+    const at = (await now()) + 10;
+    await advanceTo(at);
+    await expect(lock(htlc, { from: alice, beneficiary: bob, ...newSecret(), timelock: at, value: 1n }).send())
+      .to.be.revertedWith("Timelock must be in the future");
+  });
+});
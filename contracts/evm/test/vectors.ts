@@ -0,0 +1,49 @@
+import { expect } from "chai";
+import fs from "fs";
+import path from "path";
+import { deployHTLC, lock, now } from "./fixtures";
+
+// The shared corpus at the repo root, exported from the SDK by `pnpm vectors:generate`
+const VECTORS_FILE = path.join(__dirname, "..", "..", "..", "test-vectors", "htlc-vectors.json");
+
+describe("SimpleHTLC parity with SDK test vectors", function () {
+  const vectors = fs.existsSync(VECTORS_FILE)
+    ? JSON.parse(fs.readFileSync(VECTORS_FILE, "utf8")).vectors
+    : [];
+
+  before(function () {
+    if (vectors.length === 0) {
+      console.log(`    (no vectors at ${VECTORS_FILE}; run pnpm vectors:generate)`);
+      this.skip();
+    }
+  });
+
+  it("stores the SDK's sha256 hashlock and accepts the vector's claim calldata", async function () {
+    const { htlc, signers: [alice, bob] } = await deployHTLC(2);
+
+    for (const vector of vectors) {
+      const contractId = vector.swapId.id;
+      const timelock = (await now()) + 3600;
+
+      await lock(htlc, { id: contractId, from: alice, beneficiary: bob, hashLock: vector.evmHashLocks.sha256, timelock, value: 1n }).send();
+      const details = await htlc.contracts(contractId);
+      expect(details.hashLock).to.equal(vector.evmHashLocks.sha256);
+
+      // Sent as raw calldata, so the SDK's claim encoding is what's under test
+      await expect(bob.sendTransaction({ to: await htlc.getAddress(), data: vector.claimPayloads.evmCalldata }))
+        .to.emit(htlc, "HTLCClaimed")
+        .withArgs(contractId, bob.address, vector.secret);
+    }
+  });
+
+  it("rejects a secret from a different vector", async function () {
+    if (vectors.length < 2) this.skip();
+    const { htlc, signers: [alice, bob] } = await deployHTLC(2);
+
+    const [first, second] = vectors;
+    const timelock = (await now()) + 3600;
+    await lock(htlc, { id: first.swapId.id, from: alice, beneficiary: bob, hashLock: first.evmHashLocks.sha256, timelock, value: 1n }).send();
+
+    await expect(htlc.connect(bob).claim(first.swapId.id, second.secret)).to.be.revertedWith("Invalid preimage");
+  });
+});
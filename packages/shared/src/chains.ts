@@ -7,11 +7,33 @@ export interface ChainConfig {
   nativeDenom?: string;
   blockExplorer?: string;
   faucetUrl?: string;
+  /** Typical block interval in seconds */
+  blockTimeSeconds?: number;
+  /** Time until a block can be trusted (seconds); derived from the finality model when unset */
+  finalitySeconds?: number;
 }
 
+/**
+ * How an EVM chain finalizes blocks:
+ * - probabilistic: L1-style, a block is trusted after N confirmations
+ * - optimistic-rollup: OP Stack, trusted once the batch is posted to L1 ('safe' head)
+ * - arbitrum-rollup: Arbitrum Nitro, same as above via the 'safe' head
+ * - checkpoint: Polygon PoS, trusted once covered by a milestone ('finalized' head)
+ */
+export type EvmFinalityModel = 'probabilistic' | 'optimistic-rollup' | 'arbitrum-rollup' | 'checkpoint';
+
+/** Extra fee components charged on top of L2 execution gas */
+export type EvmGasModel = 'standard' | 'op-stack' | 'arbitrum';
+
 export interface EvmChainConfig extends ChainConfig {
   type: 'evm';
   chainId: number;
+  finality?: EvmFinalityModel;
+  gasModel?: EvmGasModel;
+  /** Blocks on top of an inclusion before acting on it (default 1) */
+  confirmations?: number;
+  /** keccak256 of the HTLC's runtime bytecode, for checking the deployment is the expected release */
+  htlcCodeHash?: string;
 }
 
 export interface CosmosChainConfig extends ChainConfig {
@@ -20,6 +42,12 @@ export interface CosmosChainConfig extends ChainConfig {
   nativeDenom: string;
   addressPrefix: string;
   codeId?: string;
+  /** sha256 of the wasm `codeId` should hold */
+  codeChecksum?: string;
+  /** Minimum gas price as a decimal amount, e.g. '0.025' */
+  gasPrice?: string;
+  /** Fee denom, when the chain charges fees in something other than nativeDenom */
+  gasDenom?: string;
 }
 
 // Environment variables with fallbacks
@@ -46,6 +74,7 @@ export const evmChains: Record<string, EvmChainConfig & { forwarders?: Forwarder
     name: 'Ethereum Sepolia',
     type: 'evm',
     chainId: 11155111,
+    blockTimeSeconds: 12,
     rpcUrl: getEnvVar('SEPOLIA_RPC_URL', 'https://eth-sepolia.g.alchemy.com/v2/MS9pGRxd1Jh3rhVjyIkFzVfG1g3BcTk3'),
     blockExplorer: 'https://sepolia.etherscan.io',
     htlcAddress: getEnvVar('NEXT_PUBLIC_SEPOLIA_HTLC', '0x5d981ca300DDAAb10D2bD98E3115264C1A2c168D'),
@@ -59,10 +88,14 @@ export const evmChains: Record<string, EvmChainConfig & { forwarders?: Forwarder
     name: 'Polygon Amoy Testnet',
     type: 'evm',
     chainId: 80002,
+    blockTimeSeconds: 2,
     rpcUrl: getEnvVar('POLYGON_AMOY_RPC_URL', 'https://polygon-amoy.g.alchemy.com/v2/MS9pGRxd1Jh3rhVjyIkFzVfG1g3BcTk3'),
     blockExplorer: 'https://amoy.polygonscan.com',
     htlcAddress: getEnvVar('NEXT_PUBLIC_POLYGON_AMOY_HTLC', '0x04139d1fCC2E6f8b964C257eFceEA99a783Df422'),
     faucetUrl: 'https://faucet.polygon.technology',
+    finality: 'checkpoint',
+    gasModel: 'standard',
+    confirmations: parseInt(getEnvVar('POLYGON_AMOY_CONFIRMATIONS', '5')),
     forwarders: {
       minimalForwarder: getEnvVar('POLYGON_AMOY_MINIMAL_FORWARDER', '0xFaE696466e232634F7349c88d6f338af4eA6fa6C'),
       htlcForwarder: null // Will be deployed when we get more MATIC
@@ -72,6 +105,7 @@ export const evmChains: Record<string, EvmChainConfig & { forwarders?: Forwarder
     name: 'Monad Testnet',
     type: 'evm',
     chainId: 10143,
+    blockTimeSeconds: 1,
     rpcUrl: getEnvVar('MONAD_RPC_URL', 'https://testnet-rpc.monad.xyz'),
     blockExplorer: 'https://testnet.monadexplorer.com',
     htlcAddress: getEnvVar('NEXT_PUBLIC_MONAD_HTLC', '0xAAaa9c73a0d91472B8a0eb4DEa373E08d3Cb60B9'), // SimpleHTLC that works
@@ -85,6 +119,7 @@ export const evmChains: Record<string, EvmChainConfig & { forwarders?: Forwarder
     name: 'Etherlink Testnet',
     type: 'evm',
     chainId: 128123, // Etherlink testnet chain ID
+    blockTimeSeconds: 1,
     rpcUrl: getEnvVar('ETHERLINK_RPC_URL', 'https://node.ghostnet.etherlink.com'),
     blockExplorer: 'https://testnet.explorer.etherlink.com',
     htlcAddress: getEnvVar('NEXT_PUBLIC_ETHERLINK_HTLC', '0xC9E4Df418AEeCA680D8933A730Bd207F17B3C260'),
@@ -93,13 +128,74 @@ export const evmChains: Record<string, EvmChainConfig & { forwarders?: Forwarder
       minimalForwarder: null,
       htlcForwarder: null
     }
+  },
+  // No SimpleHTLC is deployed on the L2s below yet: they are left out of htlcAddressBook,
+  // and so not served, until NEXT_PUBLIC_<CHAIN>_HTLC or HTLC_ADDRESS_<chainId> is set
+  arbitrumSepolia: {
+    name: 'Arbitrum Sepolia',
+    type: 'evm',
+    chainId: 421614,
+    blockTimeSeconds: 0.25,
+    rpcUrl: getEnvVar('ARBITRUM_SEPOLIA_RPC_URL', 'https://sepolia-rollup.arbitrum.io/rpc'),
+    blockExplorer: 'https://sepolia.arbiscan.io',
+    htlcAddress: getEnvVar('NEXT_PUBLIC_ARBITRUM_SEPOLIA_HTLC', ''),
+    faucetUrl: 'https://faucet.quicknode.com/arbitrum/sepolia',
+    finality: 'arbitrum-rollup',
+    gasModel: 'arbitrum',
+    confirmations: parseInt(getEnvVar('ARBITRUM_SEPOLIA_CONFIRMATIONS', '1')),
+    forwarders: {
+      minimalForwarder: null,
+      htlcForwarder: null
+    }
+  },
+  baseSepolia: {
+    name: 'Base Sepolia',
+    type: 'evm',
+    chainId: 84532,
+    blockTimeSeconds: 2,
+    rpcUrl: getEnvVar('BASE_SEPOLIA_RPC_URL', 'https://sepolia.base.org'),
+    blockExplorer: 'https://sepolia.basescan.org',
+    htlcAddress: getEnvVar('NEXT_PUBLIC_BASE_SEPOLIA_HTLC', ''),
+    faucetUrl: 'https://www.coinbase.com/faucets/base-ethereum-goerli-faucet',
+    finality: 'optimistic-rollup',
+    gasModel: 'op-stack',
+    confirmations: parseInt(getEnvVar('BASE_SEPOLIA_CONFIRMATIONS', '1')),
+    forwarders: {
+      minimalForwarder: null,
+      htlcForwarder: null
+    }
+  },
+  optimismSepolia: {
+    name: 'OP Sepolia',
+    type: 'evm',
+    chainId: 11155420,
+    blockTimeSeconds: 2,
+    rpcUrl: getEnvVar('OPTIMISM_SEPOLIA_RPC_URL', 'https://sepolia.optimism.io'),
+    blockExplorer: 'https://sepolia-optimism.etherscan.io',
+    htlcAddress: getEnvVar('NEXT_PUBLIC_OPTIMISM_SEPOLIA_HTLC', ''),
+    faucetUrl: 'https://app.optimism.io/faucet',
+    finality: 'optimistic-rollup',
+    gasModel: 'op-stack',
+    confirmations: parseInt(getEnvVar('OPTIMISM_SEPOLIA_CONFIRMATIONS', '1')),
+    forwarders: {
+      minimalForwarder: null,
+      htlcForwarder: null
+    }
   }
 };
 
+// HTLC deployments keyed by EVM chain ID; HTLC_ADDRESS_<chainId> overrides the chain default
+export const htlcAddressBook: Record<number, string> = Object.fromEntries(
+  Object.values(evmChains)
+    .map(chain => [chain.chainId, getEnvVar(`HTLC_ADDRESS_${chain.chainId}`, chain.htlcAddress)] as const)
+    .filter(([, address]) => !!address)
+);
+
 // Cosmos Chains Configuration
 export const cosmosChains: Record<string, CosmosChainConfig> = {
   local: {
     chainId: 'swap-sage-1',
+    blockTimeSeconds: 1,
     name: 'Local Wasmd',
     type: 'cosmos',
     rpcUrl: getEnvVar('COSMOS_RPC_URL', 'http://localhost:26657'),
@@ -112,6 +208,7 @@ export const cosmosChains: Record<string, CosmosChainConfig> = {
   },
   osmosisTestnet: {
     chainId: 'osmo-test-5',
+    blockTimeSeconds: 3,
     name: 'Osmosis Testnet',
     type: 'cosmos',
     rpcUrl: getEnvVar('OSMOSIS_TESTNET_RPC_URL', 'https://rpc.osmotest5.osmosis.zone'),
@@ -119,11 +216,13 @@ export const cosmosChains: Record<string, CosmosChainConfig> = {
     nativeDenom: 'uosmo',
     addressPrefix: 'osmo',
     codeId: getEnvVar('OSMOSIS_TESTNET_CODE_ID', ''),
+    gasPrice: getEnvVar('OSMOSIS_TESTNET_GAS_PRICE', '0.025'),
     blockExplorer: 'https://testnet.mintscan.io/osmosis-testnet',
     faucetUrl: 'https://faucet.osmotest5.osmosis.zone'
   },
   cosmosTestnet: {
     chainId: 'theta-testnet-001',
+    blockTimeSeconds: 6,
     name: 'Cosmos Hub Testnet',
     type: 'cosmos',
     rpcUrl: getEnvVar('COSMOS_TESTNET_RPC_URL', 'https://rpc.sentry-01.theta-testnet.polypore.xyz'),
@@ -131,8 +230,37 @@ export const cosmosChains: Record<string, CosmosChainConfig> = {
     nativeDenom: 'uatom',
     addressPrefix: 'cosmos',
     codeId: getEnvVar('COSMOS_TESTNET_CODE_ID', ''),
+    gasPrice: getEnvVar('COSMOS_TESTNET_GAS_PRICE', '0.025'),
     blockExplorer: 'https://explorer.theta-testnet.polypore.xyz',
     faucetUrl: 'Contact in Discord for testnet tokens'
+  },
+  neutronTestnet: {
+    chainId: 'pion-1',
+    blockTimeSeconds: 2,
+    name: 'Neutron Testnet',
+    type: 'cosmos',
+    rpcUrl: getEnvVar('NEUTRON_TESTNET_RPC_URL', 'https://rpc-palvus.pion-1.ntrn.tech'),
+    htlcAddress: getEnvVar('NEUTRON_TESTNET_HTLC_ADDRESS', ''),
+    nativeDenom: 'untrn',
+    addressPrefix: 'neutron',
+    codeId: getEnvVar('NEUTRON_TESTNET_CODE_ID', ''),
+    gasPrice: getEnvVar('NEUTRON_TESTNET_GAS_PRICE', '0.0053'),
+    blockExplorer: 'https://testnet.mintscan.io/neutron-testnet',
+    faucetUrl: 'https://t.me/+SyhWrlnwfCw2NGM6'
+  },
+  junoTestnet: {
+    chainId: 'uni-6',
+    blockTimeSeconds: 6,
+    name: 'Juno Testnet',
+    type: 'cosmos',
+    rpcUrl: getEnvVar('JUNO_TESTNET_RPC_URL', 'https://juno-testnet-rpc.polkachu.com'),
+    htlcAddress: getEnvVar('JUNO_TESTNET_HTLC_ADDRESS', ''),
+    nativeDenom: 'ujunox',
+    addressPrefix: 'juno',
+    codeId: getEnvVar('JUNO_TESTNET_CODE_ID', ''),
+    gasPrice: getEnvVar('JUNO_TESTNET_GAS_PRICE', '0.075'),
+    blockExplorer: 'https://testnet.mintscan.io/juno-testnet',
+    faucetUrl: 'https://faucet.reece.sh/uni-6/juno'
   }
 };
 
@@ -151,6 +279,14 @@ export const getEvmChainById = (chainId: number): EvmChainConfig | undefined =>
   return Object.values(evmChains).find((chain: EvmChainConfig & { forwarders?: ForwarderAddresses }) => chain.chainId === chainId);
 };
 
+export const getHtlcAddress = (chainId: number): string => {
+  const address = htlcAddressBook[chainId];
+  if (!address) {
+    throw new Error(`No HTLC contract deployed for chain ${chainId}`);
+  }
+  return address;
+};
+
 export const getCosmosChainById = (chainId: string): CosmosChainConfig | undefined => {
   return Object.values(cosmosChains).find((chain: CosmosChainConfig) => chain.chainId === chainId);
 };
@@ -168,6 +304,11 @@ export const isCosmosChain = (chain: ChainConfig): chain is CosmosChainConfig =>
   return chain.type === 'cosmos';
 };
 
+// Gas price string in the form cosmjs GasPrice.fromString expects, e.g. '0.025uosmo'
+export const getCosmosGasPrice = (chain: CosmosChainConfig): string => {
+  return `${chain.gasPrice || '0.025'}${chain.gasDenom || chain.nativeDenom}`;
+};
+
 // Default chains for development
 export const DEFAULT_EVM_CHAIN = evmChains.sepolia;
 export const DEFAULT_COSMOS_CHAIN = cosmosChains.local;
\ No newline at end of file
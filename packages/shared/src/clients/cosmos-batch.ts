@@ -0,0 +1,121 @@
+import type { ExecuteInstruction } from '@cosmjs/cosmwasm-stargate';
+import { CosmosHTLCClient, BatchExecuteOptions } from './cosmos';
+
+export interface CosmosBatchSubmitterOptions extends BatchExecuteOptions {
+  /** Flush as soon as this many messages are queued */
+  maxBatchSize?: number;
+  /** Flush whatever is queued at least this often (ms) */
+  flushIntervalMs?: number;
+}
+
+interface PendingMessage {
+  instruction: ExecuteInstruction;
+  resolve: (transactionHash: string) => void;
+  reject: (error: Error) => void;
+}
+
+/**
+ * Collects claims and refunds for one Cosmos account and submits them as packed
+ * multi-message transactions. Flushes run one at a time, so the account never has
+ * more than one transaction competing for the next sequence number.
+ */
+export class CosmosBatchSubmitter {
+  private queue: PendingMessage[] = [];
+  private timer?: NodeJS.Timeout;
+  private flushing?: Promise<void>;
+  private maxBatchSize: number;
+  private flushIntervalMs: number;
+
+  constructor(
+    private client: CosmosHTLCClient,
+    private senderAddress: string,
+    private options: CosmosBatchSubmitterOptions = {}
+  ) {
+    this.maxBatchSize = options.maxBatchSize ?? 20;
+    this.flushIntervalMs = options.flushIntervalMs ?? 3000;
+  }
+
+  start(): void {
+    if (this.timer) return;
+    this.timer = setInterval(() => {
+      this.flush().catch(() => undefined);
+    }, this.flushIntervalMs);
+  }
+
+  async stop(): Promise<void> {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+    await this.flush();
+  }
+
+  get pending(): number {
+    return this.queue.length;
+  }
+
+  /**
+   * Queue a claim; resolves with the hash of the transaction that carried it
+   */
+  claim(contractAddress: string, preimage: string): Promise<string> {
+    return this.enqueue({ contractAddress, msg: CosmosHTLCClient.claimMsg(preimage) });
+  }
+
+  /**
+   * Queue a refund; resolves with the hash of the transaction that carried it
+   */
+  refund(contractAddress: string): Promise<string> {
+    return this.enqueue({ contractAddress, msg: CosmosHTLCClient.refundMsg() });
+  }
+
+  async flush(): Promise<void> {
+    // Chain onto any in-progress flush instead of running two concurrently
+    while (this.flushing) {
+      await this.flushing;
+    }
+    if (this.queue.length === 0) return;
+
+    const batch = this.queue.splice(0, this.queue.length);
+    this.flushing = this.submit(batch).finally(() => {
+      this.flushing = undefined;
+    });
+    await this.flushing;
+  }
+
+  private enqueue(instruction: ExecuteInstruction): Promise<string> {
+    const duplicate = this.queue.find(pending => pending.instruction.contractAddress === instruction.contractAddress);
+    if (duplicate) {
+      return Promise.reject(new Error(`A message for ${instruction.contractAddress} is already queued`));
+    }
+
+    const promise = new Promise<string>((resolve, reject) => {
+      this.queue.push({ instruction, resolve, reject });
+    });
+
+    if (this.queue.length >= this.maxBatchSize) {
+      this.flush().catch(() => undefined);
+    }
+    return promise;
+  }
+
+  private async submit(batch: PendingMessage[]): Promise<void> {
+    try {
+      const outcomes = await this.client.executeBatch(
+        this.senderAddress,
+        batch.map(pending => pending.instruction),
+        this.options
+      );
+
+      for (const pending of batch) {
+        const outcome = outcomes.find(result => result.contractAddress === pending.instruction.contractAddress);
+        if (outcome?.transactionHash) {
+          pending.resolve(outcome.transactionHash);
+        } else {
+          pending.reject(new Error(outcome?.error || 'Message was not submitted'));
+        }
+      }
+    } catch (error) {
+      batch.forEach(pending => pending.reject(error as Error));
+    }
+  }
+}
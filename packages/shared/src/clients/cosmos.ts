@@ -2,14 +2,23 @@ import {
   SigningCosmWasmClient, 
   CosmWasmClient,
   ExecuteResult,
-  InstantiateResult
+  ExecuteInstruction,
+  InstantiateResult,
+  MsgExecuteContractEncodeObject,
+  MsgInstantiateContractEncodeObject
 } from '@cosmjs/cosmwasm-stargate';
 import { StargateClient } from '@cosmjs/stargate';
-import { OfflineSigner } from '@cosmjs/proto-signing';
-import { fromHex } from '@cosmjs/encoding';
-import { GasPrice } from '@cosmjs/stargate';
+import { OfflineSigner, DirectSecp256k1HdWallet } from '@cosmjs/proto-signing';
+import type { EncodeObject } from '@cosmjs/proto-signing';
+import { fromHex, toUtf8 } from '@cosmjs/encoding';
+import type { Coin, IndexedTx, StdFee } from '@cosmjs/stargate';
+import { GasPrice, calculateFee } from '@cosmjs/stargate';
 import type { CosmosChainConfig } from '../chains';
+import { getCosmosGasPrice } from '../chains';
 import { SwapState } from '../utils/index';
+import type { FeeAction, FeeGuard } from './fees';
+import { CosmosSequenceManager } from '../resolver/nonce-manager';
+import type { AccountManagerOptions } from '../resolver/nonce-manager';
 import type { 
   HTLCDetails, 
   CreateHTLCParams, 
@@ -55,19 +64,49 @@ export interface SwapResponse {
   state: 'Open' | 'Claimed' | 'Refunded';
 }
 
+export interface BatchExecuteOptions {
+  /** Upper bound on gas for a single packed transaction */
+  maxGasPerTx?: number;
+  /** Safety factor applied to simulated gas when packing (matches cosmjs 'auto') */
+  gasMultiplier?: number;
+  memo?: string;
+}
+
+export interface BatchExecuteOutcome {
+  contractAddress: string;
+  transactionHash?: string;
+  error?: string;
+}
+
 export interface CosmosHTLCClientOptions {
   chain: CosmosChainConfig;
   client?: CosmWasmClient | SigningCosmWasmClient;
   signer?: OfflineSigner;
+  /** Simulates and budget-checks every transaction before broadcast; without one cosmjs 'auto' fees are used */
+  feeGuard?: FeeGuard;
+  /** In-flight window and confirmation timeout for each signing account's sequence manager */
+  accounts?: AccountManagerOptions;
+}
+
+/** A signing client together with the account it signs for */
+export interface CosmosSigningAccount {
+  client: CosmosHTLCClient;
+  address: string;
 }
 
 export class CosmosHTLCClient {
   private client: CosmWasmClient | SigningCosmWasmClient;
   private signingClient?: SigningCosmWasmClient;
+  private feeGuard?: FeeGuard;
+  private accountOptions: AccountManagerOptions;
+  // One sequence manager per signing account, shared by every write path of this client
+  private sequences = new Map<string, CosmosSequenceManager>();
   public readonly chain: CosmosChainConfig;
 
   constructor(options: CosmosHTLCClientOptions) {
     this.chain = options.chain;
+    this.feeGuard = options.feeGuard;
+    this.accountOptions = options.accounts ?? {};
     
     if (options.client) {
       this.client = options.client;
@@ -80,6 +119,41 @@ export class CosmosHTLCClient {
     }
   }
 
+  /**
+   * Connect a read-only client for queries
+   */
+  static async connect(chain: CosmosChainConfig): Promise<CosmosHTLCClient> {
+    return new CosmosHTLCClient({ chain, client: await CosmWasmClient.connect(chain.rpcUrl) });
+  }
+
+  /**
+   * Build a signing client for a chain from a mnemonic, deriving the address with the chain's bech32 prefix
+   */
+  static async fromMnemonic(
+    chain: CosmosChainConfig,
+    mnemonic: string,
+    feeGuard?: FeeGuard
+  ): Promise<CosmosSigningAccount> {
+    const signer = await DirectSecp256k1HdWallet.fromMnemonic(mnemonic, { prefix: chain.addressPrefix });
+    return CosmosHTLCClient.fromSigner(chain, signer, feeGuard);
+  }
+
+  /**
+   * Connect a signing client for any OfflineSigner (e.g. a Ledger); its first
+   * account signs
+   */
+  static async fromSigner(
+    chain: CosmosChainConfig,
+    signer: OfflineSigner,
+    feeGuard?: FeeGuard
+  ): Promise<CosmosSigningAccount> {
+    const [account] = await signer.getAccounts();
+    const signingClient = await SigningCosmWasmClient.connectWithSigner(chain.rpcUrl, signer, {
+      gasPrice: GasPrice.fromString(getCosmosGasPrice(chain))
+    });
+    return { client: new CosmosHTLCClient({ chain, client: signingClient, feeGuard }), address: account.address };
+  }
+
   /**
    * Connect with signer for write operations
    */
@@ -87,9 +161,10 @@ export class CosmosHTLCClient {
     this.signingClient = await SigningCosmWasmClient.connectWithSigner(
       this.chain.rpcUrl,
       signer,
-      { gasPrice: GasPrice.fromString(`0.025${this.chain.nativeDenom}`) }
+      { gasPrice: GasPrice.fromString(getCosmosGasPrice(this.chain)) }
     );
     this.client = this.signingClient;
+    this.sequences.clear();
   }
 
   /**
@@ -128,18 +203,32 @@ export class CosmosHTLCClient {
       token: params.token || undefined
     };
 
-    const result = await this.signingClient.instantiate(
-      senderAddress,
-      actualCodeId,
-      instantiateMsg,
-      `SwapSage HTLC ${params.contractId}`,
-      'auto',
-      { funds: params.token ? [] : [{ denom: this.chain.nativeDenom, amount: params.value }] }
-    );
+    const label = `SwapSage HTLC ${params.contractId}`;
+    const funds = params.token ? [] : [{ denom: this.chain.nativeDenom, amount: params.value }];
+    const message: MsgInstantiateContractEncodeObject = {
+      typeUrl: '/cosmwasm.wasm.v1.MsgInstantiateContract',
+      value: {
+        sender: senderAddress,
+        admin: '',
+        codeId: BigInt(actualCodeId),
+        label,
+        msg: toUtf8(JSON.stringify(instantiateMsg)),
+        funds
+      }
+    };
+
+    const result = await this.broadcast(senderAddress, [message], await this.fee(['fund'], senderAddress, [message]));
+    const contractAddress = result.events
+      .find(event => event.type === 'instantiate')
+      ?.attributes.find(attribute => attribute.key === '_contract_address')
+      ?.value;
+    if (!contractAddress) {
+      throw new Error(`Instantiate transaction ${result.transactionHash} emitted no contract address`);
+    }
 
     return {
-      contractAddress: result.contractAddress,
-      result
+      contractAddress,
+      result: { ...result, contractAddress }
     };
   }
 
@@ -153,15 +242,9 @@ export class CosmosHTLCClient {
 
     const executeMsg: ExecuteMsg = { fund: {} };
     const funds = amount ? [{ denom: this.chain.nativeDenom, amount }] : [];
+    const messages = [this.executeMessage(contractAddress, senderAddress, executeMsg, funds)];
 
-    return this.signingClient.execute(
-      senderAddress,
-      contractAddress,
-      executeMsg,
-      'auto',
-      'Funding HTLC',
-      funds
-    );
+    return this.broadcast(senderAddress, messages, await this.fee(['fund'], senderAddress, messages, 'Funding HTLC'), 'Funding HTLC');
   }
 
   /**
@@ -172,22 +255,94 @@ export class CosmosHTLCClient {
       throw new Error('Signing client required for claim operation');
     }
 
-    // Convert hex preimage to base64
+    const messages = [this.executeMessage(contractAddress, senderAddress, CosmosHTLCClient.claimMsg(preimage))];
+    return this.broadcast(senderAddress, messages, await this.fee(['claim'], senderAddress, messages, 'Claiming HTLC'), 'Claiming HTLC');
+  }
+
+  /**
+   * Build a claim execute message (hex preimage is converted to base64)
+   */
+  static claimMsg(preimage: string): ExecuteMsg {
     const preimageHex = preimage.startsWith('0x') ? preimage.slice(2) : preimage;
     const preimageBytes = fromHex(preimageHex);
-    const preimageBase64 = Buffer.from(preimageBytes).toString('base64');
+    return { claim: { preimage: Buffer.from(preimageBytes).toString('base64') } };
+  }
 
-    const executeMsg: ExecuteMsg = {
-      claim: { preimage: preimageBase64 }
-    };
+  static refundMsg(): ExecuteMsg {
+    return { refund: {} };
+  }
 
-    return this.signingClient.execute(
-      senderAddress,
-      contractAddress,
-      executeMsg,
-      'auto',
-      'Claiming HTLC'
-    );
+  /**
+   * Submit several execute messages (e.g. claims and refunds on different HTLC
+   * instances) packed into as few transactions as the gas limit allows.
+   *
+   * Every message is simulated on its own first: one that would fail is reported
+   * individually instead of reverting the whole packed transaction.
+   */
+  async executeBatch(
+    senderAddress: string,
+    instructions: ExecuteInstruction[],
+    options: BatchExecuteOptions = {}
+  ): Promise<BatchExecuteOutcome[]> {
+    if (!this.signingClient) {
+      throw new Error('Signing client required for batch execute');
+    }
+
+    const maxGasPerTx = options.maxGasPerTx ?? 2_000_000;
+    const gasMultiplier = options.gasMultiplier ?? this.feeGuard?.gasMultiplier ?? 1.4;
+    const outcomes: BatchExecuteOutcome[] = [];
+    const batches: { instructions: ExecuteInstruction[]; gas: number }[] = [];
+
+    for (const instruction of instructions) {
+      let gas: number;
+      try {
+        gas = Math.ceil(await this.simulateExecute(
+          instruction.contractAddress,
+          senderAddress,
+          instruction.msg as ExecuteMsg,
+          [...(instruction.funds || [])]
+        ) * gasMultiplier);
+      } catch (error) {
+        outcomes.push({ contractAddress: instruction.contractAddress, error: (error as Error).message });
+        continue;
+      }
+
+      const current = batches[batches.length - 1];
+      if (current && current.gas + gas <= maxGasPerTx) {
+        current.instructions.push(instruction);
+        current.gas += gas;
+      } else {
+        batches.push({ instructions: [instruction], gas });
+      }
+    }
+
+    for (const batch of batches) {
+      try {
+        // Each message was already simulated, so the packed limit is the sum of the padded estimates
+        const fee: StdFee | 'auto' = this.feeGuard
+          ? this.feeGuard.cosmosForLimit(batch.instructions.map(instruction => feeActionOf(instruction.msg)), batch.gas)
+          : 'auto';
+        const memo = options.memo ?? `Batch of ${batch.instructions.length} HTLC messages`;
+        const messages = batch.instructions.map(instruction => this.executeMessage(
+          instruction.contractAddress,
+          senderAddress,
+          instruction.msg as ExecuteMsg,
+          [...(instruction.funds || [])]
+        ));
+        const result = await this.broadcast(senderAddress, messages, fee, memo);
+        outcomes.push(...batch.instructions.map(instruction => ({
+          contractAddress: instruction.contractAddress,
+          transactionHash: result.transactionHash
+        })));
+      } catch (error) {
+        outcomes.push(...batch.instructions.map(instruction => ({
+          contractAddress: instruction.contractAddress,
+          error: (error as Error).message
+        })));
+      }
+    }
+
+    return outcomes;
   }
 
   /**
@@ -198,17 +353,95 @@ export class CosmosHTLCClient {
       throw new Error('Signing client required for refund operation');
     }
 
-    const executeMsg: ExecuteMsg = { refund: {} };
+    const messages = [this.executeMessage(contractAddress, senderAddress, CosmosHTLCClient.refundMsg())];
+    return this.broadcast(senderAddress, messages, await this.fee(['refund'], senderAddress, messages, 'Refunding HTLC'), 'Refunding HTLC');
+  }
+
+  /**
+   * Simulate an execute message without broadcasting it.
+   * Returns the gas the transaction would use; throws if the contract would reject it.
+   */
+  async simulateExecute(
+    contractAddress: string,
+    senderAddress: string,
+    executeMsg: ExecuteMsg,
+    funds: Coin[] = [],
+    memo: string = ''
+  ): Promise<number> {
+    if (!this.signingClient) {
+      throw new Error('Signing client required for simulate operation');
+    }
 
-    return this.signingClient.execute(
+    return this.signingClient.simulate(
       senderAddress,
-      contractAddress,
-      executeMsg,
-      'auto',
-      'Refunding HTLC'
+      [this.executeMessage(contractAddress, senderAddress, executeMsg, funds)],
+      memo
     );
   }
 
+  private executeMessage(
+    contractAddress: string,
+    senderAddress: string,
+    executeMsg: ExecuteMsg,
+    funds: Coin[] = []
+  ): MsgExecuteContractEncodeObject {
+    return {
+      typeUrl: '/cosmwasm.wasm.v1.MsgExecuteContract',
+      value: {
+        sender: senderAddress,
+        contract: contractAddress,
+        msg: toUtf8(JSON.stringify(executeMsg)),
+        funds
+      }
+    };
+  }
+
+  /**
+   * Sign with the account's sequence manager and wait for inclusion. Transactions
+   * from one account are sequenced locally instead of by cosmjs, so several can sit
+   * in the mempool at once and sequence mismatches are recovered from. Anything else
+   * sending from the account (e.g. IBC transfers) should go through here too.
+   */
+  async broadcast(
+    senderAddress: string,
+    messages: EncodeObject[],
+    fee: StdFee | 'auto',
+    memo = ''
+  ): Promise<ExecuteResult> {
+    if (!this.signingClient) {
+      throw new Error('Signing client required for broadcast');
+    }
+
+    const explicitFee = fee === 'auto'
+      ? calculateFee(
+        Math.round(await this.signingClient!.simulate(senderAddress, messages, memo) * 1.4),
+        GasPrice.fromString(getCosmosGasPrice(this.chain))
+      )
+      : fee;
+
+    const { confirmed } = await this.sequenceManager(senderAddress).signAndBroadcast(messages, explicitFee, memo);
+    return executeResultOf(await confirmed);
+  }
+
+  private sequenceManager(senderAddress: string): CosmosSequenceManager {
+    let manager = this.sequences.get(senderAddress);
+    if (!manager) {
+      manager = new CosmosSequenceManager(this.signingClient!, senderAddress, this.chain.chainId, this.accountOptions);
+      this.sequences.set(senderAddress, manager);
+    }
+    return manager;
+  }
+
+  /**
+   * Fee for a transaction: simulated and checked against the budget when a fee
+   * guard is configured, otherwise left to cosmjs
+   */
+  private async fee(actions: FeeAction[], senderAddress: string, messages: EncodeObject[], memo = ''): Promise<StdFee | 'auto'> {
+    if (!this.feeGuard) return 'auto';
+    const gas = await this.signingClient!.simulate(senderAddress, messages, memo);
+    return this.feeGuard.cosmos(actions, gas);
+  }
+
   /**
    * Get HTLC details
    */
@@ -391,4 +624,19 @@ export class CosmosHTLCClient {
   getSigningClient(): SigningCosmWasmClient | undefined {
     return this.signingClient;
   }
-}
\ No newline at end of file
+}
+
+function executeResultOf(tx: IndexedTx): ExecuteResult {
+  return {
+    logs: [],
+    height: tx.height,
+    transactionHash: tx.hash,
+    events: tx.events,
+    gasWanted: tx.gasWanted,
+    gasUsed: tx.gasUsed
+  };
+}
+
+function feeActionOf(msg: unknown): FeeAction {
+  return msg && typeof msg === 'object' && 'claim' in msg ? 'claim' : 'refund';
+}
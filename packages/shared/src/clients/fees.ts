@@ -0,0 +1,137 @@
+import { ethers } from 'ethers';
+import { GasPrice, calculateFee } from '@cosmjs/stargate';
+import type { StdFee } from '@cosmjs/stargate';
+import type { ChainConfig, CosmosChainConfig } from '../chains';
+import { getCosmosGasPrice } from '../chains';
+
+export type FeeAction = 'fund' | 'claim' | 'refund' | 'approve';
+
+export interface FeePolicy {
+  /** Safety factor on estimated/simulated gas (default 1.2 on EVM, 1.4 on Cosmos) */
+  gasMultiplier?: number;
+  /** Safety factor on the current gas price when pricing the estimate (default 1.2) */
+  priceMultiplier?: number;
+  /** Most a single action may cost, in whole units of the fee token, e.g. { claim: '0.002' } */
+  budgets?: Partial<Record<FeeAction, string>>;
+}
+
+export class FeeBudgetExceededError extends Error {
+  constructor(
+    readonly chain: string,
+    readonly actions: FeeAction[],
+    readonly estimated: bigint,
+    readonly budget: bigint
+  ) {
+    super(`Estimated fee ${estimated} for ${actions.join('+')} on ${chain} exceeds budget ${budget}`);
+    this.name = 'FeeBudgetExceededError';
+  }
+}
+
+export interface EvmFeeEstimate {
+  gasLimit: bigint;
+  /** Worst-case cost in wei, including any L1 data fee */
+  cost: bigint;
+}
+
+/**
+ * Prices a transaction before it is sent: estimates gas on EVM or takes the
+ * simulated gas on Cosmos, pads it by the policy's multipliers, and refuses when
+ * the padded cost is over the action's budget. Estimation itself reverting means
+ * the transaction would too, so that error propagates instead of a doomed
+ * broadcast.
+ */
+export class FeeGuard {
+  private budgets: Partial<Record<FeeAction, bigint>> = {};
+
+  constructor(readonly chain: string, private config: ChainConfig, private policy: FeePolicy = {}) {
+    const decimals = config.type === 'cosmos' ? 6 : 18;
+    for (const [action, amount] of Object.entries(policy.budgets || {})) {
+      if (amount) this.budgets[action as FeeAction] = ethers.parseUnits(amount, decimals);
+    }
+  }
+
+  get gasMultiplier(): number {
+    return this.policy.gasMultiplier ?? (this.config.type === 'cosmos' ? 1.4 : 1.2);
+  }
+
+  /**
+   * Gas limit for an EVM transaction, with the estimate checked against the budget.
+   * `l1DataFee` is what the chain charges on top of execution gas, if anything.
+   */
+  async evm(
+    action: FeeAction,
+    provider: ethers.Provider,
+    tx: ethers.TransactionRequest,
+    l1DataFee: bigint = 0n
+  ): Promise<EvmFeeEstimate> {
+    const [estimate, feeData] = await Promise.all([provider.estimateGas(tx), provider.getFeeData()]);
+    const gasLimit = scale(estimate, this.gasMultiplier);
+    const price = feeData.maxFeePerGas ?? feeData.gasPrice ?? 0n;
+    const cost = gasLimit * scale(price, this.policy.priceMultiplier ?? 1.2) + l1DataFee;
+
+    this.check([action], cost);
+    return { gasLimit, cost };
+  }
+
+  /**
+   * Fee for a Cosmos transaction from its simulated gas. Pass one action per
+   * message when several are packed together; their budgets add up.
+   */
+  cosmos(actions: FeeAction[], simulatedGas: number): StdFee {
+    return this.cosmosForLimit(actions, Math.ceil(simulatedGas * this.gasMultiplier));
+  }
+
+  /** As `cosmos`, for a gas limit that already includes the safety margin */
+  cosmosForLimit(actions: FeeAction[], gasLimit: number): StdFee {
+    const gasPrice = GasPrice.fromString(getCosmosGasPrice(this.config as CosmosChainConfig));
+    const fee = calculateFee(gasLimit, gasPrice);
+    this.check(actions, BigInt(fee.amount[0]?.amount || '0'));
+    return fee;
+  }
+
+  private check(actions: FeeAction[], cost: bigint): void {
+    let budget = 0n;
+    for (const action of actions) {
+      const limit = this.budgets[action];
+      if (limit === undefined) return; // unbudgeted actions are never refused
+      budget += limit;
+    }
+    if (cost > budget) {
+      throw new FeeBudgetExceededError(this.chain, actions, cost, budget);
+    }
+  }
+}
+
+function scale(value: bigint, factor: number): bigint {
+  return value * BigInt(Math.round(factor * 1000)) / 1000n;
+}
+
+/**
+ * Fee policy for a chain from environment variables:
+ *   RESOLVER_GAS_MULTIPLIER=1.3            (padding on estimated gas, all chains)
+ *   RESOLVER_GAS_PRICE_MULTIPLIER=1.2
+ *   RESOLVER_FEE_BUDGETS=claim=0.002,refund=0.002,sepolia.fund=0.01
+ * A chain-qualified budget overrides the plain one for that chain.
+ */
+export function feePolicyFromEnv(chain: string, env: Record<string, string | undefined>): FeePolicy {
+  const budgets: Partial<Record<FeeAction, string>> = {};
+  const entries = (env.RESOLVER_FEE_BUDGETS || '')
+    .split(',')
+    .map(entry => entry.trim().split('='))
+    .filter(([key, value]) => key && value);
+
+  for (const qualified of [false, true]) {
+    for (const [key, value] of entries) {
+      const [scope, action] = key.includes('.') ? key.split('.') : [undefined, key];
+      if (qualified ? scope === chain : scope === undefined) {
+        budgets[action as FeeAction] = value;
+      }
+    }
+  }
+
+  return {
+    ...(env.RESOLVER_GAS_MULTIPLIER ? { gasMultiplier: parseFloat(env.RESOLVER_GAS_MULTIPLIER) } : {}),
+    ...(env.RESOLVER_GAS_PRICE_MULTIPLIER ? { priceMultiplier: parseFloat(env.RESOLVER_GAS_PRICE_MULTIPLIER) } : {}),
+    budgets
+  };
+}
@@ -1,3 +1,5 @@
 export * from './evm';
 export * from './cosmos';
+export * from './cosmos-batch';
+export * from './fees';
 export * from './mock';
\ No newline at end of file
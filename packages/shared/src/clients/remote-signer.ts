@@ -0,0 +1,369 @@
+import { timingSafeEqual } from 'crypto';
+import { promises as fs } from 'fs';
+import { ethers } from 'ethers';
+import { DirectSecp256k1HdWallet } from '@cosmjs/proto-signing';
+import type { AccountData, DirectSignResponse, OfflineDirectSigner } from '@cosmjs/proto-signing';
+import { toBase64 } from '@cosmjs/encoding';
+import type { SignDoc } from 'cosmjs-types/cosmos/tx/v1beta1/tx';
+import type { ChainConfig, CosmosChainConfig } from '../chains';
+import type { AdapterCredentials } from '../resolver/adapters/types';
+import { cosmosAddressFromPubkey } from '../sdk/address';
+
+/** Where a remote signer listens and how to authenticate to it */
+export interface RemoteSignerSettings {
+  /** host:port */
+  url: string;
+  /** PEM files for mutual TLS: the CA that issued the signer's certificate, and this client's certificate and key */
+  caFile?: string;
+  certFile?: string;
+  keyFile?: string;
+  /** Bearer token sent with every call */
+  token?: string;
+  /** Plain-text connection, for a signer on the same host; refused for any other host */
+  insecure?: boolean;
+}
+
+// The parts of @grpc/grpc-js used here. It is not a dependency of this package:
+// installations that use the remote signer add it themselves
+interface Grpc {
+  credentials: {
+    createSsl(ca?: Buffer, key?: Buffer, cert?: Buffer): unknown;
+    createInsecure(): unknown;
+  };
+  ServerCredentials: {
+    createSsl(ca: Buffer | null, pairs: Array<{ private_key: Buffer; cert_chain: Buffer }>, checkClientCertificate: boolean): unknown;
+    createInsecure(): unknown;
+  };
+  Metadata: new () => { add(key: string, value: string): void; get(key: string): unknown[] };
+  Server: new () => {
+    addService(service: unknown, handlers: Record<string, unknown>): void;
+    bindAsync(address: string, credentials: unknown, callback: (error: Error | null, port: number) => void): void;
+    tryShutdown(callback: () => void): void;
+  };
+  status: Record<string, number>;
+  loadPackageDefinition(definition: unknown): any;
+}
+
+interface ProtoLoader {
+  fromJSON(json: unknown, options: Record<string, unknown>): unknown;
+}
+
+type Call<T> = { request: T; metadata: { get(key: string): unknown[] } };
+type Callback = (error: { code: number; message: string } | null, response?: { signature?: Buffer; publicKey?: Buffer }) => void;
+
+// proto/omni/signer/v1/remote_signer.proto in protobufjs JSON form, so no .proto file has to be found at runtime
+const field = (type: string, id: number) => ({ type, id });
+const PROTOCOL = {
+  nested: {
+    omni: { nested: { signer: { nested: { v1: { nested: {
+      RemoteSigner: {
+        methods: {
+          GetPublicKey: { requestType: 'PublicKeyRequest', responseType: 'PublicKeyResponse' },
+          SignEvmTransaction: { requestType: 'EvmTransactionRequest', responseType: 'SignatureResponse' },
+          SignEvmMessage: { requestType: 'EvmMessageRequest', responseType: 'SignatureResponse' },
+          SignCosmosDirect: { requestType: 'CosmosDirectRequest', responseType: 'SignatureResponse' }
+        }
+      },
+      PublicKeyRequest: { fields: { chain_id: field('string', 1), key_id: field('string', 2) } },
+      PublicKeyResponse: { fields: { public_key: field('bytes', 1) } },
+      EvmTransactionRequest: { fields: { chain_id: field('string', 1), key_id: field('string', 2), unsigned_tx: field('bytes', 3) } },
+      EvmMessageRequest: { fields: { chain_id: field('string', 1), key_id: field('string', 2), message: field('bytes', 3) } },
+      CosmosDirectRequest: {
+        fields: {
+          chain_id: field('string', 1),
+          key_id: field('string', 2),
+          body_bytes: field('bytes', 3),
+          auth_info_bytes: field('bytes', 4),
+          account_number: field('uint64', 5)
+        }
+      },
+      SignatureResponse: { fields: { signature: field('bytes', 1) } }
+    } } } } } }
+  }
+};
+
+async function loadProtocol(): Promise<{ grpc: Grpc; service: any }> {
+  let grpc: Grpc;
+  let loader: ProtoLoader;
+  // Specifiers held in variables so the build does not require the packages to be installed
+  const grpcModule = '@grpc/grpc-js';
+  const loaderModule = '@grpc/proto-loader';
+  try {
+    grpc = await import(grpcModule) as Grpc;
+    loader = await import(loaderModule) as ProtoLoader;
+  } catch {
+    throw new Error('The remote signer needs @grpc/grpc-js and @grpc/proto-loader; install them with `pnpm add @grpc/grpc-js @grpc/proto-loader`');
+  }
+  const definition = loader.fromJSON(PROTOCOL, { longs: String, defaults: true });
+  return { grpc, service: grpc.loadPackageDefinition(definition).omni.signer.v1.RemoteSigner };
+}
+
+function isLoopback(address: string): boolean {
+  const host = address.replace(/:\d+$/, '').replace(/^\[|\]$/g, '');
+  return ['localhost', '127.0.0.1', '::1'].includes(host);
+}
+
+async function readPem(file?: string): Promise<Buffer | undefined> {
+  return file ? fs.readFile(file) : undefined;
+}
+
+/** A connection to a remote signer; every call names the key and the chain it signs for */
+export class RemoteSignerClient {
+  private constructor(private stub: any, private grpc: Grpc, private token?: string) {}
+
+  static async connect(settings: RemoteSignerSettings): Promise<RemoteSignerClient> {
+    const { grpc, service } = await loadProtocol();
+    let credentials: unknown;
+    if (settings.insecure) {
+      if (!isLoopback(settings.url)) throw new Error(`Refusing a plain-text connection to remote signer ${settings.url}; only a loopback address may skip TLS`);
+      credentials = grpc.credentials.createInsecure();
+    } else {
+      credentials = grpc.credentials.createSsl(await readPem(settings.caFile), await readPem(settings.keyFile), await readPem(settings.certFile));
+    }
+    return new RemoteSignerClient(new service(settings.url, credentials), grpc, settings.token);
+  }
+
+  private call<T>(method: string, request: Record<string, unknown>): Promise<T> {
+    const metadata = new this.grpc.Metadata();
+    if (this.token) metadata.add('authorization', `Bearer ${this.token}`);
+    return new Promise((resolve, reject) => {
+      this.stub[method](request, metadata, { deadline: Date.now() + 30000 }, (error: Error | null, response: T) => {
+        if (error) reject(new Error(`Remote signer ${method}: ${error.message}`));
+        else resolve(response);
+      });
+    });
+  }
+
+  async publicKey(chainId: string | number, keyId: string): Promise<Uint8Array> {
+    const response = await this.call<{ publicKey: Buffer }>('GetPublicKey', { chainId: String(chainId), keyId });
+    return new Uint8Array(response.publicKey);
+  }
+
+  async signEvmTransaction(chainId: number, keyId: string, unsignedTx: string): Promise<string> {
+    const response = await this.call<{ signature: Buffer }>('SignEvmTransaction', { chainId: String(chainId), keyId, unsignedTx: ethers.getBytes(unsignedTx) });
+    return ethers.hexlify(response.signature);
+  }
+
+  async signEvmMessage(chainId: number, keyId: string, message: Uint8Array): Promise<string> {
+    const response = await this.call<{ signature: Buffer }>('SignEvmMessage', { chainId: String(chainId), keyId, message });
+    return ethers.hexlify(response.signature);
+  }
+
+  async signCosmosDirect(chainId: string, keyId: string, signDoc: SignDoc): Promise<Uint8Array> {
+    const response = await this.call<{ signature: Buffer }>('SignCosmosDirect', {
+      chainId,
+      keyId,
+      bodyBytes: signDoc.bodyBytes,
+      authInfoBytes: signDoc.authInfoBytes,
+      accountNumber: signDoc.accountNumber.toString()
+    });
+    return new Uint8Array(response.signature);
+  }
+}
+
+/** An ethers signer whose key is on a remote signer */
+export class RemoteEvmSigner extends ethers.AbstractSigner {
+  constructor(private client: RemoteSignerClient, private keyId: string, private chainId: number, readonly address: string, provider: ethers.Provider | null = null) {
+    super(provider);
+  }
+
+  async getAddress(): Promise<string> {
+    return this.address;
+  }
+
+  connect(provider: ethers.Provider | null): RemoteEvmSigner {
+    return new RemoteEvmSigner(this.client, this.keyId, this.chainId, this.address, provider);
+  }
+
+  async signTransaction(request: ethers.TransactionRequest): Promise<string> {
+    const to = request.to ? await ethers.resolveAddress(request.to, this.provider) : request.to;
+    const from = request.from ? await ethers.resolveAddress(request.from, this.provider) : undefined;
+    if (from && from.toLowerCase() !== this.address.toLowerCase()) {
+      throw new Error(`Transaction is from ${from}, not the remote signer key ${this.address}`);
+    }
+
+    const fields = { ...request, to };
+    delete fields.from;
+    const tx = ethers.Transaction.from(fields as ethers.TransactionLike<string>);
+    tx.signature = ethers.Signature.from(await this.client.signEvmTransaction(this.chainId, this.keyId, tx.unsignedSerialized));
+    if (tx.from?.toLowerCase() !== this.address.toLowerCase()) {
+      throw new Error(`Remote signer returned a signature from ${tx.from}, not ${this.address}`);
+    }
+    return tx.serialized;
+  }
+
+  async signMessage(message: string | Uint8Array): Promise<string> {
+    const bytes = typeof message === 'string' ? ethers.toUtf8Bytes(message) : message;
+    return this.client.signEvmMessage(this.chainId, this.keyId, bytes);
+  }
+
+  async signTypedData(): Promise<string> {
+    throw new Error('The remote signer does not sign typed data');
+  }
+}
+
+/** A CosmJS direct signer whose key is on a remote signer */
+export class RemoteCosmosSigner implements OfflineDirectSigner {
+  constructor(private client: RemoteSignerClient, private keyId: string, private chainId: string, private account: AccountData) {}
+
+  async getAccounts(): Promise<readonly AccountData[]> {
+    return [this.account];
+  }
+
+  async signDirect(signerAddress: string, signDoc: SignDoc): Promise<DirectSignResponse> {
+    if (signerAddress !== this.account.address) throw new Error(`The remote signer key is ${this.account.address}, not ${signerAddress}`);
+    if (signDoc.chainId !== this.chainId) throw new Error(`Sign doc is for ${signDoc.chainId}, not ${this.chainId}`);
+    const signature = await this.client.signCosmosDirect(this.chainId, this.keyId, signDoc);
+    return {
+      signed: signDoc,
+      signature: {
+        pub_key: { type: 'tendermint/PubKeySecp256k1', value: toBase64(this.account.pubkey) },
+        signature: toBase64(signature)
+      }
+    };
+  }
+}
+
+/** Adapter credentials that sign with `keyId` on the remote signer, for a registry chain config */
+export async function remoteSignerCredentials(config: ChainConfig, client: RemoteSignerClient, keyId: string): Promise<AdapterCredentials> {
+  const publicKey = await client.publicKey(config.chainId, keyId);
+  if (config.type === 'evm') {
+    const address = ethers.computeAddress(ethers.hexlify(publicKey));
+    return { evmSigner: new RemoteEvmSigner(client, keyId, Number(config.chainId), address) };
+  }
+  const cosmos = config as CosmosChainConfig;
+  const account: AccountData = { address: cosmosAddressFromPubkey(publicKey, cosmos.addressPrefix), algo: 'secp256k1', pubkey: publicKey };
+  return { cosmosSigner: new RemoteCosmosSigner(client, keyId, cosmos.chainId, account) };
+}
+
+/** A key the signer holds: a private key for EVM chains, a mnemonic for Cosmos chains */
+export interface RemoteSignerKey {
+  type: 'evm' | 'cosmos';
+  secret: string;
+}
+
+export interface RemoteSignerServerOptions {
+  /** host:port to listen on */
+  listen: string;
+  keys: Record<string, RemoteSignerKey>;
+  /** Chain IDs the signer signs for; any when empty */
+  chainIds?: string[];
+  /** PEM files: the CA client certificates must chain to, and the server's certificate and key */
+  caFile?: string;
+  certFile?: string;
+  keyFile?: string;
+  token?: string;
+  insecure?: boolean;
+  /** Called for every request, signed or refused */
+  onRequest?: (entry: { method: string; keyId: string; chainId: string; error?: string }) => void;
+}
+
+class Refusal extends Error {
+  constructor(readonly code: number, message: string) {
+    super(message);
+  }
+}
+
+/**
+ * Serve the remote signer protocol for `keys`. Client certificates are
+ * required unless `insecure`, which is only allowed on a loopback address.
+ * Resolves with a function that stops the server.
+ */
+export async function startRemoteSignerServer(options: RemoteSignerServerOptions): Promise<() => Promise<void>> {
+  const { grpc, service } = await loadProtocol();
+  const { status } = grpc;
+
+  let credentials: unknown;
+  if (options.insecure) {
+    if (!isLoopback(options.listen)) throw new Error('A remote signer without TLS may only listen on a loopback address');
+    credentials = grpc.ServerCredentials.createInsecure();
+  } else {
+    if (!options.caFile || !options.certFile || !options.keyFile) throw new Error('A remote signer needs a CA, certificate and key for mutual TLS');
+    credentials = grpc.ServerCredentials.createSsl(
+      await fs.readFile(options.caFile),
+      [{ private_key: await fs.readFile(options.keyFile), cert_chain: await fs.readFile(options.certFile) }],
+      true
+    );
+  }
+
+  const authorize = (metadata: Call<unknown>['metadata']) => {
+    if (!options.token) return;
+    const presented = Buffer.from(String(metadata.get('authorization')[0] ?? ''));
+    const expected = Buffer.from(`Bearer ${options.token}`);
+    if (presented.length !== expected.length || !timingSafeEqual(presented, expected)) {
+      throw new Refusal(status.UNAUTHENTICATED, 'bad or missing token');
+    }
+  };
+
+  const keyFor = (request: { keyId: string; chainId: string }, type: RemoteSignerKey['type']) => {
+    const key = options.keys[request.keyId];
+    if (!key) throw new Refusal(status.NOT_FOUND, `no key ${request.keyId}`);
+    if (key.type !== type) throw new Refusal(status.FAILED_PRECONDITION, `key ${request.keyId} is a ${key.type} key`);
+    if (options.chainIds?.length && !options.chainIds.includes(request.chainId)) {
+      throw new Refusal(status.PERMISSION_DENIED, `not configured to sign for chain ${request.chainId}`);
+    }
+    return key;
+  };
+
+  const cosmosWallet = async (key: RemoteSignerKey) => {
+    const wallet = await DirectSecp256k1HdWallet.fromMnemonic(key.secret);
+    const [account] = await wallet.getAccounts();
+    return { wallet, account };
+  };
+
+  const handlers: Record<string, (request: any) => Promise<{ signature?: Buffer; publicKey?: Buffer }>> = {
+    GetPublicKey: async request => {
+      const key = options.keys[request.keyId];
+      if (!key) throw new Refusal(status.NOT_FOUND, `no key ${request.keyId}`);
+      const publicKey = key.type === 'evm'
+        ? new ethers.SigningKey(key.secret).compressedPublicKey
+        : ethers.hexlify((await cosmosWallet(key)).account.pubkey);
+      return { publicKey: Buffer.from(ethers.getBytes(publicKey)) };
+    },
+    SignEvmTransaction: async request => {
+      const key = keyFor(request, 'evm');
+      const tx = ethers.Transaction.from(ethers.hexlify(request.unsignedTx));
+      if (tx.chainId.toString() !== request.chainId) {
+        throw new Refusal(status.INVALID_ARGUMENT, `transaction is for chain ${tx.chainId}, request says ${request.chainId}`);
+      }
+      const signature = new ethers.SigningKey(key.secret).sign(tx.unsignedHash);
+      return { signature: Buffer.from(ethers.getBytes(signature.serialized)) };
+    },
+    SignEvmMessage: async request => {
+      const key = keyFor(request, 'evm');
+      const signature = await new ethers.Wallet(key.secret).signMessage(new Uint8Array(request.message));
+      return { signature: Buffer.from(ethers.getBytes(signature)) };
+    },
+    SignCosmosDirect: async request => {
+      const { wallet, account } = await cosmosWallet(keyFor(request, 'cosmos'));
+      const { signature } = await wallet.signDirect(account.address, {
+        bodyBytes: new Uint8Array(request.bodyBytes),
+        authInfoBytes: new Uint8Array(request.authInfoBytes),
+        chainId: request.chainId,
+        accountNumber: BigInt(request.accountNumber)
+      });
+      return { signature: Buffer.from(signature.signature, 'base64') };
+    }
+  };
+
+  const server = new grpc.Server();
+  server.addService(service.service, Object.fromEntries(Object.entries(handlers).map(([method, handle]) => [
+    method,
+    (call: Call<{ keyId: string; chainId: string }>, callback: Callback) => {
+      const entry = { method, keyId: call.request.keyId, chainId: call.request.chainId };
+      Promise.resolve()
+        .then(() => authorize(call.metadata))
+        .then(() => handle(call.request))
+        .then(response => {
+          options.onRequest?.(entry);
+          callback(null, response);
+        }, (error: Error) => {
+          options.onRequest?.({ ...entry, error: error.message });
+          callback({ code: error instanceof Refusal ? error.code : status.INTERNAL, message: error.message });
+        });
+    }
+  ])));
+
+  await new Promise<void>((resolve, reject) => server.bindAsync(options.listen, credentials, error => error ? reject(error) : resolve()));
+  return () => new Promise(resolve => server.tryShutdown(resolve));
+}
@@ -9,9 +9,14 @@ import {
   ResolverOperationType,
   OperationStatus,
   GaslessClaim,
-  SupportedToken
+  SupportedToken,
+  PriceFeed,
+  RelayerAction,
+  RelayerActionType,
+  RelayerActionStatus
 } from './index';
 import { v4 as uuidv4 } from 'uuid';
+import { sealSecret, PREIMAGE_CONTEXT, GASLESS_PREIMAGE_CONTEXT } from './secrets';
 
 export class FusionDAO {
   constructor(private db: FusionDatabase) {}
@@ -83,7 +88,7 @@ export class FusionDAO {
       swapData.expectedAmount,
       swapData.slippageTolerance,
       swapData.hashLock,
-      swapData.preimageHash,
+      swapData.preimageHash ? sealSecret(swapData.preimageHash, PREIMAGE_CONTEXT) : swapData.preimageHash,
       swapData.expirationTime,
       swapData.status || SwapStatus.PENDING
     ]);
@@ -99,7 +104,7 @@ export class FusionDAO {
     for (const [key, value] of Object.entries(updates)) {
       if (value !== undefined) {
         updateFields.push(`${this.camelToSnake(key)} = $${paramCount}`);
-        values.push(value);
+        values.push(key === 'preimageHash' ? sealSecret(value as string, PREIMAGE_CONTEXT) : value);
         paramCount++;
       }
     }
@@ -156,6 +161,30 @@ export class FusionDAO {
     return result.rows.map((row: any) => this.transformDbRowToSwapRequest(row));
   }
 
+  async getSwapByHtlcContract(contractId: string): Promise<SwapRequest | null> {
+    const query = `
+      SELECT * FROM swap_requests
+      WHERE LOWER(user_htlc_contract) = LOWER($1) OR LOWER(pool_htlc_contract) = LOWER($1)
+      ORDER BY created_at DESC
+      LIMIT 1
+    `;
+    const result = await this.db.query(query, [contractId]);
+    const row = result.rows[0];
+    return row ? this.transformDbRowToSwapRequest(row) : null;
+  }
+
+  async getUnsettledSwapsExpiringBefore(expirationTime: number, limit: number = 100): Promise<SwapRequest[]> {
+    const query = `
+      SELECT * FROM swap_requests
+      WHERE status IN ('PENDING', 'POOL_FULFILLED')
+      AND expiration_time <= $1
+      ORDER BY expiration_time ASC
+      LIMIT $2
+    `;
+    const result = await this.db.query(query, [expirationTime, limit]);
+    return result.rows.map((row: any) => this.transformDbRowToSwapRequest(row));
+  }
+
   // Pool Liquidity Methods
   async getPoolLiquidity(tokenAddress: string): Promise<PoolLiquidity | null> {
     const query = 'SELECT * FROM pool_liquidity WHERE token_address = $1';
@@ -288,6 +317,91 @@ export class FusionDAO {
     return result.rows;
   }
 
+  // Relayer Action Queue Methods
+  async createRelayerAction(actionData: Partial<RelayerAction>): Promise<RelayerAction> {
+    const query = `
+      INSERT INTO relayer_actions (
+        id, swap_request_id, chain, action, contract_id, status, payload
+      ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+      ON CONFLICT (chain, action, contract_id) WHERE status IN ('PENDING', 'SUBMITTED') DO NOTHING
+      RETURNING *
+    `;
+
+    const result = await this.db.query(query, [
+      uuidv4(),
+      actionData.swapRequestId,
+      actionData.chain,
+      actionData.action,
+      actionData.contractId,
+      actionData.status || RelayerActionStatus.PENDING,
+      actionData.payload ? JSON.stringify(actionData.payload) : null
+    ]);
+
+    if (result.rows[0]) {
+      return this.transformDbRowToRelayerAction(result.rows[0]);
+    }
+
+    // Already queued: hand back the open action instead of creating a duplicate
+    const existing = await this.db.query(
+      `SELECT * FROM relayer_actions
+       WHERE chain = $1 AND action = $2 AND contract_id = $3 AND status IN ('PENDING', 'SUBMITTED')`,
+      [actionData.chain, actionData.action, actionData.contractId]
+    );
+    return this.transformDbRowToRelayerAction(existing.rows[0]);
+  }
+
+  async updateRelayerAction(id: string, updates: Partial<RelayerAction>): Promise<RelayerAction | null> {
+    const updateFields = [];
+    const values = [];
+    let paramCount = 1;
+
+    for (const [key, value] of Object.entries(updates)) {
+      if (value !== undefined) {
+        updateFields.push(`${this.camelToSnake(key)} = $${paramCount}`);
+        values.push(key === 'payload' ? JSON.stringify(value) : value);
+        paramCount++;
+      }
+    }
+
+    if (updates.status === RelayerActionStatus.SUBMITTED) {
+      updateFields.push('attempts = attempts + 1');
+    }
+
+    const query = `
+      UPDATE relayer_actions
+      SET ${updateFields.join(', ')}
+      WHERE id = $${paramCount}
+      RETURNING *
+    `;
+
+    values.push(id);
+    const result = await this.db.query(query, values);
+    const row = result.rows[0];
+    return row ? this.transformDbRowToRelayerAction(row) : null;
+  }
+
+  async getOpenRelayerActions(chain: string): Promise<RelayerAction[]> {
+    const query = `
+      SELECT * FROM relayer_actions
+      WHERE chain = $1 AND status IN ('PENDING', 'SUBMITTED')
+      ORDER BY created_at ASC
+    `;
+    const result = await this.db.query(query, [chain]);
+    return result.rows.map((row: any) => this.transformDbRowToRelayerAction(row));
+  }
+
+  async getLatestRelayerAction(swapRequestId: string, action: RelayerActionType): Promise<RelayerAction | null> {
+    const query = `
+      SELECT * FROM relayer_actions
+      WHERE swap_request_id = $1 AND action = $2
+      ORDER BY created_at DESC
+      LIMIT 1
+    `;
+    const result = await this.db.query(query, [swapRequestId, action]);
+    const row = result.rows[0];
+    return row ? this.transformDbRowToRelayerAction(row) : null;
+  }
+
   // Gasless Claims Methods
   async createGaslessClaim(claimData: Partial<GaslessClaim>): Promise<GaslessClaim> {
     const id = uuidv4();
@@ -305,7 +419,7 @@ export class FusionDAO {
       claimData.claimerAddress,
       claimData.htlcContract,
       claimData.contractId,
-      claimData.preimage,
+      claimData.preimage ? sealSecret(claimData.preimage, GASLESS_PREIMAGE_CONTEXT) : claimData.preimage,
       claimData.signature,
       claimData.status || OperationStatus.PENDING
     ]);
@@ -321,7 +435,7 @@ export class FusionDAO {
     for (const [key, value] of Object.entries(updates)) {
       if (value !== undefined) {
         updateFields.push(`${this.camelToSnake(key)} = $${paramCount}`);
-        values.push(value);
+        values.push(key === 'preimage' ? sealSecret(value as string, GASLESS_PREIMAGE_CONTEXT) : value);
         paramCount++;
       }
     }
@@ -375,6 +489,29 @@ export class FusionDAO {
     return result.rows[0] || null;
   }
 
+  // Price Feed Methods
+  async getLatestPriceFeed(tokenAddress: string): Promise<PriceFeed | null> {
+    const query = `
+      SELECT * FROM price_feeds
+      WHERE token_address = $1
+      ORDER BY last_updated DESC
+      LIMIT 1
+    `;
+    const result = await this.db.query(query, [tokenAddress]);
+    const row = result.rows[0];
+    if (!row) return null;
+
+    return {
+      id: row.id,
+      tokenAddress: row.token_address,
+      priceUsd: parseFloat(row.price_usd),
+      confidence: parseFloat(row.confidence),
+      lastUpdated: row.last_updated,
+      source: row.source,
+      createdAt: row.created_at
+    };
+  }
+
   // System Health and Metrics
   async getSystemMetrics(): Promise<any> {
     const queries = await Promise.all([
@@ -431,6 +568,7 @@ export class FusionDAO {
       userHtlcContract: row.user_htlc_contract,
       poolHtlcContract: row.pool_htlc_contract,
       hashLock: row.hash_lock,
+      // Left sealed: opened only where it is used, so one unreadable row cannot break a listing
       preimageHash: row.preimage_hash,
       expirationTime: row.expiration_time,
       status: row.status,
@@ -440,6 +578,23 @@ export class FusionDAO {
       updatedAt: row.updated_at
     };
   }
+
+  private transformDbRowToRelayerAction(row: any): RelayerAction {
+    return {
+      id: row.id,
+      swapRequestId: row.swap_request_id,
+      chain: row.chain,
+      action: row.action,
+      contractId: row.contract_id,
+      status: row.status,
+      txHash: row.tx_hash,
+      attempts: row.attempts,
+      errorMessage: row.error_message,
+      payload: row.payload,
+      createdAt: row.created_at,
+      updatedAt: row.updated_at
+    };
+  }
 }
 
 export default FusionDAO;
\ No newline at end of file
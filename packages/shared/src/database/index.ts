@@ -1,5 +1,5 @@
 import { Pool, PoolClient } from 'pg';
-import { readFileSync } from 'fs';
+import { readFileSync, readdirSync } from 'fs';
 import { join } from 'path';
 
 export interface DatabaseConfig {
@@ -87,6 +87,24 @@ export class FusionDatabase {
     }
   }
 
+  /**
+   * Apply the idempotent migrations in ./migrations in file-name order, so a
+   * database created from an older schema.sql picks up later column and table changes
+   */
+  async migrate(): Promise<void> {
+    const migrationsDir = join(__dirname, 'migrations');
+    const files = readdirSync(migrationsDir).filter(file => file.endsWith('.sql')).sort();
+
+    for (const file of files) {
+      try {
+        await this.query(readFileSync(join(migrationsDir, file), 'utf8'));
+      } catch (error) {
+        console.error(`Error applying migration ${file}:`, error);
+        throw error;
+      }
+    }
+  }
+
   async healthCheck(): Promise<boolean> {
     try {
       await this.query('SELECT 1');
@@ -127,6 +145,7 @@ export interface SwapRequest {
   userHtlcContract?: string;
   poolHtlcContract?: string;
   hashLock: string;
+  /** As stored: sealed when a key is configured; open with openSecret(value, PREIMAGE_CONTEXT) where it is used */
   preimageHash: string;
   expirationTime: number;
   status: SwapStatus;
@@ -184,12 +203,41 @@ export enum OperationStatus {
   RETRYING = 'RETRYING'
 }
 
+export enum RelayerActionType {
+  FUND_POOL_HTLC = 'FUND_POOL_HTLC',
+  CLAIM = 'CLAIM',
+  REFUND = 'REFUND'
+}
+
+export enum RelayerActionStatus {
+  PENDING = 'PENDING',
+  SUBMITTED = 'SUBMITTED',
+  CONFIRMED = 'CONFIRMED',
+  FAILED = 'FAILED'
+}
+
+export interface RelayerAction {
+  id: string;
+  swapRequestId: string;
+  chain: string;
+  action: RelayerActionType;
+  contractId: string;
+  status: RelayerActionStatus;
+  txHash?: string;
+  attempts: number;
+  errorMessage?: string;
+  payload?: any;
+  createdAt: Date;
+  updatedAt: Date;
+}
+
 export interface GaslessClaim {
   id: string;
   swapRequestId: string;
   claimerAddress: string;
   htlcContract: string;
   contractId: string;
+  /** As stored: sealed when a key is configured; open with openSecret(value, GASLESS_PREIMAGE_CONTEXT) where it is used */
   preimage: string;
   signature: string;
   status: OperationStatus;
@@ -221,6 +269,16 @@ export interface SupportedToken {
   updatedAt: Date;
 }
 
+export interface PriceFeed {
+  id: string;
+  tokenAddress: string;
+  priceUsd: number;
+  confidence: number;
+  lastUpdated: Date;
+  source: string;
+  createdAt: Date;
+}
+
 // Export database configuration from environment
 export const getDatabaseConfig = (): DatabaseConfig => {
   // If DATABASE_URL is provided, parse it
@@ -251,5 +309,6 @@ export const getDatabaseConfig = (): DatabaseConfig => {
 
 // Export DAO
 export { FusionDAO } from './dao';
+export { sealSecret, openSecret, openSecretBuffer, zeroize, PREIMAGE_CONTEXT, GASLESS_PREIMAGE_CONTEXT } from './secrets';
 
 export default FusionDatabase;
\ No newline at end of file
@@ -0,0 +1,50 @@
+-- Brings a database created from an older schema.sql up to date.
+-- Idempotent: safe to run on every startup and on freshly created databases.
+
+-- Sealed preimages (enc:v1:...) no longer fit in VARCHAR(66)
+ALTER TABLE swap_requests ALTER COLUMN preimage_hash TYPE TEXT;
+ALTER TABLE gasless_claims ALTER COLUMN preimage TYPE TEXT;
+
+-- Durable relayer action queue
+DO $$ BEGIN
+    CREATE TYPE relayer_action_type AS ENUM (
+        'FUND_POOL_HTLC',
+        'CLAIM',
+        'REFUND'
+    );
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+DO $$ BEGIN
+    CREATE TYPE relayer_action_status AS ENUM (
+        'PENDING',
+        'SUBMITTED',
+        'CONFIRMED',
+        'FAILED'
+    );
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS relayer_actions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    swap_request_id UUID REFERENCES swap_requests(id) ON DELETE CASCADE,
+    chain VARCHAR(50) NOT NULL,
+    action relayer_action_type NOT NULL,
+    contract_id VARCHAR(128) NOT NULL,
+    status relayer_action_status DEFAULT 'PENDING',
+    tx_hash VARCHAR(128),
+    attempts INTEGER DEFAULT 0,
+    error_message TEXT,
+    payload JSONB,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_relayer_actions_swap ON relayer_actions(swap_request_id);
+CREATE INDEX IF NOT EXISTS idx_relayer_actions_open ON relayer_actions(chain, status) WHERE status IN ('PENDING', 'SUBMITTED');
+CREATE UNIQUE INDEX IF NOT EXISTS idx_relayer_actions_unique_open ON relayer_actions(chain, action, contract_id)
+    WHERE status IN ('PENDING', 'SUBMITTED');
+
+DROP TRIGGER IF EXISTS update_relayer_actions_updated_at ON relayer_actions;
+CREATE TRIGGER update_relayer_actions_updated_at BEFORE UPDATE ON relayer_actions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
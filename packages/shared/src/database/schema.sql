@@ -61,7 +61,7 @@ CREATE TABLE swap_requests (
     user_htlc_contract VARCHAR(66),
     pool_htlc_contract VARCHAR(66),
     hash_lock VARCHAR(66) NOT NULL,
-    preimage_hash VARCHAR(66) NOT NULL,
+    preimage_hash TEXT NOT NULL, -- Preimage, sealed as enc:v1:... when PREIMAGE_ENCRYPTION_KEY is set
     expiration_time BIGINT NOT NULL,
     status swap_status DEFAULT 'PENDING',
     pool_claimed_at TIMESTAMP,
@@ -133,6 +133,37 @@ CREATE TABLE resolver_operations (
     metadata JSONB -- Additional operation data
 );
 
+-- Relayer Action Types
+CREATE TYPE relayer_action_type AS ENUM (
+    'FUND_POOL_HTLC', -- Fund the pool side of a swap
+    'CLAIM',          -- Claim an HTLC with a known preimage
+    'REFUND'          -- Refund an expired HTLC we funded
+);
+
+-- Relayer Action Status
+CREATE TYPE relayer_action_status AS ENUM (
+    'PENDING',        -- Decided and persisted, not yet broadcast
+    'SUBMITTED',      -- Broadcast, waiting for confirmation
+    'CONFIRMED',      -- Confirmed on-chain
+    'FAILED'          -- Reverted or dropped; safe to decide again
+);
+
+-- Durable Relayer Action Queue (written before submission, reconciled at startup)
+CREATE TABLE relayer_actions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    swap_request_id UUID REFERENCES swap_requests(id) ON DELETE CASCADE,
+    chain VARCHAR(50) NOT NULL,
+    action relayer_action_type NOT NULL,
+    contract_id VARCHAR(128) NOT NULL, -- HTLC the action targets
+    status relayer_action_status DEFAULT 'PENDING',
+    tx_hash VARCHAR(128),
+    attempts INTEGER DEFAULT 0,
+    error_message TEXT,
+    payload JSONB, -- Parameters needed to rebuild the transaction
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
 -- Gas Relayer Claims Table
 CREATE TABLE gasless_claims (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
@@ -140,7 +171,7 @@ CREATE TABLE gasless_claims (
     claimer_address VARCHAR(42) NOT NULL,
     htlc_contract VARCHAR(42) NOT NULL,
     contract_id VARCHAR(66) NOT NULL,
-    preimage VARCHAR(66) NOT NULL,
+    preimage TEXT NOT NULL, -- Sealed like swap_requests.preimage_hash
     signature TEXT NOT NULL,
     status operation_status DEFAULT 'PENDING',
     tx_hash VARCHAR(66),
@@ -199,6 +230,11 @@ CREATE INDEX idx_gasless_claims_status ON gasless_claims(status);
 CREATE INDEX idx_user_auth_wallet ON user_auth(wallet_address);
 CREATE INDEX idx_user_auth_web3auth ON user_auth(web3auth_user_id);
 CREATE INDEX idx_token_price ON price_feeds(token_address, last_updated);
+CREATE INDEX idx_relayer_actions_swap ON relayer_actions(swap_request_id);
+CREATE INDEX idx_relayer_actions_open ON relayer_actions(chain, status) WHERE status IN ('PENDING', 'SUBMITTED');
+-- At most one open action per HTLC and action type, so a retried decision never submits twice
+CREATE UNIQUE INDEX idx_relayer_actions_unique_open ON relayer_actions(chain, action, contract_id)
+    WHERE status IN ('PENDING', 'SUBMITTED');
 
 -- Insert Default System Configuration
 INSERT INTO system_config (key, value, description) VALUES
@@ -241,4 +277,7 @@ CREATE TRIGGER update_supported_tokens_updated_at BEFORE UPDATE ON supported_tok
     FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
 
 CREATE TRIGGER update_system_config_updated_at BEFORE UPDATE ON system_config
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_relayer_actions_updated_at BEFORE UPDATE ON relayer_actions
     FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
\ No newline at end of file
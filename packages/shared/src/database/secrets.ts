@@ -0,0 +1,105 @@
+import crypto from 'crypto';
+import fs from 'fs';
+
+// Sealed values look like enc:v1:<base64(nonce || ciphertext || tag)>
+const SEALED_PREFIX = 'enc:v1:';
+const NONCE_BYTES = 12;
+const TAG_BYTES = 16;
+
+export const PREIMAGE_CONTEXT = 'swap_requests.preimage_hash';
+export const GASLESS_PREIMAGE_CONTEXT = 'gasless_claims.preimage';
+
+let cachedKey: Buffer | null | undefined;
+
+/**
+ * Load the 32-byte at-rest key, preferring a key file (mounted secret or keystore export)
+ * over the environment. Without a key, secrets are stored as plaintext.
+ */
+function loadKey(): Buffer | null {
+  if (cachedKey !== undefined) return cachedKey;
+
+  const keyFile = process.env.PREIMAGE_ENCRYPTION_KEY_FILE;
+  const raw = keyFile ? fs.readFileSync(keyFile, 'utf8').trim() : process.env.PREIMAGE_ENCRYPTION_KEY;
+  if (!raw) {
+    cachedKey = null;
+    return cachedKey;
+  }
+
+  const key = Buffer.from(raw.replace(/^0x/, ''), 'hex');
+  if (key.length !== 32) {
+    zeroize(key);
+    throw new Error('PREIMAGE_ENCRYPTION_KEY must be 32 bytes of hex');
+  }
+  cachedKey = key;
+  return cachedKey;
+}
+
+/**
+ * Overwrite buffers that held secret material. JS strings cannot be wiped,
+ * so secrets should be handled as Buffers wherever possible.
+ */
+export function zeroize(...buffers: (Buffer | Uint8Array | undefined)[]): void {
+  for (const buffer of buffers) {
+    buffer?.fill(0);
+  }
+}
+
+export function isSealed(value: string | null | undefined): boolean {
+  return !!value && value.startsWith(SEALED_PREFIX);
+}
+
+/**
+ * Encrypt a secret with ChaCha20-Poly1305. The context (column name) is bound as
+ * associated data so a sealed value cannot be moved to another column.
+ */
+export function sealSecret(value: string, context: string): string {
+  const key = loadKey();
+  if (!key || isSealed(value)) return value;
+
+  const nonce = crypto.randomBytes(NONCE_BYTES);
+  const plaintext = Buffer.from(value, 'utf8');
+  try {
+    const cipher = crypto.createCipheriv('chacha20-poly1305', key, nonce, { authTagLength: TAG_BYTES }) as crypto.CipherGCM;
+    cipher.setAAD(Buffer.from(context, 'utf8'), { plaintextLength: plaintext.length });
+    const ciphertext = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+    return SEALED_PREFIX + Buffer.concat([nonce, ciphertext, cipher.getAuthTag()]).toString('base64');
+  } finally {
+    zeroize(plaintext);
+  }
+}
+
+/**
+ * Decrypt into a Buffer the caller is expected to zeroize. Plaintext (legacy) values pass through.
+ */
+export function openSecretBuffer(value: string, context: string): Buffer {
+  if (!isSealed(value)) return Buffer.from(value, 'utf8');
+
+  const key = loadKey();
+  if (!key) {
+    throw new Error(`Cannot decrypt ${context}: no PREIMAGE_ENCRYPTION_KEY configured`);
+  }
+
+  const sealed = Buffer.from(value.slice(SEALED_PREFIX.length), 'base64');
+  const nonce = sealed.subarray(0, NONCE_BYTES);
+  const tag = sealed.subarray(sealed.length - TAG_BYTES);
+  const ciphertext = sealed.subarray(NONCE_BYTES, sealed.length - TAG_BYTES);
+
+  const decipher = crypto.createDecipheriv('chacha20-poly1305', key, nonce, { authTagLength: TAG_BYTES }) as crypto.DecipherGCM;
+  decipher.setAAD(Buffer.from(context, 'utf8'), { plaintextLength: ciphertext.length });
+  decipher.setAuthTag(tag);
+  return Buffer.concat([decipher.update(ciphertext), decipher.final()]);
+}
+
+export function openSecret(value: string, context: string): string;
+export function openSecret(value: string | null | undefined, context: string): string | undefined;
+export function openSecret(value: string | null | undefined, context: string): string | undefined {
+  if (!value) return value ?? undefined;
+  if (!isSealed(value)) return value;
+
+  const plaintext = openSecretBuffer(value, context);
+  try {
+    return plaintext.toString('utf8');
+  } finally {
+    zeroize(plaintext);
+  }
+}
@@ -1,9 +1,12 @@
 import { ethers } from 'ethers';
-import { FusionDatabase, FusionDAO, GaslessClaim, OperationStatus } from '../database';
+import { FusionDatabase, FusionDAO, GaslessClaim, OperationStatus, openSecret, GASLESS_PREIMAGE_CONTEXT } from '../database';
 import { EventEmitter } from 'events';
+import { FailoverJsonRpcProvider } from '../sdk/rpc';
 
 export interface RelayerConfig {
   rpcUrl: string;
+  /** Backup endpoints, tried when rpcUrl errors or times out */
+  fallbackRpcUrls?: string[];
   chainId: number;
   relayerPrivateKey: string;
   gasRelayerContractAddress: string;
@@ -41,7 +44,7 @@ export interface RelayerStats {
 
 export class FusionGasRelayer extends EventEmitter {
   private dao: FusionDAO;
-  private provider: ethers.JsonRpcProvider;
+  private provider: FailoverJsonRpcProvider;
   private relayerWallet: ethers.Wallet;
   private gasRelayerContract: ethers.Contract;
   private isProcessing = false;
@@ -56,7 +59,7 @@ export class FusionGasRelayer extends EventEmitter {
   ) {
     super();
     this.dao = new FusionDAO(database);
-    this.provider = new ethers.JsonRpcProvider(config.rpcUrl);
+    this.provider = new FailoverJsonRpcProvider([config.rpcUrl, ...(config.fallbackRpcUrls || [])], config.chainId);
     this.relayerWallet = new ethers.Wallet(config.relayerPrivateKey, this.provider);
     this.startTime = Date.now();
     
@@ -108,6 +111,8 @@ export class FusionGasRelayer extends EventEmitter {
       clearInterval(this.processingTimer);
     }
 
+    this.provider.pool.startHealthChecks();
+
     // Start processing loop
     this.processingTimer = setInterval(async () => {
       try {
@@ -133,6 +138,7 @@ export class FusionGasRelayer extends EventEmitter {
       clearInterval(this.processingTimer);
       this.processingTimer = undefined;
     }
+    this.provider.pool.stopHealthChecks();
 
     this.isProcessing = false;
     this.stats.isOperational = false;
@@ -200,6 +206,9 @@ export class FusionGasRelayer extends EventEmitter {
         status: OperationStatus.IN_PROGRESS 
       });
 
+      // Stored sealed; an unreadable preimage fails this claim only
+      claim = { ...claim, preimage: openSecret(claim.preimage, GASLESS_PREIMAGE_CONTEXT) };
+
       // Validate claim parameters
       await this.validateClaim(claim);
 
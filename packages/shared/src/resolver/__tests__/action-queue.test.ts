@@ -0,0 +1,117 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { FusionDAO, RelayerAction, RelayerActionStatus, RelayerActionType } from '../../database';
+import { DurableActionQueue } from '../action-queue';
+
+const OPEN = [RelayerActionStatus.PENDING, RelayerActionStatus.SUBMITTED];
+
+/**
+ * The relayer_actions table in memory, including its one-open-action-per-target
+ * index. Rows are handed out as copies, like the DAO's freshly mapped rows.
+ */
+class MemoryActions {
+  rows: RelayerAction[] = [];
+
+  async createRelayerAction(data: Partial<RelayerAction>): Promise<RelayerAction> {
+    const open = this.rows.find(row =>
+      row.chain === data.chain && row.action === data.action && row.contractId === data.contractId && OPEN.includes(row.status));
+    if (open) return { ...open };
+
+    const now = new Date();
+    const row: RelayerAction = {
+      id: `action-${this.rows.length + 1}`,
+      swapRequestId: data.swapRequestId!,
+      chain: data.chain!,
+      action: data.action!,
+      contractId: data.contractId!,
+      status: RelayerActionStatus.PENDING,
+      attempts: 0,
+      payload: data.payload,
+      createdAt: now,
+      updatedAt: now
+    };
+    this.rows.push(row);
+    return { ...row };
+  }
+
+  async updateRelayerAction(id: string, updates: Partial<RelayerAction>): Promise<RelayerAction | null> {
+    const row = this.rows.find(candidate => candidate.id === id);
+    if (!row) return null;
+    Object.assign(row, updates, { updatedAt: new Date() });
+    return { ...row };
+  }
+
+  async getOpenRelayerActions(chain: string): Promise<RelayerAction[]> {
+    return this.rows.filter(row => row.chain === chain && OPEN.includes(row.status)).map(row => ({ ...row }));
+  }
+
+  async getLatestRelayerAction(swapRequestId: string, action: RelayerActionType): Promise<RelayerAction | null> {
+    const latest = [...this.rows].reverse().find(row => row.swapRequestId === swapRequestId && row.action === action);
+    return latest ? { ...latest } : null;
+  }
+}
+
+const intent = { swapId: 'swap-1', action: RelayerActionType.REFUND, contractId: '0x' + 'aa'.repeat(32) };
+
+describe('DurableActionQueue', () => {
+  let table: MemoryActions;
+  let queue: DurableActionQueue;
+
+  beforeEach(() => {
+    table = new MemoryActions();
+    queue = new DurableActionQueue(table as unknown as FusionDAO, 'sepolia');
+  });
+
+  it('moves an action from PENDING through SUBMITTED to CONFIRMED', async () => {
+    const action = await queue.record(intent);
+    expect(action.status).toBe(RelayerActionStatus.PENDING);
+
+    await queue.submitted(action, '0xabc');
+    expect(table.rows[0]).toMatchObject({ status: RelayerActionStatus.SUBMITTED, txHash: '0xabc' });
+    expect(await queue.open()).toHaveLength(1);
+
+    await queue.confirmed(action);
+    expect(table.rows[0].status).toBe(RelayerActionStatus.CONFIRMED);
+    expect(await queue.open()).toHaveLength(0);
+  });
+
+  it('returns the open action instead of recording the same one twice', async () => {
+    const first = await queue.record(intent);
+    const second = await queue.record(intent);
+    expect(second.id).toBe(first.id);
+
+    await queue.failed(first, 'reverted');
+    const retry = await queue.record(intent);
+    expect(retry.id).not.toBe(first.id);
+    expect(await queue.latest(intent.swapId, intent.action)).toMatchObject({ id: retry.id });
+  });
+
+  it('confirms an open action the chain already reflects', async () => {
+    const action = await queue.record(intent);
+    expect(await queue.resolve(action, { applied: true })).toBe(RelayerActionStatus.CONFIRMED);
+    expect(table.rows[0].status).toBe(RelayerActionStatus.CONFIRMED);
+  });
+
+  it('leaves an in-flight action open', async () => {
+    const action = await queue.record(intent);
+    await queue.submitted(action, '0xabc');
+    expect(await queue.resolve(action, { applied: false, inFlight: true })).toBe(RelayerActionStatus.SUBMITTED);
+    expect(table.rows[0].status).toBe(RelayerActionStatus.SUBMITTED);
+  });
+
+  it('fails actions that reverted, were dropped or were never broadcast', async () => {
+    await queue.submitted(await queue.record({ ...intent, swapId: 'swap-1', contractId: '0x01' }), '0x1');
+    await queue.submitted(await queue.record({ ...intent, swapId: 'swap-2', contractId: '0x02' }), '0x2');
+    await queue.record({ ...intent, swapId: 'swap-3', contractId: '0x03' });
+    const [reverted, dropped, unsent] = await queue.open();
+
+    expect(await queue.resolve(reverted, { applied: false, reverted: true })).toBe(RelayerActionStatus.FAILED);
+    expect(await queue.resolve(dropped, { applied: false })).toBe(RelayerActionStatus.FAILED);
+    expect(await queue.resolve(unsent, { applied: false })).toBe(RelayerActionStatus.FAILED);
+
+    expect(table.rows.map(row => row.errorMessage)).toEqual([
+      'Transaction reverted',
+      'Transaction dropped from the mempool',
+      'Never broadcast'
+    ]);
+  });
+});
@@ -0,0 +1,120 @@
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { Alert, AlertCondition, AlertManager, AlertSink } from '../alerting';
+
+class RecordingSink implements AlertSink {
+  readonly name = 'recording';
+  alerts: Alert[] = [];
+
+  async send(alert: Alert): Promise<void> {
+    this.alerts.push(alert);
+  }
+}
+
+const alert = (dedupKey: string, condition = AlertCondition.REORG_DETECTED) => ({
+  condition,
+  severity: 'critical' as const,
+  chain: 'sepolia',
+  summary: 'test alert',
+  dedupKey,
+  details: {}
+});
+
+describe('AlertManager', () => {
+  let sink: RecordingSink;
+
+  beforeEach(() => {
+    sink = new RecordingSink();
+    vi.useFakeTimers();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('suppresses a repeated dedup key until the cooldown has passed', async () => {
+    const alerts = new AlertManager([sink], { cooldownMs: 60_000 });
+
+    await alerts.fire(alert('reorg:1'));
+    await alerts.fire(alert('reorg:1'));
+    expect(sink.alerts).toHaveLength(1);
+
+    vi.advanceTimersByTime(59_999);
+    await alerts.fire(alert('reorg:1'));
+    expect(sink.alerts).toHaveLength(1);
+
+    vi.advanceTimersByTime(1);
+    await alerts.fire(alert('reorg:1'));
+    expect(sink.alerts).toHaveLength(2);
+  });
+
+  it('delivers alerts with different dedup keys independently', async () => {
+    const alerts = new AlertManager([sink], { cooldownMs: 60_000 });
+
+    await alerts.fire(alert('reorg:1'));
+    await alerts.fire(alert('reorg:2'));
+    expect(sink.alerts.map(sent => sent.dedupKey)).toEqual(['reorg:1', 'reorg:2']);
+  });
+
+  it('escalates each timelock threshold even within the cooldown', async () => {
+    const alerts = new AlertManager([sink]);
+    const swap = { chain: 'sepolia', swapId: 'swap-1', status: 'PENDING', expirationTime: 2_000_000_000, now: 1_999_999_000 };
+
+    await alerts.timelockProgress({ ...swap, threshold: 0.5, severity: 'info' });
+    await alerts.timelockProgress({ ...swap, threshold: 0.5, severity: 'info' });
+    await alerts.timelockProgress({ ...swap, threshold: 0.8, severity: 'warning' });
+    expect(sink.alerts.map(sent => sent.severity)).toEqual(['info', 'warning']);
+  });
+
+  it('drops disabled conditions', async () => {
+    const alerts = new AlertManager([sink], { disabledConditions: [AlertCondition.REORG_DETECTED] });
+
+    await alerts.fire(alert('reorg:1'));
+    await alerts.fire(alert('balance:sepolia', AlertCondition.LOW_BALANCE));
+    expect(sink.alerts.map(sent => sent.condition)).toEqual([AlertCondition.LOW_BALANCE]);
+  });
+
+  it('alerts on consecutive failures only once the threshold is reached', async () => {
+    const alerts = new AlertManager([sink], { failureThreshold: 3 });
+    const error = new Error('nonce too low');
+
+    await alerts.recordFailure('sepolia', 'fund', error);
+    await alerts.recordFailure('sepolia', 'fund', error);
+    alerts.recordSuccess('sepolia', 'fund');
+    await alerts.recordFailure('sepolia', 'fund', error);
+    await alerts.recordFailure('sepolia', 'fund', error);
+    expect(sink.alerts).toHaveLength(0);
+
+    await alerts.recordFailure('sepolia', 'fund', error);
+    expect(sink.alerts).toHaveLength(1);
+    expect(sink.alerts[0].condition).toBe(AlertCondition.SUBMISSION_FAILURES);
+    expect(sink.alerts[0].details).toMatchObject({ key: 'fund', failures: 3 });
+  });
+
+  it('retries an alert that no sink accepted without waiting out the cooldown', async () => {
+    let down = true;
+    const flaky: AlertSink = {
+      name: 'flaky',
+      send: async alert => {
+        if (down) throw new Error('webhook down');
+        sink.alerts.push(alert);
+      }
+    };
+    const alerts = new AlertManager([flaky], { cooldownMs: 60_000 });
+
+    await alerts.fire(alert('reorg:1'));
+    expect(sink.alerts).toHaveLength(0);
+
+    down = false;
+    await alerts.fire(alert('reorg:1'));
+    await alerts.fire(alert('reorg:1'));
+    expect(sink.alerts).toHaveLength(1);
+  });
+
+  it('keeps delivering to the other sinks when one fails', async () => {
+    const failing: AlertSink = { name: 'failing', send: () => Promise.reject(new Error('webhook down')) };
+    const alerts = new AlertManager([failing, sink]);
+
+    await expect(alerts.fire(alert('reorg:1'))).resolves.toBeUndefined();
+    expect(sink.alerts).toHaveLength(1);
+  });
+});
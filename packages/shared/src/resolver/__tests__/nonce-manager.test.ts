@@ -0,0 +1,111 @@
+import { describe, it, expect, vi } from 'vitest';
+import { ethers } from 'ethers';
+import { EvmNonceManager } from '../nonce-manager';
+
+/** A signer whose pending nonce the test controls */
+function fakeSigner(initialNonce: number) {
+  const chain = { nonce: initialNonce };
+  const getNonce = vi.fn(async () => chain.nonce);
+  return { chain, getNonce, signer: { getNonce } as unknown as ethers.Signer };
+}
+
+/** A sent transaction that confirms when `confirm` is called */
+function fakeTx(nonce: number) {
+  let confirm!: () => void;
+  const receipt = new Promise<null>(resolve => { confirm = () => resolve(null); });
+  const tx = { hash: `0x${nonce.toString(16).padStart(64, '0')}`, nonce, wait: () => receipt } as unknown as ethers.TransactionResponse;
+  return { tx, confirm };
+}
+
+/** Send through the manager and record the nonce it handed out */
+async function send(manager: EvmNonceManager, fail?: Error) {
+  let used = -1;
+  let confirm = () => {};
+  await manager.send(async nonce => {
+    used = nonce;
+    if (fail) throw fail;
+    const sent = fakeTx(nonce);
+    confirm = sent.confirm;
+    return sent.tx;
+  }).catch(error => {
+    if (error !== fail) throw error;
+  });
+  return { nonce: used, confirm };
+}
+
+describe('EvmNonceManager', () => {
+  it('hands out consecutive nonces while transactions are in flight', async () => {
+    const { signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer);
+
+    const first = await send(manager);
+    const second = await send(manager);
+    expect([first.nonce, second.nonce]).toEqual([5, 6]);
+    expect(manager.inFlight).toBe(2);
+  });
+
+  it('reuses the nonce of a transaction that was never broadcast', async () => {
+    const { signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer);
+
+    await send(manager);
+    const failed = await send(manager, new Error('insufficient funds for gas'));
+    const retried = await send(manager);
+    const next = await send(manager);
+
+    expect(failed.nonce).toBe(6);
+    expect(retried.nonce).toBe(6);
+    expect(next.nonce).toBe(7);
+  });
+
+  it('resyncs from the chain after a nonce conflict', async () => {
+    const { chain, signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer);
+
+    await send(manager);
+    chain.nonce = 9; // something else sent from this key
+    await send(manager, new Error('nonce too low'));
+    const next = await send(manager);
+
+    expect(next.nonce).toBe(9);
+  });
+
+  it('picks up the chain nonce again once nothing of ours is pending', async () => {
+    const { chain, signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer);
+
+    const first = await send(manager);
+    first.confirm();
+    await vi.waitFor(() => expect(manager.inFlight).toBe(0));
+
+    chain.nonce = 8;
+    expect((await send(manager)).nonce).toBe(8);
+  });
+
+  it('resyncs when a transaction is not confirmed in time', async () => {
+    const { chain, getNonce, signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer, { confirmationTimeoutMs: 10 });
+
+    await send(manager); // never confirms
+    chain.nonce = 5; // dropped from the mempool
+    await vi.waitFor(() => expect(manager.inFlight).toBe(0));
+
+    expect(getNonce).toHaveBeenCalledTimes(2);
+    expect((await send(manager)).nonce).toBe(5);
+  });
+
+  it('holds further sends once the in-flight window is full', async () => {
+    const { signer } = fakeSigner(5);
+    const manager = new EvmNonceManager(signer, { maxInFlight: 1 });
+
+    const first = await send(manager);
+    let secondSent = false;
+    const second = send(manager).then(result => { secondSent = true; return result; });
+
+    await new Promise(resolve => setTimeout(resolve, 10));
+    expect(secondSent).toBe(false);
+
+    first.confirm();
+    expect((await second).nonce).toBe(6);
+  });
+});
@@ -0,0 +1,179 @@
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { FusionDAO, RelayerAction, RelayerActionStatus, SwapRequest, SwapStatus } from '../../database';
+import { SwapState } from '../../utils/htlc';
+import type { AlertManager } from '../alerting';
+import type { ChainAdapter, PendingTx } from '../adapters';
+import { TimelockEscalation, TimelockWatchdog } from '../timelock-watchdog';
+
+const T0 = 1_700_000_000;
+const WINDOW = 1000;
+const POOL = 'osmo1pool';
+
+function swapRequest(overrides: Partial<SwapRequest> = {}): SwapRequest {
+  return {
+    id: 'swap-1',
+    userAddress: '0x000000000000000000000000000000000000a11c',
+    sourceToken: 'sepolia:ETH',
+    sourceAmount: '1000',
+    targetToken: 'osmosisTestnet:OSMO',
+    expectedAmount: '1000',
+    slippageTolerance: 0.01,
+    hashLock: '0x' + '11'.repeat(32),
+    preimageHash: '',
+    expirationTime: T0 + WINDOW,
+    status: SwapStatus.PENDING,
+    createdAt: new Date(T0 * 1000),
+    updatedAt: new Date(T0 * 1000),
+    ...overrides
+  };
+}
+
+/** Just enough of FusionDAO for the watchdog and its action queue */
+function fakeDao(swaps: SwapRequest[]) {
+  const actions: RelayerAction[] = [];
+  const dao = {
+    actions,
+    getUnsettledSwapsExpiringBefore: vi.fn(async () => swaps),
+    updateSwapRequest: vi.fn(async () => null),
+    getLatestRelayerAction: vi.fn(async () => actions[actions.length - 1] ?? null),
+    createRelayerAction: vi.fn(async (data: Partial<RelayerAction>) => {
+      const action = { ...data, id: `action-${actions.length + 1}`, status: RelayerActionStatus.PENDING } as RelayerAction;
+      actions.push(action);
+      return action;
+    }),
+    updateRelayerAction: vi.fn(async (id: string, updates: Partial<RelayerAction>) => {
+      const action = actions.find(candidate => candidate.id === id)!;
+      return Object.assign(action, updates);
+    })
+  };
+  return dao;
+}
+
+/** Target-chain adapter holding one expired, unclaimed pool HTLC */
+function fakeAdapter(refund: () => Promise<PendingTx>) {
+  return {
+    chain: 'osmosisTestnet',
+    address: POOL,
+    getSwap: vi.fn(async () => ({ state: SwapState.OPEN, originator: POOL, timelock: T0 + WINDOW })),
+    currentTime: vi.fn(async () => T0 + WINDOW + 1),
+    submitRefund: vi.fn(refund)
+  } as unknown as ChainAdapter & { submitRefund: ReturnType<typeof vi.fn> };
+}
+
+function at(fraction: number): void {
+  vi.setSystemTime(Math.round((T0 + WINDOW * fraction) * 1000));
+}
+
+describe('TimelockWatchdog', () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  describe('escalation', () => {
+    it('escalates once per threshold with rising severity', async () => {
+      const alerts = { timelockProgress: vi.fn(async () => {}) };
+      const watchdog = new TimelockWatchdog(fakeDao([swapRequest()]) as unknown as FusionDAO, [], alerts as unknown as AlertManager);
+      const escalations: TimelockEscalation[] = [];
+      watchdog.on('escalation', escalation => escalations.push(escalation));
+
+      at(0.4);
+      await watchdog.tick();
+      expect(escalations).toHaveLength(0);
+
+      at(0.55);
+      await watchdog.tick();
+      at(0.6);
+      await watchdog.tick();
+      at(0.85);
+      await watchdog.tick();
+
+      expect(escalations.map(({ threshold, severity, remainingSeconds }) => ({ threshold, severity, remainingSeconds }))).toEqual([
+        { threshold: 0.5, severity: 'info', remainingSeconds: 450 },
+        { threshold: 0.8, severity: 'warning', remainingSeconds: 150 }
+      ]);
+      expect(alerts.timelockProgress).toHaveBeenCalledTimes(2);
+      expect(alerts.timelockProgress).toHaveBeenLastCalledWith(expect.objectContaining({ swapId: 'swap-1', chain: 'sepolia', threshold: 0.8, severity: 'warning' }));
+    });
+
+    it('jumps straight to the highest threshold crossed', async () => {
+      const watchdog = new TimelockWatchdog(fakeDao([swapRequest()]) as unknown as FusionDAO, []);
+      const escalations: TimelockEscalation[] = [];
+      watchdog.on('escalation', escalation => escalations.push(escalation));
+
+      at(0.97);
+      await watchdog.tick();
+      expect(escalations).toEqual([expect.objectContaining({ threshold: 0.95, severity: 'critical' })]);
+    });
+
+    it('grades custom thresholds from the top', async () => {
+      const watchdog = new TimelockWatchdog(fakeDao([swapRequest()]) as unknown as FusionDAO, [], undefined, { thresholds: [0.9, 0.5] });
+      const escalations: TimelockEscalation[] = [];
+      watchdog.on('escalation', escalation => escalations.push(escalation));
+
+      at(0.5);
+      await watchdog.tick();
+      at(0.9);
+      await watchdog.tick();
+      expect(escalations.map(({ threshold, severity }) => [threshold, severity])).toEqual([[0.5, 'warning'], [0.9, 'critical']]);
+    });
+  });
+
+  describe('protective refund', () => {
+    const fulfilled = () => swapRequest({ status: SwapStatus.POOL_FULFILLED, poolHtlcContract: '0x' + 'cc'.repeat(32) });
+
+    it('refunds an expired pool HTLC and marks the swap expired', async () => {
+      const dao = fakeDao([fulfilled()]);
+      const adapter = fakeAdapter(async () => ({ txHash: 'ABC', wait: async () => 100 }));
+      const watchdog = new TimelockWatchdog(dao as unknown as FusionDAO, [adapter]);
+      const refunded = vi.fn();
+      watchdog.on('refunded', refunded);
+
+      at(1.01);
+      await watchdog.tick();
+
+      expect(dao.actions[0]).toMatchObject({ status: RelayerActionStatus.CONFIRMED, txHash: 'ABC' });
+      expect(dao.updateSwapRequest).toHaveBeenCalledWith('swap-1', { status: SwapStatus.EXPIRED });
+      expect(refunded).toHaveBeenCalledOnce();
+    });
+
+    it('keeps a broadcast refund SUBMITTED when waiting for it fails', async () => {
+      const dao = fakeDao([fulfilled()]);
+      const adapter = fakeAdapter(async () => ({ txHash: 'ABC', wait: () => Promise.reject(new Error('RPC timeout')) }));
+      const watchdog = new TimelockWatchdog(dao as unknown as FusionDAO, [adapter]);
+
+      at(1.01);
+      await watchdog.tick();
+      expect(dao.actions[0]).toMatchObject({ status: RelayerActionStatus.SUBMITTED, txHash: 'ABC' });
+      expect(dao.updateSwapRequest).not.toHaveBeenCalled();
+
+      // The open action blocks a second refund on the next tick
+      await watchdog.tick();
+      expect(adapter.submitRefund).toHaveBeenCalledOnce();
+    });
+
+    it('marks a refund that could not be broadcast FAILED', async () => {
+      const dao = fakeDao([fulfilled()]);
+      const adapter = fakeAdapter(() => Promise.reject(new Error('insufficient fees')));
+      const watchdog = new TimelockWatchdog(dao as unknown as FusionDAO, [adapter]);
+
+      at(1.01);
+      await watchdog.tick();
+      expect(dao.actions[0]).toMatchObject({ status: RelayerActionStatus.FAILED, errorMessage: 'insufficient fees' });
+    });
+
+    it('only reports when execution is disabled', async () => {
+      const dao = fakeDao([fulfilled()]);
+      const adapter = fakeAdapter(async () => ({ txHash: 'ABC', wait: async () => 100 }));
+      const watchdog = new TimelockWatchdog(dao as unknown as FusionDAO, [adapter], undefined, { execute: false });
+
+      at(1.01);
+      await watchdog.tick();
+      expect(adapter.submitRefund).not.toHaveBeenCalled();
+      expect(dao.actions).toHaveLength(0);
+    });
+  });
+});
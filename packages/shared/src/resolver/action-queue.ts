@@ -0,0 +1,102 @@
+import { FusionDAO, RelayerAction, RelayerActionStatus, RelayerActionType } from '../database';
+import { createLogger } from '../utils/logger';
+
+const logger = createLogger('ActionQueue');
+
+export interface ActionIntent {
+  swapId: string;
+  action: RelayerActionType;
+  contractId: string;
+  payload?: Record<string, unknown>;
+}
+
+/**
+ * What the chain says about an action's target, as seen by the caller
+ */
+export interface ActionObservation {
+  /** The HTLC already reflects the action (funded, claimed or refunded) */
+  applied: boolean;
+  /** The recorded transaction is still in the mempool */
+  inFlight?: boolean;
+  /** The recorded transaction was mined and reverted */
+  reverted?: boolean;
+}
+
+/**
+ * Write-ahead log of settlement actions for one chain. Every action is persisted
+ * before it is broadcast and its transaction hash is recorded right after, so after
+ * a crash each open entry can be resolved against the chain instead of being lost
+ * or sent twice.
+ */
+export class DurableActionQueue {
+  constructor(private dao: FusionDAO, private chain: string) {}
+
+  /**
+   * Persist an action before submission. If the same action is already open it is
+   * returned unchanged, and the caller must reconcile it rather than resubmit.
+   */
+  async record(intent: ActionIntent): Promise<RelayerAction> {
+    return this.dao.createRelayerAction({
+      swapRequestId: intent.swapId,
+      chain: this.chain,
+      action: intent.action,
+      contractId: intent.contractId,
+      payload: intent.payload
+    });
+  }
+
+  async submitted(action: RelayerAction, txHash: string): Promise<void> {
+    await this.dao.updateRelayerAction(action.id, { status: RelayerActionStatus.SUBMITTED, txHash });
+  }
+
+  async confirmed(action: RelayerAction): Promise<void> {
+    await this.dao.updateRelayerAction(action.id, { status: RelayerActionStatus.CONFIRMED });
+  }
+
+  async failed(action: RelayerAction, error: string): Promise<void> {
+    await this.dao.updateRelayerAction(action.id, { status: RelayerActionStatus.FAILED, errorMessage: error });
+  }
+
+  open(): Promise<RelayerAction[]> {
+    return this.dao.getOpenRelayerActions(this.chain);
+  }
+
+  latest(swapId: string, action: RelayerActionType): Promise<RelayerAction | null> {
+    return this.dao.getLatestRelayerAction(swapId, action);
+  }
+
+  /**
+   * Settle an open action from an on-chain observation. Returns the resulting status;
+   * SUBMITTED means the transaction is still pending and nothing should be resent.
+   */
+  async resolve(action: RelayerAction, observation: ActionObservation): Promise<RelayerActionStatus> {
+    if (observation.applied) {
+      await this.confirmed(action);
+      logger.info('Open action already applied on-chain', this.describe(action));
+      return RelayerActionStatus.CONFIRMED;
+    }
+
+    if (observation.inFlight) {
+      logger.info('Open action still in flight', this.describe(action));
+      return RelayerActionStatus.SUBMITTED;
+    }
+
+    const reason = observation.reverted
+      ? 'Transaction reverted'
+      : action.txHash ? 'Transaction dropped from the mempool' : 'Never broadcast';
+    await this.failed(action, reason);
+    logger.warn(`Open action abandoned: ${reason}`, this.describe(action));
+    return RelayerActionStatus.FAILED;
+  }
+
+  private describe(action: RelayerAction): Record<string, unknown> {
+    return {
+      chain: this.chain,
+      correlationId: action.swapRequestId,
+      swapId: action.swapRequestId,
+      action: action.action,
+      contractId: action.contractId,
+      txHash: action.txHash
+    };
+  }
+}
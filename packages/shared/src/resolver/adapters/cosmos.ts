@@ -0,0 +1,213 @@
+import type { ChainConfig, CosmosChainConfig } from '../../chains';
+import { CosmosHTLCClient } from '../../clients/cosmos';
+import type { CosmosSigningAccount } from '../../clients/cosmos';
+import { FeeGuard } from '../../clients/fees';
+import type { CreateHTLCParams } from '../../utils/index';
+import type {
+  AdapterCredentials,
+  AdapterOptions,
+  ChainAdapter,
+  HTLCEvent,
+  PendingFund,
+  PendingTx,
+  SubscribeOptions,
+  SwapView,
+  TxStatus,
+  Unsubscribe
+} from './types';
+
+export interface CosmosAdapterOptions extends AdapterOptions {
+  /**
+   * Signing client already used elsewhere for the same account (batch submitter,
+   * event pipeline), so every sender shares one sequence manager. Takes precedence
+   * over credentials; the adapter leaves its connection open on close.
+   */
+  signingAccount?: CosmosSigningAccount;
+}
+
+/**
+ * ChainAdapter for CosmWasm chains, where every HTLC is its own contract instance
+ * and the contract address is the HTLC ID. CometBFT blocks are final once
+ * committed, so finality is just inclusion.
+ *
+ * The client's sequence manager broadcasts and then polls for inclusion, so
+ * submissions return already included and `wait` resolves immediately. Every submission is simulated first
+ * and priced against the fee policy.
+ */
+export class CosmosChainAdapter implements ChainAdapter {
+  private timers = new Set<NodeJS.Timeout>();
+
+  private constructor(
+    readonly chain: string,
+    readonly config: CosmosChainConfig,
+    private client: CosmosHTLCClient,
+    readonly address?: string,
+    private ownsClient = true
+  ) {}
+
+  static async connect(
+    chain: string,
+    config: CosmosChainConfig,
+    credentials: AdapterCredentials = {},
+    options: CosmosAdapterOptions = {}
+  ): Promise<CosmosChainAdapter> {
+    if (options.signingAccount) {
+      return new CosmosChainAdapter(chain, config, options.signingAccount.client, options.signingAccount.address, false);
+    }
+    if (credentials.cosmosSigner || credentials.mnemonic) {
+      const fees = new FeeGuard(chain, config, options.fees);
+      const { client, address } = credentials.cosmosSigner
+        ? await CosmosHTLCClient.fromSigner(config, credentials.cosmosSigner, fees)
+        : await CosmosHTLCClient.fromMnemonic(config, credentials.mnemonic!, fees);
+      return new CosmosChainAdapter(chain, config, client, address);
+    }
+    return new CosmosChainAdapter(chain, config, await CosmosHTLCClient.connect(config));
+  }
+
+  async subscribeEvents(handler: (event: HTLCEvent) => void, options: SubscribeOptions = {}): Promise<Unsubscribe> {
+    let cursor = (options.fromHeight ?? await this.client.getClient().getHeight() + 1) - 1;
+    let polling = false;
+
+    const poll = async () => {
+      if (polling) return;
+      polling = true;
+      try {
+        const head = await this.client.getClient().getHeight();
+        if (head > cursor) {
+          (await this.events(cursor + 1, head)).forEach(handler);
+          cursor = head;
+        }
+      } finally {
+        polling = false;
+      }
+    };
+
+    await poll();
+    const timer = setInterval(() => {
+      poll().catch(() => undefined);
+    }, options.pollIntervalMs ?? 6000);
+    this.timers.add(timer);
+
+    return () => {
+      clearInterval(timer);
+      this.timers.delete(timer);
+    };
+  }
+
+  async getSwap(contractId: string): Promise<SwapView | null> {
+    let details;
+    try {
+      details = await this.client.getDetails(contractId);
+    } catch {
+      return null; // not an HTLC instance
+    }
+
+    const [funded] = await this.client.getFundedEvents(contractId).catch(() => []);
+    return {
+      ...details,
+      token: details.token || this.config.nativeDenom,
+      ...(funded ? { fundedHeight: funded.blockNumber } : {})
+    };
+  }
+
+  async submitFund(params: CreateHTLCParams): Promise<PendingFund> {
+    const { contractAddress, result } = await this.client.instantiateHTLC(params, this.sender());
+    return { contractId: contractAddress, txHash: result.transactionHash, wait: async () => result.height };
+  }
+
+  async submitClaim(contractId: string, preimage: string): Promise<PendingTx> {
+    const result = await this.client.claim(contractId, this.sender(), preimage);
+    return { txHash: result.transactionHash, wait: async () => result.height };
+  }
+
+  async submitRefund(contractId: string): Promise<PendingTx> {
+    const result = await this.client.refund(contractId, this.sender());
+    return { txHash: result.transactionHash, wait: async () => result.height };
+  }
+
+  async getTxStatus(txHash: string): Promise<TxStatus> {
+    const tx = await this.client.getClient().getTx(txHash);
+    if (!tx) return 'unknown';
+    return tx.code === 0 ? 'confirmed' : 'reverted';
+  }
+
+  async isFinal(height: number): Promise<boolean> {
+    return height <= await this.client.getClient().getHeight();
+  }
+
+  async getBalance(token?: string): Promise<bigint> {
+    return BigInt(await this.client.getBalance(this.sender(), token || this.config.nativeDenom));
+  }
+
+  currentTime(): Promise<number> {
+    return this.client.getCurrentTime();
+  }
+
+  async close(): Promise<void> {
+    this.timers.forEach(timer => clearInterval(timer));
+    this.timers.clear();
+    if (this.ownsClient) {
+      this.client.getClient().disconnect();
+    }
+  }
+
+  private async events(fromHeight: number, toHeight: number): Promise<HTLCEvent[]> {
+    const range = `tx.height>=${fromHeight} AND tx.height<=${toHeight}`;
+    const events: HTLCEvent[] = [];
+
+    for (const method of ['fund', 'claim', 'refund'] as const) {
+      for (const tx of await this.client.searchTxs(`${range} AND wasm.method='${method}'`)) {
+        const attributes = wasmAttributes(tx);
+        const contractId = attributes._contract_address;
+        if (!contractId) continue;
+
+        const common = { chain: this.chain, contractId, height: tx.height, txHash: tx.hash };
+        if (method === 'fund') {
+          events.push({
+            kind: 'funded',
+            ...common,
+            originator: attributes.sender || '',
+            beneficiary: attributes.beneficiary || '',
+            token: attributes.token || this.config.nativeDenom,
+            value: attributes.amount || '0',
+            hashLock: attributes.hash_lock || '',
+            timelock: parseInt(attributes.timelock || '0')
+          });
+        } else if (method === 'claim') {
+          events.push({ kind: 'claimed', ...common, preimage: attributes.preimage || '' });
+        } else {
+          events.push({ kind: 'refunded', ...common });
+        }
+      }
+    }
+
+    return events.sort((a, b) => a.height - b.height);
+  }
+
+  private sender(): string {
+    if (!this.address) {
+      throw new Error(`No signing key configured for ${this.chain}`);
+    }
+    return this.address;
+  }
+}
+
+function wasmAttributes(tx: any): Record<string, string> {
+  const attributes: Record<string, string> = {};
+  for (const event of tx.events || []) {
+    if (event.type !== 'wasm') continue;
+    for (const attribute of event.attributes || []) {
+      attributes[attribute.key] = attribute.value;
+    }
+  }
+  return attributes;
+}
+
+export function createCosmosChainAdapter(
+  chain: string,
+  config: ChainConfig,
+  credentials: AdapterCredentials,
+  options?: CosmosAdapterOptions
+): Promise<ChainAdapter> {
+  return CosmosChainAdapter.connect(chain, config as CosmosChainConfig, credentials, options);
+}
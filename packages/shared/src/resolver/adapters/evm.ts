@@ -0,0 +1,297 @@
+import { ethers } from 'ethers';
+import type { ChainConfig, EvmChainConfig } from '../../chains';
+import { htlcAddressBook } from '../../chains';
+import { FeeGuard } from '../../clients/fees';
+import type { FeeAction } from '../../clients/fees';
+import type { CreateHTLCParams } from '../../utils/index';
+import { SwapState } from '../../utils/index';
+import { EvmNonceManager } from '../nonce-manager';
+import { estimateL1DataFee, isBlockFinal } from '../evm-chain';
+import type {
+  AdapterCredentials,
+  AdapterOptions,
+  ChainAdapter,
+  HTLCEvent,
+  PendingFund,
+  PendingTx,
+  SubscribeOptions,
+  SwapView,
+  TxStatus,
+  Unsubscribe
+} from './types';
+
+const HTLC_ABI = [
+  'function fund(bytes32 contractId, address token, address payable beneficiary, bytes32 hashLock, uint256 timelock, uint256 value) external',
+  'function fundETH(bytes32 contractId, address payable beneficiary, bytes32 hashLock, uint256 timelock) external payable',
+  'function claim(bytes32 contractId, bytes32 preimage) external',
+  'function refund(bytes32 contractId) external',
+  'function getDetails(bytes32 contractId) external view returns (tuple(address token, address beneficiary, address originator, bytes32 hashLock, uint256 timelock, uint256 value, uint8 state))',
+  'event HTLCCreated(bytes32 indexed contractId, address indexed originator, address indexed beneficiary, address token, uint256 value, bytes32 hashLock, uint256 timelock)',
+  'event HTLCClaimed(bytes32 indexed contractId, address indexed claimer, bytes32 preimage)',
+  'event HTLCRefunded(bytes32 indexed contractId, address indexed refunder)'
+];
+
+const ERC20_ABI = [
+  'function approve(address spender, uint256 amount) external returns (bool)',
+  'function allowance(address owner, address spender) view returns (uint256)',
+  'function balanceOf(address account) view returns (uint256)'
+];
+
+export interface EvmAdapterOptions extends AdapterOptions {
+  /** Blocks per eth_getLogs query; most RPCs cap the range */
+  logChunkSize?: number;
+  /** How far back getSwap looks for the funding event */
+  fundingLookbackBlocks?: number;
+  maxInFlight?: number;
+  /**
+   * Nonce manager already tracking the signing account (e.g. FusionResolver's pool
+   * wallet), so this adapter and the other sender draw from one nonce sequence
+   */
+  nonceManager?: EvmNonceManager;
+}
+
+/**
+ * ChainAdapter for chains running the SimpleHTLC contract. Transactions go through
+ * an EvmNonceManager, so several can be in flight from the same key, and each is
+ * gas-estimated and checked against the fee policy before it is signed.
+ */
+export class EvmChainAdapter implements ChainAdapter {
+  readonly address?: string;
+  private provider: ethers.JsonRpcProvider;
+  private wallet?: ethers.Signer;
+  private htlc: ethers.Contract;
+  private nonces?: EvmNonceManager;
+  private fees: FeeGuard;
+  private timers = new Set<NodeJS.Timeout>();
+
+  constructor(
+    readonly chain: string,
+    readonly config: EvmChainConfig,
+    credentials: AdapterCredentials = {},
+    private options: EvmAdapterOptions = {}
+  ) {
+    const htlcAddress = htlcAddressBook[config.chainId] || config.htlcAddress;
+    if (!htlcAddress) {
+      throw new Error(`No HTLC deployed on ${chain} (chain ${config.chainId})`);
+    }
+
+    this.provider = new ethers.JsonRpcProvider(config.rpcUrl);
+    const signer = credentials.evmSigner
+      ?? (credentials.privateKey ? new ethers.Wallet(credentials.privateKey) : undefined);
+    if (signer) {
+      this.wallet = signer.connect(this.provider);
+      this.nonces = options.nonceManager ?? new EvmNonceManager(this.wallet, { maxInFlight: options.maxInFlight });
+      this.address = signer.address;
+    }
+    this.htlc = new ethers.Contract(htlcAddress, HTLC_ABI, this.wallet || this.provider);
+    this.fees = new FeeGuard(chain, config, options.fees);
+  }
+
+  async subscribeEvents(handler: (event: HTLCEvent) => void, options: SubscribeOptions = {}): Promise<Unsubscribe> {
+    let cursor = (options.fromHeight ?? await this.provider.getBlockNumber() + 1) - 1;
+    let polling = false;
+
+    const poll = async () => {
+      if (polling) return;
+      polling = true;
+      try {
+        const head = await this.provider.getBlockNumber();
+        const chunk = this.options.logChunkSize ?? 2000;
+        while (cursor < head) {
+          const from = cursor + 1;
+          const to = Math.min(head, cursor + chunk);
+          (await this.events(from, to)).forEach(handler);
+          cursor = to;
+        }
+      } finally {
+        polling = false;
+      }
+    };
+
+    await poll();
+    const timer = setInterval(() => {
+      poll().catch(() => undefined);
+    }, options.pollIntervalMs ?? 4000);
+    this.timers.add(timer);
+
+    return () => {
+      clearInterval(timer);
+      this.timers.delete(timer);
+    };
+  }
+
+  async getSwap(contractId: string): Promise<SwapView | null> {
+    const details = await this.htlc.getDetails(contractId);
+    const state = Number(details.state) as SwapState;
+    if (state === SwapState.INVALID) return null;
+
+    const head = await this.provider.getBlockNumber();
+    const created = await this.htlc.queryFilter(
+      this.htlc.filters.HTLCCreated(contractId),
+      Math.max(0, head - (this.options.fundingLookbackBlocks ?? 5000)),
+      head
+    );
+
+    return {
+      contractId,
+      token: details.token,
+      beneficiary: details.beneficiary,
+      originator: details.originator,
+      hashLock: details.hashLock,
+      timelock: Number(details.timelock),
+      value: details.value.toString(),
+      state,
+      ...(created.length > 0 ? { fundedHeight: created[0].blockNumber } : {})
+    };
+  }
+
+  async submitFund(params: CreateHTLCParams): Promise<PendingFund> {
+    const token = params.token || ethers.ZeroAddress;
+    const native = token === ethers.ZeroAddress;
+    if (!native) {
+      await this.ensureAllowance(token, BigInt(params.value));
+    }
+
+    const gasLimit = await this.gasLimit('fund', native
+      ? await this.htlc.fundETH.populateTransaction(params.contractId, params.beneficiary, params.hashLock, params.timelock, { value: params.value })
+      : await this.htlc.fund.populateTransaction(params.contractId, token, params.beneficiary, params.hashLock, params.timelock, params.value));
+
+    const tx = await this.signer().send(nonce => native
+      ? this.htlc.fundETH(params.contractId, params.beneficiary, params.hashLock, params.timelock, { value: params.value, gasLimit, nonce })
+      : this.htlc.fund(params.contractId, token, params.beneficiary, params.hashLock, params.timelock, params.value, { gasLimit, nonce }));
+    return { contractId: params.contractId, ...this.pending(tx) };
+  }
+
+  async submitClaim(contractId: string, preimage: string): Promise<PendingTx> {
+    const gasLimit = await this.gasLimit('claim', await this.htlc.claim.populateTransaction(contractId, preimage));
+    return this.pending(await this.signer().send(nonce => this.htlc.claim(contractId, preimage, { gasLimit, nonce })));
+  }
+
+  async submitRefund(contractId: string): Promise<PendingTx> {
+    const gasLimit = await this.gasLimit('refund', await this.htlc.refund.populateTransaction(contractId));
+    return this.pending(await this.signer().send(nonce => this.htlc.refund(contractId, { gasLimit, nonce })));
+  }
+
+  async getTxStatus(txHash: string): Promise<TxStatus> {
+    const receipt = await this.provider.getTransactionReceipt(txHash);
+    if (receipt) return receipt.status === 1 ? 'confirmed' : 'reverted';
+    return await this.provider.getTransaction(txHash) ? 'pending' : 'unknown';
+  }
+
+  isFinal(height: number): Promise<boolean> {
+    return isBlockFinal(this.provider, this.config, height);
+  }
+
+  async getBalance(token?: string): Promise<bigint> {
+    if (!this.address) {
+      throw new Error(`No signing key configured for ${this.chain}`);
+    }
+    if (!token || token === ethers.ZeroAddress) {
+      return this.provider.getBalance(this.address);
+    }
+    return new ethers.Contract(token, ERC20_ABI, this.provider).balanceOf(this.address);
+  }
+
+  async currentTime(): Promise<number> {
+    const block = await this.provider.getBlock('latest');
+    return block ? block.timestamp : Math.floor(Date.now() / 1000);
+  }
+
+  async close(): Promise<void> {
+    this.timers.forEach(timer => clearInterval(timer));
+    this.timers.clear();
+    this.provider.destroy();
+  }
+
+  private async events(fromBlock: number, toBlock: number): Promise<HTLCEvent[]> {
+    const [created, claimed, refunded] = await Promise.all([
+      this.htlc.queryFilter(this.htlc.filters.HTLCCreated(), fromBlock, toBlock),
+      this.htlc.queryFilter(this.htlc.filters.HTLCClaimed(), fromBlock, toBlock),
+      this.htlc.queryFilter(this.htlc.filters.HTLCRefunded(), fromBlock, toBlock)
+    ]);
+
+    const events: HTLCEvent[] = [
+      ...(created as ethers.EventLog[]).map(log => ({
+        kind: 'funded' as const,
+        chain: this.chain,
+        contractId: log.args.contractId,
+        originator: log.args.originator,
+        beneficiary: log.args.beneficiary,
+        token: log.args.token,
+        value: log.args.value.toString(),
+        hashLock: log.args.hashLock,
+        timelock: Number(log.args.timelock),
+        height: log.blockNumber,
+        txHash: log.transactionHash
+      })),
+      ...(claimed as ethers.EventLog[]).map(log => ({
+        kind: 'claimed' as const,
+        chain: this.chain,
+        contractId: log.args.contractId,
+        preimage: log.args.preimage,
+        height: log.blockNumber,
+        txHash: log.transactionHash
+      })),
+      ...(refunded as ethers.EventLog[]).map(log => ({
+        kind: 'refunded' as const,
+        chain: this.chain,
+        contractId: log.args.contractId,
+        height: log.blockNumber,
+        txHash: log.transactionHash
+      }))
+    ];
+    return events.sort((a, b) => a.height - b.height);
+  }
+
+  private async ensureAllowance(token: string, amount: bigint): Promise<void> {
+    const erc20 = new ethers.Contract(token, ERC20_ABI, this.wallet);
+    const spender = await this.htlc.getAddress();
+    const allowance: bigint = await erc20.allowance(this.address, spender);
+    if (allowance >= amount) return;
+
+    const gasLimit = await this.gasLimit('approve', await erc20.approve.populateTransaction(spender, amount));
+    const tx = await this.signer().send(nonce => erc20.approve(spender, amount, { gasLimit, nonce }));
+    await tx.wait();
+  }
+
+  /**
+   * Padded gas limit for a transaction, refused when its estimated cost is over the
+   * action's budget. Arbitrum's estimate already includes the L1 component.
+   */
+  private async gasLimit(action: FeeAction, tx: ethers.ContractTransaction): Promise<bigint> {
+    const l1DataFee = this.config.gasModel === 'arbitrum'
+      ? 0n
+      : await estimateL1DataFee(this.provider, this.config, { to: tx.to, data: tx.data });
+    const estimate = await this.fees.evm(action, this.provider, { ...tx, from: this.address }, l1DataFee);
+    return estimate.gasLimit;
+  }
+
+  private signer(): EvmNonceManager {
+    if (!this.nonces) {
+      throw new Error(`No signing key configured for ${this.chain}`);
+    }
+    return this.nonces;
+  }
+
+  private pending(tx: ethers.ContractTransactionResponse): PendingTx {
+    return {
+      txHash: tx.hash,
+      wait: async () => {
+        const receipt = await tx.wait();
+        if (!receipt || receipt.status !== 1) {
+          throw new Error(`Transaction ${tx.hash} reverted`);
+        }
+        return receipt.blockNumber;
+      }
+    };
+  }
+}
+
+export async function createEvmChainAdapter(
+  chain: string,
+  config: ChainConfig,
+  credentials: AdapterCredentials,
+  options?: AdapterOptions
+): Promise<ChainAdapter> {
+  return new EvmChainAdapter(chain, config as EvmChainConfig, credentials, options);
+}
@@ -0,0 +1,70 @@
+import { allChains } from '../../chains';
+import { createEvmChainAdapter } from './evm';
+import { createCosmosChainAdapter } from './cosmos';
+import type { AdapterCredentials, AdapterOptions, ChainAdapter, ChainAdapterFactory } from './types';
+
+export { EvmChainAdapter } from './evm';
+export type { EvmAdapterOptions } from './evm';
+export { CosmosChainAdapter } from './cosmos';
+export type { CosmosAdapterOptions } from './cosmos';
+export type {
+  AdapterCredentials,
+  AdapterOptions,
+  ChainAdapter,
+  ChainAdapterFactory,
+  HTLCEvent,
+  PendingFund,
+  PendingTx,
+  SubscribeOptions,
+  SwapView,
+  TxStatus,
+  Unsubscribe
+} from './types';
+
+// Factories keyed by ChainConfig.type
+const factories = new Map<string, ChainAdapterFactory>([
+  ['evm', createEvmChainAdapter],
+  ['cosmos', createCosmosChainAdapter]
+]);
+
+/**
+ * Make a chain family available to createChainAdapter. New families register here
+ * instead of being special-cased in the settlement flows.
+ */
+export function registerChainAdapter(type: string, factory: ChainAdapterFactory): void {
+  factories.set(type, factory);
+}
+
+export async function createChainAdapter(
+  chain: string,
+  credentials: AdapterCredentials = {},
+  options: AdapterOptions = {}
+): Promise<ChainAdapter> {
+  const config = allChains[chain];
+  if (!config) {
+    throw new Error(`Unknown chain ${chain}`);
+  }
+
+  const factory = factories.get(config.type);
+  if (!factory) {
+    throw new Error(`No chain adapter registered for ${config.type} chains`);
+  }
+  return factory(chain, config, credentials, options);
+}
+
+/**
+ * Resolve once a block is final on the adapter's chain
+ */
+export async function waitUntilFinal(
+  adapter: ChainAdapter,
+  height: number,
+  options: { pollIntervalMs?: number; timeoutMs?: number } = {}
+): Promise<void> {
+  const deadline = Date.now() + (options.timeoutMs ?? 30 * 60 * 1000);
+  while (!await adapter.isFinal(height)) {
+    if (Date.now() > deadline) {
+      throw new Error(`Block ${height} on ${adapter.chain} not final in time`);
+    }
+    await new Promise(resolve => setTimeout(resolve, options.pollIntervalMs ?? 2000));
+  }
+}
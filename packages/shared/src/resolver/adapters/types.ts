@@ -0,0 +1,106 @@
+import type { ethers } from 'ethers';
+import type { OfflineSigner } from '@cosmjs/proto-signing';
+import type { ChainConfig } from '../../chains';
+import type { FeePolicy } from '../../clients/fees';
+import type { CreateHTLCParams, HTLCDetails } from '../../utils/index';
+
+/**
+ * HTLC lifecycle events, normalised across chains. Heights are block heights on the
+ * emitting chain; contract IDs are whatever the chain uses to address an HTLC
+ * (a bytes32 ID on EVM, a contract address on CosmWasm).
+ */
+export type HTLCEvent =
+  | {
+      kind: 'funded';
+      chain: string;
+      contractId: string;
+      originator: string;
+      beneficiary: string;
+      token: string;
+      value: string;
+      hashLock: string;
+      timelock: number;
+      height: number;
+      txHash: string;
+    }
+  | { kind: 'claimed'; chain: string; contractId: string; preimage: string; height: number; txHash: string }
+  | { kind: 'refunded'; chain: string; contractId: string; height: number; txHash: string };
+
+export interface SwapView extends HTLCDetails {
+  /** Height of the funding transaction, when it could be located */
+  fundedHeight?: number;
+}
+
+/**
+ * A broadcast transaction. `wait` resolves with the inclusion height and rejects
+ * if the transaction failed on-chain.
+ */
+export interface PendingTx {
+  txHash: string;
+  wait(): Promise<number>;
+}
+
+export interface PendingFund extends PendingTx {
+  /** ID of the new HTLC; on chains that allocate it (CosmWasm) this differs from the requested one */
+  contractId: string;
+}
+
+export type TxStatus = 'pending' | 'confirmed' | 'reverted' | 'unknown';
+
+export interface SubscribeOptions {
+  /** First height to deliver events from; defaults to the current head */
+  fromHeight?: number;
+  pollIntervalMs?: number;
+}
+
+export type Unsubscribe = () => void;
+
+/**
+ * Everything the settlement flows need from a chain. Flows only ever talk to this
+ * interface, so supporting a new chain family (Solana, NEAR, Bitcoin, ...) means
+ * writing an adapter and registering it, not touching the flows.
+ */
+export interface ChainAdapter {
+  /** Key in the chain registry, e.g. "baseSepolia" */
+  readonly chain: string;
+  readonly config: ChainConfig;
+  /** Account transactions are sent from; undefined for a read-only adapter */
+  readonly address?: string;
+
+  subscribeEvents(handler: (event: HTLCEvent) => void, options?: SubscribeOptions): Promise<Unsubscribe>;
+  /** Current on-chain view of an HTLC, or null if it does not exist */
+  getSwap(contractId: string): Promise<SwapView | null>;
+  submitFund(params: CreateHTLCParams): Promise<PendingFund>;
+  submitClaim(contractId: string, preimage: string): Promise<PendingTx>;
+  submitRefund(contractId: string): Promise<PendingTx>;
+  getTxStatus(txHash: string): Promise<TxStatus>;
+  /** Whether a block at this height can no longer be reverted under the chain's finality model */
+  isFinal(height: number): Promise<boolean>;
+  /** Balance of the adapter's account in base units; token omitted for the native asset */
+  getBalance(token?: string): Promise<bigint>;
+  /** Chain time (unix seconds), which is what timelocks are compared against */
+  currentTime(): Promise<number>;
+  close(): Promise<void>;
+}
+
+/** Signing material an adapter may need; each family picks what it understands */
+export interface AdapterCredentials {
+  privateKey?: string;
+  mnemonic?: string;
+  /** A signer holding its own key, such as a hardware wallet; used instead of `privateKey` */
+  evmSigner?: ethers.Signer & { readonly address: string };
+  /** Used instead of `mnemonic`; the first account signs */
+  cosmosSigner?: OfflineSigner;
+}
+
+export interface AdapterOptions {
+  /** Gas padding and per-action fee budgets applied before every submission */
+  fees?: FeePolicy;
+}
+
+export type ChainAdapterFactory = (
+  chain: string,
+  config: ChainConfig,
+  credentials: AdapterCredentials,
+  options?: AdapterOptions
+) => Promise<ChainAdapter>;
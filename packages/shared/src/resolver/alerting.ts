@@ -0,0 +1,296 @@
+import axios from 'axios';
+import { ethers } from 'ethers';
+import type { GasBalance } from './gas-monitor';
+import { createLogger } from '../utils/logger';
+
+const logger = createLogger('AlertManager');
+
+export type AlertSeverity = 'info' | 'warning' | 'critical';
+
+export enum AlertCondition {
+  TIMELOCK_APPROACHING = 'TIMELOCK_APPROACHING',
+  SUBMISSION_FAILURES = 'SUBMISSION_FAILURES',
+  LOW_BALANCE = 'LOW_BALANCE',
+  REORG_DETECTED = 'REORG_DETECTED'
+}
+
+export interface Alert {
+  condition: AlertCondition;
+  severity: AlertSeverity;
+  chain: string;
+  summary: string;
+  /** Alerts sharing a dedup key are suppressed during the cooldown window */
+  dedupKey: string;
+  details: Record<string, unknown>;
+  timestamp: Date;
+}
+
+export interface AlertSink {
+  readonly name: string;
+  send(alert: Alert): Promise<void>;
+}
+
+export interface AlertingConfig {
+  /** Consecutive submission failures before alerting */
+  failureThreshold: number;
+  /** Minimum time between two alerts with the same dedup key (ms) */
+  cooldownMs: number;
+  /** Conditions that should not produce alerts */
+  disabledConditions?: AlertCondition[];
+}
+
+const DEFAULT_ALERTING_CONFIG: AlertingConfig = {
+  failureThreshold: 3,
+  cooldownMs: 15 * 60 * 1000
+};
+
+/**
+ * Generic JSON webhook sink - posts the alert as-is
+ */
+export class WebhookSink implements AlertSink {
+  readonly name = 'webhook';
+
+  constructor(private url: string, private headers: Record<string, string> = {}) {}
+
+  async send(alert: Alert): Promise<void> {
+    await axios.post(this.url, alert, { headers: this.headers, timeout: 10000 });
+  }
+}
+
+/**
+ * Slack incoming-webhook sink
+ */
+export class SlackSink implements AlertSink {
+  readonly name = 'slack';
+
+  constructor(private webhookUrl: string) {}
+
+  async send(alert: Alert): Promise<void> {
+    const icon = alert.severity === 'critical' ? '🚨' : alert.severity === 'warning' ? '⚠️' : 'ℹ️';
+    const fields = Object.entries(alert.details)
+      .map(([key, value]) => `• *${key}*: \`${String(value)}\``)
+      .join('\n');
+
+    await axios.post(this.webhookUrl, {
+      text: `${icon} [${alert.chain}] ${alert.summary}`,
+      blocks: [
+        {
+          type: 'section',
+          text: { type: 'mrkdwn', text: `${icon} *${alert.condition}* on *${alert.chain}*\n${alert.summary}` }
+        },
+        ...(fields ? [{ type: 'section', text: { type: 'mrkdwn', text: fields } }] : [])
+      ]
+    }, { timeout: 10000 });
+  }
+}
+
+/**
+ * PagerDuty Events API v2 sink
+ */
+export class PagerDutySink implements AlertSink {
+  readonly name = 'pagerduty';
+  private static readonly EVENTS_URL = 'https://events.pagerduty.com/v2/enqueue';
+
+  constructor(private routingKey: string, private source: string = 'fusion-resolver') {}
+
+  async send(alert: Alert): Promise<void> {
+    await axios.post(PagerDutySink.EVENTS_URL, {
+      routing_key: this.routingKey,
+      event_action: 'trigger',
+      dedup_key: alert.dedupKey,
+      payload: {
+        summary: `[${alert.chain}] ${alert.summary}`,
+        severity: alert.severity === 'critical' ? 'critical' : alert.severity === 'warning' ? 'warning' : 'info',
+        source: this.source,
+        component: alert.chain,
+        class: alert.condition,
+        timestamp: alert.timestamp.toISOString(),
+        custom_details: alert.details
+      }
+    }, { timeout: 10000 });
+  }
+}
+
+/**
+ * Evaluates alert conditions and fans alerts out to the configured sinks
+ */
+export class AlertManager {
+  private config: AlertingConfig;
+  private lastSent: Map<string, number> = new Map();
+  private failureCounts: Map<string, number> = new Map();
+
+  constructor(private sinks: AlertSink[], config: Partial<AlertingConfig> = {}) {
+    this.config = { ...DEFAULT_ALERTING_CONFIG, ...config };
+  }
+
+  get enabled(): boolean {
+    return this.sinks.length > 0;
+  }
+
+  /**
+   * Escalating timelock warning: the swap has used up `threshold` of its window
+   * with the counterpart leg unsettled. Each threshold alerts once, so crossing a
+   * higher one is never swallowed by the cooldown of a lower one.
+   */
+  async timelockProgress(params: {
+    chain: string;
+    swapId: string;
+    status: string;
+    expirationTime: number; // unix seconds
+    threshold: number; // fraction of the window, e.g. 0.8
+    severity: AlertSeverity;
+    now?: number;
+  }): Promise<void> {
+    const now = params.now ?? Math.floor(Date.now() / 1000);
+    const remaining = params.expirationTime - now;
+    const percent = Math.round(params.threshold * 100);
+
+    await this.fire({
+      condition: AlertCondition.TIMELOCK_APPROACHING,
+      severity: params.severity,
+      chain: params.chain,
+      summary: `Swap ${params.swapId} is ${percent}% of the way to its timelock (${Math.max(remaining, 0)}s left) while still ${params.status}`,
+      dedupKey: `timelock:${params.chain}:${params.swapId}:${percent}`,
+      details: {
+        swapId: params.swapId,
+        status: params.status,
+        threshold: `${percent}%`,
+        expirationTime: new Date(params.expirationTime * 1000).toISOString(),
+        remainingSeconds: remaining
+      }
+    });
+  }
+
+  /**
+   * Track a failed submission; alerts once the consecutive failure threshold is reached
+   */
+  async recordFailure(chain: string, key: string, error: Error): Promise<void> {
+    const counterKey = `${chain}:${key}`;
+    const failures = (this.failureCounts.get(counterKey) || 0) + 1;
+    this.failureCounts.set(counterKey, failures);
+
+    if (failures < this.config.failureThreshold) {
+      return;
+    }
+
+    await this.fire({
+      condition: AlertCondition.SUBMISSION_FAILURES,
+      severity: 'critical',
+      chain,
+      summary: `${failures} consecutive ${key} failures: ${error.message}`,
+      dedupKey: `failures:${counterKey}`,
+      details: { key, failures, lastError: error.message }
+    });
+  }
+
+  recordSuccess(chain: string, key: string): void {
+    this.failureCounts.delete(`${chain}:${key}`);
+  }
+
+  async checkBalance(balance: GasBalance): Promise<void> {
+    if (!balance.isLow) {
+      return;
+    }
+
+    await this.fire({
+      condition: AlertCondition.LOW_BALANCE,
+      severity: balance.balance === 0n ? 'critical' : 'warning',
+      chain: balance.chain,
+      summary: `Gas balance ${balance.formatted} is below ${ethers.formatEther(balance.minRequired)}`,
+      dedupKey: `balance:${balance.chain}`,
+      details: {
+        balance: balance.balance.toString(),
+        minRequired: balance.minRequired.toString()
+      }
+    });
+  }
+
+  async reorgDetected(params: {
+    chain: string;
+    blockNumber: number;
+    expectedHash: string;
+    actualHash: string;
+  }): Promise<void> {
+    await this.fire({
+      condition: AlertCondition.REORG_DETECTED,
+      severity: 'critical',
+      chain: params.chain,
+      summary: `Chain reorganization detected at block ${params.blockNumber}`,
+      dedupKey: `reorg:${params.chain}:${params.blockNumber}`,
+      details: params
+    });
+  }
+
+  /**
+   * Deliver an alert to every sink, honouring the disabled list and cooldown
+   */
+  async fire(alert: Omit<Alert, 'timestamp'>): Promise<void> {
+    if (this.config.disabledConditions?.includes(alert.condition)) {
+      return;
+    }
+
+    const now = Date.now();
+    const lastSent = this.lastSent.get(alert.dedupKey);
+    if (lastSent && now - lastSent < this.config.cooldownMs) {
+      return;
+    }
+    this.lastSent.set(alert.dedupKey, now);
+
+    const fullAlert: Alert = { ...alert, timestamp: new Date(now) };
+    logger.warn(`🔔 ALERT: ${fullAlert.summary}`, {
+      chain: fullAlert.chain,
+      condition: fullAlert.condition,
+      severity: fullAlert.severity,
+      ...fullAlert.details
+    });
+
+    // A failing sink must never take the resolver down or block the other sinks
+    const results = await Promise.allSettled(this.sinks.map(sink => sink.send(fullAlert)));
+    results.forEach((result, index) => {
+      if (result.status === 'rejected') {
+        logger.error('Failed to deliver alert', {
+          sink: this.sinks[index].name,
+          error: result.reason?.message || String(result.reason)
+        });
+      }
+    });
+
+    // Nobody was told: let the next occurrence retry instead of sitting out the cooldown
+    if (results.length > 0 && results.every(result => result.status === 'rejected')) {
+      if (lastSent === undefined) {
+        this.lastSent.delete(alert.dedupKey);
+      } else {
+        this.lastSent.set(alert.dedupKey, lastSent);
+      }
+    }
+  }
+}
+
+/**
+ * Build an AlertManager from ALERT_* environment variables
+ */
+export function createAlertManagerFromEnv(env: NodeJS.ProcessEnv = process.env): AlertManager {
+  const sinks: AlertSink[] = [];
+
+  if (env.ALERT_WEBHOOK_URL) {
+    sinks.push(new WebhookSink(env.ALERT_WEBHOOK_URL));
+  }
+  if (env.ALERT_SLACK_WEBHOOK_URL) {
+    sinks.push(new SlackSink(env.ALERT_SLACK_WEBHOOK_URL));
+  }
+  if (env.ALERT_PAGERDUTY_ROUTING_KEY) {
+    sinks.push(new PagerDutySink(env.ALERT_PAGERDUTY_ROUTING_KEY));
+  }
+
+  const disabledConditions = (env.ALERT_DISABLED_CONDITIONS || '')
+    .split(',')
+    .map(condition => condition.trim())
+    .filter((condition): condition is AlertCondition =>
+      Object.values(AlertCondition).includes(condition as AlertCondition));
+
+  return new AlertManager(sinks, {
+    failureThreshold: parseInt(env.ALERT_FAILURE_THRESHOLD || '3'),
+    cooldownMs: parseInt(env.ALERT_COOLDOWN_MS || '900000'),
+    disabledConditions
+  });
+}
@@ -0,0 +1,549 @@
+import { EventEmitter } from 'events';
+import axios, { AxiosInstance } from 'axios';
+import WebSocket from 'isomorphic-ws';
+import { ethers } from 'ethers';
+import { z } from 'zod';
+import { FusionDAO, RelayerAction, RelayerActionStatus, RelayerActionType, SwapStatus } from '../database';
+import { getToken } from '../tokens';
+import { SwapState } from '../utils/index';
+import { createLogger } from '../utils/logger';
+import { zeroize } from '../database/secrets';
+import { DurableActionQueue, ActionIntent, ActionObservation } from './action-queue';
+import { ProfitabilityEngine, DatabasePriceSource, ProfitabilityConfig, PnLBreakdown } from './profitability';
+import { waitUntilFinal } from './adapters';
+import type { ChainAdapter, HTLCEvent, PendingTx } from './adapters';
+import type { SubscriptionSource } from './health';
+
+const logger = createLogger('AuctionParticipant');
+
+/**
+ * An order on the Dutch-auction feed. The maker has already locked `makingAmount`
+ * in an HTLC on the source chain with the winning resolver as beneficiary; the
+ * amount the maker asks for on the destination chain decays from
+ * `startTakingAmount` to `endTakingAmount` over the auction.
+ */
+export const AuctionOrderSchema = z.object({
+  orderHash: z.string(),
+  srcChain: z.string(),
+  dstChain: z.string(),
+  makerAsset: z.string(), // "chain:SYMBOL"
+  takerAsset: z.string(), // "chain:SYMBOL"
+  makingAmount: z.string(), // base units
+  startTakingAmount: z.string(), // base units
+  endTakingAmount: z.string(), // base units
+  maker: z.string(),
+  hashLock: z.string(),
+  srcEscrowId: z.string(),
+  srcTimelock: z.number(), // unix seconds
+  auctionStartTime: z.number(), // unix seconds
+  auctionDuration: z.number() // seconds
+});
+
+export type AuctionOrder = z.infer<typeof AuctionOrderSchema>;
+
+/**
+ * Amount the maker asks for at `nowSeconds`, decaying linearly over the auction
+ */
+export function currentTakingAmount(order: AuctionOrder, nowSeconds: number = Math.floor(Date.now() / 1000)): bigint {
+  const start = BigInt(order.startTakingAmount);
+  const end = BigInt(order.endTakingAmount);
+  const elapsed = Math.min(Math.max(nowSeconds - order.auctionStartTime, 0), order.auctionDuration);
+  if (order.auctionDuration <= 0) return end;
+  return start - (start - end) * BigInt(elapsed) / BigInt(order.auctionDuration);
+}
+
+export interface AuctionFeedOptions {
+  /** Relayer API base; active orders are polled from `${httpUrl}/orders/active` */
+  httpUrl?: string;
+  /** Streams orders as they are announced; polling is skipped when set */
+  wsUrl?: string;
+  apiKey?: string;
+  pollIntervalMs?: number;
+}
+
+/**
+ * Order source for the participant. Emits 'order' for every well-formed order
+ * received over HTTP polling or the websocket stream; malformed entries are dropped.
+ */
+export class AuctionOrderFeed extends EventEmitter implements SubscriptionSource {
+  readonly subscriptionName = 'auction-feed';
+  private http?: AxiosInstance;
+  private socket?: WebSocket;
+  private timer?: NodeJS.Timeout;
+  private heartbeatTimer?: NodeJS.Timeout;
+  private heartbeatAt?: number;
+  private stopped = true;
+
+  constructor(private options: AuctionFeedOptions) {
+    super();
+    if (!options.httpUrl && !options.wsUrl) {
+      throw new Error('Auction feed needs an httpUrl or wsUrl');
+    }
+    if (options.httpUrl) {
+      this.http = axios.create({
+        baseURL: options.httpUrl,
+        timeout: 10000,
+        headers: options.apiKey ? { Authorization: `Bearer ${options.apiKey}` } : {}
+      });
+    }
+  }
+
+  get lastHeartbeatAt(): number | undefined {
+    return this.heartbeatAt;
+  }
+
+  async start(): Promise<void> {
+    if (!this.stopped) return;
+    this.stopped = false;
+
+    if (this.options.wsUrl) {
+      this.connect();
+      // Orders can be quiet for long stretches; pongs prove the stream is still up
+      this.heartbeatTimer = setInterval(() => {
+        if (this.socket?.readyState === WebSocket.OPEN) {
+          this.socket.ping();
+        }
+      }, 15000);
+      return;
+    }
+
+    await this.poll();
+    this.timer = setInterval(() => {
+      this.poll().catch(() => undefined);
+    }, this.options.pollIntervalMs ?? 2000);
+  }
+
+  async stop(): Promise<void> {
+    this.stopped = true;
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+    if (this.heartbeatTimer) {
+      clearInterval(this.heartbeatTimer);
+      this.heartbeatTimer = undefined;
+    }
+    this.socket?.close();
+    this.socket = undefined;
+    this.heartbeatAt = undefined;
+  }
+
+  /**
+   * Publish a revealed secret so the relayer can share it with other participants
+   */
+  async reportSecret(orderHash: string, secret: string): Promise<void> {
+    if (!this.http) return;
+    await this.http.post(`/orders/${orderHash}/secret`, { secret });
+  }
+
+  private async poll(): Promise<void> {
+    try {
+      const response = await this.http!.get('/orders/active');
+      const orders = Array.isArray(response.data) ? response.data : response.data?.orders ?? [];
+      this.heartbeatAt = Date.now();
+      orders.forEach((order: unknown) => this.publish(order));
+    } catch (error) {
+      logger.warn('Failed to poll auction feed', { error: (error as Error).message });
+      this.emit('error', error);
+    }
+  }
+
+  private connect(): void {
+    const socket = new WebSocket(this.options.wsUrl!, {
+      headers: this.options.apiKey ? { Authorization: `Bearer ${this.options.apiKey}` } : {}
+    });
+    this.socket = socket;
+
+    socket.onopen = () => {
+      this.heartbeatAt = Date.now();
+    };
+    socket.on('pong', () => {
+      this.heartbeatAt = Date.now();
+    });
+    socket.onmessage = (event: any) => {
+      this.heartbeatAt = Date.now();
+      try {
+        const message = JSON.parse(event.data.toString());
+        const orders = Array.isArray(message) ? message : message.event === 'order' ? [message.data] : [];
+        orders.forEach((order: unknown) => this.publish(order));
+      } catch (error) {
+        logger.debug('Ignoring unparseable feed message', { error: (error as Error).message });
+      }
+    };
+    socket.onerror = (error: any) => {
+      this.emit('error', error);
+    };
+    socket.onclose = () => {
+      if (this.stopped) return;
+      logger.warn('Auction feed disconnected, reconnecting');
+      setTimeout(() => this.connect(), 5000);
+    };
+  }
+
+  private publish(raw: unknown): void {
+    const parsed = AuctionOrderSchema.safeParse(raw);
+    if (parsed.success) {
+      this.emit('order', parsed.data);
+    } else {
+      logger.debug('Dropping malformed auction order', { error: parsed.error.message });
+    }
+  }
+}
+
+
+export interface AuctionParticipantOptions {
+  profitability?: Partial<ProfitabilityConfig>;
+  /** How often an unprofitable order is re-priced as its auction decays (ms) */
+  repriceIntervalMs?: number;
+  /** Lifetime of the destination escrow we fund (seconds) */
+  dstTimelockSeconds?: number;
+  /** Time kept between the destination and source timelocks to claim the source leg (seconds) */
+  safetyMarginSeconds?: number;
+  maxConcurrentOrders?: number;
+  /** Evaluate and log decisions only */
+  dryRun?: boolean;
+}
+
+interface Leg {
+  adapter: ChainAdapter;
+  actions: DurableActionQueue;
+}
+
+/**
+ * Takes part in Dutch auctions as a resolver. Each order is priced with the
+ * profitability engine at the current auction rate and, once it clears the
+ * margin, settled end to end:
+ *
+ *   fund the destination escrow -> wait for the maker to claim it (revealing the
+ *   secret) -> relay the secret -> claim the source escrow with it
+ *
+ * Chains are reached only through ChainAdapters, so any pair of registered chain
+ * families can be settled. Every order is tracked as a swap row and every
+ * transaction goes through the durable action queue.
+ *
+ * Emits 'filled' when the destination escrow is funded, 'settled' when the source
+ * leg is claimed, 'expired' when the maker never claimed and we refunded, and 'error'.
+ */
+export class AuctionParticipant extends EventEmitter {
+  private legs = new Map<string, Leg>();
+  private profitability: ProfitabilityEngine;
+  private seen = new Set<string>();
+  private active = new Map<string, Promise<void>>();
+  private repriceTimers = new Map<string, NodeJS.Timeout>();
+  private feeds: AuctionOrderFeed[] = [];
+  private running = false;
+
+  constructor(private dao: FusionDAO, adapters: ChainAdapter[], private options: AuctionParticipantOptions = {}) {
+    super();
+    this.profitability = new ProfitabilityEngine(new DatabasePriceSource(dao), options.profitability);
+    for (const adapter of adapters) {
+      this.legs.set(adapter.chain, { adapter, actions: new DurableActionQueue(dao, adapter.chain) });
+    }
+  }
+
+  attach(feed: AuctionOrderFeed): void {
+    this.feeds.push(feed);
+    feed.on('order', (order: AuctionOrder) => this.consider(order, feed));
+  }
+
+  async start(): Promise<void> {
+    this.running = true;
+    await Promise.all(this.feeds.map(feed => feed.start()));
+    logger.info(`Auction participant started on ${[...this.legs.keys()].join(', ')}`);
+  }
+
+  /**
+   * Stop bidding and wait for orders already being settled to finish
+   */
+  async stop(): Promise<void> {
+    this.running = false;
+    await Promise.all(this.feeds.map(feed => feed.stop()));
+    this.repriceTimers.forEach(timer => clearTimeout(timer));
+    this.repriceTimers.clear();
+    await Promise.all([...this.active.values()]);
+  }
+
+  get activeOrders(): number {
+    return this.active.size;
+  }
+
+  private consider(order: AuctionOrder, feed: AuctionOrderFeed): void {
+    if (!this.running || this.seen.has(order.orderHash)) return;
+    if (!this.legs.has(order.srcChain) || !this.legs.has(order.dstChain)) return;
+
+    this.seen.add(order.orderHash);
+    this.bid(order, feed).catch(error => {
+      logger.error('Auction bid failed', { orderHash: order.orderHash, error: (error as Error).message });
+      this.emit('error', error);
+    });
+  }
+
+  /**
+   * Price the order at the current auction rate; retry as the rate decays until
+   * it clears the margin or the auction ends
+   */
+  private async bid(order: AuctionOrder, feed: AuctionOrderFeed): Promise<void> {
+    this.repriceTimers.delete(order.orderHash);
+    if (!this.running) return;
+
+    const now = Math.floor(Date.now() / 1000);
+    const auctionEnd = order.auctionStartTime + order.auctionDuration;
+    const dstTimelock = this.dstTimelock(order, now);
+    if (dstTimelock === null) {
+      logger.info('Order source timelock too close to settle safely', { orderHash: order.orderHash });
+      return;
+    }
+
+    const takingAmount = currentTakingAmount(order, now);
+    const pnl = await this.evaluate(order, takingAmount, dstTimelock - now);
+
+    if (!pnl.profitable || this.active.size >= (this.options.maxConcurrentOrders ?? 4)) {
+      if (now < auctionEnd) {
+        this.repriceTimers.set(order.orderHash, setTimeout(() => {
+          this.bid(order, feed).catch(error => this.emit('error', error));
+        }, this.options.repriceIntervalMs ?? 3000));
+      } else {
+        logger.info('Auction ended without a profitable fill', { orderHash: order.orderHash, reason: pnl.reason });
+      }
+      return;
+    }
+
+    logger.info('💰 Taking auction order', {
+      orderHash: order.orderHash,
+      takingAmount: takingAmount.toString(),
+      netPnlUsd: pnl.netPnlUsd,
+      marginBps: pnl.marginBps
+    });
+
+    if (this.options.dryRun) return;
+
+    const settlement = this.execute(order, takingAmount, dstTimelock, feed)
+      .catch(error => {
+        logger.error('Auction settlement failed', { orderHash: order.orderHash, error: (error as Error).message });
+        this.emit('error', error);
+      })
+      .finally(() => this.active.delete(order.orderHash));
+    this.active.set(order.orderHash, settlement);
+  }
+
+  private async evaluate(order: AuctionOrder, takingAmount: bigint, holdSeconds: number): Promise<PnLBreakdown> {
+    const source = this.asset(order.makerAsset);
+    const target = this.asset(order.takerAsset);
+
+    return this.profitability.evaluate({
+      sourceToken: order.makerAsset,
+      sourceAmount: order.makingAmount,
+      sourceDecimals: source.decimals,
+      targetToken: order.takerAsset,
+      targetAmount: takingAmount.toString(),
+      targetDecimals: target.decimals,
+      needsApproval: !!target.token && target.token !== ethers.ZeroAddress,
+      holdSeconds
+    });
+  }
+
+  /**
+   * Token as the chain's HTLC names it: undefined for a Cosmos native denom
+   */
+  private asset(assetId: string): { token?: string; decimals: number } {
+    const [chain, symbol] = assetId.split(':');
+    const token = getToken(chain, symbol);
+    if (!token) {
+      throw new Error(`Unknown auction asset ${assetId}`);
+    }
+    const native = this.legs.get(chain)?.adapter.config.nativeDenom;
+    return { token: native && token.address === native ? undefined : token.address, decimals: token.decimals };
+  }
+
+  /**
+   * Our escrow must expire well before the maker's, so a secret revealed at the
+   * last moment still leaves time to claim the source leg
+   */
+  private dstTimelock(order: AuctionOrder, now: number): number | null {
+    const margin = this.options.safetyMarginSeconds ?? 30 * 60;
+    const timelock = Math.min(now + (this.options.dstTimelockSeconds ?? 60 * 60), order.srcTimelock - margin);
+    return timelock > now + 5 * 60 ? timelock : null;
+  }
+
+  private async execute(order: AuctionOrder, takingAmount: bigint, dstTimelock: number, feed: AuctionOrderFeed): Promise<void> {
+    const src = this.legs.get(order.srcChain)!;
+    const dst = this.legs.get(order.dstChain)!;
+
+    await this.verifySourceEscrow(order, src.adapter);
+
+    const swap = await this.dao.createSwapRequest({
+      userAddress: order.maker,
+      sourceToken: order.makerAsset,
+      sourceAmount: order.makingAmount,
+      targetToken: order.takerAsset,
+      expectedAmount: takingAmount.toString(),
+      slippageTolerance: 0,
+      hashLock: order.hashLock,
+      preimageHash: '', // revealed by the maker's claim
+      expirationTime: dstTimelock,
+      status: SwapStatus.PENDING
+    });
+    const log = { correlationId: swap.id, swapId: swap.id, orderHash: order.orderHash };
+
+    // 1. Fund the destination escrow for the maker. Chains that allocate HTLC IDs
+    // (CosmWasm) return their own; elsewhere ours is derived from the order
+    const requestedId = ethers.keccak256(ethers.AbiCoder.defaultAbiCoder().encode(
+      ['string', 'bytes32', 'bytes32'],
+      [dst.adapter.address ?? '', order.hashLock, order.orderHash]
+    ));
+    let dstEscrowId = requestedId;
+    const fundHeight = await this.submit(dst, {
+      swapId: swap.id,
+      action: RelayerActionType.FUND_POOL_HTLC,
+      contractId: requestedId,
+      payload: { orderHash: order.orderHash, amount: takingAmount.toString(), timelock: dstTimelock }
+    }, async () => {
+      const pending = await dst.adapter.submitFund({
+        contractId: requestedId,
+        beneficiary: order.maker,
+        hashLock: order.hashLock,
+        timelock: dstTimelock,
+        value: takingAmount.toString(),
+        token: this.asset(order.takerAsset).token
+      });
+      dstEscrowId = pending.contractId;
+      return pending;
+    });
+
+    await this.dao.updateSwapRequest(swap.id, {
+      userHtlcContract: order.srcEscrowId,
+      poolHtlcContract: dstEscrowId,
+      status: SwapStatus.POOL_FULFILLED
+    });
+    logger.info('✅ Destination escrow funded', { ...log, dstEscrowId });
+    this.emit('filled', { order, swapId: swap.id, dstEscrowId });
+
+    // 2. Wait for the maker to claim it, which reveals the secret
+    const fromHeight = fundHeight ?? (await dst.adapter.getSwap(dstEscrowId))?.fundedHeight;
+    const preimage = await this.waitForMakerClaim(dst.adapter, dstEscrowId, dstTimelock, fromHeight);
+    if (!preimage) {
+      await this.submit(dst, { swapId: swap.id, action: RelayerActionType.REFUND, contractId: dstEscrowId },
+        () => dst.adapter.submitRefund(dstEscrowId));
+      await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.EXPIRED });
+      logger.warn('Maker never claimed; destination escrow refunded', log);
+      this.emit('expired', { order, swapId: swap.id });
+      return;
+    }
+
+    const secret = Buffer.from(preimage.replace(/^0x/, ''), 'hex');
+    try {
+      await this.dao.updateSwapRequest(swap.id, { preimageHash: preimage, userClaimedAt: new Date() });
+
+      // 3. Relay the secret; best effort, our own claim does not depend on it
+      feed.reportSecret(order.orderHash, preimage).catch(error => {
+        logger.warn('Failed to relay secret', { ...log, error: (error as Error).message });
+      });
+
+      // 4. Settle the source leg
+      await this.submit(src, { swapId: swap.id, action: RelayerActionType.CLAIM, contractId: order.srcEscrowId },
+        () => src.adapter.submitClaim(order.srcEscrowId, '0x' + secret.toString('hex')));
+      await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.USER_CLAIMED, poolClaimedAt: new Date() });
+      logger.info('🎉 Source leg settled', log);
+      this.emit('settled', { order, swapId: swap.id });
+    } finally {
+      zeroize(secret);
+    }
+  }
+
+  /**
+   * The maker's escrow must pay us the full making amount under the order's hash
+   * lock and be final before we commit inventory against it
+   */
+  private async verifySourceEscrow(order: AuctionOrder, src: ChainAdapter): Promise<void> {
+    const escrow = await src.getSwap(order.srcEscrowId);
+    const token = this.asset(order.makerAsset).token ?? src.config.nativeDenom ?? '';
+
+    if (!escrow || escrow.state !== SwapState.OPEN) throw new Error(`Source escrow ${order.srcEscrowId} is not open`);
+    if (escrow.beneficiary.toLowerCase() !== src.address?.toLowerCase()) throw new Error('Source escrow does not pay this resolver');
+    if (escrow.hashLock.toLowerCase() !== order.hashLock.toLowerCase()) throw new Error('Source escrow hash lock mismatch');
+    if (escrow.token.toLowerCase() !== token.toLowerCase()) throw new Error('Source escrow token mismatch');
+    if (BigInt(escrow.value) !== BigInt(order.makingAmount)) throw new Error('Source escrow amount mismatch');
+    if (escrow.timelock !== order.srcTimelock) throw new Error('Source escrow timelock mismatch');
+    if (escrow.fundedHeight === undefined) throw new Error('Source escrow funding transaction not found');
+
+    await waitUntilFinal(src, escrow.fundedHeight, { timeoutMs: 10 * 60 * 1000 });
+  }
+
+  /**
+   * Follow our escrow's events until the maker claims it, returning the revealed
+   * preimage, or null once it expires unclaimed
+   */
+  private async waitForMakerClaim(dst: ChainAdapter, contractId: string, timelock: number, fromHeight?: number): Promise<string | null> {
+    let settle!: (preimage: string | null) => void;
+    const settled = new Promise<string | null>(resolve => { settle = resolve; });
+
+    const unsubscribe = await dst.subscribeEvents((event: HTLCEvent) => {
+      if (event.contractId.toLowerCase() !== contractId.toLowerCase()) return;
+      if (event.kind === 'claimed') settle(event.preimage);
+      if (event.kind === 'refunded') settle(null);
+    }, { fromHeight });
+
+    const expiry = setInterval(() => {
+      dst.currentTime()
+        .then(now => { if (now >= timelock) settle(null); })
+        .catch(() => undefined);
+    }, 15000);
+
+    try {
+      return await settled;
+    } finally {
+      clearInterval(expiry);
+      unsubscribe();
+    }
+  }
+
+  /**
+   * Persist, broadcast and confirm one settlement transaction, then wait for it to
+   * be final. Returns the inclusion height, or null when the chain already reflects
+   * the action from an earlier attempt.
+   */
+  private async submit(leg: Leg, intent: ActionIntent, send: () => Promise<PendingTx>): Promise<number | null> {
+    let action = await leg.actions.record(intent);
+
+    if (action.txHash) {
+      const status = await leg.actions.resolve(action, await this.observe(leg.adapter, action));
+      if (status === RelayerActionStatus.CONFIRMED) return null;
+      if (status === RelayerActionStatus.SUBMITTED) {
+        throw new Error(`${intent.action} on ${intent.contractId} is still pending in ${action.txHash}`);
+      }
+      action = await leg.actions.record(intent);
+    }
+
+    let pending: PendingTx;
+    try {
+      pending = await send();
+    } catch (error) {
+      await leg.actions.failed(action, (error as Error).message);
+      throw error;
+    }
+
+    await leg.actions.submitted(action, pending.txHash);
+    let height: number;
+    try {
+      height = await pending.wait();
+    } catch (error) {
+      await leg.actions.failed(action, (error as Error).message);
+      throw error;
+    }
+    await leg.actions.confirmed(action);
+    await waitUntilFinal(leg.adapter, height);
+    return height;
+  }
+
+  private async observe(adapter: ChainAdapter, action: RelayerAction): Promise<ActionObservation> {
+    const swap = await adapter.getSwap(action.contractId);
+    const applied = action.action === RelayerActionType.FUND_POOL_HTLC ? !!swap
+      : action.action === RelayerActionType.CLAIM ? swap?.state === SwapState.CLAIMED
+      : swap?.state === SwapState.REFUNDED;
+
+    if (applied || !action.txHash) return { applied };
+
+    const status = await adapter.getTxStatus(action.txHash);
+    return { applied, reverted: status === 'reverted', inFlight: status === 'pending' };
+  }
+}
@@ -0,0 +1,502 @@
+import { ethers } from 'ethers';
+import { CosmWasmClient, SigningCosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import { DirectSecp256k1HdWallet, decodeTxRaw } from '@cosmjs/proto-signing';
+import { GasPrice } from '@cosmjs/stargate';
+import { FusionDAO, SwapRequest, SwapStatus } from '../database';
+import { CosmosHTLCClient } from '../clients/cosmos';
+import type { CosmosSigningAccount } from '../clients/cosmos';
+import { getCosmosGasPrice } from '../chains';
+import type { CosmosChainConfig, EvmChainConfig } from '../chains';
+import { createLogger } from '../utils/logger';
+
+const logger = createLogger('Backfill');
+
+export type BackfilledHTLCState = 'OPEN' | 'CLAIMED' | 'REFUNDED';
+
+export interface BackfilledHTLC {
+  chain: string;
+  contractId: string;
+  originator: string;
+  beneficiary: string;
+  token: string;
+  value: string;
+  hashLock: string;
+  timelock: number;
+  fundedAt: { height: number; txHash: string };
+  state: BackfilledHTLCState;
+  preimage?: string;
+  settledTxHash?: string;
+  settledHeight?: number;
+}
+
+export interface BackfillOptions {
+  fromHeight: number;
+  toHeight: number;
+  /** Blocks per log query (EVM RPCs cap eth_getLogs ranges) */
+  chunkSize?: number;
+  /** Apply DB repairs and broadcast refunds instead of only reporting them */
+  execute?: boolean;
+}
+
+export interface BackfillSettlement {
+  contractId: string;
+  swapId?: string;
+  action: 'refund';
+  txHash?: string;
+  error?: string;
+}
+
+export interface BackfillResult {
+  chain: string;
+  fromHeight: number;
+  toHeight: number;
+  htlcs: BackfilledHTLC[];
+  /** Swap ID -> status the chain history says it should have */
+  repairs: { swapId: string; from: SwapStatus; to: SwapStatus; applied: boolean }[];
+  settlements: BackfillSettlement[];
+}
+
+/** What a transaction cost and who paid it */
+export interface TxFee {
+  payer: string;
+  /** Base units */
+  amount: string;
+  /** Unset for an EVM chain's native asset */
+  denom?: string;
+  height: number;
+}
+
+const TERMINAL_STATUSES = [SwapStatus.USER_CLAIMED, SwapStatus.EXPIRED, SwapStatus.CANCELLED];
+
+/**
+ * Shared reconciliation: compare reconstructed HTLCs with swap_requests and
+ * work out which rows are stale and which expired HTLCs still need a refund.
+ * Without a DAO only the refunds are worked out.
+ */
+abstract class Backfiller {
+  constructor(protected chainName: string, protected dao: FusionDAO | null) {}
+
+  /** Reconstruct every HTLC funded or settled in the block range */
+  abstract scan(fromHeight: number, toHeight: number, chunkSize: number): Promise<BackfilledHTLC[]>;
+  abstract latestHeight(): Promise<number>;
+  /** Unix seconds of the block at `height` */
+  abstract blockTime(height: number): Promise<number>;
+  abstract txFee(txHash: string): Promise<TxFee>;
+  protected abstract ownAddress(): string | undefined;
+  protected abstract currentTime(): Promise<number>;
+  protected abstract refund(htlc: BackfilledHTLC): Promise<string>;
+
+  async run(options: BackfillOptions): Promise<BackfillResult> {
+    const { fromHeight, toHeight, chunkSize = 2000, execute = false } = options;
+    if (fromHeight > toHeight) {
+      throw new Error(`--from-height (${fromHeight}) must not exceed --to-height (${toHeight})`);
+    }
+
+    logger.info(`Replaying ${this.chainName} blocks ${fromHeight}..${toHeight}`, { chain: this.chainName, execute });
+    const htlcs = await this.scan(fromHeight, toHeight, chunkSize);
+    const result: BackfillResult = { chain: this.chainName, fromHeight, toHeight, htlcs, repairs: [], settlements: [] };
+
+    const now = await this.currentTime();
+    const ownAddress = this.ownAddress()?.toLowerCase();
+
+    for (const htlc of htlcs) {
+      const swap = this.dao ? await this.dao.getSwapByHtlcContract(htlc.contractId) : null;
+
+      if (swap) {
+        await this.repairSwap(swap, htlc, execute, result);
+      }
+
+      const refundable = htlc.state === 'OPEN' && htlc.timelock < now;
+      if (refundable && ownAddress && htlc.originator.toLowerCase() === ownAddress) {
+        await this.settleRefund(htlc, swap, execute, result);
+      }
+    }
+
+    logger.info(`Backfill of ${this.chainName} complete`, {
+      chain: this.chainName,
+      htlcs: htlcs.length,
+      repairs: result.repairs.length,
+      settlements: result.settlements.length
+    });
+    return result;
+  }
+
+  private async repairSwap(swap: SwapRequest, htlc: BackfilledHTLC, execute: boolean, result: BackfillResult): Promise<void> {
+    if (TERMINAL_STATUSES.includes(swap.status)) return;
+
+    const isUserHtlc = swap.userHtlcContract?.toLowerCase() === htlc.contractId.toLowerCase();
+    let target: SwapStatus | undefined;
+
+    if (htlc.state === 'CLAIMED' && isUserHtlc) {
+      // The pool already took the user's funds - the swap completed
+      target = SwapStatus.USER_CLAIMED;
+    } else if (htlc.state === 'REFUNDED') {
+      target = SwapStatus.EXPIRED;
+    } else if (htlc.state === 'OPEN' && !isUserHtlc && swap.status === SwapStatus.PENDING) {
+      // The pool funded its HTLC but the row never recorded it
+      target = SwapStatus.POOL_FULFILLED;
+    }
+
+    if (!target || target === swap.status) return;
+
+    if (execute) {
+      await this.dao!.updateSwapRequest(swap.id, {
+        status: target,
+        ...(target === SwapStatus.POOL_FULFILLED ? { poolHtlcContract: htlc.contractId } : {})
+      });
+    }
+    logger.info(`${execute ? 'Repaired' : 'Would repair'} swap status`, {
+      correlationId: swap.id,
+      swapId: swap.id,
+      from: swap.status,
+      to: target,
+      contractId: htlc.contractId
+    });
+    result.repairs.push({ swapId: swap.id, from: swap.status, to: target, applied: execute });
+  }
+
+  private async settleRefund(
+    htlc: BackfilledHTLC,
+    swap: SwapRequest | null,
+    execute: boolean,
+    result: BackfillResult
+  ): Promise<void> {
+    const settlement: BackfillSettlement = { contractId: htlc.contractId, swapId: swap?.id, action: 'refund' };
+
+    if (!execute) {
+      logger.info('Would refund expired HTLC', { contractId: htlc.contractId, swapId: swap?.id });
+      result.settlements.push(settlement);
+      return;
+    }
+
+    try {
+      settlement.txHash = await this.refund(htlc);
+      logger.info('Refunded expired HTLC', { contractId: htlc.contractId, swapId: swap?.id, txHash: settlement.txHash });
+
+      if (swap && !TERMINAL_STATUSES.includes(swap.status)) {
+        await this.dao!.updateSwapRequest(swap.id, { status: SwapStatus.EXPIRED });
+      }
+    } catch (error) {
+      settlement.error = (error as Error).message;
+      logger.error('Refund failed', { contractId: htlc.contractId, error: settlement.error });
+    }
+    result.settlements.push(settlement);
+  }
+}
+
+const SIMPLE_HTLC_ABI = [
+  'event HTLCCreated(bytes32 indexed contractId, address indexed originator, address indexed beneficiary, address token, uint256 value, bytes32 hashLock, uint256 timelock)',
+  'event HTLCClaimed(bytes32 indexed contractId, address indexed claimer, bytes32 preimage)',
+  'event HTLCRefunded(bytes32 indexed contractId, address indexed refunder)',
+  'function refund(bytes32 contractId) external',
+  'function getDetails(bytes32 contractId) external view returns (address token, address beneficiary, address originator, bytes32 hashLock, uint256 timelock, uint256 value, uint8 state)'
+];
+
+/**
+ * Rebuilds SimpleHTLC state from HTLCCreated / HTLCClaimed / HTLCRefunded logs
+ */
+export class EvmBackfiller extends Backfiller {
+  private provider: ethers.JsonRpcProvider;
+  private contract: ethers.Contract;
+  private wallet?: ethers.Wallet;
+
+  constructor(chainName: string, chain: EvmChainConfig, dao: FusionDAO | null, privateKey?: string) {
+    super(chainName, dao);
+    this.provider = new ethers.JsonRpcProvider(chain.rpcUrl);
+    this.wallet = privateKey ? new ethers.Wallet(privateKey, this.provider) : undefined;
+    this.contract = new ethers.Contract(chain.htlcAddress, SIMPLE_HTLC_ABI, this.wallet || this.provider);
+  }
+
+  protected ownAddress(): string | undefined {
+    return this.wallet?.address;
+  }
+
+  protected async currentTime(): Promise<number> {
+    const block = await this.provider.getBlock('latest');
+    return block ? block.timestamp : Math.floor(Date.now() / 1000);
+  }
+
+  async latestHeight(): Promise<number> {
+    return this.provider.getBlockNumber();
+  }
+
+  async blockTime(height: number): Promise<number> {
+    const block = await this.provider.getBlock(height);
+    if (!block) throw new Error(`Block ${height} not found on ${this.chainName}`);
+    return block.timestamp;
+  }
+
+  /** Execution fee plus, on OP Stack chains, the L1 data fee the receipt reports separately */
+  async txFee(txHash: string): Promise<TxFee> {
+    const receipt = await this.provider.send('eth_getTransactionReceipt', [txHash]) as {
+      from: string;
+      gasUsed: string;
+      effectiveGasPrice: string;
+      blockNumber: string;
+      l1Fee?: string;
+    } | null;
+    if (!receipt) throw new Error(`Transaction ${txHash} not found on ${this.chainName}`);
+    const fee = BigInt(receipt.gasUsed) * BigInt(receipt.effectiveGasPrice) + BigInt(receipt.l1Fee ?? 0);
+    return { payer: ethers.getAddress(receipt.from), amount: fee.toString(), height: Number(receipt.blockNumber) };
+  }
+
+  async scan(fromHeight: number, toHeight: number, chunkSize: number): Promise<BackfilledHTLC[]> {
+    const htlcs = new Map<string, BackfilledHTLC>();
+
+    for (let start = fromHeight; start <= toHeight; start += chunkSize) {
+      const end = Math.min(start + chunkSize - 1, toHeight);
+      const [created, claimed, refunded] = await Promise.all([
+        this.contract.queryFilter(this.contract.filters.HTLCCreated(), start, end),
+        this.contract.queryFilter(this.contract.filters.HTLCClaimed(), start, end),
+        this.contract.queryFilter(this.contract.filters.HTLCRefunded(), start, end)
+      ]);
+
+      for (const event of created as ethers.EventLog[]) {
+        const [contractId, originator, beneficiary, token, value, hashLock, timelock] = event.args;
+        htlcs.set(contractId, {
+          chain: this.chainName,
+          contractId,
+          originator,
+          beneficiary,
+          token,
+          value: value.toString(),
+          hashLock,
+          timelock: Number(timelock),
+          fundedAt: { height: event.blockNumber, txHash: event.transactionHash },
+          state: 'OPEN'
+        });
+      }
+
+      for (const event of claimed as ethers.EventLog[]) {
+        const htlc = await this.resolve(htlcs, event.args.contractId, event.blockNumber);
+        if (htlc) {
+          htlc.state = 'CLAIMED';
+          htlc.preimage = event.args.preimage;
+          htlc.settledTxHash = event.transactionHash;
+          htlc.settledHeight = event.blockNumber;
+        }
+      }
+
+      for (const event of refunded as ethers.EventLog[]) {
+        const htlc = await this.resolve(htlcs, event.args.contractId, event.blockNumber);
+        if (htlc) {
+          htlc.state = 'REFUNDED';
+          htlc.settledTxHash = event.transactionHash;
+          htlc.settledHeight = event.blockNumber;
+        }
+      }
+
+      logger.debug(`Scanned ${this.chainName} blocks ${start}..${end}`, { created: created.length, claimed: claimed.length, refunded: refunded.length });
+    }
+
+    return Array.from(htlcs.values());
+  }
+
+  /**
+   * Settlement events can reference HTLCs funded before the scanned range;
+   * fall back to contract storage for those
+   */
+  private async resolve(htlcs: Map<string, BackfilledHTLC>, contractId: string, height: number): Promise<BackfilledHTLC | undefined> {
+    const known = htlcs.get(contractId);
+    if (known) return known;
+
+    const details = await this.contract.getDetails(contractId);
+    if (details.originator === ethers.ZeroAddress) return undefined;
+
+    const htlc: BackfilledHTLC = {
+      chain: this.chainName,
+      contractId,
+      originator: details.originator,
+      beneficiary: details.beneficiary,
+      token: details.token,
+      value: details.value.toString(),
+      hashLock: details.hashLock,
+      timelock: Number(details.timelock),
+      fundedAt: { height, txHash: '' },
+      state: 'OPEN'
+    };
+    htlcs.set(contractId, htlc);
+    return htlc;
+  }
+
+  protected async refund(htlc: BackfilledHTLC): Promise<string> {
+    if (!this.wallet) {
+      throw new Error('A pool wallet is required to execute refunds');
+    }
+
+    // Re-check live state: the HTLC may have settled after the scanned range
+    const details = await this.contract.getDetails(htlc.contractId);
+    if (Number(details.state) !== 1) {
+      throw new Error(`HTLC is no longer open (state=${details.state})`);
+    }
+
+    const tx = await this.contract.refund(htlc.contractId);
+    const receipt = await tx.wait();
+    return receipt.hash;
+  }
+}
+
+/**
+ * Rebuilds CosmWasm HTLC state (one contract instance per swap) from indexed wasm events
+ */
+export class CosmosBackfiller extends Backfiller {
+  private client?: CosmosHTLCClient;
+  private senderAddress?: string;
+
+  /**
+   * `signer` is a mnemonic to derive the refund account from, or a signing account
+   * shared with other senders so they use one sequence manager
+   */
+  constructor(
+    chainName: string,
+    private chain: CosmosChainConfig,
+    dao: FusionDAO | null,
+    private signer?: string | CosmosSigningAccount
+  ) {
+    super(chainName, dao);
+  }
+
+  private async connect(): Promise<CosmosHTLCClient> {
+    if (this.client) return this.client;
+
+    if (this.signer && typeof this.signer !== 'string') {
+      this.client = this.signer.client;
+      this.senderAddress = this.signer.address;
+    } else if (this.signer) {
+      const wallet = await DirectSecp256k1HdWallet.fromMnemonic(this.signer, { prefix: this.chain.addressPrefix });
+      const [account] = await wallet.getAccounts();
+      this.senderAddress = account.address;
+      const signingClient = await SigningCosmWasmClient.connectWithSigner(this.chain.rpcUrl, wallet, {
+        gasPrice: GasPrice.fromString(getCosmosGasPrice(this.chain))
+      });
+      this.client = new CosmosHTLCClient({ chain: this.chain, client: signingClient });
+    } else {
+      this.client = new CosmosHTLCClient({ chain: this.chain, client: await CosmWasmClient.connect(this.chain.rpcUrl) });
+    }
+    return this.client;
+  }
+
+  protected ownAddress(): string | undefined {
+    return this.senderAddress;
+  }
+
+  protected async currentTime(): Promise<number> {
+    return (await this.connect()).getCurrentTime();
+  }
+
+  async latestHeight(): Promise<number> {
+    return (await this.connect()).getClient().getHeight();
+  }
+
+  async blockTime(height: number): Promise<number> {
+    const block = await (await this.connect()).getClient().getBlock(height);
+    return Math.floor(new Date(block.header.time).getTime() / 1000);
+  }
+
+  /** The fee in the signed auth info; the payer is its explicit payer, else the first signer as the events report it */
+  async txFee(txHash: string): Promise<TxFee> {
+    const tx = await (await this.connect()).getClient().getTx(txHash);
+    if (!tx) throw new Error(`Transaction ${txHash} not found on ${this.chainName}`);
+    const { authInfo } = decodeTxRaw(tx.tx);
+    const coin = authInfo.fee?.amount[0];
+    const attribute = (type: string, key: string) =>
+      tx.events.find(event => event.type === type && event.attributes.some(attr => attr.key === key))?.attributes.find(attr => attr.key === key)?.value;
+    return {
+      payer: authInfo.fee?.payer || attribute('tx', 'fee_payer') || attribute('message', 'sender') || '',
+      amount: coin?.amount ?? '0',
+      denom: coin?.denom ?? this.chain.nativeDenom,
+      height: tx.height
+    };
+  }
+
+  async scan(fromHeight: number, toHeight: number, chunkSize: number): Promise<BackfilledHTLC[]> {
+    const client = await this.connect();
+    const htlcs = new Map<string, BackfilledHTLC>();
+
+    for (let start = fromHeight; start <= toHeight; start += chunkSize) {
+      const end = Math.min(start + chunkSize - 1, toHeight);
+      const range = `tx.height>=${start} AND tx.height<=${end}`;
+
+      for (const method of ['fund', 'claim', 'refund'] as const) {
+        const txs = await client.searchTxs(`${range} AND wasm.method='${method}'`);
+
+        for (const tx of txs) {
+          const attributes = this.wasmAttributes(tx);
+          const contractId = attributes._contract_address;
+          if (!contractId) continue;
+
+          if (method === 'fund') {
+            htlcs.set(contractId, {
+              chain: this.chainName,
+              contractId,
+              originator: attributes.sender || '',
+              beneficiary: attributes.beneficiary || '',
+              token: attributes.token || this.chain.nativeDenom,
+              value: attributes.amount || '0',
+              hashLock: attributes.hash_lock || '',
+              timelock: parseInt(attributes.timelock || '0'),
+              fundedAt: { height: tx.height, txHash: tx.hash },
+              state: 'OPEN'
+            });
+            continue;
+          }
+
+          const htlc = htlcs.get(contractId) || await this.resolve(client, contractId, tx.height);
+          if (!htlc) continue;
+          htlcs.set(contractId, htlc);
+          htlc.state = method === 'claim' ? 'CLAIMED' : 'REFUNDED';
+          htlc.settledTxHash = tx.hash;
+          htlc.settledHeight = tx.height;
+          if (method === 'claim') htlc.preimage = attributes.preimage;
+        }
+      }
+    }
+
+    return Array.from(htlcs.values());
+  }
+
+  private async resolve(client: CosmosHTLCClient, contractId: string, height: number): Promise<BackfilledHTLC | undefined> {
+    try {
+      const details = await client.getDetails(contractId);
+      return {
+        chain: this.chainName,
+        contractId,
+        originator: details.originator,
+        beneficiary: details.beneficiary,
+        token: details.token,
+        value: details.value,
+        hashLock: details.hashLock,
+        timelock: details.timelock,
+        fundedAt: { height, txHash: '' },
+        state: 'OPEN'
+      };
+    } catch {
+      return undefined;
+    }
+  }
+
+  private wasmAttributes(tx: any): Record<string, string> {
+    const attributes: Record<string, string> = {};
+    for (const event of tx.events || []) {
+      if (event.type !== 'wasm') continue;
+      for (const attribute of event.attributes || []) {
+        attributes[attribute.key] = attribute.value;
+      }
+    }
+    return attributes;
+  }
+
+  protected async refund(htlc: BackfilledHTLC): Promise<string> {
+    const client = await this.connect();
+    if (!this.senderAddress) {
+      throw new Error('COSMOS_MNEMONIC is required to execute refunds');
+    }
+
+    if (!await client.isRefundable(htlc.contractId)) {
+      throw new Error('HTLC is no longer refundable');
+    }
+
+    const result = await client.refund(htlc.contractId, this.senderAddress);
+    return result.transactionHash;
+  }
+}
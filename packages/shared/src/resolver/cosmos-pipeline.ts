@@ -0,0 +1,134 @@
+import { EventEmitter } from 'events';
+import { CosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import { FusionDAO } from '../database';
+import type { CosmosChainConfig } from '../chains';
+import type { CosmosSigningAccount } from '../clients/cosmos';
+import { CosmosBackfiller, BackfillResult } from './backfill';
+import { createLogger } from '../utils/logger';
+
+const logger = createLogger('CosmosPipeline');
+
+export interface CosmosPipelineOptions {
+  /** How often to look for new blocks (ms) */
+  pollIntervalMs?: number;
+  /** First height to scan; defaults to the chain head at start() */
+  startHeight?: number;
+  /** Upper bound on blocks replayed per tick, so a long outage catches up gradually */
+  maxBlocksPerTick?: number;
+  /** Signer for refunds of expired HTLCs this relayer funded; read-only without it */
+  mnemonic?: string;
+  /** Signing account shared with the other senders on this chain; preferred over `mnemonic` */
+  signingAccount?: CosmosSigningAccount;
+  /** Apply swap repairs and refunds; false only reports them (dry-run) */
+  execute?: boolean;
+}
+
+export interface CosmosPipelineStatus {
+  chain: string;
+  chainId: string;
+  running: boolean;
+  scannedHeight?: number;
+  headHeight?: number;
+  lastError?: string;
+}
+
+/**
+ * Follows HTLC events on a single Cosmos chain. Each configured chain gets its own
+ * pipeline with its own RPC, cursor and signer, so a stalled chain never holds up the others.
+ *
+ * Emits 'htlc' for every HTLC seen in a new block range, plus 'tick' with the full
+ * reconciliation result and 'error' when a tick fails.
+ */
+export class CosmosEventPipeline extends EventEmitter {
+  private backfiller: CosmosBackfiller;
+  private client?: CosmWasmClient;
+  private timer?: NodeJS.Timeout;
+  private ticking = false;
+  private scannedHeight?: number;
+  private headHeight?: number;
+  private lastError?: string;
+  private pollIntervalMs: number;
+  private maxBlocksPerTick: number;
+
+  constructor(
+    public readonly chainName: string,
+    private chain: CosmosChainConfig,
+    dao: FusionDAO,
+    private options: CosmosPipelineOptions = {}
+  ) {
+    super();
+    this.backfiller = new CosmosBackfiller(chainName, chain, dao, options.signingAccount ?? options.mnemonic);
+    this.pollIntervalMs = options.pollIntervalMs ?? 6000;
+    this.maxBlocksPerTick = options.maxBlocksPerTick ?? 500;
+  }
+
+  async start(): Promise<void> {
+    if (this.timer) return;
+
+    this.client = await CosmWasmClient.connect(this.chain.rpcUrl);
+    this.headHeight = await this.client.getHeight();
+    this.scannedHeight = (this.options.startHeight ?? this.headHeight + 1) - 1;
+
+    logger.info(`Following ${this.chain.name} from height ${this.scannedHeight + 1}`, {
+      chain: this.chainName,
+      chainId: this.chain.chainId
+    });
+
+    this.timer = setInterval(() => {
+      this.tick().catch(() => undefined);
+    }, this.pollIntervalMs);
+    this.emit('started');
+  }
+
+  async stop(): Promise<void> {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+    this.client?.disconnect();
+    this.client = undefined;
+    this.emit('stopped');
+  }
+
+  getStatus(): CosmosPipelineStatus {
+    return {
+      chain: this.chainName,
+      chainId: this.chain.chainId,
+      running: !!this.timer,
+      scannedHeight: this.scannedHeight,
+      headHeight: this.headHeight,
+      ...(this.lastError ? { lastError: this.lastError } : {})
+    };
+  }
+
+  /**
+   * Replay the blocks produced since the last tick and reconcile them against the database
+   */
+  async tick(): Promise<BackfillResult | undefined> {
+    if (this.ticking || !this.client || this.scannedHeight === undefined) return undefined;
+    this.ticking = true;
+
+    try {
+      this.headHeight = await this.client.getHeight();
+      const fromHeight = this.scannedHeight + 1;
+      if (fromHeight > this.headHeight) return undefined;
+
+      const toHeight = Math.min(this.headHeight, fromHeight + this.maxBlocksPerTick - 1);
+      const result = await this.backfiller.run({ fromHeight, toHeight, execute: this.options.execute ?? true });
+
+      this.scannedHeight = toHeight;
+      this.lastError = undefined;
+
+      result.htlcs.forEach(htlc => this.emit('htlc', htlc));
+      this.emit('tick', result);
+      return result;
+    } catch (error) {
+      this.lastError = (error as Error).message;
+      logger.error(`Pipeline tick failed on ${this.chain.name}`, { chain: this.chainName, error: this.lastError });
+      this.emit('error', error);
+      throw error;
+    } finally {
+      this.ticking = false;
+    }
+  }
+}
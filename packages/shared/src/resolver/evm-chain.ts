@@ -0,0 +1,93 @@
+import { ethers } from 'ethers';
+import type { EvmChainConfig } from '../chains';
+
+type FinalityTag = 'latest' | 'safe' | 'finalized';
+
+const FINALITY_TAGS: Record<NonNullable<EvmChainConfig['finality']>, FinalityTag> = {
+  'probabilistic': 'latest',
+  'optimistic-rollup': 'safe',
+  'arbitrum-rollup': 'safe',
+  'checkpoint': 'finalized'
+};
+
+// OP Stack GasPriceOracle predeploy and Arbitrum NodeInterface precompile
+const OP_GAS_PRICE_ORACLE = '0x420000000000000000000000000000000000000F';
+const ARB_NODE_INTERFACE = '0x00000000000000000000000000000000000000C8';
+
+const OP_GAS_PRICE_ORACLE_ABI = ['function getL1Fee(bytes data) view returns (uint256)'];
+const ARB_NODE_INTERFACE_ABI = [
+  'function gasEstimateL1Component(address to, bool contractCreation, bytes data) payable returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate)'
+];
+
+export interface FinalityOptions {
+  pollIntervalMs?: number;
+  timeoutMs?: number;
+}
+
+export const confirmationDepth = (chain: EvmChainConfig): number => Math.max(1, chain.confirmations ?? 1);
+
+/**
+ * Whether a block can be acted on under the chain's finality model. Rollups and
+ * Polygon are checked against the RPC's safe/finalized head, falling back to
+ * confirmation depth on nodes that do not serve those tags.
+ */
+export async function isBlockFinal(
+  provider: ethers.Provider,
+  chain: EvmChainConfig,
+  blockNumber: number
+): Promise<boolean> {
+  const tag = FINALITY_TAGS[chain.finality ?? 'probabilistic'];
+
+  if (tag !== 'latest') {
+    const head = await provider.getBlock(tag).catch(() => null);
+    if (head) {
+      return head.number >= blockNumber;
+    }
+  }
+
+  const latest = await provider.getBlockNumber();
+  return latest - blockNumber + 1 >= confirmationDepth(chain);
+}
+
+export async function waitForFinality(
+  provider: ethers.Provider,
+  chain: EvmChainConfig,
+  blockNumber: number,
+  options: FinalityOptions = {}
+): Promise<void> {
+  const pollIntervalMs = options.pollIntervalMs ?? 2000;
+  const timeoutMs = options.timeoutMs ?? 30 * 60 * 1000;
+  const deadline = Date.now() + timeoutMs;
+
+  while (!await isBlockFinal(provider, chain, blockNumber)) {
+    if (Date.now() > deadline) {
+      throw new Error(`Block ${blockNumber} on ${chain.name} not final after ${timeoutMs}ms`);
+    }
+    await new Promise(resolve => setTimeout(resolve, pollIntervalMs));
+  }
+}
+
+/**
+ * L1 data fee (wei) an L2 charges on top of execution gas for posting the
+ * transaction's calldata to Ethereum. Zero on chains without one.
+ */
+export async function estimateL1DataFee(
+  provider: ethers.Provider,
+  chain: EvmChainConfig,
+  tx: { to: string; data: string }
+): Promise<bigint> {
+  switch (chain.gasModel) {
+    case 'op-stack': {
+      const oracle = new ethers.Contract(OP_GAS_PRICE_ORACLE, OP_GAS_PRICE_ORACLE_ABI, provider);
+      const unsigned = ethers.Transaction.from({ type: 2, chainId: chain.chainId, to: tx.to, data: tx.data }).unsignedSerialized;
+      return BigInt(await oracle.getL1Fee(unsigned));
+    }
+    case 'arbitrum': {
+      const nodeInterface = new ethers.Contract(ARB_NODE_INTERFACE, ARB_NODE_INTERFACE_ABI, provider);
+      const [gasEstimateForL1, baseFee] = await nodeInterface.gasEstimateL1Component.staticCall(tx.to, false, tx.data);
+      return BigInt(gasEstimateForL1) * BigInt(baseFee);
+    }
+    default:
+      return 0n;
+  }
+}
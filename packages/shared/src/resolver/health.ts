@@ -0,0 +1,80 @@
+export interface ComponentHealth {
+  ok: boolean;
+  latencyMs?: number;
+  error?: string;
+  details?: Record<string, unknown>;
+}
+
+export interface ResolverHealth {
+  chain: string;
+  draining: boolean;
+  rpc: ComponentHealth;
+  processingLoop: ComponentHealth;
+  key: ComponentHealth;
+}
+
+/**
+ * Run a health probe with a hard timeout so a hung dependency
+ * cannot block the probe endpoint itself
+ */
+export async function probe(
+  check: () => Promise<Record<string, unknown> | void>,
+  timeoutMs: number
+): Promise<ComponentHealth> {
+  const startTime = Date.now();
+  let timer: NodeJS.Timeout | undefined;
+
+  try {
+    const timeout = new Promise<never>((_, reject) => {
+      timer = setTimeout(() => reject(new Error(`Timed out after ${timeoutMs}ms`)), timeoutMs);
+    });
+    const details = await Promise.race([check(), timeout]);
+
+    return {
+      ok: true,
+      latencyMs: Date.now() - startTime,
+      ...(details ? { details } : {})
+    };
+  } catch (error) {
+    return {
+      ok: false,
+      latencyMs: Date.now() - startTime,
+      error: (error as Error).message
+    };
+  } finally {
+    if (timer) {
+      clearTimeout(timer);
+    }
+  }
+}
+
+/**
+ * A long-lived event subscription (websocket stream, pubsub feed) whose
+ * liveness is judged by how recently it delivered an event or heartbeat
+ */
+export interface SubscriptionSource {
+  readonly subscriptionName: string;
+  /** Epoch ms of the last event or heartbeat, undefined while not subscribed */
+  readonly lastHeartbeatAt?: number;
+}
+
+/**
+ * A subscription is healthy while it has delivered something within maxAgeMs.
+ * Sockets can die without a close frame, so a stale timestamp is the only signal.
+ */
+export function subscriptionHealth(source: SubscriptionSource, maxAgeMs: number): ComponentHealth & { name: string } {
+  const lastHeartbeatAt = source.lastHeartbeatAt;
+  const age = lastHeartbeatAt !== undefined ? Date.now() - lastHeartbeatAt : undefined;
+  const details = {
+    lastHeartbeatAt: lastHeartbeatAt !== undefined ? new Date(lastHeartbeatAt).toISOString() : null,
+    ageMs: age ?? null
+  };
+
+  if (age === undefined) {
+    return { name: source.subscriptionName, ok: false, error: 'Not subscribed', details };
+  }
+  if (age > maxAgeMs) {
+    return { name: source.subscriptionName, ok: false, error: `No event for ${age}ms (max ${maxAgeMs}ms)`, details };
+  }
+  return { name: source.subscriptionName, ok: true, details };
+}
@@ -1,20 +1,64 @@
 import { ethers } from 'ethers';
-import { FusionDatabase, FusionDAO, SwapRequest, SwapStatus, ResolverOperationType, OperationStatus } from '../database';
+import {
+  FusionDatabase,
+  FusionDAO,
+  SwapRequest,
+  SwapStatus,
+  ResolverOperationType,
+  OperationStatus,
+  RelayerAction,
+  RelayerActionStatus,
+  RelayerActionType
+} from '../database';
 import { EventEmitter } from 'events';
 import { getToken } from '../tokens';
 import { GasMonitor, GasBalance } from './gas-monitor';
+import { ResolverHealth, probe } from './health';
+import { AlertManager } from './alerting';
+import { createTracingLogger, withSpan } from './tracing';
+import { EvmNonceManager, Mutex } from './nonce-manager';
+import { ProfitabilityEngine, ProfitabilityConfig, DatabasePriceSource, PnLBreakdown } from './profitability';
+import { confirmationDepth, estimateL1DataFee, waitForFinality } from './evm-chain';
+import { DurableActionQueue, ActionIntent, ActionObservation } from './action-queue';
+import { evmChains, EvmChainConfig } from '../chains';
+import { FeeGuard } from '../clients/fees';
+import type { FeeAction, FeePolicy } from '../clients/fees';
+import { Logger } from '../utils/logger';
+import { openSecret, zeroize, PREIMAGE_CONTEXT } from '../database/secrets';
+import { deriveSwapId } from '../sdk/swap-id';
 
 export interface ResolverConfig {
   processingInterval: number; // ms
   maxBatchSize: number;
   maxRetries: number;
-  gasLimit: number;
   maxGasPrice: string; // wei
   htlcContractAddress: string;
   poolWalletPrivateKey: string;
   rpcUrl: string;
   chainId: number;
   chainName: string; // Add chain identifier
+  dryRun?: boolean; // Observe and decide, but only simulate transactions and skip state changes
+  profitability?: Partial<ProfitabilityConfig>; // Enables the minimum-margin gate when set
+  maxInFlightTxs?: number; // Unconfirmed transactions allowed per pool wallet (default 4)
+  maxConcurrentSwaps?: number; // Swaps processed in parallel per tick (default 1)
+  confirmations?: number; // Overrides the chain's configured confirmation depth
+  fees?: FeePolicy; // Gas padding and per-action fee budgets checked before every submission
+}
+
+export interface DryRunAction {
+  action: 'approve' | 'fundPoolHtlc' | 'claimUserHtlc';
+  ok: boolean;
+  gasEstimate?: string;
+  error?: string;
+}
+
+export interface DryRunReport {
+  swapId: string;
+  chain: string;
+  roles: { source: boolean; target: boolean };
+  decision: 'execute' | 'wait' | 'reject';
+  reason?: string;
+  actions: DryRunAction[];
 }
 
 export interface PoolLiquidityInfo {
@@ -33,6 +77,56 @@ export interface PriceInfo {
 }
 
 export { GasMonitor, GasBalance } from './gas-monitor';
+export { probe, subscriptionHealth } from './health';
+export type { ComponentHealth, ResolverHealth, SubscriptionSource } from './health';
+export {
+  AlertManager,
+  AlertCondition,
+  WebhookSink,
+  SlackSink,
+  PagerDutySink,
+  createAlertManagerFromEnv
+} from './alerting';
+export type { Alert, AlertSink, AlertSeverity, AlertingConfig } from './alerting';
+export { createTracingLogger, withSpan, currentSpan } from './tracing';
+export type { SpanContext } from './tracing';
+export { ProfitabilityEngine, DatabasePriceSource, DEFAULT_PROFITABILITY_CONFIG } from './profitability';
+export { EvmNonceManager, CosmosSequenceManager } from './nonce-manager';
+export { isBlockFinal, waitForFinality, estimateL1DataFee, confirmationDepth } from './evm-chain';
+export type { FinalityOptions } from './evm-chain';
+export { DurableActionQueue } from './action-queue';
+export type { ActionIntent, ActionObservation } from './action-queue';
+export { MempoolWatcher, EarlyClaimRelay } from './mempool-watcher';
+export type { MempoolWatcherOptions, DiscoveredPreimage } from './mempool-watcher';
+export type { AccountManagerOptions, CosmosBroadcast } from './nonce-manager';
+export { CosmosEventPipeline } from './cosmos-pipeline';
+export type { CosmosPipelineOptions, CosmosPipelineStatus } from './cosmos-pipeline';
+export { AuctionParticipant, AuctionOrderFeed, AuctionOrderSchema, currentTakingAmount } from './auction-participant';
+export type { AuctionOrder, AuctionFeedOptions, AuctionParticipantOptions } from './auction-participant';
+export {
+  EvmChainAdapter,
+  CosmosChainAdapter,
+  createChainAdapter,
+  registerChainAdapter,
+  waitUntilFinal
+} from './adapters';
+export { TimelockWatchdog } from './timelock-watchdog';
+export type { TimelockWatchdogOptions, TimelockEscalation } from './timelock-watchdog';
+export { Rebalancer } from './rebalancer';
+export type { Corridor, InventoryHolding, RebalanceRoute, RebalanceTransfer, RebalancerOptions, RouteSubmission } from './rebalancer';
+export { IbcTransferRoute, CctpRoute } from './rebalance-routes';
+export type { IbcChannel, IbcRouteOptions, CctpDomain, CctpRouteOptions } from './rebalance-routes';
+export type {
+  ChainAdapter,
+  ChainAdapterFactory,
+  AdapterCredentials,
+  CosmosAdapterOptions,
+  EvmAdapterOptions,
+  HTLCEvent,
+  PendingTx,
+  SwapView
+} from './adapters';
+export type { ProfitabilityConfig, ProfitabilityQuote, PnLBreakdown, PriceSource, TokenPrice } from './profitability';
 
 export class FusionResolver extends EventEmitter {
   private dao: FusionDAO;
@@ -40,22 +134,52 @@ export class FusionResolver extends EventEmitter {
   private poolWallet: ethers.Wallet;
   private htlcContract: ethers.Contract;
   private isProcessing = false;
+  private draining = false;
+  private currentTick?: Promise<void>;
   private processingTimer?: NodeJS.Timeout;
   private chainName: string;
   private gasMonitor: GasMonitor;
   private lastGasCheck: number = 0;
   private gasCheckInterval = 60000; // Check gas every minute
+  private lastTickAt?: number;
+  private recentBlockHashes: Map<number, string> = new Map();
+  private static readonly REORG_WINDOW = 64; // blocks of hash history kept for reorg detection
+  private log: Logger;
+  private profitability?: ProfitabilityEngine;
+  private nonceManager: EvmNonceManager;
+  private chainProfile: EvmChainConfig;
+  private actions: DurableActionQueue;
+  private fees: FeeGuard;
+  /** Serializes allowance check, approve and fund per ERC-20 token across concurrent swaps */
+  private tokenLocks = new Map<string, Mutex>();
 
   constructor(
     private config: ResolverConfig,
-    database: FusionDatabase
+    database: FusionDatabase,
+    private alerts?: AlertManager
   ) {
     super();
     this.chainName = config.chainName;
+    this.log = createTracingLogger('FusionResolver', { chain: config.chainName });
     this.dao = new FusionDAO(database);
     this.provider = new ethers.JsonRpcProvider(config.rpcUrl);
     this.poolWallet = new ethers.Wallet(config.poolWalletPrivateKey, this.provider);
+    this.nonceManager = new EvmNonceManager(this.poolWallet, { maxInFlight: config.maxInFlightTxs });
+    this.chainProfile = {
+      name: config.chainName,
+      type: 'evm',
+      rpcUrl: config.rpcUrl,
+      htlcAddress: config.htlcContractAddress,
+      ...evmChains[config.chainName],
+      chainId: config.chainId,
+      ...(config.confirmations !== undefined ? { confirmations: config.confirmations } : {})
+    };
     this.gasMonitor = new GasMonitor();
+    this.actions = new DurableActionQueue(this.dao, config.chainName);
+    this.fees = new FeeGuard(config.chainName, this.chainProfile, config.fees);
+    if (config.profitability) {
+      this.profitability = new ProfitabilityEngine(new DatabasePriceSource(this.dao), config.profitability);
+    }
     
     // Initialize HTLC contract (we'll need the ABI)
     const htlcABI = [
@@ -76,23 +200,31 @@ export class FusionResolver extends EventEmitter {
    * Start the resolver processing loop
    */
   async start(): Promise<void> {
-    console.log('Starting Fusion Resolver...');
+    this.log.info(this.config.dryRun ? 'Starting Fusion Resolver in DRY-RUN mode...' : 'Starting Fusion Resolver...');
     
     if (this.processingTimer) {
       clearInterval(this.processingTimer);
     }
+    this.draining = false;
+
+    // Settle whatever a previous run decided but never saw confirmed before taking new work
+    if (!this.config.dryRun) {
+      await this.reconcileActions();
+    }
 
     // Start processing loop
     this.processingTimer = setInterval(async () => {
+      this.lastTickAt = Date.now();
       try {
-        await this.processSwapQueue();
+        this.currentTick = this.processSwapQueue();
+        await this.currentTick;
       } catch (error) {
-        console.error('Error in processing loop:', error);
+        this.log.error('Error in processing loop', { error: (error as Error).message });
         this.emit('error', error);
       }
     }, this.config.processingInterval);
 
-    console.log(`Resolver started with ${this.config.processingInterval}ms interval`);
+    this.log.info(`Resolver started with ${this.config.processingInterval}ms interval`);
     this.emit('started');
   }
 
@@ -100,7 +232,7 @@ export class FusionResolver extends EventEmitter {
    * Stop the resolver
    */
   async stop(): Promise<void> {
-    console.log('Stopping Fusion Resolver...');
+    this.log.info('Stopping Fusion Resolver...');
     
     if (this.processingTimer) {
       clearInterval(this.processingTimer);
@@ -108,10 +240,60 @@ export class FusionResolver extends EventEmitter {
     }
 
     this.isProcessing = false;
-    console.log('Resolver stopped');
+    this.log.info('Resolver stopped');
     this.emit('stopped');
   }
 
+  get isDraining(): boolean {
+    return this.draining;
+  }
+
+  /**
+   * Nonce manager for the pool wallet. Anything else sending from that key on this
+   * chain must use it, or the two senders will hand out the same nonces.
+   */
+  get poolNonceManager(): EvmNonceManager {
+    return this.nonceManager;
+  }
+
+  /**
+   * Stop taking new swaps, let the swap in progress finish, and wait until every
+   * transaction already broadcast has confirmed. The resolver stays up (health
+   * endpoints keep answering) so it can be taken out of rotation before exiting.
+   */
+  async drain(timeoutMs: number = 5 * 60 * 1000): Promise<void> {
+    if (this.draining) return;
+
+    this.draining = true;
+    this.log.info('Draining: no new swaps will be picked up');
+    this.emit('draining');
+
+    if (this.processingTimer) {
+      clearInterval(this.processingTimer);
+      this.processingTimer = undefined;
+    }
+
+    const deadline = Date.now() + timeoutMs;
+    const remaining = () => Math.max(0, deadline - Date.now());
+
+    if (this.currentTick) {
+      const finished = await Promise.race([
+        this.currentTick.then(() => true, () => true),
+        new Promise<boolean>(resolve => setTimeout(() => resolve(false), remaining()))
+      ]);
+      if (!finished) {
+        this.log.warn('Drain timed out waiting for the current batch to finish');
+      }
+    }
+
+    while (this.nonceManager.inFlight > 0 && remaining() > 0) {
+      await new Promise(resolve => setTimeout(resolve, 1000));
+    }
+
+    this.log.info('Drain complete', { inFlightTxs: this.nonceManager.inFlight });
+    this.emit('drained');
+  }
+
   /**
    * Main processing loop - handles pending swaps
    */
@@ -123,12 +305,14 @@ export class FusionResolver extends EventEmitter {
     this.isProcessing = true;
 
     try {
-      // Check gas balance periodically
+      // Check gas balance periodically; timelocks are watched by the TimelockWatchdog
       if (Date.now() - this.lastGasCheck > this.gasCheckInterval) {
         await this.checkGasBalance();
         this.lastGasCheck = Date.now();
       }
 
+      await this.checkForReorg();
+
       // Get pending swaps
       const pendingSwaps = await this.dao.getPendingSwaps(this.config.maxBatchSize);
       
@@ -136,7 +320,7 @@ export class FusionResolver extends EventEmitter {
         return;
       }
 
-      console.log(`Processing ${pendingSwaps.length} pending swaps`);
+      this.log.info(`Processing ${pendingSwaps.length} pending swaps`);
 
       // Filter out invalid swaps before processing
       const validSwaps = pendingSwaps.filter(swap => this.isSwapValid(swap));
@@ -144,19 +328,34 @@ export class FusionResolver extends EventEmitter {
 
       // Cancel invalid swaps
       for (const invalidSwap of invalidSwaps) {
-        console.log(`Cancelling invalid swap ${invalidSwap.id}: amount=${invalidSwap.sourceAmount}`);
-        await this.dao.updateSwapRequest(invalidSwap.id, { status: SwapStatus.CANCELLED });
+        this.log.info(this.config.dryRun ? '[dry-run] Would cancel invalid swap' : 'Cancelling invalid swap', {
+          correlationId: invalidSwap.id,
+          swapId: invalidSwap.id,
+          sourceAmount: invalidSwap.sourceAmount
+        });
+        if (!this.config.dryRun) {
+          await this.dao.updateSwapRequest(invalidSwap.id, { status: SwapStatus.CANCELLED });
+        }
       }
 
-      // Process valid swaps
-      for (const swap of validSwaps) {
-        try {
-          await this.processSwap(swap);
-        } catch (error) {
-          console.error(`Error processing swap ${swap.id}:`, error);
-          await this.handleSwapError(swap.id, error as Error);
+      // Process valid swaps; nonces are managed per account so swaps can run side by side
+      const queue = [...validSwaps];
+      const workers = Array.from({ length: Math.max(1, this.config.maxConcurrentSwaps ?? 1) }, async () => {
+        // A drain lets the swap in hand finish but stops handing out new ones
+        for (let swap = queue.shift(); swap && !this.draining; swap = queue.shift()) {
+          try {
+            await this.processSwap(swap);
+          } catch (error) {
+            this.log.error('Error processing swap', {
+              correlationId: swap.id,
+              swapId: swap.id,
+              error: (error as Error).message
+            });
+            await this.handleSwapError(swap.id, error as Error);
+          }
         }
-      }
+      });
+      await Promise.all(workers);
     } finally {
       this.isProcessing = false;
     }
@@ -175,14 +374,56 @@ export class FusionResolver extends EventEmitter {
 
       const warning = this.gasMonitor.getWarningMessage(gasBalance);
       if (warning) {
-        console.warn(warning);
+        this.log.warn(warning);
         this.emit('lowGasBalance', gasBalance);
+        await this.alerts?.checkBalance(gasBalance);
       }
 
       // Log current balance
-      console.log(`💰 Gas balance on ${this.chainName}: ${gasBalance.formatted}`);
+      this.log.info(`💰 Gas balance: ${gasBalance.formatted}`, { balance: gasBalance.balance });
     } catch (error) {
-      console.error('Failed to check gas balance:', error);
+      this.log.error('Failed to check gas balance', { error: (error as Error).message });
+    }
+  }
+
+  /**
+   * Track recent block hashes and report when the canonical chain changes underneath us
+   */
+  private async checkForReorg(): Promise<void> {
+    try {
+      const block = await this.provider.getBlock('latest');
+      if (!block || !block.hash) return;
+
+      const knownHash = this.recentBlockHashes.get(block.number);
+      const knownParentHash = this.recentBlockHashes.get(block.number - 1);
+
+      let reorg: { blockNumber: number; expectedHash: string; actualHash: string } | undefined;
+      if (knownHash && knownHash !== block.hash) {
+        reorg = { blockNumber: block.number, expectedHash: knownHash, actualHash: block.hash };
+      } else if (knownParentHash && knownParentHash !== block.parentHash) {
+        reorg = { blockNumber: block.number - 1, expectedHash: knownParentHash, actualHash: block.parentHash };
+      }
+
+      if (reorg) {
+        this.log.warn(`⚠️ Reorg detected at block ${reorg.blockNumber}`, reorg);
+        // Anything at or above the fork point is no longer trustworthy
+        for (const height of this.recentBlockHashes.keys()) {
+          if (height >= reorg.blockNumber) this.recentBlockHashes.delete(height);
+        }
+        this.emit('reorgDetected', reorg);
+        await this.alerts?.reorgDetected({ chain: this.chainName, ...reorg });
+      }
+
+      this.recentBlockHashes.set(block.number, block.hash);
+      if (reorg) {
+        this.recentBlockHashes.set(block.number - 1, block.parentHash);
+      }
+
+      for (const height of this.recentBlockHashes.keys()) {
+        if (height < block.number - FusionResolver.REORG_WINDOW) this.recentBlockHashes.delete(height);
+      }
+    } catch (error) {
+      this.log.error('Failed to check for reorg', { error: (error as Error).message });
     }
   }
 
@@ -193,13 +434,13 @@ export class FusionResolver extends EventEmitter {
     // Check if amount is valid
     const amount = BigInt(swap.sourceAmount || '0');
     if (amount <= 0) {
-      console.log(`Swap ${swap.id} has invalid amount: ${swap.sourceAmount}`);
+      this.log.info('Swap has invalid amount', { correlationId: swap.id, swapId: swap.id, sourceAmount: swap.sourceAmount });
       return false;
     }
 
     // Check if HTLC contract is valid
     if (!swap.userHtlcContract || swap.userHtlcContract.length !== 66) {
-      console.log(`Swap ${swap.id} has invalid HTLC contract: ${swap.userHtlcContract}`);
+      this.log.info('Swap has invalid HTLC contract', { correlationId: swap.id, swapId: swap.id, userHtlcContract: swap.userHtlcContract });
       return false;
     }
 
@@ -208,18 +449,172 @@ export class FusionResolver extends EventEmitter {
     const now = Date.now();
     const maxPendingTime = 24 * 60 * 60 * 1000; // 24 hours
     if (now - createdAt > maxPendingTime) {
-      console.log(`Swap ${swap.id} has been pending too long`);
+      this.log.info('Swap has been pending too long', { correlationId: swap.id, swapId: swap.id });
       return false;
     }
 
     return true;
   }
 
-    /**
+  /**
+   * Process a single swap inside a span whose correlation ID is the swap ID,
+   * so every log line of the swap's lifecycle can be grepped together
+   */
+  private async processSwap(swap: SwapRequest): Promise<void> {
+    return withSpan(
+      this.log,
+      'swap',
+      { correlationId: swap.id, swapId: swap.id },
+      () => this.config.dryRun ? this.simulateSwap(swap).then(() => undefined) : this.executeSwap(swap)
+    );
+  }
+
+  /**
+   * Dry-run counterpart of executeSwap: runs the same observation and validation,
+   * then simulates the transactions it would send via eth_call and estimateGas.
+   * Nothing is written to the database and nothing is broadcast.
+   */
+  async simulateSwap(swap: SwapRequest): Promise<DryRunReport> {
+    const sourceChain = swap.sourceToken.split(':')[0];
+    const targetChain = swap.targetToken.split(':')[0];
+    const report: DryRunReport = {
+      swapId: swap.id,
+      chain: this.chainName,
+      roles: { source: sourceChain === this.chainName, target: targetChain === this.chainName },
+      decision: 'execute',
+      actions: []
+    };
+
+    try {
+      if (!report.roles.source && !report.roles.target) {
+        report.decision = 'reject';
+        report.reason = 'Neither source nor target chain matches this resolver';
+        return report;
+      }
+
+      if (report.roles.target) {
+        if (!await this.checkPoolLiquidity(swap)) {
+          report.decision = 'reject';
+          report.reason = 'Insufficient pool liquidity';
+          return report;
+        }
+        await this.validatePricing(swap);
+        await this.assertProfitable(swap);
+
+        if (!swap.poolHtlcContract) {
+          report.actions.push(...await this.simulatePoolHTLCFunding(swap));
+        } else {
+          await this.verifyPoolHTLC(swap);
+        }
+      }
+
+      if (report.roles.source) {
+        await this.validateUserHTLC(swap);
+        await this.validatePricing(swap);
+
+        if (sourceChain !== targetChain) {
+          const updatedSwap = await this.dao.getSwapRequest(swap.id);
+          if (!updatedSwap?.poolHtlcContract) {
+            report.decision = 'wait';
+            report.reason = 'Pool HTLC not deployed by target chain yet';
+            return report;
+          }
+        }
+
+        report.actions.push(await this.simulateClaim(swap));
+      }
+
+      if (report.actions.some(action => !action.ok)) {
+        report.decision = 'reject';
+        report.reason = 'Transaction simulation failed';
+      }
+    } catch (error) {
+      report.decision = 'reject';
+      report.reason = (error as Error).message;
+    } finally {
+      this.log.info(`🧪 [dry-run] Would ${report.decision} swap`, { ...report });
+      this.emit('swapSimulated', report);
+    }
+
+    return report;
+  }
+
+  /**
+   * Simulate the approve + fund calls deployPoolHTLC would make
+   */
+  private async simulatePoolHTLCFunding(swap: SwapRequest): Promise<DryRunAction[]> {
+    const targetTokenAddress = this.resolveTokenAddress(swap.targetToken);
+    const amountInWei = BigInt(swap.expectedAmount.toString());
+    const poolContractId = this.computePoolContractId(swap, targetTokenAddress, amountInWei);
+    const actions: DryRunAction[] = [];
+
+    if (targetTokenAddress === ethers.ZeroAddress) {
+      actions.push(await this.simulateCall('fundPoolHtlc', this.htlcContract.fundETH, [
+        poolContractId, swap.userAddress, swap.hashLock, swap.expirationTime, { value: amountInWei }
+      ]));
+      return actions;
+    }
+
+    const tokenContract = new ethers.Contract(
+      targetTokenAddress,
+      [
+        'function approve(address spender, uint256 amount) external returns (bool)',
+        'function allowance(address owner, address spender) view returns (uint256)'
+      ],
+      this.poolWallet
+    );
+    const currentAllowance: bigint = await tokenContract.allowance(
+      this.poolWallet.address,
+      this.config.htlcContractAddress
+    );
+
+    if (currentAllowance < amountInWei) {
+      actions.push(await this.simulateCall('approve', tokenContract.approve, [
+        this.config.htlcContractAddress, amountInWei
+      ]));
+      // fund would revert on transferFrom until the approval lands, so it cannot be simulated yet
+      actions.push({ action: 'fundPoolHtlc', ok: true, error: 'Not simulated: requires approval first' });
+      return actions;
+    }
+
+    actions.push(await this.simulateCall('fundPoolHtlc', this.htlcContract.fund, [
+      poolContractId, targetTokenAddress, swap.userAddress, swap.hashLock, swap.expirationTime, amountInWei
+    ]));
+    return actions;
+  }
+
+  /**
+   * Simulate the claim call claimUserTokens would make
+   */
+  private async simulateClaim(swap: SwapRequest): Promise<DryRunAction> {
+    if (!swap.preimageHash || !swap.userHtlcContract) {
+      return { action: 'claimUserHtlc', ok: false, error: 'Missing preimage or user HTLC contract' };
+    }
+
+    return this.simulateCall('claimUserHtlc', this.htlcContract.claim, [
+      swap.userHtlcContract, openSecret(swap.preimageHash, PREIMAGE_CONTEXT)
+    ]);
+  }
+
+  private async simulateCall(
+    action: DryRunAction['action'],
+    method: ethers.BaseContractMethod,
+    args: unknown[]
+  ): Promise<DryRunAction> {
+    try {
+      await method.staticCall(...(args as any[]));
+      const gasEstimate = await method.estimateGas(...(args as any[]));
+      return { action, ok: true, gasEstimate: gasEstimate.toString() };
+    } catch (error) {
+      return { action, ok: false, error: (error as Error).message };
+    }
+  }
+
+  /**
    * Process a single swap request with atomic transaction guarantees
    */
-private async processSwap(swap: SwapRequest): Promise<void> {
-    console.log(`Processing swap ${swap.id}: ${swap.sourceToken} -> ${swap.targetToken}`);
+  private async executeSwap(swap: SwapRequest): Promise<void> {
+    this.log.info(`Processing swap: ${swap.sourceToken} -> ${swap.targetToken}`);
 
     // Parse source and target chains from token format (e.g., "sepolia:MONSTER")
     const sourceChain = swap.sourceToken.split(':')[0];
@@ -234,14 +629,15 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     const isTargetChainResolver = targetChain === this.chainName;
     
     if (!isSourceChainResolver && !isTargetChainResolver) {
-      console.log(`Skipping swap ${swap.id}: Neither source (${sourceChain}) nor target (${targetChain}) chain matches resolver chain ${this.chainName}`);
+      this.log.info('Skipping swap: neither source nor target chain matches this resolver', { sourceChain, targetChain });
       return;
     }
     
-    console.log(`🎯 Resolver role for swap ${swap.id}:`);
-    console.log(`   - Is source chain resolver: ${isSourceChainResolver}`);
-    console.log(`   - Is target chain resolver: ${isTargetChainResolver}`);
-    console.log(`   - Cross-chain swap: ${sourceChain !== targetChain}`);
+    this.log.info('🎯 Resolver role', {
+      isSourceChainResolver,
+      isTargetChainResolver,
+      crossChain: sourceChain !== targetChain
+    });
 
     // Create resolver operation tracking
     const operation = await this.dao.createResolverOperation({
@@ -258,28 +654,31 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     try {
       // ATOMIC SWAP LOGIC: Target chain MUST deploy pool HTLC first
       if (isTargetChainResolver) {
-        await this.processTargetChain(swap, operation);
+        await withSpan(this.log, 'leg', { leg: 'target', legChain: targetChain }, () =>
+          this.processTargetChain(swap, operation));
       }
       
       // Source chain can only claim AFTER pool HTLC is deployed and verified
       if (isSourceChainResolver) {
-        await this.processSourceChain(swap, operation);
+        await withSpan(this.log, 'leg', { leg: 'source', legChain: sourceChain }, () =>
+          this.processSourceChain(swap, operation));
       }
 
       // Step 5: Finalize
       await this.updateOperation(operation.id, ResolverOperationType.FINALIZE, OperationStatus.COMPLETED);
 
-      console.log(`Successfully processed swap ${swap.id}`);
+      this.log.info('Successfully processed swap');
+      this.alerts?.recordSuccess(this.chainName, 'submissions');
       this.emit('swapProcessed', swap.id);
 
     } catch (error) {
-      console.error(`Failed to process swap ${swap.id}:`, error);
+      this.log.error('Failed to process swap', { error: (error as Error).message });
       
       // ATOMIC ROLLBACK: Release reserved liquidity on failure
       try {
         await this.dao.releasePoolLiquidity(swap.targetToken, swap.expectedAmount);
       } catch (releaseError) {
-        console.error('Failed to release reserved liquidity:', releaseError);
+        this.log.error('Failed to release reserved liquidity', { error: (releaseError as Error).message });
       }
 
       await this.updateOperation(operation.id, operation.operationType, OperationStatus.FAILED, (error as Error).message);
@@ -291,7 +690,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    * Process target chain - deploy pool HTLC with atomic guarantees
    */
   private async processTargetChain(swap: SwapRequest, operation: any): Promise<void> {
-    console.log(`📋 Processing as TARGET chain resolver (ATOMIC STEP 1)`);
+    this.log.info(`📋 Processing as TARGET chain resolver (ATOMIC STEP 1)`);
     
     // Step 1: Check pool liquidity
     const hasLiquidity = await this.checkPoolLiquidity(swap);
@@ -299,8 +698,9 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       throw new Error('Insufficient pool liquidity');
     }
 
-    // Step 2: Validate pricing
+    // Step 2: Validate pricing and expected PnL before committing inventory
     await this.validatePricing(swap);
+    await this.assertProfitable(swap);
     await this.updateOperation(operation.id, ResolverOperationType.MATCH_SWAP, OperationStatus.IN_PROGRESS);
 
     // Step 3: Reserve liquidity
@@ -314,11 +714,11 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         status: SwapStatus.POOL_FULFILLED
       });
       await this.updateOperation(operation.id, ResolverOperationType.DEPLOY_HTLC, OperationStatus.IN_PROGRESS);
-      console.log(`✅ Pool HTLC deployed and verified: ${poolHTLCAddress}`);
+      this.log.info('✅ Pool HTLC deployed and verified', { poolHtlcContract: poolHTLCAddress });
     } else {
       // Verify existing pool HTLC is still valid
       await this.verifyPoolHTLC(swap);
-      console.log(`✅ Pool HTLC already deployed and verified: ${swap.poolHtlcContract}`);
+      this.log.info('✅ Pool HTLC already deployed and verified', { poolHtlcContract: swap.poolHtlcContract });
       
       // Update status if not already updated
       if (swap.status === SwapStatus.PENDING) {
@@ -326,14 +726,14 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       }
     }
     
-    console.log(`✅ Target chain processing complete for swap ${swap.id}`);
+    this.log.info('✅ Target chain processing complete');
   }
 
   /**
    * Process source chain - validate and claim user HTLC with atomic safeguards
    */
   private async processSourceChain(swap: SwapRequest, operation: any): Promise<void> {
-    console.log(`📋 Processing as SOURCE chain resolver (ATOMIC STEP 2)`);
+    this.log.info(`📋 Processing as SOURCE chain resolver (ATOMIC STEP 2)`);
     
     // Step 1: Validate user HTLC
     await this.validateUserHTLC(swap);
@@ -348,12 +748,12 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     const targetChain = swap.targetToken.split(':')[0];
     
     if (sourceChain !== targetChain) {
-      console.log(`⏳ ATOMIC CHECK: Verifying pool HTLC exists and is valid...`);
+      this.log.info(`⏳ ATOMIC CHECK: Verifying pool HTLC exists and is valid...`);
       
       // Get latest swap data
       const updatedSwap = await this.dao.getSwapRequest(swap.id);
       if (!updatedSwap?.poolHtlcContract) {
-        console.log(`❌ ATOMIC ABORT: Pool HTLC not deployed by target chain. Will retry later.`);
+        this.log.info(`❌ ATOMIC ABORT: Pool HTLC not deployed by target chain. Will retry later.`);
         // Don't mark as failed - just return and retry later
         return;
       }
@@ -361,7 +761,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       // CRITICAL: Verify pool HTLC is actually funded on-chain before claiming user tokens
       swap.poolHtlcContract = updatedSwap.poolHtlcContract;
       await this.verifyPoolHTLCOnChain(swap, targetChain);
-      console.log(`✅ ATOMIC VERIFIED: Pool HTLC confirmed on-chain: ${swap.poolHtlcContract}`);
+      this.log.info('✅ ATOMIC VERIFIED: Pool HTLC confirmed on-chain', { poolHtlcContract: swap.poolHtlcContract });
     }
 
     // Step 4: Claim user tokens (ONLY after pool HTLC is verified)
@@ -374,7 +774,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       poolClaimedAt: new Date()
     });
     
-    console.log(`✅ Source chain processing complete for swap ${swap.id}`);
+    this.log.info('✅ Source chain processing complete');
   }
 
   /**
@@ -385,17 +785,18 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       throw new Error('User HTLC contract not deployed');
     }
 
-    console.log(`🔍 Validating user HTLC for swap ${swap.id}:`);
-    console.log(`   Contract ID: ${swap.userHtlcContract}`);
-    console.log(`   Expected amount: ${swap.sourceAmount}`);
-    console.log(`   Expected hash: ${swap.hashLock}`);
-    console.log(`   User address: ${swap.userAddress}`);
+    this.log.info('🔍 Validating user HTLC', {
+      userHtlcContract: swap.userHtlcContract,
+      expectedAmount: swap.sourceAmount,
+      hashLock: swap.hashLock,
+      userAddress: swap.userAddress
+    });
 
     // Check if HTLC is funded on-chain
     try {
       let contractDetails = await this.htlcContract.getDetails(swap.userHtlcContract);
       
-      console.log(`HTLC Details for ${swap.userHtlcContract}:`, {
+      this.log.debug('User HTLC details', {
         token: contractDetails.token,
         beneficiary: contractDetails.beneficiary,
         originator: contractDetails.originator,
@@ -407,11 +808,11 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       
       // If we get state 0, retry once after a delay (RPC propagation issue)
       if (Number(contractDetails.state) === 0) {
-        console.log(`⚠️  Got state 0, retrying after 3 seconds...`);
+        this.log.warn('⚠️  Got state 0, retrying after 3 seconds...');
         await new Promise(resolve => setTimeout(resolve, 3000));
         
         contractDetails = await this.htlcContract.getDetails(swap.userHtlcContract);
-        console.log(`Retry HTLC Details:`, {
+        this.log.debug('User HTLC details (retry)', {
           token: contractDetails.token,
           beneficiary: contractDetails.beneficiary,
           originator: contractDetails.originator,
@@ -430,10 +831,9 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       
       // Check if this is a valid HTLC (not empty)
       if (state === 0) {
-        console.log(`⚠️  HTLC ${swap.userHtlcContract} is in INVALID state. This usually means:`);
-        console.log(`   - The contract ID doesn't exist on-chain`);
-        console.log(`   - The HTLC was never funded`);
-        console.log(`   - Using wrong HTLC contract address`);
+        this.log.warn('⚠️  User HTLC is in INVALID state: the contract ID does not exist on-chain, was never funded, or the HTLC address is wrong', {
+          userHtlcContract: swap.userHtlcContract
+        });
         throw new Error(`User HTLC ${swap.userHtlcContract} not found on chain (state=INVALID)`);
       }
       
@@ -446,10 +846,6 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       // Amount is already stored in wei in the database
       const expectedValueWei = swap.sourceAmount.toString();
       
-      console.log(`💰 Amount validation:`);
-      console.log(`   Contract has: ${contractValueWei} wei (${ethers.formatEther(contractValueWei)} tokens)`);
-      console.log(`   Expected: ${expectedValueWei} wei (${ethers.formatEther(expectedValueWei)} tokens)`);
-      
       // Allow for small tolerance (0.1%) to account for gas fees and rounding
       const tolerance = BigInt(expectedValueWei) / 1000n; // 0.1% tolerance
       const contractValueBigInt = BigInt(contractValueWei);
@@ -458,28 +854,33 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         contractValueBigInt - expectedValueBigInt : 
         expectedValueBigInt - contractValueBigInt;
       
-      console.log(`   Amount tolerance check:`);
-      console.log(`     Expected: ${expectedValueBigInt} wei`);
-      console.log(`     Got: ${contractValueBigInt} wei`);
-      console.log(`     Difference: ${difference} wei`);
-      console.log(`     Tolerance: ${tolerance} wei`);
-      console.log(`     Within tolerance: ${difference <= tolerance}`);
+      this.log.info('💰 Amount validation', {
+        expectedWei: expectedValueWei,
+        contractWei: contractValueWei,
+        differenceWei: difference,
+        toleranceWei: tolerance,
+        withinTolerance: difference <= tolerance
+      });
       
       if (difference > tolerance) {
         throw new Error(`User HTLC amount mismatch. Expected: ${expectedValueWei} wei (${ethers.formatEther(expectedValueWei)} tokens), Got: ${contractValueWei} wei (${ethers.formatEther(contractValueWei)} tokens), Difference: ${difference} wei (tolerance: ${tolerance} wei)`);
       }
       
-      console.log(`✅ Amount validation passed within tolerance`);
-      console.log(`   Difference: ${ethers.formatEther(difference.toString())} tokens (${Number(difference * 10000n / expectedValueBigInt) / 100}%)`);
+      this.log.info('✅ Amount validation passed within tolerance', {
+        differencePercent: Number(difference * 10000n / expectedValueBigInt) / 100
+      });
 
       if (contractDetails.hashLock !== swap.hashLock) {
         throw new Error(`User HTLC hash lock mismatch. Expected: ${swap.hashLock}, Got: ${contractDetails.hashLock}`);
       }
 
-      console.log(`✅ User HTLC validation passed for ${swap.userHtlcContract}`);
+      this.log.info('✅ User HTLC validation passed', { userHtlcContract: swap.userHtlcContract });
 
     } catch (error) {
-      console.error(`HTLC validation error for ${swap.userHtlcContract}:`, error);
+      this.log.error('HTLC validation error', {
+        userHtlcContract: swap.userHtlcContract,
+        error: (error as Error).message
+      });
       
       // Check if this is a contract call error (HTLC doesn't exist)
       if ((error as Error).message.includes('call revert exception') || 
@@ -495,22 +896,21 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    * Check if pool has sufficient liquidity for the swap
    */
   private async checkPoolLiquidity(swap: SwapRequest): Promise<boolean> {
-    console.log(`🏊 Checking pool liquidity for token: ${swap.targetToken}`);
-    console.log(`   Required amount: ${swap.expectedAmount}`);
+    this.log.info('🏊 Checking pool liquidity', { token: swap.targetToken, requiredAmount: swap.expectedAmount });
     
     try {
       const liquidity = await this.dao.getPoolLiquidity(swap.targetToken);
       
-      console.log(`   Pool liquidity data:`, liquidity);
+      this.log.debug('Pool liquidity data', { liquidity });
       
       if (!liquidity) {
-        console.log(`❌ No liquidity data found for token: ${swap.targetToken}`);
+        this.log.warn('❌ No liquidity data found', { token: swap.targetToken });
         
         // TEMPORARY: For testing, assume we have sufficient liquidity for demo tokens
         if (swap.targetToken === 'monadTestnet:OMNI' || 
             swap.targetToken === 'monadTestnet:OMNIMONSTER' || 
             swap.targetToken === 'sepolia:MONSTER') {
-          console.log(`✅ TEMPORARY: Assuming sufficient liquidity for demo token ${swap.targetToken}`);
+          this.log.info(`✅ TEMPORARY: Assuming sufficient liquidity for demo token ${swap.targetToken}`);
           return true;
         }
         
@@ -525,19 +925,23 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         availableBigInt = BigInt(availableBalance);
         // If available balance is negative, treat as 0
         if (availableBigInt < 0n) {
-          console.log(`   ⚠️ Available balance is negative, treating as 0`);
+          this.log.warn('⚠️ Available balance is negative, treating as 0');
           availableBigInt = 0n;
         }
       } catch (error) {
-        console.log(`   ❌ Invalid available balance: ${(liquidity as any).available_balance || liquidity.availableBalance}`);
+        this.log.warn('❌ Invalid available balance', {
+          availableBalance: (liquidity as any).available_balance || liquidity.availableBalance
+        });
         return false;
       }
       
       const requiredBigInt = BigInt(swap.expectedAmount);
 
-      console.log(`   Available: ${(liquidity as any).available_balance || liquidity.availableBalance} tokens`);
-      console.log(`   Required: ${swap.expectedAmount} tokens`);
-      console.log(`   Has sufficient liquidity: ${availableBigInt >= requiredBigInt}`);
+      this.log.info('Pool liquidity check', {
+        available: availableBigInt,
+        required: requiredBigInt,
+        sufficient: availableBigInt >= requiredBigInt
+      });
 
       const hasSufficient = availableBigInt >= requiredBigInt;
       
@@ -545,17 +949,17 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       if (!hasSufficient && (swap.targetToken === 'monadTestnet:OMNI' || 
                             swap.targetToken === 'monadTestnet:OMNIMONSTER' || 
                             swap.targetToken === 'sepolia:MONSTER')) {
-        console.log(`✅ TEMPORARY: Allowing insufficient liquidity for demo token ${swap.targetToken}`);
+        this.log.info(`✅ TEMPORARY: Allowing insufficient liquidity for demo token ${swap.targetToken}`);
         return true;
       }
       
       return hasSufficient;
     } catch (error) {
-      console.error(`❌ Error checking pool liquidity:`, error);
+      this.log.error(`❌ Error checking pool liquidity`, { error: (error as Error).message });
       
       // TEMPORARY: For testing, assume we have sufficient liquidity for demo tokens
       if (swap.targetToken === 'monadTestnet:OMNI' || swap.targetToken === 'sepolia:MONSTER') {
-        console.log(`✅ TEMPORARY: Assuming sufficient liquidity for demo token ${swap.targetToken} due to DB error`);
+        this.log.info(`✅ TEMPORARY: Assuming sufficient liquidity for demo token ${swap.targetToken} due to DB error`);
         return true;
       }
       
@@ -578,8 +982,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     if ((swap.sourceToken === 'sepolia:MONSTER' && swap.targetToken === 'monadTestnet:OMNI') ||
         (swap.sourceToken === 'sepolia:MONSTER' && swap.targetToken === 'monadTestnet:OMNIMONSTER') ||
         (swap.sourceToken === 'monadTestnet:OMNIMONSTER' && swap.targetToken === 'sepolia:MONSTER')) {
-      console.log(`✅ TEMPORARY: Allowing demo token pair for pricing validation`);
-      console.log(`   Using default decimals (18) for both tokens`);
+      this.log.info('✅ TEMPORARY: Allowing demo token pair for pricing validation (default 18 decimals)');
       
       // Mock token data for demo tokens
       const sourceSymbol = swap.sourceToken.includes('OMNIMONSTER') ? 'OMNIMONSTER' : 'MONSTER';
@@ -592,16 +995,17 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       const targetAmountNum = parseFloat(swap.expectedAmount) / Math.pow(10, mockTargetToken.decimals);
       const requestedRatio = targetAmountNum / sourceAmountNum;
       
-      console.log(`   Demo pricing: ${sourceAmountNum} ${mockSourceToken.symbol} -> ${targetAmountNum} ${mockTargetToken.symbol} (ratio: ${requestedRatio})`);
+      this.log.info(`Demo pricing: ${sourceAmountNum} ${mockSourceToken.symbol} -> ${targetAmountNum} ${mockTargetToken.symbol}`, { ratio: requestedRatio });
       
       // For demo, accept any ratio (in production you'd validate against oracle)
       return;
     }
 
     if (!sourceToken || !targetToken) {
-      console.log(`❌ Unsupported token pair: ${swap.sourceToken} -> ${swap.targetToken}`);
-      console.log(`   Source token found: ${!!sourceToken}`);
-      console.log(`   Target token found: ${!!targetToken}`);
+      this.log.warn(`❌ Unsupported token pair: ${swap.sourceToken} -> ${swap.targetToken}`, {
+        sourceTokenFound: !!sourceToken,
+        targetTokenFound: !!targetToken
+      });
       throw new Error('Unsupported token pair');
     }
 
@@ -612,7 +1016,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
 
     // Here you would fetch actual market prices and compare
     // For now, we'll use a simple validation
-    console.log(`Validating price ratio: ${requestedRatio} for ${sourceToken.symbol}/${targetToken.symbol}`);
+    this.log.info(`Validating price ratio: ${requestedRatio} for ${sourceToken.symbol}/${targetToken.symbol}`);
     
     // Allow reasonable slippage beyond user's specified tolerance
     // const maxAllowedSlippage = swap.slippageTolerance + 0.01; // Add 1% buffer
@@ -623,6 +1027,41 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     }
   }
 
+  /**
+   * Gate the target leg on expected PnL (no-op unless profitability is configured)
+   */
+  private async assertProfitable(swap: SwapRequest): Promise<PnLBreakdown | undefined> {
+    if (!this.profitability) return undefined;
+
+    const [sourceChain, sourceSymbol] = swap.sourceToken.split(':');
+    const [targetChain, targetSymbol] = swap.targetToken.split(':');
+    const supportedSource = await this.dao.getSupportedToken(swap.sourceToken);
+    const targetTokenAddress = this.resolveTokenAddress(swap.targetToken);
+
+    const pnl = await this.profitability.evaluate({
+      sourceToken: swap.sourceToken,
+      sourceAmount: swap.sourceAmount,
+      sourceDecimals: getToken(sourceChain, sourceSymbol)?.decimals ?? 18,
+      targetToken: swap.targetToken,
+      targetAmount: swap.expectedAmount,
+      targetDecimals: getToken(targetChain, targetSymbol)?.decimals ?? 18,
+      feePercentage: (supportedSource as any)?.fee_percentage !== undefined
+        ? parseFloat((supportedSource as any).fee_percentage)
+        : undefined,
+      needsApproval: targetTokenAddress !== ethers.ZeroAddress,
+      holdSeconds: Number(swap.expirationTime) - Math.floor(Date.now() / 1000)
+    });
+
+    this.log.info(pnl.profitable ? '📈 Swap is profitable' : '📉 Swap rejected by profitability gate', { ...pnl });
+
+    if (!pnl.profitable) {
+      this.emit('swapUnprofitable', swap.id, pnl);
+      throw new Error(`Unprofitable swap: ${pnl.reason}`);
+    }
+
+    return pnl;
+  }
+
   /**
    * Resolve token format (e.g., "monadTestnet:OMNI") to actual contract address
    */
@@ -644,7 +1083,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       throw new Error(`Token not found: ${symbol} on chain ${chainKey}`);
     }
 
-    console.log(`🔗 Resolved token ${tokenFormat} to address: ${token.address}`);
+    this.log.info(`🔗 Resolved token ${tokenFormat} to address: ${token.address}`);
     return token.address;
   }
 
@@ -671,7 +1110,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       throw new Error('No pool HTLC contract to verify');
     }
 
-    console.log(`🔍 Verifying pool HTLC ${swap.poolHtlcContract}...`);
+    this.log.info('🔍 Verifying pool HTLC...', { poolHtlcContract: swap.poolHtlcContract });
     
     try {
       const contractDetails = await this.htlcContract.getDetails(swap.poolHtlcContract);
@@ -696,10 +1135,10 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         throw new Error(`Pool HTLC hash lock mismatch. Expected: ${swap.hashLock}, Got: ${contractDetails.hashLock}`);
       }
       
-      console.log(`✅ Pool HTLC verification passed`);
+      this.log.info(`✅ Pool HTLC verification passed`);
       
     } catch (error) {
-      console.error(`❌ Pool HTLC verification failed:`, error);
+      this.log.error(`❌ Pool HTLC verification failed`, { error: (error as Error).message });
       throw new Error(`Pool HTLC verification failed: ${(error as Error).message}`);
     }
   }
@@ -712,7 +1151,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     // For now, we'll trust the database record, but in production you'd want
     // to verify on the actual target chain RPC
     
-    console.log(`🔗 Cross-chain verification: Pool HTLC ${swap.poolHtlcContract} on ${targetChain}`);
+    this.log.info('🔗 Cross-chain verification of pool HTLC', { poolHtlcContract: swap.poolHtlcContract, targetChain });
     
     // TODO: Implement actual cross-chain RPC verification
     // For now, just verify the contract ID format is valid
@@ -720,7 +1159,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       throw new Error(`Invalid pool HTLC contract ID format: ${swap.poolHtlcContract}`);
     }
     
-    console.log(`✅ Cross-chain pool HTLC format verification passed`);
+    this.log.info(`✅ Cross-chain pool HTLC format verification passed`);
   }
 
   /**
@@ -731,48 +1170,56 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       // Resolve token address from format like "monadTestnet:OMNI" to actual contract address
       const targetTokenAddress = this.resolveTokenAddress(swap.targetToken);
       
-      console.log(`🚀 Deploying pool HTLC for swap ${swap.id}:`);
-      console.log(`   Target token: ${swap.targetToken} → ${targetTokenAddress}`);
-      console.log(`   Amount: ${swap.expectedAmount}`);
-      console.log(`   Hash lock: ${swap.hashLock}`);
-      
       // Amount should already be in wei from the database
       // No need to convert again
       const amountInWei = BigInt(swap.expectedAmount.toString());
-      console.log(`   Amount in wei: ${amountInWei.toString()}`);
+
+      this.log.info('🚀 Deploying pool HTLC', {
+        targetToken: swap.targetToken,
+        targetTokenAddress,
+        amountWei: amountInWei,
+        hashLock: swap.hashLock
+      });
       
-      // Generate unique contract ID for pool HTLC (deterministic)
-      const nonce = Date.now();
-      const poolContractId = ethers.keccak256(
-        ethers.AbiCoder.defaultAbiCoder().encode(
-          ['address', 'address', 'bytes32', 'uint256', 'address', 'uint256', 'uint256'],
-          [this.poolWallet.address, swap.userAddress, swap.hashLock, swap.expirationTime, targetTokenAddress, amountInWei, nonce]
-        )
-      );
+      // The pool HTLC ID is derived from the swap terms, so a retry reuses the ID of the earlier
+      // attempt and, if that one lands late, the second funding reverts instead of doubling up.
+      // Actions recorded before IDs were canonical keep the ID they were submitted with.
+      const previousFunding = await this.actions.latest(swap.id, RelayerActionType.FUND_POOL_HTLC);
+      const poolContractId = previousFunding?.contractId ??
+        this.computePoolContractId(swap, targetTokenAddress, amountInWei);
 
       // Fund the pool HTLC
-      let tx;
+      let send: () => Promise<ethers.ContractTransactionResponse>;
+      let approve: (() => Promise<void>) | undefined;
       if (targetTokenAddress === ethers.ZeroAddress) {
         // ETH transfer
-        tx = await this.htlcContract.fundETH(
-          poolContractId,
-          swap.userAddress,
-          swap.hashLock,
-          swap.expirationTime,
-          { 
-            value: amountInWei,
-            gasLimit: this.config.gasLimit,
-            maxFeePerGas: this.config.maxGasPrice
-          }
-        );
+        send = async () => {
+          const gas = await this.gasLimitFor('fund', await this.htlcContract.fundETH.populateTransaction(
+            poolContractId,
+            swap.userAddress,
+            swap.hashLock,
+            swap.expirationTime,
+            { value: amountInWei }
+          ));
+          return this.nonceManager.send(nonce => this.htlcContract.fundETH(
+            poolContractId,
+            swap.userAddress,
+            swap.hashLock,
+            swap.expirationTime,
+            {
+              value: amountInWei,
+              ...gas,
+              maxFeePerGas: this.config.maxGasPrice,
+              nonce
+            }
+          ));
+        };
       } else {
         // ERC20 transfer
         // Setup gas configuration first
         const feeData = await this.provider.getFeeData();
         
-        const gasOptions: Record<string, any> = {
-          gasLimit: this.config.gasLimit
-        };
+        const gasOptions: Record<string, any> = {};
         
         // Force legacy gas pricing for cost efficiency on Monad
         if (feeData.gasPrice) {
@@ -786,7 +1233,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
           gasOptions.gasPrice = this.config.maxGasPrice;
         }
         
-        console.log(`⛽ Pool HTLC gas options:`, gasOptions);
+        this.log.debug('⛽ Pool HTLC gas options', gasOptions);
         
         // First check if we need to approve the HTLC contract to spend tokens
         const tokenContract = new ethers.Contract(
@@ -798,54 +1245,229 @@ private async processSwap(swap: SwapRequest): Promise<void> {
           this.poolWallet
         );
         
-        // Check current allowance
-        const currentAllowance = await tokenContract.allowance(
-          this.poolWallet.address,
-          this.config.htlcContractAddress
-        );
-        
-        const requiredAmount = amountInWei;
-        
-        // Only approve if current allowance is insufficient
-        if (BigInt(currentAllowance) < requiredAmount) {
-          console.log(`   Current allowance: ${currentAllowance}, required: ${requiredAmount}`);
-          console.log(`   Approving token spend...`);
-          
-          const approveTx = await tokenContract.approve(
-            this.config.htlcContractAddress,
-            amountInWei,
-            gasOptions
+        approve = async () => {
+          // Check current allowance
+          const currentAllowance = await tokenContract.allowance(
+            this.poolWallet.address,
+            this.config.htlcContractAddress
           );
-          await approveTx.wait();
-          console.log(`   ✅ Approval complete`);
-        } else {
-          console.log(`   ✅ Sufficient allowance already exists: ${currentAllowance}`);
-        }
 
-        // Then fund the HTLC
-        
-        tx = await this.htlcContract.fund(
-          poolContractId,
-          targetTokenAddress,
-          swap.userAddress,
-          swap.hashLock,
-          swap.expirationTime,
-          amountInWei,
-          gasOptions
-        );
+          const requiredAmount = amountInWei;
+
+          // Only approve if current allowance is insufficient
+          if (BigInt(currentAllowance) < requiredAmount) {
+            this.log.info('Approving token spend...', { currentAllowance, required: requiredAmount });
+
+            const approveGas = await this.gasLimitFor('approve', await tokenContract.approve.populateTransaction(
+              this.config.htlcContractAddress,
+              amountInWei
+            ));
+            const approveTx = await this.nonceManager.send(nonce => tokenContract.approve(
+              this.config.htlcContractAddress,
+              amountInWei,
+              { ...gasOptions, ...approveGas, nonce }
+            ));
+            await withSpan(this.log, 'tx', { action: 'approve', txHash: approveTx.hash }, () => approveTx.wait());
+            this.log.info('✅ Approval complete');
+          } else {
+            this.log.info('✅ Sufficient allowance already exists', { currentAllowance });
+          }
+        };
+
+        // Then fund the HTLC; estimated only now, since it reverts until the approval has landed
+        send = async () => {
+          const gas = await this.gasLimitFor('fund', await this.htlcContract.fund.populateTransaction(
+            poolContractId,
+            targetTokenAddress,
+            swap.userAddress,
+            swap.hashLock,
+            swap.expirationTime,
+            amountInWei
+          ));
+          return this.nonceManager.send(nonce => this.htlcContract.fund(
+            poolContractId,
+            targetTokenAddress,
+            swap.userAddress,
+            swap.hashLock,
+            swap.expirationTime,
+            amountInWei,
+            { ...gasOptions, ...gas, nonce }
+          ));
+        };
       }
 
-      const receipt = await tx.wait();
-      console.log(`Pool HTLC deployed: ${poolContractId}, tx: ${receipt.hash}`);
+      const submit = () => this.submitAction(
+        {
+          swapId: swap.id,
+          action: RelayerActionType.FUND_POOL_HTLC,
+          contractId: poolContractId,
+          payload: { token: targetTokenAddress, amount: amountInWei.toString(), expirationTime: swap.expirationTime }
+        },
+        'fundPoolHtlc',
+        send
+      );
+
+      // approve() sets rather than adds to the allowance, so a concurrent swap on the same
+      // token could shrink it between our approve and fund; hold the token until fund is mined
+      const receipt = approve
+        ? await this.tokenLock(targetTokenAddress).run(async () => {
+          await approve!();
+          return submit();
+        })
+        : await submit();
+      if (receipt) {
+        this.log.info('Pool HTLC deployed', { poolHtlcContract: poolContractId, txHash: receipt.hash });
+        await this.awaitFinality(receipt, 'fundPoolHtlc');
+      }
 
       return poolContractId;
 
     } catch (error) {
-      console.error(`❌ Failed to deploy pool HTLC:`, error);
+      this.log.error(`❌ Failed to deploy pool HTLC`, { error: (error as Error).message });
       throw new Error(`Failed to deploy pool HTLC: ${(error as Error).message}`);
     }
   }
 
+  /**
+   * Send a settlement transaction through the durable action queue: the action is
+   * persisted before broadcast and its hash right after. If the same action is already
+   * open from an earlier attempt it is reconciled first, and nothing is resent while its
+   * transaction is still pending. Returns null when the chain already reflects the action.
+   */
+  private async submitAction(
+    intent: ActionIntent,
+    spanAction: string,
+    send: () => Promise<ethers.ContractTransactionResponse>
+  ): Promise<ethers.TransactionReceipt | null> {
+    let action = await this.actions.record(intent);
+
+    if (action.txHash) {
+      const status = await this.actions.resolve(action, await this.observeAction(action));
+      if (status === RelayerActionStatus.CONFIRMED) return null;
+      if (status === RelayerActionStatus.SUBMITTED) {
+        throw new Error(`${intent.action} on ${intent.contractId} is still pending in ${action.txHash}`);
+      }
+      action = await this.actions.record(intent);
+    }
+
+    let tx: ethers.ContractTransactionResponse;
+    try {
+      tx = await send();
+    } catch (error) {
+      await this.actions.failed(action, (error as Error).message);
+      throw error;
+    }
+
+    // From here on a failure leaves the action SUBMITTED for the next attempt to reconcile
+    await this.actions.submitted(action, tx.hash);
+    const receipt = await withSpan(this.log, 'tx', { action: spanAction, txHash: tx.hash }, () => tx.wait());
+    await this.actions.confirmed(action);
+    return receipt;
+  }
+
+  /**
+   * Check an action's target HTLC and recorded transaction on-chain
+   */
+  private async observeAction(action: RelayerAction): Promise<ActionObservation> {
+    const details = await this.htlcContract.getDetails(action.contractId);
+    const state = Number(details.state); // 0=INVALID, 1=OPEN, 2=CLAIMED, 3=REFUNDED
+    const applied = action.action === RelayerActionType.FUND_POOL_HTLC ? state !== 0
+      : action.action === RelayerActionType.CLAIM ? state === 2
+      : state === 3;
+
+    if (applied || !action.txHash) {
+      return { applied };
+    }
+
+    const receipt = await this.provider.getTransactionReceipt(action.txHash);
+    if (receipt) {
+      return { applied, reverted: receipt.status === 0 };
+    }
+    return { applied, inFlight: !!await this.provider.getTransaction(action.txHash) };
+  }
+
+  /**
+   * Resolve every open action left by a previous run and carry confirmed ones through to the swap row
+   */
+  private async reconcileActions(): Promise<void> {
+    const open = await this.actions.open();
+    if (open.length === 0) return;
+
+    this.log.info(`Reconciling ${open.length} open relayer actions`);
+
+    for (const action of open) {
+      try {
+        const status = await this.actions.resolve(action, await this.observeAction(action));
+        if (status !== RelayerActionStatus.CONFIRMED) continue;
+
+        const swap = await this.dao.getSwapRequest(action.swapRequestId);
+        if (!swap || [SwapStatus.USER_CLAIMED, SwapStatus.EXPIRED, SwapStatus.CANCELLED].includes(swap.status)) continue;
+
+        if (action.action === RelayerActionType.FUND_POOL_HTLC && !swap.poolHtlcContract) {
+          await this.dao.updateSwapRequest(swap.id, { poolHtlcContract: action.contractId, status: SwapStatus.POOL_FULFILLED });
+        } else if (action.action === RelayerActionType.CLAIM) {
+          await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.USER_CLAIMED, poolClaimedAt: new Date() });
+        } else if (action.action === RelayerActionType.REFUND) {
+          await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.EXPIRED });
+        }
+      } catch (error) {
+        this.log.error('Failed to reconcile relayer action', {
+          correlationId: action.swapRequestId,
+          actionId: action.id,
+          error: (error as Error).message
+        });
+      }
+    }
+  }
+
+  /**
+   * Padded gas limit for a transaction, refusing it when the estimated cost is over the
+   * action's fee budget. Arbitrum already folds the L1 calldata cost into the estimate;
+   * OP Stack charges it separately, so it is added to the cost there.
+   */
+  private async gasLimitFor(action: FeeAction, tx: ethers.ContractTransaction): Promise<{ gasLimit: bigint }> {
+    const l1DataFee = this.chainProfile.gasModel === 'arbitrum'
+      ? 0n
+      : await estimateL1DataFee(this.provider, this.chainProfile, { to: tx.to, data: tx.data });
+    const { gasLimit, cost } = await this.fees.evm(action, this.provider, { ...tx, from: this.poolWallet.address }, l1DataFee);
+    this.log.debug('⛽ Gas estimate', { action, gasLimit, cost });
+    return { gasLimit };
+  }
+
+  /**
+   * Hold a confirmed transaction until its block is final under this chain's finality
+   * model, so swap state is never advanced on a block that can still be reorged out
+   */
+  private async awaitFinality(receipt: ethers.TransactionReceipt, action: string): Promise<void> {
+    const finality = this.chainProfile.finality ?? 'probabilistic';
+    if (finality === 'probabilistic' && confirmationDepth(this.chainProfile) <= 1) return;
+
+    await withSpan(this.log, 'finality', { action, txHash: receipt.hash, blockNumber: receipt.blockNumber, finality }, () =>
+      waitForFinality(this.provider, this.chainProfile, receipt.blockNumber));
+  }
+
+  private tokenLock(tokenAddress: string): Mutex {
+    const key = tokenAddress.toLowerCase();
+    let lock = this.tokenLocks.get(key);
+    if (!lock) {
+      lock = new Mutex();
+      this.tokenLocks.set(key, lock);
+    }
+    return lock;
+  }
+
+  private computePoolContractId(swap: SwapRequest, tokenAddress: string, amountInWei: bigint): string {
+    return deriveSwapId({
+      chainId: this.config.chainId,
+      hashLock: swap.hashLock,
+      originator: this.poolWallet.address,
+      beneficiary: swap.userAddress,
+      token: tokenAddress,
+      amount: amountInWei,
+      timelock: Number(swap.expirationTime)
+    });
+  }
+
   /**
    * Claim tokens from user HTLC
    */
@@ -853,14 +1475,16 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     const maxRetries = 3;
     
     try {
-      // Get preimage from database
-      const preimage = swap.preimageHash;
+      // Get preimage from database; it is stored sealed and only opened here
+      const preimage = openSecret(swap.preimageHash, PREIMAGE_CONTEXT);
       
-      console.log(`🔍 Attempting to claim user HTLC for swap ${swap.id}:`);
-      console.log(`   Contract ID: ${swap.userHtlcContract || 'MISSING'}`);
-      console.log(`   Hash Lock: ${swap.hashLock}`);
-      console.log(`   Preimage available: ${!!preimage}`);
-      console.log(`   Retry attempt: ${retryCount}/${maxRetries}`);
+      this.log.info('🔍 Attempting to claim user HTLC', {
+        userHtlcContract: swap.userHtlcContract || 'MISSING',
+        hashLock: swap.hashLock,
+        preimageAvailable: !!preimage,
+        attempt: retryCount,
+        maxRetries
+      });
       
       if (!preimage) {
         throw new Error('No preimage available for claiming HTLC');
@@ -885,20 +1509,19 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       const crypto = await import('crypto');
       const preimageBuffer = Buffer.from(preimage.slice(2), 'hex');
       const calculatedHash = '0x' + crypto.createHash('sha256').update(preimageBuffer).digest('hex');
-      console.log(`   Calculated hash (SHA256): ${calculatedHash}`);
+      zeroize(preimageBuffer);
+      this.log.debug('Calculated preimage hash (SHA256)', { calculatedHash });
       
       if (calculatedHash.toLowerCase() !== swap.hashLock.toLowerCase()) {
         throw new Error(`Preimage hash mismatch! Expected: ${swap.hashLock}, got: ${calculatedHash}`);
       }
 
-      console.log(`✅ Preimage verification passed, claiming HTLC...`);
+      this.log.info(`✅ Preimage verification passed, claiming HTLC...`);
 
       // Get proper gas configuration for claim transaction
       const feeData = await this.provider.getFeeData();
       
-      const gasOptions: Record<string, any> = {
-        gasLimit: this.config.gasLimit
-      };
+      const gasOptions: Record<string, any> = {};
       
       // Force legacy gas pricing for cost efficiency  
       if (feeData.gasPrice) {
@@ -910,15 +1533,29 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         gasOptions.gasPrice = this.config.maxGasPrice;
       }
 
-      const tx = await this.htlcContract.claim(
-        swap.userHtlcContract,
-        preimage,
-        gasOptions
+      const receipt = await this.submitAction(
+        { swapId: swap.id, action: RelayerActionType.CLAIM, contractId: swap.userHtlcContract },
+        'claimUserHtlc',
+        async () => {
+          const gas = await this.gasLimitFor('claim', await this.htlcContract.claim.populateTransaction(
+            swap.userHtlcContract!,
+            preimage
+          ));
+          const tx = await this.nonceManager.send(nonce => this.htlcContract.claim(
+            swap.userHtlcContract!,
+            preimage,
+            { ...gasOptions, ...gas, nonce }
+          ));
+          this.log.info('📡 Claim transaction sent', { txHash: tx.hash });
+          return tx;
+        }
       );
-
-      console.log(`📡 Claim transaction sent: ${tx.hash}`);
-      const receipt = await tx.wait();
-      console.log(`✅ Claimed user tokens: ${receipt.hash}, status: ${receipt.status}`);
+      if (!receipt) {
+        this.log.info('✅ User HTLC was already claimed by an earlier run');
+        return;
+      }
+      this.log.info('✅ Claimed user tokens', { txHash: receipt.hash, receiptStatus: receipt.status });
+      await this.awaitFinality(receipt, 'claimUserHtlc');
 
       // Record the operation in pool_operations table
       await this.dao.query(
@@ -927,7 +1564,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
       );
 
     } catch (error) {
-      console.error(`❌ Claim failed for swap ${swap.id}:`, error);
+      this.log.error('❌ Claim failed', { error: (error as Error).message });
       
       // Retry logic for transient errors
       const errorMessage = (error as Error).message;
@@ -938,7 +1575,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
         errorMessage.includes('network');
       
       if (isRetryableError && retryCount < maxRetries) {
-        console.log(`🔄 Retrying claim for swap ${swap.id} (attempt ${retryCount + 1}/${maxRetries})...`);
+        this.log.info(`🔄 Retrying claim (attempt ${retryCount + 1}/${maxRetries})...`);
         
         // Wait before retry (exponential backoff)
         const delay = Math.pow(2, retryCount) * 1000; // 1s, 2s, 4s
@@ -974,17 +1611,23 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    */
   private async handleSwapError(swapId: string, error: Error): Promise<void> {
     // Log error and potentially retry based on error type
-    console.error(`Swap ${swapId} error:`, error.message);
+    this.log.error('Swap error', { correlationId: swapId, swapId, error: error.message });
     
     // Update swap status based on error type
-    if (error.message.includes('liquidity')) {
+    if (this.config.dryRun) {
+      // Dry-run never mutates swap state
+    } else if (error.message.includes('liquidity')) {
       // Mark as expired if liquidity issues
       await this.dao.updateSwapRequest(swapId, { status: SwapStatus.EXPIRED });
     } else {
       // For other errors, leave as pending for retry
-      console.log(`Leaving swap ${swapId} as pending for retry`);
+      this.log.info('Leaving swap as pending for retry', { correlationId: swapId, swapId });
     }
 
+    // Nothing was broadcast in dry-run, so a failed simulation must not page anyone
+    if (!this.config.dryRun) {
+      await this.alerts?.recordFailure(this.chainName, 'submissions', error);
+    }
     this.emit('swapError', swapId, error);
   }
 
@@ -992,6 +1635,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    * Get resolver status and metrics
    */
   async getStatus(): Promise<{
+    chain: string;
     processing: boolean;
     queueSize: number;
     metrics: {
@@ -1014,6 +1658,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     `);
 
     return {
+      chain: this.chainName,
       processing: this.isProcessing,
       queueSize: pendingSwaps.length,
       metrics: {
@@ -1024,6 +1669,51 @@ private async processSwap(swap: SwapRequest): Promise<void> {
     };
   }
 
+  /**
+   * Probe RPC connectivity, processing loop liveness and key availability
+   */
+  async getHealth(timeoutMs: number = 5000): Promise<ResolverHealth> {
+    const rpc = await probe(async () => {
+      const blockNumber = await this.provider.getBlockNumber();
+      return { blockNumber };
+    }, timeoutMs);
+
+    // The loop is considered stalled once it misses three consecutive ticks
+    const maxTickAge = this.config.processingInterval * 3;
+    const tickAge = this.lastTickAt ? Date.now() - this.lastTickAt : undefined;
+    // A draining resolver has stopped its loop on purpose and must not be restarted for it
+    const processingLoop = this.draining ? {
+      ok: true,
+      details: { running: false, draining: true, inFlightTxs: this.nonceManager.inFlight }
+    } : {
+      ok: !!this.processingTimer && (tickAge === undefined || tickAge <= maxTickAge),
+      details: {
+        running: !!this.processingTimer,
+        lastTickAt: this.lastTickAt ? new Date(this.lastTickAt).toISOString() : null,
+        processing: this.isProcessing
+      },
+      ...(this.processingTimer ? {} : { error: 'Processing loop is not running' }),
+      ...(tickAge !== undefined && tickAge > maxTickAge
+        ? { error: `No processing tick for ${tickAge}ms (max ${maxTickAge}ms)` }
+        : {})
+    };
+
+    const key = await probe(async () => {
+      if (!this.poolWallet.signingKey) {
+        throw new Error('Pool wallet signing key unavailable');
+      }
+      return { address: this.poolWallet.address };
+    }, timeoutMs);
+
+    return {
+      chain: this.chainName,
+      draining: this.draining,
+      rpc,
+      processingLoop,
+      key
+    };
+  }
+
   /**
    * Manual retry of a specific swap
    */
@@ -1045,7 +1735,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    */
   pause(): void {
     this.isProcessing = false;
-    console.log('Resolver processing paused');
+    this.log.info('Resolver processing paused');
     this.emit('paused');
   }
 
@@ -1053,7 +1743,7 @@ private async processSwap(swap: SwapRequest): Promise<void> {
    * Resume processing
    */
   resume(): void {
-    console.log('Resolver processing resumed');
+    this.log.info('Resolver processing resumed');
     this.emit('resumed');
   }
 }
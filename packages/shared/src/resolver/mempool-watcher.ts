@@ -0,0 +1,203 @@
+import { EventEmitter } from 'events';
+import { createHash } from 'crypto';
+import { ethers } from 'ethers';
+import { FusionDAO } from '../database';
+import { CosmosBatchSubmitter } from '../clients/cosmos-batch';
+import { CosmosHTLCClient } from '../clients/cosmos';
+import { createLogger } from '../utils/logger';
+import { zeroize } from '../database/secrets';
+import type { SubscriptionSource } from './health';
+
+const logger = createLogger('MempoolWatcher');
+
+const CLAIM_ABI = ['function claim(bytes32 contractId, bytes32 preimage) external'];
+
+export interface MempoolWatcherOptions {
+  /** Websocket endpoint that serves eth_subscribe('newPendingTransactions') */
+  wsUrl: string;
+  htlcAddress: string;
+  chainName: string;
+}
+
+export interface DiscoveredPreimage {
+  chain: string;
+  contractId: string;
+  preimage: string;
+  txHash: string;
+  seenAt: number;
+}
+
+/**
+ * Watches the pending-transaction stream for claim() calls on our HTLC contract.
+ * A claim carries the preimage in its calldata, so it is known before inclusion.
+ *
+ * Emits 'preimage' as soon as a pending claim is decoded, then either 'claimConfirmed'
+ * once the same transaction is mined successfully or 'claimDropped' if it is not.
+ */
+export class MempoolWatcher extends EventEmitter implements SubscriptionSource {
+  private provider?: ethers.WebSocketProvider;
+  private heartbeatAt?: number;
+  private iface = new ethers.Interface(CLAIM_ABI);
+  private claimSelector = this.iface.getFunction('claim')!.selector;
+  private seen = new Set<string>();
+
+  constructor(private options: MempoolWatcherOptions) {
+    super();
+  }
+
+  get subscriptionName(): string {
+    return `mempool:${this.options.chainName}`;
+  }
+
+  get lastHeartbeatAt(): number | undefined {
+    return this.heartbeatAt;
+  }
+
+  async start(): Promise<void> {
+    if (this.provider) return;
+
+    this.provider = new ethers.WebSocketProvider(this.options.wsUrl);
+    this.heartbeatAt = Date.now();
+    // Pending transactions can be sparse on testnets; new heads prove the socket is still alive
+    await this.provider.on('block', () => {
+      this.heartbeatAt = Date.now();
+    });
+    await this.provider.on('pending', (txHash: string) => {
+      this.heartbeatAt = Date.now();
+      this.inspect(txHash).catch(error => {
+        logger.debug('Failed to inspect pending transaction', { txHash, error: (error as Error).message });
+      });
+    });
+    logger.info(`Watching ${this.options.chainName} mempool for HTLC claims`, { chain: this.options.chainName });
+  }
+
+  async stop(): Promise<void> {
+    if (!this.provider) return;
+    await this.provider.destroy();
+    this.provider = undefined;
+    this.heartbeatAt = undefined;
+    this.seen.clear();
+  }
+
+  private async inspect(txHash: string): Promise<void> {
+    if (!this.provider || this.seen.has(txHash)) return;
+
+    const tx = await this.provider.getTransaction(txHash);
+    if (!tx || !tx.to || tx.to.toLowerCase() !== this.options.htlcAddress.toLowerCase()) return;
+    if (!tx.data.startsWith(this.claimSelector)) return;
+
+    this.seen.add(txHash);
+    const [contractId, preimage] = this.iface.decodeFunctionData('claim', tx.data);
+
+    const discovered: DiscoveredPreimage = {
+      chain: this.options.chainName,
+      contractId,
+      preimage,
+      txHash,
+      seenAt: Date.now()
+    };
+    logger.info('🔓 Preimage seen in mempool', { chain: discovered.chain, contractId, txHash });
+    this.emit('preimage', discovered);
+
+    try {
+      const receipt = await tx.wait();
+      this.emit(receipt?.status === 1 ? 'claimConfirmed' : 'claimDropped', discovered);
+    } catch (error) {
+      logger.warn('Pending claim did not confirm', { txHash, error: (error as Error).message });
+      this.emit('claimDropped', discovered);
+    } finally {
+      this.seen.delete(txHash);
+    }
+  }
+}
+
+interface PreparedClaim {
+  chain: string;
+  contractAddress: string;
+  preimage: string;
+  swapId: string;
+}
+
+/**
+ * Turns preimages discovered on an EVM chain into claims on the counterpart Cosmos
+ * HTLC. The claim is resolved and checked while the EVM claim is still pending, then
+ * submitted immediately when it confirms.
+ */
+export class EarlyClaimRelay {
+  private prepared = new Map<string, Promise<PreparedClaim | null>>();
+
+  constructor(
+    private dao: FusionDAO,
+    /** Cosmos chain name -> signing client and batch submitter for that chain */
+    private cosmos: Map<string, { client: CosmosHTLCClient; submitter: CosmosBatchSubmitter }>
+  ) {}
+
+  attach(watcher: MempoolWatcher): void {
+    watcher.on('preimage', (discovered: DiscoveredPreimage) => {
+      this.prepared.set(discovered.txHash, this.prepare(discovered).catch(error => {
+        logger.warn('Could not prepare early Cosmos claim', { txHash: discovered.txHash, error: (error as Error).message });
+        return null;
+      }));
+    });
+    watcher.on('claimDropped', (discovered: DiscoveredPreimage) => {
+      this.prepared.delete(discovered.txHash);
+    });
+    watcher.on('claimConfirmed', (discovered: DiscoveredPreimage) => {
+      this.submit(discovered).catch(error => {
+        logger.error('Early Cosmos claim failed', { txHash: discovered.txHash, error: (error as Error).message });
+      });
+    });
+  }
+
+  private async prepare(discovered: DiscoveredPreimage): Promise<PreparedClaim | null> {
+    const swap = await this.dao.getSwapByHtlcContract(discovered.contractId);
+    if (!swap) return null;
+
+    // SimpleHTLC hashes the raw 32 bytes with sha256; a mismatching preimage would claim nothing
+    const preimageBytes = Buffer.from(discovered.preimage.slice(2), 'hex');
+    const hash = '0x' + createHash('sha256').update(preimageBytes).digest('hex');
+    zeroize(preimageBytes);
+    if (hash.toLowerCase() !== swap.hashLock.toLowerCase()) {
+      logger.warn('Mempool preimage does not match swap hash lock', { correlationId: swap.id, swapId: swap.id });
+      return null;
+    }
+
+    // The counterpart is whichever leg of the swap is not the HTLC being claimed
+    const claimedIsPool = swap.poolHtlcContract?.toLowerCase() === discovered.contractId.toLowerCase();
+    const contractAddress = claimedIsPool ? swap.userHtlcContract : swap.poolHtlcContract;
+    const chain = (claimedIsPool ? swap.sourceToken : swap.targetToken).split(':')[0];
+
+    const cosmos = this.cosmos.get(chain);
+    if (!cosmos || !contractAddress) return null;
+
+    if (!await cosmos.client.isClaimable(contractAddress)) {
+      logger.info('Counterpart HTLC is not claimable', { correlationId: swap.id, chain, contractAddress });
+      return null;
+    }
+
+    logger.info('Prepared early Cosmos claim', { correlationId: swap.id, swapId: swap.id, chain, contractAddress });
+    return { chain, contractAddress, preimage: discovered.preimage, swapId: swap.id };
+  }
+
+  private async submit(discovered: DiscoveredPreimage): Promise<void> {
+    const pending = this.prepared.get(discovered.txHash) ?? this.prepare(discovered);
+    this.prepared.delete(discovered.txHash);
+
+    const claim = await pending;
+    if (!claim) return;
+
+    const { submitter } = this.cosmos.get(claim.chain)!;
+    const result = submitter.claim(claim.contractAddress, claim.preimage);
+    await submitter.flush();
+    const transactionHash = await result;
+
+    logger.info('⚡ Submitted early Cosmos claim', {
+      correlationId: claim.swapId,
+      swapId: claim.swapId,
+      chain: claim.chain,
+      evmTxHash: discovered.txHash,
+      transactionHash,
+      latencyMs: Date.now() - discovered.seenAt
+    });
+  }
+}
@@ -0,0 +1,282 @@
+import { ethers } from 'ethers';
+import type { SigningCosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import type { EncodeObject } from '@cosmjs/proto-signing';
+import type { IndexedTx, StdFee } from '@cosmjs/stargate';
+import { TxRaw } from 'cosmjs-types/cosmos/tx/v1beta1/tx';
+import { createLogger } from '../utils/logger';
+
+const logger = createLogger('NonceManager');
+
+export interface AccountManagerOptions {
+  /** Maximum number of broadcast-but-unconfirmed transactions per account */
+  maxInFlight?: number;
+  /** Give up waiting for a confirmation after this long and resync from chain (ms) */
+  confirmationTimeoutMs?: number;
+}
+
+/**
+ * Counting semaphore bounding concurrent in-flight transactions
+ */
+class InFlightWindow {
+  private active = 0;
+  private waiters: (() => void)[] = [];
+
+  constructor(private size: number) {}
+
+  get inFlight(): number {
+    return this.active;
+  }
+
+  async acquire(): Promise<void> {
+    if (this.active < this.size) {
+      this.active++;
+      return;
+    }
+    await new Promise<void>(resolve => this.waiters.push(resolve));
+  }
+
+  release(): void {
+    const next = this.waiters.shift();
+    if (next) {
+      next(); // hand the slot straight to the next waiter
+    } else {
+      this.active = Math.max(0, this.active - 1);
+    }
+  }
+}
+
+/**
+ * Serialize async critical sections without blocking the event loop
+ */
+export class Mutex {
+  private tail: Promise<void> = Promise.resolve();
+
+  async run<T>(fn: () => Promise<T>): Promise<T> {
+    const previous = this.tail;
+    let release!: () => void;
+    this.tail = new Promise<void>(resolve => { release = resolve; });
+    await previous;
+    try {
+      return await fn();
+    } finally {
+      release();
+    }
+  }
+}
+
+function withTimeout<T>(promise: Promise<T>, timeoutMs: number): Promise<T | undefined> {
+  let timer: NodeJS.Timeout;
+  const timeout = new Promise<undefined>(resolve => {
+    timer = setTimeout(() => resolve(undefined), timeoutMs);
+  });
+  return Promise.race([promise, timeout]).finally(() => clearTimeout(timer));
+}
+
+const EVM_NONCE_ERRORS = ['nonce too low', 'nonce has already been used', 'NONCE_EXPIRED', 'replacement transaction underpriced', 'already known'];
+
+/**
+ * Hands out EVM nonces locally so several transactions from the same account can be
+ * in flight at once. Nonces for transactions that were never broadcast are reused first
+ * so gaps are filled; anything that looks like a nonce conflict triggers a resync.
+ */
+export class EvmNonceManager {
+  private nextNonce?: number;
+  private unusedNonces: number[] = [];
+  private window: InFlightWindow;
+  private mutex = new Mutex();
+  private confirmationTimeoutMs: number;
+
+  constructor(private signer: ethers.Signer, options: AccountManagerOptions = {}) {
+    this.window = new InFlightWindow(options.maxInFlight ?? 4);
+    this.confirmationTimeoutMs = options.confirmationTimeoutMs ?? 5 * 60 * 1000;
+  }
+
+  get inFlight(): number {
+    return this.window.inFlight;
+  }
+
+  /**
+   * Send a transaction built with a managed nonce. The window slot is held until
+   * the transaction confirms (or the confirmation timeout passes).
+   */
+  async send<T extends ethers.TransactionResponse>(build: (nonce: number) => Promise<T>): Promise<T> {
+    await this.window.acquire();
+
+    let nonce: number;
+    try {
+      nonce = await this.takeNonce();
+    } catch (error) {
+      this.window.release();
+      throw error;
+    }
+
+    let tx: T;
+    try {
+      tx = await build(nonce);
+    } catch (error) {
+      await this.returnNonce(nonce, error as Error);
+      this.window.release();
+      throw error;
+    }
+
+    withTimeout(tx.wait().catch(() => null), this.confirmationTimeoutMs)
+      .then(receipt => {
+        if (receipt === undefined) {
+          logger.warn('Transaction not confirmed in time, resyncing nonce', { txHash: tx.hash, nonce });
+          return this.resync();
+        }
+      })
+      .finally(() => this.window.release());
+
+    return tx;
+  }
+
+  /**
+   * Drop local state and reload the next nonce from the chain's pending count
+   */
+  async resync(): Promise<void> {
+    await this.mutex.run(async () => {
+      this.nextNonce = await this.signer.getNonce('pending');
+      this.unusedNonces = [];
+    });
+  }
+
+  private takeNonce(): Promise<number> {
+    return this.mutex.run(async () => {
+      // Nothing of ours is pending: pick up any transactions sent from this key elsewhere
+      if (this.nextNonce === undefined || (this.window.inFlight <= 1 && this.unusedNonces.length === 0)) {
+        const chainNonce = await this.signer.getNonce('pending');
+        this.nextNonce = Math.max(chainNonce, this.nextNonce ?? 0);
+      }
+
+      if (this.unusedNonces.length > 0) {
+        return this.unusedNonces.shift()!;
+      }
+      return this.nextNonce++;
+    });
+  }
+
+  private async returnNonce(nonce: number, error: Error): Promise<void> {
+    if (EVM_NONCE_ERRORS.some(pattern => error.message.includes(pattern))) {
+      logger.warn('Nonce conflict, resyncing from chain', { nonce, error: error.message });
+      await this.resync();
+      return;
+    }
+
+    // Never broadcast: reuse it for the next transaction so later nonces are not stuck behind a gap
+    await this.mutex.run(async () => {
+      this.unusedNonces.push(nonce);
+      this.unusedNonces.sort((a, b) => a - b);
+    });
+  }
+}
+
+export interface CosmosBroadcast {
+  transactionHash: string;
+  sequence: number;
+  /** Resolves with the included transaction; rejects if it failed on-chain */
+  confirmed: Promise<IndexedTx>;
+}
+
+/**
+ * Tracks the Cosmos account sequence locally and signs with explicit signer data,
+ * so several transactions can sit in the mempool at once. Sequence mismatch errors
+ * carry the expected value, which is used to recover immediately.
+ */
+export class CosmosSequenceManager {
+  private accountNumber?: number;
+  private nextSequence?: number;
+  private window: InFlightWindow;
+  private mutex = new Mutex();
+  private confirmationTimeoutMs: number;
+
+  constructor(
+    private client: SigningCosmWasmClient,
+    private address: string,
+    private chainId: string,
+    options: AccountManagerOptions = {}
+  ) {
+    this.window = new InFlightWindow(options.maxInFlight ?? 4);
+    this.confirmationTimeoutMs = options.confirmationTimeoutMs ?? 2 * 60 * 1000;
+  }
+
+  get inFlight(): number {
+    return this.window.inFlight;
+  }
+
+  async signAndBroadcast(messages: EncodeObject[], fee: StdFee, memo: string = ''): Promise<CosmosBroadcast> {
+    await this.window.acquire();
+
+    try {
+      const { transactionHash, sequence } = await this.mutex.run(async () => {
+        if (this.nextSequence === undefined || this.accountNumber === undefined || this.window.inFlight <= 1) {
+          await this.loadSequence();
+        }
+
+        const sequence = this.nextSequence!;
+        const txRaw = await this.client.sign(this.address, messages, fee, memo, {
+          accountNumber: this.accountNumber!,
+          sequence,
+          chainId: this.chainId
+        });
+
+        try {
+          const hash = await this.client.broadcastTxSync(TxRaw.encode(txRaw).finish());
+          this.nextSequence = sequence + 1;
+          return { transactionHash: hash, sequence };
+        } catch (error) {
+          this.recoverFromError(error as Error);
+          throw error;
+        }
+      });
+
+      const confirmed = this.waitForInclusion(transactionHash).finally(() => this.window.release());
+      confirmed.catch(() => undefined);
+      return { transactionHash, sequence, confirmed };
+    } catch (error) {
+      this.window.release();
+      throw error;
+    }
+  }
+
+  async resync(): Promise<void> {
+    await this.mutex.run(() => this.loadSequence());
+  }
+
+  private async loadSequence(): Promise<void> {
+    const { accountNumber, sequence } = await this.client.getSequence(this.address);
+    this.accountNumber = accountNumber;
+    this.nextSequence = sequence;
+  }
+
+  private recoverFromError(error: Error): void {
+    const mismatch = error.message.match(/account sequence mismatch, expected (\d+)/);
+    if (mismatch) {
+      logger.warn('Sequence mismatch, adopting chain value', { expected: mismatch[1] });
+      this.nextSequence = parseInt(mismatch[1]);
+      return;
+    }
+    // Unknown rejection: force a reload before the next transaction
+    this.nextSequence = undefined;
+  }
+
+  private async waitForInclusion(transactionHash: string): Promise<IndexedTx> {
+    const deadline = Date.now() + this.confirmationTimeoutMs;
+
+    while (Date.now() < deadline) {
+      const tx = await this.client.getTx(transactionHash);
+      if (tx) {
+        if (tx.code !== 0) {
+          // A failed DeliverTx still consumes the sequence, but nothing after it is affected
+          throw new Error(`Transaction ${transactionHash} failed with code ${tx.code}: ${tx.rawLog}`);
+        }
+        return tx;
+      }
+      await new Promise(resolve => setTimeout(resolve, 1000));
+    }
+
+    // Dropped from the mempool: our local sequence is now ahead of the chain
+    this.nextSequence = undefined;
+    throw new Error(`Transaction ${transactionHash} was not included within ${this.confirmationTimeoutMs}ms`);
+  }
+}
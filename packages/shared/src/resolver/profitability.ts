@@ -0,0 +1,208 @@
+import { ethers } from 'ethers';
+import { FusionDAO } from '../database';
+import { evmChains } from '../chains';
+import { estimateL1DataFee } from './evm-chain';
+
+export interface ProfitabilityConfig {
+  /** Minimum net margin, in basis points of the delivered value, required to take a swap */
+  minMarginBps: number;
+  /** Protocol fee charged on the input when the token has no fee of its own (bps) */
+  protocolFeeBps: number;
+  /** Annualised cost of holding inventory locked in an HTLC (bps per year) */
+  inventoryCostBpsPerYear: number;
+  /** Oracle prices older than this are treated as missing (seconds) */
+  maxPriceAgeSeconds: number;
+  /** Oracle prices below this confidence are treated as missing (0-1) */
+  minPriceConfidence: number;
+  /** Gas units assumed per action when the caller does not supply an estimate */
+  gasUnits: { fund: number; approve: number; claim: number };
+}
+
+export const DEFAULT_PROFITABILITY_CONFIG: ProfitabilityConfig = {
+  minMarginBps: 30,
+  protocolFeeBps: 0,
+  inventoryCostBpsPerYear: 500,
+  maxPriceAgeSeconds: 300,
+  minPriceConfidence: 0.9,
+  gasUnits: { fund: 200000, approve: 50000, claim: 100000 }
+};
+
+export interface TokenPrice {
+  priceUsd: number;
+  confidence: number;
+  lastUpdated: Date;
+}
+
+/**
+ * Price lookup keyed by token id ("chain:SYMBOL"). Native gas tokens use "chain:NATIVE".
+ */
+export interface PriceSource {
+  getPrice(tokenId: string): Promise<TokenPrice | null>;
+}
+
+export class DatabasePriceSource implements PriceSource {
+  constructor(private dao: FusionDAO) {}
+
+  async getPrice(tokenId: string): Promise<TokenPrice | null> {
+    return this.dao.getLatestPriceFeed(tokenId);
+  }
+}
+
+export interface ProfitabilityQuote {
+  sourceToken: string;
+  sourceAmount: string; // base units
+  sourceDecimals: number;
+  targetToken: string;
+  targetAmount: string; // base units
+  targetDecimals: number;
+  /** Token-specific protocol fee in percent, as stored in supported_tokens */
+  feePercentage?: number;
+  /** Whether the target leg needs an ERC20 approval before funding */
+  needsApproval?: boolean;
+  /** Seconds the target inventory is expected to stay locked */
+  holdSeconds: number;
+}
+
+export interface PnLBreakdown {
+  inputUsd: number;
+  outputUsd: number;
+  quotedRate: number;
+  oracleRate: number;
+  gasUsd: { source: number; target: number };
+  protocolFeeUsd: number;
+  inventoryCostUsd: number;
+  netPnlUsd: number;
+  marginBps: number;
+  profitable: boolean;
+  reason?: string;
+}
+
+const SECONDS_PER_YEAR = 365 * 24 * 60 * 60;
+
+// Stand-in for fund() calldata (selector + 6 words) when pricing the L1 data fee on rollups
+const SAMPLE_CALLDATA = '0x' + 'ff'.repeat(196);
+
+/**
+ * Computes the expected PnL of filling a swap before the resolver commits inventory to it:
+ *
+ *   net = value received (source leg) - value delivered (target leg)
+ *         - gas on both chains - protocol fee - cost of locking inventory until settlement
+ */
+export class ProfitabilityEngine {
+  private config: ProfitabilityConfig;
+  private providers: Map<string, ethers.JsonRpcProvider> = new Map();
+
+  constructor(private prices: PriceSource, config: Partial<ProfitabilityConfig> = {}) {
+    this.config = { ...DEFAULT_PROFITABILITY_CONFIG, ...config };
+  }
+
+  async evaluate(quote: ProfitabilityQuote): Promise<PnLBreakdown> {
+    const sourceChain = quote.sourceToken.split(':')[0];
+    const targetChain = quote.targetToken.split(':')[0];
+
+    const [sourcePrice, targetPrice, sourceNative, targetNative] = await Promise.all([
+      this.freshPrice(quote.sourceToken),
+      this.freshPrice(quote.targetToken),
+      this.freshPrice(`${sourceChain}:NATIVE`),
+      this.freshPrice(`${targetChain}:NATIVE`)
+    ]);
+
+    const inputAmount = parseFloat(ethers.formatUnits(quote.sourceAmount, quote.sourceDecimals));
+    const outputAmount = parseFloat(ethers.formatUnits(quote.targetAmount, quote.targetDecimals));
+    const quotedRate = inputAmount > 0 ? outputAmount / inputAmount : 0;
+
+    const missing = [
+      [quote.sourceToken, sourcePrice],
+      [quote.targetToken, targetPrice],
+      [`${sourceChain}:NATIVE`, sourceNative],
+      [`${targetChain}:NATIVE`, targetNative]
+    ].filter(([, price]) => !price).map(([token]) => token);
+
+    if (missing.length > 0) {
+      return this.reject(quotedRate, `No fresh oracle price for ${missing.join(', ')}`);
+    }
+
+    const inputUsd = inputAmount * sourcePrice!.priceUsd;
+    const outputUsd = outputAmount * targetPrice!.priceUsd;
+    const oracleRate = sourcePrice!.priceUsd / targetPrice!.priceUsd;
+
+    const targetGasUnits = this.config.gasUnits.fund + (quote.needsApproval ? this.config.gasUnits.approve : 0);
+    const [sourceGasUsd, targetGasUsd] = await Promise.all([
+      this.gasCostUsd(sourceChain, this.config.gasUnits.claim, sourceNative!.priceUsd),
+      this.gasCostUsd(targetChain, targetGasUnits, targetNative!.priceUsd)
+    ]);
+
+    const feeBps = quote.feePercentage !== undefined ? quote.feePercentage * 100 : this.config.protocolFeeBps;
+    const protocolFeeUsd = inputUsd * feeBps / 10000;
+    const inventoryCostUsd = outputUsd * (this.config.inventoryCostBpsPerYear / 10000) *
+      (Math.max(quote.holdSeconds, 0) / SECONDS_PER_YEAR);
+
+    const netPnlUsd = inputUsd - outputUsd - sourceGasUsd - targetGasUsd - protocolFeeUsd - inventoryCostUsd;
+    const marginBps = outputUsd > 0 ? (netPnlUsd / outputUsd) * 10000 : 0;
+    const profitable = marginBps >= this.config.minMarginBps;
+
+    return {
+      inputUsd,
+      outputUsd,
+      quotedRate,
+      oracleRate,
+      gasUsd: { source: sourceGasUsd, target: targetGasUsd },
+      protocolFeeUsd,
+      inventoryCostUsd,
+      netPnlUsd,
+      marginBps,
+      profitable,
+      ...(profitable
+        ? {}
+        : { reason: `Margin ${marginBps.toFixed(1)} bps is below the ${this.config.minMarginBps} bps minimum` })
+    };
+  }
+
+  private async freshPrice(tokenId: string): Promise<TokenPrice | null> {
+    const price = await this.prices.getPrice(tokenId);
+    if (!price) return null;
+
+    const ageSeconds = (Date.now() - new Date(price.lastUpdated).getTime()) / 1000;
+    if (ageSeconds > this.config.maxPriceAgeSeconds || price.confidence < this.config.minPriceConfidence) {
+      return null;
+    }
+    return price;
+  }
+
+  private async gasCostUsd(chain: string, gasUnits: number, nativePriceUsd: number): Promise<number> {
+    const chainConfig = evmChains[chain];
+    if (!chainConfig) {
+      throw new Error(`No RPC configured to price gas on ${chain}`);
+    }
+
+    let provider = this.providers.get(chain);
+    if (!provider) {
+      provider = new ethers.JsonRpcProvider(chainConfig.rpcUrl);
+      this.providers.set(chain, provider);
+    }
+
+    const [feeData, l1DataFee] = await Promise.all([
+      provider.getFeeData(),
+      estimateL1DataFee(provider, chainConfig, { to: chainConfig.htlcAddress || ethers.ZeroAddress, data: SAMPLE_CALLDATA })
+    ]);
+    const gasPrice = feeData.maxFeePerGas ?? feeData.gasPrice ?? 0n;
+    const costNative = parseFloat(ethers.formatEther(gasPrice * BigInt(gasUnits) + l1DataFee));
+    return costNative * nativePriceUsd;
+  }
+
+  private reject(quotedRate: number, reason: string): PnLBreakdown {
+    return {
+      inputUsd: 0,
+      outputUsd: 0,
+      quotedRate,
+      oracleRate: 0,
+      gasUsd: { source: 0, target: 0 },
+      protocolFeeUsd: 0,
+      inventoryCostUsd: 0,
+      netPnlUsd: 0,
+      marginBps: 0,
+      profitable: false,
+      reason
+    };
+  }
+}
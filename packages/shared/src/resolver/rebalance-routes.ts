@@ -0,0 +1,245 @@
+import { ethers } from 'ethers';
+import axios from 'axios';
+import { fromBech32, toBech32 } from '@cosmjs/encoding';
+import type { MsgTransferEncodeObject } from '@cosmjs/stargate';
+import { allChains, isCosmosChain, isEvmChain } from '../chains';
+import type { EvmChainConfig } from '../chains';
+import type { CosmosSigningAccount } from '../clients/cosmos';
+import { createLogger } from '../utils/logger';
+import type { ChainAdapter } from './adapters';
+import type { RebalanceRoute, RebalanceTransfer, RouteSubmission } from './rebalancer';
+
+const logger = createLogger('RebalanceRoutes');
+
+/**
+ * Resolve once the destination balance has grown by at least `amount` over `before`
+ */
+async function waitForArrival(
+  adapter: ChainAdapter,
+  token: string | undefined,
+  before: bigint,
+  amount: bigint,
+  timeoutMs: number,
+  pollIntervalMs = 15000
+): Promise<void> {
+  const deadline = Date.now() + timeoutMs;
+  while (Date.now() < deadline) {
+    await new Promise(resolve => setTimeout(resolve, pollIntervalMs));
+    if (await adapter.getBalance(token) - before >= amount) return;
+  }
+  throw new Error(`Funds did not arrive on ${adapter.chain} within ${Math.round(timeoutMs / 1000)}s`);
+}
+
+function tokenArg(adapter: ChainAdapter, token: string): string | undefined {
+  return adapter.config.nativeDenom === token || token === ethers.ZeroAddress ? undefined : token;
+}
+
+export interface IbcChannel {
+  from: string;
+  to: string;
+  /** Transfer channel on the source chain, e.g. "channel-0" */
+  channel: string;
+}
+
+export interface IbcRouteOptions {
+  channels: IbcChannel[];
+  /** Packet timeout (default 10 minutes) */
+  timeoutSeconds?: number;
+  /** How long to wait for the funds to show up before giving up (default 30 minutes) */
+  arrivalTimeoutMs?: number;
+}
+
+/**
+ * ICS-20 transfers between Cosmos chains. The destination token in the corridor
+ * must be the voucher denom the source asset arrives as (ibc/...), since that is
+ * what the destination balance is read in.
+ */
+export class IbcTransferRoute implements RebalanceRoute {
+  readonly name = 'ibc';
+  private adapters = new Map<string, ChainAdapter>();
+
+  /**
+   * `signingAccount` returns the account transfers are sent from on a chain; it should
+   * be the one the HTLC flows use, so both share a sequence manager
+   */
+  constructor(
+    adapters: ChainAdapter[],
+    private signingAccount: (chain: string) => Promise<CosmosSigningAccount>,
+    private options: IbcRouteOptions
+  ) {
+    adapters.forEach(adapter => this.adapters.set(adapter.chain, adapter));
+  }
+
+  supports(transfer: RebalanceTransfer): boolean {
+    return !!this.channel(transfer.from.chain, transfer.to.chain);
+  }
+
+  async transfer(transfer: RebalanceTransfer): Promise<RouteSubmission> {
+    const source = allChains[transfer.from.chain];
+    const destination = allChains[transfer.to.chain];
+    const target = this.adapters.get(transfer.to.chain);
+    if (!source || !destination || !isCosmosChain(source) || !isCosmosChain(destination) || !target) {
+      throw new Error(`IBC route needs Cosmos chains on both ends, got ${transfer.from.chain} -> ${transfer.to.chain}`);
+    }
+
+    const { client, address } = await this.signingAccount(transfer.from.chain);
+    const recipient = toBech32(destination.addressPrefix, fromBech32(address).data);
+    const before = await target.getBalance(tokenArg(target, transfer.to.token));
+    const timeoutSeconds = this.options.timeoutSeconds ?? 600;
+
+    const message: MsgTransferEncodeObject = {
+      typeUrl: '/ibc.applications.transfer.v1.MsgTransfer',
+      value: {
+        sourcePort: 'transfer',
+        sourceChannel: this.channel(transfer.from.chain, transfer.to.chain)!,
+        sender: address,
+        receiver: recipient,
+        token: { denom: transfer.from.token, amount: transfer.amount.toString() },
+        timeoutTimestamp: BigInt(Date.now() + timeoutSeconds * 1000) * 1_000_000n,
+        memo: ''
+      }
+    };
+    // Rejects when the transfer fails on-chain
+    const result = await client.broadcast(address, [message], 'auto', `rebalance ${transfer.corridor}`);
+
+    return {
+      txHash: result.transactionHash,
+      completed: waitForArrival(
+        target,
+        tokenArg(target, transfer.to.token),
+        before,
+        transfer.amount,
+        this.options.arrivalTimeoutMs ?? 30 * 60 * 1000
+      )
+    };
+  }
+
+  private channel(from: string, to: string): string | undefined {
+    return this.options.channels.find(entry => entry.from === from && entry.to === to)?.channel;
+  }
+}
+
+export interface CctpDomain {
+  /** Circle domain ID of the chain */
+  domain: number;
+  tokenMessenger: string;
+  messageTransmitter: string;
+}
+
+export interface CctpRouteOptions {
+  /** CCTP contracts per chain key; chains without an entry are not served */
+  domains: Record<string, CctpDomain>;
+  /** Circle attestation service (default: the sandbox API) */
+  attestationUrl?: string;
+  attestationTimeoutMs?: number;
+}
+
+const TOKEN_MESSENGER_ABI = [
+  'function depositForBurn(uint256 amount, uint32 destinationDomain, bytes32 mintRecipient, address burnToken) external returns (uint64)'
+];
+
+const MESSAGE_TRANSMITTER_ABI = [
+  'event MessageSent(bytes message)',
+  'function receiveMessage(bytes message, bytes attestation) external returns (bool)'
+];
+
+const ERC20_ABI = [
+  'function approve(address spender, uint256 amount) external returns (bool)',
+  'function allowance(address owner, address spender) view returns (uint256)'
+];
+
+/**
+ * Native USDC between EVM chains via Circle's CCTP: burn on the source, wait for
+ * Circle to attest the burn message, then mint on the destination ourselves so
+ * there is no dependency on a third-party relayer.
+ */
+export class CctpRoute implements RebalanceRoute {
+  readonly name = 'cctp';
+
+  constructor(private privateKey: string, private options: CctpRouteOptions) {}
+
+  supports(transfer: RebalanceTransfer): boolean {
+    const from = this.options.domains[transfer.from.chain];
+    const to = this.options.domains[transfer.to.chain];
+    return !!from && !!to && transfer.from.asset.split(':')[1] === 'USDC' && transfer.to.asset.split(':')[1] === 'USDC';
+  }
+
+  async transfer(transfer: RebalanceTransfer): Promise<RouteSubmission> {
+    const source = this.wallet(transfer.from.chain);
+    const destination = this.wallet(transfer.to.chain);
+    const from = this.options.domains[transfer.from.chain];
+    const to = this.options.domains[transfer.to.chain];
+
+    const usdc = new ethers.Contract(transfer.from.token, ERC20_ABI, source);
+    const allowance: bigint = await usdc.allowance(source.address, from.tokenMessenger);
+    if (allowance < transfer.amount) {
+      await (await usdc.approve(from.tokenMessenger, transfer.amount)).wait();
+    }
+
+    const messenger = new ethers.Contract(from.tokenMessenger, TOKEN_MESSENGER_ABI, source);
+    const tx = await messenger.depositForBurn(
+      transfer.amount,
+      to.domain,
+      ethers.zeroPadValue(destination.address, 32),
+      transfer.from.token
+    );
+
+    return { txHash: tx.hash, completed: this.complete(tx, from, to, destination) };
+  }
+
+  private async complete(
+    tx: ethers.ContractTransactionResponse,
+    from: CctpDomain,
+    to: CctpDomain,
+    destination: ethers.Wallet
+  ): Promise<void> {
+    const receipt = await tx.wait();
+    if (!receipt || receipt.status !== 1) {
+      throw new Error(`Burn ${tx.hash} reverted`);
+    }
+
+    const transmitter = new ethers.Interface(MESSAGE_TRANSMITTER_ABI);
+    const sent = receipt.logs
+      .filter(log => log.address.toLowerCase() === from.messageTransmitter.toLowerCase())
+      .map(log => transmitter.parseLog(log))
+      .find(parsed => parsed?.name === 'MessageSent');
+    if (!sent) {
+      throw new Error(`No MessageSent event in burn ${tx.hash}`);
+    }
+
+    const message: string = sent.args.message;
+    const attestation = await this.attestation(ethers.keccak256(message));
+
+    const mint = new ethers.Contract(to.messageTransmitter, MESSAGE_TRANSMITTER_ABI, destination);
+    const minted = await (await mint.receiveMessage(message, attestation)).wait();
+    if (!minted || minted.status !== 1) {
+      throw new Error(`Mint for burn ${tx.hash} reverted`);
+    }
+    logger.info('CCTP mint confirmed', { burn: tx.hash, mint: minted.hash });
+  }
+
+  private async attestation(messageHash: string): Promise<string> {
+    const url = `${this.options.attestationUrl ?? 'https://iris-api-sandbox.circle.com'}/v1/attestations/${messageHash}`;
+    const deadline = Date.now() + (this.options.attestationTimeoutMs ?? 45 * 60 * 1000);
+
+    while (Date.now() < deadline) {
+      try {
+        const { data } = await axios.get(url, { timeout: 10000 });
+        if (data?.status === 'complete' && data.attestation) return data.attestation;
+      } catch (error) {
+        if (!axios.isAxiosError(error) || error.response?.status !== 404) throw error;
+        // 404 until Circle has observed the burn
+      }
+      await new Promise(resolve => setTimeout(resolve, 20000));
+    }
+    throw new Error(`No attestation for ${messageHash}`);
+  }
+
+  private wallet(chain: string): ethers.Wallet {
+    const config = allChains[chain];
+    if (!config || !isEvmChain(config)) {
+      throw new Error(`CCTP route needs an EVM chain, got ${chain}`);
+    }
+    return new ethers.Wallet(this.privateKey, new ethers.JsonRpcProvider((config as EvmChainConfig).rpcUrl));
+  }
+}
@@ -0,0 +1,242 @@
+import { EventEmitter } from 'events';
+import { ethers } from 'ethers';
+import { getToken } from '../tokens';
+import { createLogger } from '../utils/logger';
+import type { AlertManager } from './alerting';
+import type { ChainAdapter } from './adapters';
+
+const logger = createLogger('Rebalancer');
+
+/**
+ * One chain's share of a corridor's inventory
+ */
+export interface InventoryHolding {
+  /** Token id, "chain:SYMBOL" */
+  asset: string;
+  /** Fraction of the corridor's total this chain should hold */
+  targetShare: number;
+}
+
+/**
+ * A set of equivalent assets on several chains (e.g. USDC on Sepolia, Base and
+ * Arbitrum) whose combined inventory is kept spread according to target shares
+ */
+export interface Corridor {
+  name: string;
+  holdings: InventoryHolding[];
+  /** Rebalance when a chain's share deviates from its target by more than this (absolute fraction) */
+  maxDeviation: number;
+  /** Transfers smaller than this (whole tokens) are not worth the fees */
+  minTransfer: number;
+}
+
+export interface RebalanceTransfer {
+  corridor: string;
+  from: { chain: string; asset: string; token: string; decimals: number };
+  to: { chain: string; asset: string; token: string; decimals: number };
+  /** Base units on the source chain */
+  amount: bigint;
+}
+
+export interface RouteSubmission {
+  txHash: string;
+  /** Resolves once funds are usable on the destination chain */
+  completed: Promise<void>;
+}
+
+/**
+ * A way of moving inventory between two chains: IBC, CCTP, or any bridge.
+ * New bridges plug in by implementing this interface.
+ */
+export interface RebalanceRoute {
+  readonly name: string;
+  supports(transfer: RebalanceTransfer): boolean;
+  transfer(transfer: RebalanceTransfer): Promise<RouteSubmission>;
+}
+
+export interface RebalancerOptions {
+  corridors: Corridor[];
+  pollIntervalMs?: number;
+  /** Only report the transfers that would be made */
+  dryRun?: boolean;
+}
+
+interface HoldingBalance {
+  chain: string;
+  asset: string;
+  token: string;
+  decimals: number;
+  balance: bigint;
+  amount: number; // whole tokens
+  targetShare: number;
+}
+
+/**
+ * Keeps corridors liquid by watching the resolver's balance of each asset on every
+ * chain and moving inventory from the most over-weight chain to the most
+ * under-weight one when the skew passes the corridor's threshold. At most one
+ * transfer per corridor is in flight at a time.
+ *
+ * Emits 'transfer' when a rebalance is submitted, 'completed' when it lands, and 'error'.
+ */
+export class Rebalancer extends EventEmitter {
+  private adapters = new Map<string, ChainAdapter>();
+  private inFlight = new Map<string, RebalanceTransfer>();
+  private timer?: NodeJS.Timeout;
+  private ticking = false;
+
+  constructor(
+    adapters: ChainAdapter[],
+    private routes: RebalanceRoute[],
+    private options: RebalancerOptions,
+    private alerts?: AlertManager
+  ) {
+    super();
+    adapters.forEach(adapter => this.adapters.set(adapter.chain, adapter));
+  }
+
+  start(): void {
+    if (this.timer) return;
+    this.timer = setInterval(() => {
+      this.tick().catch(() => undefined);
+    }, this.options.pollIntervalMs ?? 5 * 60 * 1000);
+    logger.info(`Rebalancing ${this.options.corridors.map(corridor => corridor.name).join(', ')}`);
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  async tick(): Promise<RebalanceTransfer[]> {
+    if (this.ticking) return [];
+    this.ticking = true;
+
+    const planned: RebalanceTransfer[] = [];
+    try {
+      for (const corridor of this.options.corridors) {
+        if (this.inFlight.has(corridor.name)) continue;
+
+        try {
+          const transfer = this.plan(corridor, await this.balances(corridor));
+          if (!transfer) continue;
+          planned.push(transfer);
+          await this.execute(transfer);
+        } catch (error) {
+          logger.error(`Rebalance of ${corridor.name} failed`, { corridor: corridor.name, error: (error as Error).message });
+          this.alerts?.recordFailure(corridor.name, 'rebalance', error as Error).catch(() => undefined);
+          this.emit('error', error);
+        }
+      }
+    } finally {
+      this.ticking = false;
+    }
+    return planned;
+  }
+
+  private async balances(corridor: Corridor): Promise<HoldingBalance[]> {
+    return Promise.all(corridor.holdings.map(async holding => {
+      const [chain, symbol] = holding.asset.split(':');
+      const adapter = this.adapters.get(chain);
+      const token = getToken(chain, symbol);
+      if (!adapter || !token) {
+        throw new Error(`Cannot read ${holding.asset}: chain or token not configured`);
+      }
+
+      const native = adapter.config.nativeDenom && token.address === adapter.config.nativeDenom;
+      const balance = await adapter.getBalance(native ? undefined : token.address);
+      return {
+        chain,
+        asset: holding.asset,
+        token: token.address,
+        decimals: token.decimals,
+        balance,
+        amount: parseFloat(ethers.formatUnits(balance, token.decimals)),
+        targetShare: holding.targetShare
+      };
+    }));
+  }
+
+  /**
+   * Pick the transfer that brings the most over-weight and most under-weight
+   * chains closest to target, or nothing if every chain is within tolerance
+   */
+  private plan(corridor: Corridor, balances: HoldingBalance[]): RebalanceTransfer | null {
+    const total = balances.reduce((sum, holding) => sum + holding.amount, 0);
+    if (total <= 0) return null;
+
+    const deviations = balances.map(holding => ({ holding, deviation: holding.amount / total - holding.targetShare }));
+    const surplus = deviations.reduce((max, entry) => entry.deviation > max.deviation ? entry : max);
+    const deficit = deviations.reduce((min, entry) => entry.deviation < min.deviation ? entry : min);
+
+    if (surplus.deviation <= corridor.maxDeviation && -deficit.deviation <= corridor.maxDeviation) {
+      return null;
+    }
+
+    const amount = Math.min(surplus.deviation, -deficit.deviation) * total;
+    if (amount < corridor.minTransfer) return null;
+
+    const { holding: from } = surplus;
+    const { holding: to } = deficit;
+    logger.info(`${corridor.name} is skewed`, {
+      corridor: corridor.name,
+      shares: Object.fromEntries(balances.map(holding => [holding.chain, (holding.amount / total).toFixed(3)])),
+      from: from.chain,
+      to: to.chain,
+      amount
+    });
+
+    return {
+      corridor: corridor.name,
+      from: { chain: from.chain, asset: from.asset, token: from.token, decimals: from.decimals },
+      to: { chain: to.chain, asset: to.asset, token: to.token, decimals: to.decimals },
+      amount: ethers.parseUnits(amount.toFixed(from.decimals), from.decimals)
+    };
+  }
+
+  private async execute(transfer: RebalanceTransfer): Promise<void> {
+    const route = this.routes.find(candidate => candidate.supports(transfer));
+    const log = {
+      corridor: transfer.corridor,
+      from: transfer.from.chain,
+      to: transfer.to.chain,
+      amount: ethers.formatUnits(transfer.amount, transfer.from.decimals)
+    };
+    if (!route) {
+      logger.warn('No route configured for rebalance', log);
+      return;
+    }
+
+    if (this.options.dryRun) {
+      logger.info(`[dry-run] Would rebalance via ${route.name}`, log);
+      return;
+    }
+
+    this.inFlight.set(transfer.corridor, transfer);
+    let submission: RouteSubmission;
+    try {
+      submission = await route.transfer(transfer);
+    } catch (error) {
+      this.inFlight.delete(transfer.corridor);
+      throw error;
+    }
+
+    logger.info(`🔁 Rebalance submitted via ${route.name}`, { ...log, txHash: submission.txHash });
+    this.emit('transfer', { ...transfer, route: route.name, txHash: submission.txHash });
+
+    submission.completed
+      .then(() => {
+        this.alerts?.recordSuccess(transfer.corridor, 'rebalance');
+        logger.info(`✅ Rebalance via ${route.name} landed`, log);
+        this.emit('completed', transfer);
+      })
+      .catch(error => {
+        logger.error(`Rebalance via ${route.name} did not complete`, { ...log, error: (error as Error).message });
+        this.alerts?.recordFailure(transfer.corridor, 'rebalance', error as Error).catch(() => undefined);
+        this.emit('error', error);
+      })
+      .finally(() => this.inFlight.delete(transfer.corridor));
+  }
+}
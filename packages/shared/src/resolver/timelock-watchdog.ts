@@ -0,0 +1,204 @@
+import { EventEmitter } from 'events';
+import { FusionDAO, RelayerActionStatus, RelayerActionType, SwapRequest, SwapStatus } from '../database';
+import { SwapState } from '../utils/index';
+import { createLogger } from '../utils/logger';
+import type { AlertManager, AlertSeverity } from './alerting';
+import { DurableActionQueue } from './action-queue';
+import type { ChainAdapter, PendingTx } from './adapters';
+
+const logger = createLogger('TimelockWatchdog');
+
+export interface TimelockWatchdogOptions {
+  /** Fractions of the timelock window that trigger an alert, ascending */
+  thresholds?: number[];
+  pollIntervalMs?: number;
+  /** Maximum open swaps inspected per tick */
+  batchSize?: number;
+  /** Refund our expired HTLCs instead of only alerting */
+  execute?: boolean;
+}
+
+export interface TimelockEscalation {
+  swapId: string;
+  chain: string;
+  threshold: number;
+  severity: AlertSeverity;
+  remainingSeconds: number;
+}
+
+/**
+ * Watches every open swap's progress towards its timelock. While the counterpart
+ * leg is unsettled it raises alerts of rising severity as each threshold is
+ * crossed (by default 50%, 80% and 95% of the window). Once our own HTLC on the
+ * target chain has expired unclaimed, waiting any longer only risks the user
+ * claiming it after their source-leg refund, so it is refunded straight away.
+ *
+ * Emits 'escalation' for each threshold crossed and 'refunded' for protective refunds.
+ */
+export class TimelockWatchdog extends EventEmitter {
+  private thresholds: number[];
+  private adapters = new Map<string, ChainAdapter>();
+  private queues = new Map<string, DurableActionQueue>();
+  /** Highest threshold index already reported per swap */
+  private reported = new Map<string, number>();
+  private timer?: NodeJS.Timeout;
+  private ticking = false;
+
+  constructor(
+    private dao: FusionDAO,
+    adapters: ChainAdapter[],
+    private alerts?: AlertManager,
+    private options: TimelockWatchdogOptions = {}
+  ) {
+    super();
+    this.thresholds = [...(options.thresholds ?? [0.5, 0.8, 0.95])].sort((a, b) => a - b);
+    for (const adapter of adapters) {
+      this.adapters.set(adapter.chain, adapter);
+      this.queues.set(adapter.chain, new DurableActionQueue(dao, adapter.chain));
+    }
+  }
+
+  start(): void {
+    if (this.timer) return;
+    this.timer = setInterval(() => {
+      this.tick().catch(error => {
+        logger.error('Timelock watchdog tick failed', { error: (error as Error).message });
+      });
+    }, this.options.pollIntervalMs ?? 30000);
+    logger.info(`Watching timelocks at ${this.thresholds.map(t => `${Math.round(t * 100)}%`).join(', ')}`);
+  }
+
+  stop(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  async tick(): Promise<void> {
+    if (this.ticking) return;
+    this.ticking = true;
+
+    try {
+      const swaps = await this.dao.getUnsettledSwapsExpiringBefore(Number.MAX_SAFE_INTEGER, this.options.batchSize ?? 500);
+      const open = new Set(swaps.map(swap => swap.id));
+
+      for (const swap of swaps) {
+        try {
+          await this.inspect(swap);
+        } catch (error) {
+          logger.error('Failed to inspect swap timelock', {
+            correlationId: swap.id,
+            swapId: swap.id,
+            error: (error as Error).message
+          });
+        }
+      }
+
+      // Forget swaps that settled since the last tick
+      for (const swapId of this.reported.keys()) {
+        if (!open.has(swapId)) this.reported.delete(swapId);
+      }
+    } finally {
+      this.ticking = false;
+    }
+  }
+
+  private async inspect(swap: SwapRequest): Promise<void> {
+    const chain = swap.sourceToken.split(':')[0];
+    const now = Math.floor(Date.now() / 1000);
+    const start = Math.floor(new Date(swap.createdAt).getTime() / 1000);
+    const expiration = Number(swap.expirationTime);
+    const window = Math.max(expiration - start, 1);
+    const elapsed = (now - start) / window;
+
+    const crossed = this.thresholds.filter(threshold => elapsed >= threshold).length - 1;
+    const previous = this.reported.get(swap.id) ?? -1;
+    if (crossed > previous) {
+      this.reported.set(swap.id, crossed);
+      await this.escalate(swap, chain, crossed, expiration - now);
+    }
+
+    if (now >= expiration && swap.status === SwapStatus.POOL_FULFILLED && swap.poolHtlcContract) {
+      await this.protectiveRefund(swap);
+    }
+  }
+
+  private async escalate(swap: SwapRequest, chain: string, level: number, remainingSeconds: number): Promise<void> {
+    const threshold = this.thresholds[level];
+    const fromTop = this.thresholds.length - 1 - level;
+    const severity: AlertSeverity = fromTop === 0 ? 'critical' : fromTop === 1 ? 'warning' : 'info';
+
+    const escalation: TimelockEscalation = { swapId: swap.id, chain, threshold, severity, remainingSeconds };
+    logger.warn(`⏳ Swap at ${Math.round(threshold * 100)}% of its timelock`, {
+      correlationId: swap.id,
+      ...escalation,
+      status: swap.status
+    });
+    this.emit('escalation', escalation);
+
+    await this.alerts?.timelockProgress({
+      chain,
+      swapId: swap.id,
+      status: swap.status,
+      expirationTime: Number(swap.expirationTime),
+      threshold,
+      severity
+    });
+  }
+
+  /**
+   * Refund the pool HTLC we funded on the target chain once the chain itself
+   * agrees it has expired
+   */
+  private async protectiveRefund(swap: SwapRequest): Promise<void> {
+    const chain = swap.targetToken.split(':')[0];
+    const adapter = this.adapters.get(chain);
+    const queue = this.queues.get(chain);
+    const contractId = swap.poolHtlcContract!;
+    if (!adapter || !queue || !adapter.address) return;
+
+    const htlc = await adapter.getSwap(contractId);
+    if (!htlc) return;
+
+    if (htlc.state === SwapState.CLAIMED) return; // the user claimed in time; the source leg is settled elsewhere
+    if (htlc.state === SwapState.REFUNDED) {
+      if (this.options.execute !== false) {
+        await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.EXPIRED });
+      }
+      return;
+    }
+    if (htlc.originator.toLowerCase() !== adapter.address.toLowerCase()) return;
+    if (await adapter.currentTime() < htlc.timelock) return;
+
+    const log = { correlationId: swap.id, swapId: swap.id, chain, contractId };
+    if (this.options.execute === false) {
+      logger.info('[dry-run] Would refund expired pool HTLC', log);
+      return;
+    }
+
+    const latest = await queue.latest(swap.id, RelayerActionType.REFUND);
+    if (latest && [RelayerActionStatus.PENDING, RelayerActionStatus.SUBMITTED].includes(latest.status)) {
+      return; // a refund is already on its way
+    }
+
+    const action = await queue.record({ swapId: swap.id, action: RelayerActionType.REFUND, contractId });
+    let pending: PendingTx;
+    try {
+      pending = await adapter.submitRefund(contractId);
+    } catch (error) {
+      await queue.failed(action, (error as Error).message);
+      throw error;
+    }
+
+    // Once broadcast, the refund can still be mined even if waiting for it fails, so the
+    // action stays SUBMITTED for reconcileActions rather than being marked FAILED and resent
+    await queue.submitted(action, pending.txHash);
+    await pending.wait();
+    await queue.confirmed(action);
+
+    await this.dao.updateSwapRequest(swap.id, { status: SwapStatus.EXPIRED });
+    logger.warn('🛡️ Protective refund of expired pool HTLC', log);
+    this.emit('refunded', { swapId: swap.id, chain, contractId });
+  }
+}
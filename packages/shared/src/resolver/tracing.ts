@@ -0,0 +1,76 @@
+import { AsyncLocalStorage } from 'async_hooks';
+import { randomUUID } from 'crypto';
+import { Logger, createLogger } from '../utils/logger';
+
+export interface SpanContext {
+  /** Shared by every line logged for one swap - the swap ID */
+  correlationId?: string;
+  spanId: string;
+  parentSpanId?: string;
+  span: string;
+  [field: string]: unknown;
+}
+
+const spanStorage = new AsyncLocalStorage<SpanContext>();
+
+export function currentSpan(): SpanContext | undefined {
+  return spanStorage.getStore();
+}
+
+/**
+ * Logger whose lines carry the fields of whichever span is active when they are written.
+ * Spans propagate across awaits, so helpers deep in the call stack do not need a logger
+ * passed down to them.
+ */
+export function createTracingLogger(component: string, context: Record<string, unknown> = {}): Logger {
+  const base = createLogger(component, context);
+  const withSpan = (): Logger => {
+    const span = spanStorage.getStore();
+    return span ? base.child(span) : base;
+  };
+
+  return {
+    debug: (message, meta) => withSpan().debug(message, meta),
+    info: (message, meta) => withSpan().info(message, meta),
+    warn: (message, meta) => withSpan().warn(message, meta),
+    error: (message, meta) => withSpan().error(message, meta),
+    child: (childContext) => createTracingLogger(component, { ...context, ...childContext })
+  };
+}
+
+/**
+ * Run `fn` inside a named span nested under the current one. Span fields are
+ * inherited by child spans; start/end are logged at debug level with the duration.
+ */
+export async function withSpan<T>(
+  logger: Logger,
+  name: string,
+  fields: Record<string, unknown>,
+  fn: () => Promise<T>
+): Promise<T> {
+  const parent = spanStorage.getStore();
+  const span: SpanContext = {
+    ...parent,
+    ...fields,
+    span: parent ? `${parent.span}.${name}` : name,
+    spanId: randomUUID().slice(0, 8),
+    parentSpanId: parent?.spanId
+  };
+
+  return spanStorage.run(span, async () => {
+    const startTime = Date.now();
+    logger.debug(`${name} started`);
+
+    try {
+      const result = await fn();
+      logger.debug(`${name} finished`, { durationMs: Date.now() - startTime });
+      return result;
+    } catch (error) {
+      logger.warn(`${name} failed`, {
+        durationMs: Date.now() - startTime,
+        error: (error as Error).message
+      });
+      throw error;
+    }
+  });
+}
@@ -0,0 +1,112 @@
+import { bench, describe } from 'vitest';
+import { ethers } from 'ethers';
+import { HashLock } from '../hashlock';
+import { HASH_ALGORITHMS } from '../secret';
+import { deriveSwapId } from '../swap-id';
+import type { SwapIdParams } from '../swap-id';
+import { parseTxEvents } from '../events';
+import type { TxEvent } from '../events';
+import { HTLC_EVENTS, decodeHtlcLogs } from '../evm';
+import { HtlcMsg } from '../builder';
+import { decodeEvmOrder, encodeEvmOrder, toInstantiateMsg, SwapOrderSchema } from '../types';
+import type { SwapOrder } from '../types';
+
+// What a resolver does per order and per observed event. Run with `pnpm bench`.
+
+const PREIMAGE = ethers.getBytes('0x' + '11'.repeat(32));
+const HASH_LOCK = HashLock.fromPreimage(PREIMAGE).toEvm();
+const HTLC = '0x5FbDB2315678afecb367f032d93F642f64180aa3';
+const COSMOS_HTLC = 'osmo14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sq2r9g9';
+const CONTRACT_ID = '0x' + '22'.repeat(32);
+
+const evmSwap: SwapIdParams = {
+  chainId: 11155111,
+  hashLock: HASH_LOCK,
+  originator: '0x1111111111111111111111111111111111111111',
+  beneficiary: '0x2222222222222222222222222222222222222222',
+  amount: '1000000000000000000',
+  timelock: 1735689600
+};
+const cosmosSwap: SwapIdParams = {
+  ...evmSwap,
+  chainId: 'osmo-test-5',
+  originator: 'osmo1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du',
+  beneficiary: 'osmo1qgpqyqszqgpqyqszqgpqyqszqgpqyqszqzx3le5',
+  token: 'uosmo'
+};
+
+function log(name: string, values: unknown[], index: number): ethers.Log {
+  const { data, topics } = HTLC_EVENTS.encodeEventLog(name, values);
+  return { address: HTLC, data, topics, blockNumber: 100, transactionHash: ethers.id(`tx${index}`), index } as unknown as ethers.Log;
+}
+const logs = [
+  log('HTLCCreated', [CONTRACT_ID, evmSwap.originator, evmSwap.beneficiary, ethers.ZeroAddress, evmSwap.amount, HASH_LOCK, evmSwap.timelock], 0),
+  log('HTLCClaimed', [CONTRACT_ID, evmSwap.beneficiary, ethers.hexlify(PREIMAGE)], 1),
+  log('HTLCRefunded', [CONTRACT_ID, evmSwap.originator], 2)
+];
+
+const attrs = (entries: Record<string, string>) => Object.entries(entries).map(([key, value]) => ({ key, value }));
+const cosmosTx: TxEvent[] = [
+  { type: 'message', attributes: attrs({ action: '/cosmwasm.wasm.v1.MsgExecuteContract', sender: cosmosSwap.beneficiary }) },
+  { type: 'execute', attributes: attrs({ _contract_address: COSMOS_HTLC }) },
+  { type: 'wasm', attributes: attrs({ _contract_address: COSMOS_HTLC, method: 'claim', preimage: '11'.repeat(32) }) },
+  { type: 'transfer', attributes: attrs({ recipient: cosmosSwap.beneficiary, amount: '1000000uosmo' }) }
+];
+
+const order: SwapOrder = {
+  chain: 'sepolia',
+  contractId: CONTRACT_ID,
+  originator: evmSwap.originator,
+  beneficiary: evmSwap.beneficiary,
+  hashLock: HASH_LOCK,
+  timelock: evmSwap.timelock,
+  amount: evmSwap.amount.toString()
+};
+const encodedOrder = encodeEvmOrder(order);
+
+describe('hashlock', () => {
+  for (const algorithm of HASH_ALGORITHMS) {
+    bench(algorithm, () => {
+      HashLock.fromPreimage(PREIMAGE, algorithm);
+    });
+  }
+  bench('verify', () => {
+    HashLock.fromEvm(HASH_LOCK).verify(PREIMAGE);
+  });
+});
+
+describe('swap ID', () => {
+  bench('EVM leg', () => {
+    deriveSwapId(evmSwap);
+  });
+  bench('Cosmos leg', () => {
+    deriveSwapId(cosmosSwap);
+  });
+});
+
+describe('event decoding', () => {
+  bench('SimpleHTLC logs (created, claimed, refunded)', () => {
+    decodeHtlcLogs(logs, HTLC);
+  });
+  bench('CosmWasm claim tx events', () => {
+    parseTxEvents(cosmosTx, { contracts: [COSMOS_HTLC] });
+  });
+});
+
+describe('message serialization', () => {
+  bench('CosmWasm claim msg', () => {
+    JSON.stringify(HtlcMsg.claim(ethers.hexlify(PREIMAGE)));
+  });
+  bench('CosmWasm instantiate msg', () => {
+    JSON.stringify(toInstantiateMsg(order));
+  });
+  bench('SimpleHTLC order encode', () => {
+    encodeEvmOrder(order);
+  });
+  bench('SimpleHTLC order decode', () => {
+    decodeEvmOrder('sepolia', CONTRACT_ID, encodedOrder);
+  });
+  bench('order JSON round trip with validation', () => {
+    SwapOrderSchema.parse(JSON.parse(JSON.stringify(order)));
+  });
+});
@@ -0,0 +1,55 @@
+import { describe, it, expect } from 'vitest';
+import { ethers } from 'ethers';
+import {
+  bech32ToHex,
+  hexToBech32,
+  cosmosAddressFromPubkey,
+  ethermintAddressFromPubkey,
+  evmAddressFromPubkey,
+  toChainAddress
+} from '../address';
+import { ValidationError } from '../../utils/errors';
+
+// Private key 1: its EVM address is a well-known constant
+const pubkey = new ethers.SigningKey('0x' + '00'.repeat(31) + '01').publicKey;
+const EVM_ADDRESS = '0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf';
+
+describe('bech32 <-> hex', () => {
+  it('round-trips 20-byte accounts', () => {
+    const bech32 = hexToBech32(EVM_ADDRESS, 'evmos');
+    expect(bech32).toMatch(/^evmos1/);
+    expect(bech32ToHex(bech32)).toBe(EVM_ADDRESS);
+  });
+
+  it('rejects account bytes of the wrong length', () => {
+    expect(() => hexToBech32('0x1234', 'cosmos')).toThrow(ValidationError);
+  });
+});
+
+describe('toChainAddress', () => {
+  const ethermint = ethermintAddressFromPubkey(pubkey, 'inj');
+
+  it('converts ethermint accounts both ways when the key type is asserted', () => {
+    expect(evmAddressFromPubkey(pubkey)).toBe(EVM_ADDRESS);
+    expect(toChainAddress(ethermint, { type: 'evm' }, { keyType: 'eth_secp256k1' })).toBe(EVM_ADDRESS);
+    expect(toChainAddress(EVM_ADDRESS, { type: 'cosmos', addressPrefix: 'inj' }, { keyType: 'eth_secp256k1' })).toBe(ethermint);
+  });
+
+  it('refuses to cross families without an eth_secp256k1 assertion', () => {
+    const cosmos = cosmosAddressFromPubkey(pubkey, 'cosmos');
+    expect(() => toChainAddress(cosmos, { type: 'evm' })).toThrow(ValidationError);
+    expect(() => toChainAddress(cosmos, { type: 'evm' }, { keyType: 'secp256k1' })).toThrow(ValidationError);
+    expect(() => toChainAddress(EVM_ADDRESS, { type: 'cosmos', addressPrefix: 'cosmos' })).toThrow(ValidationError);
+  });
+
+  it('converts within a family without a key type', () => {
+    const cosmos = cosmosAddressFromPubkey(pubkey, 'cosmos');
+    expect(toChainAddress(cosmos, { type: 'cosmos', addressPrefix: 'osmo' })).toBe(cosmosAddressFromPubkey(pubkey, 'osmo'));
+    expect(toChainAddress(EVM_ADDRESS.toLowerCase(), { type: 'evm' })).toBe(EVM_ADDRESS);
+  });
+
+  it('has no EVM form for 32-byte accounts', () => {
+    const contract = hexToBech32(ethers.hexlify(new Uint8Array(32).fill(7)), 'osmo');
+    expect(() => toChainAddress(contract, { type: 'evm' }, { keyType: 'eth_secp256k1' })).toThrow(ValidationError);
+  });
+});
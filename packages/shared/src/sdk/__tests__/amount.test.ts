@@ -0,0 +1,30 @@
+import { describe, it, expect } from 'vitest';
+import { Amount } from '../amount';
+
+describe('Amount', () => {
+  it('converts between 6 and 18 decimals without losing value', () => {
+    const usdc = Amount.parse('12.345678', 6);
+    const wei = usdc.convert(18);
+
+    expect(wei.base).toBe(12_345_678_000_000_000_000n);
+    expect(wei.convert(6).equals(usdc)).toBe(true);
+  });
+
+  it('refuses a lossy conversion unless told how to round', () => {
+    const amount = Amount.fromBase(1_500_000_000_000_500_000n, 18);
+
+    expect(() => amount.convert(6)).toThrow('cannot be represented');
+    expect(amount.convert(6, 'floor').base).toBe(1_500_000n);
+    expect(amount.convert(6, 'ceil').base).toBe(1_500_001n);
+  });
+
+  it('rounds ties to even', () => {
+    expect(Amount.parse('0.0000025', 6, { rounding: 'half-even' }).base).toBe(2n);
+    expect(Amount.parse('0.0000035', 6, { rounding: 'half-even' }).base).toBe(4n);
+  });
+
+  it('only adds amounts with the same decimals', () => {
+    expect(() => Amount.parse('1', 6).add(Amount.parse('1', 18))).toThrow('convert one first');
+    expect(Amount.parse('1', 6).add(Amount.parse('0.5', 6)).toDisplay()).toBe('1.5');
+  });
+});
@@ -0,0 +1,33 @@
+import { describe, it, expect } from 'vitest';
+import { describeSwap } from '../describe';
+import { SwapState } from '../../utils/htlc';
+import type { SwapOrder } from '../types';
+
+const order: SwapOrder = {
+  chain: 'cosmosTestnet',
+  originator: 'cosmos1alice',
+  beneficiary: 'cosmos1bob',
+  hashLock: '0xab12' + '00'.repeat(28) + 'cdef',
+  timelock: 1719842400,
+  amount: '1000000',
+  token: 'uatom',
+  state: SwapState.OPEN
+};
+const names = { cosmos1alice: 'Alice', cosmos1bob: 'Bob' };
+
+describe('describeSwap', () => {
+  it('summarizes an open swap', () => {
+    expect(describeSwap(order, { names, now: order.timelock - 60 })).toBe(
+      'Alice locks 1.0 ATOM for Bob, claimable until Jul 1, 2024, 2:00 PM UTC with SHA-256 hashlock 0xab12…cdef'
+    );
+  });
+
+  it('formats amounts for the locale', () => {
+    const summary = describeSwap({ ...order, amount: '1234567500000' }, { names, locale: 'de-DE', now: 0 });
+    expect(summary).toContain('1.234.567,5 ATOM');
+  });
+
+  it('says when an open swap can be refunded', () => {
+    expect(describeSwap(order, { names, now: order.timelock + 1 })).toContain('refundable by Alice');
+  });
+});
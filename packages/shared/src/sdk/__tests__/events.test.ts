@@ -0,0 +1,48 @@
+import { describe, it, expect } from 'vitest';
+import { parseTxEvents, TxEvent } from '../events';
+
+const HTLC = 'osmo14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sq2r9g9';
+const PREIMAGE = '0x' + '11'.repeat(32);
+
+const attrs = (entries: Record<string, string>) =>
+  Object.entries(entries).map(([key, value]) => ({ key, value }));
+
+describe('parseTxEvents', () => {
+  it('decodes an instantiate transaction into created and funded', () => {
+    const events: TxEvent[] = [
+      { type: 'message', attributes: attrs({ action: '/cosmwasm.wasm.v1.MsgInstantiateContract' }) },
+      { type: 'instantiate', attributes: attrs({ _contract_address: HTLC, code_id: '42' }) },
+      {
+        type: 'wasm',
+        attributes: attrs({
+          _contract_address: HTLC,
+          method: 'instantiate',
+          amount: '1000',
+          timelock: '1735689600',
+          hash_lock: Buffer.from('22'.repeat(32), 'hex').toString('base64')
+        })
+      }
+    ];
+
+    expect(parseTxEvents(events)).toEqual([
+      { kind: 'created', contractAddress: HTLC, codeId: 42 },
+      { kind: 'funded', contractAddress: HTLC, amount: '1000', timelock: 1735689600, hashLock: '0x' + '22'.repeat(32) }
+    ]);
+  });
+
+  it('ignores attribute order and unknown attributes', () => {
+    const claim: TxEvent = {
+      type: 'wasm',
+      attributes: attrs({ schema_version: '2', preimage: PREIMAGE.slice(2), method: 'claim', _contract_address: HTLC })
+    };
+    expect(parseTxEvents([claim])).toEqual([{ kind: 'claimed', contractAddress: HTLC, preimage: PREIMAGE }]);
+  });
+
+  it('skips unrecognized methods and unwanted contracts', () => {
+    const events: TxEvent[] = [
+      { type: 'wasm', attributes: attrs({ _contract_address: HTLC, method: 'extend' }) },
+      { type: 'wasm', attributes: attrs({ _contract_address: 'osmo1other', method: 'refund' }) }
+    ];
+    expect(parseTxEvents(events, { contracts: [HTLC] })).toEqual([]);
+  });
+});
@@ -0,0 +1,62 @@
+import { describe, it, expect } from 'vitest';
+import { ethers } from 'ethers';
+import { cosmosAddressFromPubkey } from '../address';
+import { decodeHandoff, encodeHandoffUri, signHandoff, verifyHandoff } from '../handoff';
+import type { SwapHandoff } from '../handoff';
+
+const MNEMONIC = 'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about';
+const evmWallet = ethers.HDNodeWallet.fromPhrase(MNEMONIC);
+const cosmosAddress = cosmosAddressFromPubkey(
+  ethers.HDNodeWallet.fromPhrase(MNEMONIC, '', "m/44'/118'/0'/0/0").signingKey.compressedPublicKey,
+  'osmo'
+);
+
+function handoff(originator: string): SwapHandoff {
+  return {
+    version: 1,
+    maker: {
+      chain: 'sepolia',
+      chainId: 11155111,
+      swapId: ethers.ZeroHash.replace(/0$/, '1'),
+      originator,
+      beneficiary: '0x2222222222222222222222222222222222222222',
+      hashLock: ethers.sha256(ethers.ZeroHash),
+      timelock: 1735693200,
+      amount: '1000000000000000'
+    },
+    ask: {
+      chain: 'osmosisTestnet',
+      chainId: 'osmo-test-5',
+      beneficiary: cosmosAddress,
+      amount: '1000000',
+      timelock: 1735689600
+    },
+    createdAt: 1735686000
+  };
+}
+
+describe('swap handoff', () => {
+  it('round-trips an EVM-signed handoff through its URI', async () => {
+    const signed = await signHandoff(handoff(evmWallet.address), { type: 'evm', privateKey: evmWallet.privateKey });
+    const uri = encodeHandoffUri(signed);
+    expect(uri.startsWith('omni-swap:')).toBe(true);
+    expect(verifyHandoff(decodeHandoff(uri))).toEqual(signed.handoff);
+  });
+
+  it('verifies an ADR-36 signature from a Cosmos maker', async () => {
+    const signed = await signHandoff(handoff(cosmosAddress), { type: 'cosmos', mnemonic: MNEMONIC });
+    expect(signed.signature.scheme).toBe('adr36');
+    expect(() => verifyHandoff(decodeHandoff(JSON.stringify(signed)))).not.toThrow();
+  });
+
+  it('rejects a handoff altered after signing', async () => {
+    const signed = await signHandoff(handoff(evmWallet.address), { type: 'evm', privateKey: evmWallet.privateKey });
+    const tampered = { ...signed, handoff: { ...signed.handoff, ask: { ...signed.handoff.ask, amount: '1' } } };
+    expect(() => verifyHandoff(tampered)).toThrow(/not signed by its originator/);
+  });
+
+  it('refuses to sign for another originator', async () => {
+    await expect(signHandoff(handoff('osmo1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du'), { type: 'cosmos', mnemonic: MNEMONIC }))
+      .rejects.toThrow(/not the handoff originator/);
+  });
+});
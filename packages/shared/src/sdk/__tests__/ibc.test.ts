@@ -0,0 +1,30 @@
+import { describe, it, expect } from 'vitest';
+import { ibcDenom, denomAfterTransfer } from '../ibc';
+
+// ATOM on Osmosis over channel-0
+const OSMOSIS_ATOM = 'ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2';
+
+describe('ibc denoms', () => {
+  it('hashes the trace path the way ICS-20 does', () => {
+    expect(ibcDenom('transfer/channel-0', 'uatom')).toBe(OSMOSIS_ATOM);
+    expect(ibcDenom('', 'uatom')).toBe('uatom');
+  });
+
+  it('adds a hop for a token leaving its chain', () => {
+    const denom = denomAfterTransfer(
+      { path: '', baseDenom: 'uatom' },
+      { port: 'transfer', channel: 'channel-141' },
+      { port: 'transfer', channel: 'channel-0' }
+    );
+    expect(denom).toBe(OSMOSIS_ATOM);
+  });
+
+  it('unwinds the hop for a token sent back the way it came', () => {
+    const denom = denomAfterTransfer(
+      { path: 'transfer/channel-0', baseDenom: 'uatom' },
+      { port: 'transfer', channel: 'channel-0' },
+      { port: 'transfer', channel: 'channel-141' }
+    );
+    expect(denom).toBe('uatom');
+  });
+});
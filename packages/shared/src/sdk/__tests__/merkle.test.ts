@@ -0,0 +1,24 @@
+import { describe, it, expect } from 'vitest';
+import { MerkleSecrets, verifyMerkleProof } from '../merkle';
+
+describe('MerkleSecrets', () => {
+  it('produces a proof for every leaf that verifies against the root', () => {
+    // 5 parts -> 6 leaves, so one level has an odd node carried up
+    const secrets = MerkleSecrets.generate(5);
+    for (let index = 0; index <= secrets.parts; index++) {
+      expect(verifyMerkleProof(secrets.leaf(index), secrets.proof(index), secrets.root)).toBe(true);
+    }
+    expect(verifyMerkleProof(secrets.leaf(0), secrets.proof(1), secrets.root)).toBe(false);
+  });
+
+  it('picks the part a fill ends in and refuses to reuse a secret', () => {
+    const secrets = MerkleSecrets.generate(4);
+
+    const first = secrets.indexForFill(100n, 0n, 30n);
+    expect(first).toBe(1);
+    secrets.consume(first);
+
+    expect(() => secrets.indexForFill(100n, 30n, 10n)).toThrow('already used');
+    expect(secrets.indexForFill(100n, 30n, 70n)).toBe(4);
+  });
+});
@@ -0,0 +1,44 @@
+import { describe, it, expect } from 'vitest';
+import { planRoute, Corridor } from '../routing';
+
+const JUNO = { chain: 'junoTestnet', asset: 'ujunox' };
+const USDC = { chain: 'nobleTestnet', asset: 'uusdc' };
+const ETH = { chain: 'sepolia', asset: 'native' };
+const NOW = 1_700_000_000;
+
+const corridors: Corridor[] = [
+  { from: JUNO, to: USDC, quote: amount => amount / 4n },
+  // USDC (6 decimals) -> ETH (18 decimals) at 2000 USDC/ETH
+  { from: USDC, to: ETH, quote: amount => amount * 10n ** 12n / 2000n }
+];
+
+describe('planRoute', () => {
+  it('routes through an intermediate asset when there is no direct corridor', () => {
+    const plan = planRoute(JUNO, ETH, 8_000_000_000n, corridors, { now: NOW })!;
+
+    expect(plan.legs.map(leg => leg.chain)).toEqual(['junoTestnet', 'nobleTestnet', 'sepolia']);
+    expect(plan.amountOut).toBe(1_000_000_000_000_000_000n);
+  });
+
+  it('staggers timelocks so each leg outlives the next by its safety gap', () => {
+    const plan = planRoute(JUNO, ETH, 1_000_000n, corridors, {
+      now: NOW,
+      finalLegSeconds: 1800,
+      safetyMarginSeconds: 600,
+      timing: {
+        sepolia: { finalitySeconds: 780, inclusionSeconds: 60 },
+        nobleTestnet: { finalitySeconds: 10, inclusionSeconds: 10 },
+        junoTestnet: { finalitySeconds: 10, inclusionSeconds: 10 }
+      }
+    })!;
+
+    const [juno, noble, sepolia] = plan.legs.map(leg => leg.timelock);
+    expect(sepolia).toBe(NOW + 1800);
+    expect(noble - sepolia).toBe(780 + 10 + 600);
+    expect(juno - noble).toBe(10 + 10 + 600);
+  });
+
+  it('returns null when the route would lock funds for too long', () => {
+    expect(planRoute(JUNO, ETH, 1_000_000n, corridors, { now: NOW, maxTotalSeconds: 3600 })).toBeNull();
+  });
+});
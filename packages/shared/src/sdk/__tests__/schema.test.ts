@@ -0,0 +1,31 @@
+import { describe, it, expect } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import { HTLC_CONTRACT_VERSION, htlcContractSchema, htlcSchemaFiles } from '../schema';
+
+// Committed output of `pnpm schema:generate`, one directory per contract release
+const SNAPSHOTS = path.resolve(__dirname, '../../../../../schema/htlc', HTLC_CONTRACT_VERSION);
+
+function snapshot(file: string): unknown {
+  const location = path.join(SNAPSHOTS, file);
+  if (!fs.existsSync(location)) {
+    throw new Error(`No committed schema at ${location}; run pnpm schema:generate for HTLC ${HTLC_CONTRACT_VERSION}`);
+  }
+  return JSON.parse(fs.readFileSync(location, 'utf8'));
+}
+
+// A failure here means a message or response changed shape. If that is intended,
+// bump HTLC_CONTRACT_VERSION and commit the new schema directory alongside the old one.
+describe(`HTLC ${HTLC_CONTRACT_VERSION} message schemas`, () => {
+  it('match the committed contract schema', () => {
+    expect(htlcContractSchema()).toEqual(snapshot('htlc.json'));
+  });
+
+  it.each(Object.entries(htlcSchemaFiles()))('match the committed raw/%s', (name, schema) => {
+    expect(schema).toEqual(snapshot(path.join('raw', name)));
+  });
+
+  it('has no raw schema files the SDK no longer generates', () => {
+    expect(fs.readdirSync(path.join(SNAPSHOTS, 'raw')).sort()).toEqual(Object.keys(htlcSchemaFiles()).sort());
+  });
+});
@@ -0,0 +1,70 @@
+import { describe, it, expect } from 'vitest';
+import { ethers } from 'ethers';
+import { deriveSwapId, SwapIdParams } from '../swap-id';
+
+// sha256 of 32 zero bytes
+const HASH_LOCK = '0x66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925';
+
+const evmParams: SwapIdParams = {
+  chainId: 11155111,
+  hashLock: HASH_LOCK,
+  originator: '0x1111111111111111111111111111111111111111',
+  beneficiary: '0x2222222222222222222222222222222222222222',
+  amount: '1000000000000000000',
+  timelock: 1735689600
+};
+
+const cosmosParams: SwapIdParams = {
+  chainId: 'osmo-test-5',
+  hashLock: HASH_LOCK,
+  originator: 'osmo1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du',
+  beneficiary: 'osmo1qgpqyqszqgpqyqszqgpqyqszqgpqyqszqzx3le5',
+  amount: 1000000n,
+  timelock: 1735689600
+};
+
+describe('deriveSwapId', () => {
+  // Expected values come from an independent implementation of the documented
+  // layout (Python hashlib), not from this module
+  it('matches the reference vectors', () => {
+    expect(deriveSwapId(evmParams)).toBe('0x5ed243ae550826aa59683c3fec9f5c2ab8ce887808efbdce9dc8f92e5c31d0d2');
+    expect(deriveSwapId(cosmosParams)).toBe('0xe1cf63eedcd872a54319bfaf354076d9bc81fd3f036ede669df2c9cfe5e3ad86');
+  });
+
+  it('matches the layout built with solidity abi.encodePacked', () => {
+    const str = (value: string) => [ethers.toUtf8Bytes(value).length, value];
+    const packed = ethers.solidityPacked(
+      ['string', 'uint32', 'string', 'bytes32', 'uint32', 'string', 'uint32', 'string', 'uint32', 'string', 'uint256', 'uint64'],
+      [
+        'htlc-swap-id/v1',
+        ...str('11155111'),
+        HASH_LOCK,
+        ...str(evmParams.originator),
+        ...str(evmParams.beneficiary),
+        ...str(''),
+        evmParams.amount,
+        evmParams.timelock
+      ]
+    );
+    expect(deriveSwapId(evmParams)).toBe(ethers.sha256(packed));
+  });
+
+  it('normalizes address case and the native token', () => {
+    const checksummed = {
+      ...evmParams,
+      originator: ethers.getAddress(evmParams.originator),
+      token: ethers.ZeroAddress
+    };
+    expect(deriveSwapId(checksummed)).toBe(deriveSwapId(evmParams));
+  });
+
+  it('separates fields so shifted strings do not collide', () => {
+    const a = deriveSwapId({ ...cosmosParams, originator: 'ab', beneficiary: 'c' });
+    const b = deriveSwapId({ ...cosmosParams, originator: 'a', beneficiary: 'bc' });
+    expect(a).not.toBe(b);
+  });
+
+  it('rejects malformed hash locks', () => {
+    expect(() => deriveSwapId({ ...evmParams, hashLock: '0x1234' })).toThrow('32 bytes');
+  });
+});
@@ -0,0 +1,90 @@
+import { describe, it, expect, vi } from 'vitest';
+import { CrossChainSwap, CrossChainSwapParams } from '../swap';
+import { MockChain } from '../testing';
+import { SwapState } from '../../utils/htlc';
+
+const ALICE = '0x000000000000000000000000000000000000a11c';
+const BOB = '0x0000000000000000000000000000000000000b0b';
+const ALICE_OSMO = 'osmo1alice';
+const BOB_OSMO = 'osmo1bob';
+const NOW = 1_700_000_000;
+
+function setup() {
+  const source = MockChain.create('sepolia', { address: ALICE, now: NOW });
+  const destination = MockChain.create('osmosisTestnet', { address: ALICE_OSMO, now: NOW, allocateIds: true });
+  const params: CrossChainSwapParams = {
+    source: { chain: 'sepolia', beneficiary: BOB, amount: '1000', timelock: NOW + 4 * 3600 },
+    destination: { chain: 'osmosisTestnet', beneficiary: ALICE_OSMO, minAmount: '500' }
+  };
+  return { source, destination, params };
+}
+
+describe('CrossChainSwap on MockChain', () => {
+  it('runs to completion once the counterparty locks', async () => {
+    const { source, destination, params } = setup();
+    const relaySecret = vi.fn().mockResolvedValue(undefined);
+    const swap = CrossChainSwap.create(params, { source, destination }, { relaySecret });
+
+    expect(await swap.step()).toBe('source_locked');
+    expect(await swap.step()).toBe('source_locked'); // nothing on the destination yet
+
+    await destination.as(BOB_OSMO).lock({
+      contractId: '',
+      beneficiary: ALICE_OSMO,
+      hashLock: swap.hashLock,
+      timelock: NOW + 2 * 3600,
+      amount: '600'
+    });
+
+    expect(await swap.step()).toBe('destination_locked');
+    expect(await swap.step()).toBe('destination_claimed');
+    expect(await swap.step()).toBe('completed');
+
+    const secret = relaySecret.mock.calls[0][1];
+    expect(await destination.revealedPreimage(swap.snapshot().destinationContractId!)).toBe(secret);
+    expect(swap.snapshot().secret).toBeUndefined();
+  });
+
+  it('refunds the source leg when no acceptable destination lock appears', async () => {
+    const { source, destination, params } = setup();
+    const swap = CrossChainSwap.create(params, { source, destination });
+    await swap.step();
+
+    // Too close to the source expiry to be safe
+    await destination.as(BOB_OSMO).lock({
+      contractId: '',
+      beneficiary: ALICE_OSMO,
+      hashLock: swap.hashLock,
+      timelock: params.source.timelock - 60,
+      amount: '600'
+    });
+
+    source.advanceTime(5 * 3600);
+    expect(await swap.step()).toBe('refunded');
+    expect((await source.getHtlc(swap.snapshot().sourceContractId!))?.state).toBe(SwapState.REFUNDED);
+  });
+
+  it('reports lock, claim and failures to telemetry', async () => {
+    const { source, destination, params } = setup();
+    const telemetry = { onSwapCreated: vi.fn(), onClaim: vi.fn(), onError: vi.fn(() => { throw new Error('sink down'); }) };
+    const swap = CrossChainSwap.create(params, { source, destination }, { telemetry });
+
+    source.failNext('lock');
+    await expect(swap.step()).rejects.toThrow('Injected lock failure');
+    expect(telemetry.onError).toHaveBeenCalledWith(expect.objectContaining({ operation: 'created' }));
+
+    await swap.step();
+    expect(telemetry.onSwapCreated).toHaveBeenCalledWith(expect.objectContaining({ chain: 'sepolia', amount: '1000' }));
+
+    await destination.as(BOB_OSMO).lock({
+      contractId: '',
+      beneficiary: ALICE_OSMO,
+      hashLock: swap.hashLock,
+      timelock: NOW + 2 * 3600,
+      amount: '600'
+    });
+    await swap.step();
+    await swap.step();
+    expect(telemetry.onClaim).toHaveBeenCalledWith(expect.objectContaining({ chain: 'osmosisTestnet', hashLock: swap.hashLock }));
+  });
+});
@@ -0,0 +1,28 @@
+import { describe, it, expect } from 'vitest';
+import { chainTiming, recommend, checkTimelocks } from '../timelock';
+
+const NOW = 1_700_000_000;
+
+describe('timelock advisor', () => {
+  it('waits out the destination finality plus source inclusion and skew', () => {
+    // Sepolia: 1 confirmation x 12s; Osmosis: 3s blocks, inclusion floored at a minute
+    expect(chainTiming('sepolia')).toEqual({ finalitySeconds: 12, inclusionSeconds: 60 });
+
+    const advice = recommend('osmosisTestnet', 'sepolia', { clockSkewSeconds: 60 });
+    expect(advice.marginSeconds).toBe(12 + 60 + 120);
+    expect(advice.sourceDurationSeconds).toBe(3600 + advice.marginSeconds);
+  });
+
+  it('uses the rollup safe head for optimistic chains', () => {
+    expect(chainTiming('baseSepolia').finalitySeconds).toBe(1200);
+  });
+
+  it('rejects a source expiry inside the margin', () => {
+    const { marginSeconds } = recommend('osmosisTestnet', 'sepolia');
+    const destExpiry = NOW + 3600;
+
+    expect(checkTimelocks('osmosisTestnet', 'sepolia', { destExpiry, sourceExpiry: destExpiry + marginSeconds + 1 }, NOW)).toEqual([]);
+    const errors = checkTimelocks('osmosisTestnet', 'sepolia', { destExpiry, sourceExpiry: destExpiry + marginSeconds }, NOW);
+    expect(errors.map(error => error.kind)).toEqual(['InvalidTimelock']);
+  });
+});
@@ -0,0 +1,37 @@
+import { describe, it, expect } from 'vitest';
+import fs from 'fs';
+import path from 'path';
+import { ethers } from 'ethers';
+import { generateTestVectors, TEST_VECTORS_VERSION } from '../vectors';
+import type { TestVectors } from '../vectors';
+import { HashLock } from '../hashlock';
+import { deriveSwapId } from '../swap-id';
+import { HtlcMsg } from '../builder';
+import { GASLESS_CLAIM_TYPES, typedDataDigest } from '../eip712';
+
+// The committed corpus every implementation tests against (`pnpm vectors:generate`)
+const CORPUS = path.resolve(__dirname, '../../../../../test-vectors/htlc-vectors.json');
+const corpus: TestVectors | undefined = fs.existsSync(CORPUS) ? JSON.parse(fs.readFileSync(CORPUS, 'utf8')) : undefined;
+
+describe.skipIf(!corpus)('shared test vector corpus', () => {
+  it('is what the SDK generates today', () => {
+    const { seed, vectors } = corpus!;
+    expect(corpus!.version).toBe(TEST_VECTORS_VERSION);
+    expect(generateTestVectors({ seed, count: vectors.length })).toEqual(corpus);
+  });
+
+  it('agrees with the SDK primitives vector by vector', () => {
+    for (const vector of corpus!.vectors) {
+      const secret = ethers.getBytes(vector.secret);
+      for (const [algorithm, hex] of Object.entries(vector.hashLocks)) {
+        expect(HashLock.fromPreimage(secret, algorithm as keyof typeof vector.hashLocks).hex(), `${algorithm} #${vector.index}`).toBe(hex);
+      }
+      const { id, ...swap } = vector.swapId;
+      expect(deriveSwapId({ ...swap, hashLock: vector.hashLocks.sha256 })).toBe(id);
+      expect(HtlcMsg.claim(vector.secret)).toEqual(vector.claimPayloads.cosmwasm);
+
+      const { domain, message, digest } = vector.eip712.gaslessClaim;
+      expect(typedDataDigest(domain, GASLESS_CLAIM_TYPES, message)).toBe(digest);
+    }
+  });
+});
@@ -0,0 +1,46 @@
+import { describe, it, expect } from 'vitest';
+import { ethers } from 'ethers';
+import { SwapWatcher } from '../watch';
+import { MockChain } from '../testing';
+
+const ALICE = '0x000000000000000000000000000000000000a11c';
+const BOB = '0x0000000000000000000000000000000000000b0b';
+const NOW = 1_700_000_000;
+const SECRET = '0x' + '11'.repeat(32);
+const HASH_LOCK = ethers.sha256(SECRET);
+
+function setup() {
+  const source = MockChain.create('sepolia', { now: NOW });
+  const destination = MockChain.create('osmosisTestnet', { now: NOW, allocateIds: true });
+  return { source, destination };
+}
+
+describe('SwapWatcher', () => {
+  it('reports a completed swap found from its hashlock', async () => {
+    const { source, destination } = setup();
+    const sourceLock = await source.as(ALICE).lock({ contractId: '0x' + 'aa'.repeat(32), beneficiary: BOB, hashLock: HASH_LOCK, timelock: NOW + 7200, amount: '1000' });
+    const destLock = await destination.as('osmo1bob').lock({ contractId: '', beneficiary: 'osmo1alice', hashLock: HASH_LOCK, timelock: NOW + 3600, amount: '500' });
+    await destination.as('osmo1alice').claim(destLock.contractId, SECRET);
+    await source.as(BOB).claim(sourceLock.contractId, SECRET);
+
+    const watcher = new SwapWatcher({ source, destination }, { hashLock: HASH_LOCK }, { pollIntervalMs: 1 });
+    const report = await watcher.run();
+
+    expect(report.outcome).toBe('completed');
+    expect(report.destination.preimage).toBe(SECRET);
+  });
+
+  it('reports a refund when the other side never locked', async () => {
+    const { source, destination } = setup();
+    const contractId = '0x' + 'bb'.repeat(32);
+    await source.as(ALICE).lock({ contractId, beneficiary: BOB, hashLock: HASH_LOCK, timelock: NOW + 600, amount: '1000' });
+    source.advanceTime(601);
+    await source.as(ALICE).refund(contractId);
+
+    const report = await new SwapWatcher({ source, destination }, { swapId: contractId }, { pollIntervalMs: 1 }).run();
+
+    expect(report.outcome).toBe('refunded');
+    expect(report.hashLock).toBe(HASH_LOCK);
+    expect(report.destination.status).toBe('unseen');
+  });
+});
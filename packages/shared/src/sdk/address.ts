@@ -0,0 +1,116 @@
+import { ethers } from 'ethers';
+import { fromBech32, toBech32 } from '@cosmjs/encoding';
+import { ValidationError } from '../utils/errors';
+
+export type AddressKind = 'bech32' | 'evm';
+
+/**
+ * How a Cosmos account's address is derived from its key: plain secp256k1
+ * (ripemd160 of sha256) or ethermint's eth_secp256k1 (keccak, same bytes as EVM)
+ */
+export type AccountKeyType = 'secp256k1' | 'eth_secp256k1';
+
+/**
+ * Whether a string is a bech32 address, optionally with a specific prefix
+ */
+export function isBech32Address(address: string, prefix?: string): boolean {
+  try {
+    const decoded = fromBech32(address);
+    return (prefix === undefined || decoded.prefix === prefix) && (decoded.data.length === 20 || decoded.data.length === 32);
+  } catch {
+    return false;
+  }
+}
+
+export function isEvmAddress(address: string): boolean {
+  return ethers.isAddress(address) && address.startsWith('0x');
+}
+
+export function addressKind(address: string): AddressKind {
+  if (isEvmAddress(address)) return 'evm';
+  if (isBech32Address(address)) return 'bech32';
+  throw new ValidationError('Not a bech32 or EVM address', { address });
+}
+
+/**
+ * The account bytes behind a bech32 address as 0x hex. For 20-byte accounts
+ * this is the checksummed EVM form of the same bytes.
+ */
+export function bech32ToHex(address: string): string {
+  const { data } = decodeBech32(address);
+  return data.length === 20 ? ethers.getAddress(ethers.hexlify(data)) : ethers.hexlify(data);
+}
+
+export function hexToBech32(hex: string, prefix: string): string {
+  const data = ethers.getBytes(hex);
+  if (data.length !== 20 && data.length !== 32) {
+    throw new ValidationError('Account bytes must be 20 or 32 bytes', { length: data.length });
+  }
+  return toBech32(prefix, data);
+}
+
+/**
+ * Same account under another chain's prefix, e.g. cosmos1... -> osmo1...
+ * Only meaningful between chains that derive addresses the same way.
+ */
+export function convertBech32Prefix(address: string, prefix: string): string {
+  return toBech32(prefix, decodeBech32(address).data);
+}
+
+/**
+ * Address derived from a secp256k1 public key (33-byte compressed or 65-byte
+ * uncompressed, hex or bytes) under each scheme:
+ * - cosmos: ripemd160(sha256(compressed key)), as on the Cosmos Hub and Osmosis
+ * - evm: last 20 bytes of keccak256(uncompressed key)
+ * - ethermint: the evm bytes with a bech32 prefix, as on Evmos and Injective
+ */
+export function cosmosAddressFromPubkey(pubkey: string | Uint8Array, prefix: string): string {
+  const compressed = ethers.SigningKey.computePublicKey(pubkey, true);
+  return toBech32(prefix, ethers.getBytes(ethers.ripemd160(ethers.sha256(compressed))));
+}
+
+export function evmAddressFromPubkey(pubkey: string | Uint8Array): string {
+  return ethers.computeAddress(ethers.SigningKey.computePublicKey(pubkey, false));
+}
+
+export function ethermintAddressFromPubkey(pubkey: string | Uint8Array, prefix: string): string {
+  return hexToBech32(evmAddressFromPubkey(pubkey), prefix);
+}
+
+/**
+ * Normalize an address to the form a chain expects: bech32 with its prefix on
+ * Cosmos chains, checksummed 0x on EVM chains. Converting between families only
+ * reaches the same account when the key is eth_secp256k1, which the bytes alone
+ * cannot show, so the caller must assert it with `keyType`.
+ */
+export function toChainAddress(
+  address: string,
+  target: { type: 'evm' } | { type: 'cosmos'; addressPrefix: string },
+  options: { keyType?: AccountKeyType } = {}
+): string {
+  const kind = addressKind(address);
+  const crossFamily = (target.type === 'evm') !== (kind === 'evm');
+  if (crossFamily && options.keyType !== 'eth_secp256k1') {
+    throw new ValidationError('Converting between bech32 and EVM addresses needs an eth_secp256k1 (ethermint) account', {
+      address,
+      keyType: options.keyType ?? 'secp256k1'
+    });
+  }
+
+  if (target.type === 'evm') {
+    const hex = kind === 'evm' ? address : bech32ToHex(address);
+    if (ethers.getBytes(hex).length !== 20) {
+      throw new ValidationError('A 32-byte account has no EVM form', { address });
+    }
+    return ethers.getAddress(hex);
+  }
+  return kind === 'evm' ? hexToBech32(address, target.addressPrefix) : convertBech32Prefix(address, target.addressPrefix);
+}
+
+function decodeBech32(address: string) {
+  try {
+    return fromBech32(address);
+  } catch (error) {
+    throw new ValidationError(`Invalid bech32 address: ${(error as Error).message}`, { address });
+  }
+}
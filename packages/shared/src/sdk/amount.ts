@@ -0,0 +1,161 @@
+import { ValidationError } from '../utils/errors';
+
+/**
+ * What to do with digits lost when moving to fewer decimals:
+ * - exact: refuse, throwing a ValidationError
+ * - floor / ceil: toward zero / away from zero (amounts are never negative)
+ * - half-up / half-even: to nearest, ties away from zero / to even
+ */
+export type Rounding = 'exact' | 'floor' | 'ceil' | 'half-up' | 'half-even';
+
+const MAX_DECIMALS = 77; // 10^77 is the largest power of ten below 2^256
+
+/**
+ * A non-negative token amount in base units with the decimals it is counted
+ * in. Converting between a 6-decimal denom and an 18-decimal ERC-20 goes
+ * through `convert`, which is exact unless a rounding policy says otherwise.
+ * Arithmetic only combines amounts with the same decimals.
+ */
+export class Amount {
+  private constructor(readonly base: bigint, readonly decimals: number, readonly symbol?: string) {}
+
+  static fromBase(base: bigint | string | number, decimals: number, symbol?: string): Amount {
+    checkDecimals(decimals);
+    let value: bigint;
+    try {
+      value = BigInt(base);
+    } catch {
+      throw new ValidationError(`Not an integer amount: ${base}`);
+    }
+    if (value < 0n) throw new ValidationError('Amount cannot be negative', { base: value.toString() });
+    return new Amount(value, decimals, symbol);
+  }
+
+  /**
+   * Parse a whole-unit decimal string such as "1.5". More fraction digits than
+   * `decimals` are rounded by `rounding` (default: refused).
+   */
+  static parse(display: string, decimals: number, options: { symbol?: string; rounding?: Rounding } = {}): Amount {
+    checkDecimals(decimals);
+    const match = /^(\d+)(?:\.(\d*))?$/.exec(display.trim());
+    if (!match) throw new ValidationError(`Not a decimal amount: ${display}`);
+    const fraction = match[2] ?? '';
+    const scale = Math.max(decimals, fraction.length);
+    const exact = new Amount(BigInt(match[1] + fraction.padEnd(scale, '0')), scale, options.symbol);
+    return exact.convert(decimals, options.rounding ?? 'exact');
+  }
+
+  static zero(decimals: number, symbol?: string): Amount {
+    return Amount.fromBase(0n, decimals, symbol);
+  }
+
+  /**
+   * The same amount counted in `decimals`
+   */
+  convert(decimals: number, rounding: Rounding = 'exact'): Amount {
+    checkDecimals(decimals);
+    if (decimals >= this.decimals) {
+      return new Amount(this.base * 10n ** BigInt(decimals - this.decimals), decimals, this.symbol);
+    }
+    const factor = 10n ** BigInt(this.decimals - decimals);
+    const quotient = this.base / factor;
+    const remainder = this.base % factor;
+    if (remainder === 0n) return new Amount(quotient, decimals, this.symbol);
+
+    switch (rounding) {
+      case 'exact':
+        throw new ValidationError(`${this.toString()} cannot be represented with ${decimals} decimals`, {
+          base: this.base.toString(),
+          from: this.decimals,
+          to: decimals
+        });
+      case 'floor':
+        return new Amount(quotient, decimals, this.symbol);
+      case 'ceil':
+        return new Amount(quotient + 1n, decimals, this.symbol);
+      case 'half-up':
+        return new Amount(remainder * 2n >= factor ? quotient + 1n : quotient, decimals, this.symbol);
+      case 'half-even': {
+        const twice = remainder * 2n;
+        const up = twice > factor || (twice === factor && quotient % 2n === 1n);
+        return new Amount(up ? quotient + 1n : quotient, decimals, this.symbol);
+      }
+    }
+  }
+
+  add(other: Amount): Amount {
+    this.checkSameScale(other);
+    return new Amount(this.base + other.base, this.decimals, this.symbol);
+  }
+
+  sub(other: Amount): Amount {
+    this.checkSameScale(other);
+    if (other.base > this.base) {
+      throw new ValidationError(`${other.toString()} is more than ${this.toString()}`);
+    }
+    return new Amount(this.base - other.base, this.decimals, this.symbol);
+  }
+
+  /**
+   * Scale by `numerator / denominator`, e.g. a fee in basis points as (bps, 10000n)
+   */
+  mulDiv(numerator: bigint, denominator: bigint, rounding: Exclude<Rounding, 'exact'> = 'floor'): Amount {
+    if (numerator < 0n || denominator <= 0n) throw new ValidationError('mulDiv needs a non-negative ratio');
+    const product = this.base * numerator;
+    const quotient = product / denominator;
+    const remainder = product % denominator;
+    let result = quotient;
+    if (remainder !== 0n) {
+      if (rounding === 'ceil') result += 1n;
+      if (rounding === 'half-up' && remainder * 2n >= denominator) result += 1n;
+      if (rounding === 'half-even' && (remainder * 2n > denominator || (remainder * 2n === denominator && quotient % 2n === 1n))) result += 1n;
+    }
+    return new Amount(result, this.decimals, this.symbol);
+  }
+
+  /** -1, 0 or 1; amounts with different decimals are compared by value */
+  compare(other: Amount): number {
+    const decimals = Math.max(this.decimals, other.decimals);
+    const a = this.convert(decimals).base;
+    const b = other.convert(decimals).base;
+    return a < b ? -1 : a > b ? 1 : 0;
+  }
+
+  equals(other: Amount): boolean {
+    return this.compare(other) === 0;
+  }
+
+  isZero(): boolean {
+    return this.base === 0n;
+  }
+
+  /** Whole units without trailing zeros, e.g. "1.5" */
+  toDisplay(): string {
+    if (this.decimals === 0) return this.base.toString();
+    const digits = this.base.toString().padStart(this.decimals + 1, '0');
+    const whole = digits.slice(0, -this.decimals);
+    const fraction = digits.slice(-this.decimals).replace(/0+$/, '');
+    return fraction ? `${whole}.${fraction}` : whole;
+  }
+
+  toString(): string {
+    return this.symbol ? `${this.toDisplay()} ${this.symbol}` : this.toDisplay();
+  }
+
+  /** Base units as a decimal string, the form contracts and messages take */
+  toJSON(): string {
+    return this.base.toString();
+  }
+
+  private checkSameScale(other: Amount): void {
+    if (other.decimals !== this.decimals) {
+      throw new ValidationError(`Cannot combine amounts with ${this.decimals} and ${other.decimals} decimals; convert one first`);
+    }
+  }
+}
+
+function checkDecimals(decimals: number): void {
+  if (!Number.isInteger(decimals) || decimals < 0 || decimals > MAX_DECIMALS) {
+    throw new ValidationError(`Invalid decimals: ${decimals}`);
+  }
+}
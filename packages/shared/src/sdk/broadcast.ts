@@ -0,0 +1,170 @@
+import axios from 'axios';
+import { ethers } from 'ethers';
+import type { CosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import type { EncodeObject, OfflineSigner } from '@cosmjs/proto-signing';
+import type { StdFee } from '@cosmjs/stargate';
+import { toBase64 } from '@cosmjs/encoding';
+import { NetworkError, SwapSageError } from '../utils/errors';
+import { parseContractError } from './errors';
+import { signCosmosOffline } from './offline';
+import { cosmWasmPool, FailoverPool } from './rpc';
+import type { FailoverOptions } from './rpc';
+
+/**
+ * How long `send` waits:
+ * - async: until a node has the bytes, before CheckTx
+ * - sync: until CheckTx accepts it into the mempool
+ * - commit: until it is in a block (polled with getTx)
+ */
+export type BroadcastMode = 'async' | 'sync' | 'commit';
+
+export interface BroadcasterOptions extends Pick<FailoverOptions, 'timeoutMs' | 'race'> {
+  /** Tendermint RPC endpoints of the chain, tried in order */
+  endpoints: string[];
+  /** How often to poll for inclusion in commit mode (default 1.5s) */
+  pollIntervalMs?: number;
+  /** How long to wait for inclusion in commit mode (default 60s) */
+  confirmTimeoutMs?: number;
+}
+
+export interface BroadcastResult {
+  txHash: string;
+  mode: BroadcastMode;
+  sequence: number;
+  /** Set in commit mode */
+  height?: number;
+  gasUsed?: bigint;
+  gasWanted?: bigint;
+}
+
+// sdk ErrWrongSequence
+const WRONG_SEQUENCE = 32;
+
+/**
+ * Assembles, signs (SIGN_MODE_DIRECT) and broadcasts Cosmos transactions for one
+ * account. Account number and sequence are fetched from the chain and then
+ * tracked locally, so several transactions can go out back to back; a sequence
+ * mismatch reported by the node resyncs and re-signs once.
+ */
+export class CosmosBroadcaster {
+  private pool: FailoverPool<CosmWasmClient>;
+  private account?: Promise<{ chainId: string; accountNumber: number }>;
+  private nextSequence?: number;
+  private queue: Promise<unknown> = Promise.resolve();
+
+  constructor(private signer: OfflineSigner, readonly address: string, private options: BroadcasterOptions) {
+    this.pool = cosmWasmPool(options.endpoints, {
+      timeoutMs: options.timeoutMs,
+      race: options.race,
+      // A node that ran CheckTx and said no would say the same everywhere
+      isFatal: error => typeof (error as { code?: unknown }).code === 'number'
+    });
+  }
+
+  /**
+   * Sign and broadcast `messages`. Calls are serialized so sequences never collide.
+   */
+  send(messages: EncodeObject[], fee: StdFee, options: { memo?: string; mode?: BroadcastMode } = {}): Promise<BroadcastResult> {
+    const result = this.queue.then(() => this.sendNow(messages, fee, options.memo ?? '', options.mode ?? 'commit'));
+    this.queue = result.catch(() => undefined);
+    return result;
+  }
+
+  /**
+   * Poll until a transaction is in a block. Throws a ContractError (or
+   * SwapSageError) if it was included but failed.
+   */
+  async waitForTx(txHash: string): Promise<Required<Omit<BroadcastResult, 'mode' | 'sequence'>>> {
+    const deadline = Date.now() + (this.options.confirmTimeoutMs ?? 60000);
+    while (Date.now() < deadline) {
+      const tx = await this.pool.read(client => client.getTx(txHash));
+      if (tx) {
+        if (tx.code !== 0) {
+          throw parseContractError(tx.rawLog) ??
+            new SwapSageError(`Transaction ${txHash} failed with code ${tx.code}: ${tx.rawLog}`, 'TX_FAILED', { txHash, code: tx.code });
+        }
+        return { txHash, height: tx.height, gasUsed: tx.gasUsed, gasWanted: tx.gasWanted };
+      }
+      await new Promise(resolve => setTimeout(resolve, this.options.pollIntervalMs ?? 1500));
+    }
+    throw new NetworkError(`Transaction ${txHash} not included within ${this.options.confirmTimeoutMs ?? 60000}ms`, { txHash });
+  }
+
+  /** Forget the tracked sequence, e.g. after sending from the same account elsewhere */
+  resync(): void {
+    this.nextSequence = undefined;
+  }
+
+  disconnect(): void {
+    this.pool.close();
+  }
+
+  private async sendNow(messages: EncodeObject[], fee: StdFee, memo: string, mode: BroadcastMode): Promise<BroadcastResult> {
+    const { chainId, accountNumber } = await this.accountInfo();
+    let sequence = this.nextSequence ?? await this.fetchSequence();
+
+    for (let attempt = 0; ; attempt++) {
+      const tx = await signCosmosOffline(this.signer, this.address, messages, fee, { chainId, accountNumber, sequence }, memo);
+      try {
+        const txHash = await this.broadcast(tx, mode);
+        this.nextSequence = sequence + 1;
+        if (mode !== 'commit') return { txHash, mode, sequence };
+        return { ...await this.waitForTx(txHash), mode, sequence };
+      } catch (error) {
+        const expected = expectedSequence(error);
+        if (expected === undefined || attempt > 0) {
+          this.nextSequence = undefined;
+          throw parseContractError((error as { log?: string }).log) ?? error;
+        }
+        sequence = expected;
+      }
+    }
+  }
+
+  private async broadcast(tx: Uint8Array, mode: BroadcastMode): Promise<string> {
+    if (mode !== 'async') {
+      return this.pool.request(client => client.broadcastTxSync(tx));
+    }
+    // cosmjs clients only expose sync broadcast, so async goes over JSON-RPC
+    return this.pool.request(async (_, endpoint) => {
+      const response = await axios.post(endpoint, {
+        jsonrpc: '2.0',
+        id: 1,
+        method: 'broadcast_tx_async',
+        params: { tx: toBase64(tx) }
+      });
+      if (response.data?.error) {
+        throw new NetworkError(`broadcast_tx_async failed: ${JSON.stringify(response.data.error)}`, { endpoint });
+      }
+      return ethers.sha256(tx).slice(2).toUpperCase();
+    });
+  }
+
+  private accountInfo(): Promise<{ chainId: string; accountNumber: number }> {
+    if (!this.account) {
+      this.account = this.pool.request(async client => {
+        const [chainId, account] = await Promise.all([client.getChainId(), client.getAccount(this.address)]);
+        if (!account) throw new SwapSageError(`Account ${this.address} does not exist on chain yet`, 'ACCOUNT_NOT_FOUND');
+        return { chainId, accountNumber: account.accountNumber };
+      });
+      this.account.catch(() => { this.account = undefined; });
+    }
+    return this.account;
+  }
+
+  private async fetchSequence(): Promise<number> {
+    const account = await this.pool.request(client => client.getAccount(this.address));
+    return account?.sequence ?? 0;
+  }
+}
+
+/**
+ * The sequence a node expected, from an "account sequence mismatch, expected 5, got 4" rejection
+ */
+function expectedSequence(error: unknown): number | undefined {
+  const { code, log, message } = (error ?? {}) as { code?: number; log?: string; message?: string };
+  const text = `${log ?? ''} ${message ?? ''}`;
+  if (code !== WRONG_SEQUENCE && !/sequence mismatch/i.test(text)) return undefined;
+  const match = /expected (\d+)/i.exec(text);
+  return match ? Number(match[1]) : undefined;
+}
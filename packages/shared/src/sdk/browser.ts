@@ -0,0 +1,66 @@
+// Browser-safe subset of the SDK: pure functions over ethers and cosmjs,
+// with no node built-ins, sockets or filesystem access. Web frontends import this
+// to generate secrets, compute hashlocks and build messages with exactly the code
+// the backend runs.
+export { SwapBuilder, HtlcMsg, htlcQueryMsg } from './builder';
+export type { SwapSpec, HtlcQueryResponses, HtlcQueryKind } from './builder';
+export { Secret, withSecret, hashPreimage, HASH_ALGORITHMS } from './secret';
+export type { HashAlgorithm } from './secret';
+export { HashLock } from './hashlock';
+export { deriveSwapId, predictCosmosHtlcAddress, SWAP_ID_DOMAIN } from './swap-id';
+export type { SwapIdParams } from './swap-id';
+export {
+  SwapOrderSchema,
+  EVM_ORDER_TUPLE,
+  toInstantiateMsg,
+  fromSwapResponse,
+  encodeEvmOrder,
+  decodeEvmOrder
+} from './types';
+export type { SwapOrder } from './types';
+export { describeSwap } from './describe';
+export type { DescribeOptions } from './describe';
+export { Amount } from './amount';
+export type { Rounding } from './amount';
+export { MerkleSecrets, merkleLeaf, verifyMerkleProof } from './merkle';
+export type { MerkleFillState } from './merkle';
+export { chainTiming, recommend as recommendTimelocks, checkTimelocks } from './timelock';
+export type { TimelockOptions, TimelockAdvice } from './timelock';
+export { noopTelemetry, emitTelemetry } from './telemetry';
+export type { Telemetry } from './telemetry';
+export { parseTxEvents } from './events';
+export type { SwapEvent, TxEvent } from './events';
+export { ContractError, parseContractError, withContractErrors } from './errors';
+export type { ContractErrorKind } from './errors';
+export { checkLock, checkClaim, checkRefund, assertValid } from './validate';
+export type { ValidationRules } from './validate';
+export { Swap } from './typestate';
+export type { Created, Funded, Claimed, Refunded, LegPhase } from './typestate';
+export { planRoute } from './routing';
+export type { AssetRef, Corridor, ChainTiming, PlanOptions, PlannedLeg, RoutePlan } from './routing';
+export {
+  MINIMAL_FORWARDER_TYPES,
+  ERC2771_FORWARDER_TYPES,
+  GASLESS_CLAIM_TYPES,
+  fetchDomain,
+  minimalForwarderDomain,
+  buildClaimRequest,
+  typedDataDigest,
+  signTypedData,
+  recoverTypedDataSigner,
+  digestSigner
+} from './eip712';
+export type { TypedDataSigner, TypedDataTypes, ForwardRequest } from './eip712';
+export {
+  isBech32Address,
+  isEvmAddress,
+  addressKind,
+  bech32ToHex,
+  hexToBech32,
+  convertBech32Prefix,
+  cosmosAddressFromPubkey,
+  evmAddressFromPubkey,
+  ethermintAddressFromPubkey,
+  toChainAddress
+} from './address';
+export type { AddressKind, AccountKeyType } from './address';
@@ -0,0 +1,223 @@
+import { ethers } from 'ethers';
+import { fromBech32, fromHex, toBase64 } from '@cosmjs/encoding';
+import type { CosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import type { ChainConfig } from '../chains';
+import type { ExecuteMsg, InstantiateMsg, QueryMsg, SwapResponse } from '../clients/cosmos';
+import type { CreateHTLCParams } from '../utils/htlc';
+import { ValidationError } from '../utils/errors';
+import { validateContractId, validateHashLock, validatePreimage } from '../utils/validation';
+import { HashLock } from './hashlock';
+import { toInstantiateMsg } from './types';
+
+/**
+ * A validated, chain-agnostic description of one HTLC leg
+ */
+export interface SwapSpec {
+  chain: ChainConfig;
+  /** Funder; required on Cosmos, where it is part of the instantiate message */
+  sender?: string;
+  beneficiary: string;
+  /** 0x-prefixed 32-byte hash */
+  hashLock: string;
+  /** Unix seconds */
+  timelock: number;
+  /** Base units */
+  amount: string;
+  /** ERC-20 address or CW20 contract; omitted for the chain's native asset */
+  token?: string;
+  /** Caller-chosen HTLC ID; required on EVM */
+  contractId?: string;
+}
+
+/**
+ * Fluent builder for HTLC creation messages. Every field is checked against the
+ * rules the contracts enforce before anything is produced, so a built message
+ * only fails on-chain for reasons the builder cannot see (balances, clocks).
+ *
+ *   const msg = SwapBuilder.on(cosmosChains.osmosisTestnet)
+ *     .sender(me).beneficiary(them).hashLock(lock).expiresIn(3600).amount('1000000')
+ *     .toInstantiateMsg();
+ */
+export class SwapBuilder {
+  private spec: Partial<SwapSpec>;
+
+  private constructor(chain: ChainConfig) {
+    this.spec = { chain };
+  }
+
+  static on(chain: ChainConfig): SwapBuilder {
+    return new SwapBuilder(chain);
+  }
+
+  sender(address: string): this {
+    this.spec.sender = address;
+    return this;
+  }
+
+  beneficiary(address: string): this {
+    this.spec.beneficiary = address;
+    return this;
+  }
+
+  /**
+   * Both HTLC contracts verify sha256, so a HashLock under any other algorithm is refused
+   * rather than producing a leg that can never be claimed
+   */
+  hashLock(hashLock: string | HashLock): this {
+    if (hashLock instanceof HashLock) {
+      if (hashLock.algorithm !== 'sha256') {
+        throw new ValidationError(`The HTLC contracts verify sha256 hashlocks, not ${hashLock.algorithm}`);
+      }
+      hashLock = hashLock.toEvm();
+    }
+    this.spec.hashLock = hashLock;
+    return this;
+  }
+
+  timelock(unixSeconds: number): this {
+    this.spec.timelock = unixSeconds;
+    return this;
+  }
+
+  expiresIn(seconds: number, now: number = Math.floor(Date.now() / 1000)): this {
+    return this.timelock(now + seconds);
+  }
+
+  amount(baseUnits: string | bigint): this {
+    this.spec.amount = baseUnits.toString();
+    return this;
+  }
+
+  token(token: string): this {
+    this.spec.token = token;
+    return this;
+  }
+
+  contractId(contractId: string): this {
+    this.spec.contractId = contractId;
+    return this;
+  }
+
+  /**
+   * Validate and return the spec
+   */
+  build(now: number = Math.floor(Date.now() / 1000)): SwapSpec {
+    const { chain, sender, beneficiary, hashLock, timelock, amount, token, contractId } = this.spec;
+    if (!chain) throw new ValidationError('Chain is required');
+    if (!beneficiary) throw new ValidationError('Beneficiary is required');
+    if (!hashLock) throw new ValidationError('Hash lock is required');
+    if (timelock === undefined) throw new ValidationError('Timelock is required');
+    if (!amount) throw new ValidationError('Amount is required');
+
+    validateHashLock(hashLock);
+    if (!Number.isInteger(timelock) || timelock <= now) {
+      throw new ValidationError('Timelock must be in the future', { timelock, now });
+    }
+    if (!/^\d+$/.test(amount) || BigInt(amount) === 0n) {
+      throw new ValidationError('Amount must be a positive integer in base units', { amount });
+    }
+
+    if (chain.type === 'cosmos') {
+      if (!sender) throw new ValidationError('Sender is required on Cosmos chains');
+      checkBech32(sender, 'Sender');
+      checkBech32(beneficiary, 'Beneficiary');
+      if (token) checkBech32(token, 'Token');
+    } else {
+      if (!contractId) throw new ValidationError('Contract ID is required on EVM chains');
+      validateContractId(contractId);
+      checkEvmAddress(beneficiary, 'Beneficiary');
+      if (sender) checkEvmAddress(sender, 'Sender');
+      if (token && token !== ethers.ZeroAddress) checkEvmAddress(token, 'Token');
+    }
+
+    return { chain, sender, beneficiary, hashLock, timelock, amount, token, contractId };
+  }
+
+  /**
+   * CosmWasm instantiate message; each HTLC is its own contract instance
+   */
+  toInstantiateMsg(now?: number): InstantiateMsg {
+    const spec = this.build(now);
+    if (spec.chain.type !== 'cosmos') {
+      throw new ValidationError(`${spec.chain.name} is not a Cosmos chain`);
+    }
+    return toInstantiateMsg({
+      chain: spec.chain.name,
+      originator: spec.sender!,
+      beneficiary: spec.beneficiary,
+      hashLock: spec.hashLock,
+      timelock: spec.timelock,
+      amount: spec.amount,
+      token: spec.token
+    });
+  }
+
+  /**
+   * Arguments for SimpleHTLC.fund / fundETH
+   */
+  toCreateParams(now?: number): CreateHTLCParams {
+    const spec = this.build(now);
+    if (spec.chain.type !== 'evm') {
+      throw new ValidationError(`${spec.chain.name} is not an EVM chain`);
+    }
+    return {
+      contractId: spec.contractId!,
+      beneficiary: spec.beneficiary,
+      hashLock: spec.hashLock,
+      timelock: spec.timelock,
+      value: spec.amount,
+      token: spec.token || ethers.ZeroAddress
+    };
+  }
+}
+
+/**
+ * Execute messages for an existing CosmWasm HTLC
+ */
+export const HtlcMsg = {
+  fund: (): ExecuteMsg => ({ fund: {} }),
+  /** Preimage as 0x-prefixed hex; the contract takes it base64-encoded */
+  claim: (preimage: string): ExecuteMsg => {
+    validatePreimage(preimage);
+    return { claim: { preimage: toBase64(fromHex(preimage.slice(2))) } };
+  },
+  refund: (): ExecuteMsg => ({ refund: {} })
+};
+
+/** Response type of each CosmWasm HTLC query */
+export interface HtlcQueryResponses {
+  get_swap: SwapResponse;
+  is_claimable: boolean;
+  is_refundable: boolean;
+}
+
+export type HtlcQueryKind = keyof HtlcQueryResponses;
+
+export function htlcQueryMsg(kind: HtlcQueryKind): QueryMsg {
+  return { [kind]: {} } as QueryMsg;
+}
+
+/**
+ * Run a smart query against an HTLC instance with its response typed by the query kind
+ */
+export function queryHtlc<K extends HtlcQueryKind>(
+  client: CosmWasmClient,
+  contractAddress: string,
+  kind: K
+): Promise<HtlcQueryResponses[K]> {
+  return client.queryContractSmart(contractAddress, htlcQueryMsg(kind));
+}
+
+function checkBech32(address: string, name: string): void {
+  try {
+    fromBech32(address);
+  } catch {
+    throw new ValidationError(`${name} is not a valid bech32 address`, { address });
+  }
+}
+
+function checkEvmAddress(address: string, name: string): void {
+  if (!ethers.isAddress(address) || address === ethers.ZeroAddress) {
+    throw new ValidationError(`${name} is not a valid EVM address`, { address });
+  }
+}
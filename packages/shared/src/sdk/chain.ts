@@ -0,0 +1,123 @@
+import type { ChainAdapter, HTLCEvent, Unsubscribe } from '../resolver/adapters/types';
+import type { HTLCDetails } from '../utils/htlc';
+import { assertValid, checkClaim, checkLock, checkRefund } from './validate';
+import type { ValidationRules } from './validate';
+
+export interface LockParams {
+  /** Requested HTLC ID; chains that allocate their own (CosmWasm) return a different one */
+  contractId: string;
+  beneficiary: string;
+  hashLock: string;
+  timelock: number;
+  amount: string;
+  token?: string;
+}
+
+export interface SubmittedTx {
+  txHash: string;
+}
+
+/**
+ * What the SDK needs from one chain to drive or watch a swap. A ChainAdapter
+ * from the resolver can be wrapped with `fromChainAdapter`; tests can use
+ * `MockChain` from `@swap-sage/shared/sdk/testing`.
+ */
+export interface SwapChain {
+  readonly chain: string;
+  /** Account this chain signs with; undefined when watch-only */
+  readonly address?: string;
+
+  lock(params: LockParams): Promise<SubmittedTx & { contractId: string }>;
+  claim(contractId: string, preimage: string): Promise<SubmittedTx>;
+  refund(contractId: string): Promise<SubmittedTx>;
+  getHtlc(contractId: string): Promise<HTLCDetails | null>;
+  /** An HTLC locked with this hashlock since the chain was wrapped, if one has been seen */
+  findHtlc(hashLock: string, beneficiary?: string): Promise<HTLCDetails | null>;
+  /** Preimage revealed by a claim of this HTLC, if it has been claimed */
+  revealedPreimage(contractId: string): Promise<string | null>;
+  currentTime(): Promise<number>;
+}
+
+/**
+ * Wrap a resolver ChainAdapter. HTLCs are discovered from the adapter's event
+ * stream, so `findHtlc` only sees locks made after the wrapper was created (or
+ * after `fromHeight`). Call `close()` to stop the subscription.
+ *
+ * Locks, claims and refunds are checked against the contract rules before they
+ * are broadcast and throw a ContractError instead of paying for a failed tx;
+ * pass `validation: false` to skip that.
+ */
+export async function fromChainAdapter(
+  adapter: ChainAdapter,
+  options: { fromHeight?: number; pollIntervalMs?: number; validation?: ValidationRules | false } = {}
+): Promise<SwapChain & { close(): void }> {
+  const funded = new Map<string, Extract<HTLCEvent, { kind: 'funded' }>>();
+  const preimages = new Map<string, string>();
+  const validate = options.validation !== false;
+  // Only SimpleHTLC's caller rules are known; CosmWasm callers are left to the contract
+  const evm = adapter.config.type === 'evm';
+
+  const unsubscribe: Unsubscribe = await adapter.subscribeEvents(event => {
+    if (event.kind === 'funded') funded.set(event.contractId, event);
+    if (event.kind === 'claimed') preimages.set(event.contractId, event.preimage);
+  }, options);
+
+  return {
+    chain: adapter.chain,
+    address: adapter.address,
+
+    async lock(params) {
+      if (validate) assertValid(checkLock(adapter.config, params, await adapter.currentTime(), options.validation || {}));
+      const pending = await adapter.submitFund({
+        contractId: params.contractId,
+        beneficiary: params.beneficiary,
+        hashLock: params.hashLock,
+        timelock: params.timelock,
+        value: params.amount,
+        token: params.token
+      });
+      await pending.wait();
+      return { txHash: pending.txHash, contractId: pending.contractId };
+    },
+
+    async claim(contractId, preimage) {
+      if (validate) {
+        const [htlc, now] = await Promise.all([adapter.getSwap(contractId), adapter.currentTime()]);
+        assertValid(checkClaim(htlc, preimage, now, evm ? adapter.address : undefined));
+      }
+      const pending = await adapter.submitClaim(contractId, preimage);
+      await pending.wait();
+      return { txHash: pending.txHash };
+    },
+
+    async refund(contractId) {
+      if (validate) {
+        const [htlc, now] = await Promise.all([adapter.getSwap(contractId), adapter.currentTime()]);
+        assertValid(checkRefund(htlc, now, evm ? adapter.address : undefined));
+      }
+      const pending = await adapter.submitRefund(contractId);
+      await pending.wait();
+      return { txHash: pending.txHash };
+    },
+
+    getHtlc: contractId => adapter.getSwap(contractId),
+
+    async findHtlc(hashLock, beneficiary) {
+      // Event hashlocks are in each chain's native encoding; getSwap normalizes to hex
+      for (const event of funded.values()) {
+        if (beneficiary && event.beneficiary.toLowerCase() !== beneficiary.toLowerCase()) continue;
+        const details = await adapter.getSwap(event.contractId);
+        if (details && details.hashLock.toLowerCase() === hashLock.toLowerCase()) return details;
+      }
+      return null;
+    },
+
+    async revealedPreimage(contractId) {
+      return preimages.get(contractId) ?? null;
+    },
+
+    currentTime: () => adapter.currentTime(),
+
+    close: unsubscribe
+  };
+}
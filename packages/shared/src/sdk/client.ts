@@ -0,0 +1,152 @@
+import type { CosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import type { SwapResponse } from '../clients/cosmos';
+import { NetworkError } from '../utils/errors';
+import { SwapState } from '../utils/htlc';
+import { queryHtlc } from './builder';
+import { HashLock } from './hashlock';
+import { cosmWasmPool } from './rpc';
+import type { EndpointStatus, FailoverPool } from './rpc';
+import { SwapEventSubscription } from './subscription';
+import type { SubscribeOptions } from './subscription';
+
+/**
+ * On-chain state of a CosmWasm HTLC instance, decoded from the contract's response
+ */
+export interface HtlcSwap {
+  address: string;
+  sender: string;
+  beneficiary: string;
+  /** 0x-prefixed hex */
+  hashLock: string;
+  timelock: number;
+  amount: string;
+  token?: string;
+  state: SwapState;
+}
+
+export interface HtlcClientOptions {
+  /** RPC endpoints, tried in order; the first that answers is preferred until it fails */
+  endpoints: string[];
+  /** Code ID of the HTLC contract; needed for listSwaps */
+  codeId?: number;
+  /** Per-request timeout (default 10s) */
+  timeoutMs?: number;
+  /** Query every endpoint at once and take the first answer */
+  race?: boolean;
+  /** Probe endpoints in the background at this interval; off by default */
+  healthCheckIntervalMs?: number;
+}
+
+const STATES: Record<SwapResponse['state'], SwapState> = {
+  Open: SwapState.OPEN,
+  Claimed: SwapState.CLAIMED,
+  Refunded: SwapState.REFUNDED
+};
+
+export function decodeSwapResponse(address: string, response: SwapResponse): HtlcSwap {
+  return {
+    address,
+    sender: response.sender,
+    beneficiary: response.beneficiary,
+    hashLock: HashLock.fromCosmos(response.hash_lock).hex(),
+    timelock: response.timelock,
+    amount: response.amount,
+    ...(response.token ? { token: response.token } : {}),
+    state: STATES[response.state] ?? SwapState.INVALID
+  };
+}
+
+/**
+ * Read-only client for CosmWasm HTLC instances. Requests go to the current
+ * endpoint and move on to the next one when it errors or times out.
+ */
+export class HtlcClient {
+  private pool: FailoverPool<CosmWasmClient>;
+
+  constructor(private options: HtlcClientOptions) {
+    if (options.endpoints.length === 0) {
+      throw new Error('HtlcClient needs at least one endpoint');
+    }
+    this.pool = cosmWasmPool(options.endpoints, {
+      timeoutMs: options.timeoutMs,
+      race: options.race,
+      isFatal: error => isContractError(error.message)
+    });
+    if (options.healthCheckIntervalMs) {
+      this.pool.startHealthChecks(options.healthCheckIntervalMs);
+    }
+  }
+
+  /**
+   * The swap at a contract address, or null if it is not an HTLC instance
+   */
+  async getSwap(address: string): Promise<HtlcSwap | null> {
+    try {
+      return decodeSwapResponse(address, await this.request(client => queryHtlc(client, address, 'get_swap')));
+    } catch (error) {
+      if (error instanceof NetworkError) throw error;
+      return null;
+    }
+  }
+
+  isClaimable(address: string): Promise<boolean> {
+    return this.request(client => queryHtlc(client, address, 'is_claimable'));
+  }
+
+  isRefundable(address: string): Promise<boolean> {
+    return this.request(client => queryHtlc(client, address, 'is_refundable'));
+  }
+
+  /**
+   * Every instance of the HTLC code, optionally filtered by state
+   */
+  async listSwaps(filter: { state?: SwapState } = {}): Promise<HtlcSwap[]> {
+    const codeId = this.options.codeId;
+    if (codeId === undefined) {
+      throw new Error('listSwaps needs the HTLC code ID');
+    }
+
+    const addresses = await this.request(client => client.getContracts(codeId));
+    const swaps: HtlcSwap[] = [];
+    for (const address of addresses) {
+      const swap = await this.getSwap(address);
+      if (swap && (filter.state === undefined || swap.state === filter.state)) {
+        swaps.push(swap);
+      }
+    }
+    return swaps;
+  }
+
+  /**
+   * Live swap events over the endpoints' Tendermint websockets. Gaps left by a
+   * reconnect are backfilled from tx search on the same endpoints.
+   */
+  subscribe(options: SubscribeOptions = {}): SwapEventSubscription {
+    return new SwapEventSubscription(
+      this.options.endpoints,
+      fromHeight => this.request(client => client.searchTx(`tx.height>=${fromHeight} AND wasm.method EXISTS`)),
+      options
+    );
+  }
+
+  /** Health of each endpoint as last observed */
+  endpointStatus(): EndpointStatus[] {
+    return this.pool.status();
+  }
+
+  disconnect(): void {
+    this.pool.close();
+  }
+
+  /**
+   * Run a query with failover. Contract errors (the query reached a node and was
+   * rejected) are returned as-is; only transport failures move to the next endpoint.
+   */
+  private request<T>(query: (client: CosmWasmClient) => Promise<T>): Promise<T> {
+    return this.pool.read(query);
+  }
+}
+
+function isContractError(message: string): boolean {
+  return /query wasm contract failed|not found|unknown variant|Error parsing/i.test(message);
+}
@@ -0,0 +1,93 @@
+import { ethers } from 'ethers';
+import { allChains } from '../chains';
+import { SwapState } from '../utils/htlc';
+import type { SwapOrder } from './types';
+
+export interface DescribeOptions {
+  /** BCP 47 locale for numbers and dates (default 'en-US') */
+  locale?: string;
+  /** IANA time zone for the expiry (default 'UTC') */
+  timeZone?: string;
+  /** Display symbol and decimals of the locked asset; micro-denoms on Cosmos chains are inferred */
+  asset?: { symbol: string; decimals: number };
+  /** Labels for known addresses, e.g. { 'osmo1…': 'Alice' } */
+  names?: Record<string, string>;
+  /** Unix seconds to judge expiry against (default now) */
+  now?: number;
+}
+
+/**
+ * One-line summary of a swap for CLIs and support tooling, e.g.
+ * "Alice locks 1.0 ATOM for Bob, claimable until Jul 1, 2024, 2:00 PM UTC with
+ * SHA-256 hashlock 0xab12…cdef". Wording is English; amounts and dates follow
+ * `locale`.
+ */
+export function describeSwap(order: SwapOrder, options: DescribeOptions = {}): string {
+  const locale = options.locale ?? 'en-US';
+  const now = options.now ?? Math.floor(Date.now() / 1000);
+  const sender = label(order.originator, options.names);
+  const beneficiary = label(order.beneficiary, options.names);
+  const amount = formatAsset(order, locale, options.asset);
+  const until = formatTime(order.timelock, locale, options.timeZone ?? 'UTC');
+  const hashLock = `SHA-256 hashlock ${abbreviate(order.hashLock)}`;
+
+  switch (order.state) {
+    case SwapState.CLAIMED:
+      return `${beneficiary} claimed ${amount} from ${sender} (${hashLock})`;
+    case SwapState.REFUNDED:
+      return `${sender} was refunded ${amount} after ${beneficiary} did not claim by ${until} (${hashLock})`;
+    default:
+      return order.timelock < now
+        ? `${sender} locked ${amount} for ${beneficiary}; expired ${until}, refundable by ${sender} (${hashLock})`
+        : `${sender} locks ${amount} for ${beneficiary}, claimable until ${until} with ${hashLock}`;
+  }
+}
+
+function formatAsset(order: SwapOrder, locale: string, asset?: { symbol: string; decimals: number }): string {
+  const known = asset ?? inferAsset(order);
+  if (!known) {
+    return `${formatDecimal(order.amount, locale)} ${order.token ? abbreviate(order.token) : 'base units'}`;
+  }
+  return `${formatDecimal(ethers.formatUnits(order.amount, known.decimals), locale)} ${known.symbol}`;
+}
+
+function inferAsset(order: SwapOrder): { symbol: string; decimals: number } | undefined {
+  const chain = allChains[order.chain];
+  const denom = order.token ?? (chain?.type === 'cosmos' ? chain.nativeDenom : undefined);
+  if (denom && /^u[a-z]{2,}$/.test(denom)) {
+    return { symbol: denom.slice(1).toUpperCase(), decimals: 6 };
+  }
+  return undefined;
+}
+
+/**
+ * Group and punctuate a decimal string for `locale` without going through a
+ * float, so large 18-decimal amounts keep every digit
+ */
+function formatDecimal(value: string, locale: string): string {
+  const [whole, fraction] = value.split('.');
+  const decimalSeparator = new Intl.NumberFormat(locale).formatToParts(1.5).find(part => part.type === 'decimal')?.value ?? '.';
+  const grouped = BigInt(whole).toLocaleString(locale);
+  return fraction ? `${grouped}${decimalSeparator}${fraction}` : grouped;
+}
+
+function formatTime(timestamp: number, locale: string, timeZone: string): string {
+  return new Intl.DateTimeFormat(locale, {
+    year: 'numeric',
+    month: 'short',
+    day: 'numeric',
+    hour: 'numeric',
+    minute: '2-digit',
+    timeZone,
+    timeZoneName: 'short'
+  }).format(new Date(timestamp * 1000));
+}
+
+function label(address: string, names?: Record<string, string>): string {
+  const name = names?.[address] ?? names?.[address.toLowerCase()];
+  return name ?? abbreviate(address);
+}
+
+function abbreviate(value: string): string {
+  return value.length > 14 ? `${value.slice(0, 6)}…${value.slice(-4)}` : value;
+}
@@ -0,0 +1,165 @@
+import { ethers } from 'ethers';
+
+export type TypedDataTypes = Record<string, ethers.TypedDataField[]>;
+
+/**
+ * Anything that can produce an EIP-712 signature. An ethers Wallet or
+ * JsonRpcSigner already fits; `digestSigner` adapts a raw secp256k1 signer
+ * (HSM, KMS, remote service).
+ */
+export interface TypedDataSigner {
+  getAddress(): Promise<string>;
+  signTypedData(domain: ethers.TypedDataDomain, types: TypedDataTypes, value: Record<string, unknown>): Promise<string>;
+}
+
+/** OpenZeppelin 4 MinimalForwarder request */
+export const MINIMAL_FORWARDER_TYPES: TypedDataTypes = {
+  ForwardRequest: [
+    { name: 'from', type: 'address' },
+    { name: 'to', type: 'address' },
+    { name: 'value', type: 'uint256' },
+    { name: 'gas', type: 'uint256' },
+    { name: 'nonce', type: 'uint256' },
+    { name: 'data', type: 'bytes' }
+  ]
+};
+
+/** OpenZeppelin 5 ERC2771Forwarder request, used by FusionForwarder */
+export const ERC2771_FORWARDER_TYPES: TypedDataTypes = {
+  ForwardRequest: [
+    { name: 'from', type: 'address' },
+    { name: 'to', type: 'address' },
+    { name: 'value', type: 'uint256' },
+    { name: 'gas', type: 'uint256' },
+    { name: 'nonce', type: 'uint256' },
+    { name: 'deadline', type: 'uint48' },
+    { name: 'data', type: 'bytes' }
+  ]
+};
+
+/** Gas relayer claim request, in the field order of executeGaslessClaim's tuple */
+export const GASLESS_CLAIM_TYPES: TypedDataTypes = {
+  ClaimRequest: [
+    { name: 'htlcContract', type: 'address' },
+    { name: 'contractId', type: 'bytes32' },
+    { name: 'preimage', type: 'bytes32' },
+    { name: 'beneficiary', type: 'address' },
+    { name: 'maxGasPrice', type: 'uint256' },
+    { name: 'gasCompensation', type: 'uint256' },
+    { name: 'nonce', type: 'uint256' },
+    { name: 'deadline', type: 'uint256' }
+  ]
+};
+
+const ERC5267_ABI = [
+  'function eip712Domain() view returns (bytes1 fields, string name, string version, uint256 chainId, address verifyingContract, bytes32 salt, uint256[] extensions)'
+];
+const FORWARDER_NONCE_ABI = ['function nonces(address owner) view returns (uint256)', 'function getNonce(address from) view returns (uint256)'];
+const HTLC_FORWARDER_ABI = ['function claim(bytes32 contractId, bytes32 preimage)', 'function refund(bytes32 contractId)'];
+
+/**
+ * The domain a contract verifies against, read from its ERC-5267 `eip712Domain()`
+ * so name and version never drift from the deployment
+ */
+export async function fetchDomain(provider: ethers.Provider, verifyingContract: string): Promise<ethers.TypedDataDomain> {
+  const contract = new ethers.Contract(verifyingContract, ERC5267_ABI, provider);
+  const [fields, name, version, chainId, address, salt] = await contract.eip712Domain();
+  // Bit i of `fields` marks which of name, version, chainId, verifyingContract, salt are in use
+  const used = (bit: number) => (Number(fields) & (1 << bit)) !== 0;
+  return {
+    ...(used(0) ? { name } : {}),
+    ...(used(1) ? { version } : {}),
+    ...(used(2) ? { chainId } : {}),
+    ...(used(3) ? { verifyingContract: address } : {}),
+    ...(used(4) ? { salt } : {})
+  };
+}
+
+/** Domain of an OpenZeppelin 4 MinimalForwarder, which predates ERC-5267 */
+export function minimalForwarderDomain(chainId: number | bigint, verifyingContract: string): ethers.TypedDataDomain {
+  return { name: 'MinimalForwarder', version: '0.0.1', chainId, verifyingContract };
+}
+
+export interface ForwardRequest {
+  from: string;
+  to: string;
+  value: bigint;
+  gas: bigint;
+  nonce: bigint;
+  /** Unix seconds; only on ERC2771Forwarder */
+  deadline?: number;
+  data: string;
+}
+
+/**
+ * A forwarder request that claims through an ERC-2771 HTLC forwarder, so the
+ * beneficiary signs and a relayer pays the gas. The nonce is read from the forwarder.
+ */
+export async function buildClaimRequest(
+  provider: ethers.Provider,
+  params: { forwarder: string; htlcForwarder: string; from: string; contractId: string; preimage: string; gas?: bigint; deadline?: number }
+): Promise<ForwardRequest> {
+  const forwarder = new ethers.Contract(params.forwarder, FORWARDER_NONCE_ABI, provider);
+  const nonce: bigint = params.deadline !== undefined
+    ? await forwarder.nonces(params.from)
+    : await forwarder.getNonce(params.from);
+  return {
+    from: params.from,
+    to: params.htlcForwarder,
+    value: 0n,
+    gas: params.gas ?? 200000n,
+    nonce,
+    ...(params.deadline !== undefined ? { deadline: params.deadline } : {}),
+    data: new ethers.Interface(HTLC_FORWARDER_ABI).encodeFunctionData('claim', [params.contractId, params.preimage])
+  };
+}
+
+/**
+ * EIP-712 digest the contract recovers the signer from
+ */
+export function typedDataDigest(domain: ethers.TypedDataDomain, types: TypedDataTypes, value: Record<string, unknown>): string {
+  return ethers.TypedDataEncoder.hash(domain, types, value);
+}
+
+/**
+ * Sign typed data, checking first that the signer is the account the struct
+ * names (`from` / `beneficiary`), since the contract would reject anyone else
+ */
+export async function signTypedData(
+  signer: TypedDataSigner,
+  domain: ethers.TypedDataDomain,
+  types: TypedDataTypes,
+  value: Record<string, unknown>
+): Promise<string> {
+  const expected = (value.from ?? value.beneficiary) as string | undefined;
+  const address = await signer.getAddress();
+  if (expected && expected.toLowerCase() !== address.toLowerCase()) {
+    throw new Error(`Request is for ${expected} but the signer is ${address}`);
+  }
+  return signer.signTypedData(domain, types, value);
+}
+
+export function recoverTypedDataSigner(
+  domain: ethers.TypedDataDomain,
+  types: TypedDataTypes,
+  value: Record<string, unknown>,
+  signature: string
+): string {
+  return ethers.verifyTypedData(domain, types, value, signature);
+}
+
+/**
+ * Adapt a signer that only signs 32-byte digests and returns (r, s, v) or a
+ * 65-byte signature
+ */
+export function digestSigner(
+  address: string,
+  signDigest: (digest: string) => Promise<string | ethers.SignatureLike>
+): TypedDataSigner {
+  return {
+    getAddress: async () => address,
+    async signTypedData(domain, types, value) {
+      return ethers.Signature.from(await signDigest(typedDataDigest(domain, types, value))).serialized;
+    }
+  };
+}
@@ -0,0 +1,81 @@
+import { SwapSageError } from '../utils/errors';
+
+export type ContractErrorKind =
+  | 'InvalidPreimage'
+  | 'TimelockExpired'
+  | 'TimelockNotExpired'
+  | 'InvalidTimelock'
+  | 'InvalidHashLock'
+  | 'Unauthorized'
+  | 'AlreadyExists'
+  | 'NotFound'
+  | 'NotOpen'
+  | 'InvalidAmount'
+  | 'InvalidAddress'
+  | 'TransferFailed';
+
+/**
+ * How each kind is reported. Variant names come first so a contract that
+ * emits them verbatim is matched exactly; the phrases cover the SimpleHTLC
+ * revert reasons and the CosmWasm HTLC's error messages.
+ */
+const PATTERNS: [ContractErrorKind, RegExp][] = [
+  ['InvalidPreimage', /\bInvalidPreimage\b|invalid preimage|preimage (does not|doesn't) match/i],
+  ['TimelockNotExpired', /\bTimelockNotExpired\b|has not expired|not (yet )?expired|not refundable/i],
+  ['TimelockExpired', /\bTimelockExpired\b|has expired|swap expired|timelock expired/i],
+  ['InvalidTimelock', /\bInvalidTimelock\b|timelock must be in the future/i],
+  ['InvalidHashLock', /\bInvalidHashLock\b|invalid hash ?lock|hash ?lock must be 32 bytes/i],
+  ['Unauthorized', /\bUnauthorized\b|only (the )?(beneficiary|originator|sender)/i],
+  ['AlreadyExists', /\bAlreadyExists\b|already exists/i],
+  ['NotFound', /\bNotFound\b|does not exist|contract: not found/i],
+  ['NotOpen', /\bNotOpen\b|is not pending|already (claimed|refunded)|not open/i],
+  ['InvalidAmount', /\bInvalidAmount\b|value must be greater than 0|invalid (amount|funds)|no funds/i],
+  ['InvalidAddress', /\bInvalidAddress\b|cannot be zero address|invalid address/i],
+  ['TransferFailed', /\bTransferFailed\b|transfer failed/i]
+];
+
+/**
+ * An HTLC contract rejected the call. `kind` is stable across chains, so callers
+ * can branch on it instead of on message text; `raw` keeps what the node said.
+ */
+export class ContractError extends SwapSageError {
+  constructor(readonly kind: ContractErrorKind, readonly raw: string, context?: Record<string, unknown>) {
+    super(`${kind}: ${raw}`, 'CONTRACT_ERROR', context);
+    this.name = 'ContractError';
+  }
+}
+
+/**
+ * Recognize an HTLC contract error in whatever a failed call threw: an ethers
+ * CALL_EXCEPTION (revert reason), a cosmjs broadcast or query error, or a plain
+ * message. Returns null for anything else, e.g. transport failures.
+ */
+export function parseContractError(error: unknown): ContractError | null {
+  if (error instanceof ContractError) return error;
+  const raw = errorText(error);
+  if (!raw) return null;
+  for (const [kind, pattern] of PATTERNS) {
+    if (pattern.test(raw)) return new ContractError(kind, raw);
+  }
+  return null;
+}
+
+/**
+ * Run a contract call, rethrowing recognized contract errors as ContractError
+ */
+export async function withContractErrors<T>(call: () => Promise<T>): Promise<T> {
+  try {
+    return await call();
+  } catch (error) {
+    throw parseContractError(error) ?? error;
+  }
+}
+
+function errorText(error: unknown): string {
+  if (typeof error === 'string') return error;
+  if (!error || typeof error !== 'object') return '';
+  const { reason, shortMessage, message, rawLog } = error as Record<string, unknown>;
+  // ethers puts the decoded revert string in `reason`; DeliverTx failures carry the log
+  const text = [reason, rawLog, shortMessage, message].find(value => typeof value === 'string' && value.length > 0);
+  return (text as string | undefined) ?? '';
+}
@@ -0,0 +1,115 @@
+import { ethers } from 'ethers';
+import { fromBase64 } from '@cosmjs/encoding';
+
+/** A Tendermint event as cosmjs returns it from getTx / searchTx / broadcast results */
+export interface TxEvent {
+  type: string;
+  attributes: readonly { key: string; value: string }[];
+}
+
+interface EventBase {
+  /** HTLC instance that emitted the event */
+  contractAddress: string;
+}
+
+export type SwapEvent =
+  | EventBase & { kind: 'created'; codeId?: number }
+  | EventBase & {
+      kind: 'funded';
+      sender?: string;
+      beneficiary?: string;
+      /** 0x hex */
+      hashLock?: string;
+      timelock?: number;
+      amount?: string;
+      token?: string;
+    }
+  | EventBase & { kind: 'claimed'; claimer?: string; /** 0x hex */ preimage: string }
+  | EventBase & { kind: 'refunded'; refunder?: string };
+
+const METHODS: Record<string, SwapEvent['kind']> = {
+  instantiate: 'funded', // the HTLC is funded by its instantiate message
+  fund: 'funded',
+  claim: 'claimed',
+  refund: 'refunded'
+};
+
+/**
+ * Turn a transaction's events into typed swap events, in emission order.
+ * Attributes are looked up by key, so their order does not matter and keys the
+ * contract adds later are ignored; events from other contracts, or wasm events
+ * without a recognized method, are skipped. Pass `contracts` to keep only events
+ * from those HTLC instances.
+ */
+export function parseTxEvents(events: readonly TxEvent[], options: { contracts?: string[] } = {}): SwapEvent[] {
+  const wanted = options.contracts ? new Set(options.contracts) : undefined;
+  const parsed: SwapEvent[] = [];
+
+  for (const event of events) {
+    const attributes = attributeMap(event);
+    const contractAddress = attributes._contract_address;
+    if (!contractAddress || (wanted && !wanted.has(contractAddress))) continue;
+
+    if (event.type === 'instantiate') {
+      const codeId = attributes.code_id ? parseInt(attributes.code_id) : undefined;
+      parsed.push({ kind: 'created', contractAddress, ...(codeId !== undefined ? { codeId } : {}) });
+      continue;
+    }
+    if (event.type !== 'wasm') continue;
+
+    const kind = METHODS[attributes.method ?? attributes.action ?? ''];
+    switch (kind) {
+      case 'funded':
+        parsed.push(compact({
+          kind,
+          contractAddress,
+          sender: attributes.sender,
+          beneficiary: attributes.beneficiary,
+          hashLock: attributes.hash_lock ? toHex32(attributes.hash_lock) : undefined,
+          timelock: attributes.timelock ? parseInt(attributes.timelock) : undefined,
+          amount: attributes.amount,
+          token: attributes.token
+        }));
+        break;
+      case 'claimed':
+        if (!attributes.preimage) continue;
+        parsed.push(compact({ kind, contractAddress, claimer: attributes.claimer ?? attributes.sender, preimage: toHex32(attributes.preimage) }));
+        break;
+      case 'refunded':
+        parsed.push(compact({ kind, contractAddress, refunder: attributes.refunder ?? attributes.sender }));
+        break;
+    }
+  }
+
+  return parsed;
+}
+
+function attributeMap(event: TxEvent): Record<string, string> {
+  const attributes: Record<string, string> = {};
+  for (const { key, value } of event.attributes) {
+    // First occurrence wins; a later duplicate key is most likely an addition we don't know
+    if (!(key in attributes)) attributes[key] = value;
+  }
+  return attributes;
+}
+
+/**
+ * Hashes and preimages are emitted as hex on some contract versions and base64
+ * on others; both come out as 0x hex. Anything else is returned unchanged.
+ */
+function toHex32(value: string): string {
+  if (/^(0x)?[0-9a-fA-F]{64}$/.test(value)) {
+    return ethers.hexlify('0x' + value.replace(/^0x/, ''));
+  }
+  try {
+    const bytes = fromBase64(value);
+    if (bytes.length === 32) return ethers.hexlify(bytes);
+  } catch {
+    // not base64 either
+  }
+  return value;
+}
+
+function compact<T extends object>(event: T): T {
+  return Object.fromEntries(Object.entries(event).filter(([, value]) => value !== undefined)) as T;
+}
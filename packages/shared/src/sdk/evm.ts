@@ -0,0 +1,184 @@
+import { ethers } from 'ethers';
+import { evmChains } from '../chains';
+import type { EvmChainConfig } from '../chains';
+import { waitForFinality } from '../resolver/evm-chain';
+import type { FinalityOptions } from '../resolver/evm-chain';
+import type { CreateHTLCParams } from '../utils/htlc';
+import { validateContractId, validatePreimage } from '../utils/validation';
+import { EVM_ORDER_TUPLE, decodeEvmOrder } from './types';
+import type { SwapOrder } from './types';
+import { withContractErrors } from './errors';
+import type { SwapEvent } from './events';
+
+/** SimpleHTLC events */
+export const HTLC_EVENTS = new ethers.Interface([
+  'event HTLCCreated(bytes32 indexed contractId, address indexed originator, address indexed beneficiary, address token, uint256 value, bytes32 hashLock, uint256 timelock)',
+  'event HTLCClaimed(bytes32 indexed contractId, address indexed claimer, bytes32 preimage)',
+  'event HTLCRefunded(bytes32 indexed contractId, address indexed refunder)'
+]);
+
+const HTLC_ABI = [
+  'function fund(bytes32 contractId, address token, address beneficiary, bytes32 hashLock, uint256 timelock, uint256 value)',
+  'function fundETH(bytes32 contractId, address beneficiary, bytes32 hashLock, uint256 timelock) payable',
+  'function claim(bytes32 contractId, bytes32 preimage)',
+  'function refund(bytes32 contractId)',
+  `function getDetails(bytes32 contractId) view returns (${EVM_ORDER_TUPLE})`,
+  ...HTLC_EVENTS.fragments.map(fragment => fragment.format('full'))
+];
+
+const ERC20_ABI = [
+  'function allowance(address owner, address spender) view returns (uint256)',
+  'function approve(address spender, uint256 amount) returns (bool)'
+];
+
+/**
+ * A swap event from a SimpleHTLC log. EVM swaps share one contract, so
+ * `contractId` identifies the swap and `contractAddress` the HTLC.
+ */
+export type EvmSwapEvent = SwapEvent & {
+  contractId: string;
+  blockNumber: number;
+  transactionHash: string;
+  logIndex: number;
+};
+
+/**
+ * Decode SimpleHTLC events from receipt or getLogs output, skipping logs from
+ * other contracts or with unknown topics
+ */
+export function decodeHtlcLogs(logs: readonly ethers.Log[], htlcAddress?: string): EvmSwapEvent[] {
+  const events: EvmSwapEvent[] = [];
+  for (const log of logs) {
+    if (htlcAddress && log.address.toLowerCase() !== htlcAddress.toLowerCase()) continue;
+    const parsed = HTLC_EVENTS.parseLog({ topics: [...log.topics], data: log.data });
+    if (!parsed) continue;
+
+    const base = {
+      contractAddress: log.address,
+      contractId: parsed.args.contractId as string,
+      blockNumber: log.blockNumber,
+      transactionHash: log.transactionHash,
+      logIndex: log.index
+    };
+    switch (parsed.name) {
+      case 'HTLCCreated':
+        events.push({
+          ...base,
+          kind: 'funded',
+          sender: parsed.args.originator,
+          beneficiary: parsed.args.beneficiary,
+          hashLock: parsed.args.hashLock,
+          timelock: Number(parsed.args.timelock),
+          amount: parsed.args.value.toString(),
+          ...(parsed.args.token !== ethers.ZeroAddress ? { token: parsed.args.token } : {})
+        });
+        break;
+      case 'HTLCClaimed':
+        events.push({ ...base, kind: 'claimed', claimer: parsed.args.claimer, preimage: parsed.args.preimage });
+        break;
+      case 'HTLCRefunded':
+        events.push({ ...base, kind: 'refunded', refunder: parsed.args.refunder });
+        break;
+    }
+  }
+  return events;
+}
+
+/**
+ * A sent HTLC transaction. `wait` resolves once it is mined and, with
+ * `finality`, once the block is final under the chain's finality model.
+ */
+export interface EvmPendingTx {
+  hash: string;
+  wait(options?: { finality?: boolean } & FinalityOptions): Promise<{ receipt: ethers.TransactionReceipt; events: EvmSwapEvent[] }>;
+}
+
+/**
+ * Typed SimpleHTLC bindings for one chain. Reverts come back as ContractError.
+ */
+export class EvmHtlc {
+  private contract: ethers.Contract;
+  /** Registry key the chain is under, used for the orders `getSwap` returns */
+  readonly key: string;
+
+  constructor(readonly chain: EvmChainConfig, private runner: ethers.ContractRunner) {
+    this.contract = new ethers.Contract(chain.htlcAddress, HTLC_ABI, runner);
+    this.key = Object.keys(evmChains).find(key => evmChains[key].chainId === chain.chainId) ?? String(chain.chainId);
+  }
+
+  /**
+   * Lock funds. ERC-20 allowance is topped up first when it is short.
+   */
+  async fund(params: CreateHTLCParams): Promise<EvmPendingTx> {
+    validateContractId(params.contractId);
+    const { contractId, beneficiary, hashLock, timelock, value, token } = params;
+
+    if (!token || token === ethers.ZeroAddress) {
+      return this.send(() => this.contract.fundETH(contractId, beneficiary, hashLock, timelock, { value }));
+    }
+    await this.ensureAllowance(token, BigInt(value));
+    return this.send(() => this.contract.fund(contractId, token, beneficiary, hashLock, timelock, value));
+  }
+
+  claim(contractId: string, preimage: string): Promise<EvmPendingTx> {
+    validateContractId(contractId);
+    validatePreimage(preimage);
+    return this.send(() => this.contract.claim(contractId, preimage));
+  }
+
+  refund(contractId: string): Promise<EvmPendingTx> {
+    validateContractId(contractId);
+    return this.send(() => this.contract.refund(contractId));
+  }
+
+  async getSwap(contractId: string): Promise<SwapOrder> {
+    return decodeEvmOrder(this.key, contractId, await this.contract.getDetails(contractId));
+  }
+
+  /**
+   * Past events for one swap, or for the whole contract when `contractId` is omitted
+   */
+  async events(options: { contractId?: string; fromBlock?: number; toBlock?: number | 'latest' } = {}): Promise<EvmSwapEvent[]> {
+    const provider = this.provider();
+    const topics = [HTLC_EVENTS.fragments.map(fragment => (fragment as ethers.EventFragment).topicHash)];
+    if (options.contractId) topics.push([options.contractId]);
+
+    const logs = await provider.getLogs({
+      address: this.chain.htlcAddress,
+      topics,
+      fromBlock: options.fromBlock ?? 0,
+      toBlock: options.toBlock ?? 'latest'
+    });
+    return decodeHtlcLogs(logs, this.chain.htlcAddress);
+  }
+
+  private async send(call: () => Promise<ethers.ContractTransactionResponse>): Promise<EvmPendingTx> {
+    const tx = await withContractErrors(call);
+    return {
+      hash: tx.hash,
+      wait: async (options = {}) => {
+        const receipt = await withContractErrors(() => tx.wait());
+        if (!receipt) throw new Error(`Transaction ${tx.hash} was replaced or dropped`);
+        if (options.finality) {
+          await waitForFinality(this.provider(), this.chain, receipt.blockNumber, options);
+        }
+        return { receipt, events: decodeHtlcLogs(receipt.logs, this.chain.htlcAddress) };
+      }
+    };
+  }
+
+  private async ensureAllowance(token: string, amount: bigint): Promise<void> {
+    const runner = this.runner as ethers.Signer;
+    const owner = await runner.getAddress();
+    const erc20 = new ethers.Contract(token, ERC20_ABI, runner);
+    if (await erc20.allowance(owner, this.chain.htlcAddress) >= amount) return;
+    const approval: ethers.ContractTransactionResponse = await erc20.approve(this.chain.htlcAddress, amount);
+    await approval.wait();
+  }
+
+  private provider(): ethers.Provider {
+    const provider = this.runner.provider;
+    if (!provider) throw new Error('EvmHtlc needs a runner with a provider');
+    return provider;
+  }
+}
@@ -0,0 +1,152 @@
+import { ethers } from 'ethers';
+import type { MsgInstantiateContractEncodeObject, SigningCosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import { toBase64, toUtf8 } from '@cosmjs/encoding';
+import type { ChainConfig, CosmosChainConfig, EvmChainConfig } from '../chains';
+import { FeeGuard } from '../clients/fees';
+import type { FeePolicy } from '../clients/fees';
+import { estimateL1DataFee } from '../resolver/evm-chain';
+import { ValidationError } from '../utils/errors';
+import type { InstantiateMsg } from '../clients/cosmos';
+import type { CreateHTLCParams } from '../utils/htlc';
+import { HtlcMsg } from './builder';
+import { HTLC_INTERFACE, executeContractMsg } from './offline';
+
+/**
+ * The call to price. Claims are simulated with the real preimage, since the
+ * contracts reject (and so cannot estimate) a claim that would fail. An ERC-20
+ * fund can only be estimated once the HTLC's allowance covers it.
+ */
+export type FeeCall =
+  | { action: 'fund'; params: CreateHTLCParams }
+  | { action: 'claim'; contractId: string; preimage: string }
+  | { action: 'refund'; contractId: string };
+
+export type FeeConnection =
+  | { client: SigningCosmWasmClient; sender: string }
+  | { provider: ethers.Provider; from: string };
+
+export interface CosmosFeeSuggestion {
+  type: 'cosmos';
+  gasUsed: number;
+  gasLimit: number;
+  denom: string;
+  /** Base units of `denom` */
+  amount: string;
+  /** `amount` in whole units (6 decimals), for display */
+  formatted: string;
+}
+
+export interface EvmFeeSuggestion {
+  type: 'evm';
+  gasUsed: bigint;
+  gasLimit: bigint;
+  maxFeePerGas: bigint;
+  maxPriorityFeePerGas: bigint;
+  /** Charged by OP Stack rollups for posting calldata; zero elsewhere */
+  l1DataFee: bigint;
+  /** Most the transaction can cost, in wei */
+  maxCost: bigint;
+  /** `maxCost` in whole units of the native token */
+  formatted: string;
+}
+
+export type FeeSuggestion = CosmosFeeSuggestion | EvmFeeSuggestion;
+
+/**
+ * Simulate a fund, claim or refund and suggest a fee for it, padded by the same
+ * multipliers the resolver uses (overridable through `policy`). Throws whatever
+ * the chain throws when the call itself would fail.
+ */
+export async function estimateFees(
+  call: FeeCall,
+  chain: ChainConfig,
+  connection: FeeConnection,
+  policy: FeePolicy = {}
+): Promise<FeeSuggestion> {
+  const guard = new FeeGuard(chain.name, chain, { ...policy, budgets: {} });
+
+  if (chain.type === 'cosmos') {
+    if (!('client' in connection)) {
+      throw new ValidationError(`${chain.name} needs a CosmWasm client to estimate fees`);
+    }
+    const message = call.action === 'fund'
+      ? instantiateHtlcMsg(connection.sender, chain as CosmosChainConfig, call.params)
+      : executeContractMsg(connection.sender, call.contractId, call.action === 'claim' ? HtlcMsg.claim(call.preimage) : HtlcMsg.refund());
+    const gasUsed = await connection.client.simulate(connection.sender, [message], '');
+    const fee = guard.cosmos([call.action], gasUsed);
+    const { denom, amount } = fee.amount[0] ?? { denom: (chain as CosmosChainConfig).nativeDenom, amount: '0' };
+    return {
+      type: 'cosmos',
+      gasUsed,
+      gasLimit: Number(fee.gas),
+      denom,
+      amount,
+      formatted: ethers.formatUnits(amount, 6)
+    };
+  }
+
+  if (!('provider' in connection)) {
+    throw new ValidationError(`${chain.name} needs an ethers provider to estimate fees`);
+  }
+  let data: string;
+  let value = 0n;
+  if (call.action === 'fund') {
+    const { contractId, token, beneficiary, hashLock, timelock, value: amount } = call.params;
+    const native = !token || token === ethers.ZeroAddress;
+    data = native
+      ? HTLC_INTERFACE.encodeFunctionData('fundETH', [contractId, beneficiary, hashLock, timelock])
+      : HTLC_INTERFACE.encodeFunctionData('fund', [contractId, token, beneficiary, hashLock, timelock, amount]);
+    if (native) value = BigInt(amount);
+  } else {
+    data = call.action === 'claim'
+      ? HTLC_INTERFACE.encodeFunctionData('claim', [call.contractId, call.preimage])
+      : HTLC_INTERFACE.encodeFunctionData('refund', [call.contractId]);
+  }
+  const tx = { to: chain.htlcAddress, from: connection.from, data, value };
+
+  const evmChain = chain as EvmChainConfig;
+  const [gasUsed, feeData, l1DataFee] = await Promise.all([
+    connection.provider.estimateGas(tx),
+    connection.provider.getFeeData(),
+    // Arbitrum already includes the L1 component in estimateGas
+    evmChain.gasModel === 'arbitrum' ? Promise.resolve(0n) : estimateL1DataFee(connection.provider, evmChain, tx)
+  ]);
+
+  const gasLimit = gasUsed * BigInt(Math.round(guard.gasMultiplier * 1000)) / 1000n;
+  const maxFeePerGas = feeData.maxFeePerGas ?? feeData.gasPrice ?? 0n;
+  const maxPriorityFeePerGas = feeData.maxPriorityFeePerGas ?? 0n;
+  const maxCost = gasLimit * maxFeePerGas + l1DataFee;
+  return {
+    type: 'evm',
+    gasUsed,
+    gasLimit,
+    maxFeePerGas,
+    maxPriorityFeePerGas,
+    l1DataFee,
+    maxCost,
+    formatted: ethers.formatEther(maxCost)
+  };
+}
+
+// The MsgInstantiateContract CosmosHTLCClient.instantiateHTLC sends, for simulation
+function instantiateHtlcMsg(sender: string, chain: CosmosChainConfig, params: CreateHTLCParams): MsgInstantiateContractEncodeObject {
+  const msg: InstantiateMsg = {
+    sender,
+    beneficiary: params.beneficiary,
+    hash_lock: toBase64(ethers.getBytes(params.hashLock)),
+    timelock: params.timelock,
+    amount: params.value,
+    token: params.token || undefined
+  };
+  return {
+    typeUrl: '/cosmwasm.wasm.v1.MsgInstantiateContract',
+    value: {
+      sender,
+      admin: '',
+      codeId: BigInt(chain.codeId || '1'),
+      label: `SwapSage HTLC ${params.contractId}`,
+      msg: toUtf8(JSON.stringify(msg)),
+      funds: params.token ? [] : [{ denom: chain.nativeDenom, amount: params.value }]
+    }
+  };
+}
@@ -0,0 +1,212 @@
+import { ethers } from 'ethers';
+import { fromBase64, fromBech32, toBase64 } from '@cosmjs/encoding';
+import { ValidationError } from '../utils/errors';
+import { addressKind, cosmosAddressFromPubkey } from './address';
+
+export const HANDOFF_VERSION = 1;
+export const HANDOFF_URI_SCHEME = 'omni-swap:';
+
+/**
+ * The public half of a two-leg swap, as the side holding the secret (the
+ * maker) hands it to the counterparty: the maker's lock, and the lock the
+ * maker asks for in return. Never carries the preimage.
+ */
+export interface SwapHandoff {
+  version: number;
+  /** The maker's HTLC; `contractId` is set once it is funded */
+  maker: {
+    chain: string;
+    chainId: string | number;
+    swapId: string;
+    contractId?: string;
+    originator: string;
+    beneficiary: string;
+    hashLock: string;
+    timelock: number;
+    amount: string;
+    token?: string;
+  };
+  /** The HTLC the counterparty should create under the same hashlock */
+  ask: {
+    chain: string;
+    chainId: string | number;
+    /** Maker's address on the ask chain */
+    beneficiary: string;
+    amount: string;
+    token?: string;
+    /** Latest acceptable expiry, Unix seconds */
+    timelock: number;
+  };
+  createdAt: number;
+}
+
+export interface HandoffSignature {
+  /** eip191: personal_sign by an EVM maker; adr36: signArbitrary by a Cosmos maker */
+  scheme: 'eip191' | 'adr36';
+  /** Compressed secp256k1 key, hex; adr36 only, since bech32 addresses cannot be recovered from */
+  pubKey?: string;
+  signature: string;
+}
+
+export interface SignedHandoff {
+  handoff: SwapHandoff;
+  signature: HandoffSignature;
+}
+
+/**
+ * The originator key, or for hardware wallets a signer holding it: an ethers
+ * Signer on EVM, an amino signer (e.g. a Ledger) on Cosmos
+ */
+export type HandoffSigner =
+  | { type: 'evm'; privateKey: string }
+  | { type: 'evm'; signer: ethers.Signer }
+  | { type: 'cosmos'; mnemonic: string }
+  | { type: 'cosmos'; signer: HandoffAminoSigner };
+
+/** The part of cosmjs's OfflineAminoSigner a handoff needs */
+export interface HandoffAminoSigner {
+  signAmino(address: string, signDoc: Adr36SignDoc): Promise<{ signature: { pub_key: { value: string }; signature: string } }>;
+}
+
+type Adr36SignDoc = ReturnType<typeof adr36Doc>;
+
+// Default Cosmos HD path, as DirectSecp256k1HdWallet derives it
+const COSMOS_HD_PATH = "m/44'/118'/0'/0/0";
+
+/**
+ * Sign a handoff with the maker's originator key. The signed bytes are the
+ * handoff as JSON with keys sorted, so any implementation can reproduce them.
+ */
+export async function signHandoff(handoff: SwapHandoff, signer: HandoffSigner): Promise<SignedHandoff> {
+  const message = canonicalJson(handoff);
+  if (signer.type === 'evm') {
+    const wallet = 'signer' in signer ? signer.signer : new ethers.Wallet(signer.privateKey);
+    return { handoff, signature: { scheme: 'eip191', signature: await wallet.signMessage(message) } };
+  }
+
+  if ('signer' in signer) {
+    const { signature } = await signer.signer.signAmino(handoff.maker.originator, adr36Doc(handoff.maker.originator, message));
+    const pubKey = ethers.hexlify(fromBase64(signature.pub_key.value));
+    const { prefix } = fromBech32(handoff.maker.originator);
+    if (cosmosAddressFromPubkey(pubKey, prefix) !== handoff.maker.originator) {
+      throw new ValidationError('Signing key is not the handoff originator', { originator: handoff.maker.originator });
+    }
+    return { handoff, signature: { scheme: 'adr36', pubKey, signature: signature.signature } };
+  }
+
+  const key = ethers.HDNodeWallet.fromPhrase(signer.mnemonic, '', COSMOS_HD_PATH).signingKey;
+  const { prefix } = fromBech32(handoff.maker.originator);
+  const address = cosmosAddressFromPubkey(key.compressedPublicKey, prefix);
+  if (address !== handoff.maker.originator) {
+    throw new ValidationError('Signing key is not the handoff originator', { originator: handoff.maker.originator, address });
+  }
+  const signature = key.sign(ethers.sha256(adr36SignDoc(address, message)));
+  return {
+    handoff,
+    signature: {
+      scheme: 'adr36',
+      pubKey: key.compressedPublicKey,
+      signature: toBase64(ethers.getBytes(ethers.concat([signature.r, signature.s])))
+    }
+  };
+}
+
+/**
+ * Check a handoff is well-formed and signed by its maker's originator.
+ * Throws ValidationError otherwise.
+ */
+export function verifyHandoff(signed: SignedHandoff): SwapHandoff {
+  const { handoff, signature } = signed;
+  if (handoff?.version !== HANDOFF_VERSION) {
+    throw new ValidationError(`Unsupported handoff version ${handoff?.version}`);
+  }
+  if (ethers.getBytes(handoff.maker.hashLock).length !== 32) {
+    throw new ValidationError('Handoff hash lock must be 32 bytes');
+  }
+  if (!ethers.isHexString(handoff.maker.swapId, 32)) {
+    throw new ValidationError('Handoff swap ID must be 32 bytes');
+  }
+  for (const amount of [handoff.maker.amount, handoff.ask.amount]) {
+    if (!/^\d+$/.test(amount) || BigInt(amount) === 0n) {
+      throw new ValidationError(`Invalid handoff amount ${amount}`);
+    }
+  }
+
+  const message = canonicalJson(handoff);
+  const originator = handoff.maker.originator;
+  let signer: string;
+  if (signature.scheme === 'eip191') {
+    signer = ethers.verifyMessage(message, signature.signature);
+  } else if (signature.scheme === 'adr36' && signature.pubKey) {
+    const { prefix } = fromBech32(originator);
+    const rs = fromBase64(signature.signature);
+    if (rs.length !== 64) throw new ValidationError('ADR-36 signature must be 64 bytes');
+    const digest = ethers.sha256(adr36SignDoc(originator, message));
+    const valid = [27, 28].some(v => {
+      try {
+        const recovered = ethers.SigningKey.recoverPublicKey(digest, { r: ethers.hexlify(rs.slice(0, 32)), s: ethers.hexlify(rs.slice(32)), v });
+        return ethers.SigningKey.computePublicKey(recovered, true) === ethers.SigningKey.computePublicKey(signature.pubKey!, true);
+      } catch {
+        return false;
+      }
+    });
+    if (!valid) throw new ValidationError('Handoff signature does not match its public key');
+    signer = cosmosAddressFromPubkey(signature.pubKey, prefix);
+  } else {
+    throw new ValidationError(`Unsupported handoff signature scheme ${signature.scheme}`);
+  }
+
+  const same = addressKind(originator) === 'evm'
+    ? signer.toLowerCase() === originator.toLowerCase()
+    : signer === originator;
+  if (!same) {
+    throw new ValidationError('Handoff is not signed by its originator', { originator, signer });
+  }
+  return handoff;
+}
+
+/** `omni-swap:<base64url JSON>`, short enough for a chat message or QR code */
+export function encodeHandoffUri(signed: SignedHandoff): string {
+  return HANDOFF_URI_SCHEME + ethers.encodeBase64(ethers.toUtf8Bytes(JSON.stringify(signed)))
+    .replace(/\+/g, '-')
+    .replace(/\//g, '_')
+    .replace(/=+$/, '');
+}
+
+/** Parse a handoff from its URI or its JSON file contents; does not verify it */
+export function decodeHandoff(text: string): SignedHandoff {
+  const trimmed = text.trim();
+  try {
+    if (trimmed.startsWith(HANDOFF_URI_SCHEME)) {
+      const base64 = trimmed.slice(HANDOFF_URI_SCHEME.length).replace(/-/g, '+').replace(/_/g, '/');
+      return JSON.parse(ethers.toUtf8String(ethers.decodeBase64(base64.padEnd(Math.ceil(base64.length / 4) * 4, '='))));
+    }
+    return JSON.parse(trimmed);
+  } catch (error) {
+    throw new ValidationError(`Not a swap handoff: ${(error as Error).message}`);
+  }
+}
+
+function canonicalJson(value: unknown): string {
+  return JSON.stringify(value, (_key, inner) =>
+    inner && typeof inner === 'object' && !Array.isArray(inner)
+      ? Object.fromEntries(Object.keys(inner).sort().map(key => [key, inner[key]]))
+      : inner
+  );
+}
+
+// Amino sign doc of an ADR-36 MsgSignData, as Keplr's signArbitrary signs it
+function adr36Doc(signer: string, data: string) {
+  return {
+    account_number: '0',
+    chain_id: '',
+    fee: { amount: [], gas: '0' },
+    memo: '',
+    msgs: [{ type: 'sign/MsgSignData', value: { data: toBase64(ethers.toUtf8Bytes(data)), signer } }],
+    sequence: '0'
+  };
+}
+
+function adr36SignDoc(signer: string, data: string): Uint8Array {
+  return ethers.toUtf8Bytes(canonicalJson(adr36Doc(signer, data)));
+}
@@ -0,0 +1,96 @@
+import { ethers } from 'ethers';
+import { fromBase64, toBase64 } from '@cosmjs/encoding';
+import { ValidationError } from '../utils/errors';
+import { hashPreimage, Secret } from './secret';
+import type { HashAlgorithm } from './secret';
+
+const DIGEST_BYTES: Record<HashAlgorithm, number> = { sha256: 32, keccak256: 32, hash160: 20 };
+
+/**
+ * A hashlock together with the algorithm that produced it, so both legs of a
+ * swap are built from one value instead of two hex strings that only agree by
+ * convention.
+ *
+ * Encodings: Cosmos takes the raw digest as base64 `Binary`; EVM takes a
+ * `bytes32`, with 20-byte hash160 digests left-aligned and zero-padded the way
+ * Solidity widens `bytes20`.
+ */
+export class HashLock {
+  private constructor(readonly algorithm: HashAlgorithm, private digest: Uint8Array) {
+    if (digest.length !== DIGEST_BYTES[algorithm]) {
+      throw new ValidationError(`A ${algorithm} hashlock is ${DIGEST_BYTES[algorithm]} bytes, got ${digest.length}`);
+    }
+    if (digest.every(byte => byte === 0)) {
+      throw new ValidationError('Hash lock cannot be all zeroes');
+    }
+  }
+
+  static fromPreimage(preimage: Secret | Uint8Array, algorithm: HashAlgorithm = 'sha256'): HashLock {
+    if (preimage instanceof Secret) {
+      const bytes = preimage.toBytes();
+      try {
+        return new HashLock(algorithm, hashPreimage(bytes, algorithm));
+      } finally {
+        bytes.fill(0);
+      }
+    }
+    return new HashLock(algorithm, hashPreimage(preimage, algorithm));
+  }
+
+  static fromHex(hex: string, algorithm: HashAlgorithm = 'sha256'): HashLock {
+    return new HashLock(algorithm, ethers.getBytes(hex.startsWith('0x') ? hex : `0x${hex}`));
+  }
+
+  /** From the base64 `Binary` a CosmWasm HTLC stores */
+  static fromCosmos(binary: string, algorithm: HashAlgorithm = 'sha256'): HashLock {
+    return new HashLock(algorithm, fromBase64(binary));
+  }
+
+  /** From the `bytes32` an EVM HTLC stores */
+  static fromEvm(bytes32: string, algorithm: HashAlgorithm = 'sha256'): HashLock {
+    const word = ethers.getBytes(bytes32);
+    if (word.length !== 32) {
+      throw new ValidationError('EVM hashlock must be bytes32');
+    }
+    const size = DIGEST_BYTES[algorithm];
+    if (word.slice(size).some(byte => byte !== 0)) {
+      throw new ValidationError(`bytes32 does not hold a ${algorithm} digest (non-zero padding)`);
+    }
+    return new HashLock(algorithm, word.slice(0, size));
+  }
+
+  /** 0x-prefixed hex of the raw digest */
+  hex(): string {
+    return ethers.hexlify(this.digest);
+  }
+
+  toCosmos(): string {
+    return toBase64(this.digest);
+  }
+
+  toEvm(): string {
+    return ethers.zeroPadBytes(this.digest, 32);
+  }
+
+  verify(preimage: Secret | Uint8Array | string): boolean {
+    let bytes: Uint8Array;
+    if (preimage instanceof Secret) {
+      bytes = preimage.toBytes();
+    } else if (typeof preimage === 'string') {
+      bytes = ethers.getBytes(preimage.startsWith('0x') ? preimage : `0x${preimage}`);
+    } else {
+      bytes = new Uint8Array(preimage);
+    }
+
+    try {
+      const digest = hashPreimage(bytes, this.algorithm);
+      return digest.length === this.digest.length && digest.every((byte, i) => byte === this.digest[i]);
+    } finally {
+      bytes.fill(0);
+    }
+  }
+
+  equals(other: HashLock): boolean {
+    return this.algorithm === other.algorithm && this.hex() === other.hex();
+  }
+}
@@ -0,0 +1,123 @@
+import axios from 'axios';
+import { ethers } from 'ethers';
+import { fromBase64, toHex } from '@cosmjs/encoding';
+import { QueryDenomTraceRequest, QueryDenomTraceResponse } from 'cosmjs-types/ibc/applications/transfer/v1/query';
+import { QueryChannelRequest, QueryChannelResponse } from 'cosmjs-types/ibc/core/channel/v1/query';
+import { NetworkError, ValidationError } from '../utils/errors';
+
+export interface DenomTrace {
+  /** The denom as held on this chain: `ibc/<HASH>` or a native denom */
+  denom: string;
+  baseDenom: string;
+  /** `port/channel` pairs the token travelled through, most recent first; empty if native */
+  path: string;
+  hops: { port: string; channel: string }[];
+}
+
+/**
+ * `ibc/<HASH>` for a trace path and base denom, as ICS-20 computes it
+ */
+export function ibcDenom(path: string, baseDenom: string): string {
+  if (!path) return baseDenom;
+  return `ibc/${ethers.sha256(ethers.toUtf8Bytes(`${path}/${baseDenom}`)).slice(2).toUpperCase()}`;
+}
+
+export function parseTracePath(path: string): DenomTrace['hops'] {
+  const parts = path ? path.split('/') : [];
+  if (parts.length % 2 !== 0) {
+    throw new ValidationError('Trace path must be port/channel pairs', { path });
+  }
+  const hops: DenomTrace['hops'] = [];
+  for (let i = 0; i < parts.length; i += 2) {
+    hops.push({ port: parts[i], channel: parts[i + 1] });
+  }
+  return hops;
+}
+
+/**
+ * Base denom and channel path behind a denom on the chain at `rpcUrl`. Native
+ * denoms resolve to themselves with an empty path.
+ */
+export async function resolveDenomTrace(rpcUrl: string, denom: string): Promise<DenomTrace> {
+  if (!denom.startsWith('ibc/')) {
+    return { denom, baseDenom: denom, path: '', hops: [] };
+  }
+  const response = QueryDenomTraceResponse.decode(await abciQuery(
+    rpcUrl,
+    '/ibc.applications.transfer.v1.Query/DenomTrace',
+    QueryDenomTraceRequest.encode(QueryDenomTraceRequest.fromPartial({ hash: denom.slice(4) })).finish()
+  ));
+  if (!response.denomTrace) {
+    throw new ValidationError(`Unknown IBC denom ${denom}`);
+  }
+  const { path, baseDenom } = response.denomTrace;
+  return { denom, baseDenom, path, hops: parseTracePath(path) };
+}
+
+/**
+ * Denom a token arrives as after an ICS-20 transfer out of `sourcePort/sourceChannel`,
+ * given the counterparty end of that channel. Sending a token back the way it
+ * came unwinds the last hop instead of adding one.
+ */
+export function denomAfterTransfer(
+  trace: Pick<DenomTrace, 'path' | 'baseDenom'>,
+  source: { port: string; channel: string },
+  destination: { port: string; channel: string }
+): string {
+  const prefix = `${source.port}/${source.channel}`;
+  if (trace.path === prefix || trace.path.startsWith(`${prefix}/`)) {
+    return ibcDenom(trace.path.slice(prefix.length + 1), trace.baseDenom);
+  }
+  const path = trace.path ? `${destination.port}/${destination.channel}/${trace.path}` : `${destination.port}/${destination.channel}`;
+  return ibcDenom(path, trace.baseDenom);
+}
+
+/**
+ * Denom `denom` will have on the other side of `channel`, looking up both the
+ * trace and the channel's counterparty on the source chain
+ */
+export async function expectedDestinationDenom(
+  rpcUrl: string,
+  denom: string,
+  channel: string,
+  port = 'transfer'
+): Promise<string> {
+  const [trace, counterparty] = await Promise.all([resolveDenomTrace(rpcUrl, denom), channelCounterparty(rpcUrl, port, channel)]);
+  return denomAfterTransfer(trace, { port, channel }, counterparty);
+}
+
+export async function channelCounterparty(rpcUrl: string, port: string, channel: string): Promise<{ port: string; channel: string }> {
+  const response = QueryChannelResponse.decode(await abciQuery(
+    rpcUrl,
+    '/ibc.core.channel.v1.Query/Channel',
+    QueryChannelRequest.encode(QueryChannelRequest.fromPartial({ portId: port, channelId: channel })).finish()
+  ));
+  const counterparty = response.channel?.counterparty;
+  if (!counterparty?.channelId) {
+    throw new ValidationError(`Channel ${port}/${channel} not found or not open`);
+  }
+  return { port: counterparty.portId, channel: counterparty.channelId };
+}
+
+/**
+ * gRPC query over Tendermint's abci_query, so only the RPC endpoint the chain
+ * config already has is needed
+ */
+async function abciQuery(rpcUrl: string, path: string, data: Uint8Array): Promise<Uint8Array> {
+  let result: any;
+  try {
+    const response = await axios.post(rpcUrl, {
+      jsonrpc: '2.0',
+      id: 1,
+      method: 'abci_query',
+      params: { path, data: toHex(data), prove: false }
+    });
+    result = response.data?.result?.response;
+  } catch (error) {
+    throw new NetworkError(`abci_query ${path} failed: ${(error as Error).message}`, { rpcUrl });
+  }
+  if (!result || Number(result.code ?? 0) !== 0) {
+    throw new ValidationError(`abci_query ${path} rejected: ${result?.log || 'no response'}`);
+  }
+  return fromBase64(result.value ?? '');
+}
@@ -0,0 +1,119 @@
+// Integrator-facing SDK: build, validate and track HTLC swaps without hand-rolling
+// contract JSON or ABI calls
+export { SwapBuilder, HtlcMsg, htlcQueryMsg, queryHtlc } from './builder';
+export type { SwapSpec, HtlcQueryResponses, HtlcQueryKind } from './builder';
+export { HtlcClient, decodeSwapResponse } from './client';
+export type { HtlcSwap, HtlcClientOptions } from './client';
+export { SwapEventSubscription } from './subscription';
+export type { ObservedSwapEvent, SubscribeOptions } from './subscription';
+export { FailoverPool, FailoverJsonRpcProvider, cosmWasmPool } from './rpc';
+export type { FailoverOptions, Connector, EndpointStatus } from './rpc';
+export { Secret, withSecret, hashPreimage, HASH_ALGORITHMS } from './secret';
+export type { HashAlgorithm } from './secret';
+export { SecretVault } from './vault';
+export { HashLock } from './hashlock';
+export { deriveSwapId, predictCosmosHtlcAddress, SWAP_ID_DOMAIN } from './swap-id';
+export type { SwapIdParams } from './swap-id';
+export {
+  isBech32Address,
+  isEvmAddress,
+  addressKind,
+  bech32ToHex,
+  hexToBech32,
+  convertBech32Prefix,
+  cosmosAddressFromPubkey,
+  evmAddressFromPubkey,
+  ethermintAddressFromPubkey,
+  toChainAddress
+} from './address';
+export type { AddressKind, AccountKeyType } from './address';
+export { fromChainAdapter } from './chain';
+export type { SwapChain, LockParams, SubmittedTx } from './chain';
+export { CrossChainSwap } from './swap';
+export type { SwapPhase, CrossChainSwapParams, CrossChainSwapOptions, SwapSnapshot } from './swap';
+export { SwapRelay } from './relay';
+export type { RelayEndpoint, SwapRelayOptions } from './relay';
+export {
+  SwapOrderSchema,
+  EVM_ORDER_TUPLE,
+  toInstantiateMsg,
+  fromSwapResponse,
+  encodeEvmOrder,
+  decodeEvmOrder
+} from './types';
+export type { SwapOrder } from './types';
+export { describeSwap } from './describe';
+export type { DescribeOptions } from './describe';
+export { Amount } from './amount';
+export type { Rounding } from './amount';
+export { MerkleSecrets, merkleLeaf, verifyMerkleProof } from './merkle';
+export type { MerkleFillState } from './merkle';
+export { chainTiming, recommend as recommendTimelocks, checkTimelocks } from './timelock';
+export type { TimelockOptions, TimelockAdvice } from './timelock';
+export { noopTelemetry, emitTelemetry } from './telemetry';
+export type { Telemetry } from './telemetry';
+export { SwapWatcher, watchSwap } from './watch';
+export type { WatchTarget, WatchOptions, WatchReport, WatchOutcome, LegReport, LegStatus } from './watch';
+export { generateTestVectors, TEST_VECTORS_VERSION } from './vectors';
+export type { TestVector, TestVectors } from './vectors';
+export { htlcContractSchema, htlcSchemaFiles, HTLC_CONTRACT_VERSION } from './schema';
+export { parseTxEvents } from './events';
+export type { SwapEvent, TxEvent } from './events';
+export { ContractError, parseContractError, withContractErrors } from './errors';
+export type { ContractErrorKind } from './errors';
+export { checkLock, checkClaim, checkRefund, assertValid } from './validate';
+export type { ValidationRules } from './validate';
+export { Swap } from './typestate';
+export type { Created, Funded, Claimed, Refunded, LegPhase } from './typestate';
+export { planRoute } from './routing';
+export type { AssetRef, Corridor, ChainTiming, PlanOptions, PlannedLeg, RoutePlan } from './routing';
+export {
+  MINIMAL_FORWARDER_TYPES,
+  ERC2771_FORWARDER_TYPES,
+  GASLESS_CLAIM_TYPES,
+  fetchDomain,
+  minimalForwarderDomain,
+  buildClaimRequest,
+  typedDataDigest,
+  signTypedData,
+  recoverTypedDataSigner,
+  digestSigner
+} from './eip712';
+export type { TypedDataSigner, TypedDataTypes, ForwardRequest } from './eip712';
+export { QuoteAggregator, rfqSource, skipSource, fusionSource } from './quotes';
+export type { Quote, QuoteFee, QuoteAsset, QuoteRequest, QuoteSource, QuoteAggregatorOptions } from './quotes';
+export {
+  ibcDenom,
+  parseTracePath,
+  resolveDenomTrace,
+  denomAfterTransfer,
+  expectedDestinationDenom,
+  channelCounterparty
+} from './ibc';
+export type { DenomTrace } from './ibc';
+export {
+  HTLC_INTERFACE,
+  executeContractMsg,
+  signCosmosOffline,
+  evmFundTx,
+  evmClaimTx,
+  evmRefundTx,
+  signEvmOffline
+} from './offline';
+export type { CosmosSignerData, EvmTxParams } from './offline';
+export { CosmosBroadcaster } from './broadcast';
+export type { BroadcastMode, BroadcasterOptions, BroadcastResult } from './broadcast';
+export { EvmHtlc, HTLC_EVENTS, decodeHtlcLogs } from './evm';
+export type { EvmSwapEvent, EvmPendingTx } from './evm';
+export { estimateFees } from './fees';
+export type { FeeCall, FeeConnection, FeeSuggestion, CosmosFeeSuggestion, EvmFeeSuggestion } from './fees';
+export type { InstantiateMsg, ExecuteMsg, QueryMsg, SwapResponse } from '../clients/cosmos';
+export {
+  HANDOFF_VERSION,
+  HANDOFF_URI_SCHEME,
+  signHandoff,
+  verifyHandoff,
+  encodeHandoffUri,
+  decodeHandoff
+} from './handoff';
+export type { SwapHandoff, SignedHandoff, HandoffSignature, HandoffSigner, HandoffAminoSigner } from './handoff';
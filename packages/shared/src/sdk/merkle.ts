@@ -0,0 +1,198 @@
+import { ethers } from 'ethers';
+import { ValidationError } from '../utils/errors';
+import { Secret } from './secret';
+import type { SecretVault } from './vault';
+
+/**
+ * What to persist between runs to resume a partially filled order. Secrets are
+ * not in it; they live in a SecretVault under `<swapId>:<index>`.
+ */
+export interface MerkleFillState {
+  parts: number;
+  root: string;
+  consumed: number[];
+}
+
+/**
+ * Secrets for an order that can be filled in up to `parts` pieces, laid out the
+ * way 1inch Fusion+ does it: `parts + 1` secrets, leaf i is
+ * `keccak256(abi.encodePacked(uint64(i), keccak256(secret_i)))`, and the tree
+ * hashes sorted pairs so proofs verify with OpenZeppelin's `MerkleProof`. The
+ * root takes the place of the hashlock.
+ *
+ * Fill k of the order uses the secret for the part its cumulative amount ends
+ * in, with the extra last secret reserved for the fill that completes the order.
+ */
+export class MerkleSecrets {
+  private readonly layers: string[][];
+  private readonly consumed: Set<number>;
+
+  private constructor(private readonly secrets: Secret[], consumed: Iterable<number> = []) {
+    if (secrets.length < 2) {
+      throw new ValidationError('A partial-fill order needs at least 2 secrets');
+    }
+    this.layers = buildLayers(secrets.map((secret, index) => merkleLeaf(index, secret.hashLock('keccak256'))));
+    this.consumed = new Set(consumed);
+  }
+
+  /** Fresh secrets for an order split into `parts` */
+  static generate(parts: number): MerkleSecrets {
+    if (!Number.isInteger(parts) || parts < 1) {
+      throw new ValidationError('Parts must be a positive integer', { parts });
+    }
+    return new MerkleSecrets(Array.from({ length: parts + 1 }, () => Secret.generate()));
+  }
+
+  static fromSecrets(secrets: Secret[]): MerkleSecrets {
+    return new MerkleSecrets(secrets);
+  }
+
+  /**
+   * Rebuild from secrets saved with `save`, checking they still produce the saved root
+   */
+  static load(vault: SecretVault, swapId: string, state: MerkleFillState): MerkleSecrets {
+    const secrets: Secret[] = [];
+    for (let index = 0; index <= state.parts; index++) {
+      const secret = vault.get(`${swapId}:${index}`);
+      if (!secret) throw new ValidationError(`Vault has no secret ${index} for ${swapId}`);
+      secrets.push(secret);
+    }
+    const restored = new MerkleSecrets(secrets, state.consumed);
+    if (restored.root !== state.root) {
+      throw new ValidationError(`Secrets in the vault do not match root ${state.root}`, { swapId });
+    }
+    return restored;
+  }
+
+  get parts(): number {
+    return this.secrets.length - 1;
+  }
+
+  get root(): string {
+    return this.layers[this.layers.length - 1][0];
+  }
+
+  leaf(index: number): string {
+    this.checkIndex(index);
+    return this.layers[0][index];
+  }
+
+  secret(index: number): Secret {
+    this.checkIndex(index);
+    return this.secrets[index];
+  }
+
+  /** keccak256 of secret `index`, what the contract compares the revealed secret against */
+  secretHash(index: number): string {
+    return this.secret(index).hashLock('keccak256');
+  }
+
+  /** Sibling hashes from leaf `index` up to the root */
+  proof(index: number): string[] {
+    this.checkIndex(index);
+    const proof: string[] = [];
+    let position = index;
+    for (const layer of this.layers.slice(0, -1)) {
+      const sibling = position ^ 1;
+      if (sibling < layer.length) proof.push(layer[sibling]);
+      position = Math.floor(position / 2);
+    }
+    return proof;
+  }
+
+  /**
+   * Secret index for a fill of `fillAmount` after `filledAmount` of `totalAmount`
+   * is already filled. Throws when the fill would reuse a consumed index, i.e.
+   * it is too small to reach the next part.
+   */
+  indexForFill(totalAmount: bigint, filledAmount: bigint, fillAmount: bigint): number {
+    if (fillAmount <= 0n || filledAmount < 0n || filledAmount + fillAmount > totalAmount) {
+      throw new ValidationError('Fill is outside the order', {
+        totalAmount: totalAmount.toString(),
+        filledAmount: filledAmount.toString(),
+        fillAmount: fillAmount.toString()
+      });
+    }
+    const after = filledAmount + fillAmount;
+    const index = after === totalAmount
+      ? this.parts
+      : Number((after - 1n) * BigInt(this.parts) / totalAmount);
+    const last = this.lastConsumed();
+    if (last !== undefined && index <= last) {
+      throw new ValidationError(`Fill does not reach a new part; secret ${index} is already used`, { index, last });
+    }
+    return index;
+  }
+
+  consume(index: number): void {
+    this.checkIndex(index);
+    this.consumed.add(index);
+  }
+
+  isConsumed(index: number): boolean {
+    return this.consumed.has(index);
+  }
+
+  /** True once the completing secret has been used */
+  get complete(): boolean {
+    return this.consumed.has(this.parts);
+  }
+
+  state(): MerkleFillState {
+    return { parts: this.parts, root: this.root, consumed: [...this.consumed].sort((a, b) => a - b) };
+  }
+
+  /** Store every secret in `vault` so `load` can resume the order later */
+  async save(vault: SecretVault, swapId: string): Promise<MerkleFillState> {
+    for (const [index, secret] of this.secrets.entries()) {
+      await vault.put(`${swapId}:${index}`, secret);
+    }
+    return this.state();
+  }
+
+  zeroize(): void {
+    for (const secret of this.secrets) secret.zeroize();
+  }
+
+  private lastConsumed(): number | undefined {
+    return this.consumed.size > 0 ? Math.max(...this.consumed) : undefined;
+  }
+
+  private checkIndex(index: number): void {
+    if (!Number.isInteger(index) || index < 0 || index > this.parts) {
+      throw new ValidationError(`Secret index ${index} out of range 0..${this.parts}`);
+    }
+  }
+}
+
+export function merkleLeaf(index: number, secretHash: string): string {
+  return ethers.solidityPackedKeccak256(['uint64', 'bytes32'], [index, secretHash]);
+}
+
+/**
+ * OpenZeppelin MerkleProof.verify: hash the leaf up through the proof with sorted pairs
+ */
+export function verifyMerkleProof(leaf: string, proof: readonly string[], root: string): boolean {
+  const computed = proof.reduce(hashPair, leaf);
+  return computed.toLowerCase() === root.toLowerCase();
+}
+
+function buildLayers(leaves: string[]): string[][] {
+  const layers = [leaves];
+  while (layers[layers.length - 1].length > 1) {
+    const below = layers[layers.length - 1];
+    const layer: string[] = [];
+    for (let i = 0; i < below.length; i += 2) {
+      // An odd node out is carried up unchanged
+      layer.push(i + 1 < below.length ? hashPair(below[i], below[i + 1]) : below[i]);
+    }
+    layers.push(layer);
+  }
+  return layers;
+}
+
+function hashPair(a: string, b: string): string {
+  return BigInt(a) < BigInt(b)
+    ? ethers.keccak256(ethers.concat([a, b]))
+    : ethers.keccak256(ethers.concat([b, a]));
+}
@@ -0,0 +1,121 @@
+import { ethers } from 'ethers';
+import { SigningCosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import type { MsgExecuteContractEncodeObject } from '@cosmjs/cosmwasm-stargate';
+import type { EncodeObject, OfflineSigner } from '@cosmjs/proto-signing';
+import type { Coin, StdFee } from '@cosmjs/stargate';
+import { toUtf8 } from '@cosmjs/encoding';
+import { TxRaw } from 'cosmjs-types/cosmos/tx/v1beta1/tx';
+import type { ExecuteMsg } from '../clients/cosmos';
+import type { CreateHTLCParams } from '../utils/htlc';
+import { ValidationError } from '../utils/errors';
+import { validateContractId, validatePreimage } from '../utils/validation';
+
+/** SimpleHTLC entry points an offline signer needs */
+export const HTLC_INTERFACE = new ethers.Interface([
+  'function fund(bytes32 contractId, address token, address beneficiary, bytes32 hashLock, uint256 timelock, uint256 value)',
+  'function fundETH(bytes32 contractId, address beneficiary, bytes32 hashLock, uint256 timelock) payable',
+  'function claim(bytes32 contractId, bytes32 preimage)',
+  'function refund(bytes32 contractId)'
+]);
+
+/**
+ * Account state a Cosmos transaction commits to. Offline there is no node to
+ * ask, so it has to come from the online side (e.g. `getAccount`) beforehand.
+ */
+export interface CosmosSignerData {
+  chainId: string;
+  accountNumber: number;
+  sequence: number;
+}
+
+/**
+ * Everything an EVM transaction needs that a node would normally fill in
+ */
+export interface EvmTxParams {
+  chainId: number | bigint;
+  htlcAddress: string;
+  nonce: number;
+  gasLimit: bigint | string;
+  maxFeePerGas: bigint | string;
+  maxPriorityFeePerGas: bigint | string;
+}
+
+export function executeContractMsg(
+  sender: string,
+  contract: string,
+  msg: ExecuteMsg,
+  funds: Coin[] = []
+): MsgExecuteContractEncodeObject {
+  return {
+    typeUrl: '/cosmwasm.wasm.v1.MsgExecuteContract',
+    value: { sender, contract, msg: toUtf8(JSON.stringify(msg)), funds }
+  };
+}
+
+/**
+ * Sign Cosmos messages without a node connection. Returns TxRaw bytes ready for
+ * `broadcastTx` on any client. The fee is not simulated, so size it generously.
+ */
+export async function signCosmosOffline(
+  signer: OfflineSigner,
+  signerAddress: string,
+  messages: EncodeObject[],
+  fee: StdFee,
+  signerData: CosmosSignerData,
+  memo = ''
+): Promise<Uint8Array> {
+  const client = await SigningCosmWasmClient.offline(signer);
+  try {
+    const raw = await client.sign(signerAddress, messages, fee, memo, signerData);
+    return TxRaw.encode(raw).finish();
+  } finally {
+    client.disconnect();
+  }
+}
+
+export function evmFundTx(params: EvmTxParams, htlc: CreateHTLCParams): ethers.TransactionRequest {
+  validateContractId(htlc.contractId);
+  const native = !htlc.token || htlc.token === ethers.ZeroAddress;
+  const data = native
+    ? HTLC_INTERFACE.encodeFunctionData('fundETH', [htlc.contractId, htlc.beneficiary, htlc.hashLock, htlc.timelock])
+    : HTLC_INTERFACE.encodeFunctionData('fund', [htlc.contractId, htlc.token, htlc.beneficiary, htlc.hashLock, htlc.timelock, htlc.value]);
+  return evmTx(params, data, native ? BigInt(htlc.value) : 0n);
+}
+
+export function evmClaimTx(params: EvmTxParams, contractId: string, preimage: string): ethers.TransactionRequest {
+  validateContractId(contractId);
+  validatePreimage(preimage);
+  return evmTx(params, HTLC_INTERFACE.encodeFunctionData('claim', [contractId, preimage]));
+}
+
+export function evmRefundTx(params: EvmTxParams, contractId: string): ethers.TransactionRequest {
+  validateContractId(contractId);
+  return evmTx(params, HTLC_INTERFACE.encodeFunctionData('refund', [contractId]));
+}
+
+/**
+ * Sign an EVM transaction without a provider. Returns the serialized signed
+ * transaction for `eth_sendRawTransaction`.
+ */
+export async function signEvmOffline(wallet: ethers.Wallet, tx: ethers.TransactionRequest): Promise<string> {
+  for (const field of ['chainId', 'nonce', 'gasLimit', 'maxFeePerGas', 'maxPriorityFeePerGas'] as const) {
+    if (tx[field] === undefined || tx[field] === null) {
+      throw new ValidationError(`Offline EVM transactions need an explicit ${field}`);
+    }
+  }
+  return wallet.signTransaction({ ...tx, type: 2 });
+}
+
+function evmTx(params: EvmTxParams, data: string, value = 0n): ethers.TransactionRequest {
+  return {
+    type: 2,
+    chainId: BigInt(params.chainId),
+    to: params.htlcAddress,
+    nonce: params.nonce,
+    gasLimit: BigInt(params.gasLimit),
+    maxFeePerGas: BigInt(params.maxFeePerGas),
+    maxPriorityFeePerGas: BigInt(params.maxPriorityFeePerGas),
+    data,
+    value
+  };
+}
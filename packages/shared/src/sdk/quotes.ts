@@ -0,0 +1,241 @@
+import axios from 'axios';
+import { z } from 'zod';
+import type { FusionClient } from '../services/fusion/FusionClient';
+import type { AssetRef } from './routing';
+
+export interface QuoteAsset extends AssetRef {
+  decimals: number;
+}
+
+export interface QuoteRequest {
+  from: QuoteAsset;
+  to: QuoteAsset;
+  /** Base units of `from` */
+  amountIn: bigint;
+  /** Address that will send the input; some sources need it */
+  taker?: string;
+}
+
+export interface QuoteFee {
+  label: string;
+  amount: bigint;
+  /** Asset the fee is charged in */
+  asset: AssetRef;
+  /** Whether `amountOut` already has this fee taken out */
+  deducted: boolean;
+}
+
+/**
+ * A quote from any source in one shape. `amountOut` is what the taker
+ * receives; fees that are not taken out of it (e.g. gas paid separately) are
+ * listed with `deducted: false`.
+ */
+export interface Quote {
+  source: string;
+  request: QuoteRequest;
+  amountOut: bigint;
+  fees: QuoteFee[];
+  /** Output per input in whole units, after every fee the aggregator could price */
+  effectiveRate: number;
+  /** Unix seconds after which the quote will not be honored */
+  expiresAt?: number;
+  /** Source-specific handle to accept the quote with */
+  quoteId?: string;
+  raw: unknown;
+}
+
+export interface QuoteSource {
+  readonly name: string;
+  /** A quote, or null when the source does not serve this pair */
+  quote(request: QuoteRequest): Promise<Omit<Quote, 'effectiveRate'> | null>;
+}
+
+export interface QuoteAggregatorOptions {
+  /** Per-source timeout (default 5s) */
+  timeoutMs?: number;
+  /**
+   * Value of a fee in output base units, for fees not already deducted. Fees it
+   * returns undefined for are left out of the comparison.
+   */
+  priceFee?: (fee: QuoteFee, request: QuoteRequest) => bigint | undefined;
+}
+
+/**
+ * Asks every source for a quote in parallel and ranks them by what the taker
+ * ends up with after fees. A failing or slow source is reported, not fatal.
+ */
+export class QuoteAggregator {
+  constructor(private sources: QuoteSource[], private options: QuoteAggregatorOptions = {}) {}
+
+  async quotes(request: QuoteRequest): Promise<{ quotes: Quote[]; errors: { source: string; error: string }[] }> {
+    const quotes: Quote[] = [];
+    const errors: { source: string; error: string }[] = [];
+
+    await Promise.all(this.sources.map(async source => {
+      try {
+        const quote = await this.withTimeout(source.quote(request));
+        if (quote && quote.amountOut > 0n) {
+          quotes.push({ ...quote, effectiveRate: this.rate(request, this.netOut(quote)) });
+        }
+      } catch (error) {
+        errors.push({ source: source.name, error: (error as Error).message });
+      }
+    }));
+
+    quotes.sort((a, b) => {
+      const difference = this.netOut(b) - this.netOut(a);
+      return difference > 0n ? 1 : difference < 0n ? -1 : 0;
+    });
+    return { quotes, errors };
+  }
+
+  async best(request: QuoteRequest): Promise<Quote | null> {
+    const now = Math.floor(Date.now() / 1000);
+    const { quotes } = await this.quotes(request);
+    return quotes.find(quote => !quote.expiresAt || quote.expiresAt > now) ?? null;
+  }
+
+  private netOut(quote: Omit<Quote, 'effectiveRate'>): bigint {
+    let net = quote.amountOut;
+    for (const fee of quote.fees) {
+      if (fee.deducted) continue;
+      const value = this.options.priceFee?.(fee, quote.request);
+      if (value !== undefined) net -= value;
+    }
+    return net;
+  }
+
+  private rate(request: QuoteRequest, amountOut: bigint): number {
+    return (Number(amountOut) / 10 ** request.to.decimals) / (Number(request.amountIn) / 10 ** request.from.decimals);
+  }
+
+  private withTimeout<T>(promise: Promise<T>): Promise<T> {
+    const timeoutMs = this.options.timeoutMs ?? 5000;
+    let timer: ReturnType<typeof setTimeout>;
+    return Promise.race([
+      promise,
+      new Promise<never>((_, reject) => {
+        timer = setTimeout(() => reject(new Error(`Timed out after ${timeoutMs}ms`)), timeoutMs);
+      })
+    ]).finally(() => clearTimeout(timer));
+  }
+}
+
+const RfqResponseSchema = z.object({
+  amountOut: z.string().regex(/^\d+$/),
+  fee: z.string().regex(/^\d+$/).optional(),
+  expiresAt: z.number().optional(),
+  quoteId: z.string().optional()
+});
+
+/**
+ * A resolver's RFQ endpoint: POST {url}/quote with the pair and amount, answered
+ * with `{ amountOut, fee?, expiresAt?, quoteId? }` where amountOut is net of fee
+ */
+export function rfqSource(name: string, url: string, options: { apiKey?: string } = {}): QuoteSource {
+  return {
+    name,
+    async quote(request) {
+      const response = await axios.post(`${url.replace(/\/+$/, '')}/quote`, {
+        from: request.from,
+        to: request.to,
+        amountIn: request.amountIn.toString(),
+        taker: request.taker
+      }, { headers: options.apiKey ? { Authorization: `Bearer ${options.apiKey}` } : {} });
+      if (response.status === 204) return null;
+
+      const body = RfqResponseSchema.parse(response.data);
+      return {
+        source: name,
+        request,
+        amountOut: BigInt(body.amountOut),
+        fees: body.fee ? [{ label: 'resolver fee', amount: BigInt(body.fee), asset: request.to, deducted: true }] : [],
+        expiresAt: body.expiresAt,
+        quoteId: body.quoteId,
+        raw: response.data
+      };
+    }
+  };
+}
+
+const SkipRouteSchema = z.object({
+  amount_out: z.string(),
+  estimated_fees: z.array(z.object({
+    fee_type: z.string().optional(),
+    amount: z.string(),
+    origin_asset: z.object({ denom: z.string(), chain_id: z.string() })
+  })).optional()
+});
+
+/**
+ * Skip Go routes (IBC, CCTP, bridges). `chainIds` maps registry keys to the
+ * chain IDs Skip uses, e.g. { osmosisTestnet: 'osmo-test-5', sepolia: '11155111' }.
+ */
+export function skipSource(chainIds: Record<string, string>, options: { apiUrl?: string; apiKey?: string } = {}): QuoteSource {
+  const apiUrl = options.apiUrl ?? 'https://api.skip.build';
+  return {
+    name: 'skip',
+    async quote(request) {
+      const sourceChain = chainIds[request.from.chain];
+      const destChain = chainIds[request.to.chain];
+      if (!sourceChain || !destChain) return null;
+
+      const response = await axios.post(`${apiUrl}/v2/fungible/route`, {
+        amount_in: request.amountIn.toString(),
+        source_asset_denom: request.from.asset,
+        source_asset_chain_id: sourceChain,
+        dest_asset_denom: request.to.asset,
+        dest_asset_chain_id: destChain,
+        allow_multi_tx: true
+      }, { headers: options.apiKey ? { Authorization: options.apiKey } : {} });
+
+      const route = SkipRouteSchema.parse(response.data);
+      const registryKey = Object.fromEntries(Object.entries(chainIds).map(([key, id]) => [id, key]));
+      return {
+        source: 'skip',
+        request,
+        amountOut: BigInt(route.amount_out),
+        // Skip's estimated fees are paid on top of the route, not out of amount_out
+        fees: (route.estimated_fees || []).map(fee => ({
+          label: fee.fee_type ?? 'bridge fee',
+          amount: BigInt(fee.amount),
+          asset: { chain: registryKey[fee.origin_asset.chain_id] ?? fee.origin_asset.chain_id, asset: fee.origin_asset.denom },
+          deducted: false
+        })),
+        raw: response.data
+      };
+    }
+  };
+}
+
+/**
+ * Same-chain 1inch quotes through a FusionClient; skipped for cross-chain pairs.
+ * Gas is listed in the chain's native asset as a fee not deducted from the output.
+ */
+export function fusionSource(client: FusionClient): QuoteSource {
+  return {
+    name: '1inch-fusion',
+    async quote(request) {
+      if (request.from.chain !== request.to.chain || !request.taker) return null;
+      const quote = await client.getFusionQuote({
+        src: request.from.asset,
+        dst: request.to.asset,
+        amount: request.amountIn.toString(),
+        from: request.taker,
+        slippage: 1
+      });
+      return {
+        source: '1inch-fusion',
+        request,
+        amountOut: BigInt(quote.toTokenAmount),
+        fees: [{
+          label: 'gas',
+          amount: BigInt(quote.estimatedGas) * BigInt(quote.gasPrice),
+          asset: { chain: request.from.chain, asset: 'native' },
+          deducted: false
+        }],
+        raw: quote
+      };
+    }
+  };
+}
@@ -0,0 +1,168 @@
+import { EventEmitter } from 'events';
+import type { HTLCEvent, Unsubscribe } from '../resolver/adapters/types';
+import { SwapState } from '../utils/htlc';
+import type { SwapChain } from './chain';
+
+type Funded = Extract<HTLCEvent, { kind: 'funded' }>;
+
+/** A chain the relay acts on, and the HTLC events it learns about it from */
+export interface RelayEndpoint {
+  swapChain: SwapChain;
+  subscribe(handler: (event: HTLCEvent) => void): Promise<Unsubscribe>;
+}
+
+export interface SwapRelayOptions {
+  /** Claim HTLCs paying us once their preimage is revealed elsewhere (default true) */
+  claim?: boolean;
+  /** Refund our own HTLCs once they expire (default true) */
+  refund?: boolean;
+}
+
+/**
+ * The relaying a counterparty needs for their own swaps: when a claim reveals
+ * a preimage on one chain, claim every open HTLC with the same hashlock that
+ * pays one of our addresses, and refund HTLCs we funded once they expire.
+ * Time only matters to `sweep`, which the caller schedules and which also
+ * retries failed claims, so a test can drive the relay step by step.
+ *
+ * Emits 'claimed', 'refunded' ({ chain, contractId, txHash }) and 'error'
+ * ({ chain, contractId, error }).
+ */
+export class SwapRelay extends EventEmitter {
+  private endpoints = new Map<string, RelayEndpoint>();
+  private unsubscribes: Unsubscribe[] = [];
+  private funded = new Map<string, Funded>();
+  /** Preimages revealed so far by hashlock, for locks whose events arrive after the claim */
+  private revealed = new Map<string, string>();
+  /** HTLCs seen claimed or refunded, so a late or repeated funding event can't reopen them */
+  private closed = new Set<string>();
+  private inFlight = new Set<string>();
+  private handling = new Set<Promise<void>>();
+
+  constructor(endpoints: RelayEndpoint[], private options: SwapRelayOptions = {}) {
+    super();
+    for (const endpoint of endpoints) this.endpoints.set(endpoint.swapChain.chain, endpoint);
+  }
+
+  /** Subscribe to every chain; events are matched against every chain's address, so all are known first */
+  async start(): Promise<void> {
+    for (const [chain, endpoint] of this.endpoints) {
+      this.unsubscribes.push(await endpoint.subscribe(event => {
+        const handled = this.onEvent(event)
+          .catch(error => { this.emit('error', { chain, contractId: event.contractId, error }); })
+          .finally(() => this.handling.delete(handled));
+        this.handling.add(handled);
+      }));
+    }
+  }
+
+  stop(): void {
+    this.unsubscribes.forEach(unsubscribe => unsubscribe());
+    this.unsubscribes = [];
+  }
+
+  /** HTLCs seen so far that involve one of our addresses */
+  tracked(): Funded[] {
+    return [...this.funded.values()];
+  }
+
+  /** Resolves once every event delivered so far has been acted on */
+  async idle(): Promise<void> {
+    while (this.handling.size) await Promise.all(this.handling);
+  }
+
+  /** The periodic pass: retry claims that failed earlier, then refund what has expired */
+  async sweep(): Promise<void> {
+    await this.retryClaims();
+    await this.sweepRefunds();
+  }
+
+  /** Claim every HTLC paying us whose preimage we already know, e.g. after a failed submission */
+  async retryClaims(): Promise<void> {
+    if (this.options.claim === false) return;
+    const ready = [...this.funded.values()].filter(htlc =>
+      this.isOurs(htlc.chain, htlc.beneficiary) && this.revealed.has(htlc.hashLock.toLowerCase())
+    );
+    await Promise.all(ready.map(htlc => this.settle('claim', htlc, this.revealed.get(htlc.hashLock.toLowerCase()))));
+  }
+
+  /** Refund every HTLC we funded whose timelock has passed on its chain */
+  async sweepRefunds(): Promise<void> {
+    if (this.options.refund === false) return;
+    for (const htlc of this.funded.values()) {
+      if (!this.isOurs(htlc.chain, htlc.originator)) continue;
+      const chain = this.endpoints.get(htlc.chain)!.swapChain;
+      try {
+        if (await chain.currentTime() > htlc.timelock) await this.settle('refund', htlc);
+      } catch (error) {
+        this.emit('error', { chain: htlc.chain, contractId: htlc.contractId, error });
+      }
+    }
+  }
+
+  private async onEvent(event: HTLCEvent): Promise<void> {
+    if (event.kind === 'funded') {
+      const ours = [event.originator, event.beneficiary].some(address => this.isOurs(event.chain, address));
+      if (!ours || this.closed.has(key(event.chain, event.contractId))) return;
+      this.funded.set(key(event.chain, event.contractId), event);
+      const preimage = this.revealed.get(event.hashLock.toLowerCase());
+      if (preimage && this.options.claim !== false && this.isOurs(event.chain, event.beneficiary)) {
+        await this.settle('claim', event, preimage);
+      }
+      return;
+    }
+    const id = key(event.chain, event.contractId);
+    if (event.kind === 'refunded') {
+      this.close(id);
+      return;
+    }
+
+    // A claim: its preimage opens every other lock under the same hashlock
+    const claimed = this.funded.get(id) ?? await this.endpoints.get(event.chain)?.swapChain.getHtlc(event.contractId);
+    this.close(id);
+    if (!claimed || this.options.claim === false) return;
+
+    const hashLock = claimed.hashLock.toLowerCase();
+    this.revealed.set(hashLock, event.preimage);
+    const targets = [...this.funded.values()].filter(htlc =>
+      htlc.hashLock.toLowerCase() === hashLock && this.isOurs(htlc.chain, htlc.beneficiary)
+    );
+    await Promise.all(targets.map(htlc => this.settle('claim', htlc, event.preimage)));
+  }
+
+  private async settle(action: 'claim' | 'refund', htlc: Funded, preimage?: string): Promise<void> {
+    const id = key(htlc.chain, htlc.contractId);
+    if (this.inFlight.has(id)) return;
+    this.inFlight.add(id);
+    const chain = this.endpoints.get(htlc.chain)!.swapChain;
+    try {
+      // Someone else may have settled it already
+      const current = await chain.getHtlc(htlc.contractId);
+      if (!current || current.state !== SwapState.OPEN) {
+        this.close(id);
+        return;
+      }
+      const { txHash } = action === 'claim' ? await chain.claim(htlc.contractId, preimage!) : await chain.refund(htlc.contractId);
+      this.close(id);
+      this.emit(action === 'claim' ? 'claimed' : 'refunded', { chain: htlc.chain, contractId: htlc.contractId, txHash });
+    } catch (error) {
+      this.emit('error', { chain: htlc.chain, contractId: htlc.contractId, error });
+    } finally {
+      this.inFlight.delete(id);
+    }
+  }
+
+  private close(id: string): void {
+    this.funded.delete(id);
+    this.closed.add(id);
+  }
+
+  private isOurs(chain: string, address: string): boolean {
+    const ours = this.endpoints.get(chain)?.swapChain.address;
+    return !!ours && ours.toLowerCase() === address.toLowerCase();
+  }
+}
+
+function key(chain: string, contractId: string): string {
+  return `${chain}:${contractId.toLowerCase()}`;
+}
@@ -0,0 +1,131 @@
+import { ValidationError } from '../utils/errors';
+import { chainTiming } from './timelock';
+
+export interface AssetRef {
+  /** Chain registry key, e.g. "junoTestnet" */
+  chain: string;
+  /** Denom, CW20 / ERC-20 address, or "native" */
+  asset: string;
+}
+
+/**
+ * A corridor someone will make a direct HTLC swap on, in one direction
+ */
+export interface Corridor {
+  from: AssetRef;
+  to: AssetRef;
+  /** Output in `to` base units for an input in `from` base units, net of the counterparty's fee */
+  quote(amountIn: bigint): bigint;
+  /** Largest input the counterparty will take */
+  maxAmountIn?: bigint;
+}
+
+export interface ChainTiming {
+  /** Time to trust a block on this chain, i.e. to act on a revealed secret (seconds) */
+  finalitySeconds: number;
+  /** Time to get a claim included once sent (seconds) */
+  inclusionSeconds: number;
+}
+
+export interface PlanOptions {
+  maxHops?: number;
+  /** Unix seconds the plan is built for (default now) */
+  now?: number;
+  /** How long the final leg stays open for the recipient to claim (default 1h) */
+  finalLegSeconds?: number;
+  /** Extra slack added to every timelock gap (default 15 min) */
+  safetyMarginSeconds?: number;
+  /** Refuse plans whose first leg locks funds for longer than this (default 48h) */
+  maxTotalSeconds?: number;
+  /** Per-chain timing; chains not listed get `chainTiming` from the registry */
+  timing?: Record<string, ChainTiming>;
+}
+
+/**
+ * One HTLC in the chain of legs. All legs share the plan's hashlock; leg i is
+ * claimed by the counterparty of corridor i once the secret appears on leg i+1.
+ */
+export interface PlannedLeg {
+  chain: string;
+  asset: string;
+  amount: bigint;
+  /** Absolute expiry, Unix seconds; strictly decreasing along the route */
+  timelock: number;
+}
+
+export interface RoutePlan {
+  corridors: Corridor[];
+  legs: PlannedLeg[];
+  amountOut: bigint;
+}
+
+/**
+ * Best route from one asset to another through at most `maxHops` corridors,
+ * with amounts and timelocks filled in. Routes are compared by output amount,
+ * then by hop count. Returns null when no route fits.
+ *
+ * Timelocks are staggered from the end backwards: the final leg gets
+ * `finalLegSeconds`, and each earlier leg outlives the next one by the time to
+ * see the secret revealed there, get a claim included here, and the margin, so
+ * no intermediary can be left holding a claim it has no time to make.
+ */
+export function planRoute(
+  from: AssetRef,
+  to: AssetRef,
+  amountIn: bigint,
+  corridors: Corridor[],
+  options: PlanOptions = {}
+): RoutePlan | null {
+  if (amountIn <= 0n) throw new ValidationError('Amount must be positive');
+  const maxHops = options.maxHops ?? 3;
+  let best: RoutePlan | null = null;
+
+  const visit = (at: AssetRef, amount: bigint, path: Corridor[], seen: Set<string>) => {
+    if (path.length > 0 && key(at) === key(to)) {
+      const plan = schedule(from, amountIn, path, options);
+      if (plan && (!best || plan.amountOut > best.amountOut ||
+        (plan.amountOut === best.amountOut && plan.corridors.length < best.corridors.length))) {
+        best = plan;
+      }
+      return;
+    }
+    if (path.length === maxHops) return;
+
+    for (const corridor of corridors) {
+      if (key(corridor.from) !== key(at) || seen.has(key(corridor.to))) continue;
+      if (corridor.maxAmountIn !== undefined && amount > corridor.maxAmountIn) continue;
+      const out = corridor.quote(amount);
+      if (out <= 0n) continue;
+      seen.add(key(corridor.to));
+      visit(corridor.to, out, [...path, corridor], seen);
+      seen.delete(key(corridor.to));
+    }
+  };
+
+  visit(from, amountIn, [], new Set([key(from)]));
+  return best;
+}
+
+function schedule(from: AssetRef, amountIn: bigint, path: Corridor[], options: PlanOptions): RoutePlan | null {
+  const now = options.now ?? Math.floor(Date.now() / 1000);
+  const margin = options.safetyMarginSeconds ?? 900;
+  const timing = (chain: string) => options.timing?.[chain] ?? chainTiming(chain);
+
+  const legs: PlannedLeg[] = [{ chain: from.chain, asset: from.asset, amount: amountIn, timelock: 0 }];
+  for (const corridor of path) {
+    legs.push({ chain: corridor.to.chain, asset: corridor.to.asset, amount: corridor.quote(legs[legs.length - 1].amount), timelock: 0 });
+  }
+
+  legs[legs.length - 1].timelock = now + (options.finalLegSeconds ?? 3600);
+  for (let i = legs.length - 2; i >= 0; i--) {
+    const gap = timing(legs[i + 1].chain).finalitySeconds + timing(legs[i].chain).inclusionSeconds + margin;
+    legs[i].timelock = legs[i + 1].timelock + gap;
+  }
+
+  if (legs[0].timelock - now > (options.maxTotalSeconds ?? 48 * 3600)) return null;
+  return { corridors: path, legs, amountOut: legs[legs.length - 1].amount };
+}
+
+function key(asset: AssetRef): string {
+  return `${asset.chain}:${asset.asset}`;
+}
@@ -0,0 +1,238 @@
+import { ethers } from 'ethers';
+import { CosmWasmClient } from '@cosmjs/cosmwasm-stargate';
+import { NetworkError } from '../utils/errors';
+
+export interface FailoverOptions {
+  /** Per-request timeout (default 10s) */
+  timeoutMs?: number;
+  /** Send reads to every healthy endpoint at once and take the first answer */
+  race?: boolean;
+  /** How long an endpoint that failed is passed over before being retried (default 30s) */
+  cooldownMs?: number;
+  /**
+   * Errors that mean the request itself was rejected (a revert, a contract error),
+   * which another endpoint would only repeat. They are thrown without failover.
+   */
+  isFatal?: (error: Error) => boolean;
+}
+
+export interface Connector<C> {
+  connect(endpoint: string): Promise<C>;
+  /** Cheap call that proves the endpoint is serving, e.g. fetching the height */
+  check(client: C): Promise<unknown>;
+  disconnect?(client: C): void;
+}
+
+export interface EndpointStatus {
+  endpoint: string;
+  healthy: boolean;
+  consecutiveFailures: number;
+  latencyMs?: number;
+  lastError?: string;
+}
+
+interface Endpoint<C> extends EndpointStatus {
+  client?: Promise<C>;
+  downUntil: number;
+}
+
+/**
+ * A set of interchangeable RPC endpoints. Requests go to the preferred healthy
+ * endpoint and fail over on errors or timeouts; endpoints that failed sit out a
+ * cooldown, but are still tried as a last resort when nothing else answers.
+ * Periodic health checks keep the preference order fresh between requests.
+ */
+export class FailoverPool<C> {
+  private endpoints: Endpoint<C>[];
+  private preferred = 0;
+  private healthTimer?: ReturnType<typeof setInterval>;
+
+  constructor(urls: string[], private connector: Connector<C>, private options: FailoverOptions = {}) {
+    if (urls.length === 0) {
+      throw new Error('FailoverPool needs at least one endpoint');
+    }
+    this.endpoints = urls.map(endpoint => ({ endpoint, healthy: true, consecutiveFailures: 0, downUntil: 0 }));
+  }
+
+  /**
+   * Run a call on one endpoint at a time until one succeeds
+   */
+  async request<T>(call: (client: C, endpoint: string) => Promise<T>): Promise<T> {
+    const errors: string[] = [];
+    for (const index of this.order()) {
+      try {
+        const result = await this.attempt(index, call);
+        this.preferred = index;
+        return result;
+      } catch (error) {
+        if (this.isFatal(error as Error)) throw error;
+        errors.push(`${this.endpoints[index].endpoint}: ${(error as Error).message}`);
+      }
+    }
+    throw new NetworkError('All endpoints failed', { errors });
+  }
+
+  /**
+   * Run a call on every available endpoint and return the first success.
+   * A fatal error is only thrown if no endpoint succeeds.
+   */
+  async race<T>(call: (client: C, endpoint: string) => Promise<T>): Promise<T> {
+    const candidates = this.order().filter(index => this.available(index));
+    const attempts = (candidates.length > 0 ? candidates : this.order()).map(index => this.attempt(index, call));
+    try {
+      return await firstSuccess(attempts);
+    } catch (failures) {
+      const fatal = (failures as Error[]).find(failure => this.isFatal(failure));
+      if (fatal) throw fatal;
+      throw new NetworkError('All endpoints failed', { errors: (failures as Error[]).map(failure => failure.message) });
+    }
+  }
+
+  /** `race` when the pool is configured to race reads, otherwise `request` */
+  read<T>(call: (client: C, endpoint: string) => Promise<T>): Promise<T> {
+    return this.options.race ? this.race(call) : this.request(call);
+  }
+
+  /**
+   * Probe every endpoint and prefer the fastest healthy one
+   */
+  async checkHealth(): Promise<EndpointStatus[]> {
+    await Promise.allSettled(this.endpoints.map((_, index) => this.attempt(index, client => this.connector.check(client))));
+    const healthy = this.endpoints
+      .map((endpoint, index) => ({ endpoint, index }))
+      .filter(({ endpoint }) => endpoint.healthy)
+      .sort((a, b) => (a.endpoint.latencyMs ?? Infinity) - (b.endpoint.latencyMs ?? Infinity));
+    if (healthy.length > 0) this.preferred = healthy[0].index;
+    return this.status();
+  }
+
+  startHealthChecks(intervalMs = 30000): void {
+    this.stopHealthChecks();
+    this.healthTimer = setInterval(() => { void this.checkHealth(); }, intervalMs);
+  }
+
+  stopHealthChecks(): void {
+    if (this.healthTimer) clearInterval(this.healthTimer);
+    this.healthTimer = undefined;
+  }
+
+  status(): EndpointStatus[] {
+    return this.endpoints.map(({ endpoint, healthy, consecutiveFailures, latencyMs, lastError }) =>
+      ({ endpoint, healthy, consecutiveFailures, latencyMs, lastError }));
+  }
+
+  close(): void {
+    this.stopHealthChecks();
+    for (const endpoint of this.endpoints) {
+      const client = endpoint.client;
+      endpoint.client = undefined;
+      client?.then(connected => this.connector.disconnect?.(connected)).catch(() => undefined);
+    }
+  }
+
+  /** Preferred endpoint first, then the others; endpoints in cooldown go last */
+  private order(): number[] {
+    const rotated = this.endpoints.map((_, offset) => (this.preferred + offset) % this.endpoints.length);
+    return [...rotated.filter(index => this.available(index)), ...rotated.filter(index => !this.available(index))];
+  }
+
+  private available(index: number): boolean {
+    return this.endpoints[index].downUntil <= Date.now();
+  }
+
+  private async attempt<T>(index: number, call: (client: C, endpoint: string) => Promise<T>): Promise<T> {
+    const endpoint = this.endpoints[index];
+    const started = Date.now();
+    try {
+      endpoint.client ??= this.connector.connect(endpoint.endpoint);
+      const result = await this.withTimeout(endpoint.client.then(client => call(client, endpoint.endpoint)));
+      Object.assign(endpoint, { healthy: true, consecutiveFailures: 0, downUntil: 0, latencyMs: Date.now() - started });
+      return result;
+    } catch (error) {
+      if (!this.isFatal(error as Error)) {
+        endpoint.healthy = false;
+        endpoint.consecutiveFailures++;
+        endpoint.lastError = (error as Error).message;
+        endpoint.downUntil = Date.now() + (this.options.cooldownMs ?? 30000);
+        endpoint.client = undefined; // reconnect next time
+      }
+      throw error;
+    }
+  }
+
+  private isFatal(error: Error): boolean {
+    return this.options.isFatal?.(error) ?? false;
+  }
+
+  private withTimeout<T>(promise: Promise<T>): Promise<T> {
+    const timeoutMs = this.options.timeoutMs ?? 10000;
+    let timer: ReturnType<typeof setTimeout>;
+    return Promise.race([
+      promise,
+      new Promise<never>((_, reject) => {
+        timer = setTimeout(() => reject(new Error(`Timed out after ${timeoutMs}ms`)), timeoutMs);
+      })
+    ]).finally(() => clearTimeout(timer));
+  }
+}
+
+/** First fulfilled promise, or a rejection with every error once all have failed */
+function firstSuccess<T>(attempts: Promise<T>[]): Promise<T> {
+  return new Promise((resolve, reject) => {
+    const failures: Error[] = [];
+    attempts.forEach(attempt => attempt.then(resolve, error => {
+      failures.push(error);
+      if (failures.length === attempts.length) reject(failures);
+    }));
+  });
+}
+
+/**
+ * Failover pool of CosmWasm query clients
+ */
+export function cosmWasmPool(endpoints: string[], options: FailoverOptions = {}): FailoverPool<CosmWasmClient> {
+  return new FailoverPool(endpoints, {
+    connect: endpoint => CosmWasmClient.connect(endpoint),
+    check: client => client.getHeight(),
+    disconnect: client => client.disconnect()
+  }, options);
+}
+
+// Calls that only read state and are safe to race across providers
+const READ_METHODS = new Set([
+  'eth_blockNumber', 'eth_call', 'eth_chainId', 'eth_estimateGas', 'eth_feeHistory', 'eth_gasPrice',
+  'eth_getBalance', 'eth_getBlockByNumber', 'eth_getBlockByHash', 'eth_getCode', 'eth_getLogs',
+  'eth_getStorageAt', 'eth_getTransactionByHash', 'eth_getTransactionCount', 'eth_getTransactionReceipt',
+  'eth_maxPriorityFeePerGas'
+]);
+
+/**
+ * JsonRpcProvider that spreads calls over several endpoints of one chain. It
+ * drops in wherever a JsonRpcProvider is expected (wallets, contracts). Writes
+ * always go to a single endpoint at a time; reads are raced when `race` is set.
+ */
+export class FailoverJsonRpcProvider extends ethers.JsonRpcProvider {
+  readonly pool: FailoverPool<ethers.JsonRpcProvider>;
+
+  constructor(urls: string[], chainId: number, options: FailoverOptions = {}) {
+    const network = ethers.Network.from(chainId);
+    super(urls[0], network, { staticNetwork: network });
+    this.pool = new FailoverPool(urls, {
+      connect: async url => new ethers.JsonRpcProvider(url, network, { staticNetwork: network, batchMaxCount: 1 }),
+      check: provider => provider.getBlockNumber(),
+      disconnect: provider => provider.destroy()
+    }, options);
+  }
+
+  async _send(payload: ethers.JsonRpcPayload | ethers.JsonRpcPayload[]): Promise<ethers.JsonRpcResult[]> {
+    const payloads = Array.isArray(payload) ? payload : [payload];
+    const call = (provider: ethers.JsonRpcProvider) => provider._send(payload);
+    // A JSON-RPC error in the result (e.g. a revert) is an answer, not a transport failure
+    return payloads.every(entry => READ_METHODS.has(entry.method)) ? this.pool.read(call) : this.pool.request(call);
+  }
+
+  destroy(): void {
+    this.pool.close();
+    super.destroy();
+  }
+}
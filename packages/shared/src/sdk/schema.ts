@@ -0,0 +1,135 @@
+/**
+ * Release of the CosmWasm HTLC contract the message types in clients/cosmos
+ * describe. Bump it with every contract release that changes a message.
+ */
+export const HTLC_CONTRACT_VERSION = '0.1.0';
+
+type JsonSchema = Record<string, unknown>;
+
+const DRAFT_07 = 'http://json-schema.org/draft-07/schema#';
+
+const uint128: JsonSchema = {
+  description: 'A string containing a 128-bit integer in decimal representation.',
+  type: 'string'
+};
+const binary: JsonSchema = {
+  description: 'Binary data encoded in base64.',
+  type: 'string'
+};
+const empty: JsonSchema = { type: 'object', additionalProperties: false };
+
+function root(title: string, schema: JsonSchema, definitions?: Record<string, JsonSchema>): JsonSchema {
+  return { $schema: DRAFT_07, title, ...schema, ...(definitions ? { definitions } : {}) };
+}
+
+// Externally tagged enum variant, as serde writes it: { "<name>": { ... } }
+function variant(name: string, body: JsonSchema, description?: string): JsonSchema {
+  return {
+    ...(description ? { description } : {}),
+    type: 'object',
+    required: [name],
+    properties: { [name]: body },
+    additionalProperties: false
+  };
+}
+
+const instantiate = root('InstantiateMsg', {
+  type: 'object',
+  required: ['sender', 'beneficiary', 'hash_lock', 'timelock', 'amount'],
+  properties: {
+    sender: { type: 'string' },
+    beneficiary: { type: 'string' },
+    hash_lock: { $ref: '#/definitions/Binary', description: 'sha256 of the preimage' },
+    timelock: { type: 'integer', format: 'uint64', minimum: 0, description: 'Unix seconds after which the sender may refund' },
+    amount: { $ref: '#/definitions/Uint128' },
+    token: { type: ['string', 'null'], description: 'CW20 contract; null for the native denom' }
+  },
+  additionalProperties: false
+}, { Binary: binary, Uint128: uint128 });
+
+const execute = root('ExecuteMsg', {
+  oneOf: [
+    variant('fund', empty, 'Lock the native funds sent with the message'),
+    variant('claim', {
+      type: 'object',
+      required: ['preimage'],
+      properties: { preimage: { $ref: '#/definitions/Binary' } },
+      additionalProperties: false
+    }, 'Pay the beneficiary, revealing the preimage'),
+    variant('refund', empty, 'Return the funds to the sender after the timelock'),
+    variant('receive', { $ref: '#/definitions/Cw20ReceiveMsg' }, 'CW20 funding hook')
+  ]
+}, {
+  Binary: binary,
+  Uint128: uint128,
+  Cw20ReceiveMsg: {
+    type: 'object',
+    required: ['sender', 'amount', 'msg'],
+    properties: {
+      sender: { type: 'string' },
+      amount: { $ref: '#/definitions/Uint128' },
+      msg: { $ref: '#/definitions/Binary' }
+    },
+    additionalProperties: false
+  }
+});
+
+const query = root('QueryMsg', {
+  oneOf: [
+    variant('get_swap', empty),
+    variant('is_claimable', empty),
+    variant('is_refundable', empty)
+  ]
+});
+
+const swapResponse = root('SwapResponse', {
+  type: 'object',
+  required: ['sender', 'beneficiary', 'hash_lock', 'timelock', 'amount', 'state'],
+  properties: {
+    sender: { type: 'string' },
+    beneficiary: { type: 'string' },
+    hash_lock: { $ref: '#/definitions/Binary' },
+    timelock: { type: 'integer', format: 'uint64', minimum: 0 },
+    amount: { $ref: '#/definitions/Uint128' },
+    token: { type: ['string', 'null'] },
+    state: { type: 'string', enum: ['Open', 'Claimed', 'Refunded'] }
+  },
+  additionalProperties: false
+}, { Binary: binary, Uint128: uint128 });
+
+/**
+ * JSON schemas for the CosmWasm HTLC's messages and query responses, in the
+ * layout cosmwasm-schema writes, so the same codegen (ts-codegen,
+ * cosmwasm-typescript-gen, ...) works on either
+ */
+export function htlcContractSchema() {
+  return {
+    contract_name: 'htlc',
+    contract_version: HTLC_CONTRACT_VERSION,
+    idl_version: '1.0.0',
+    instantiate,
+    execute,
+    query,
+    migrate: null,
+    sudo: null,
+    responses: {
+      get_swap: swapResponse,
+      is_claimable: root('Boolean', { type: 'boolean' }),
+      is_refundable: root('Boolean', { type: 'boolean' })
+    }
+  };
+}
+
+/**
+ * The same schemas as one file each, named the way cosmwasm-schema's
+ * `write_api!` does for its raw/ directory
+ */
+export function htlcSchemaFiles(): Record<string, JsonSchema> {
+  const schema = htlcContractSchema();
+  return {
+    'instantiate.json': schema.instantiate,
+    'execute.json': schema.execute,
+    'query.json': schema.query,
+    ...Object.fromEntries(Object.entries(schema.responses).map(([name, response]) => [`response_to_${name}.json`, response]))
+  };
+}
@@ -0,0 +1,92 @@
+import { ethers } from 'ethers';
+
+export type HashAlgorithm = 'sha256' | 'keccak256' | 'hash160';
+
+export const HASH_ALGORITHMS: readonly HashAlgorithm[] = ['sha256', 'keccak256', 'hash160'];
+
+/**
+ * Digest of a preimage under one of the hashlock schemes. SimpleHTLC and the
+ * CosmWasm HTLC both use sha256; keccak256 and hash160 (Bitcoin-style
+ * ripemd160(sha256)) are for counterparties that lock with those.
+ */
+export function hashPreimage(preimage: Uint8Array, algorithm: HashAlgorithm): Uint8Array {
+  switch (algorithm) {
+    case 'sha256':
+      return ethers.getBytes(ethers.sha256(preimage));
+    case 'keccak256':
+      return ethers.getBytes(ethers.keccak256(preimage));
+    case 'hash160':
+      return ethers.getBytes(ethers.ripemd160(ethers.sha256(preimage)));
+  }
+}
+
+/**
+ * A swap preimage held as bytes so it can be wiped once it has been revealed
+ * or stored. Strings cannot be wiped, so `hex()` should be called as late as
+ * possible and its result not kept around.
+ */
+export class Secret {
+  private bytes: Uint8Array;
+  private wiped = false;
+
+  constructor(bytes: Uint8Array) {
+    if (bytes.length !== 32) {
+      throw new Error('A swap secret must be 32 bytes');
+    }
+    this.bytes = new Uint8Array(bytes);
+  }
+
+  /** 32 bytes from the platform CSPRNG (crypto.getRandomValues / node crypto) */
+  static generate(): Secret {
+    const random = ethers.randomBytes(32);
+    const secret = new Secret(random);
+    random.fill(0);
+    return secret;
+  }
+
+  static fromHex(hex: string): Secret {
+    return new Secret(ethers.getBytes(hex.startsWith('0x') ? hex : `0x${hex}`));
+  }
+
+  /** 0x-prefixed hex digest of the secret */
+  hashLock(algorithm: HashAlgorithm = 'sha256'): string {
+    return ethers.hexlify(hashPreimage(this.expose(), algorithm));
+  }
+
+  /** 0x-prefixed hex, the form claim calls take */
+  hex(): string {
+    return ethers.hexlify(this.expose());
+  }
+
+  /** Copy of the raw bytes; the caller owns and should wipe it */
+  toBytes(): Uint8Array {
+    return new Uint8Array(this.expose());
+  }
+
+  zeroize(): void {
+    this.bytes.fill(0);
+    this.wiped = true;
+  }
+
+  get isZeroized(): boolean {
+    return this.wiped;
+  }
+
+  private expose(): Uint8Array {
+    if (this.wiped) {
+      throw new Error('Secret has been zeroized');
+    }
+    return this.bytes;
+  }
+}
+
+/**
+ * Run `fn` with a fresh secret and wipe it afterwards, even if `fn` throws
+ */
+export async function withSecret<T>(fn: (secret: Secret) => Promise<T> | T, secret: Secret = Secret.generate()): Promise<T> {
+  try {
+    return await fn(secret);
+  } finally {
+    secret.zeroize();
+  }
+}
@@ -0,0 +1,148 @@
+import WebSocket from 'isomorphic-ws';
+import { parseTxEvents } from './events';
+import type { SwapEvent, TxEvent } from './events';
+
+export type ObservedSwapEvent = SwapEvent & { txHash: string; height: number };
+
+export interface SubscribeOptions {
+  /** Only events from these HTLC instances */
+  contracts?: string[];
+  /** Delay before reconnecting after the socket drops (default 2s) */
+  reconnectDelayMs?: number;
+  /**
+   * CometBFT 0.34 nodes base64-encode event attributes over the websocket;
+   * 0.37 and later send them as plain strings (the default)
+   */
+  attributeEncoding?: 'plain' | 'base64';
+}
+
+/** Transactions at or above a height, used to fill the gap a reconnect leaves */
+export type BackfillFn = (fromHeight: number) => Promise<{ hash: string; height: number; events: readonly TxEvent[] }[]>;
+
+const QUERY = "tm.event='Tx' AND wasm.method EXISTS";
+const SEEN_LIMIT = 10000;
+
+/**
+ * Swap events from a Tendermint websocket, as an async iterator:
+ *
+ *   for await (const event of client.subscribe()) { ... }
+ *
+ * A dropped socket reconnects to the next endpoint and replays what it missed
+ * through `backfill`; events are deduplicated by transaction and position, so
+ * each is delivered once. Breaking out of the loop (or `close()`) ends it.
+ */
+export class SwapEventSubscription implements AsyncIterableIterator<ObservedSwapEvent> {
+  private socket?: WebSocket;
+  private queue: ObservedSwapEvent[] = [];
+  private waiting?: (result: IteratorResult<ObservedSwapEvent>) => void;
+  private seen = new Set<string>();
+  private endpointIndex = 0;
+  private lastHeight = 0;
+  private closed = false;
+
+  constructor(
+    private endpoints: string[],
+    private backfill: BackfillFn,
+    private options: SubscribeOptions = {}
+  ) {
+    this.connect();
+  }
+
+  [Symbol.asyncIterator](): this {
+    return this;
+  }
+
+  next(): Promise<IteratorResult<ObservedSwapEvent>> {
+    const event = this.queue.shift();
+    if (event) return Promise.resolve({ value: event, done: false });
+    if (this.closed) return Promise.resolve({ value: undefined, done: true });
+    return new Promise(resolve => { this.waiting = resolve; });
+  }
+
+  async return(): Promise<IteratorResult<ObservedSwapEvent>> {
+    this.close();
+    return { value: undefined, done: true };
+  }
+
+  close(): void {
+    this.closed = true;
+    this.socket?.close();
+    this.waiting?.({ value: undefined, done: true });
+    this.waiting = undefined;
+  }
+
+  private connect(): void {
+    if (this.closed) return;
+    const endpoint = this.endpoints[this.endpointIndex % this.endpoints.length];
+    const socket = new WebSocket(websocketUrl(endpoint));
+    this.socket = socket;
+
+    socket.onopen = () => {
+      socket.send(JSON.stringify({ jsonrpc: '2.0', id: 1, method: 'subscribe', params: { query: QUERY } }));
+      if (this.lastHeight > 0) {
+        this.backfill(this.lastHeight)
+          .then(txs => txs.forEach(tx => this.publish(tx.hash, tx.height, tx.events)))
+          .catch(() => undefined); // best effort; the live stream continues regardless
+      }
+    };
+
+    socket.onmessage = (message: any) => {
+      try {
+        const result = JSON.parse(message.data.toString()).result;
+        const txResult = result?.data?.value?.TxResult;
+        if (!txResult) return; // the subscribe acknowledgement
+        const hash = result.events?.['tx.hash']?.[0] ?? '';
+        this.publish(hash, Number(txResult.height), this.decode(txResult.result?.events ?? []));
+      } catch {
+        // ignore frames we cannot parse
+      }
+    };
+
+    socket.onerror = () => socket.close();
+
+    socket.onclose = () => {
+      if (this.closed) return;
+      this.endpointIndex++;
+      setTimeout(() => this.connect(), this.options.reconnectDelayMs ?? 2000);
+    };
+  }
+
+  private publish(txHash: string, height: number, events: readonly TxEvent[]): void {
+    const parsed = parseTxEvents(events, { contracts: this.options.contracts });
+    parsed.forEach((event, index) => {
+      const key = `${txHash}:${index}`;
+      if (this.seen.has(key)) return;
+      this.remember(key);
+      this.lastHeight = Math.max(this.lastHeight, height);
+
+      const observed = { ...event, txHash, height };
+      if (this.waiting) {
+        this.waiting({ value: observed, done: false });
+        this.waiting = undefined;
+      } else {
+        this.queue.push(observed);
+      }
+    });
+  }
+
+  private remember(key: string): void {
+    this.seen.add(key);
+    if (this.seen.size > SEEN_LIMIT) {
+      this.seen.delete(this.seen.values().next().value as string);
+    }
+  }
+
+  private decode(events: TxEvent[]): TxEvent[] {
+    if (this.options.attributeEncoding !== 'base64') return events;
+    const text = (value: string) => (value ? Buffer.from(value, 'base64').toString('utf8') : '');
+    return events.map(event => ({
+      type: event.type,
+      attributes: event.attributes.map(({ key, value }) => ({ key: text(key), value: text(value) }))
+    }));
+  }
+}
+
+function websocketUrl(endpoint: string): string {
+  const url = endpoint.replace(/^http/, 'ws').replace(/\/+$/, '');
+  return url.endsWith('/websocket') ? url : `${url}/websocket`;
+}
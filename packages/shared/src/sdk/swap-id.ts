@@ -0,0 +1,85 @@
+import { ethers } from 'ethers';
+import { instantiate2Address } from '@cosmjs/cosmwasm-stargate';
+import { fromHex } from '@cosmjs/encoding';
+import { ValidationError } from '../utils/errors';
+
+export const SWAP_ID_DOMAIN = 'htlc-swap-id/v1';
+
+export interface SwapIdParams {
+  /** Chain ID the HTLC lives on: the EVM chain ID or the Cosmos chain-id string */
+  chainId: string | number;
+  /** 0x-prefixed 32-byte hashlock */
+  hashLock: string;
+  originator: string;
+  beneficiary: string;
+  /** Token address / CW20 contract; empty or omitted for the native asset */
+  token?: string;
+  amount: string | bigint;
+  /** Unix seconds */
+  timelock: number;
+}
+
+/**
+ * Canonical swap ID, the same in every implementation:
+ *
+ *   sha256(
+ *     utf8("htlc-swap-id/v1")
+ *     || str(chainId) || hashLock[32] || str(originator) || str(beneficiary) || str(token)
+ *     || uint256_be(amount) || uint64_be(timelock)
+ *   )
+ *
+ * where str(x) = uint32_be(byte length) || utf8(x). Addresses are lowercased
+ * (0x hex on EVM, bech32 as-is since it is case-insensitive and conventionally
+ * lower), the native asset and the EVM zero address are both the empty string,
+ * and chainId is its decimal or chain-id string. sha256 keeps it reproducible
+ * in Solidity (precompile), CosmWasm (sha2) and any scripting language.
+ *
+ * On EVM the ID is passed as the HTLC's contractId; on CosmWasm it is the
+ * instantiate2 salt, which fixes the HTLC's address (see predictCosmosHtlcAddress).
+ */
+export function deriveSwapId(params: SwapIdParams): string {
+  const hashLock = ethers.getBytes(params.hashLock);
+  if (hashLock.length !== 32) {
+    throw new ValidationError('Hash lock must be 32 bytes');
+  }
+  const amount = BigInt(params.amount);
+  if (amount < 0n || amount >= 2n ** 256n) {
+    throw new ValidationError('Amount out of uint256 range');
+  }
+  if (!Number.isInteger(params.timelock) || params.timelock < 0) {
+    throw new ValidationError('Timelock must be a non-negative integer');
+  }
+
+  const token = !params.token || params.token === ethers.ZeroAddress ? '' : params.token;
+  return ethers.sha256(ethers.concat([
+    ethers.toUtf8Bytes(SWAP_ID_DOMAIN),
+    field(params.chainId.toString()),
+    hashLock,
+    field(normalize(params.originator)),
+    field(normalize(params.beneficiary)),
+    field(normalize(token)),
+    ethers.toBeHex(amount, 32),
+    ethers.toBeHex(params.timelock, 8)
+  ]));
+}
+
+/**
+ * Address a CosmWasm HTLC gets when instantiated with instantiate2 using the swap ID as salt
+ */
+export function predictCosmosHtlcAddress(
+  swapId: string,
+  codeChecksum: string,
+  creator: string,
+  prefix: string
+): string {
+  return instantiate2Address(fromHex(codeChecksum.replace(/^0x/, '')), creator, ethers.getBytes(swapId), prefix);
+}
+
+function field(value: string): string {
+  const bytes = ethers.toUtf8Bytes(value);
+  return ethers.concat([ethers.toBeHex(bytes.length, 4), bytes]);
+}
+
+function normalize(address: string): string {
+  return address.trim().toLowerCase();
+}
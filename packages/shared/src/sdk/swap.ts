@@ -0,0 +1,294 @@
+import { EventEmitter } from 'events';
+import { allChains } from '../chains';
+import { SwapState } from '../utils/htlc';
+import type { HTLCDetails } from '../utils/htlc';
+import { Secret } from './secret';
+import { deriveSwapId } from './swap-id';
+import type { SwapChain } from './chain';
+import { emitTelemetry } from './telemetry';
+import type { Telemetry } from './telemetry';
+
+export type SwapPhase =
+  | 'created'            // secret generated, nothing on-chain yet
+  | 'source_locked'      // our HTLC on the source chain is funded
+  | 'destination_locked' // the counterparty's HTLC on the destination chain checks out
+  | 'destination_claimed'// we claimed it, revealing the secret on-chain
+  | 'completed'          // the secret has been handed to the counterparty
+  | 'refunded';          // the source HTLC expired and was refunded
+
+export interface CrossChainSwapParams {
+  source: {
+    chain: string;
+    /** Counterparty's address on the source chain */
+    beneficiary: string;
+    amount: string;
+    token?: string;
+    timelock: number;
+  };
+  destination: {
+    chain: string;
+    /** Our address on the destination chain */
+    beneficiary: string;
+    /** Least we accept, in base units */
+    minAmount: string;
+    token?: string;
+    /** The destination HTLC must expire at least this long before the source one (default 1h) */
+    minTimelockGap?: number;
+  };
+}
+
+/**
+ * Serializable state of a swap. Contains the secret until the swap is
+ * terminal, so store it as you would a private key.
+ */
+export interface SwapSnapshot {
+  version: 1;
+  phase: SwapPhase;
+  params: CrossChainSwapParams;
+  hashLock: string;
+  secret?: string;
+  sourceContractId?: string;
+  destinationContractId?: string;
+  txs: Partial<Record<'lock' | 'claim' | 'refund', string>>;
+}
+
+export interface CrossChainSwapOptions {
+  /** Hands the secret to whoever claims the source leg (e.g. the order feed's secret endpoint) */
+  relaySecret?: (snapshot: SwapSnapshot, secret: string) => Promise<void>;
+  telemetry?: Telemetry;
+}
+
+/**
+ * Drives one side of an atomic swap as the party holding the secret:
+ *
+ *   created -> source_locked -> destination_locked -> destination_claimed -> completed
+ *                    \-> refunded (no acceptable destination HTLC before the source expires)
+ *
+ * Each `step()` attempts one transition and is safe to repeat; `snapshot()`
+ * after every step (the 'phase' event is a convenient hook) lets a crashed
+ * process `resume()` exactly where it left off.
+ */
+export class CrossChainSwap extends EventEmitter {
+  private state: SwapSnapshot;
+  private secret?: Secret;
+
+  private constructor(
+    state: SwapSnapshot,
+    private source: SwapChain,
+    private destination: SwapChain,
+    private options: CrossChainSwapOptions
+  ) {
+    super();
+    this.state = state;
+    if (state.secret) this.secret = Secret.fromHex(state.secret);
+  }
+
+  static create(
+    params: CrossChainSwapParams,
+    chains: { source: SwapChain; destination: SwapChain },
+    options: CrossChainSwapOptions = {}
+  ): CrossChainSwap {
+    const secret = Secret.generate();
+    const swap = new CrossChainSwap({
+      version: 1,
+      phase: 'created',
+      params,
+      hashLock: secret.hashLock(),
+      secret: secret.hex(),
+      txs: {}
+    }, chains.source, chains.destination, options);
+    secret.zeroize();
+    return swap;
+  }
+
+  static resume(
+    snapshot: SwapSnapshot,
+    chains: { source: SwapChain; destination: SwapChain },
+    options: CrossChainSwapOptions = {}
+  ): CrossChainSwap {
+    if (snapshot.version !== 1) {
+      throw new Error(`Unsupported swap snapshot version ${snapshot.version}`);
+    }
+    return new CrossChainSwap({ ...snapshot, txs: { ...snapshot.txs } }, chains.source, chains.destination, options);
+  }
+
+  get phase(): SwapPhase {
+    return this.state.phase;
+  }
+
+  get hashLock(): string {
+    return this.state.hashLock;
+  }
+
+  get isTerminal(): boolean {
+    return this.state.phase === 'completed' || this.state.phase === 'refunded';
+  }
+
+  snapshot(): SwapSnapshot {
+    return { ...this.state, txs: { ...this.state.txs } };
+  }
+
+  /**
+   * Attempt the next transition. Returns the phase afterwards, which is unchanged
+   * when the swap is waiting on the counterparty or the clock.
+   */
+  async step(): Promise<SwapPhase> {
+    const phase = this.state.phase;
+    try {
+      switch (phase) {
+        case 'created': await this.lockSource(); break;
+        case 'source_locked': await this.awaitDestination(); break;
+        case 'destination_locked': await this.claimDestination(); break;
+        case 'destination_claimed': await this.relay(); break;
+      }
+    } catch (error) {
+      emitTelemetry(this.options.telemetry, 'onError', { operation: phase, hashLock: this.state.hashLock, error: error as Error });
+      throw error;
+    }
+    return this.state.phase;
+  }
+
+  /**
+   * Step until the swap is terminal
+   */
+  async run(options: { pollIntervalMs?: number } = {}): Promise<SwapPhase> {
+    while (!this.isTerminal) {
+      const before = this.state.phase;
+      const after = await this.step();
+      if (after === before && !this.isTerminal) {
+        await new Promise(resolve => setTimeout(resolve, options.pollIntervalMs ?? 5000));
+      }
+    }
+    return this.state.phase;
+  }
+
+  private async lockSource(): Promise<void> {
+    const { source } = this.state.params;
+    const contractId = this.state.sourceContractId ?? this.deriveContractId();
+
+    // A lock that landed before a crash is picked up rather than sent twice. Chains that
+    // allocate their own IDs are searched by hashlock instead.
+    const existing = await this.source.getHtlc(contractId) ??
+      await this.source.findHtlc(this.state.hashLock, source.beneficiary);
+    if (existing && existing.state !== SwapState.INVALID) {
+      this.transition('source_locked', { sourceContractId: existing.contractId });
+      this.reportCreated(existing.contractId);
+      return;
+    }
+
+    this.state.sourceContractId = contractId;
+    const locked = await this.source.lock({
+      contractId,
+      beneficiary: source.beneficiary,
+      hashLock: this.state.hashLock,
+      timelock: source.timelock,
+      amount: source.amount,
+      token: source.token
+    });
+    this.state.txs.lock = locked.txHash;
+    this.transition('source_locked', { sourceContractId: locked.contractId });
+    this.reportCreated(locked.contractId, locked.txHash);
+  }
+
+  private reportCreated(contractId: string, txHash?: string): void {
+    emitTelemetry(this.options.telemetry, 'onSwapCreated', {
+      chain: this.source.chain,
+      contractId,
+      hashLock: this.state.hashLock,
+      amount: this.state.params.source.amount,
+      ...(txHash ? { txHash } : {})
+    });
+  }
+
+  private async awaitDestination(): Promise<void> {
+    const { source, destination } = this.state.params;
+    const htlc = await this.destination.findHtlc(this.state.hashLock, destination.beneficiary);
+
+    if (htlc && this.acceptable(htlc)) {
+      this.transition('destination_locked', { destinationContractId: htlc.contractId });
+      return;
+    }
+
+    if (await this.source.currentTime() > source.timelock) {
+      const refunded = await this.source.refund(this.state.sourceContractId!);
+      this.state.txs.refund = refunded.txHash;
+      this.wipeSecret();
+      this.transition('refunded');
+    }
+  }
+
+  private acceptable(htlc: HTLCDetails): boolean {
+    const { source, destination } = this.state.params;
+    const gap = destination.minTimelockGap ?? 3600;
+    return htlc.state === SwapState.OPEN &&
+      BigInt(htlc.value) >= BigInt(destination.minAmount) &&
+      (!destination.token || htlc.token.toLowerCase() === destination.token.toLowerCase()) &&
+      htlc.timelock + gap <= source.timelock;
+  }
+
+  private async claimDestination(): Promise<void> {
+    const contractId = this.state.destinationContractId!;
+    const htlc = await this.destination.getHtlc(contractId);
+
+    if (htlc?.state === SwapState.CLAIMED) {
+      this.transition('destination_claimed');
+      return;
+    }
+    if (!htlc || htlc.state !== SwapState.OPEN || await this.destination.currentTime() >= htlc.timelock) {
+      // Never reveal the secret on a leg that can no longer pay out; go back to waiting,
+      // which ends in a refund of the source leg if nothing better turns up
+      this.transition('source_locked', { destinationContractId: undefined });
+      return;
+    }
+
+    const claimed = await this.destination.claim(contractId, this.requireSecret().hex());
+    this.state.txs.claim = claimed.txHash;
+    this.transition('destination_claimed');
+    emitTelemetry(this.options.telemetry, 'onClaim', {
+      chain: this.destination.chain,
+      contractId,
+      hashLock: this.state.hashLock,
+      txHash: claimed.txHash
+    });
+  }
+
+  private async relay(): Promise<void> {
+    if (this.options.relaySecret) {
+      await this.options.relaySecret(this.snapshot(), this.requireSecret().hex());
+    }
+    this.wipeSecret();
+    this.transition('completed');
+  }
+
+  private deriveContractId(): string {
+    const { source } = this.state.params;
+    return deriveSwapId({
+      chainId: allChains[source.chain]?.chainId ?? source.chain,
+      hashLock: this.state.hashLock,
+      originator: this.source.address ?? '',
+      beneficiary: source.beneficiary,
+      token: source.token,
+      amount: source.amount,
+      timelock: source.timelock
+    });
+  }
+
+  private requireSecret(): Secret {
+    if (!this.secret) {
+      throw new Error('Swap secret is no longer available');
+    }
+    return this.secret;
+  }
+
+  private wipeSecret(): void {
+    this.secret?.zeroize();
+    this.secret = undefined;
+    delete this.state.secret;
+  }
+
+  private transition(phase: SwapPhase, changes: Partial<SwapSnapshot> = {}): void {
+    const from = this.state.phase;
+    Object.assign(this.state, changes, { phase });
+    this.emit('phase', { from, to: phase, snapshot: this.snapshot() });
+  }
+}
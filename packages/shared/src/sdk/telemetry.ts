@@ -0,0 +1,32 @@
+/**
+ * Callbacks for plugging the SDK into an integrator's own metrics or analytics.
+ * Every hook is optional; exceptions thrown by a hook are swallowed so a
+ * broken metrics sink can never break a swap.
+ */
+export interface Telemetry {
+  /** An HTLC was locked on chain (or found already locked after a restart) */
+  onSwapCreated?(event: { chain: string; contractId: string; hashLock: string; amount: string; txHash?: string }): void;
+  /** A claim landed, revealing the preimage on `chain` */
+  onClaim?(event: { chain: string; contractId: string; hashLock: string; txHash: string }): void;
+  /** A step failed; the error is still thrown to the caller */
+  onError?(event: { operation: string; hashLock?: string; error: Error }): void;
+}
+
+export const noopTelemetry: Telemetry = {};
+
+/**
+ * Call one hook, ignoring a missing hook and anything it throws
+ */
+export function emitTelemetry<K extends keyof Telemetry>(
+  telemetry: Telemetry | undefined,
+  hook: K,
+  event: Parameters<NonNullable<Telemetry[K]>>[0]
+): void {
+  const callback = telemetry?.[hook] as ((event: unknown) => void) | undefined;
+  if (!callback) return;
+  try {
+    callback.call(telemetry, event);
+  } catch {
+    // Telemetry must never affect the swap
+  }
+}
@@ -0,0 +1,297 @@
+// Test doubles and fault injection. Published only as @swap-sage/shared/sdk/testing,
+// never from the ./sdk or ./sdk/browser entry points.
+
+import { ethers } from 'ethers';
+import { SwapState } from '../utils/htlc';
+import type { HTLCDetails } from '../utils/htlc';
+import type { HTLCEvent, Unsubscribe } from '../resolver/adapters/types';
+import type { LockParams, SubmittedTx, SwapChain } from './chain';
+import type { RelayEndpoint } from './relay';
+
+type Method = 'lock' | 'claim' | 'refund';
+
+interface Ledger {
+  now: number;
+  htlcs: Map<string, HTLCDetails>;
+  preimages: Map<string, string>;
+  failures: Map<Method, Error[]>;
+  txCount: number;
+  listeners: Set<(event: HTLCEvent) => void>;
+  /** Every event published so far, for subscribers that start from an earlier height */
+  history: HTLCEvent[];
+}
+
+export interface MockChainOptions {
+  /** Account the chain signs as (default a fixed test address) */
+  address?: string;
+  /** Starting clock, Unix seconds (default 1_700_000_000) */
+  now?: number;
+  /** Allocate HTLC IDs like a CosmWasm chain instead of using the requested one */
+  allocateIds?: boolean;
+}
+
+/**
+ * In-memory SwapChain for testing swap flows without a network. It enforces
+ * the same rules as the HTLC contracts (sha256 preimage, beneficiary-only
+ * claim before expiry, originator-only refund after it), and the clock only
+ * moves when told to.
+ *
+ *   const chain = MockChain.create('sepolia');
+ *   const counterparty = chain.as('0xabc...');
+ *   chain.advanceTime(3600);
+ */
+export class MockChain implements SwapChain {
+  readonly address: string;
+
+  private constructor(
+    readonly chain: string,
+    address: string,
+    private ledger: Ledger,
+    private allocateIds: boolean
+  ) {
+    this.address = address;
+  }
+
+  static create(chain: string, options: MockChainOptions = {}): MockChain {
+    return new MockChain(chain, options.address ?? '0x000000000000000000000000000000000000a11c', {
+      now: options.now ?? 1_700_000_000,
+      htlcs: new Map(),
+      preimages: new Map(),
+      failures: new Map(),
+      txCount: 0,
+      listeners: new Set(),
+      history: []
+    }, options.allocateIds ?? false);
+  }
+
+  /**
+   * The same chain seen by another account, e.g. the counterparty
+   */
+  as(address: string): MockChain {
+    return new MockChain(this.chain, address, this.ledger, this.allocateIds);
+  }
+
+  advanceTime(seconds: number): void {
+    this.ledger.now += seconds;
+  }
+
+  setTime(unixSeconds: number): void {
+    this.ledger.now = unixSeconds;
+  }
+
+  /**
+   * Make the next call to `method` throw, to exercise error handling
+   */
+  failNext(method: Method, error: Error = new Error(`Injected ${method} failure`)): void {
+    const queue = this.ledger.failures.get(method) ?? [];
+    queue.push(error);
+    this.ledger.failures.set(method, queue);
+  }
+
+  /**
+   * HTLC events as a ChainAdapter would report them, delivered synchronously
+   * when each lock, claim or refund lands; the height is the tx count. With
+   * `fromHeight`, earlier events from that height on are replayed first.
+   */
+  async subscribe(handler: (event: HTLCEvent) => void, options: { fromHeight?: number } = {}): Promise<Unsubscribe> {
+    if (options.fromHeight !== undefined) {
+      this.ledger.history.filter(event => event.height >= options.fromHeight!).forEach(handler);
+    }
+    this.ledger.listeners.add(handler);
+    return () => this.ledger.listeners.delete(handler);
+  }
+
+  /** Every HTLC on the chain */
+  htlcs(): HTLCDetails[] {
+    return [...this.ledger.htlcs.values()].map(htlc => ({ ...htlc }));
+  }
+
+  async lock(params: LockParams): Promise<SubmittedTx & { contractId: string }> {
+    this.injectFailure('lock');
+    const contractId = this.allocateIds
+      ? `${this.chain}1htlc${(this.ledger.htlcs.size + 1).toString().padStart(6, '0')}`
+      : params.contractId;
+
+    if (this.ledger.htlcs.has(contractId)) throw new Error('Contract already exists');
+    if (params.timelock <= this.ledger.now) throw new Error('Timelock must be in the future');
+    if (BigInt(params.amount) <= 0n) throw new Error('Value must be greater than 0');
+
+    const htlc: HTLCDetails = {
+      contractId,
+      token: params.token ?? ethers.ZeroAddress,
+      beneficiary: params.beneficiary,
+      originator: this.address,
+      hashLock: params.hashLock.toLowerCase(),
+      timelock: params.timelock,
+      value: params.amount,
+      state: SwapState.OPEN
+    };
+    this.ledger.htlcs.set(contractId, htlc);
+    const txHash = this.txHash();
+    this.publish({
+      kind: 'funded',
+      chain: this.chain,
+      contractId,
+      originator: htlc.originator,
+      beneficiary: htlc.beneficiary,
+      token: htlc.token,
+      value: htlc.value,
+      hashLock: htlc.hashLock,
+      timelock: htlc.timelock,
+      height: this.ledger.txCount,
+      txHash
+    });
+    return { txHash, contractId };
+  }
+
+  async claim(contractId: string, preimage: string): Promise<SubmittedTx> {
+    this.injectFailure('claim');
+    const htlc = this.pending(contractId);
+    if (this.ledger.now > htlc.timelock) throw new Error('Contract has expired');
+    if (ethers.sha256(preimage).toLowerCase() !== htlc.hashLock) throw new Error('Invalid preimage');
+    if (!sameAddress(this.address, htlc.beneficiary)) throw new Error('Only beneficiary can claim');
+
+    htlc.state = SwapState.CLAIMED;
+    this.ledger.preimages.set(contractId, preimage);
+    const txHash = this.txHash();
+    this.publish({ kind: 'claimed', chain: this.chain, contractId, preimage, height: this.ledger.txCount, txHash });
+    return { txHash };
+  }
+
+  async refund(contractId: string): Promise<SubmittedTx> {
+    this.injectFailure('refund');
+    const htlc = this.pending(contractId);
+    if (this.ledger.now <= htlc.timelock) throw new Error('Contract has not expired');
+    if (!sameAddress(this.address, htlc.originator)) throw new Error('Only originator can refund');
+
+    htlc.state = SwapState.REFUNDED;
+    const txHash = this.txHash();
+    this.publish({ kind: 'refunded', chain: this.chain, contractId, height: this.ledger.txCount, txHash });
+    return { txHash };
+  }
+
+  async getHtlc(contractId: string): Promise<HTLCDetails | null> {
+    const htlc = this.ledger.htlcs.get(contractId);
+    return htlc ? { ...htlc } : null;
+  }
+
+  async findHtlc(hashLock: string, beneficiary?: string): Promise<HTLCDetails | null> {
+    const match = [...this.ledger.htlcs.values()].find(htlc =>
+      htlc.hashLock === hashLock.toLowerCase() && (!beneficiary || sameAddress(htlc.beneficiary, beneficiary)));
+    return match ? { ...match } : null;
+  }
+
+  async revealedPreimage(contractId: string): Promise<string | null> {
+    return this.ledger.preimages.get(contractId) ?? null;
+  }
+
+  async currentTime(): Promise<number> {
+    return this.ledger.now;
+  }
+
+  private pending(contractId: string): HTLCDetails {
+    const htlc = this.ledger.htlcs.get(contractId);
+    if (!htlc) throw new Error('Contract does not exist');
+    if (htlc.state !== SwapState.OPEN) throw new Error('Contract is not pending');
+    return htlc;
+  }
+
+  private injectFailure(method: Method): void {
+    const error = this.ledger.failures.get(method)?.shift();
+    if (error) throw error;
+  }
+
+  private publish(event: HTLCEvent): void {
+    this.ledger.history.push(event);
+    this.ledger.listeners.forEach(listener => listener(event));
+  }
+
+  private txHash(): string {
+    return ethers.id(`${this.chain}:${++this.ledger.txCount}`);
+  }
+}
+
+export interface ChaosOptions {
+  /** Seed for the fault schedule; the same seed injects the same faults */
+  seed: number;
+  /** Share of events that are never delivered (default 0.2) */
+  dropRate?: number;
+  /** Share of chain calls that fail like a flaky RPC (default 0.2) */
+  errorRate?: number;
+  /** Most scheduler turns a call or event is held back, which also reorders them (default 20) */
+  maxDelay?: number;
+}
+
+/**
+ * Fault injection for exercising a relay: wraps the chains it submits through
+ * and the event feeds it listens to. Delays are counted in scheduler turns
+ * rather than wall-clock time, so a seeded run is fast and repeatable.
+ *
+ *   const chaos = new Chaos({ seed: 42 });
+ *   new SwapRelay([chaos.endpoint({ swapChain, subscribe })]);
+ */
+export class Chaos {
+  private next: () => number;
+
+  constructor(private options: ChaosOptions) {
+    this.next = seededRandom(options.seed);
+  }
+
+  /** The chain with every call delayed and some failing before they reach it */
+  chain(chain: SwapChain): SwapChain {
+    const flaky = <T>(method: string, call: () => Promise<T>) => async (): Promise<T> => {
+      await this.delay();
+      if (this.next() < (this.options.errorRate ?? 0.2)) throw new Error(`Injected RPC error in ${method}`);
+      return call();
+    };
+    return {
+      chain: chain.chain,
+      address: chain.address,
+      lock: params => flaky('lock', () => chain.lock(params))(),
+      claim: (contractId, preimage) => flaky('claim', () => chain.claim(contractId, preimage))(),
+      refund: contractId => flaky('refund', () => chain.refund(contractId))(),
+      getHtlc: contractId => flaky('getHtlc', () => chain.getHtlc(contractId))(),
+      findHtlc: (hashLock, beneficiary) => flaky('findHtlc', () => chain.findHtlc(hashLock, beneficiary))(),
+      revealedPreimage: contractId => flaky('revealedPreimage', () => chain.revealedPreimage(contractId))(),
+      currentTime: () => flaky('currentTime', () => chain.currentTime())()
+    };
+  }
+
+  /** The event feed with some events dropped and the rest delayed */
+  subscribe(subscribe: RelayEndpoint['subscribe']): RelayEndpoint['subscribe'] {
+    return handler => subscribe(event => {
+      if (this.next() < (this.options.dropRate ?? 0.2)) return;
+      void this.delay().then(() => handler(event));
+    });
+  }
+
+  /** A coin flip from the same seeded schedule, for the test's own choices */
+  chance(probability: number): boolean {
+    return this.next() < probability;
+  }
+
+  endpoint(endpoint: RelayEndpoint): RelayEndpoint {
+    return { swapChain: this.chain(endpoint.swapChain), subscribe: this.subscribe(endpoint.subscribe) };
+  }
+
+  private async delay(): Promise<void> {
+    const turns = Math.floor(this.next() * ((this.options.maxDelay ?? 20) + 1));
+    for (let turn = 0; turn < turns; turn++) await Promise.resolve();
+  }
+}
+
+/** mulberry32 */
+function seededRandom(seed: number): () => number {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 2 ** 32;
+  };
+}
+
+function sameAddress(a: string, b: string): boolean {
+  return a.toLowerCase() === b.toLowerCase();
+}
@@ -0,0 +1,102 @@
+import { allChains } from '../chains';
+import type { ChainConfig, EvmChainConfig } from '../chains';
+import { ContractError } from './errors';
+import type { ChainTiming } from './routing';
+
+export interface TimelockOptions {
+  /** How far either chain's block time may be off from real time (default 2 min) */
+  clockSkewSeconds?: number;
+  /** Blocks to allow for getting a claim included (default 5) */
+  inclusionBlocks?: number;
+  /** How long the destination leg stays claimable (default 1h) */
+  destDurationSeconds?: number;
+}
+
+export interface TimelockAdvice {
+  source: ChainTiming;
+  dest: ChainTiming;
+  /** Smallest safe gap between the two expiries */
+  marginSeconds: number;
+  destDurationSeconds: number;
+  /** `destDurationSeconds + marginSeconds` */
+  sourceDurationSeconds: number;
+}
+
+// For chains not in the registry
+const UNKNOWN_CHAIN: ChainTiming = { finalitySeconds: 900, inclusionSeconds: 120 };
+
+// Time for the 'safe' / 'finalized' head to pass a block under each model
+const MODEL_FINALITY_SECONDS: Record<NonNullable<EvmChainConfig['finality']>, number | undefined> = {
+  'probabilistic': undefined, // confirmations x block time
+  'optimistic-rollup': 1200,
+  'arbitrum-rollup': 1200,
+  'checkpoint': 300
+};
+
+/**
+ * Finality and inclusion time of a registry chain. `finalitySeconds` on the
+ * chain config wins; otherwise it follows the finality model, with Cosmos
+ * chains final after one block. Inclusion is never under a minute, to leave
+ * room for relaying and RPC latency on fast chains.
+ */
+export function chainTiming(chain: string | ChainConfig, inclusionBlocks = 5): ChainTiming {
+  const config = typeof chain === 'string' ? allChains[chain] : chain;
+  if (!config) return UNKNOWN_CHAIN;
+
+  const blockTime = config.blockTimeSeconds ?? (config.type === 'cosmos' ? 6 : 12);
+  let finality = config.finalitySeconds;
+  if (finality === undefined) {
+    if (config.type === 'cosmos') {
+      finality = blockTime;
+    } else {
+      const evm = config as EvmChainConfig;
+      finality = MODEL_FINALITY_SECONDS[evm.finality ?? 'probabilistic'] ?? Math.max(1, evm.confirmations ?? 1) * blockTime;
+    }
+  }
+  return {
+    finalitySeconds: Math.ceil(finality),
+    inclusionSeconds: Math.max(60, Math.ceil(inclusionBlocks * blockTime))
+  };
+}
+
+/**
+ * Timelocks for a swap locked on `sourceChain` and paid out on `destChain`.
+ * Once the secret is revealed on the destination, the source-side claimer
+ * needs time to trust that block, get its own claim included, and absorb
+ * clock skew on both chains before the source lock expires.
+ */
+export function recommend(sourceChain: string | ChainConfig, destChain: string | ChainConfig, options: TimelockOptions = {}): TimelockAdvice {
+  const source = chainTiming(sourceChain, options.inclusionBlocks);
+  const dest = chainTiming(destChain, options.inclusionBlocks);
+  const marginSeconds = dest.finalitySeconds + source.inclusionSeconds + 2 * (options.clockSkewSeconds ?? 120);
+  const destDurationSeconds = options.destDurationSeconds ?? 3600;
+  return { source, dest, marginSeconds, destDurationSeconds, sourceDurationSeconds: destDurationSeconds + marginSeconds };
+}
+
+/**
+ * Problems with a pair of user-supplied expiries (Unix seconds): both must be
+ * in the future and `destExpiry + margin < sourceExpiry`
+ */
+export function checkTimelocks(
+  sourceChain: string | ChainConfig,
+  destChain: string | ChainConfig,
+  expiries: { sourceExpiry: number; destExpiry: number },
+  now: number,
+  options: TimelockOptions = {}
+): ContractError[] {
+  const errors: ContractError[] = [];
+  const { marginSeconds } = recommend(sourceChain, destChain, options);
+  const { sourceExpiry, destExpiry } = expiries;
+
+  if (destExpiry <= now) {
+    errors.push(new ContractError('InvalidTimelock', 'Destination timelock must be in the future'));
+  }
+  if (destExpiry + marginSeconds >= sourceExpiry) {
+    errors.push(new ContractError(
+      'InvalidTimelock',
+      `Source timelock must be more than ${marginSeconds}s after the destination timelock (gap is ${sourceExpiry - destExpiry}s)`,
+      { sourceExpiry, destExpiry, marginSeconds }
+    ));
+  }
+  return errors;
+}
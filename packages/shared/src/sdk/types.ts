@@ -0,0 +1,99 @@
+import { ethers } from 'ethers';
+import { z } from 'zod';
+import type { InstantiateMsg, SwapResponse } from '../clients/cosmos';
+import { SwapState } from '../utils/htlc';
+import { HashLock } from './hashlock';
+
+const hex32 = z.string().regex(/^0x[0-9a-fA-F]{64}$/, 'expected 0x-prefixed 32-byte hex');
+const uint = z.string().regex(/^\d+$/, 'expected an unsigned integer string');
+
+/**
+ * One HTLC leg in a chain-neutral shape. JSON-safe: amounts are decimal strings
+ * and hashes 0x hex, so the same value can be stored, sent over the wire and
+ * materialized on either chain family.
+ */
+export const SwapOrderSchema = z.object({
+  /** Chain registry key, e.g. "sepolia" or "osmosisTestnet" */
+  chain: z.string().min(1),
+  /** HTLC ID on EVM; the contract address on CosmWasm once instantiated */
+  contractId: z.string().optional(),
+  originator: z.string().min(1),
+  beneficiary: z.string().min(1),
+  hashLock: hex32,
+  timelock: z.number().int().positive(),
+  amount: uint,
+  /** ERC-20 / CW20 address; omitted for the native asset */
+  token: z.string().optional(),
+  state: z.nativeEnum(SwapState).optional()
+});
+
+export type SwapOrder = z.infer<typeof SwapOrderSchema>;
+
+/** Layout of SimpleHTLC.getDetails, the EVM struct an order maps onto */
+export const EVM_ORDER_TUPLE =
+  'tuple(address token, address beneficiary, address originator, bytes32 hashLock, uint256 timelock, uint256 value, uint8 state)';
+
+export function toInstantiateMsg(order: SwapOrder): InstantiateMsg {
+  return {
+    sender: order.originator,
+    beneficiary: order.beneficiary,
+    hash_lock: HashLock.fromEvm(order.hashLock).toCosmos(),
+    timelock: order.timelock,
+    amount: order.amount,
+    ...(order.token ? { token: order.token } : {})
+  };
+}
+
+export function fromSwapResponse(chain: string, contractAddress: string, response: SwapResponse): SwapOrder {
+  const states: Record<SwapResponse['state'], SwapState> = {
+    Open: SwapState.OPEN,
+    Claimed: SwapState.CLAIMED,
+    Refunded: SwapState.REFUNDED
+  };
+  return {
+    chain,
+    contractId: contractAddress,
+    originator: response.sender,
+    beneficiary: response.beneficiary,
+    hashLock: HashLock.fromCosmos(response.hash_lock).toEvm(),
+    timelock: response.timelock,
+    amount: response.amount,
+    ...(response.token ? { token: response.token } : {}),
+    state: states[response.state] ?? SwapState.INVALID
+  };
+}
+
+/**
+ * ABI-encode an order as the SimpleHTLC details struct
+ */
+export function encodeEvmOrder(order: SwapOrder): string {
+  return ethers.AbiCoder.defaultAbiCoder().encode([EVM_ORDER_TUPLE], [[
+    order.token || ethers.ZeroAddress,
+    order.beneficiary,
+    order.originator,
+    order.hashLock,
+    order.timelock,
+    order.amount,
+    order.state ?? SwapState.INVALID
+  ]]);
+}
+
+/**
+ * Decode a SimpleHTLC details struct, either ABI-encoded or as returned by ethers
+ */
+export function decodeEvmOrder(chain: string, contractId: string, encoded: string | ethers.Result): SwapOrder {
+  const [token, beneficiary, originator, hashLock, timelock, value, state] = typeof encoded === 'string'
+    ? ethers.AbiCoder.defaultAbiCoder().decode([EVM_ORDER_TUPLE], encoded)[0]
+    : encoded;
+  return {
+    chain,
+    contractId,
+    originator,
+    beneficiary,
+    hashLock,
+    timelock: Number(timelock),
+    amount: value.toString(),
+    ...(token !== ethers.ZeroAddress ? { token } : {}),
+    state: Number(state) as SwapState
+  };
+}
@@ -0,0 +1,88 @@
+import { SwapState } from '../utils/htlc';
+import type { HTLCDetails } from '../utils/htlc';
+import type { LockParams, SwapChain } from './chain';
+
+export type Created = 'created';
+export type Funded = 'funded';
+export type Claimed = 'claimed';
+export type Refunded = 'refunded';
+export type LegPhase = Created | Funded | Claimed | Refunded;
+
+/**
+ * One HTLC whose phase is part of its type, so only legal transitions compile:
+ *
+ *   const created = Swap.create(chain, params);   // Swap<Created>
+ *   const funded = await created.fund();           // Swap<Funded>
+ *   await funded.claim(preimage);                  // Swap<Claimed>
+ *   await created.claim(preimage);                 // compile error
+ *
+ * Every transition returns a new value; the old one still describes the
+ * earlier phase and should be dropped. Use `Swap.attach` for an HTLC that
+ * already exists, then narrow on `phase`.
+ */
+export class Swap<P extends LegPhase> {
+  private constructor(
+    readonly chain: SwapChain,
+    readonly phase: P,
+    readonly params: LockParams,
+    /** Set from Funded on; may differ from params.contractId on chains that allocate IDs */
+    readonly contractId: P extends Created ? undefined : string,
+    readonly txHash?: string
+  ) {}
+
+  static create(chain: SwapChain, params: LockParams): Swap<Created> {
+    return new Swap<Created>(chain, 'created', params, undefined);
+  }
+
+  /**
+   * Wrap an existing HTLC in whatever phase the chain reports
+   */
+  static async attach(chain: SwapChain, contractId: string): Promise<Swap<Funded> | Swap<Claimed> | Swap<Refunded>> {
+    const htlc = await chain.getHtlc(contractId);
+    if (!htlc || htlc.state === SwapState.INVALID) {
+      throw new Error(`No HTLC ${contractId} on ${chain.chain}`);
+    }
+    const params = paramsOf(htlc);
+    switch (htlc.state) {
+      case SwapState.CLAIMED: return new Swap<Claimed>(chain, 'claimed', params, htlc.contractId);
+      case SwapState.REFUNDED: return new Swap<Refunded>(chain, 'refunded', params, htlc.contractId);
+      default: return new Swap<Funded>(chain, 'funded', params, htlc.contractId);
+    }
+  }
+
+  async fund(this: Swap<Created>): Promise<Swap<Funded>> {
+    const locked = await this.chain.lock(this.params);
+    return new Swap<Funded>(this.chain, 'funded', this.params, locked.contractId, locked.txHash);
+  }
+
+  async claim(this: Swap<Funded>, preimage: string): Promise<Swap<Claimed>> {
+    const claimed = await this.chain.claim(this.contractId, preimage);
+    return new Swap<Claimed>(this.chain, 'claimed', this.params, this.contractId, claimed.txHash);
+  }
+
+  async refund(this: Swap<Funded>): Promise<Swap<Refunded>> {
+    const refunded = await this.chain.refund(this.contractId);
+    return new Swap<Refunded>(this.chain, 'refunded', this.params, this.contractId, refunded.txHash);
+  }
+
+  /** The preimage the claim revealed */
+  async preimage(this: Swap<Claimed>): Promise<string | null> {
+    return this.chain.revealedPreimage(this.contractId);
+  }
+
+  /** Current on-chain state; a Funded value can be stale if someone else claimed or refunded */
+  async details(this: Swap<Funded | Claimed | Refunded>): Promise<HTLCDetails | null> {
+    return this.chain.getHtlc(this.contractId as string);
+  }
+}
+
+function paramsOf(htlc: HTLCDetails): LockParams {
+  return {
+    contractId: htlc.contractId,
+    beneficiary: htlc.beneficiary,
+    hashLock: htlc.hashLock,
+    timelock: htlc.timelock,
+    amount: htlc.value,
+    token: htlc.token
+  };
+}
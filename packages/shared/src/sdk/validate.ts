@@ -0,0 +1,109 @@
+import { ethers } from 'ethers';
+import type { ChainConfig, CosmosChainConfig } from '../chains';
+import { SwapState } from '../utils/htlc';
+import type { HTLCDetails } from '../utils/htlc';
+import { isBech32Address } from './address';
+import { ContractError } from './errors';
+import type { LockParams } from './chain';
+
+export interface ValidationRules {
+  /** Shortest lock accepted, so it cannot expire while the tx is in the mempool (default 5 min) */
+  minDurationSeconds?: number;
+  /** Longest lock accepted (default 7 days) */
+  maxDurationSeconds?: number;
+}
+
+// Cosmos SDK denom syntax (sdk.Coin validation)
+const DENOM = /^[a-zA-Z][a-zA-Z0-9/:._-]{2,127}$/;
+
+/**
+ * Everything wrong with a lock, checked the way the HTLC contracts check it
+ * plus duration bounds. Each problem is the ContractError the chain would
+ * have returned, so callers handle pre-flight and on-chain failures alike.
+ */
+export function checkLock(
+  chain: ChainConfig,
+  params: Omit<LockParams, 'contractId'>,
+  now: number,
+  rules: ValidationRules = {}
+): ContractError[] {
+  const errors: ContractError[] = [];
+  const fail = (kind: ContractError['kind'], message: string) => errors.push(new ContractError(kind, message, { chain: chain.name }));
+
+  const min = rules.minDurationSeconds ?? 300;
+  const max = rules.maxDurationSeconds ?? 7 * 24 * 3600;
+  if (!Number.isInteger(params.timelock) || params.timelock <= now) {
+    fail('InvalidTimelock', 'Timelock must be in the future');
+  } else if (params.timelock - now < min) {
+    fail('InvalidTimelock', `Timelock must be at least ${min}s away`);
+  } else if (params.timelock - now > max) {
+    fail('InvalidTimelock', `Timelock must be at most ${max}s away`);
+  }
+
+  if (!/^0x[0-9a-fA-F]{64}$/.test(params.hashLock) || BigInt(params.hashLock) === 0n) {
+    fail('InvalidHashLock', 'Hash lock must be 32 non-zero bytes');
+  }
+
+  if (!/^\d+$/.test(params.amount) || BigInt(params.amount) === 0n) {
+    fail('InvalidAmount', 'Value must be greater than 0');
+  }
+
+  if (chain.type === 'cosmos') {
+    const prefix = (chain as CosmosChainConfig).addressPrefix;
+    if (!isBech32Address(params.beneficiary, prefix)) {
+      fail('InvalidAddress', `Beneficiary is not a ${prefix} address`);
+    }
+    // A token is either a CW20 contract or a bank denom
+    if (params.token && !isBech32Address(params.token, prefix) && !DENOM.test(params.token)) {
+      fail('InvalidAddress', `Token ${params.token} is neither a CW20 address nor a valid denom`);
+    }
+  } else {
+    if (!ethers.isAddress(params.beneficiary) || params.beneficiary === ethers.ZeroAddress) {
+      fail('InvalidAddress', 'Beneficiary cannot be zero address');
+    }
+    if (params.token && params.token !== ethers.ZeroAddress && !ethers.isAddress(params.token)) {
+      fail('InvalidAddress', `Token ${params.token} is not an address`);
+    }
+  }
+
+  return errors;
+}
+
+/**
+ * Why a claim would be rejected. `claimer` is checked against the beneficiary
+ * when given; the EVM contract only lets the beneficiary claim.
+ */
+export function checkClaim(htlc: HTLCDetails | null, preimage: string, now: number, claimer?: string): ContractError[] {
+  if (!htlc || htlc.state === SwapState.INVALID) return [new ContractError('NotFound', 'Contract does not exist')];
+  const errors: ContractError[] = [];
+  if (htlc.state !== SwapState.OPEN) errors.push(new ContractError('NotOpen', 'Contract is not pending'));
+  if (now > htlc.timelock) errors.push(new ContractError('TimelockExpired', 'Contract has expired'));
+  if (!/^0x[0-9a-fA-F]{64}$/.test(preimage) || ethers.sha256(preimage).toLowerCase() !== htlc.hashLock.toLowerCase()) {
+    errors.push(new ContractError('InvalidPreimage', 'Invalid preimage'));
+  }
+  if (claimer && claimer.toLowerCase() !== htlc.beneficiary.toLowerCase()) {
+    errors.push(new ContractError('Unauthorized', 'Only beneficiary can claim'));
+  }
+  return errors;
+}
+
+/**
+ * Why a refund would be rejected; `caller` is checked against the originator when given
+ */
+export function checkRefund(htlc: HTLCDetails | null, now: number, caller?: string): ContractError[] {
+  if (!htlc || htlc.state === SwapState.INVALID) return [new ContractError('NotFound', 'Contract does not exist')];
+  const errors: ContractError[] = [];
+  if (htlc.state !== SwapState.OPEN) errors.push(new ContractError('NotOpen', 'Contract is not pending'));
+  if (now <= htlc.timelock) errors.push(new ContractError('TimelockNotExpired', 'Contract has not expired'));
+  if (caller && caller.toLowerCase() !== htlc.originator.toLowerCase()) {
+    errors.push(new ContractError('Unauthorized', 'Only originator can refund'));
+  }
+  return errors;
+}
+
+/**
+ * Throw the first problem, if any
+ */
+export function assertValid(errors: ContractError[]): void {
+  if (errors.length > 0) throw errors[0];
+}
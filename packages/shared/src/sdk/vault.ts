@@ -0,0 +1,164 @@
+import { createCipheriv, createDecipheriv, randomBytes, scryptSync } from 'crypto';
+import { promises as fs } from 'fs';
+import { Secret } from './secret';
+import type { CrossChainSwap, SwapPhase } from './swap';
+
+interface Sealed {
+  iv: string;
+  data: string;
+  tag: string;
+}
+
+interface VaultFile {
+  version: 1;
+  kdf: { name: 'scrypt'; salt: string; N: number; r: number; p: number };
+  /** Known plaintext sealed with the key, so a wrong passphrase fails on open */
+  check: Sealed;
+  entries: Record<string, Sealed & { createdAt: number }>;
+}
+
+const CHECK_PLAINTEXT = 'swap-secret-vault';
+const KDF = { N: 2 ** 15, r: 8, p: 1 };
+const TERMINAL: SwapPhase[] = ['completed', 'refunded'];
+
+/**
+ * Preimages on disk, keyed by swap ID, each sealed with AES-256-GCM under a
+ * key derived from a passphrase with scrypt. The file is rewritten atomically
+ * and created owner-readable only. Node only.
+ *
+ *   const vault = await SecretVault.open('./secrets.vault', process.env.VAULT_PASSPHRASE!);
+ *   await vault.put(swapId, secret);
+ *   vault.track(swapId, swap); // removed once the swap completes or refunds
+ */
+export class SecretVault {
+  private constructor(private path: string, private key: Buffer, private file: VaultFile) {}
+
+  /**
+   * Open a vault, creating it if the file does not exist. Throws on a wrong passphrase.
+   */
+  static async open(path: string, passphrase: string): Promise<SecretVault> {
+    let file: VaultFile;
+    try {
+      file = JSON.parse(await fs.readFile(path, 'utf8'));
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== 'ENOENT') throw error;
+      const salt = randomBytes(16).toString('hex');
+      const key = deriveKey(passphrase, salt, KDF);
+      const vault = new SecretVault(path, key, {
+        version: 1,
+        kdf: { name: 'scrypt', salt, ...KDF },
+        check: seal(key, Buffer.from(CHECK_PLAINTEXT)),
+        entries: {}
+      });
+      await vault.save();
+      return vault;
+    }
+
+    if (file.version !== 1) {
+      throw new Error(`Unsupported vault version ${file.version}`);
+    }
+    const key = deriveKey(passphrase, file.kdf.salt, file.kdf);
+    try {
+      if (unseal(key, file.check).toString() !== CHECK_PLAINTEXT) throw new Error();
+    } catch {
+      key.fill(0);
+      throw new Error('Wrong vault passphrase');
+    }
+    return new SecretVault(path, key, file);
+  }
+
+  async put(swapId: string, secret: Secret): Promise<void> {
+    const bytes = Buffer.from(secret.toBytes());
+    try {
+      this.file.entries[swapId] = { ...seal(this.key, bytes), createdAt: Math.floor(Date.now() / 1000) };
+    } finally {
+      bytes.fill(0);
+    }
+    await this.save();
+  }
+
+  /** The stored secret, or null; the caller should zeroize it when done */
+  get(swapId: string): Secret | null {
+    const entry = this.file.entries[swapId];
+    if (!entry) return null;
+    const bytes = unseal(this.key, entry);
+    try {
+      return new Secret(bytes);
+    } finally {
+      bytes.fill(0);
+    }
+  }
+
+  has(swapId: string): boolean {
+    return swapId in this.file.entries;
+  }
+
+  list(): string[] {
+    return Object.keys(this.file.entries);
+  }
+
+  async delete(swapId: string): Promise<boolean> {
+    if (!this.has(swapId)) return false;
+    delete this.file.entries[swapId];
+    await this.save();
+    return true;
+  }
+
+  /**
+   * Delete the secret once `swap` reaches a terminal phase. Returns a function that stops tracking.
+   */
+  track(swapId: string, swap: CrossChainSwap): () => void {
+    const onPhase = ({ to }: { to: SwapPhase }) => {
+      if (TERMINAL.includes(to)) {
+        swap.off('phase', onPhase);
+        void this.delete(swapId);
+      }
+    };
+    swap.on('phase', onPhase);
+    return () => swap.off('phase', onPhase);
+  }
+
+  /**
+   * Delete secrets of swaps `isTerminal` reports as finished, e.g. from a
+   * swap store on startup. Returns the IDs removed.
+   */
+  async prune(isTerminal: (swapId: string) => boolean | Promise<boolean>): Promise<string[]> {
+    const removed: string[] = [];
+    for (const swapId of this.list()) {
+      if (await isTerminal(swapId)) {
+        delete this.file.entries[swapId];
+        removed.push(swapId);
+      }
+    }
+    if (removed.length > 0) await this.save();
+    return removed;
+  }
+
+  /** Wipe the key from memory; the vault cannot be used afterwards */
+  close(): void {
+    this.key.fill(0);
+  }
+
+  private async save(): Promise<void> {
+    const temp = `${this.path}.${process.pid}.tmp`;
+    await fs.writeFile(temp, JSON.stringify(this.file, null, 2), { mode: 0o600 });
+    await fs.rename(temp, this.path);
+  }
+}
+
+function deriveKey(passphrase: string, salt: string, params: { N: number; r: number; p: number }): Buffer {
+  return scryptSync(passphrase, Buffer.from(salt, 'hex'), 32, { ...params, maxmem: 256 * params.N * params.r });
+}
+
+function seal(key: Buffer, plaintext: Buffer): Sealed {
+  const iv = randomBytes(12);
+  const cipher = createCipheriv('aes-256-gcm', key, iv);
+  const data = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+  return { iv: iv.toString('hex'), data: data.toString('hex'), tag: cipher.getAuthTag().toString('hex') };
+}
+
+function unseal(key: Buffer, sealed: Sealed): Buffer {
+  const decipher = createDecipheriv('aes-256-gcm', key, Buffer.from(sealed.iv, 'hex'));
+  decipher.setAuthTag(Buffer.from(sealed.tag, 'hex'));
+  return Buffer.concat([decipher.update(Buffer.from(sealed.data, 'hex')), decipher.final()]);
+}
@@ -0,0 +1,129 @@
+import { ethers } from 'ethers';
+import { HashLock } from './hashlock';
+import { HASH_ALGORITHMS } from './secret';
+import type { HashAlgorithm } from './secret';
+import { SWAP_ID_DOMAIN, deriveSwapId } from './swap-id';
+import { ERC2771_FORWARDER_TYPES, GASLESS_CLAIM_TYPES, typedDataDigest } from './eip712';
+import { HtlcMsg } from './builder';
+import type { ExecuteMsg } from '../clients/cosmos';
+
+export const TEST_VECTORS_VERSION = 2;
+
+interface TypedDataVector {
+  domain: { name: string; version: string; chainId: number; verifyingContract: string };
+  /** Message with uint fields as decimal strings */
+  message: Record<string, string | number>;
+  digest: string;
+}
+
+export interface TestVector {
+  index: number;
+  /** 0x hex, 32 bytes */
+  secret: string;
+  /** Raw digest per algorithm */
+  hashLocks: Record<HashAlgorithm, string>;
+  /** The bytes32 an EVM contract stores for each algorithm */
+  evmHashLocks: Record<HashAlgorithm, string>;
+  swapId: {
+    chainId: number;
+    originator: string;
+    beneficiary: string;
+    /** Empty for the native asset */
+    token: string;
+    amount: string;
+    timelock: number;
+    id: string;
+  };
+  /** What each chain is sent to claim the swap with this vector's secret */
+  claimPayloads: {
+    /** SimpleHTLC `claim(swapId, secret)` calldata */
+    evmCalldata: string;
+    /** CosmWasm HTLC execute message */
+    cosmwasm: ExecuteMsg;
+  };
+  eip712: {
+    gaslessClaim: TypedDataVector;
+    forwardRequest: TypedDataVector;
+  };
+}
+
+export interface TestVectors {
+  version: number;
+  seed: string;
+  swapIdDomain: string;
+  vectors: TestVector[];
+}
+
+/**
+ * Deterministic vectors for checking other implementations (Solidity tests,
+ * CosmWasm, scripts) byte for byte against this SDK. Every value is derived
+ * from `seed`, so the same seed gives the same file on any machine.
+ */
+export function generateTestVectors(options: { seed?: string; count?: number } = {}): TestVectors {
+  const seed = options.seed ?? 'omnimonster-test-vectors';
+  const count = options.count ?? 8;
+  const derive = (label: string, index: number) =>
+    ethers.getBytes(ethers.sha256(ethers.concat([ethers.toUtf8Bytes(`${seed}/${label}`), ethers.toBeHex(index, 4)])));
+  const address = (label: string, index: number) => ethers.getAddress(ethers.hexlify(derive(label, index).slice(12)));
+
+  const vectors: TestVector[] = [];
+  for (let index = 0; index < count; index++) {
+    const secret = derive('secret', index);
+    const hashLocks = {} as Record<HashAlgorithm, string>;
+    const evmHashLocks = {} as Record<HashAlgorithm, string>;
+    for (const algorithm of HASH_ALGORITHMS) {
+      const lock = HashLock.fromPreimage(secret, algorithm);
+      hashLocks[algorithm] = lock.hex();
+      evmHashLocks[algorithm] = lock.toEvm();
+    }
+
+    const swap = {
+      chainId: 31337,
+      originator: address('originator', index),
+      beneficiary: address('beneficiary', index),
+      // Alternate native and ERC-20 so both token encodings are covered
+      token: index % 2 === 0 ? '' : address('token', index),
+      amount: (ethers.WeiPerEther * BigInt(index + 1)).toString(),
+      timelock: 1_700_000_000 + index * 3600
+    };
+    const id = deriveSwapId({ ...swap, hashLock: hashLocks.sha256 });
+
+    const claim = {
+      htlcContract: address('htlc', index),
+      contractId: id,
+      preimage: ethers.hexlify(secret),
+      beneficiary: swap.beneficiary,
+      maxGasPrice: (50n * 10n ** 9n).toString(),
+      gasCompensation: (10n ** 15n).toString(),
+      nonce: index.toString(),
+      deadline: swap.timelock.toString()
+    };
+    const claimCalldata = ethers.concat([ethers.id('claim(bytes32,bytes32)').slice(0, 10), id, claim.preimage]);
+    const forward = {
+      from: swap.beneficiary,
+      to: claim.htlcContract,
+      value: '0',
+      gas: '200000',
+      nonce: index.toString(),
+      deadline: swap.timelock,
+      data: claimCalldata
+    };
+    const relayerDomain = { name: 'SwapSageGasRelayer', version: '1', chainId: swap.chainId, verifyingContract: address('relayer', index) };
+    const forwarderDomain = { name: 'FusionForwarder', version: '1', chainId: swap.chainId, verifyingContract: address('forwarder', index) };
+
+    vectors.push({
+      index,
+      secret: ethers.hexlify(secret),
+      hashLocks,
+      evmHashLocks,
+      swapId: { ...swap, id },
+      claimPayloads: { evmCalldata: claimCalldata, cosmwasm: HtlcMsg.claim(claim.preimage) },
+      eip712: {
+        gaslessClaim: { domain: relayerDomain, message: claim, digest: typedDataDigest(relayerDomain, GASLESS_CLAIM_TYPES, claim) },
+        forwardRequest: { domain: forwarderDomain, message: forward, digest: typedDataDigest(forwarderDomain, ERC2771_FORWARDER_TYPES, forward) }
+      }
+    });
+  }
+
+  return { version: TEST_VECTORS_VERSION, seed, swapIdDomain: SWAP_ID_DOMAIN, vectors };
+}
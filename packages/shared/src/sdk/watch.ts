@@ -0,0 +1,179 @@
+import { EventEmitter } from 'events';
+import { SwapState } from '../utils/htlc';
+import type { HTLCDetails } from '../utils/htlc';
+import { ValidationError } from '../utils/errors';
+import type { SwapChain } from './chain';
+
+/**
+ * What to watch: a hashlock, or a swap ID (the HTLC ID on either chain) from
+ * which the hashlock is read
+ */
+export type WatchTarget = { hashLock: string } | { swapId: string };
+
+export type LegStatus = 'unseen' | 'open' | 'expired' | 'claimed' | 'refunded';
+
+export interface LegReport {
+  chain: string;
+  status: LegStatus;
+  htlc?: HTLCDetails;
+  /** Set once the leg has been claimed and the claim's preimage is known */
+  preimage?: string;
+}
+
+/**
+ * - completed: both legs claimed
+ * - refunded: every leg that was locked has been refunded
+ * - one-sided: one leg claimed and the other refunded, so one party lost out
+ * - timed-out: no terminal state before `timeoutMs`
+ */
+export type WatchOutcome = 'completed' | 'refunded' | 'one-sided' | 'timed-out';
+
+export interface WatchReport {
+  outcome: WatchOutcome;
+  hashLock?: string;
+  source: LegReport;
+  destination: LegReport;
+}
+
+export interface WatchOptions {
+  /** Default 15s */
+  pollIntervalMs?: number;
+  /** Give up after this long; unset watches until a terminal state */
+  timeoutMs?: number;
+}
+
+type Leg = 'source' | 'destination';
+
+/**
+ * Follows a swap on both chains without any keys until it settles. Wrap the
+ * chains watch-only (no signer) and, since HTLCs are found from the chains'
+ * event streams, from a height before the swap was locked.
+ *
+ * Emits 'update' ({ leg, report }) whenever a leg changes status and 'done'
+ * with the final report.
+ */
+export class SwapWatcher extends EventEmitter {
+  private legs: Record<Leg, LegReport>;
+  private hashLock?: string;
+  private stopped = false;
+  private wake?: () => void;
+
+  constructor(
+    private chains: { source: SwapChain; destination: SwapChain },
+    private target: WatchTarget,
+    private options: WatchOptions = {}
+  ) {
+    super();
+    this.legs = {
+      source: { chain: chains.source.chain, status: 'unseen' },
+      destination: { chain: chains.destination.chain, status: 'unseen' }
+    };
+    if ('hashLock' in target) this.hashLock = target.hashLock;
+  }
+
+  async run(): Promise<WatchReport> {
+    const deadline = this.options.timeoutMs !== undefined ? Date.now() + this.options.timeoutMs : Infinity;
+    let outcome: WatchOutcome | null = null;
+
+    while (!this.stopped && outcome === null) {
+      await this.poll();
+      outcome = this.outcome();
+      if (outcome === null && Date.now() >= deadline) outcome = 'timed-out';
+      if (outcome === null) await this.sleep();
+    }
+
+    const report: WatchReport = { outcome: outcome ?? 'timed-out', hashLock: this.hashLock, ...this.report() };
+    this.emit('done', report);
+    return report;
+  }
+
+  stop(): void {
+    this.stopped = true;
+    this.wake?.();
+  }
+
+  report(): Record<Leg, LegReport> {
+    return { source: { ...this.legs.source }, destination: { ...this.legs.destination } };
+  }
+
+  private async poll(): Promise<void> {
+    if (!this.hashLock && 'swapId' in this.target) {
+      for (const leg of ['source', 'destination'] as const) {
+        const htlc = await this.chains[leg].getHtlc(this.target.swapId);
+        if (htlc && htlc.state !== SwapState.INVALID) {
+          this.hashLock = htlc.hashLock;
+          break;
+        }
+      }
+      if (!this.hashLock) return;
+    }
+    await Promise.all((['source', 'destination'] as const).map(leg => this.pollLeg(leg)));
+  }
+
+  private async pollLeg(leg: Leg): Promise<void> {
+    const chain = this.chains[leg];
+    const current = this.legs[leg];
+    if (current.status === 'claimed' || current.status === 'refunded') return;
+
+    const htlc = current.htlc
+      ? await chain.getHtlc(current.htlc.contractId)
+      : await chain.findHtlc(this.hashLock!);
+    if (!htlc || htlc.state === SwapState.INVALID) return;
+
+    let status: LegStatus;
+    let preimage: string | undefined;
+    if (htlc.state === SwapState.CLAIMED) {
+      status = 'claimed';
+      preimage = (await chain.revealedPreimage(htlc.contractId)) ?? undefined;
+    } else if (htlc.state === SwapState.REFUNDED) {
+      status = 'refunded';
+    } else {
+      status = (await chain.currentTime()) > htlc.timelock ? 'expired' : 'open';
+    }
+
+    const changed = status !== current.status;
+    this.legs[leg] = { chain: chain.chain, status, htlc, ...(preimage ? { preimage } : {}) };
+    if (changed) this.emit('update', { leg, report: { ...this.legs[leg] } });
+  }
+
+  private outcome(): WatchOutcome | null {
+    const source = this.legs.source.status;
+    const destination = this.legs.destination.status;
+    const settled = (status: LegStatus) => status === 'claimed' || status === 'refunded';
+
+    if (settled(source) && settled(destination)) {
+      if (source === 'claimed' && destination === 'claimed') return 'completed';
+      if (source === 'refunded' && destination === 'refunded') return 'refunded';
+      return 'one-sided';
+    }
+    // A refund with nothing ever locked on the other side
+    if ((source === 'refunded' && destination === 'unseen') || (destination === 'refunded' && source === 'unseen')) {
+      return 'refunded';
+    }
+    return null;
+  }
+
+  private sleep(): Promise<void> {
+    return new Promise(resolve => {
+      const timer = setTimeout(resolve, this.options.pollIntervalMs ?? 15000);
+      this.wake = () => {
+        clearTimeout(timer);
+        resolve();
+      };
+    });
+  }
+}
+
+/**
+ * Watch a swap until it settles and return the outcome
+ */
+export function watchSwap(
+  chains: { source: SwapChain; destination: SwapChain },
+  target: WatchTarget,
+  options: WatchOptions = {}
+): Promise<WatchReport> {
+  if ('hashLock' in target && !/^0x[0-9a-fA-F]{64}$/.test(target.hashLock)) {
+    throw new ValidationError('Hash lock must be 0x-prefixed 32-byte hex');
+  }
+  return new SwapWatcher(chains, target, options).run();
+}
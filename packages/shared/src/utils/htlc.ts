@@ -10,6 +10,7 @@ export interface HTLCParams {
 /**
  * Generate a deterministic HTLC ID using SHA256
  * Format: sha256(srcChain + dstChain + nonce + hashlock)
+ * @deprecated Unseparated concatenation is ambiguous; use deriveSwapId from the SDK
  */
 export function generateHTLCId(params: HTLCParams): string {
   const { srcChain, dstChain, nonce, hashlock } = params;
@@ -5,15 +5,36 @@ export enum LogLevel {
   ERROR = 3,
 }
 
+export type LogFormat = 'text' | 'json';
+
 export interface Logger {
   debug(message: string, meta?: Record<string, unknown>): void;
   info(message: string, meta?: Record<string, unknown>): void;
   warn(message: string, meta?: Record<string, unknown>): void;
   error(message: string, meta?: Record<string, unknown>): void;
+  /** Derive a logger that attaches the given fields to every line */
+  child(context: Record<string, unknown>): Logger;
+}
+
+/**
+ * Serialize values JSON.stringify cannot handle on its own (bigint, Error)
+ */
+function toLoggable(_key: string, value: unknown): unknown {
+  if (typeof value === 'bigint') {
+    return value.toString();
+  }
+  if (value instanceof Error) {
+    return { name: value.name, message: value.message, stack: value.stack };
+  }
+  return value;
 }
 
 class ConsoleLogger implements Logger {
-  constructor(private level: LogLevel = LogLevel.INFO) {}
+  constructor(
+    private level: LogLevel = LogLevel.INFO,
+    private format: LogFormat = 'text',
+    private context: Record<string, unknown> = {}
+  ) {}
 
   private shouldLog(level: LogLevel): boolean {
     return level >= this.level;
@@ -21,8 +42,23 @@ class ConsoleLogger implements Logger {
 
   private formatMessage(level: string, message: string, meta?: Record<string, unknown>): string {
     const timestamp = new Date().toISOString();
-    const metaStr = meta ? ` ${JSON.stringify(meta)}` : '';
-    return `[${timestamp}] ${level}: ${message}${metaStr}`;
+    const { component, ...context } = this.context;
+
+    if (this.format === 'json') {
+      return JSON.stringify({
+        timestamp,
+        level: level.toLowerCase(),
+        ...(component ? { component } : {}),
+        message,
+        ...context,
+        ...meta
+      }, toLoggable);
+    }
+
+    const prefix = component ? `[${component}] ` : '';
+    const fields = { ...context, ...meta };
+    const metaStr = Object.keys(fields).length > 0 ? ` ${JSON.stringify(fields, toLoggable)}` : '';
+    return `[${timestamp}] ${level}: ${prefix}${message}${metaStr}`;
   }
 
   debug(message: string, meta?: Record<string, unknown>): void {
@@ -48,23 +84,35 @@ class ConsoleLogger implements Logger {
       console.error(this.formatMessage('ERROR', message, meta));
     }
   }
+
+  child(context: Record<string, unknown>): Logger {
+    return new ConsoleLogger(this.level, this.format, { ...this.context, ...context });
+  }
 }
 
-// Create default logger instance
+// Level names only: `in LogLevel` would also accept the enum's numeric reverse keys ("0", "1", ...)
+const LOG_LEVELS: Record<string, LogLevel> = {
+  DEBUG: LogLevel.DEBUG,
+  INFO: LogLevel.INFO,
+  WARN: LogLevel.WARN,
+  ERROR: LogLevel.ERROR
+};
+
+function resolveLogLevel(): LogLevel {
+  const configured = process.env.LOG_LEVEL?.toUpperCase();
+  if (configured && Object.prototype.hasOwnProperty.call(LOG_LEVELS, configured)) {
+    return LOG_LEVELS[configured];
+  }
+  return process.env.NODE_ENV === 'development' ? LogLevel.DEBUG : LogLevel.INFO;
+}
+
+// Create default logger instance (LOG_FORMAT=json for machine-readable output)
 export const logger: Logger = new ConsoleLogger(
-  process.env.NODE_ENV === 'development' ? LogLevel.DEBUG : LogLevel.INFO
+  resolveLogLevel(),
+  process.env.LOG_FORMAT === 'json' ? 'json' : 'text'
 );
 
 // Factory function for creating loggers with prefixes
-export function createLogger(prefix: string): Logger {
-  return {
-    debug: (message: string, meta?: Record<string, unknown>) => 
-      logger.debug(`[${prefix}] ${message}`, meta),
-    info: (message: string, meta?: Record<string, unknown>) => 
-      logger.info(`[${prefix}] ${message}`, meta),
-    warn: (message: string, meta?: Record<string, unknown>) => 
-      logger.warn(`[${prefix}] ${message}`, meta),
-    error: (message: string, meta?: Record<string, unknown>) => 
-      logger.error(`[${prefix}] ${message}`, meta),
-  };
-}
\ No newline at end of file
+export function createLogger(prefix: string, context: Record<string, unknown> = {}): Logger {
+  return logger.child({ component: prefix, ...context });
+}
@@ -0,0 +1,131 @@
+#!/usr/bin/env tsx
+/**
+ * Export SimpleHTLC swaps from a live chain as replay fixtures.
+ *
+ *   tsx scripts/export-replay-fixtures.ts --rpc <url> --htlc <address> --from-block <n>
+ *     [--to-block <n>] [--chunk 5000] [--limit 50] [--out <file>]
+ *
+ * Every swap funded in the block range is written with its events, the
+ * decoded call behind each one, and the timing relative to funding. The
+ * Hardhat test in contracts/evm/test/replay.ts replays them against the
+ * current SimpleHTLC. The default output is
+ * contracts/evm/test/replay/<chainId>-<from>-<to>.json.
+ */
+import fs from 'fs';
+import path from 'path';
+import { ethers } from 'ethers';
+
+const REPLAY_FIXTURE_VERSION = 1;
+
+const HTLC = new ethers.Interface([
+  'function fund(bytes32 contractId, address token, address beneficiary, bytes32 hashLock, uint256 timelock, uint256 value)',
+  'function fundETH(bytes32 contractId, address beneficiary, bytes32 hashLock, uint256 timelock) payable',
+  'function claim(bytes32 contractId, bytes32 preimage)',
+  'function refund(bytes32 contractId)',
+  'event HTLCCreated(bytes32 indexed contractId, address indexed originator, address indexed beneficiary, address token, uint256 value, bytes32 hashLock, uint256 timelock)',
+  'event HTLCClaimed(bytes32 indexed contractId, address indexed claimer, bytes32 preimage)',
+  'event HTLCRefunded(bytes32 indexed contractId, address indexed refunder)'
+]);
+
+function option(name: string): string | undefined {
+  const index = process.argv.indexOf(`--${name}`);
+  return index >= 0 ? process.argv[index + 1] : undefined;
+}
+
+function required(name: string): string {
+  const value = option(name);
+  if (!value) throw new Error(`--${name} is required`);
+  return value;
+}
+
+interface ReplayCall {
+  method: 'fund' | 'fundETH' | 'claim' | 'refund';
+  /** Role of the account that caused it */
+  from: 'originator' | 'beneficiary' | 'other';
+  secondsAfterFunding: number;
+  txHash: string;
+  /** Set when the transaction went through another contract, e.g. a forwarder or relayer */
+  via?: string;
+  /** The decoded HTLC call when the transaction called the HTLC directly */
+  input?: Record<string, string>;
+  event: { name: string; args: Record<string, string> };
+}
+
+async function main() {
+  const provider = new ethers.JsonRpcProvider(required('rpc'));
+  const htlc = ethers.getAddress(required('htlc'));
+  const fromBlock = Number(required('from-block'));
+  const toBlock = option('to-block') ? Number(option('to-block')) : await provider.getBlockNumber();
+  const chunk = Number(option('chunk') ?? 5000);
+  const limit = Number(option('limit') ?? 50);
+  const { chainId } = await provider.getNetwork();
+
+  const logs: ethers.Log[] = [];
+  for (let start = fromBlock; start <= toBlock; start += chunk) {
+    logs.push(...await provider.getLogs({ address: htlc, fromBlock: start, toBlock: Math.min(start + chunk - 1, toBlock) }));
+  }
+
+  const timestamps = new Map<number, number>();
+  const timestampOf = async (blockNumber: number) => {
+    if (!timestamps.has(blockNumber)) timestamps.set(blockNumber, (await provider.getBlock(blockNumber))!.timestamp);
+    return timestamps.get(blockNumber)!;
+  };
+  const argsOf = (description: ethers.LogDescription | ethers.TransactionDescription) => Object.fromEntries(
+    description.fragment.inputs.map((input, index) => [input.name, description.args[index].toString()])
+  );
+
+  const swaps = new Map<string, { created: ethers.LogDescription; fundedAt: number; calls: ReplayCall[] }>();
+  for (const log of logs) {
+    const event = HTLC.parseLog({ topics: [...log.topics], data: log.data });
+    if (!event) continue;
+    const contractId = (event.args.contractId as string).toLowerCase();
+    if (event.name === 'HTLCCreated') {
+      if (swaps.size >= limit) continue;
+      swaps.set(contractId, { created: event, fundedAt: await timestampOf(log.blockNumber), calls: [] });
+    }
+    const swap = swaps.get(contractId);
+    // Settlements of swaps funded before the range can't be replayed on their own
+    if (!swap) continue;
+
+    const tx = (await provider.getTransaction(log.transactionHash))!;
+    const direct = tx.to && ethers.getAddress(tx.to) === htlc;
+    const call = direct ? HTLC.parseTransaction({ data: tx.data, value: tx.value }) : null;
+    const actor = event.name === 'HTLCCreated' ? event.args.originator : event.name === 'HTLCClaimed' ? event.args.claimer : event.args.refunder;
+    const method = event.name === 'HTLCCreated'
+      ? (event.args.token === ethers.ZeroAddress ? 'fundETH' : 'fund')
+      : event.name === 'HTLCClaimed' ? 'claim' : 'refund';
+
+    swap.calls.push({
+      method,
+      from: actor === swap.created.args.originator ? 'originator' : actor === swap.created.args.beneficiary ? 'beneficiary' : 'other',
+      secondsAfterFunding: (await timestampOf(log.blockNumber)) - swap.fundedAt,
+      txHash: log.transactionHash,
+      ...(direct ? {} : { via: tx.to ?? undefined }),
+      ...(call ? { input: { method: call.name, value: tx.value.toString(), ...argsOf(call) } } : {}),
+      event: { name: event.name, args: argsOf(event) }
+    });
+  }
+
+  const fixture = {
+    version: REPLAY_FIXTURE_VERSION,
+    source: { chainId: Number(chainId), htlc, fromBlock, toBlock, exportedAt: new Date().toISOString() },
+    swaps: [...swaps.entries()].map(([contractId, { created, fundedAt, calls }]) => ({
+      contractId,
+      token: created.args.token === ethers.ZeroAddress ? 'native' : 'erc20',
+      value: created.args.value.toString(),
+      hashLock: created.args.hashLock,
+      timelockAfterFunding: Number(created.args.timelock) - fundedAt,
+      calls
+    }))
+  };
+
+  const out = option('out') ?? path.join(__dirname, '..', 'contracts', 'evm', 'test', 'replay', `${chainId}-${fromBlock}-${toBlock}.json`);
+  fs.mkdirSync(path.dirname(out), { recursive: true });
+  fs.writeFileSync(out, JSON.stringify(fixture, null, 2) + '\n');
+  console.log(`Wrote ${fixture.swaps.length} swaps to ${path.relative(process.cwd(), out)}`);
+}
+
+main().catch(error => {
+  console.error(error);
+  process.exit(1);
+});
@@ -0,0 +1,23 @@
+#!/usr/bin/env tsx
+/**
+ * Write the CosmWasm HTLC's JSON schemas for codegen.
+ *
+ *   tsx scripts/generate-schema.ts [out-dir]
+ *
+ * Writes <out-dir>/<contract version>/htlc.json plus one file per message
+ * under raw/; out-dir defaults to schema/htlc. The default output is committed
+ * and the SDK tests fail when the schemas drift from it.
+ */
+import fs from 'fs';
+import path from 'path';
+import { HTLC_CONTRACT_VERSION, htlcContractSchema, htlcSchemaFiles } from '../packages/shared/src/sdk/schema';
+
+const out = path.join(process.argv[2] ?? path.join(__dirname, '..', 'schema', 'htlc'), HTLC_CONTRACT_VERSION);
+const raw = path.join(out, 'raw');
+
+fs.mkdirSync(raw, { recursive: true });
+fs.writeFileSync(path.join(out, 'htlc.json'), JSON.stringify(htlcContractSchema(), null, 2) + '\n');
+for (const [name, schema] of Object.entries(htlcSchemaFiles())) {
+  fs.writeFileSync(path.join(raw, name), JSON.stringify(schema, null, 2) + '\n');
+}
+console.log(`Wrote HTLC ${HTLC_CONTRACT_VERSION} schemas to ${path.relative(process.cwd(), out)}`);
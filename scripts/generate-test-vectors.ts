@@ -0,0 +1,35 @@
+#!/usr/bin/env tsx
+/**
+ * Write the SDK's parity test vectors as JSON.
+ *
+ *   tsx scripts/generate-test-vectors.ts [out-file] [--seed <seed>] [--count <n>]
+ *
+ * Defaults to the shared corpus at test-vectors/htlc-vectors.json, which the SDK
+ * and Hardhat tests both read; pass "-" to print to stdout instead. Commit the
+ * result whenever the SDK's encodings change on purpose.
+ */
+import fs from 'fs';
+import path from 'path';
+import { generateTestVectors } from '../packages/shared/src/sdk/vectors';
+
+const DEFAULT_OUT = path.join(__dirname, '..', 'test-vectors', 'htlc-vectors.json');
+
+function option(name: string): string | undefined {
+  const index = process.argv.indexOf(`--${name}`);
+  return index >= 0 ? process.argv[index + 1] : undefined;
+}
+
+const positional = process.argv.slice(2).filter((arg, i, args) => !arg.startsWith('--') && !args[i - 1]?.startsWith('--'));
+const out = positional[0] ?? DEFAULT_OUT;
+const count = option('count');
+
+const vectors = generateTestVectors({ seed: option('seed'), count: count ? Number(count) : undefined });
+const json = JSON.stringify(vectors, null, 2) + '\n';
+
+if (out === '-') {
+  process.stdout.write(json);
+} else {
+  fs.mkdirSync(path.dirname(out), { recursive: true });
+  fs.writeFileSync(out, json);
+  console.log(`Wrote ${vectors.vectors.length} vectors to ${path.relative(process.cwd(), out)}`);
+}
@@ -0,0 +1,190 @@
+#!/usr/bin/env tsx
+/**
+ * Soak test: run swaps through the relay against a localnet for hours.
+ *
+ *   tsx scripts/soak.ts --source <chain> --destination <chain> --alice-key <name> --bob-key <name>
+ *     [--hours 4] [--concurrency 4] [--amount 1000] [--refund-share 0.1]
+ *     [--timelock 600] [--report-every 60] [--max-error-rate 0.01] [--out soak.json]
+ *
+ * Alice locks on the source chain and Bob on the destination chain. Alice then
+ * claims Bob's lock, and the relay, running in this process as Bob, has to claim
+ * Alice's lock. A `--refund-share` of swaps is never claimed, so the relay has to
+ * refund Bob's lock instead. Chains and keys come from the active omni profile.
+ *
+ * A JSON line goes to stdout every `--report-every` seconds, and a summary at
+ * the end. Each reports relay latency percentiles, memory growth since start,
+ * and the error rate. The exit code is non-zero if the error rate ends above
+ * `--max-error-rate`.
+ */
+import fs from 'fs';
+import { ethers } from 'ethers';
+import { openChain } from '../cli/services/omni-chains';
+import { SelfRelay } from '../cli/services/omni-relay';
+
+function option(name: string, fallback?: string): string {
+  const index = process.argv.indexOf(`--${name}`);
+  const value = index >= 0 ? process.argv[index + 1] : fallback;
+  if (value === undefined) throw new Error(`--${name} is required`);
+  return value;
+}
+
+const SOURCE = option('source');
+const DESTINATION = option('destination');
+const ALICE_KEY = option('alice-key');
+const BOB_KEY = option('bob-key');
+const DURATION_MS = Number(option('hours', '4')) * 3_600_000;
+const CONCURRENCY = Number(option('concurrency', '4'));
+const AMOUNT = option('amount', '1000');
+const REFUND_SHARE = Number(option('refund-share', '0.1'));
+// Bob's lock gets `timelock` seconds and Alice's twice that, as in a real swap
+const TIMELOCK = Number(option('timelock', '600'));
+const REPORT_EVERY_MS = Number(option('report-every', '60')) * 1000;
+const MAX_ERROR_RATE = Number(option('max-error-rate', '0.01'));
+const OUT = process.argv.includes('--out') ? option('out') : undefined;
+
+/** How long past the moment it becomes possible the relay may take to settle before it counts as an error */
+const SETTLE_TIMEOUT_MS = 5 * 60_000;
+
+const stats = {
+  started: 0,
+  claimed: 0,
+  refunded: 0,
+  errors: {} as Record<string, number>,
+  claimLatencyMs: [] as number[],
+  refundLatencyMs: [] as number[]
+};
+const memoryAtStart = process.memoryUsage();
+const startedAt = Date.now();
+
+function fail(kind: string, error: unknown): void {
+  stats.errors[kind] = (stats.errors[kind] ?? 0) + 1;
+  console.error(JSON.stringify({ at: new Date().toISOString(), error: kind, message: (error as Error).message ?? String(error) }));
+}
+
+function percentile(sorted: number[], p: number): number | null {
+  if (sorted.length === 0) return null;
+  return sorted[Math.min(sorted.length - 1, Math.floor((p / 100) * sorted.length))];
+}
+
+function latency(samples: number[]) {
+  const sorted = [...samples].sort((a, b) => a - b);
+  return { count: sorted.length, p50: percentile(sorted, 50), p90: percentile(sorted, 90), p99: percentile(sorted, 99), max: sorted.at(-1) ?? null };
+}
+
+function report(final = false) {
+  const memory = process.memoryUsage();
+  const errors = Object.values(stats.errors).reduce((sum, count) => sum + count, 0);
+  return {
+    at: new Date().toISOString(),
+    final,
+    elapsedMinutes: Math.round((Date.now() - startedAt) / 60_000),
+    swaps: { started: stats.started, claimed: stats.claimed, refunded: stats.refunded },
+    claimLatencyMs: latency(stats.claimLatencyMs),
+    refundLatencyMs: latency(stats.refundLatencyMs),
+    memoryMb: {
+      rss: Math.round(memory.rss / 2 ** 20),
+      heapUsed: Math.round(memory.heapUsed / 2 ** 20),
+      rssGrowth: Math.round((memory.rss - memoryAtStart.rss) / 2 ** 20),
+      heapGrowth: Math.round((memory.heapUsed - memoryAtStart.heapUsed) / 2 ** 20)
+    },
+    errors: stats.errors,
+    errorRate: stats.started ? errors / stats.started : 0
+  };
+}
+
+async function main() {
+  const alice = { source: await openChain(SOURCE, { sign: true, key: ALICE_KEY }), destination: await openChain(DESTINATION, { sign: true, key: ALICE_KEY }) };
+  const bob = { source: await openChain(SOURCE, { sign: true, key: BOB_KEY }), destination: await openChain(DESTINATION, { sign: true, key: BOB_KEY }) };
+
+  const relay = new SelfRelay({ chains: { [SOURCE]: { key: BOB_KEY }, [DESTINATION]: { key: BOB_KEY } }, pollIntervalSeconds: 5 });
+  // Settlements the driver is waiting on, by `<claimed|refunded>:<contractId>`
+  const waiting = new Map<string, () => void>();
+  const settled = (action: string) => ({ contractId }: { contractId: string }) => waiting.get(`${action}:${contractId.toLowerCase()}`)?.();
+  relay.on('claimed', settled('claimed'));
+  relay.on('refunded', settled('refunded'));
+  relay.on('error', ({ error }) => fail('relay', error));
+  await relay.start();
+
+  const until = (action: string, contractId: string, timeoutMs: number) => {
+    const settlement = new Promise<void>((resolve, reject) => {
+      const key = `${action}:${contractId.toLowerCase()}`;
+      const timer = setTimeout(() => {
+        waiting.delete(key);
+        reject(new Error(`relay did not report ${key} within ${timeoutMs / 1000}s`));
+      }, timeoutMs);
+      waiting.set(key, () => {
+        clearTimeout(timer);
+        waiting.delete(key);
+        resolve();
+      });
+    });
+    // Awaited later, possibly never if the swap fails first; don't let the timeout crash the run
+    settlement.catch(() => {});
+    return settlement;
+  };
+
+  const sleepUntilChainTime = async (chain: typeof alice.source, unixSeconds: number) => {
+    while (await chain.currentTime() <= unixSeconds) await new Promise(resolve => setTimeout(resolve, 2000));
+  };
+
+  async function swap(): Promise<void> {
+    stats.started++;
+    const preimage = ethers.hexlify(ethers.randomBytes(32));
+    const hashLock = ethers.sha256(preimage);
+    const refundOnly = Math.random() < REFUND_SHARE;
+
+    const sourceTimelock = (await alice.source.currentTime()) + 2 * TIMELOCK;
+    const { contractId: aliceLock } = await alice.source.lock({
+      contractId: ethers.hexlify(ethers.randomBytes(32)), beneficiary: bob.source.address!, hashLock, timelock: sourceTimelock, amount: AMOUNT
+    });
+    const destinationTimelock = (await bob.destination.currentTime()) + TIMELOCK;
+    const { contractId: bobLock } = await bob.destination.lock({
+      contractId: ethers.hexlify(ethers.randomBytes(32)), beneficiary: alice.destination.address!, hashLock, timelock: destinationTimelock, amount: AMOUNT
+    });
+
+    if (!refundOnly) {
+      const claimed = until('claimed', aliceLock, SETTLE_TIMEOUT_MS);
+      const claimedAt = Date.now();
+      await alice.destination.claim(bobLock, preimage);
+      await claimed;
+      stats.claimLatencyMs.push(Date.now() - claimedAt);
+      stats.claimed++;
+      return;
+    }
+
+    // Nobody claims: the relay refunds Bob's lock once it expires, and Alice refunds hers later
+    const refunded = until('refunded', bobLock, TIMELOCK * 1000 + SETTLE_TIMEOUT_MS);
+    await sleepUntilChainTime(bob.destination, destinationTimelock);
+    const expiredAt = Date.now();
+    await refunded;
+    stats.refundLatencyMs.push(Date.now() - expiredAt);
+    await sleepUntilChainTime(alice.source, sourceTimelock);
+    await alice.source.refund(aliceLock);
+    stats.refunded++;
+  }
+
+  const reporter = setInterval(() => console.log(JSON.stringify(report())), REPORT_EVERY_MS);
+  const worker = async () => {
+    while (Date.now() - startedAt < DURATION_MS) {
+      await swap().catch(error => fail('swap', error));
+    }
+  };
+  await Promise.all(Array.from({ length: CONCURRENCY }, worker));
+
+  clearInterval(reporter);
+  relay.stop();
+  [alice.source, alice.destination, bob.source, bob.destination].forEach(chain => chain.close());
+
+  const summary = report(true);
+  console.log(JSON.stringify(summary));
+  if (OUT) fs.writeFileSync(OUT, JSON.stringify(summary, null, 2) + '\n');
+  if (summary.errorRate > MAX_ERROR_RATE) {
+    console.error(`Error rate ${summary.errorRate.toFixed(4)} is above --max-error-rate ${MAX_ERROR_RATE}`);
+    process.exit(1);
+  }
+}
+
+main().catch(error => {
+  console.error(error);
+  process.exit(1);
+});
@@ -26,7 +26,6 @@ async function startResolver() {
       processingInterval: 5000, // 5 seconds for testing
       maxBatchSize: 5,
       maxRetries: 3,
-      gasLimit: 500000,
       maxGasPrice: '20000000000', // 20 gwei
       htlcContractAddress: chainConfig.htlcAddress,
       poolWalletPrivateKey: process.env.POOL_MANAGER_PRIVATE_KEY!,
@@ -0,0 +1,126 @@
+import { FusionDAO, FusionDatabase, getDatabaseConfig } from '../../../packages/shared/src/database';
+import { evmChains, cosmosChains } from '../../../packages/shared/src/chains';
+import { BackfillResult, CosmosBackfiller, EvmBackfiller } from '../../../packages/shared/src/resolver/backfill';
+import dotenv from 'dotenv';
+import path from 'path';
+
+dotenv.config({ path: path.join(__dirname, '../../../.env.local') });
+
+const USAGE = `Usage: pnpm backfill --from-height <X> --to-height <Y> [options]
+
+Replays past blocks, reconstructs HTLCs and reconciles them with the swap database.
+
+Options:
+  --chain <name>        Chain to replay (repeatable). Defaults to the resolver chains.
+  --chunk-size <n>      Blocks per log query (default 2000)
+  --execute             Apply status repairs and broadcast refunds for expired pool HTLCs.
+                        Without this flag the backfill only reports what it would do.
+`;
+
+interface BackfillArgs {
+  chains: string[];
+  fromHeight: number;
+  toHeight: number;
+  chunkSize: number;
+  execute: boolean;
+}
+
+function parseArgs(argv: string[]): BackfillArgs {
+  const args: Partial<BackfillArgs> & { chains: string[] } = { chains: [], chunkSize: 2000, execute: false };
+
+  for (let i = 0; i < argv.length; i++) {
+    const flag = argv[i];
+    const value = argv[i + 1];
+
+    switch (flag) {
+      case '--chain':
+        args.chains.push(value);
+        i++;
+        break;
+      case '--from-height':
+        args.fromHeight = parseInt(value);
+        i++;
+        break;
+      case '--to-height':
+        args.toHeight = parseInt(value);
+        i++;
+        break;
+      case '--chunk-size':
+        args.chunkSize = parseInt(value);
+        i++;
+        break;
+      case '--execute':
+        args.execute = true;
+        break;
+      case '--help':
+      case '-h':
+        console.log(USAGE);
+        process.exit(0);
+      default:
+        throw new Error(`Unknown option: ${flag}`);
+    }
+  }
+
+  if (args.fromHeight === undefined || args.toHeight === undefined || isNaN(args.fromHeight) || isNaN(args.toHeight)) {
+    throw new Error('--from-height and --to-height are required');
+  }
+
+  return {
+    chains: args.chains.length > 0 ? args.chains : ['sepolia', 'monadTestnet', 'etherlinkTestnet'],
+    fromHeight: args.fromHeight,
+    toHeight: args.toHeight,
+    chunkSize: args.chunkSize!,
+    execute: args.execute!
+  };
+}
+
+function printSummary(result: BackfillResult): void {
+  const open = result.htlcs.filter(htlc => htlc.state === 'OPEN').length;
+  const claimed = result.htlcs.filter(htlc => htlc.state === 'CLAIMED').length;
+  const refunded = result.htlcs.filter(htlc => htlc.state === 'REFUNDED').length;
+
+  console.log(`\n📦 ${result.chain} (${result.fromHeight}..${result.toHeight})`);
+  console.log(`   HTLCs: ${result.htlcs.length} (open ${open}, claimed ${claimed}, refunded ${refunded})`);
+  for (const repair of result.repairs) {
+    console.log(`   ${repair.applied ? '🔧' : '📝'} swap ${repair.swapId}: ${repair.from} -> ${repair.to}`);
+  }
+  for (const settlement of result.settlements) {
+    const outcome = settlement.error ? `❌ ${settlement.error}` : settlement.txHash ? `✅ ${settlement.txHash}` : '📝 pending --execute';
+    console.log(`   ↩️  refund ${settlement.contractId}: ${outcome}`);
+  }
+}
+
+async function main() {
+  const args = parseArgs(process.argv.slice(2));
+  const database = FusionDatabase.getInstance(getDatabaseConfig());
+  const dao = new FusionDAO(database);
+
+  console.log(`\n⏪ Backfilling blocks ${args.fromHeight}..${args.toHeight}${args.execute ? ' (EXECUTE)' : ' (report only)'}`);
+
+  try {
+    for (const chainName of args.chains) {
+      let result: BackfillResult;
+
+      if (evmChains[chainName]) {
+        const backfiller = new EvmBackfiller(chainName, evmChains[chainName], dao, process.env.POOL_WALLET_PRIVATE_KEY);
+        result = await backfiller.run(args);
+      } else if (cosmosChains[chainName]) {
+        const backfiller = new CosmosBackfiller(chainName, cosmosChains[chainName], dao, process.env.COSMOS_MNEMONIC);
+        result = await backfiller.run(args);
+      } else {
+        console.log(`⚠️  Skipping ${chainName} - not configured`);
+        continue;
+      }
+
+      printSummary(result);
+    }
+  } finally {
+    await database.close();
+  }
+}
+
+main().catch(error => {
+  console.error(`❌ Backfill failed: ${error.message}\n`);
+  console.error(USAGE);
+  process.exit(1);
+});
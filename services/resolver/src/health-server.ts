@@ -0,0 +1,180 @@
+import http from 'http';
+import { FusionResolver, ResolverHealth, SubscriptionSource, probe, subscriptionHealth } from '../../../packages/shared/src/resolver';
+import { FusionDatabase } from '../../../packages/shared/src/database';
+
+export interface HealthServerOptions {
+  port: number;
+  probeTimeoutMs?: number;
+  /** Websocket/event subscriptions whose liveness gates readiness */
+  subscriptions?: SubscriptionSource[];
+  /** A subscription with no event or heartbeat for this long is considered dead (default 120s) */
+  maxSubscriptionAgeMs?: number;
+  /** Bearer token required on /admin routes; admin routes are disabled without one */
+  adminToken?: string;
+  /** Invoked by POST /admin/drain */
+  onDrain?: () => Promise<void>;
+}
+
+/**
+ * Kubernetes-style probe endpoints for the resolver service
+ *
+ * - `/healthz` (liveness): every processing loop is ticking and every pool key is loaded.
+ *   Failing this means the process is wedged and should be restarted.
+ * - `/readyz` (readiness): liveness plus RPC connectivity on every chain, database
+ *   reachability and a recent event or heartbeat on every subscription. Failing this
+ *   means the pod should be taken out of rotation.
+ *   A draining resolver always reports not ready.
+ * - `/status`: per-chain queue depth and 24h settlement metrics, for dashboards.
+ * - `POST /admin/drain`: stop taking new work and wait for in-flight transactions,
+ *   without exiting. Requires `Authorization: Bearer <adminToken>`.
+ */
+export class HealthServer {
+  private server?: http.Server;
+  private probeTimeoutMs: number;
+  private maxSubscriptionAgeMs: number;
+
+  constructor(
+    private resolvers: FusionResolver[],
+    private database: FusionDatabase,
+    private options: HealthServerOptions
+  ) {
+    this.probeTimeoutMs = options.probeTimeoutMs ?? 5000;
+    this.maxSubscriptionAgeMs = options.maxSubscriptionAgeMs ?? 120000;
+  }
+
+  async start(): Promise<void> {
+    this.server = http.createServer((req, res) => {
+      this.handle(req, res).catch(error => {
+        this.respond(res, 500, { status: 'error', error: (error as Error).message });
+      });
+    });
+
+    await new Promise<void>(resolve => this.server!.listen(this.options.port, resolve));
+    console.log(`🩺 Health endpoints listening on :${this.options.port} (/healthz, /readyz, /status, POST /admin/drain)`);
+  }
+
+  async stop(): Promise<void> {
+    if (!this.server) return;
+
+    await new Promise<void>(resolve => this.server!.close(() => resolve()));
+    this.server = undefined;
+  }
+
+  private async handle(req: http.IncomingMessage, res: http.ServerResponse): Promise<void> {
+    const path = (req.url || '/').split('?')[0];
+
+    if (path.startsWith('/admin/')) {
+      await this.admin(req, res, path);
+      return;
+    }
+
+    if (req.method !== 'GET') {
+      this.respond(res, 405, { status: 'error', error: 'Method not allowed' });
+      return;
+    }
+
+    switch (path) {
+      case '/healthz':
+        await this.liveness(res);
+        return;
+      case '/readyz':
+        await this.readiness(res);
+        return;
+      case '/status':
+        await this.status(res);
+        return;
+      default:
+        this.respond(res, 404, { status: 'error', error: 'Not found' });
+    }
+  }
+
+  private async admin(req: http.IncomingMessage, res: http.ServerResponse, path: string): Promise<void> {
+    if (!this.options.adminToken || req.headers.authorization !== `Bearer ${this.options.adminToken}`) {
+      this.respond(res, 401, { status: 'error', error: 'Unauthorized' });
+      return;
+    }
+
+    if (req.method !== 'POST') {
+      this.respond(res, 405, { status: 'error', error: 'Method not allowed' });
+      return;
+    }
+
+    switch (path) {
+      case '/admin/drain':
+        if (!this.options.onDrain) {
+          this.respond(res, 404, { status: 'error', error: 'Drain not supported' });
+          return;
+        }
+        // Respond once the drain has started; readiness flips to not_ready immediately
+        this.options.onDrain().catch(error => {
+          console.error('❌ Drain failed:', (error as Error).message);
+        });
+        this.respond(res, 202, { status: 'draining', timestamp: new Date().toISOString() });
+        return;
+      default:
+        this.respond(res, 404, { status: 'error', error: 'Not found' });
+    }
+  }
+
+  private async liveness(res: http.ServerResponse): Promise<void> {
+    const chains = await this.collectResolverHealth();
+    const ok = chains.length > 0 && chains.every(chain => chain.processingLoop.ok && chain.key.ok);
+
+    this.respond(res, ok ? 200 : 503, {
+      status: ok ? 'ok' : 'unhealthy',
+      timestamp: new Date().toISOString(),
+      chains: chains.map(chain => ({
+        chain: chain.chain,
+        processingLoop: chain.processingLoop,
+        key: chain.key
+      }))
+    });
+  }
+
+  private async readiness(res: http.ServerResponse): Promise<void> {
+    const [chains, database] = await Promise.all([
+      this.collectResolverHealth(),
+      probe(async () => {
+        const healthy = await this.database.healthCheck();
+        if (!healthy) {
+          throw new Error('Database query failed');
+        }
+      }, this.probeTimeoutMs)
+    ]);
+
+    const subscriptions = (this.options.subscriptions ?? [])
+      .map(source => subscriptionHealth(source, this.maxSubscriptionAgeMs));
+
+    const chainsReady = chains.length > 0 &&
+      chains.every(chain => !chain.draining && chain.rpc.ok && chain.processingLoop.ok && chain.key.ok);
+    const ok = chainsReady && database.ok && subscriptions.every(subscription => subscription.ok);
+
+    this.respond(res, ok ? 200 : 503, {
+      status: ok ? 'ready' : 'not_ready',
+      timestamp: new Date().toISOString(),
+      database,
+      chains,
+      subscriptions
+    });
+  }
+
+  private async status(res: http.ServerResponse): Promise<void> {
+    const chains = await Promise.all(this.resolvers.map(async resolver => ({
+      ...await resolver.getStatus(),
+      draining: resolver.isDraining
+    })));
+    this.respond(res, 200, { timestamp: new Date().toISOString(), chains });
+  }
+
+  private collectResolverHealth(): Promise<ResolverHealth[]> {
+    return Promise.all(this.resolvers.map(resolver => resolver.getHealth(this.probeTimeoutMs)));
+  }
+
+  private respond(res: http.ServerResponse, statusCode: number, body: unknown): void {
+    res.writeHead(statusCode, {
+      'Content-Type': 'application/json',
+      'Cache-Control': 'no-store'
+    });
+    res.end(JSON.stringify(body));
+  }
+}
@@ -1,30 +1,138 @@
-import { FusionResolver, ResolverConfig } from '../../../packages/shared/src/resolver';
-import { FusionDatabase, getDatabaseConfig } from '../../../packages/shared/src/database';
-import { evmChains } from '../../../packages/shared/src/chains';
+import {
+  FusionResolver,
+  ResolverConfig,
+  AlertManager,
+  CosmosEventPipeline,
+  MempoolWatcher,
+  EarlyClaimRelay,
+  AuctionParticipant,
+  AuctionOrderFeed,
+  createChainAdapter,
+  TimelockWatchdog,
+  Rebalancer,
+  IbcTransferRoute,
+  CctpRoute,
+  createAlertManagerFromEnv
+} from '../../../packages/shared/src/resolver';
+import type {
+  AdapterCredentials,
+  ChainAdapter,
+  CosmosAdapterOptions,
+  Corridor,
+  EvmAdapterOptions,
+  EvmNonceManager,
+  RebalanceRoute,
+  IbcChannel,
+  CctpDomain
+} from '../../../packages/shared/src/resolver';
+import { CosmosHTLCClient, CosmosBatchSubmitter, FeeGuard, feePolicyFromEnv } from '../../../packages/shared/src/clients';
+import type { CosmosSigningAccount } from '../../../packages/shared/src/clients';
+import { RemoteSignerClient, remoteSignerCredentials } from '../../../packages/shared/src/clients/remote-signer';
+import type { BackfillResult } from '../../../packages/shared/src/resolver/backfill';
+import { FusionDAO, FusionDatabase, getDatabaseConfig } from '../../../packages/shared/src/database';
+import { evmChains, cosmosChains, htlcAddressBook } from '../../../packages/shared/src/chains';
+import { HealthServer } from './health-server';
+import { createLogger } from '../../../packages/shared/src/utils/logger';
+import { ethers } from 'ethers';
 import dotenv from 'dotenv';
+import fs from 'fs';
 import path from 'path';
 
 dotenv.config({ path: path.join(__dirname, '../../../.env.local') });
 
+const logger = createLogger('ResolverService');
+
 class ResolverService {
-  private resolver: FusionResolver | null = null;
-  private networks: string[] = ['sepolia', 'monadTestnet', 'etherlinkTestnet'];
+  private resolvers: FusionResolver[] = [];
+  private cosmosPipelines: CosmosEventPipeline[] = [];
+  private mempoolWatchers: MempoolWatcher[] = [];
+  private cosmosSubmitters: CosmosBatchSubmitter[] = [];
+  private auctionParticipant: AuctionParticipant | null = null;
+  private auctionFeed: AuctionOrderFeed | null = null;
+  private chainAdapters: ChainAdapter[] = [];
+  // Every consumer shares one adapter per chain and signing account, so transactions
+  // from the same key are sequenced by a single nonce/sequence manager
+  private adapterCache = new Map<string, Promise<ChainAdapter>>();
+  private cosmosAccounts = new Map<string, Promise<CosmosSigningAccount>>();
+  private poolNonceManagers = new Map<string, EvmNonceManager>();
+  private remoteCredentials?: Promise<((networkName: string) => Promise<AdapterCredentials>) | undefined>;
+  private timelockWatchdog: TimelockWatchdog | null = null;
+  private rebalancer: Rebalancer | null = null;
+  private healthServer: HealthServer | null = null;
+  private alerts: AlertManager = createAlertManagerFromEnv();
+  // Comma-separated evmChains keys, e.g. "sepolia,arbitrumSepolia,baseSepolia". The L2s are
+  // skipped until their HTLC address is configured (NEXT_PUBLIC_<CHAIN>_HTLC or HTLC_ADDRESS_<chainId>)
+  private networks: string[] = (process.env.RESOLVER_EVM_CHAINS || 'sepolia,polygonAmoy,monadTestnet,etherlinkTestnet')
+    .split(',')
+    .map(name => name.trim())
+    .filter(Boolean);
+  // Comma-separated cosmosChains keys, e.g. "osmosisTestnet,neutronTestnet,junoTestnet"
+  private cosmosNetworks: string[] = (process.env.RESOLVER_COSMOS_CHAINS || '')
+    .split(',')
+    .map(name => name.trim())
+    .filter(Boolean);
+  private dryRun = process.argv.includes('--dry-run') || process.env.RESOLVER_DRY_RUN === 'true';
+  private drainTimeoutMs = parseInt(process.env.RESOLVER_DRAIN_TIMEOUT_MS || '300000');
+  private draining?: Promise<void>;
+  private shuttingDown = false;
 
   async start() {
     console.log('\n🚀 FUSION RESOLVER SERVICE STARTING...\n');
     console.log('═'.repeat(60));
+    if (this.dryRun) {
+      console.log('🧪 DRY-RUN MODE: transactions are simulated only, no state is changed');
+    }
     
     try {
       // Initialize database
       const dbConfig = getDatabaseConfig();
       const database = FusionDatabase.getInstance(dbConfig);
+      await database.migrate();
       console.log('✅ Database connected');
 
+      if (!this.alerts.enabled) {
+        console.log('⚠️  No alert sinks configured (set ALERT_WEBHOOK_URL, ALERT_SLACK_WEBHOOK_URL or ALERT_PAGERDUTY_ROUTING_KEY)');
+      }
+
       // Start resolver for each network
       for (const networkName of this.networks) {
         await this.startResolverForNetwork(networkName, database);
       }
 
+      // Each Cosmos chain runs its own event pipeline
+      for (const networkName of this.cosmosNetworks) {
+        await this.startCosmosPipeline(networkName, database);
+      }
+
+      // Escalating timelock alerts and protective refunds across all chains
+      await this.startTimelockWatchdog(database);
+
+      // Optional: learn preimages from pending EVM claims and fast-submit Cosmos claims
+      if (process.env.RESOLVER_MEMPOOL_WS_URLS) {
+        await this.startMempoolWatchers(database);
+      }
+
+      // Optional: bid on Dutch-auction orders from an external order feed
+      if (process.env.RESOLVER_AUCTION_FEED_URL || process.env.RESOLVER_AUCTION_WS_URL) {
+        await this.startAuctionParticipant(database);
+      }
+
+      // Optional: keep inventory spread across chains according to corridor targets
+      if (process.env.RESOLVER_REBALANCE_CONFIG) {
+        await this.startRebalancer(process.env.RESOLVER_REBALANCE_CONFIG);
+      }
+
+      // Expose liveness/readiness probes for orchestrators
+      this.healthServer = new HealthServer(this.resolvers, database, {
+        port: parseInt(process.env.RESOLVER_HEALTH_PORT || '8080'),
+        probeTimeoutMs: parseInt(process.env.RESOLVER_HEALTH_TIMEOUT_MS || '5000'),
+        subscriptions: [...this.mempoolWatchers, ...(this.auctionFeed ? [this.auctionFeed] : [])],
+        maxSubscriptionAgeMs: parseInt(process.env.RESOLVER_SUBSCRIPTION_MAX_AGE_MS || '120000'),
+        adminToken: process.env.RESOLVER_ADMIN_TOKEN,
+        onDrain: () => this.drain()
+      });
+      await this.healthServer.start();
+
       console.log('\n🎉 ALL RESOLVER SERVICES STARTED!');
       console.log('═'.repeat(60));
       console.log('📊 Monitoring:');
@@ -51,22 +159,38 @@ class ResolverService {
       return;
     }
 
+    const htlcAddress = htlcAddressBook[chainConfig.chainId];
+    if (!htlcAddress) {
+      console.log(`⚠️  Skipping ${networkName} - no HTLC deployed for chain ${chainConfig.chainId}`);
+      return;
+    }
+
     console.log(`\n🔗 Starting resolver for ${chainConfig.name}...`);
 
     const config: ResolverConfig = {
       processingInterval: 10000, // 10 seconds
       maxBatchSize: 10,
       maxRetries: 3,
-      gasLimit: 300000, // Reduced from 500k to 300k to lower gas costs
       maxGasPrice: networkName === 'monadTestnet' ? '100000000000' : '20000000000', // 100 gwei for Monad, 20 gwei for others
-      htlcContractAddress: chainConfig.htlcAddress,
+      htlcContractAddress: htlcAddress,
       poolWalletPrivateKey: process.env.POOL_WALLET_PRIVATE_KEY || 'e736d47829f72409da6cd0eb8e7127cdd8195c455c4e5c39b532de58a59f2647',
       rpcUrl: chainConfig.rpcUrl,
       chainId: chainConfig.chainId,
-      chainName: networkName
+      chainName: networkName,
+      dryRun: this.dryRun,
+      maxInFlightTxs: parseInt(process.env.RESOLVER_MAX_IN_FLIGHT_TXS || '4'),
+      maxConcurrentSwaps: parseInt(process.env.RESOLVER_MAX_CONCURRENT_SWAPS || '1'),
+      fees: feePolicyFromEnv(networkName, process.env),
+      ...(process.env.RESOLVER_MIN_MARGIN_BPS ? {
+        profitability: {
+          minMarginBps: parseFloat(process.env.RESOLVER_MIN_MARGIN_BPS),
+          protocolFeeBps: parseFloat(process.env.RESOLVER_PROTOCOL_FEE_BPS || '0'),
+          inventoryCostBpsPerYear: parseFloat(process.env.RESOLVER_INVENTORY_COST_BPS || '500')
+        }
+      } : {})
     };
 
-    const resolver = new FusionResolver(config, database);
+    const resolver = new FusionResolver(config, database, this.alerts);
 
     // Set up event listeners
     resolver.on('started', () => {
@@ -74,32 +198,363 @@ class ResolverService {
     });
 
     resolver.on('swapProcessed', (swapId: string) => {
-      console.log(`✅ Swap processed: ${swapId} on ${chainConfig.name}`);
+      logger.info('✅ Swap processed', { chain: networkName, correlationId: swapId, swapId });
     });
 
     resolver.on('error', (error: Error) => {
-      console.error(`❌ Resolver error on ${chainConfig.name}:`, error.message);
+      logger.error('❌ Resolver error', { chain: networkName, error: error.message });
     });
 
     resolver.on('poolLiquidityLow', (tokenAddress: string) => {
       console.warn(`⚠️  Low liquidity warning for ${tokenAddress} on ${chainConfig.name}`);
     });
 
+    resolver.on('reorgDetected', (reorg: { blockNumber: number }) => {
+      console.warn(`⚠️  Reorg detected at block ${reorg.blockNumber} on ${chainConfig.name}`);
+    });
+
     // Start the resolver
     await resolver.start();
     
-    // Store reference for shutdown
-    if (!this.resolver) {
-      this.resolver = resolver; // Store first resolver for graceful shutdown
+    // Store reference for health probes and shutdown
+    this.resolvers.push(resolver);
+    this.poolNonceManagers.set(networkName, resolver.poolNonceManager);
+  }
+
+  private async startCosmosPipeline(networkName: string, database: FusionDatabase) {
+    const chainConfig = cosmosChains[networkName];
+    if (!chainConfig) {
+      console.log(`⚠️  Skipping ${networkName} - not a configured Cosmos chain`);
+      return;
+    }
+
+    console.log(`\n🌌 Starting Cosmos pipeline for ${chainConfig.name} (${chainConfig.chainId})...`);
+
+    const pipeline = new CosmosEventPipeline(networkName, chainConfig, new FusionDAO(database), {
+      pollIntervalMs: parseInt(process.env.RESOLVER_COSMOS_POLL_INTERVAL_MS || '6000'),
+      ...(process.env.COSMOS_MNEMONIC ? { signingAccount: await this.cosmosAccount(networkName) } : {}),
+      execute: !this.dryRun
+    });
+
+    pipeline.on('tick', (result: BackfillResult) => {
+      this.alerts.recordSuccess(networkName, 'pipeline');
+      if (result.repairs.length > 0 || result.settlements.length > 0) {
+        logger.info('🌌 Cosmos pipeline reconciled swaps', {
+          chain: networkName,
+          repairs: result.repairs.length,
+          settlements: result.settlements.length
+        });
+      }
+    });
+
+    pipeline.on('error', (error: Error) => {
+      logger.error('❌ Cosmos pipeline error', { chain: networkName, error: error.message });
+      this.alerts.recordFailure(networkName, 'pipeline', error).catch(() => undefined);
+    });
+
+    await pipeline.start();
+    this.cosmosPipelines.push(pipeline);
+    console.log(`✅ Cosmos pipeline started for ${chainConfig.name}`);
+  }
+
+  /**
+   * RESOLVER_MEMPOOL_WS_URLS maps EVM chains to websocket RPCs, e.g. "sepolia=wss://...,baseSepolia=wss://..."
+   */
+  private async startMempoolWatchers(database: FusionDatabase) {
+    const mnemonic = process.env.COSMOS_MNEMONIC;
+    if (!mnemonic || this.dryRun) {
+      console.log('⚠️  Mempool watching needs COSMOS_MNEMONIC and is disabled in dry-run mode');
+      return;
+    }
+
+    const cosmos = new Map<string, { client: CosmosHTLCClient; submitter: CosmosBatchSubmitter }>();
+    for (const networkName of this.cosmosNetworks) {
+      if (!cosmosChains[networkName]) continue;
+
+      const { client, address } = await this.cosmosAccount(networkName);
+      const submitter = new CosmosBatchSubmitter(client, address);
+      submitter.start();
+      this.cosmosSubmitters.push(submitter);
+      cosmos.set(networkName, { client, submitter });
+    }
+
+    const relay = new EarlyClaimRelay(new FusionDAO(database), cosmos);
+
+    for (const entry of process.env.RESOLVER_MEMPOOL_WS_URLS!.split(',')) {
+      const [networkName, wsUrl] = entry.split('=').map(part => part.trim());
+      const chainConfig = evmChains[networkName];
+      const htlcAddress = chainConfig && htlcAddressBook[chainConfig.chainId];
+      if (!htlcAddress || !wsUrl) {
+        console.log(`⚠️  Skipping mempool watcher for ${networkName} - not configured`);
+        continue;
+      }
+
+      const watcher = new MempoolWatcher({ wsUrl, htlcAddress, chainName: networkName });
+      relay.attach(watcher);
+      await watcher.start();
+      this.mempoolWatchers.push(watcher);
+      console.log(`👀 Mempool watcher started for ${chainConfig.name}`);
     }
   }
 
+  private async startTimelockWatchdog(database: FusionDatabase) {
+    const adapters = await this.connectAdapters({
+      privateKey: process.env.POOL_WALLET_PRIVATE_KEY,
+      mnemonic: process.env.COSMOS_MNEMONIC
+    });
+
+    const thresholds = process.env.RESOLVER_TIMELOCK_THRESHOLDS
+      ?.split(',')
+      .map(value => parseFloat(value))
+      .filter(value => value > 0 && value < 1);
+
+    this.timelockWatchdog = new TimelockWatchdog(new FusionDAO(database), adapters, this.alerts, {
+      ...(thresholds?.length ? { thresholds } : {}),
+      pollIntervalMs: parseInt(process.env.RESOLVER_TIMELOCK_POLL_INTERVAL_MS || '30000'),
+      execute: !this.dryRun
+    });
+
+    this.timelockWatchdog.on('refunded', ({ swapId, chain }) => {
+      logger.warn('🛡️ Protective refund submitted', { chain, correlationId: swapId, swapId });
+    });
+
+    this.timelockWatchdog.start();
+    console.log('✅ Timelock watchdog started');
+  }
+
+  /**
+   * A ChainAdapter for every configured EVM and Cosmos chain, skipping chains that cannot
+   * be reached. Adapters are built once per chain and signing account and shared by every
+   * caller; the pool wallet's EVM adapters reuse the resolver's nonce manager.
+   */
+  private async connectAdapters(credentials: { privateKey?: string; mnemonic?: string }): Promise<ChainAdapter[]> {
+    const adapters: ChainAdapter[] = [];
+    const remote = await this.remoteSigner();
+    for (const networkName of [...this.networks, ...this.cosmosNetworks]) {
+      const isEvm = !!evmChains[networkName];
+      const signer = isEvm
+        ? credentials.privateKey && new ethers.Wallet(credentials.privateKey).address
+        : credentials.mnemonic && 'mnemonic';
+      const account = remote ? 'remote' : signer || 'read-only';
+      const key = `${networkName}:${account}`;
+
+      let adapter = this.adapterCache.get(key);
+      if (!adapter) {
+        adapter = this.createAdapter(networkName, isEvm, credentials, remote);
+        this.adapterCache.set(key, adapter);
+        adapter.then(created => this.chainAdapters.push(created), () => this.adapterCache.delete(key));
+      }
+
+      try {
+        adapters.push(await adapter);
+      } catch (error) {
+        console.log(`⚠️  No chain adapter for ${networkName}: ${(error as Error).message}`);
+      }
+    }
+    return adapters;
+  }
+
+  private async createAdapter(
+    networkName: string,
+    isEvm: boolean,
+    credentials: { privateKey?: string; mnemonic?: string },
+    remote?: (networkName: string) => Promise<AdapterCredentials>
+  ): Promise<ChainAdapter> {
+    const options: EvmAdapterOptions & CosmosAdapterOptions = { fees: feePolicyFromEnv(networkName, process.env) };
+    if (remote) {
+      return createChainAdapter(networkName, await remote(networkName), options);
+    }
+
+    if (isEvm && credentials.privateKey && credentials.privateKey === process.env.POOL_WALLET_PRIVATE_KEY) {
+      options.nonceManager = this.poolNonceManagers.get(networkName);
+    }
+    if (!isEvm && credentials.mnemonic && credentials.mnemonic === process.env.COSMOS_MNEMONIC) {
+      options.signingAccount = await this.cosmosAccount(networkName);
+    }
+    return createChainAdapter(networkName, credentials, options);
+  }
+
+  /**
+   * The COSMOS_MNEMONIC account on a chain, connected once and shared by the batch
+   * submitter, event pipeline, chain adapter and IBC route so they sign through one
+   * sequence manager
+   */
+  private cosmosAccount(networkName: string): Promise<CosmosSigningAccount> {
+    let account = this.cosmosAccounts.get(networkName);
+    if (!account) {
+      const chainConfig = cosmosChains[networkName];
+      const mnemonic = process.env.COSMOS_MNEMONIC;
+      if (!chainConfig || !mnemonic) {
+        return Promise.reject(new Error(`No Cosmos signing account for ${networkName}`));
+      }
+
+      account = CosmosHTLCClient.fromMnemonic(
+        chainConfig,
+        mnemonic,
+        new FeeGuard(networkName, chainConfig, feePolicyFromEnv(networkName, process.env))
+      );
+      this.cosmosAccounts.set(networkName, account);
+      account.catch(() => this.cosmosAccounts.delete(networkName));
+    }
+    return account;
+  }
+
+  /**
+   * With REMOTE_SIGNER_URL set, adapters sign with REMOTE_SIGNER_EVM_KEY /
+   * REMOTE_SIGNER_COSMOS_KEY on that signer instead of with local keys
+   */
+  private remoteSigner(): Promise<((networkName: string) => Promise<AdapterCredentials>) | undefined> {
+    if (!this.remoteCredentials) {
+      this.remoteCredentials = this.connectRemoteSigner();
+    }
+    return this.remoteCredentials;
+  }
+
+  private async connectRemoteSigner(): Promise<((networkName: string) => Promise<AdapterCredentials>) | undefined> {
+    const url = process.env.REMOTE_SIGNER_URL;
+    if (!url) return undefined;
+
+    const client = await RemoteSignerClient.connect({
+      url,
+      caFile: process.env.REMOTE_SIGNER_TLS_CA,
+      certFile: process.env.REMOTE_SIGNER_TLS_CERT,
+      keyFile: process.env.REMOTE_SIGNER_TLS_KEY,
+      token: process.env.REMOTE_SIGNER_TOKEN,
+      insecure: process.env.REMOTE_SIGNER_INSECURE === 'true'
+    });
+    return async networkName => {
+      const config = evmChains[networkName] ?? cosmosChains[networkName];
+      const keyId = config.type === 'evm' ? process.env.REMOTE_SIGNER_EVM_KEY : process.env.REMOTE_SIGNER_COSMOS_KEY;
+      if (!keyId) throw new Error(`REMOTE_SIGNER_${config.type.toUpperCase()}_KEY is not set`);
+      return remoteSignerCredentials(config, client, keyId);
+    };
+  }
+
+  private async startAuctionParticipant(database: FusionDatabase) {
+    const privateKey = process.env.RESOLVER_AUCTION_PRIVATE_KEY;
+    if (!privateKey) {
+      console.log('⚠️  Auction participation needs RESOLVER_AUCTION_PRIVATE_KEY');
+      return;
+    }
+
+    const adapters = await this.connectAdapters({ privateKey, mnemonic: process.env.COSMOS_MNEMONIC });
+
+    const participant = new AuctionParticipant(new FusionDAO(database), adapters, {
+      dryRun: this.dryRun,
+      maxConcurrentOrders: parseInt(process.env.RESOLVER_AUCTION_MAX_CONCURRENT || '4'),
+      ...(process.env.RESOLVER_MIN_MARGIN_BPS ? {
+        profitability: { minMarginBps: parseFloat(process.env.RESOLVER_MIN_MARGIN_BPS) }
+      } : {})
+    });
+
+    this.auctionFeed = new AuctionOrderFeed({
+      httpUrl: process.env.RESOLVER_AUCTION_FEED_URL,
+      wsUrl: process.env.RESOLVER_AUCTION_WS_URL,
+      apiKey: process.env.RESOLVER_AUCTION_API_KEY
+    });
+    participant.attach(this.auctionFeed);
+
+    participant.on('filled', ({ swapId, order }) => {
+      logger.info('🔨 Auction order filled', { correlationId: swapId, swapId, orderHash: order.orderHash });
+    });
+    participant.on('settled', ({ swapId, order }) => {
+      this.alerts.recordSuccess('auction', 'auction');
+      logger.info('🎉 Auction order settled', { correlationId: swapId, swapId, orderHash: order.orderHash });
+    });
+    participant.on('error', (error: Error) => {
+      logger.error('❌ Auction participant error', { error: error.message });
+      this.alerts.recordFailure('auction', 'auction', error).catch(() => undefined);
+    });
+
+    await participant.start();
+    this.auctionParticipant = participant;
+    console.log('✅ Auction participant started');
+  }
+
+  /**
+   * Start the inventory rebalancer from a JSON file of the form
+   * { corridors: Corridor[], ibcChannels?: IbcChannel[], cctp?: { domains, attestationUrl? }, pollIntervalMs? }
+   */
+  private async startRebalancer(configPath: string) {
+    const config: {
+      corridors: Corridor[];
+      ibcChannels?: IbcChannel[];
+      cctp?: { domains: Record<string, CctpDomain>; attestationUrl?: string };
+      pollIntervalMs?: number;
+    } = JSON.parse(fs.readFileSync(path.resolve(configPath), 'utf8'));
+
+    const privateKey = process.env.POOL_WALLET_PRIVATE_KEY;
+    const mnemonic = process.env.COSMOS_MNEMONIC;
+    const adapters = await this.connectAdapters({ privateKey, mnemonic });
+
+    const routes: RebalanceRoute[] = [];
+    if (config.ibcChannels?.length && mnemonic) {
+      routes.push(new IbcTransferRoute(adapters, chain => this.cosmosAccount(chain), { channels: config.ibcChannels }));
+    }
+    if (config.cctp && privateKey) {
+      routes.push(new CctpRoute(privateKey, config.cctp));
+    }
+    if (routes.length === 0) {
+      console.log('⚠️  Rebalancer has no usable routes (configure ibcChannels or cctp and their keys)');
+    }
+
+    this.rebalancer = new Rebalancer(adapters, routes, {
+      corridors: config.corridors,
+      pollIntervalMs: config.pollIntervalMs,
+      dryRun: this.dryRun
+    }, this.alerts);
+
+    this.rebalancer.on('transfer', ({ corridor, route, txHash }) => {
+      logger.info('🔁 Inventory rebalance submitted', { corridor, route, txHash });
+    });
+    this.rebalancer.on('error', (error: Error) => {
+      logger.error('❌ Rebalancer error', { error: error.message });
+    });
+
+    this.rebalancer.start();
+    console.log(`✅ Rebalancer started for ${config.corridors.length} corridor(s)`);
+  }
+
+  /**
+   * Stop accepting new work and settle everything in flight, but keep the process
+   * (and its health endpoints) up. Safe to call more than once.
+   */
+  drain(): Promise<void> {
+    if (!this.draining) {
+      console.log('\n🚰 Draining resolver service...');
+      this.draining = (async () => {
+        // Stop the sources of new work first, then let in-flight work settle
+        await Promise.all(this.mempoolWatchers.map(watcher => watcher.stop()));
+        await this.auctionParticipant?.stop();
+        this.timelockWatchdog?.stop();
+        this.rebalancer?.stop();
+        await Promise.all(this.chainAdapters.map(adapter => adapter.close()));
+        await Promise.all(this.cosmosPipelines.map(pipeline => pipeline.stop()));
+        await Promise.all(this.resolvers.map(resolver => resolver.drain(this.drainTimeoutMs)));
+        // Flush queued Cosmos claims/refunds
+        await Promise.all(this.cosmosSubmitters.map(submitter => submitter.stop()));
+        console.log('✅ Drain complete');
+      })();
+    }
+    return this.draining;
+  }
+
   private async gracefulShutdown() {
+    if (this.shuttingDown) return;
+    this.shuttingDown = true;
     console.log('\n📤 Gracefully shutting down resolver service...');
-    
-    if (this.resolver) {
-      await this.resolver.stop();
+
+    try {
+      await this.drain();
+    } catch (error) {
+      console.error('❌ Drain failed during shutdown:', (error as Error).message);
     }
+
+    if (this.healthServer) {
+      await this.healthServer.stop();
+    }
+
+    await Promise.all(this.resolvers.map(resolver => resolver.stop()));
     
     console.log('✅ Resolver service stopped');
     process.exit(0);